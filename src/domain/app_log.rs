@@ -0,0 +1,134 @@
+use std::{
+    collections::VecDeque,
+    sync::{Mutex, OnceLock},
+};
+
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use web_time::Instant;
+
+// oldest records are dropped, the console should not grow without limit in long sessions
+const MAX_RECORDS: usize = 5000;
+
+pub struct LogRecord {
+    // seconds since application start
+    pub time: f32,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogRecord {
+    pub fn to_line(&self) -> String {
+        format!("{:10.3} {:5} [{}] {}", self.time, self.level, self.target, self.message)
+    }
+}
+
+/// Collects the log records for the log console and writes them also to the standard output
+pub struct AppLogger {
+    start: Instant,
+    records: Mutex<VecDeque<LogRecord>>,
+}
+
+static LOGGER: OnceLock<AppLogger> = OnceLock::new();
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // libraries (http client, ui) are too verbose on debug level
+        if metadata.target().starts_with("rdf_glance") || !metadata.target().contains("::") {
+            metadata.level() <= Level::Debug
+        } else {
+            metadata.level() <= Level::Warn
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let log_record = LogRecord {
+            time: self.start.elapsed().as_secs_f32(),
+            level: record.level(),
+            target: short_target(record.target()).to_string(),
+            message: record.args().to_string(),
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        if log_record.level <= Level::Warn {
+            eprintln!("{}", log_record.to_line());
+        } else {
+            println!("{}", log_record.to_line());
+        }
+        if let Ok(mut records) = self.records.lock() {
+            if records.len() >= MAX_RECORDS {
+                records.pop_front();
+            }
+            records.push_back(log_record);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+// module path targets are shortened to the last segment, explicit targets (e.g. "import") are kept
+fn short_target(target: &str) -> &str {
+    target.rsplit("::").next().unwrap_or(target)
+}
+
+/// Installs the logger, can be called more times (e.g. by tests)
+pub fn init_logger() {
+    let logger = LOGGER.get_or_init(|| AppLogger {
+        start: Instant::now(),
+        records: Mutex::new(VecDeque::new()),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(LevelFilter::Debug);
+    }
+}
+
+/// Calls the function with the records having at least the given level
+pub fn with_records<R>(min_level: Level, f: impl FnOnce(&[&LogRecord]) -> R) -> R {
+    let Some(logger) = LOGGER.get() else {
+        return f(&[]);
+    };
+    let Ok(records) = logger.records.lock() else {
+        return f(&[]);
+    };
+    let filtered: Vec<&LogRecord> = records.iter().filter(|record| record.level <= min_level).collect();
+    f(&filtered)
+}
+
+pub fn clear_records() {
+    if let Some(logger) = LOGGER.get()
+        && let Ok(mut records) = logger.records.lock()
+    {
+        records.clear();
+    }
+}
+
+/// Text for clipboard or export file
+pub fn records_to_text(min_level: Level) -> String {
+    with_records(min_level, |records| {
+        let mut text = String::new();
+        for record in records {
+            text.push_str(&record.to_line());
+            text.push('\n');
+        }
+        text
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_log() {
+        init_logger();
+        log::info!(target: "import", "test info message");
+        log::debug!("test debug message");
+        assert_eq!("layout", short_target("rdf_glance::uistate::layout"));
+        let text = records_to_text(Level::Info);
+        assert!(text.contains("INFO  [import] test info message"));
+        assert!(!text.contains("test debug message"));
+        assert!(records_to_text(Level::Debug).contains("[tests] test debug message"));
+    }
+}
@@ -1,14 +1,31 @@
 use serde::{Deserialize, Serialize};
 use crate::domain::config::Config;
+use crate::domain::graph_snapshot::GraphSnapshots;
+use crate::domain::recent_items::{RecentItems, RecentKind};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Default)]
 pub struct AppPersistentData {
+    // The plain lists are only read to migrate the settings of older versions to recent items
+    #[serde(default, skip_serializing)]
     pub last_files: Vec<Box<str>>,
+    #[serde(default, skip_serializing)]
     pub last_endpoints: Vec<Box<str>>,
-    #[serde(default = "default_last_projects")]
+    #[serde(default = "default_last_projects", skip_serializing)]
     pub last_projects: Vec<Box<str>>,
+    #[serde(default)]
+    pub recent_items: RecentItems,
     #[serde(default = "default_config_data")]
     pub config_data: Config,
+    #[serde(default)]
+    pub graph_snapshots: GraphSnapshots,
+}
+
+impl AppPersistentData {
+    pub fn migrate_recent_items(&mut self) {
+        self.recent_items.migrate(RecentKind::File, &std::mem::take(&mut self.last_files));
+        self.recent_items.migrate(RecentKind::Endpoint, &std::mem::take(&mut self.last_endpoints));
+        self.recent_items.migrate(RecentKind::Project, &std::mem::take(&mut self.last_projects));
+    }
 }
 
 fn default_config_data() -> Config {
@@ -17,4 +34,4 @@ fn default_config_data() -> Config {
 
 fn default_last_projects() -> Vec<Box<str>> {
     Vec::new()
-}
\ No newline at end of file
+}
@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+
+use crate::{
+    IriIndex,
+    domain::{
+        Literal, NObject, NodeData,
+        rdf_list::{ListPredicates, MAX_LIST_LEN},
+    },
+};
+
+// deeper trees are cut, they are mostly cycles of blank nodes
+const MAX_TREE_DEPTH: usize = 16;
+
+/// Member of a rdf list
+#[derive(Clone, Copy, PartialEq)]
+pub enum ListItem<'a> {
+    Literal(&'a Literal),
+    // named node or blank node, the tree of a blank node is one of the children
+    Node(IriIndex),
+}
+
+/**
+ * Blank node referenced by a named node together with its nested blank nodes.
+ * Some data (e.g. bibliographic BIBFRAME) describe each resource by deep trees of blank nodes.
+ * If flattening is enabled the trees are shown as property groups of the named node
+ * and the named node is one composite node in the visual graph.
+ */
+pub struct BlankNodeTree<'a> {
+    pub predicate: IriIndex,
+    pub node_index: IriIndex,
+    pub node: &'a NObject,
+    pub properties: Vec<(IriIndex, &'a Literal)>,
+    // references to named nodes
+    pub references: Vec<(IriIndex, IriIndex)>,
+    pub children: Vec<BlankNodeTree<'a>>,
+    // members of a rdf list (rdf:first/rdf:rest chain), the list nodes itself are collapsed into this tree
+    pub list_items: Vec<ListItem<'a>>,
+}
+
+impl BlankNodeTree<'_> {
+    pub fn is_list(&self) -> bool {
+        !self.list_items.is_empty()
+    }
+
+    fn collect_references(&self, references: &mut Vec<(IriIndex, IriIndex)>) {
+        for reference in self.references.iter() {
+            if !references.contains(reference) {
+                references.push(*reference);
+            }
+        }
+        for child in self.children.iter() {
+            child.collect_references(references);
+        }
+    }
+}
+
+pub fn blank_node_trees<'a>(node_data: &'a NodeData, node: &'a NObject) -> Vec<BlankNodeTree<'a>> {
+    let mut visited: HashSet<IriIndex> = HashSet::new();
+    let list_predicates = ListPredicates::new(node_data);
+    subtrees(node_data, &list_predicates, node, &mut visited, 0)
+}
+
+fn subtrees<'a>(
+    node_data: &'a NodeData,
+    list_predicates: &ListPredicates,
+    node: &'a NObject,
+    visited: &mut HashSet<IriIndex>,
+    depth: usize,
+) -> Vec<BlankNodeTree<'a>> {
+    let mut trees = Vec::new();
+    if depth >= MAX_TREE_DEPTH {
+        return trees;
+    }
+    for (predicate, target_index) in node.references.iter() {
+        let Some((_, target)) = node_data.get_node_by_index(*target_index) else {
+            continue;
+        };
+        if !target.is_blank_node || !visited.insert(*target_index) {
+            continue;
+        }
+        trees.push(blank_tree(node_data, list_predicates, *predicate, *target_index, target, visited, depth));
+    }
+    trees
+}
+
+fn blank_tree<'a>(
+    node_data: &'a NodeData,
+    list_predicates: &ListPredicates,
+    predicate: IriIndex,
+    node_index: IriIndex,
+    node: &'a NObject,
+    visited: &mut HashSet<IriIndex>,
+    depth: usize,
+) -> BlankNodeTree<'a> {
+    if list_predicates.is_list_node(node) {
+        list_tree(node_data, list_predicates, predicate, node_index, node, visited, depth)
+    } else {
+        node_tree(node_data, list_predicates, predicate, node_index, node, visited, depth)
+    }
+}
+
+fn node_tree<'a>(
+    node_data: &'a NodeData,
+    list_predicates: &ListPredicates,
+    predicate: IriIndex,
+    node_index: IriIndex,
+    node: &'a NObject,
+    visited: &mut HashSet<IriIndex>,
+    depth: usize,
+) -> BlankNodeTree<'a> {
+    BlankNodeTree {
+        predicate,
+        node_index,
+        node,
+        properties: node.properties.iter().map(|(predicate, literal)| (*predicate, literal)).collect(),
+        references: node
+            .references
+            .iter()
+            .filter(|(_, reference_index)| !is_blank_node(node_data, *reference_index))
+            .copied()
+            .collect(),
+        children: subtrees(node_data, list_predicates, node, visited, depth + 1),
+        list_items: Vec::new(),
+    }
+}
+
+/// Collapses the chain of list nodes starting at the head into one tree with the list members
+fn list_tree<'a>(
+    node_data: &'a NodeData,
+    list_predicates: &ListPredicates,
+    predicate: IriIndex,
+    head_index: IriIndex,
+    head: &'a NObject,
+    visited: &mut HashSet<IriIndex>,
+    depth: usize,
+) -> BlankNodeTree<'a> {
+    let mut list_items = Vec::new();
+    let mut references = Vec::new();
+    let mut children = Vec::new();
+    let mut list_node = head;
+    while list_items.len() < MAX_LIST_LEN {
+        for (_, literal) in list_node
+            .properties
+            .iter()
+            .filter(|(predicate, _)| Some(*predicate) == list_predicates.first)
+        {
+            list_items.push(ListItem::Literal(literal));
+        }
+        let mut next: Option<(IriIndex, &'a NObject)> = None;
+        for (list_predicate, target_index) in list_node.references.iter() {
+            if Some(*list_predicate) == list_predicates.first {
+                list_items.push(ListItem::Node(*target_index));
+                match node_data.get_node_by_index(*target_index) {
+                    Some((_, target)) if target.is_blank_node => {
+                        if depth + 1 < MAX_TREE_DEPTH && visited.insert(*target_index) {
+                            children.push(blank_tree(
+                                node_data,
+                                list_predicates,
+                                *list_predicate,
+                                *target_index,
+                                target,
+                                visited,
+                                depth + 1,
+                            ));
+                        }
+                    }
+                    _ => references.push((predicate, *target_index)),
+                }
+            } else if Some(*list_predicate) == list_predicates.rest
+                && let Some((_, rest)) = node_data.get_node_by_index(*target_index)
+                && list_predicates.is_list_node(rest)
+                && visited.insert(*target_index)
+            {
+                next = Some((*target_index, rest));
+            }
+        }
+        match next {
+            Some((_, rest)) => list_node = rest,
+            None => break,
+        }
+    }
+    BlankNodeTree {
+        predicate,
+        node_index: head_index,
+        node: head,
+        properties: Vec::new(),
+        // named members are referenced with the predicate of the list
+        references,
+        children,
+        list_items,
+    }
+}
+
+pub fn is_blank_node(node_data: &NodeData, node_index: IriIndex) -> bool {
+    node_data
+        .get_node_by_index(node_index)
+        .is_some_and(|(_, node)| node.is_blank_node)
+}
+
+pub fn has_blank_node_tree(node_data: &NodeData, node: &NObject) -> bool {
+    !node.is_blank_node
+        && node
+            .references
+            .iter()
+            .any(|(_, target_index)| is_blank_node(node_data, *target_index))
+}
+
+/// Named nodes referenced from the blank node trees of the node (predicate of the last blank node, named node)
+pub fn composite_references(node_data: &NodeData, node: &NObject) -> Vec<(IriIndex, IriIndex)> {
+    let mut references = Vec::new();
+    if node.is_blank_node {
+        return references;
+    }
+    for tree in blank_node_trees(node_data, node) {
+        tree.collect_references(&mut references);
+    }
+    references
+}
+
+/// Named nodes that reference the blank node directly or through other blank nodes
+pub fn blank_node_roots(node_data: &NodeData, blank_index: IriIndex) -> Vec<IriIndex> {
+    let mut roots = Vec::new();
+    let mut visited: HashSet<IriIndex> = HashSet::new();
+    let mut to_visit = vec![(blank_index, 0)];
+    visited.insert(blank_index);
+    while let Some((node_index, depth)) = to_visit.pop() {
+        let Some((_, node)) = node_data.get_node_by_index(node_index) else {
+            continue;
+        };
+        for (_, source_index) in node.reverse_references.iter() {
+            if !visited.insert(*source_index) {
+                continue;
+            }
+            if !is_blank_node(node_data, *source_index) {
+                roots.push(*source_index);
+            } else if depth < MAX_TREE_DEPTH {
+                to_visit.push((*source_index, depth + 1));
+            }
+        }
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_blank_node_trees() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+A <http://example.org/work> <http://id.loc.gov/ontologies/bibframe/title> _:title .
+A _:title <http://id.loc.gov/ontologies/bibframe/mainTitle> "Moby Dick" .
+A <http://example.org/work> <http://id.loc.gov/ontologies/bibframe/contribution> _:contribution .
+A _:contribution <http://id.loc.gov/ontologies/bibframe/agent> <http://example.org/melville> .
+A _:contribution <http://id.loc.gov/ontologies/bibframe/role> _:role .
+A _:role <http://id.loc.gov/ontologies/bibframe/code> "aut" .
+A _:role <http://id.loc.gov/ontologies/bibframe/source> <http://example.org/relators> .
+A _:role <http://id.loc.gov/ontologies/bibframe/parent> _:contribution .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let work_index = node_data.get_node_index("http://example.org/work").unwrap();
+        let (_, work) = node_data.get_node_by_index(work_index).unwrap();
+        assert!(has_blank_node_tree(node_data, work));
+        let trees = blank_node_trees(node_data, work);
+        assert_eq!(2, trees.len());
+        let title = trees.iter().find(|tree| tree.properties.len() == 1).unwrap();
+        assert_eq!("Moby Dick", title.properties[0].1.as_str_ref(&node_data.indexers));
+        let contribution = trees.iter().find(|tree| !tree.children.is_empty()).unwrap();
+        assert_eq!(1, contribution.references.len());
+        // the cycle back to the contribution is not followed
+        assert_eq!(1, contribution.children.len());
+        assert!(contribution.children[0].children.is_empty());
+
+        let melville_index = node_data.get_node_index("http://example.org/melville").unwrap();
+        let relators_index = node_data.get_node_index("http://example.org/relators").unwrap();
+        let references: Vec<IriIndex> = composite_references(node_data, work)
+            .iter()
+            .map(|(_, target_index)| *target_index)
+            .collect();
+        assert_eq!(vec![melville_index, relators_index], references);
+
+        let role_index = contribution.children[0].node_index;
+        assert_eq!(vec![work_index], blank_node_roots(node_data, role_index));
+        let (_, melville) = node_data.get_node_by_index(melville_index).unwrap();
+        assert!(!has_blank_node_tree(node_data, melville));
+    }
+
+    #[test]
+    fn test_rdf_list_tree() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+A <http://example.org/shape> <http://www.w3.org/ns/shacl#in> _:l1 .
+A _:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> "a" .
+A _:l1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:l2 .
+A _:l2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> <http://example.org/b> .
+A _:l2 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> _:l3 .
+A _:l3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#first> _:member .
+A _:l3 <http://www.w3.org/1999/02/22-rdf-syntax-ns#rest> <http://www.w3.org/1999/02/22-rdf-syntax-ns#nil> .
+A _:member <http://example.org/name> "member" .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let shape_index = node_data.get_node_index("http://example.org/shape").unwrap();
+        let b_index = node_data.get_node_index("http://example.org/b").unwrap();
+        let (_, shape) = node_data.get_node_by_index(shape_index).unwrap();
+        let trees = blank_node_trees(node_data, shape);
+        // the chain of list nodes is one tree
+        assert_eq!(1, trees.len());
+        let list = &trees[0];
+        assert!(list.is_list());
+        assert_eq!(3, list.list_items.len());
+        assert!(matches!(list.list_items[0], ListItem::Literal(literal) if literal.as_str_ref(&node_data.indexers) == "a"));
+        assert!(list.list_items[1] == ListItem::Node(b_index));
+        assert_eq!(1, list.children.len());
+        assert_eq!("member", list.children[0].properties[0].1.as_str_ref(&node_data.indexers));
+        // named members are referenced with the list predicate
+        assert_eq!(vec![(list.predicate, b_index)], composite_references(node_data, shape));
+    }
+}
@@ -0,0 +1,478 @@
+use oxrdf::{BlankNode, NamedNode, NamedOrBlankNode, Term, Triple};
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    IriIndex,
+    domain::{Literal, NodeData, RdfData, config::TurtleStyle, prefix_manager::PrefixManager},
+    integration::{
+        rdf_patch::{PatchFormat, PatchOperation, PatchResult, RdfPatch},
+        turtle::turtle_triples,
+    },
+};
+
+/**
+ * Triples added and deleted by editing the data in the node details or by authoring in the graph view.
+ * Undo and redo of edits apply the inverse operations in the same way, so the change set follows the data.
+ * Adding a deleted triple again (or deleting an added one) removes the change.
+ */
+#[derive(Clone, Default)]
+pub struct ChangeSet {
+    pub operations: Vec<PatchOperation>,
+}
+
+fn operation_triple(operation: &PatchOperation) -> (&Triple, bool) {
+    match operation {
+        PatchOperation::Add(triple) => (triple, true),
+        PatchOperation::Delete(triple) => (triple, false),
+    }
+}
+
+impl ChangeSet {
+    pub fn record(&mut self, operation: PatchOperation) {
+        let (triple, is_add) = operation_triple(&operation);
+        if let Some(position) = self
+            .operations
+            .iter()
+            .position(|recorded| operation_triple(recorded).0 == triple)
+        {
+            if operation_triple(&self.operations[position]).1 != is_add {
+                self.operations.remove(position);
+            }
+            return;
+        }
+        self.operations.push(operation);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.operations.clear();
+    }
+
+    /// Changes as SPARQL 1.1 Update request, the deletes are executed first
+    pub fn to_sparql_update(&self) -> String {
+        self.sparql_update_statements().join(";\n")
+    }
+
+    /**
+     * DELETE DATA and INSERT DATA statements of the changes, the deletes go first.
+     * DELETE DATA may not contain blank nodes, so these deletes are sent as DELETE WHERE
+     * with a variable for each blank node, see blank_node_deletes.
+     */
+    pub fn sparql_update_statements(&self) -> Vec<String> {
+        let mut statements = Vec::new();
+        for (is_add, keyword) in [(false, "DELETE DATA"), (true, "INSERT DATA")] {
+            let triples: Vec<&Triple> = self
+                .triples(is_add)
+                .into_iter()
+                .filter(|triple| is_add || !has_blank_node(triple))
+                .collect();
+            if !is_add && let Some(statement) = self.delete_where_statement() {
+                statements.push(statement);
+            }
+            if !triples.is_empty() {
+                let mut statement = format!("{} {{\n", keyword);
+                for triple in triples {
+                    statement.push_str(&format!("  {} .\n", triple));
+                }
+                statement.push_str("}\n");
+                statements.push(statement);
+            }
+        }
+        statements
+    }
+
+    /// Deleted triples with blank nodes, they match all nodes of the endpoint with the same triples
+    pub fn blank_node_deletes(&self) -> Vec<&Triple> {
+        self.triples(false).into_iter().filter(|triple| has_blank_node(triple)).collect()
+    }
+
+    // the same blank node gets the same variable, so the patterns are joined
+    fn delete_where_statement(&self) -> Option<String> {
+        let triples = self.blank_node_deletes();
+        if triples.is_empty() {
+            return None;
+        }
+        let mut variables: HashMap<String, String> = HashMap::new();
+        let mut variable = |blank_node: &BlankNode| -> String {
+            let count = variables.len();
+            variables
+                .entry(blank_node.as_str().to_string())
+                .or_insert_with(|| format!("?b{}", count))
+                .clone()
+        };
+        let mut statement = String::from("DELETE WHERE {\n");
+        for triple in triples {
+            let subject = match &triple.subject {
+                NamedOrBlankNode::BlankNode(blank_node) => variable(blank_node),
+                subject => subject.to_string(),
+            };
+            let object = match &triple.object {
+                Term::BlankNode(blank_node) => variable(blank_node),
+                object => object.to_string(),
+            };
+            statement.push_str(&format!("  {} {} {} .\n", subject, triple.predicate, object));
+        }
+        statement.push_str("}\n");
+        Some(statement)
+    }
+
+    /**
+     * Changes as SPARQL Update for the export, the data blocks are pretty printed with the turtle style,
+     * so the exported files are review friendly in diffs.
+     */
+    pub fn to_sparql_update_pretty(&self, node_data: &NodeData, prefix_manager: &PrefixManager, style: &TurtleStyle) -> String {
+        let mut prefixes = BTreeMap::new();
+        let mut statements = Vec::new();
+        for (is_add, keyword) in [(false, "DELETE DATA"), (true, "INSERT DATA")] {
+            let triples: Vec<&Triple> = self
+                .triples(is_add)
+                .into_iter()
+                .filter(|triple| is_add || !has_blank_node(triple))
+                .collect();
+            if !is_add && let Some(statement) = self.delete_where_statement() {
+                statements.push(statement);
+            }
+            if triples.is_empty() {
+                continue;
+            }
+            let (used_prefixes, body) = turtle_triples(node_data, prefix_manager, &triples, style);
+            prefixes.extend(used_prefixes);
+            let mut statement = format!("{} {{\n", keyword);
+            for line in body.trim_end().lines() {
+                if !line.is_empty() {
+                    statement.push_str("  ");
+                    statement.push_str(line);
+                }
+                statement.push('\n');
+            }
+            statement.push_str("}\n");
+            statements.push(statement);
+        }
+        let mut update = String::new();
+        for (prefix, iri) in prefixes.iter() {
+            update.push_str(&format!("PREFIX {}: <{}>\n", prefix, iri));
+        }
+        if !prefixes.is_empty() {
+            update.push('\n');
+        }
+        update.push_str(&statements.join(";\n"));
+        update
+    }
+
+    fn triples(&self, is_add: bool) -> Vec<&Triple> {
+        self.operations
+            .iter()
+            .map(operation_triple)
+            .filter(|(_, add)| *add == is_add)
+            .map(|(triple, _)| triple)
+            .collect()
+    }
+
+    /// Changes as RDF Patch in one transaction, it can be applied again with Apply Patch File
+    pub fn to_rdf_patch(&self) -> String {
+        let mut patch = String::from("TX .\n");
+        for operation in self.operations.iter() {
+            match operation {
+                PatchOperation::Add(triple) => patch.push_str(&format!("A {} .\n", triple)),
+                PatchOperation::Delete(triple) => patch.push_str(&format!("D {} .\n", triple)),
+            }
+        }
+        patch.push_str("TC .\n");
+        patch
+    }
+}
+
+fn has_blank_node(triple: &Triple) -> bool {
+    triple.subject.is_blank_node() || triple.object.is_blank_node()
+}
+
+fn full_iri(iri: &str, prefix_manager: &PrefixManager) -> String {
+    prefix_manager.get_full_opt(iri).map_or_else(|| iri.to_string(), |full_iri| full_iri.into())
+}
+
+fn node_subject(node_data: &NodeData, prefix_manager: &PrefixManager, node_index: IriIndex) -> Option<NamedOrBlankNode> {
+    let (iri, node) = node_data.get_node_by_index(node_index)?;
+    if node.is_blank_node {
+        Some(BlankNode::new_unchecked(iri.as_ref()).into())
+    } else {
+        NamedNode::new(full_iri(iri, prefix_manager)).ok().map(NamedOrBlankNode::from)
+    }
+}
+
+/// Predicate, type or node given by the user as full or prefixed iri
+pub fn iri_node(iri: &str, prefix_manager: &PrefixManager) -> Option<NamedNode> {
+    let iri = iri.trim().trim_start_matches('<').trim_end_matches('>');
+    match prefix_manager.get_full_opt(iri) {
+        Some(full_iri) => NamedNode::new(full_iri.as_ref()).ok(),
+        None if iri.contains("://") || iri.starts_with("urn:") => NamedNode::new(iri).ok(),
+        None => None,
+    }
+}
+
+/// New iri in the namespace that is not used by any node, the local name is the name with a number
+pub fn mint_iri(namespace: &str, name: &str, node_data: &NodeData, prefix_manager: &PrefixManager) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let name = if name.is_empty() { "node".to_string() } else { name };
+    let mut number = 1;
+    loop {
+        let iri = format!("{}{}-{}", namespace, name, number);
+        if node_data.get_node_index(&prefix_manager.get_prefixed(&iri)).is_none() {
+            return iri;
+        }
+        number += 1;
+    }
+}
+
+/**
+ * Triple of a data property with the value. The language or the data type is taken from the kind literal,
+ * so an edited value keeps them. Without kind the value is a simple string literal.
+ */
+pub fn property_triple(
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    node_index: IriIndex,
+    predicate: NamedNode,
+    value: &str,
+    kind: Option<&Literal>,
+) -> Option<Triple> {
+    let subject = node_subject(node_data, prefix_manager, node_index)?;
+    let object = match kind {
+        Some(Literal::NoValue()) => return None,
+        Some(Literal::LangString(language_index, _)) => match node_data.get_language(*language_index) {
+            Some(language) => oxrdf::Literal::new_language_tagged_literal_unchecked(value, language),
+            None => oxrdf::Literal::new_simple_literal(value),
+        },
+        Some(Literal::TypedString(data_type_index, _)) => {
+            let data_type = node_data
+                .indexers
+                .datatype_indexer
+                .index_to_str(*data_type_index as IriIndex)?;
+            oxrdf::Literal::new_typed_literal(value, NamedNode::new(full_iri(data_type, prefix_manager)).ok()?)
+        }
+        Some(Literal::StringShort(_)) | Some(Literal::String(_)) | None => oxrdf::Literal::new_simple_literal(value),
+    };
+    Some(Triple::new(subject, predicate, object))
+}
+
+/// Triple of an existing data property of the node
+pub fn literal_triple(
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    node_index: IriIndex,
+    predicate_index: IriIndex,
+    literal: &Literal,
+) -> Option<Triple> {
+    let predicate = NamedNode::new(full_iri(node_data.get_predicate(predicate_index)?, prefix_manager)).ok()?;
+    property_triple(
+        node_data,
+        prefix_manager,
+        node_index,
+        predicate,
+        literal.as_str_ref(&node_data.indexers),
+        Some(literal),
+    )
+}
+
+/// Triple of a reference between two nodes
+pub fn reference_triple(
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    node_index: IriIndex,
+    predicate: NamedNode,
+    reference_index: IriIndex,
+) -> Option<Triple> {
+    let subject = node_subject(node_data, prefix_manager, node_index)?;
+    let object = node_subject(node_data, prefix_manager, reference_index)?;
+    Some(Triple::new(subject, predicate, object))
+}
+
+impl RdfData {
+    /// Applies the edit operations to the data and records the operations that changed the data in the change set
+    pub fn edit_triples(&mut self, operations: Vec<PatchOperation>) -> PatchResult {
+        let mut result = PatchResult::default();
+        for operation in operations {
+            let mut patch = RdfPatch {
+                format: PatchFormat::RdfPatch,
+                operations: vec![operation],
+                errors: Vec::new(),
+            };
+            let mut operation_result = patch.apply(self, &[]);
+            if operation_result.added + operation_result.deleted > 0
+                && let Some(operation) = patch.operations.pop()
+            {
+                self.node_data.change_set.record(operation);
+            }
+            result.added += operation_result.added;
+            result.duplicates += operation_result.duplicates;
+            result.filtered += operation_result.filtered;
+            result.deleted += operation_result.deleted;
+            result.not_found += operation_result.not_found;
+            result.applied.append(&mut operation_result.applied);
+            result.deleted_graphs.append(&mut operation_result.deleted_graphs);
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data() -> RdfData {
+        RdfData::from_patch(
+            "A <http://example.org/a> <http://xmlns.com/foaf/0.1/name> \"Alice\"@en .\n\
+             A <http://example.org/a> <http://xmlns.com/foaf/0.1/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n",
+        )
+    }
+
+    #[test]
+    fn test_edit_triples() {
+        let mut rdf_data = test_data();
+        let node_data = &rdf_data.node_data;
+        let a = node_data.get_node_index("http://example.org/a").unwrap();
+        let (_, node) = node_data.get_node_by_index(a).unwrap();
+        let (name_index, name) = node.properties[0].clone();
+        let name_predicate = NamedNode::new("http://xmlns.com/foaf/0.1/name").unwrap();
+        let old_triple = literal_triple(node_data, &rdf_data.prefix_manager, a, name_index, &name).unwrap();
+        let new_triple =
+            property_triple(node_data, &rdf_data.prefix_manager, a, name_predicate, "Alicia", Some(&name)).unwrap();
+        assert_eq!("<http://example.org/a> <http://xmlns.com/foaf/0.1/name> \"Alicia\"@en", new_triple.to_string());
+        let (age_index, age) = node.properties[1].clone();
+        let age_triple = literal_triple(node_data, &rdf_data.prefix_manager, a, age_index, &age).unwrap();
+        assert_eq!(
+            "<http://example.org/a> <http://xmlns.com/foaf/0.1/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer>",
+            age_triple.to_string()
+        );
+
+        let result = rdf_data.edit_triples(vec![
+            PatchOperation::Delete(old_triple.clone()),
+            PatchOperation::Add(new_triple.clone()),
+            PatchOperation::Delete(age_triple.clone()),
+        ]);
+        assert_eq!(1, result.added);
+        assert_eq!(2, result.deleted);
+        let node = rdf_data.node_data.get_node("http://example.org/a").unwrap();
+        assert_eq!(1, node.properties.len());
+        assert!(
+            node.properties
+                .iter()
+                .any(|(_, literal)| literal.as_str_ref(&rdf_data.node_data.indexers) == "Alicia")
+        );
+        assert_eq!(3, rdf_data.node_data.change_set.len());
+        assert_eq!(2, rdf_data.node_data.change_set.sparql_update_statements().len());
+        let sparql = rdf_data.node_data.change_set.to_sparql_update();
+        assert!(sparql.starts_with("DELETE DATA {\n  <http://example.org/a> <http://xmlns.com/foaf/0.1/name> \"Alice\"@en .\n"));
+        assert!(sparql.contains(";\nINSERT DATA {\n  <http://example.org/a> <http://xmlns.com/foaf/0.1/name> \"Alicia\"@en .\n}\n"));
+
+        // the patch can be read again
+        let patch = RdfPatch::parse_rdf_patch(&rdf_data.node_data.change_set.to_rdf_patch());
+        assert!(patch.errors.is_empty(), "{:?}", patch.errors);
+        assert_eq!(3, patch.operations.len());
+
+        // the export groups the triples by subject and uses the prefixes
+        let pretty = rdf_data.node_data.change_set.to_sparql_update_pretty(
+            &rdf_data.node_data,
+            &rdf_data.prefix_manager,
+            &TurtleStyle::default(),
+        );
+        assert!(pretty.starts_with("PREFIX "));
+        assert!(pretty.contains("DELETE DATA {\n  <http://example.org/a> "));
+        assert!(pretty.contains("\"42\"^^xsd:integer"));
+        assert!(pretty.contains(" ;\n      "));
+
+        // duplicates are not recorded
+        let duplicate = PatchOperation::Add(new_triple.clone());
+        assert_eq!(1, rdf_data.edit_triples(vec![duplicate]).duplicates);
+        assert_eq!(3, rdf_data.node_data.change_set.len());
+
+        // reverting the edit removes the changes
+        rdf_data.edit_triples(vec![PatchOperation::Delete(new_triple), PatchOperation::Add(old_triple)]);
+        assert_eq!(1, rdf_data.node_data.change_set.len());
+    }
+
+    #[test]
+    fn test_update_with_blank_nodes() {
+        let a = NamedNode::new("http://example.org/a").unwrap();
+        let address = NamedNode::new("http://example.org/address").unwrap();
+        let street = NamedNode::new("http://example.org/street").unwrap();
+        let home = BlankNode::new("home").unwrap();
+        let work = BlankNode::new("work").unwrap();
+        let [main, side, old] = ["Main", "Side", "Old"].map(oxrdf::Literal::new_simple_literal);
+        let mut change_set = ChangeSet::default();
+        change_set.record(PatchOperation::Delete(Triple::new(a.clone(), address.clone(), home.clone())));
+        change_set.record(PatchOperation::Delete(Triple::new(home, street.clone(), main)));
+        change_set.record(PatchOperation::Delete(Triple::new(work, street.clone(), side)));
+        change_set.record(PatchOperation::Delete(Triple::new(a.clone(), street, old)));
+        change_set.record(PatchOperation::Add(Triple::new(a, address, BlankNode::new("new").unwrap())));
+        assert_eq!(3, change_set.blank_node_deletes().len());
+
+        // blank nodes are not allowed in DELETE DATA, the same blank node is joined by the same variable
+
+        let statements = change_set.sparql_update_statements();
+        assert_eq!(
+            vec![
+                "DELETE WHERE {\n  <http://example.org/a> <http://example.org/address> ?b0 .\n  ?b0 <http://example.org/street> \"Main\" .\n  ?b1 <http://example.org/street> \"Side\" .\n}\n",
+                "DELETE DATA {\n  <http://example.org/a> <http://example.org/street> \"Old\" .\n}\n",
+                "INSERT DATA {\n  <http://example.org/a> <http://example.org/address> _:new .\n}\n",
+            ],
+            statements
+        );
+    }
+
+    #[test]
+    fn test_iri_node() {
+        let prefix_manager = PrefixManager::new();
+        assert_eq!(
+            Some("http://xmlns.com/foaf/0.1/nick"),
+            iri_node("foaf:nick", &prefix_manager).as_ref().map(|node| node.as_str())
+        );
+        assert_eq!(
+            Some("http://example.org/p"),
+            iri_node("<http://example.org/p>", &prefix_manager).as_ref().map(|node| node.as_str())
+        );
+        assert!(iri_node("unknown:p", &prefix_manager).is_none());
+    }
+
+    #[test]
+    fn test_new_nodes() {
+        let mut rdf_data = test_data();
+        let a = rdf_data.node_data.get_node_index("http://example.org/a").unwrap();
+        let namespace = "http://example.org/";
+        assert_eq!(
+            "http://example.org/Person-1",
+            mint_iri(namespace, "Person", &rdf_data.node_data, &rdf_data.prefix_manager)
+        );
+        let subject = NamedNode::new("http://example.org/Person-1").unwrap();
+        let type_triple = Triple::new(
+            subject,
+            oxrdf::vocab::rdf::TYPE,
+            NamedNode::new("http://xmlns.com/foaf/0.1/Person").unwrap(),
+        );
+        assert_eq!(1, rdf_data.edit_triples(vec![PatchOperation::Add(type_triple)]).added);
+        assert_eq!(
+            "http://example.org/Person-2",
+            mint_iri(namespace, "Person", &rdf_data.node_data, &rdf_data.prefix_manager)
+        );
+        assert_eq!("http://example.org/node-1", mint_iri(namespace, "", &rdf_data.node_data, &rdf_data.prefix_manager));
+
+        let person = rdf_data.node_data.get_node_index("http://example.org/Person-1").unwrap();
+        let knows = NamedNode::new("http://xmlns.com/foaf/0.1/knows").unwrap();
+        let reference =
+            reference_triple(&rdf_data.node_data, &rdf_data.prefix_manager, a, knows, person).unwrap();
+        assert_eq!(1, rdf_data.edit_triples(vec![PatchOperation::Add(reference)]).added);
+        let node = rdf_data.node_data.get_node("http://example.org/a").unwrap();
+        assert_eq!(vec![person], node.references.iter().map(|(_, reference)| *reference).collect::<Vec<_>>());
+        assert_eq!(2, rdf_data.node_data.change_set.len());
+    }
+}
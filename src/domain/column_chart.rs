@@ -0,0 +1,275 @@
+use std::collections::HashMap;
+
+use crate::{
+    IriIndex,
+    domain::{Literal, NodeData, type_index::ValueTypes},
+};
+
+// categories beyond this count are summed up as one bar
+pub const MAX_CATEGORIES: usize = 30;
+const MAX_BINS: usize = 50;
+
+pub struct ChartBar {
+    pub label: String,
+    // center of the bar on the x axis
+    pub position: f64,
+    pub width: f64,
+    pub count: u32,
+}
+
+/**
+ * Bars of a histogram (numeric values) or of a bar chart (categorical values).
+ * The chart is not bound to a source, so it can show any value distribution.
+ */
+pub struct ColumnChart {
+    pub title: String,
+    pub numeric: bool,
+    pub bars: Vec<ChartBar>,
+    // number of instances without a value
+    pub missing: usize,
+}
+
+impl ColumnChart {
+    /**
+     * Chart of the values of the predicate for the given instances, multiple values of an instance are counted each.
+     * Only the top_k most frequent categorical values get their own bar.
+     */
+    pub fn from_instances(
+        title: String,
+        predicate: IriIndex,
+        node_data: &NodeData,
+        instances: &[IriIndex],
+        top_k: usize,
+    ) -> Self {
+        let mut numbers: Vec<f64> = Vec::new();
+        let mut all_integers = true;
+        let mut categories: HashMap<&str, u32> = HashMap::new();
+        let mut missing = 0;
+        for instance_index in instances {
+            let Some((_iri, node)) = node_data.get_node_by_index(*instance_index) else {
+                continue;
+            };
+            let mut found = false;
+            for (property_index, value) in node.properties.iter() {
+                if *property_index != predicate {
+                    continue;
+                }
+                found = true;
+                let value_str = value.as_str_ref(&node_data.indexers);
+                *categories.entry(value_str).or_insert(0) += 1;
+                let value_type = value.value_type(&node_data.indexers);
+                if value_type.intersects(ValueTypes::INTEGER | ValueTypes::DOUBLE)
+                    && let Ok(number) = value_str.trim().parse::<f64>()
+                {
+                    all_integers = all_integers && value_type.contains(ValueTypes::INTEGER);
+                    numbers.push(number);
+                }
+            }
+            if !found {
+                missing += 1;
+            }
+        }
+        let value_count: u32 = categories.values().sum();
+        // the column is numeric only if all values are numbers
+        let numeric = !numbers.is_empty() && numbers.len() as u32 == value_count;
+        let bars = if numeric {
+            histogram(&numbers, all_integers)
+        } else {
+            category_bars(categories, top_k)
+        };
+        ColumnChart {
+            title,
+            numeric,
+            bars,
+            missing,
+        }
+    }
+
+    /// Chart of the language tags of the predicate values, values without language are counted as "(none)"
+    pub fn language_distribution(
+        title: String,
+        predicate: IriIndex,
+        node_data: &NodeData,
+        instances: &[IriIndex],
+    ) -> Self {
+        let mut languages: HashMap<&str, u32> = HashMap::new();
+        let mut missing = 0;
+        for instance_index in instances {
+            let Some((_iri, node)) = node_data.get_node_by_index(*instance_index) else {
+                continue;
+            };
+            let mut found = false;
+            for (property_index, value) in node.properties.iter() {
+                if *property_index != predicate {
+                    continue;
+                }
+                found = true;
+                let language = match value {
+                    Literal::LangString(language_index, _) => node_data.get_language(*language_index).unwrap_or("?"),
+                    _ => "(none)",
+                };
+                *languages.entry(language).or_insert(0) += 1;
+            }
+            if !found {
+                missing += 1;
+            }
+        }
+        ColumnChart {
+            title,
+            numeric: false,
+            bars: category_bars(languages, MAX_CATEGORIES),
+            missing,
+        }
+    }
+
+    pub fn export_csv<W: std::io::Write>(&self, wtr: &mut csv::Writer<W>) -> Result<(), Box<dyn std::error::Error>> {
+        wtr.write_record([self.title.as_str(), "count"])?;
+        for bar in self.bars.iter() {
+            wtr.write_record([bar.label.as_str(), bar.count.to_string().as_str()])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+fn histogram(numbers: &[f64], all_integers: bool) -> Vec<ChartBar> {
+    let min = numbers.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = numbers.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    // Sturges' rule
+    let mut bins = ((numbers.len() as f64).log2().ceil() as usize + 1).clamp(1, MAX_BINS);
+    let mut width = (max - min) / bins as f64;
+    let integer_bars = all_integers && max - min + 1.0 <= bins as f64;
+    if integer_bars {
+        // one bar for each integer value
+        bins = (max - min) as usize + 1;
+        width = 1.0;
+    } else if width == 0.0 {
+        bins = 1;
+        width = 1.0;
+    }
+    let mut counts = vec![0u32; bins];
+    for number in numbers {
+        let bin = ((number - min) / width) as usize;
+        counts[bin.min(bins - 1)] += 1;
+    }
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(bin, count)| {
+            let start = min + bin as f64 * width;
+            let (label, position) = if integer_bars {
+                (format!("{}", start), start)
+            } else {
+                (format!("{:.3}..{:.3}", start, start + width), start + width / 2.0)
+            };
+            ChartBar {
+                label,
+                position,
+                width,
+                count,
+            }
+        })
+        .collect()
+}
+
+fn category_bars(categories: HashMap<&str, u32>, top_k: usize) -> Vec<ChartBar> {
+    let mut categories: Vec<(&str, u32)> = categories.into_iter().collect();
+    categories.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    let other: u32 = categories.iter().skip(top_k).map(|(_, count)| count).sum();
+    let mut bars: Vec<ChartBar> = categories
+        .into_iter()
+        .take(top_k)
+        .enumerate()
+        .map(|(position, (value, count))| ChartBar {
+            label: value.to_string(),
+            position: position as f64,
+            width: 0.8,
+            count,
+        })
+        .collect();
+    if other > 0 {
+        bars.push(ChartBar {
+            label: "(other)".to_string(),
+            position: bars.len() as f64,
+            width: 0.8,
+            count: other,
+        });
+    }
+    bars
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::RdfData,
+        integration::rdf_patch::RdfPatch,
+    };
+
+    const DATA: &str = r#"
+PA ex: <http://example.org/> .
+A ex:a ex:age "20"^^<http://www.w3.org/2001/XMLSchema#integer> .
+A ex:a ex:color "red" .
+A ex:b ex:age "21"^^<http://www.w3.org/2001/XMLSchema#integer> .
+A ex:b ex:color "red" .
+A ex:c ex:age "21"^^<http://www.w3.org/2001/XMLSchema#integer> .
+A ex:c ex:color "blue" .
+A ex:d ex:color "green" .
+A ex:a ex:name "Anna"@en .
+A ex:b ex:name "Anna"@de .
+A ex:c ex:name "Anne"@en .
+A ex:c ex:name "Anne" .
+"#;
+
+    #[test]
+    fn test_column_chart() {
+        let mut rdf_data = RdfData::empty();
+        rdf_data.prefix_manager.add_prefix("ex", "http://example.org/");
+        RdfPatch::parse_rdf_patch(DATA).apply(&mut rdf_data, &[]);
+        let node_data = &rdf_data.node_data;
+        let instances: Vec<IriIndex> = ["ex:a", "ex:b", "ex:c", "ex:d"]
+            .iter()
+            .map(|iri| node_data.get_node_index(iri).unwrap())
+            .collect();
+
+        let age = node_data.find_predicate_index(&rdf_data.prefix_manager, "http://example.org/age").unwrap();
+        let chart = ColumnChart::from_instances("age".to_string(), age, node_data, &instances, MAX_CATEGORIES);
+        assert!(chart.numeric);
+        assert_eq!(1, chart.missing);
+        assert_eq!(vec![("20", 1), ("21", 2)], bar_counts(&chart));
+
+        let color = node_data.find_predicate_index(&rdf_data.prefix_manager, "http://example.org/color").unwrap();
+        let chart = ColumnChart::from_instances("color".to_string(), color, node_data, &instances, MAX_CATEGORIES);
+        assert!(!chart.numeric);
+        assert_eq!(0, chart.missing);
+        assert_eq!(vec![("red", 2), ("blue", 1), ("green", 1)], bar_counts(&chart));
+
+        let mut wtr = csv::Writer::from_writer(Vec::new());
+        chart.export_csv(&mut wtr).unwrap();
+        let csv = String::from_utf8(wtr.into_inner().unwrap()).unwrap();
+        assert_eq!("color,count\nred,2\nblue,1\ngreen,1\n", csv);
+
+        let chart = ColumnChart::from_instances("color".to_string(), color, node_data, &instances, 1);
+        assert_eq!(vec![("red", 2), ("(other)", 2)], bar_counts(&chart));
+
+        let name = node_data.find_predicate_index(&rdf_data.prefix_manager, "http://example.org/name").unwrap();
+        let chart = ColumnChart::language_distribution("name".to_string(), name, node_data, &instances);
+        assert_eq!(1, chart.missing);
+        assert_eq!(vec![("en", 2), ("(none)", 1), ("de", 1)], bar_counts(&chart));
+    }
+
+    #[test]
+    fn test_histogram() {
+        let numbers: Vec<f64> = (0..100).map(|n| n as f64 / 10.0).collect();
+        let bars = histogram(&numbers, false);
+        assert_eq!(8, bars.len());
+        assert_eq!(100, bars.iter().map(|bar| bar.count).sum::<u32>());
+        let bars = histogram(&[5.0, 5.0], false);
+        assert_eq!(1, bars.len());
+        assert_eq!(2, bars[0].count);
+    }
+
+    fn bar_counts(chart: &ColumnChart) -> Vec<(&str, u32)> {
+        chart.bars.iter().map(|bar| (bar.label.as_str(), bar.count)).collect()
+    }
+}
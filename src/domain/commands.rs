@@ -0,0 +1,304 @@
+use crate::{
+    domain::{RdfData, config::Config, named_graphs::TripleGraphs},
+    integration::{
+        rdf_patch::{PatchFormat, PatchOperation, RdfPatch},
+        rdfwrap::restore_triple_graphs,
+    },
+};
+
+/// How the data of an operation are restored by undo and redo
+enum DataDelta {
+    /**
+     * The triple operations that changed the data, undo applies the inverse operations in reverse order.
+     * The nodes created by the operation are appended to the node cache, they are removed again by undo.
+     */
+    Triples {
+        operations: Vec<PatchOperation>,
+        // deleted triples are added to their named graphs again by undo
+        deleted_graphs: TripleGraphs,
+        node_count: usize,
+        // the operations are edits that are recorded in the change set too
+        is_edit: bool,
+    },
+    // Copy of the whole data before (undo) or after (redo) the operation
+    Snapshot(Box<RdfData>),
+}
+
+/// A data changing operation with the delta to undo or redo it
+pub struct DataOperation {
+    pub description: String,
+    delta: DataDelta,
+}
+
+/**
+ * Undo and redo of data changing operations (load, merge, clean, patch ...).
+ * The layout undo of the visual graph is kept in SortedNodeLayout.
+ * Triple operations (patch, SPARQL merge, edits) keep only the changed triples.
+ * Other operations need a copy of the whole data, it is limited by the number of nodes in the config.
+ */
+#[derive(Default)]
+pub struct DataHistory {
+    undo_stack: Vec<DataOperation>,
+    redo_stack: Vec<DataOperation>,
+}
+
+impl DataHistory {
+    /**
+     * Stores the triple operations that changed the data.
+     * node_count is the number of nodes before the operation, deleted_graphs are the named graphs of deleted triples.
+     * Returns false if undo is disabled.
+     */
+    pub fn record_triples(
+        &mut self,
+        description: &str,
+        node_count: usize,
+        operations: Vec<PatchOperation>,
+        deleted_graphs: TripleGraphs,
+        is_edit: bool,
+        config: &Config,
+    ) -> bool {
+        self.redo_stack.clear();
+        if config.data_undo_levels == 0 {
+            self.undo_stack.clear();
+            return false;
+        }
+        if operations.is_empty() {
+            return true;
+        }
+        self.push_undo(
+            DataOperation {
+                description: description.to_string(),
+                delta: DataDelta::Triples {
+                    operations,
+                    deleted_graphs,
+                    node_count,
+                    is_edit,
+                },
+            },
+            config,
+        );
+        true
+    }
+
+    /**
+     * Stores a copy of the data before an operation that can not be expressed as triple operations.
+     * Returns false if snapshots are disabled or the data are too big, older states are dropped then.
+     */
+    pub fn record_snapshot(&mut self, description: &str, rdf_data: &RdfData, config: &Config) -> bool {
+        self.redo_stack.clear();
+        if config.data_undo_levels == 0
+            || !config.data_undo_snapshots
+            || (config.data_undo_max_nodes > 0 && rdf_data.node_data.len() > config.data_undo_max_nodes)
+        {
+            // the older states would undo the not recorded operation too
+            self.undo_stack.clear();
+            return false;
+        }
+        self.push_undo(
+            DataOperation {
+                description: description.to_string(),
+                delta: DataDelta::Snapshot(Box::new(rdf_data.clone())),
+            },
+            config,
+        );
+        true
+    }
+
+    fn push_undo(&mut self, operation: DataOperation, config: &Config) {
+        self.undo_stack.push(operation);
+        if self.undo_stack.len() > config.data_undo_levels {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Restores the data before the last operation, returns the description of the undone operation
+    pub fn undo(&mut self, rdf_data: &mut RdfData) -> Option<String> {
+        let operation = self.undo_stack.pop()?;
+        let description = operation.description.clone();
+        self.redo_stack.push(operation.apply(rdf_data, true));
+        Some(description)
+    }
+
+    /// Restores the data after the last undone operation, returns the description of the operation
+    pub fn redo(&mut self, rdf_data: &mut RdfData) -> Option<String> {
+        let operation = self.redo_stack.pop()?;
+        let description = operation.description.clone();
+        self.undo_stack.push(operation.apply(rdf_data, false));
+        Some(description)
+    }
+
+    pub fn undo_description(&self) -> Option<&str> {
+        self.undo_stack.last().map(|operation| operation.description.as_str())
+    }
+
+    pub fn redo_description(&self) -> Option<&str> {
+        self.redo_stack.last().map(|operation| operation.description.as_str())
+    }
+
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+impl DataOperation {
+    // Undoes (inverse) or redoes the operation, returns the operation for the other stack
+    fn apply(self, rdf_data: &mut RdfData, inverse: bool) -> DataOperation {
+        let delta = match self.delta {
+            DataDelta::Triples {
+                operations,
+                deleted_graphs,
+                node_count,
+                is_edit,
+            } => {
+                let patch_operations: Vec<PatchOperation> = if inverse {
+                    operations
+                        .iter()
+                        .rev()
+                        .map(|operation| match operation {
+                            PatchOperation::Add(triple) => PatchOperation::Delete(triple.clone()),
+                            PatchOperation::Delete(triple) => PatchOperation::Add(triple.clone()),
+                        })
+                        .collect()
+                } else {
+                    operations.clone()
+                };
+                if is_edit {
+                    rdf_data.edit_triples(patch_operations);
+                } else {
+                    let patch = RdfPatch {
+                        format: PatchFormat::RdfPatch,
+                        operations: patch_operations,
+                        errors: Vec::new(),
+                    };
+                    patch.apply(rdf_data, &[]);
+                }
+                if inverse {
+                    restore_triple_graphs(rdf_data, &deleted_graphs);
+                    rdf_data.node_data.node_cache.remove_empty_nodes_from(node_count);
+                }
+                DataDelta::Triples {
+                    operations,
+                    deleted_graphs,
+                    node_count,
+                    is_edit,
+                }
+            }
+            DataDelta::Snapshot(snapshot) => DataDelta::Snapshot(Box::new(std::mem::replace(rdf_data, *snapshot))),
+        };
+        DataOperation {
+            description: self.description,
+            delta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::RwLock};
+
+    use super::*;
+    use crate::integration::rdfwrap::RDFWrap;
+
+    fn apply_patch(rdf_data: &mut RdfData, patch: &str) -> Vec<PatchOperation> {
+        RdfPatch::parse_rdf_patch(patch).apply(rdf_data, &[]).applied
+    }
+
+    #[test]
+    fn test_data_history() {
+        let mut rdf_data = RdfData::empty();
+        let mut config = Config::default();
+        config.data_undo_levels = 2;
+        let mut history = DataHistory::default();
+        for (description, patch) in [
+            ("first", "A <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n"),
+            ("second", "A <http://example.org/b> <http://example.org/knows> <http://example.org/c> .\n"),
+            ("third", "A <http://example.org/c> <http://example.org/knows> <http://example.org/d> .\n"),
+        ] {
+            let node_count = rdf_data.node_data.len();
+            let operations = apply_patch(&mut rdf_data, patch);
+            assert!(history.record_triples(description, node_count, operations, Vec::new(), false, &config));
+        }
+        assert_eq!(4, rdf_data.node_data.len());
+        assert_eq!(Some("third".to_string()), history.undo(&mut rdf_data));
+        assert_eq!(3, rdf_data.node_data.len());
+        assert_eq!(Some("second"), history.undo_description());
+        assert_eq!(Some("second".to_string()), history.undo(&mut rdf_data));
+        assert_eq!(2, rdf_data.node_data.len());
+        // only 2 levels are kept
+        assert_eq!(None, history.undo(&mut rdf_data));
+        assert_eq!(Some("second".to_string()), history.redo(&mut rdf_data));
+        assert_eq!(3, rdf_data.node_data.len());
+        let c = rdf_data.node_data.get_node_index("http://example.org/c").unwrap();
+        let b = rdf_data.node_data.get_node("http://example.org/b").unwrap();
+        assert!(b.references.iter().any(|(_, object)| *object == c));
+        assert_eq!(Some("third"), history.redo_description());
+        // snapshots can be disabled and are limited by the number of nodes
+        config.data_undo_snapshots = false;
+        assert!(!history.record_snapshot("clean", &rdf_data, &config));
+        assert_eq!(None, history.redo_description());
+        assert_eq!(None, history.undo_description());
+        config.data_undo_snapshots = true;
+        assert!(history.record_snapshot("clean", &rdf_data, &config));
+        apply_patch(&mut rdf_data, "D <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n");
+        assert_eq!(Some("clean".to_string()), history.undo(&mut rdf_data));
+        let a = rdf_data.node_data.get_node("http://example.org/a").unwrap();
+        assert_eq!(1, a.references.len());
+        config.data_undo_max_nodes = 1;
+        assert!(!history.record_snapshot("big", &rdf_data, &config));
+        assert_eq!(None, history.redo_description());
+    }
+
+    #[test]
+    fn test_undo_delete() {
+        let mut rdf_data = RdfData::empty();
+        let config = Config::default();
+        let mut history = DataHistory::default();
+        apply_patch(
+            &mut rdf_data,
+            "A <http://example.org/a> <http://example.org/name> \"Alice\" .\n\
+             A <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n",
+        );
+        let node_count = rdf_data.node_data.len();
+        let operations = apply_patch(
+            &mut rdf_data,
+            "D <http://example.org/a> <http://example.org/name> \"Alice\" .\n\
+             D <http://example.org/a> <http://example.org/name> \"Bob\" .\n\
+             A <http://example.org/a> <http://example.org/knows> <http://example.org/c> .\n",
+        );
+        // the not found delete is not recorded
+        assert_eq!(2, operations.len());
+        history.record_triples("patch", node_count, operations, Vec::new(), false, &config);
+        assert_eq!(3, rdf_data.node_data.len());
+        history.undo(&mut rdf_data);
+        assert_eq!(2, rdf_data.node_data.len());
+        let a = rdf_data.node_data.get_node("http://example.org/a").unwrap();
+        assert_eq!(1, a.properties.len());
+        assert_eq!(1, a.references.len());
+    }
+
+    #[test]
+    fn test_undo_load() {
+        let rdf_data = RwLock::new(RdfData::empty());
+        let config = Config::default();
+        let mut history = DataHistory::default();
+        let load = |data: &'static str| {
+            RDFWrap::load_file_reader("data.nt", "nt", "data", io::Cursor::new(data), &rdf_data, &[], None).unwrap();
+        };
+        load("<http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n");
+        let node_count = rdf_data.read().unwrap().node_data.len();
+        assert_eq!(2, node_count);
+
+        // the default config keeps a copy of small data
+        assert!(history.record_snapshot("load", &rdf_data.read().unwrap(), &config));
+        load(
+            "<http://example.org/b> <http://example.org/knows> <http://example.org/c> .\n\
+             <http://example.org/c> <http://example.org/knows> <http://example.org/d> .\n",
+        );
+        assert_eq!(4, rdf_data.read().unwrap().node_data.len());
+        assert_eq!(Some("load".to_string()), history.undo(&mut rdf_data.write().unwrap()));
+        let rdf_data = rdf_data.read().unwrap();
+        assert_eq!(node_count, rdf_data.node_data.len());
+        assert!(rdf_data.node_data.get_node("http://example.org/c").is_none());
+    }
+}
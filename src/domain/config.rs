@@ -1,6 +1,11 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize)]
+use crate::domain::{
+    identifier_resolution::{IdentifierTemplate, default_identifier_templates},
+    keymap::Keymap,
+};
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
     // nodes force
     pub repulsion_constant: f32,
@@ -29,9 +34,107 @@ pub struct Config {
     pub max_visible_nodes: usize,
     #[serde(default = "default_250")]
     pub gravity_effect_radius: f32,
+    #[serde(default)]
+    pub turtle_style: TurtleStyle,
+    // accept rdfglance:// links forwarded from other processes
+    #[serde(default)]
+    pub deep_links: bool,
+    // identifier properties displayed as resolved links
+    #[serde(default = "default_identifier_templates")]
+    pub identifier_templates: Vec<IdentifierTemplate>,
+    // edge labels are drawn only from this zoom level
+    #[serde(default = "default_edge_label_min_zoom")]
+    pub edge_label_min_zoom: f32,
+    // no edge labels if graph has more edges, 0 for no limit
+    #[serde(default = "default_edge_label_max_edges")]
+    pub edge_label_max_edges: usize,
+    #[serde(default)]
+    pub edge_labels_selected_only: bool,
+    // more parallel edges between two nodes are drawn as one bundle (if bundling is on)
+    #[serde(default = "default_edge_bundle_threshold")]
+    pub edge_bundle_threshold: usize,
+    // drawing is simplified automatically if the visual graph has more nodes than the thresholds below
+    #[serde(default = "default_true")]
+    pub auto_render_mode: bool,
+    // thresholds in number of visible nodes, 0 - never
+    #[serde(default = "default_simple_shapes_min_nodes")]
+    pub simple_shapes_min_nodes: usize,
+    #[serde(default = "default_hide_labels_min_nodes")]
+    pub hide_labels_min_nodes: usize,
+    #[serde(default = "default_hide_edge_labels_min_nodes")]
+    pub hide_edge_labels_min_nodes: usize,
+    #[serde(default = "default_lod_min_nodes")]
+    pub lod_min_nodes: usize,
+    // number of data changes (load, merge, clean, patch) that can be undone, 0 - no undo
+    #[serde(default = "default_data_undo_levels")]
+    pub data_undo_levels: usize,
+    // operations without triple log (load, clean ...) are undone from a copy of the whole data, up to data_undo_max_nodes
+    #[serde(default = "default_true")]
+    pub data_undo_snapshots: bool,
+    // the data are not copied for undo if they have more nodes, 0 - no limit
+    #[serde(default = "default_data_undo_max_nodes")]
+    pub data_undo_max_nodes: usize,
+    // trees of blank nodes are shown as part of the referencing node (detail and visual graph)
+    #[serde(default)]
+    pub flatten_blank_nodes: bool,
+    // language tags tried in this order if there is no label or value in the display language
+    #[serde(default = "default_language_fallback")]
+    pub language_fallback: Vec<String>,
+    // values in any language are used as the last fallback
+    #[serde(default)]
+    pub language_fallback_any: bool,
+    // keyboard shortcuts of the visual graph
+    #[serde(default)]
+    pub keymap: Keymap,
+    // betweenness centrality is approximated from random source nodes (for large graphs)
+    #[serde(default)]
+    pub betweenness_approximate: bool,
+    #[serde(default = "default_betweenness_samples")]
+    pub betweenness_samples: usize,
+    // tooltip with properties and reference counts for the hovered node in visual graph
+    #[serde(default = "default_true")]
+    pub node_tooltips: bool,
+    // namespace of the iris minted for new nodes created in the graph view
+    #[serde(default = "default_new_node_namespace")]
+    pub new_node_namespace: String,
+    // generated type colors share a hue family for all types of a namespace
+    #[serde(default)]
+    pub color_by_namespace: bool,
 }
 
-#[derive(Serialize, Deserialize, PartialEq, Copy, Clone)]
+/**
+ * Formatting options for turtle export.
+ * The defaults produce stable, review friendly output.
+ */
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TurtleStyle {
+    // One block per subject with ';' and ',' instead of one line per triple
+    pub group_by_subject: bool,
+    // rdf:type is written first as 'a'
+    pub types_first: bool,
+    // label predicates (rdfs:label, skos:prefLabel, ...) follow directly after the types
+    pub labels_first: bool,
+    // other predicates are sorted alphabetically, otherwise they keep the data order
+    pub sort_predicates: bool,
+    // object lists longer than the line width are written one object per line
+    pub line_width: usize,
+    pub use_prefixes: bool,
+}
+
+impl Default for TurtleStyle {
+    fn default() -> Self {
+        Self {
+            group_by_subject: true,
+            types_first: true,
+            labels_first: true,
+            sort_predicates: true,
+            line_width: 100,
+            use_prefixes: true,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 pub enum IriDisplay {
     Full,
     Prefixed,
@@ -40,6 +143,19 @@ pub enum IriDisplay {
     Shorten,
 }
 
+impl IriDisplay {
+    pub fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(IriDisplay::Full),
+            1 => Some(IriDisplay::Prefixed),
+            2 => Some(IriDisplay::Label),
+            3 => Some(IriDisplay::LabelOrShorten),
+            4 => Some(IriDisplay::Shorten),
+            _ => None,
+        }
+    }
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self {
@@ -57,6 +173,30 @@ impl Default for Config {
             short_iri: true,
             max_visible_nodes: 40_000,
             gravity_effect_radius: 250.0,
+            turtle_style: TurtleStyle::default(),
+            deep_links: false,
+            identifier_templates: default_identifier_templates(),
+            edge_label_min_zoom: default_edge_label_min_zoom(),
+            edge_label_max_edges: default_edge_label_max_edges(),
+            edge_labels_selected_only: false,
+            edge_bundle_threshold: default_edge_bundle_threshold(),
+            auto_render_mode: true,
+            simple_shapes_min_nodes: default_simple_shapes_min_nodes(),
+            hide_labels_min_nodes: default_hide_labels_min_nodes(),
+            hide_edge_labels_min_nodes: default_hide_edge_labels_min_nodes(),
+            lod_min_nodes: default_lod_min_nodes(),
+            data_undo_levels: default_data_undo_levels(),
+            data_undo_snapshots: true,
+            data_undo_max_nodes: default_data_undo_max_nodes(),
+            flatten_blank_nodes: false,
+            language_fallback: default_language_fallback(),
+            language_fallback_any: false,
+            keymap: Keymap::default(),
+            betweenness_approximate: false,
+            betweenness_samples: default_betweenness_samples(),
+            node_tooltips: true,
+            new_node_namespace: default_new_node_namespace(),
+            color_by_namespace: false,
         }
     }
 }
@@ -81,7 +221,125 @@ fn default_40_000() -> usize {
     40_000
 }
 
+fn default_edge_label_min_zoom() -> f32 {
+    0.4
+}
+
+fn default_edge_label_max_edges() -> usize {
+    3_000
+}
+
+fn default_edge_bundle_threshold() -> usize {
+    3
+}
+
+fn default_simple_shapes_min_nodes() -> usize {
+    3_000
+}
+
+fn default_hide_labels_min_nodes() -> usize {
+    8_000
+}
+
+fn default_hide_edge_labels_min_nodes() -> usize {
+    1_500
+}
+
+fn default_lod_min_nodes() -> usize {
+    15_000
+}
+
+fn default_data_undo_levels() -> usize {
+    5
+}
+
+fn default_data_undo_max_nodes() -> usize {
+    50_000
+}
+
+fn default_betweenness_samples() -> usize {
+    1_000
+}
+
+fn default_language_fallback() -> Vec<String> {
+    vec!["en".to_string()]
+}
+
+fn default_new_node_namespace() -> String {
+    "http://example.org/".to_string()
+}
+
+/**
+ * Project level values that overwrite the global config.
+ * Not set values are taken from the global config.
+ */
+#[derive(Default, Clone)]
+pub struct ConfigOverrides {
+    pub language_filter: Option<String>,
+    pub suppress_other_language_data: Option<bool>,
+    pub iri_display: Option<IriDisplay>,
+    pub short_iri: Option<bool>,
+    pub m_repulsion_constant: Option<f32>,
+    pub m_attraction_factor: Option<f32>,
+    pub gravity_effect_radius: Option<f32>,
+    pub color_by_namespace: Option<bool>,
+}
+
+impl ConfigOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.language_filter.is_none()
+            && self.suppress_other_language_data.is_none()
+            && self.iri_display.is_none()
+            && self.short_iri.is_none()
+            && self.m_repulsion_constant.is_none()
+            && self.m_attraction_factor.is_none()
+            && self.gravity_effect_radius.is_none()
+            && self.color_by_namespace.is_none()
+    }
+
+    /// Layout forces changed in graph view are stored on the level they come from
+    pub fn store_layout_forces(&mut self, config: &Config, global: &mut Config) {
+        match &mut self.m_repulsion_constant {
+            Some(value) => *value = config.m_repulsion_constant,
+            None => global.m_repulsion_constant = config.m_repulsion_constant,
+        }
+        match &mut self.m_attraction_factor {
+            Some(value) => *value = config.m_attraction_factor,
+            None => global.m_attraction_factor = config.m_attraction_factor,
+        }
+    }
+}
+
 impl Config {
+    pub fn with_overrides(&self, overrides: &ConfigOverrides) -> Config {
+        let mut config = self.clone();
+        if let Some(language_filter) = &overrides.language_filter {
+            config.language_filter = language_filter.clone();
+        }
+        if let Some(suppress_other_language_data) = overrides.suppress_other_language_data {
+            config.suppress_other_language_data = suppress_other_language_data;
+        }
+        if let Some(iri_display) = overrides.iri_display {
+            config.iri_display = iri_display;
+        }
+        if let Some(short_iri) = overrides.short_iri {
+            config.short_iri = short_iri;
+        }
+        if let Some(m_repulsion_constant) = overrides.m_repulsion_constant {
+            config.m_repulsion_constant = m_repulsion_constant;
+        }
+        if let Some(m_attraction_factor) = overrides.m_attraction_factor {
+            config.m_attraction_factor = m_attraction_factor;
+        }
+        if let Some(gravity_effect_radius) = overrides.gravity_effect_radius {
+            config.gravity_effect_radius = gravity_effect_radius;
+        }
+        if let Some(color_by_namespace) = overrides.color_by_namespace {
+            config.color_by_namespace = color_by_namespace;
+        }
+        config
+    }
+
     pub fn language_filter(&self) -> Vec<String> {
         self.language_filter
             .split(',')
@@ -89,4 +347,30 @@ impl Config {
             .map(|s| s.to_string())
             .collect()
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_overrides() {
+        let mut global = Config::default();
+        let mut overrides = ConfigOverrides {
+            language_filter: Some("de".to_string()),
+            m_attraction_factor: Some(2.0),
+            ..Default::default()
+        };
+        let mut config = global.with_overrides(&overrides);
+        assert_eq!("de", config.language_filter);
+        assert_eq!(global.iri_display, config.iri_display);
+        assert_eq!(2.0, config.m_attraction_factor);
+
+        config.m_attraction_factor = 1.5;
+        config.m_repulsion_constant = 3.0;
+        overrides.store_layout_forces(&config, &mut global);
+        assert_eq!(Some(1.5), overrides.m_attraction_factor);
+        assert_eq!(3.0, global.m_repulsion_constant);
+        assert_eq!("en", global.language_filter);
+    }
+}
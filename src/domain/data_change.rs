@@ -0,0 +1,73 @@
+use std::sync::{Arc, Mutex};
+
+/// Change of the loaded data that the dependent structures (type index, meta graph, tables) have to follow
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DataChange {
+    // new nodes, e.g. generated or imported data
+    NodesAdded,
+    // triples merged into the data (patches, SPARQL results, resolved references)
+    TriplesMerged,
+    // type or edge styles changed
+    StyleChanged,
+}
+
+impl DataChange {
+    /**
+     * The type index and everything derived from it must be rebuilt.
+     * The index is always rebuilt from all nodes. Merged triples can change the statistics of already indexed nodes
+     * and the statistics can not be decremented, so even small merges do not update the index incrementally.
+     */
+    pub fn changes_data(&self) -> bool {
+        matches!(self, DataChange::NodesAdded | DataChange::TriplesMerged)
+    }
+}
+
+/**
+ * Changes notified since the last frame.
+ * The app processes them once per frame, so the views do not need to call the updates itself.
+ * The handle can be cloned and moved to background threads.
+ */
+#[derive(Clone, Default)]
+pub struct DataChanges {
+    changes: Arc<Mutex<Vec<DataChange>>>,
+}
+
+impl DataChanges {
+    pub fn notify(&self, change: DataChange) {
+        if let Ok(mut changes) = self.changes.lock()
+            && !changes.contains(&change)
+        {
+            changes.push(change);
+        }
+    }
+
+    pub fn take(&self) -> Vec<DataChange> {
+        match self.changes.lock() {
+            Ok(mut changes) => std::mem::take(&mut *changes),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_data_changes() {
+        let data_changes = DataChanges::default();
+        let background = data_changes.clone();
+        std::thread::spawn(move || {
+            background.notify(DataChange::NodesAdded);
+            background.notify(DataChange::NodesAdded);
+        })
+        .join()
+        .unwrap();
+        data_changes.notify(DataChange::StyleChanged);
+        let changes = data_changes.take();
+        assert_eq!(vec![DataChange::NodesAdded, DataChange::StyleChanged], changes);
+        assert!(changes[0].changes_data());
+        assert!(!changes[1].changes_data());
+        assert!(data_changes.take().is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+
+use crate::IriIndex;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum IssueSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl IssueSeverity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            IssueSeverity::Info => "Info",
+            IssueSeverity::Warning => "Warning",
+            IssueSeverity::Error => "Error",
+        }
+    }
+}
+
+pub struct QualityIssue {
+    // Name of the check that found the issue, used for grouping
+    pub check: &'static str,
+    pub severity: IssueSeverity,
+    pub message: String,
+    pub nodes: Vec<IriIndex>,
+}
+
+/**
+ * Result of data quality checks run on the whole loaded data (not only on visible nodes).
+ */
+#[derive(Default)]
+pub struct DataQualityReport {
+    pub title: String,
+    pub issues: Vec<QualityIssue>,
+}
+
+impl DataQualityReport {
+    pub fn new(title: &str, issues: Vec<QualityIssue>) -> Self {
+        Self {
+            title: title.to_string(),
+            issues,
+        }
+    }
+
+    /// Checks in the order of the first found issue with number of issues
+    pub fn checks(&self) -> Vec<(&'static str, usize)> {
+        let mut checks: Vec<(&'static str, usize)> = Vec::new();
+        for issue in self.issues.iter() {
+            if let Some(check) = checks.iter_mut().find(|(check, _)| *check == issue.check) {
+                check.1 += 1;
+            } else {
+                checks.push((issue.check, 1));
+            }
+        }
+        checks
+    }
+
+    pub fn count(&self, severity: IssueSeverity) -> usize {
+        self.issues.iter().filter(|issue| issue.severity == severity).count()
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "{}", self.title);
+        for (check, count) in self.checks() {
+            let _ = writeln!(out, "{} ({})", check, count);
+            for issue in self.issues.iter().filter(|issue| issue.check == check) {
+                let _ = writeln!(out, "  {}: {}", issue.severity.label(), issue.message);
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,284 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use egui::Color32;
+
+use crate::{
+    IriIndex,
+    domain::{Literal, NObject, NodeData, RdfData, prefix_manager::PrefixManager},
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiffStatus {
+    // node has triples only in the current data
+    Added,
+    // node has triples only in the compared data
+    Removed,
+    Changed,
+}
+
+impl DiffStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffStatus::Added => "added",
+            DiffStatus::Removed => "removed",
+            DiffStatus::Changed => "changed",
+        }
+    }
+
+    pub fn color(&self) -> Color32 {
+        match self {
+            DiffStatus::Added => Color32::from_rgb(80, 180, 80),
+            DiffStatus::Removed => Color32::from_rgb(210, 70, 70),
+            DiffStatus::Changed => Color32::from_rgb(230, 160, 40),
+        }
+    }
+}
+
+pub struct NodeDiff {
+    pub iri: String,
+    // index in the current data, removed nodes can be only referenced there
+    pub node_index: Option<IriIndex>,
+    pub status: DiffStatus,
+    // triples as "predicate object" of the subject
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/**
+ * Differences between the current data and a compared dataset (e.g. the previous release).
+ * Nodes are matched by IRI and compared by their outgoing triples.
+ * Blank nodes can not be matched between datasets, so they are not compared as subjects
+ * and all blank node objects are treated as equal.
+ */
+pub struct DatasetDiff {
+    pub source: String,
+    pub nodes: Vec<NodeDiff>,
+    pub status: HashMap<IriIndex, DiffStatus>,
+    pub added_triples: usize,
+    pub removed_triples: usize,
+    pub status_filter: Option<DiffStatus>,
+    pub iri_filter: String,
+    pub selected: Option<usize>,
+}
+
+impl DatasetDiff {
+    pub fn new(source: &str, current: &RdfData, compared: &RdfData) -> Self {
+        // all triples are written with the prefixes of the current data, so both sides are comparable
+        let display_prefixes = &current.prefix_manager;
+        let current_triples = dataset_triples(current, display_prefixes);
+        let mut compared_triples = dataset_triples(compared, display_prefixes);
+        let mut nodes = Vec::new();
+        let mut added_triples = 0;
+        let mut removed_triples = 0;
+        for (iri, triples) in current_triples.into_iter() {
+            let compared_node_triples = compared_triples.remove(&iri).unwrap_or_default();
+            if triples == compared_node_triples {
+                continue;
+            }
+            let added: Vec<String> = triples.difference(&compared_node_triples).cloned().collect();
+            let removed: Vec<String> = compared_node_triples.difference(&triples).cloned().collect();
+            added_triples += added.len();
+            removed_triples += removed.len();
+            let status = if compared_node_triples.is_empty() {
+                DiffStatus::Added
+            } else {
+                DiffStatus::Changed
+            };
+            nodes.push(NodeDiff {
+                node_index: node_index(current, &iri),
+                iri,
+                status,
+                added,
+                removed,
+            });
+        }
+        for (iri, triples) in compared_triples.into_iter() {
+            removed_triples += triples.len();
+            nodes.push(NodeDiff {
+                node_index: node_index(current, &iri),
+                iri,
+                status: DiffStatus::Removed,
+                added: Vec::new(),
+                removed: triples.into_iter().collect(),
+            });
+        }
+        nodes.sort_by(|a, b| a.iri.cmp(&b.iri));
+        let status = nodes
+            .iter()
+            .filter_map(|node_diff| node_diff.node_index.map(|node_index| (node_index, node_diff.status)))
+            .collect();
+        Self {
+            source: source.to_string(),
+            nodes,
+            status,
+            added_triples,
+            removed_triples,
+            status_filter: None,
+            iri_filter: String::new(),
+            selected: None,
+        }
+    }
+
+    pub fn count(&self, status: DiffStatus) -> usize {
+        self.nodes.iter().filter(|node_diff| node_diff.status == status).count()
+    }
+
+    pub fn is_visible(&self, node_diff: &NodeDiff) -> bool {
+        self.status_filter.is_none_or(|status| node_diff.status == status)
+            && (self.iri_filter.is_empty() || node_diff.iri.contains(&self.iri_filter))
+    }
+
+    pub fn node_status(&self, node_index: IriIndex) -> Option<DiffStatus> {
+        self.status.get(&node_index).copied()
+    }
+}
+
+fn node_index(rdf_data: &RdfData, iri: &str) -> Option<IriIndex> {
+    rdf_data
+        .node_data
+        .get_node_index(&rdf_data.prefix_manager.get_prefixed(iri))
+        .or_else(|| rdf_data.node_data.get_node_index(iri))
+}
+
+// Triples of all named subjects by full IRI
+fn dataset_triples(rdf_data: &RdfData, display_prefixes: &PrefixManager) -> BTreeMap<String, BTreeSet<String>> {
+    let mut triples = BTreeMap::new();
+    for (iri, node) in rdf_data.node_data.iter() {
+        if node.is_blank_node || !node.has_subject {
+            continue;
+        }
+        let node_triples = node_triples(node, rdf_data, display_prefixes);
+        if !node_triples.is_empty() {
+            triples.insert(full_iri(iri, &rdf_data.prefix_manager), node_triples);
+        }
+    }
+    triples
+}
+
+fn node_triples(node: &NObject, rdf_data: &RdfData, display_prefixes: &PrefixManager) -> BTreeSet<String> {
+    let node_data = &rdf_data.node_data;
+    let prefix_manager = &rdf_data.prefix_manager;
+    let term = |iri: &str| iri_term(&full_iri(iri, prefix_manager), display_prefixes);
+    let mut triples = BTreeSet::new();
+    for type_index in node.types.iter() {
+        if let Some(type_iri) = node_data.get_type(*type_index) {
+            triples.insert(format!("a {}", term(type_iri)));
+        }
+    }
+    for (predicate_index, literal) in node.properties.iter() {
+        if let Some(predicate_iri) = node_data.get_predicate(*predicate_index) {
+            triples.insert(format!(
+                "{} {}",
+                term(predicate_iri),
+                literal_term(literal, node_data, &term)
+            ));
+        }
+    }
+    for (predicate_index, reference_index) in node.references.iter() {
+        let (Some(predicate_iri), Some((reference_iri, reference))) = (
+            node_data.get_predicate(*predicate_index),
+            node_data.get_node_by_index(*reference_index),
+        ) else {
+            continue;
+        };
+        let object = if reference.is_blank_node {
+            "[]".to_string()
+        } else {
+            term(reference_iri)
+        };
+        triples.insert(format!("{} {}", term(predicate_iri), object));
+    }
+    triples
+}
+
+fn literal_term(literal: &Literal, node_data: &NodeData, term: &dyn Fn(&str) -> String) -> String {
+    let value = literal
+        .as_str_ref(&node_data.indexers)
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    match literal {
+        Literal::LangString(language_index, _) => {
+            format!("\"{}\"@{}", value, node_data.get_language(*language_index).unwrap_or_default())
+        }
+        Literal::TypedString(data_type_index, _) => {
+            match node_data.indexers.datatype_indexer.index_to_str(*data_type_index as IriIndex) {
+                Some(data_type) => format!("\"{}\"^^{}", value, term(data_type)),
+                None => format!("\"{}\"", value),
+            }
+        }
+        _ => format!("\"{}\"", value),
+    }
+}
+
+// The iris in the indexers are stored already prefixed if the prefix is known
+fn full_iri(iri: &str, prefix_manager: &PrefixManager) -> String {
+    match prefix_manager.get_full_opt(iri) {
+        Some(full_iri) => full_iri.into(),
+        None => iri.to_string(),
+    }
+}
+
+fn iri_term(full_iri: &str, display_prefixes: &PrefixManager) -> String {
+    match display_prefixes.get_prefixed_opt(full_iri) {
+        Some(prefixed) => prefixed,
+        None => format!("<{}>", full_iri),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integration::rdf_patch::RdfPatch;
+
+    fn rdf_data(patch: &str, prefix: &str) -> RdfData {
+        let mut rdf_data = RdfData::empty();
+        rdf_data.prefix_manager.add_prefix(prefix, "http://example.org/");
+        RdfPatch::parse_rdf_patch(patch).apply(&mut rdf_data, &[]);
+        rdf_data
+    }
+
+    #[test]
+    fn test_dataset_diff() {
+        let current = rdf_data(
+            r#"
+A <http://example.org/a> a <http://example.org/Person> .
+A <http://example.org/a> <http://example.org/name> "Anna" .
+A <http://example.org/b> <http://example.org/name> "Bob" .
+A <http://example.org/b> <http://example.org/knows> <http://example.org/a> .
+A <http://example.org/d> <http://example.org/name> "Dora"@en .
+"#,
+            "ex",
+        );
+        // the compared data use another prefix for the same namespace
+        let compared = rdf_data(
+            r#"
+A <http://example.org/a> a <http://example.org/Person> .
+A <http://example.org/a> <http://example.org/name> "Anna" .
+A <http://example.org/b> <http://example.org/name> "Robert" .
+A <http://example.org/c> <http://example.org/name> "Cecil" .
+A <http://example.org/d> <http://example.org/name> "Dora"@en .
+"#,
+            "other",
+        );
+        let diff = DatasetDiff::new("old.ttl", &current, &compared);
+        assert_eq!(2, diff.nodes.len());
+        let b = &diff.nodes[0];
+        assert_eq!("http://example.org/b", b.iri);
+        assert_eq!(DiffStatus::Changed, b.status);
+        assert_eq!(vec!["ex:knows ex:a", "ex:name \"Bob\""], b.added);
+        assert_eq!(vec!["ex:name \"Robert\""], b.removed);
+        let c = &diff.nodes[1];
+        assert_eq!(DiffStatus::Removed, c.status);
+        assert_eq!(None, c.node_index);
+        assert_eq!(2, diff.added_triples);
+        assert_eq!(2, diff.removed_triples);
+        let b_index = current.node_data.get_node_index("ex:b").unwrap();
+        assert_eq!(Some(DiffStatus::Changed), diff.node_status(b_index));
+
+        let reverse = DatasetDiff::new("new.ttl", &compared, &current);
+        assert_eq!(1, reverse.count(DiffStatus::Added));
+        assert_eq!(1, reverse.count(DiffStatus::Changed));
+        assert_eq!(0, reverse.count(DiffStatus::Removed));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::{
+    IriIndex,
+    domain::{LangIndex, NObject, NodeData, prefix_manager::PrefixManager},
+    uistate::ImportFormat,
+};
+
+const DCAT: &str = "http://www.w3.org/ns/dcat#";
+const DCT: &str = "http://purl.org/dc/terms/";
+
+pub struct DcatDistribution {
+    pub node_index: IriIndex,
+    pub title: Option<String>,
+    pub format: Option<String>,
+    pub url: Option<String>,
+    // None if the distribution is not in a RDF format
+    pub import_format: Option<ImportFormat>,
+}
+
+pub struct DcatDataset {
+    pub node_index: IriIndex,
+    pub title: String,
+    pub description: Option<String>,
+    pub catalog: Option<usize>,
+    pub distributions: Vec<DcatDistribution>,
+}
+
+pub struct DcatCatalogEntry {
+    pub node_index: IriIndex,
+    pub title: String,
+}
+
+/**
+ * Catalogs, datasets and distributions described by DCAT (data portals publish their metadata this way).
+ * Datasets are nodes of type dcat:Dataset or nodes with dcat:distribution.
+ */
+#[derive(Default)]
+pub struct DcatCatalog {
+    pub catalogs: Vec<DcatCatalogEntry>,
+    pub datasets: Vec<DcatDataset>,
+}
+
+struct DcatPredicates {
+    dataset: Option<IriIndex>,
+    distribution: Option<IriIndex>,
+    title: Option<IriIndex>,
+    description: Option<IriIndex>,
+    format: Option<IriIndex>,
+    media_type: Option<IriIndex>,
+    download_url: Option<IriIndex>,
+    access_url: Option<IriIndex>,
+}
+
+impl DcatCatalog {
+    pub fn new(node_data: &NodeData, prefix_manager: &PrefixManager, language_index: LangIndex) -> Self {
+        let predicate = |namespace: &str, name: &str| {
+            node_data.find_predicate_index(prefix_manager, &format!("{}{}", namespace, name))
+        };
+        let predicates = DcatPredicates {
+            dataset: predicate(DCAT, "dataset"),
+            distribution: predicate(DCAT, "distribution"),
+            title: predicate(DCT, "title"),
+            description: predicate(DCT, "description"),
+            format: predicate(DCT, "format"),
+            media_type: predicate(DCAT, "mediaType"),
+            download_url: predicate(DCAT, "downloadURL"),
+            access_url: predicate(DCAT, "accessURL"),
+        };
+        let dataset_type = node_data.find_type_index(prefix_manager, &format!("{}Dataset", DCAT));
+        let catalog_type = node_data.find_type_index(prefix_manager, &format!("{}Catalog", DCAT));
+        let mut dcat_catalog = DcatCatalog::default();
+        let mut dataset_catalogs: Vec<(IriIndex, usize)> = Vec::new();
+        for (node_index, (iri, node)) in node_data.iter().enumerate() {
+            let is_catalog = catalog_type.is_some_and(|catalog_type| node.types.contains(&catalog_type));
+            if !is_catalog {
+                continue;
+            }
+            let title = value(node_data, prefix_manager, node, predicates.title, language_index)
+                .unwrap_or_else(|| iri.to_string());
+            for dataset_index in references(node, predicates.dataset) {
+                dataset_catalogs.push((dataset_index, dcat_catalog.catalogs.len()));
+            }
+            dcat_catalog.catalogs.push(DcatCatalogEntry {
+                node_index: node_index as IriIndex,
+                title,
+            });
+        }
+        for (node_index, (iri, node)) in node_data.iter().enumerate() {
+            let node_index = node_index as IriIndex;
+            let is_dataset = dataset_type.is_some_and(|dataset_type| node.types.contains(&dataset_type))
+                || references(node, predicates.distribution).next().is_some();
+            if !is_dataset {
+                continue;
+            }
+            let distributions = references(node, predicates.distribution)
+                .filter_map(|distribution_index| {
+                    node_data
+                        .get_node_by_index(distribution_index)
+                        .map(|(_, distribution)| {
+                            distribution_entry(
+                                node_data,
+                                prefix_manager,
+                                &predicates,
+                                distribution_index,
+                                distribution,
+                                language_index,
+                            )
+                        })
+                })
+                .collect();
+            dcat_catalog.datasets.push(DcatDataset {
+                node_index,
+                title: value(node_data, prefix_manager, node, predicates.title, language_index)
+                    .unwrap_or_else(|| iri.to_string()),
+                description: value(node_data, prefix_manager, node, predicates.description, language_index),
+                catalog: dataset_catalogs
+                    .iter()
+                    .find(|(dataset_index, _)| *dataset_index == node_index)
+                    .map(|(_, catalog)| *catalog),
+                distributions,
+            });
+        }
+        dcat_catalog.datasets.sort_by_key(|dataset| dataset.title.to_lowercase());
+        dcat_catalog
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.datasets.is_empty()
+    }
+}
+
+fn distribution_entry(
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    predicates: &DcatPredicates,
+    node_index: IriIndex,
+    distribution: &NObject,
+    language_index: LangIndex,
+) -> DcatDistribution {
+    let url = value(node_data, prefix_manager, distribution, predicates.download_url, language_index)
+        .or_else(|| value(node_data, prefix_manager, distribution, predicates.access_url, language_index));
+    let format = value(node_data, prefix_manager, distribution, predicates.media_type, language_index)
+        .or_else(|| value(node_data, prefix_manager, distribution, predicates.format, language_index))
+        .map(|format| short_format(&format));
+    DcatDistribution {
+        node_index,
+        title: value(node_data, prefix_manager, distribution, predicates.title, language_index),
+        import_format: rdf_import_format(format.as_deref(), url.as_deref()),
+        format,
+        url,
+    }
+}
+
+fn references(node: &NObject, predicate: Option<IriIndex>) -> impl Iterator<Item = IriIndex> + '_ {
+    node.references
+        .iter()
+        .filter(move |(reference_predicate, _)| Some(*reference_predicate) == predicate)
+        .map(|(_, target_index)| *target_index)
+}
+
+/// Literal value in the display language or the full IRI of the referenced node
+fn value(
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    node: &NObject,
+    predicate: Option<IriIndex>,
+    language_index: LangIndex,
+) -> Option<String> {
+    let predicate = predicate?;
+    let literal = node.get_property(predicate, language_index, &node_data.indexers.language_fallback).or_else(|| {
+        node.properties
+            .iter()
+            .find(|(property_predicate, _)| *property_predicate == predicate)
+            .map(|(_, literal)| literal)
+    });
+    if let Some(literal) = literal {
+        return Some(literal.as_str_ref(&node_data.indexers).to_string());
+    }
+    let target_index = references(node, Some(predicate)).next()?;
+    let (iri, _) = node_data.get_node_by_index(target_index)?;
+    Some(
+        prefix_manager
+            .get_full_opt(iri)
+            .map(|full_iri| full_iri.to_string())
+            .unwrap_or_else(|| iri.to_string()),
+    )
+}
+
+/// Media type or file type from the IRIs of IANA or the EU file type vocabulary
+fn short_format(format: &str) -> String {
+    if let Some((_, media_type)) = format.split_once("/media-types/") {
+        return media_type.to_string();
+    }
+    if let Some((_, file_type)) = format.split_once("/file-type/") {
+        return file_type.to_string();
+    }
+    format.to_string()
+}
+
+pub fn rdf_import_format(format: Option<&str>, url: Option<&str>) -> Option<ImportFormat> {
+    if let Some(format) = format {
+        let format = format.to_lowercase();
+        let import_format = match format.as_str() {
+            "text/turtle" | "turtle" | "ttl" => Some(ImportFormat::Turtle),
+            "application/rdf+xml" | "rdf_xml" | "rdf/xml" | "rdf" => Some(ImportFormat::RdfXml),
+            "application/n-triples" | "rdf_n_triples" | "n-triples" | "nt" => Some(ImportFormat::NTriples),
+            "application/ld+json" | "json_ld" | "json-ld" | "jsonld" => Some(ImportFormat::JsonLd),
+            _ => None,
+        };
+        if import_format.is_some() {
+            return import_format;
+        }
+    }
+    let url = url?.to_lowercase();
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    if path.ends_with(".ttl") {
+        Some(ImportFormat::Turtle)
+    } else if path.ends_with(".rdf") || path.ends_with(".owl") {
+        Some(ImportFormat::RdfXml)
+    } else if path.ends_with(".nt") {
+        Some(ImportFormat::NTriples)
+    } else if path.ends_with(".jsonld") {
+        Some(ImportFormat::JsonLd)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_dcat_catalog() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA dcat: <http://www.w3.org/ns/dcat#> .
+PA dct: <http://purl.org/dc/terms/> .
+PA ex: <http://example.org/> .
+A ex:catalog a dcat:Catalog .
+A ex:catalog dct:title "Open Data" .
+A ex:catalog dcat:dataset ex:trees .
+A ex:trees a dcat:Dataset .
+A ex:trees dct:title "Trees"@en .
+A ex:trees dcat:distribution ex:trees_ttl .
+A ex:trees dcat:distribution ex:trees_csv .
+A ex:trees_ttl dcat:downloadURL <http://example.org/trees.ttl> .
+A ex:trees_ttl dcat:mediaType <https://www.iana.org/assignments/media-types/text/turtle> .
+A ex:trees_csv dcat:downloadURL <http://example.org/trees.csv> .
+A ex:trees_csv dct:format "CSV" .
+A ex:bridges dcat:distribution ex:bridges_rdf .
+A ex:bridges_rdf dcat:accessURL <http://example.org/bridges.rdf?version=2> .
+"#,
+        );
+        let dcat_catalog = DcatCatalog::new(&rdf_data.node_data, &rdf_data.prefix_manager, 0);
+        assert_eq!(1, dcat_catalog.catalogs.len());
+        assert_eq!("Open Data", dcat_catalog.catalogs[0].title);
+        assert_eq!(2, dcat_catalog.datasets.len());
+        let bridges = &dcat_catalog.datasets[0];
+        assert_eq!("http://example.org/bridges", bridges.title);
+        assert_eq!(None, bridges.catalog);
+        assert!(matches!(bridges.distributions[0].import_format, Some(ImportFormat::RdfXml)));
+        let trees = &dcat_catalog.datasets[1];
+        assert_eq!("Trees", trees.title);
+        assert_eq!(Some(0), trees.catalog);
+        let ttl = trees
+            .distributions
+            .iter()
+            .find(|distribution| distribution.format.as_deref() == Some("text/turtle"))
+            .unwrap();
+        assert_eq!(Some("http://example.org/trees.ttl"), ttl.url.as_deref());
+        assert!(matches!(ttl.import_format, Some(ImportFormat::Turtle)));
+        let csv = trees
+            .distributions
+            .iter()
+            .find(|distribution| distribution.format.as_deref() == Some("CSV"))
+            .unwrap();
+        assert!(csv.import_format.is_none());
+    }
+}
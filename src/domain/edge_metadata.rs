@@ -0,0 +1,331 @@
+use std::collections::{BTreeSet, HashMap};
+
+use egui::Color32;
+use oxrdf::vocab::rdf;
+
+use crate::{
+    IriIndex,
+    domain::{NodeData, graph_styles::EdgeStyle, prefix_manager::PrefixManager},
+};
+
+/// (subject, predicate, object) of an edge
+pub type EdgeKey = (IriIndex, IriIndex, IriIndex);
+
+const MIN_EDGE_WIDTH: f32 = 1.0;
+const MAX_EDGE_WIDTH: f32 = 8.0;
+
+/**
+ * Statements about edges given by RDF reification (rdf:Statement with rdf:subject, rdf:predicate and rdf:object).
+ * The properties of the statement nodes are the edge metadata (weights, validity intervals).
+ */
+#[derive(Default)]
+pub struct EdgeMetadata {
+    statements: HashMap<EdgeKey, Vec<IriIndex>>,
+}
+
+impl EdgeMetadata {
+    pub fn new(node_data: &NodeData, prefix_manager: &PrefixManager) -> Self {
+        let mut statements: HashMap<EdgeKey, Vec<IriIndex>> = HashMap::new();
+        let find = |iri: &str| node_data.find_predicate_index(prefix_manager, iri);
+        let (Some(rdf_subject), Some(rdf_predicate), Some(rdf_object)) =
+            (find(rdf::SUBJECT.as_str()), find(rdf::PREDICATE.as_str()), find(rdf::OBJECT.as_str()))
+        else {
+            return Self { statements };
+        };
+        for (statement_index, (_, node)) in node_data.iter().enumerate() {
+            let target = |predicate: IriIndex| {
+                node.references
+                    .iter()
+                    .find(|(reference_predicate, _)| *reference_predicate == predicate)
+                    .map(|(_, target_index)| *target_index)
+            };
+            let (Some(subject), Some(predicate_node), Some(object)) =
+                (target(rdf_subject), target(rdf_predicate), target(rdf_object))
+            else {
+                continue;
+            };
+            // the predicate is referenced as node, nodes are stored with full iri
+            let predicate = node_data
+                .get_node_by_index(predicate_node)
+                .and_then(|(predicate_iri, _)| find(predicate_iri));
+            if let Some(predicate) = predicate {
+                statements
+                    .entry((subject, predicate, object))
+                    .or_default()
+                    .push(statement_index as IriIndex);
+            }
+        }
+        Self { statements }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.statements.len()
+    }
+
+    /// Data properties of the statements with (predicate, has numeric values, has date values)
+    pub fn metadata_predicates(&self, node_data: &NodeData) -> Vec<(IriIndex, bool, bool)> {
+        let mut numeric: BTreeSet<IriIndex> = BTreeSet::new();
+        let mut dates: BTreeSet<IriIndex> = BTreeSet::new();
+        let mut all: BTreeSet<IriIndex> = BTreeSet::new();
+        for statement_index in self.statements.values().flatten() {
+            if let Some((_, node)) = node_data.get_node_by_index(*statement_index) {
+                for (predicate_index, literal) in node.properties.iter() {
+                    let value = literal.as_str_ref(&node_data.indexers);
+                    all.insert(*predicate_index);
+                    if parse_number(value).is_some() {
+                        numeric.insert(*predicate_index);
+                    } else if parse_date_days(value).is_some() {
+                        dates.insert(*predicate_index);
+                    }
+                }
+            }
+        }
+        all.into_iter()
+            .map(|predicate_index| {
+                (
+                    predicate_index,
+                    numeric.contains(&predicate_index),
+                    dates.contains(&predicate_index),
+                )
+            })
+            .collect()
+    }
+
+    /// First parsable value of the predicate for each edge
+    pub fn values<T>(
+        &self,
+        node_data: &NodeData,
+        predicate: IriIndex,
+        parse: impl Fn(&str) -> Option<T>,
+    ) -> HashMap<EdgeKey, T> {
+        let mut values: HashMap<EdgeKey, T> = HashMap::new();
+        for (edge_key, statement_nodes) in self.statements.iter() {
+            let value = statement_nodes
+                .iter()
+                .filter_map(|statement_index| node_data.get_node_by_index(*statement_index))
+                .flat_map(|(_, node)| node.properties.iter())
+                .filter(|(predicate_index, _)| *predicate_index == predicate)
+                .find_map(|(_, literal)| parse(literal.as_str_ref(&node_data.indexers)));
+            if let Some(value) = value {
+                values.insert(*edge_key, value);
+            }
+        }
+        values
+    }
+}
+
+pub fn parse_number(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+/// Days since 1970-01-01 for xsd:date, xsd:dateTime (time is ignored) and xsd:gYear values
+pub fn parse_date_days(value: &str) -> Option<i64> {
+    let value = value.trim();
+    let (sign, value) = match value.strip_prefix('-') {
+        Some(value) => (-1, value),
+        None => (1, value),
+    };
+    let mut parts = value.split(['-', 'T']);
+    let year_part = parts.next()?;
+    if year_part.len() < 4 || !year_part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let year: i64 = sign * year_part.parse::<i64>().ok()?;
+    let month: u32 = match parts.next() {
+        Some(month) => month.parse().ok().filter(|month| (1..=12).contains(month))?,
+        None => 1,
+    };
+    let day: u32 = match parts.next() {
+        Some(day) => day.get(0..2)?.parse().ok().filter(|day| (1..=31).contains(day))?,
+        None => 1,
+    };
+    Some(days_from_civil(year, month, day))
+}
+
+// Howard Hinnant's algorithm for the proleptic gregorian calendar
+pub fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+pub fn days_to_date(days: i64) -> String {
+    let (year, month, day) = civil_from_days(days);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// (year, month, day) of the days since 1970-01-01
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month as u32, day as u32)
+}
+
+/// Mapping of edge metadata to the edge display in the visual graph
+#[derive(Default)]
+pub struct EdgeMetadataStyle {
+    pub metadata: EdgeMetadata,
+    pub weight_predicate: Option<IriIndex>,
+    pub weight_to_width: bool,
+    pub weight_to_color: bool,
+    pub weights: HashMap<EdgeKey, f64>,
+    pub weight_range: (f64, f64),
+    pub date_predicate: Option<IriIndex>,
+    pub dates: HashMap<EdgeKey, i64>,
+    pub date_range: (i64, i64),
+    // only edges with a date in this interval are shown, edges without date are not filtered
+    pub date_filter: (i64, i64),
+}
+
+pub enum EdgeDisplay {
+    Default,
+    Hidden,
+    Styled(EdgeStyle),
+}
+
+impl EdgeMetadataStyle {
+    pub fn new(node_data: &NodeData, prefix_manager: &PrefixManager) -> Self {
+        Self {
+            metadata: EdgeMetadata::new(node_data, prefix_manager),
+            weight_to_width: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn update_weights(&mut self, node_data: &NodeData) {
+        self.weights = match self.weight_predicate {
+            Some(predicate) => self.metadata.values(node_data, predicate, parse_number),
+            None => HashMap::new(),
+        };
+        self.weight_range = self.weights.values().fold((f64::MAX, f64::MIN), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        });
+    }
+
+    pub fn update_dates(&mut self, node_data: &NodeData) {
+        self.dates = match self.date_predicate {
+            Some(predicate) => self.metadata.values(node_data, predicate, parse_date_days),
+            None => HashMap::new(),
+        };
+        self.date_range = self.dates.values().fold((i64::MAX, i64::MIN), |(min, max), value| {
+            (min.min(*value), max.max(*value))
+        });
+        self.date_filter = self.date_range;
+    }
+
+    pub fn is_active(&self) -> bool {
+        !self.weights.is_empty() || !self.dates.is_empty()
+    }
+
+    pub fn edge_display(&self, edge_key: &EdgeKey, edge_style: &EdgeStyle) -> EdgeDisplay {
+        if let Some(date) = self.dates.get(edge_key)
+            && (*date < self.date_filter.0 || *date > self.date_filter.1)
+        {
+            return EdgeDisplay::Hidden;
+        }
+        let Some(weight) = self.weights.get(edge_key) else {
+            return EdgeDisplay::Default;
+        };
+        if !self.weight_to_width && !self.weight_to_color {
+            return EdgeDisplay::Default;
+        }
+        let (min, max) = self.weight_range;
+        let normalized = if max > min { ((weight - min) / (max - min)) as f32 } else { 1.0 };
+        let mut style = edge_style.clone();
+        if self.weight_to_width {
+            style.width = MIN_EDGE_WIDTH + normalized * (MAX_EDGE_WIDTH - MIN_EDGE_WIDTH);
+        }
+        if self.weight_to_color {
+            // from blue (low) to red (high)
+            style.color = Color32::from_rgb((normalized * 220.0) as u8, 60, ((1.0 - normalized) * 220.0) as u8);
+        }
+        EdgeDisplay::Styled(style)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_date_days() {
+        assert_eq!(Some(0), parse_date_days("1970-01-01"));
+        assert_eq!(Some(31), parse_date_days("1970-02-01T10:00:00Z"));
+        assert_eq!(Some(-365), parse_date_days("1969"));
+        assert_eq!(None, parse_date_days("12.5"));
+        assert_eq!(None, parse_date_days("2020-13-01"));
+        for date in ["2024-02-29", "1999-12-31", "0100-03-01"] {
+            assert_eq!(date, days_to_date(parse_date_days(date).unwrap()));
+        }
+    }
+
+    #[test]
+    fn test_edge_metadata() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+PA ex: <http://example.org/> .
+A ex:a ex:knows ex:b .
+A ex:a ex:knows ex:c .
+A ex:s1 a rdf:Statement .
+A ex:s1 rdf:subject ex:a .
+A ex:s1 rdf:predicate ex:knows .
+A ex:s1 rdf:object ex:b .
+A ex:s1 ex:weight "2.5" .
+A ex:s1 ex:since "2020-01-01" .
+A ex:s2 rdf:subject ex:a .
+A ex:s2 rdf:predicate ex:knows .
+A ex:s2 rdf:object ex:c .
+A ex:s2 ex:weight "10" .
+A ex:s2 ex:since "2010-05-01" .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let prefix_manager = &rdf_data.prefix_manager;
+        let mut style = EdgeMetadataStyle::new(node_data, prefix_manager);
+        assert_eq!(2, style.metadata.len());
+        let weight = node_data.find_predicate_index(prefix_manager, "http://example.org/weight").unwrap();
+        let since = node_data.find_predicate_index(prefix_manager, "http://example.org/since").unwrap();
+        assert_eq!(
+            vec![(weight, true, false), (since, false, true)],
+            style.metadata.metadata_predicates(node_data)
+        );
+        style.weight_predicate = Some(weight);
+        style.update_weights(node_data);
+        assert_eq!((2.5, 10.0), style.weight_range);
+        style.date_predicate = Some(since);
+        style.update_dates(node_data);
+        assert!(style.is_active());
+        let node = |name: &str| node_data.get_node_index(&format!("http://example.org/{}", name)).unwrap();
+        let knows = node_data.find_predicate_index(prefix_manager, "http://example.org/knows").unwrap();
+        let edge_ab = (node("a"), knows, node("b"));
+        let edge_ac = (node("a"), knows, node("c"));
+        match style.edge_display(&edge_ac, &EdgeStyle::default()) {
+            EdgeDisplay::Styled(edge_style) => assert_eq!(MAX_EDGE_WIDTH, edge_style.width),
+            _ => panic!("edge should be styled"),
+        }
+        style.date_filter.0 = parse_date_days("2015").unwrap();
+        assert!(matches!(style.edge_display(&edge_ac, &EdgeStyle::default()), EdgeDisplay::Hidden));
+        match style.edge_display(&edge_ab, &EdgeStyle::default()) {
+            EdgeDisplay::Styled(edge_style) => assert_eq!(MIN_EDGE_WIDTH, edge_style.width),
+            _ => panic!("edge should be styled"),
+        }
+    }
+}
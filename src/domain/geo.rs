@@ -0,0 +1,222 @@
+use crate::{
+    IriIndex,
+    domain::{NObject, NodeData, prefix_manager::PrefixManager},
+};
+
+const LAT_PREDICATES: [&str; 3] = [
+    "http://www.w3.org/2003/01/geo/wgs84_pos#lat",
+    "http://schema.org/latitude",
+    "https://schema.org/latitude",
+];
+const LONG_PREDICATES: [&str; 3] = [
+    "http://www.w3.org/2003/01/geo/wgs84_pos#long",
+    "http://schema.org/longitude",
+    "https://schema.org/longitude",
+];
+const WKT_PREDICATES: [&str; 2] = [
+    "http://www.opengis.net/ont/geosparql#asWKT",
+    // wikidata coordinate location
+    "http://www.wikidata.org/prop/direct/P625",
+];
+// the WKT literal is often placed on a separate geometry node
+const GEOMETRY_PREDICATES: [&str; 2] = [
+    "http://www.opengis.net/ont/geosparql#hasGeometry",
+    "http://www.opengis.net/ont/geosparql#hasDefaultGeometry",
+];
+// the only common CRS with latitude first, WKT literals without CRS use CRS84 (longitude first)
+const CRS_EPSG_4326: &str = "http://www.opengis.net/def/crs/EPSG/0/4326";
+
+/// Node position in degrees (WGS84)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct GeoPoint {
+    pub node: IriIndex,
+    pub lon: f64,
+    pub lat: f64,
+}
+
+/**
+ * All nodes with coordinates.
+ * The coordinates are read from geo:lat/geo:long (or schema.org) literals or from WKT literals.
+ * Geometries other than points are placed at the mean of their vertices.
+ */
+pub struct GeoIndex {
+    pub points: Vec<GeoPoint>,
+}
+
+struct GeoPredicates {
+    lat: Vec<IriIndex>,
+    long: Vec<IriIndex>,
+    wkt: Vec<IriIndex>,
+    geometry: Vec<IriIndex>,
+}
+
+impl GeoIndex {
+    pub fn new(node_data: &NodeData, prefix_manager: &PrefixManager) -> Self {
+        let find = |iris: &[&str]| -> Vec<IriIndex> {
+            iris.iter()
+                .filter_map(|iri| node_data.find_predicate_index(prefix_manager, iri))
+                .collect()
+        };
+        let predicates = GeoPredicates {
+            lat: find(&LAT_PREDICATES),
+            long: find(&LONG_PREDICATES),
+            wkt: find(&WKT_PREDICATES),
+            geometry: find(&GEOMETRY_PREDICATES),
+        };
+        let mut points = Vec::new();
+        if predicates.wkt.is_empty() && (predicates.lat.is_empty() || predicates.long.is_empty()) {
+            return Self { points };
+        }
+        for (node_index, (_iri, node)) in node_data.iter().enumerate() {
+            if let Some((lon, lat)) = node_position(node_data, node, &predicates) {
+                points.push(GeoPoint {
+                    node: node_index as IriIndex,
+                    lon,
+                    lat,
+                });
+            }
+        }
+        Self { points }
+    }
+
+    /// (min_lon, min_lat, max_lon, max_lat) of the points that pass the filter
+    pub fn bounds(&self, filter: impl Fn(&GeoPoint) -> bool) -> Option<(f64, f64, f64, f64)> {
+        self.points.iter().filter(|point| filter(point)).fold(None, |bounds, point| {
+            Some(match bounds {
+                None => (point.lon, point.lat, point.lon, point.lat),
+                Some((min_lon, min_lat, max_lon, max_lat)) => (
+                    min_lon.min(point.lon),
+                    min_lat.min(point.lat),
+                    max_lon.max(point.lon),
+                    max_lat.max(point.lat),
+                ),
+            })
+        })
+    }
+}
+
+fn node_position(node_data: &NodeData, node: &NObject, predicates: &GeoPredicates) -> Option<(f64, f64)> {
+    let literal = |predicate_indexes: &[IriIndex]| {
+        node.properties
+            .iter()
+            .find(|(predicate, _)| predicate_indexes.contains(predicate))
+            .map(|(_, literal)| literal.as_str_ref(&node_data.indexers))
+    };
+    if let Some(lat) = literal(&predicates.lat).and_then(parse_degrees)
+        && let Some(lon) = literal(&predicates.long).and_then(parse_degrees)
+        && is_valid(lon, lat)
+    {
+        return Some((lon, lat));
+    }
+    if let Some(position) = literal(&predicates.wkt).and_then(parse_wkt_position) {
+        return Some(position);
+    }
+    node.references
+        .iter()
+        .filter(|(predicate, _)| predicates.geometry.contains(predicate))
+        .filter_map(|(_, geometry)| node_data.get_node_by_index(*geometry))
+        .find_map(|(_, geometry)| {
+            geometry
+                .properties
+                .iter()
+                .find(|(predicate, _)| predicates.wkt.contains(predicate))
+                .and_then(|(_, literal)| parse_wkt_position(literal.as_str_ref(&node_data.indexers)))
+        })
+}
+
+fn parse_degrees(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|value| value.is_finite())
+}
+
+fn is_valid(lon: f64, lat: f64) -> bool {
+    (-180.0..=180.0).contains(&lon) && (-90.0..=90.0).contains(&lat)
+}
+
+/**
+ * Position (longitude, latitude) of a WKT literal, e.g. "POINT(13.4 52.5)".
+ * An optional CRS IRI in angle brackets could precede the geometry.
+ * For lines and polygons the mean of all vertices is returned.
+ */
+pub fn parse_wkt_position(wkt: &str) -> Option<(f64, f64)> {
+    let mut wkt = wkt.trim();
+    let mut lat_first = false;
+    if wkt.starts_with('<') {
+        let end = wkt.find('>')?;
+        lat_first = &wkt[1..end] == CRS_EPSG_4326;
+        wkt = wkt[end + 1..].trim_start();
+    }
+    let open = wkt.find('(')?;
+    if wkt[..open].trim().is_empty() {
+        return None;
+    }
+    let close = wkt.rfind(')')?;
+    if close < open {
+        return None;
+    }
+    let mut sum_lon = 0.0;
+    let mut sum_lat = 0.0;
+    let mut count = 0;
+    for vertex in wkt[open + 1..close].split(',') {
+        let mut coordinates = vertex
+            .split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+            .filter(|part| !part.is_empty());
+        let first = parse_degrees(coordinates.next()?)?;
+        let second = parse_degrees(coordinates.next()?)?;
+        let (lon, lat) = if lat_first { (second, first) } else { (first, second) };
+        sum_lon += lon;
+        sum_lat += lat;
+        count += 1;
+    }
+    if count == 0 {
+        return None;
+    }
+    let position = (sum_lon / count as f64, sum_lat / count as f64);
+    is_valid(position.0, position.1).then_some(position)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RdfData;
+
+    #[test]
+    fn test_parse_wkt_position() {
+        assert_eq!(Some((13.4, 52.5)), parse_wkt_position("POINT(13.4 52.5)"));
+        assert_eq!(Some((13.4, 52.5)), parse_wkt_position("Point Z (13.4 52.5 34)"));
+        assert_eq!(
+            Some((13.4, 52.5)),
+            parse_wkt_position("<http://www.opengis.net/def/crs/OGC/1.3/CRS84> POINT(13.4 52.5)")
+        );
+        assert_eq!(
+            Some((13.4, 52.5)),
+            parse_wkt_position("<http://www.opengis.net/def/crs/EPSG/0/4326> POINT(52.5 13.4)")
+        );
+        assert_eq!(Some((1.0, 1.0)), parse_wkt_position("POLYGON((0 0, 2 0, 2 2, 0 2))"));
+        assert_eq!(None, parse_wkt_position("POINT EMPTY"));
+        assert_eq!(None, parse_wkt_position("(1 2)"));
+        assert_eq!(None, parse_wkt_position("POINT(200 10)"));
+    }
+
+    #[test]
+    fn test_geo_index() {
+        let patch = r#"
+A <http://example.org/berlin> <http://www.w3.org/2003/01/geo/wgs84_pos#lat> "52.52" .
+A <http://example.org/berlin> <http://www.w3.org/2003/01/geo/wgs84_pos#long> "13.405" .
+A <http://example.org/paris> <http://www.opengis.net/ont/geosparql#hasGeometry> <http://example.org/paris_geom> .
+A <http://example.org/paris_geom> <http://www.opengis.net/ont/geosparql#asWKT> "POINT(2.35 48.86)" .
+A <http://example.org/nowhere> <http://www.w3.org/2003/01/geo/wgs84_pos#lat> "10" .
+A <http://example.org/nowhere> <http://www.w3.org/2000/01/rdf-schema#label> "no longitude" .
+"#;
+        let rdf_data = RdfData::from_patch(patch);
+        let geo_index = GeoIndex::new(&rdf_data.node_data, &rdf_data.prefix_manager);
+        let node = |iri: &str| rdf_data.node_data.get_node_index(iri).unwrap();
+        let point_of = |node_index: IriIndex| geo_index.points.iter().find(|point| point.node == node_index);
+        assert_eq!(Some((13.405, 52.52)), point_of(node("http://example.org/berlin")).map(|p| (p.lon, p.lat)));
+        // both the node and its geometry node are placed
+        assert_eq!(Some((2.35, 48.86)), point_of(node("http://example.org/paris")).map(|p| (p.lon, p.lat)));
+        assert!(point_of(node("http://example.org/paris_geom")).is_some());
+        assert!(point_of(node("http://example.org/nowhere")).is_none());
+        assert_eq!(Some((2.35, 48.86, 13.405, 52.52)), geo_index.bounds(|_| true));
+        assert_eq!(None, geo_index.bounds(|_| false));
+    }
+}
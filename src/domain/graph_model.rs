@@ -1,13 +1,16 @@
+use std::collections::{HashMap, HashSet};
+
 use indexmap::IndexMap;
 use oxrdf::vocab::rdf;
+use string_interner::Symbol;
 
-use crate::domain::{config::IriDisplay, graph_styles::GVisualizationStyle, prefix_manager::PrefixManager, string_indexer::{IndexSpan, StringCache, StringIndexer}, type_index::ValueTypes};
+use crate::domain::{change_set::ChangeSet, config::IriDisplay, graph_styles::GVisualizationStyle, named_graphs::NamedGraphs, prefix_manager::{PrefixManager, namespace_of}, rdf_list::{ListMember, RdfList, RdfLists}, string_indexer::{IndexSpan, StringCache, StringIndexer}, type_index::ValueTypes};
 
 pub type IriIndex = u32;
 pub type LangIndex = u16;
 pub type DataTypeIndex = u16;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 pub enum Literal {
     StringShort(IriIndex),
     String(IndexSpan),
@@ -65,6 +68,7 @@ pub type ObjectType = Literal;
 pub type PredicateLiteral = (IriIndex, ObjectType);
 pub type PredicateReference = (IriIndex, IriIndex); // (predicate_index, referenced_node_index)
 
+#[derive(Clone)]
 pub struct NObject {
     pub types: Vec<IriIndex>,
     pub properties: Vec<PredicateLiteral>,
@@ -74,15 +78,23 @@ pub struct NObject {
     pub is_blank_node: bool,
 }
 
+#[derive(Clone)]
 pub struct NodeData {
     pub node_cache: NodeCache,
     pub indexers: Indexers,
+    pub named_graphs: NamedGraphs,
+    // order of the members of the resolved rdf lists
+    pub rdf_lists: RdfLists,
+    // triples changed by editing the data
+    pub change_set: ChangeSet,
 }
 
+#[derive(Clone)]
 pub struct NodeCache {
     pub cache: IndexMap<Box<str>, NObject>,
 }
 
+#[derive(Clone)]
 pub struct Indexers {
     pub predicate_indexer: StringIndexer,
     pub type_indexer: StringIndexer,
@@ -91,6 +103,59 @@ pub struct Indexers {
     pub short_literal_indexer: StringIndexer,
     pub literal_cache: StringCache,
     pub value_type_idx: Vec<ValueTypes>,
+    pub language_fallback: LanguageFallback,
+}
+
+/**
+ * Languages used for labels and literal values if there is no value in the display language.
+ * The languages are tried in the order, then values without language and at last (if set) values in any language.
+ */
+#[derive(Clone, PartialEq, Debug)]
+pub struct LanguageFallback {
+    pub languages: Vec<LangIndex>,
+    pub any_language: bool,
+}
+
+impl Default for LanguageFallback {
+    fn default() -> Self {
+        // "en" has always the index 0
+        Self {
+            languages: vec![0],
+            any_language: false,
+        }
+    }
+}
+
+impl LanguageFallback {
+    /// Languages given by tags, tags that are not in the data are skipped
+    pub fn from_tags(tags: &[String], any_language: bool, language_indexer: &StringIndexer) -> Self {
+        let languages = tags
+            .iter()
+            .filter_map(|tag| language_indexer.map.get(tag.as_str()))
+            .map(|symbol| symbol.to_usize() as LangIndex)
+            .collect();
+        Self {
+            languages,
+            any_language,
+        }
+    }
+
+    // lower rank is preferred, None for values that are not displayed
+    fn language_rank(&self, lang: LangIndex, language_index: LangIndex) -> Option<usize> {
+        if lang == language_index {
+            Some(0)
+        } else if let Some(pos) = self.languages.iter().position(|l| *l == lang) {
+            Some(pos + 1)
+        } else if self.any_language {
+            Some(self.languages.len() + 2)
+        } else {
+            None
+        }
+    }
+
+    fn no_language_rank(&self) -> usize {
+        self.languages.len() + 1
+    }
 }
 
 pub enum LabelDisplayValue<'a> {
@@ -162,7 +227,7 @@ impl NObject {
     ) -> Option<&'a str> {
         for type_index in self.types.iter() {
             if let Some(type_style) = styles.node_styles.get(type_index) {
-                let prop = self.get_property(type_style.label_index, language_index);
+                let prop = self.get_property(type_style.label_index, language_index, &indexers.language_fallback);
                 if let Some(prop) = prop {
                     return Some(prop.as_str_ref(indexers));
                 }
@@ -171,80 +236,90 @@ impl NObject {
         None
     }
 
-    pub fn get_property(&self, predicate_index: IriIndex, language_index: LangIndex) -> Option<&ObjectType> {
-        let mut no_lang: Option<&ObjectType> = None;
-        let mut fallback_lang: Option<&ObjectType> = None;
-        for (predicate, value) in &self.properties {
-            if predicate == &predicate_index {
-                match value {
-                    ObjectType::LangString(lang, _) => {
-                        if *lang == language_index {
-                            return Some(value);
-                        }
-                        if *lang == 0 {
-                            fallback_lang = Some(value);
-                        }
-                    }
-                    ObjectType::String(_) | ObjectType::TypedString(_, _) | ObjectType::StringShort(_) => {
-                        no_lang = Some(value);
-                    }
-                    ObjectType::NoValue() => {
-
-                    }
-                }
-            }
-        }
-        if fallback_lang.is_some() {
-            return fallback_lang;
-        }
-        if no_lang.is_some() {
-            return no_lang;
-        }
-        None
+    pub fn get_property(
+        &self,
+        predicate_index: IriIndex,
+        language_index: LangIndex,
+        fallback: &LanguageFallback,
+    ) -> Option<&ObjectType> {
+        self.get_property_count(predicate_index, language_index, fallback)
+            .map(|(value, _)| value)
     }
 
+    /// Value in the language or in the first available fallback language and the number of all values of the predicate
     pub fn get_property_count(
         &self,
         predicate_index: IriIndex,
         language_index: LangIndex,
+        fallback: &LanguageFallback,
     ) -> Option<(&ObjectType, u32)> {
-        let mut no_lang: Option<&ObjectType> = None;
-        let mut fallback_lang: Option<&ObjectType> = None;
-        let mut lang_value: Option<&ObjectType> = None;
+        let mut best: Option<(usize, &ObjectType)> = None;
         let mut count: u32 = 0;
         for (predicate, value) in &self.properties {
             if predicate == &predicate_index {
                 count += 1;
-                match value {
-                    ObjectType::LangString(lang, _) => {
-                        if *lang == language_index && lang_value.is_none() {
-                            lang_value = Some(value);
-                        }
-                        if *lang == 0 && fallback_lang.is_none() {
-                            fallback_lang = Some(value);
-                        }
-                    }
+                let rank = match value {
+                    ObjectType::LangString(lang, _) => fallback.language_rank(*lang, language_index),
                     ObjectType::String(_) | ObjectType::TypedString(_, _) | ObjectType::StringShort(_) => {
-                        if no_lang.is_none() {
-                            no_lang = Some(value);
-                        }
+                        Some(fallback.no_language_rank())
                     }
-                    ObjectType::NoValue() => {
+                    ObjectType::NoValue() => None,
+                };
+                if let Some(rank) = rank
+                    && best.is_none_or(|(best_rank, _)| rank < best_rank)
+                {
+                    best = Some((rank, value));
+                }
+            }
+        }
+        best.map(|(_, value)| (value, count))
+    }
 
+    /// Like get_property_count, but a value in another language is used if there is no value in the requested language.
+    /// The language of such fallback value is returned, so it can be marked.
+    pub fn get_property_language(
+        &self,
+        predicate_index: IriIndex,
+        language_index: LangIndex,
+        fallback: &LanguageFallback,
+    ) -> Option<(&ObjectType, u32, Option<LangIndex>)> {
+        match self.get_property_count(predicate_index, language_index, fallback) {
+            Some((value, count)) => match value {
+                ObjectType::LangString(lang, _) if *lang != language_index => Some((value, count, Some(*lang))),
+                _ => Some((value, count, None)),
+            },
+            None => {
+                // get_property_count falls back only to the configured languages
+                let mut count: u32 = 0;
+                let mut other_lang: Option<(&ObjectType, LangIndex)> = None;
+                for (predicate, value) in &self.properties {
+                    if predicate == &predicate_index {
+                        count += 1;
+                        if let ObjectType::LangString(lang, _) = value
+                            && other_lang.is_none()
+                        {
+                            other_lang = Some((value, *lang));
+                        }
                     }
                 }
+                other_lang.map(|(value, lang)| (value, count, Some(lang)))
             }
         }
-        if let Some(lang) = lang_value {
-            return Some((lang, count));
-        }
-        if let Some(fallback_lang) = fallback_lang {
-            return Some((fallback_lang, count));
-        }
-        if let Some(no_lang) = no_lang {
-            return Some((no_lang, count));
-        }
-        None
+    }
+
+    /// All values of the predicate with their language, values without language first
+    pub fn get_property_languages(&self, predicate_index: IriIndex) -> Vec<(Option<LangIndex>, &ObjectType)> {
+        let mut values: Vec<(Option<LangIndex>, &ObjectType)> = self
+            .properties
+            .iter()
+            .filter(|(predicate, _)| *predicate == predicate_index)
+            .map(|(_, value)| match value {
+                ObjectType::LangString(lang, _) => (Some(*lang), value),
+                _ => (None, value),
+            })
+            .collect();
+        values.sort_by_key(|(lang, _)| *lang);
+        values
     }
 
     pub fn apply_filter(&self, filter: &str, iri: &str, indexers: &Indexers) -> bool {
@@ -311,6 +386,7 @@ impl Indexers {
             short_literal_indexer: StringIndexer::new(),
             literal_cache: StringCache::default(),
             value_type_idx: Vec::new(),
+            language_fallback: LanguageFallback::default(),
         };
         indexer.language_indexer.get_index("en");
         indexer.predicate_indexer.get_index("rdfs:label");
@@ -333,6 +409,17 @@ impl Indexers {
         }
         idx as DataTypeIndex
     }
+    // the value types are not stored in project files, so they are derived again from the restored data types
+    pub fn rebuild_value_types(&mut self) {
+        self.value_type_idx = (0..self.datatype_indexer.map.len())
+            .map(|idx| {
+                self.datatype_indexer
+                    .index_to_str(idx as IriIndex)
+                    .map(rdf_type_to_value_type)
+                    .unwrap_or(ValueTypes::UNKNOWN)
+            })
+            .collect();
+    }
     pub fn get_value_type(&self, data_type_index: DataTypeIndex) -> ValueTypes {
         self.value_type_idx[data_type_index as usize]
     }
@@ -344,6 +431,7 @@ impl Indexers {
         self.language_indexer.get_index("en");
         self.predicate_indexer.get_index("rdfs:label");
         self.value_type_idx.clear();
+        self.language_fallback = LanguageFallback::default();
     }
 }
 
@@ -428,6 +516,17 @@ impl NodeCache {
     pub fn is_empty(&self) -> bool {
         self.cache.is_empty()
     }
+    /// Removes the nodes from the index on if none of them has data anymore (undo of the operation that created them)
+    pub fn remove_empty_nodes_from(&mut self, index: usize) {
+        if self.cache.values().skip(index).all(|node| {
+            node.types.is_empty()
+                && node.properties.is_empty()
+                && node.references.is_empty()
+                && node.reverse_references.is_empty()
+        }) {
+            self.cache.truncate(index);
+        }
+    }
     pub fn iter(&self) -> indexmap::map::Iter<'_, Box<str>, NObject> {
         self.cache.iter()
     }
@@ -448,6 +547,31 @@ impl NodeCache {
             panic!("Node can not be replaced");
         }
     }
+    /// Replaces the iris for which rename returns a new value, the node indexes stay the same
+    pub fn rename<F>(&mut self, rename: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        // the new iris are computed first, an iri is kept if its new value is already used
+        let mut new_iris: HashMap<usize, Box<str>> = HashMap::new();
+        let mut used: HashSet<Box<str>> = HashSet::new();
+        for (index, iri) in self.cache.keys().enumerate() {
+            if let Some(new_iri) = rename(iri)
+                && !self.cache.contains_key(new_iri.as_str())
+                && used.insert(new_iri.as_str().into())
+            {
+                new_iris.insert(index, new_iri.into());
+            }
+        }
+        if new_iris.is_empty() {
+            return;
+        }
+        self.cache = std::mem::take(&mut self.cache)
+            .into_iter()
+            .enumerate()
+            .map(|(index, (iri, node))| (new_iris.remove(&index).unwrap_or(iri), node))
+            .collect();
+    }
 }
 
 impl Default for NodeData {
@@ -461,6 +585,9 @@ impl NodeData {
         Self {
             node_cache: NodeCache::new(),
             indexers: Indexers::new(),
+            named_graphs: NamedGraphs::default(),
+            rdf_lists: RdfLists::default(),
+            change_set: ChangeSet::default(),
         }
     }
     pub fn get_node_by_index(&self, index: IriIndex) -> Option<(&Box<str>, &NObject)> {
@@ -505,6 +632,9 @@ impl NodeData {
     pub fn split_mut(&mut self) -> (&mut Indexers, &mut NodeCache) {
         (&mut self.indexers, &mut self.node_cache)
     }
+    pub fn split_graphs_mut(&mut self) -> (&mut Indexers, &mut NodeCache, &mut NamedGraphs) {
+        (&mut self.indexers, &mut self.node_cache, &mut self.named_graphs)
+    }
     pub fn get_type(&self, type_index: IriIndex) -> Option<&str> {
         self.indexers.type_indexer.index_to_str(type_index)
     }
@@ -517,6 +647,35 @@ impl NodeData {
     pub fn get_predicate_index(&mut self, predicate_name: &str) -> IriIndex {
         self.indexers.predicate_indexer.get_index(predicate_name)
     }
+    /**
+     * Replaces the full iris of nodes, types, predicates and datatypes with their prefixed form,
+     * used after new prefixes were added to the loaded data.
+     */
+    pub fn apply_prefixes(&mut self, prefix_manager: &PrefixManager) {
+        let rename = |iri: &str| namespace_of(iri).and_then(|_| prefix_manager.get_prefixed_opt(iri));
+        self.node_cache.rename(rename);
+        self.indexers.type_indexer.rename(rename);
+        self.indexers.predicate_indexer.rename(rename);
+        self.indexers.datatype_indexer.rename(rename);
+    }
+    /// Index of already known predicate given by full iri (the indexer stores the iris prefixed)
+    pub fn find_predicate_index(&self, prefix_manager: &PrefixManager, predicate_iri: &str) -> Option<IriIndex> {
+        let predicate_iri = prefix_manager.get_prefixed(predicate_iri);
+        self.indexers
+            .predicate_indexer
+            .map
+            .get(predicate_iri.as_str())
+            .map(|symbol| symbol.to_usize() as IriIndex)
+    }
+    /// Index of already known type given by full iri
+    pub fn find_type_index(&self, prefix_manager: &PrefixManager, type_iri: &str) -> Option<IriIndex> {
+        let type_iri = prefix_manager.get_prefixed(type_iri);
+        self.indexers
+            .type_indexer
+            .map
+            .get(type_iri.as_str())
+            .map(|symbol| symbol.to_usize() as IriIndex)
+    }
     pub fn get_language(&self, language_index: LangIndex) -> Option<&str> {
         self.indexers.language_indexer.index_to_str(language_index as IriIndex)
     }
@@ -538,6 +697,9 @@ impl NodeData {
     pub fn clean(&mut self) {
         self.node_cache.cache.clear();
         self.indexers.clean();
+        self.named_graphs.clean();
+        self.rdf_lists.clear();
+        self.change_set.clear();
     }
     pub fn type_label<'a>(
         &self,
@@ -548,7 +710,7 @@ impl NodeData {
         let type_iri = self.indexers.type_indexer.index_to_str(type_index);
         if let Some(type_iri) = type_iri {
             if let Some(node) = self.get_node(type_iri) {
-                let prop = node.get_property(0, language_index);
+                let prop = node.get_property(0, language_index, &self.indexers.language_fallback);
                 if let Some(prop) = prop {
                     return Some(prop.as_str_ref(indexers));
                 }
@@ -565,7 +727,7 @@ impl NodeData {
         let predicate_iri = self.indexers.predicate_indexer.index_to_str(type_index);
         if let Some(predicate_iri) = predicate_iri {
             if let Some(node) = self.get_node(predicate_iri) {
-                let prop = node.get_property(0, language_index);
+                let prop = node.get_property(0, language_index, &self.indexers.language_fallback);
                 if let Some(prop) = prop {
                     return Some(prop.as_str_ref(indexers));
                 }
@@ -696,6 +858,7 @@ impl NodeData {
                 });
             }
             let mut list_holders: Vec<(IriIndex, IriIndex)> = Vec::new();
+            let mut members: Vec<ListMember> = Vec::new();
             for node_index in list.iter() {
                 let node = self.get_node_by_index_mut(*node_index).unwrap().1;
                 let mut literal: Option<Literal> = None;
@@ -714,6 +877,11 @@ impl NodeData {
                         }
                     }
                 }
+                if let Some(literal) = &literal {
+                    members.push(ListMember::Literal(literal.clone()));
+                } else if let Some(reference) = reference {
+                    members.push(ListMember::Node(reference));
+                }
                 if list_holders.is_empty() {
                     list_holders = node.reverse_references.clone();
                     if list_holders.is_empty() {
@@ -735,6 +903,12 @@ impl NodeData {
                 holder_node
                     .references
                     .retain(|(ref_predicate, ref_index)| ref_predicate != predicate || ref_index != head_node);
+                self.rdf_lists.add(RdfList {
+                    holder: *holder,
+                    predicate: *predicate,
+                    head: *head_node,
+                    members: members.clone(),
+                });
             }
         }
     }
@@ -758,7 +932,7 @@ impl<'a> LabelContext<'a> {
 
 #[cfg(test)]
 mod tests {
-    use super::NodeData;
+    use super::{LangIndex, LanguageFallback, NodeData};
     use crate::{domain::config::IriDisplay, domain::LabelContext, domain::prefix_manager::PrefixManager};
     use oxrdf::Triple;
 
@@ -768,10 +942,7 @@ mod tests {
         let prefix_manager = PrefixManager::new();
 
         let language_filter: Vec<String> = vec![];
-        let mut index_cache = crate::integration::rdfwrap::IndexCache {
-            index: 0,
-            iri: String::with_capacity(100),
-        };
+        let mut index_cache = crate::integration::rdfwrap::IndexCache::new();
         let subject = oxrdf::NamedNode::new("http://example.org#subject").unwrap();
         let rdf_type = oxrdf::NamedNode::new("http://example.org#ClassFoo").unwrap();
         let data_predicate = oxrdf::NamedNode::new("http://example.org#pred").unwrap();
@@ -814,7 +985,7 @@ mod tests {
         assert_eq!(0, node.references.len());
         assert_eq!(1, node.properties.len());
 
-        let lit = node.get_property(pred_index, 0);
+        let lit = node.get_property(pred_index, 0, &node_data.indexers.language_fallback);
         assert!(lit.is_some());
         assert_eq!(lit.unwrap().as_str_ref(&node_data.indexers), "test");
     }
@@ -825,10 +996,7 @@ mod tests {
         let prefix_manager = PrefixManager::new();
 
         let language_filter: Vec<String> = vec![];
-        let mut index_cache = crate::integration::rdfwrap::IndexCache {
-            index: 0,
-            iri: String::with_capacity(100),
-        };
+        let mut index_cache = crate::integration::rdfwrap::IndexCache::new();
         let subject = oxrdf::NamedNode::new("http://example.org#subject").unwrap();
         let rdf_type = oxrdf::NamedNode::new("http://example.org#ClassFoo").unwrap();
         let data_predicate = oxrdf::NamedNode::new("http://example.org#pred").unwrap();
@@ -967,4 +1135,51 @@ mod tests {
         let type_display = node_data.predicate_display(*prop_index, &label_context, &node_data.indexers);
         assert_eq!("http://example.org#pred", type_display.as_str());
     }
+
+    #[test]
+    fn test_property_languages() {
+        use crate::domain::RdfData;
+
+        let mut rdf_data = RdfData::from_patch(
+            r#"
+A <http://example.org/cat> <http://example.org/label> "Chat"@fr .
+A <http://example.org/cat> <http://example.org/label> "Katze"@de .
+A <http://example.org/dog> <http://example.org/label> "Dog"@en .
+A <http://example.org/dog> <http://example.org/label> "Hund"@de .
+"#,
+        );
+        let node_data = &mut rdf_data.node_data;
+        let de = node_data.get_language_index("de");
+        let fr = node_data.get_language_index("fr");
+        let es = node_data.get_language_index("es");
+        let label = node_data.indexers.predicate_indexer.get_index("http://example.org/label");
+        let cat = node_data.get_node("http://example.org/cat").unwrap();
+        let default_fallback = LanguageFallback::default();
+        let (value, count, fallback) = cat.get_property_language(label, de, &default_fallback).unwrap();
+        assert_eq!(("Katze", 2, None), (value.as_str_ref(&node_data.indexers), count, fallback));
+        // no value in the default language, so the first other language is used
+        let (value, _, fallback) = cat.get_property_language(label, es, &default_fallback).unwrap();
+        assert_eq!(("Chat", Some(fr)), (value.as_str_ref(&node_data.indexers), fallback));
+        let dog = node_data.get_node("http://example.org/dog").unwrap();
+        let (value, _, fallback) = dog.get_property_language(label, es, &default_fallback).unwrap();
+        assert_eq!(("Dog", Some(0)), (value.as_str_ref(&node_data.indexers), fallback));
+
+        // fallback chain es -> de -> en
+        let de_en = LanguageFallback::from_tags(
+            &["de".to_string(), "en".to_string(), "xx".to_string()],
+            false,
+            &node_data.indexers.language_indexer,
+        );
+        assert_eq!(vec![de, 0], de_en.languages);
+        let value = dog.get_property(label, es, &de_en).unwrap();
+        assert_eq!("Hund", value.as_str_ref(&node_data.indexers));
+        let value = dog.get_property(label, 0, &de_en).unwrap();
+        assert_eq!("Dog", value.as_str_ref(&node_data.indexers));
+        let only_es = LanguageFallback::from_tags(&[], false, &node_data.indexers.language_indexer);
+        assert!(cat.get_property(label, es, &only_es).is_none());
+        let any = LanguageFallback::from_tags(&[], true, &node_data.indexers.language_indexer);
+        assert!(cat.get_property(label, es, &any).is_some());
+        let languages: Vec<Option<LangIndex>> = dog.get_property_languages(label).iter().map(|(lang, _)| *lang).collect();
+        assert_eq!(vec![Some(0), Some(de)], languages);
+    }
 }
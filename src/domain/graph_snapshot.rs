@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+
+/**
+ * Named view of the visual graph stored in the application settings.
+ * Nodes and predicates are stored by iri, so the snapshot survives reloading of the data.
+ */
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct GraphSnapshot {
+    pub name: String,
+    // sources of the data the snapshot was taken from, only informative
+    pub sources: Vec<String>,
+    pub nodes: Vec<SnapshotNode>,
+    pub hidden_predicates: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct SnapshotNode {
+    pub iri: String,
+    pub x: f32,
+    pub y: f32,
+    #[serde(default)]
+    pub locked: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct GraphSnapshots {
+    pub snapshots: Vec<GraphSnapshot>,
+}
+
+impl GraphSnapshot {
+    pub fn is_same_data(&self, sources: &[String]) -> bool {
+        !sources.is_empty() && self.sources == sources
+    }
+}
+
+impl GraphSnapshots {
+    /// Snapshot with the same name is replaced
+    pub fn put(&mut self, snapshot: GraphSnapshot) {
+        if let Some(existing) = self.snapshots.iter_mut().find(|s| s.name == snapshot.name) {
+            *existing = snapshot;
+        } else {
+            self.snapshots.push(snapshot);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&GraphSnapshot> {
+        self.snapshots.iter().find(|s| s.name == name)
+    }
+
+    pub fn remove(&mut self, name: &str) {
+        self.snapshots.retain(|s| s.name != name);
+    }
+
+    /// Name that is not used yet, the base is extended by number
+    pub fn unique_name(&self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut counter = 1;
+        while self.get(&name).is_some() {
+            counter += 1;
+            name = format!("{} {}", base, counter);
+        }
+        name
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.snapshots.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_graph_snapshots() {
+        let mut snapshots = GraphSnapshots::default();
+        assert!(snapshots.is_empty());
+        assert_eq!("View", snapshots.unique_name("View"));
+        snapshots.put(GraphSnapshot {
+            name: "View".to_string(),
+            sources: vec!["data.ttl".to_string()],
+            nodes: vec![SnapshotNode {
+                iri: "http://example.org/a".to_string(),
+                x: 1.0,
+                y: 2.0,
+                locked: false,
+            }],
+            hidden_predicates: Vec::new(),
+        });
+        assert_eq!("View 2", snapshots.unique_name("View"));
+        snapshots.put(GraphSnapshot {
+            name: "View".to_string(),
+            ..Default::default()
+        });
+        assert_eq!(1, snapshots.snapshots.len());
+        assert!(snapshots.get("View").unwrap().nodes.is_empty());
+        assert!(!snapshots.get("View").unwrap().is_same_data(&["data.ttl".to_string()]));
+
+        let json = serde_json::to_string(&snapshots).unwrap();
+        let restored: GraphSnapshots = serde_json::from_str(&json).unwrap();
+        assert_eq!(snapshots, restored);
+
+        snapshots.remove("View");
+        assert!(snapshots.is_empty());
+    }
+}
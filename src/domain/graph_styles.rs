@@ -1,14 +1,27 @@
 use std::collections::HashMap;
 
 use egui::Color32;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    IriIndex, domain::{StringIndexer, type_index::TypeInstanceIndex}, support::distinct_colors::next_distinct_color,
+    IriIndex,
+    domain::{
+        StringIndexer,
+        prefix_manager::{PrefixManager, namespace_of},
+        size_mapping::SizeMapping,
+        style_rules::StyleRules,
+        type_index::TypeInstanceIndex,
+    },
+    support::distinct_colors::{family_color, next_distinct_color},
 };
 
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct NodeStyle {
     pub color: egui::Color32,
     pub priority: u32,
+    // index of the label predicate, not serialized because the indexes are specific to the data
+    #[serde(skip)]
     pub label_index: IriIndex,
     pub node_shape: NodeShape,
     pub node_size: NodeSize,
@@ -23,6 +36,7 @@ pub struct NodeStyle {
     pub font_size: f32,
     pub label_color: egui::Color32,
     pub icon_style: Option<IconStyle>,
+    #[serde(skip)]
     pub is_default: bool,
 }
 
@@ -50,11 +64,40 @@ impl Default for NodeStyle {
     }
 }
 
+impl NodeStyle {
+    /// Copy of the style with the mapped size as width and height
+    pub fn with_mapped_size(&self, size: f32) -> NodeStyle {
+        NodeStyle {
+            color: self.color,
+            priority: self.priority,
+            label_index: self.label_index,
+            node_shape: self.node_shape,
+            node_size: NodeSize::Fixed,
+            width: size,
+            height: size,
+            border_width: self.border_width,
+            border_color: self.border_color,
+            corner_radius: self.corner_radius,
+            max_lines: self.max_lines,
+            label_position: self.label_position,
+            label_max_width: self.label_max_width,
+            font_size: self.font_size,
+            label_color: self.label_color,
+            icon_style: self.icon_style.clone(),
+            is_default: self.is_default,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct IconStyle {
     pub icon_character: char,
     pub icon_position: IconPosition,
     pub icon_size: f32,
     pub icon_color: egui::Color32,
+    // custom image drawn instead of the icon character
+    pub image: Option<IconImage>,
 }
 
 impl Default for IconStyle {
@@ -64,13 +107,41 @@ impl Default for IconStyle {
             icon_position: IconPosition::Center,
             icon_size: 20.0,
             icon_color: Color32::BLACK,
+            image: None,
         }
     }
 }
 
+/// Image file of the icon, only the path is stored in the project
+#[derive(Serialize, Deserialize, Clone)]
+pub struct IconImage {
+    pub path: String,
+    // set by the ui after the image file is loaded
+    #[serde(skip)]
+    pub texture: Option<egui::TextureHandle>,
+    #[serde(skip)]
+    pub load_failed: bool,
+}
+
+impl IconImage {
+    pub fn new(path: String) -> Self {
+        Self {
+            path,
+            texture: None,
+            load_failed: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct EdgeFont {
     pub font_size: f32,
     pub font_color: Color32,
+    pub label_visible: bool,
+    pub label_position: EdgeLabelPosition,
+    pub label_rotate: bool,
+    pub label_background: bool,
 }
 
 impl Default for EdgeFont {
@@ -78,11 +149,47 @@ impl Default for EdgeFont {
         Self {
             font_size: 16.0,
             font_color: Color32::BLACK,
+            label_visible: true,
+            label_position: EdgeLabelPosition::Middle,
+            label_rotate: true,
+            label_background: false,
         }
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+#[repr(u8)]
+pub enum EdgeLabelPosition {
+    Start = 1,
+    Middle = 2,
+    End = 3,
+}
+
+impl EdgeLabelPosition {
+    /// Relative position of the label along the edge line
+    pub fn fraction(&self) -> f32 {
+        match self {
+            EdgeLabelPosition::Start => 0.2,
+            EdgeLabelPosition::Middle => 0.5,
+            EdgeLabelPosition::End => 0.8,
+        }
+    }
+}
+
+impl TryFrom<u8> for EdgeLabelPosition {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(EdgeLabelPosition::Start),
+            2 => Ok(EdgeLabelPosition::Middle),
+            3 => Ok(EdgeLabelPosition::End),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum NodeShape {
     None = 0,
@@ -105,11 +212,13 @@ impl TryFrom<u8> for NodeShape {
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum NodeSize {
     Fixed = 1,
     Label = 2,
+    // size from the size mapping of the style, fixed size for nodes without value
+    Mapped = 3,
 }
 
 impl TryFrom<u8> for NodeSize {
@@ -119,12 +228,13 @@ impl TryFrom<u8> for NodeSize {
         match value {
             1 => Ok(NodeSize::Fixed),
             2 => Ok(NodeSize::Label),
+            3 => Ok(NodeSize::Mapped),
             _ => Err(()),
         }
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum LabelPosition {
     Center = 1,
@@ -149,7 +259,7 @@ impl TryFrom<u8> for LabelPosition {
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum IconPosition {
     Center = 1,
@@ -174,6 +284,8 @@ impl TryFrom<u8> for IconPosition {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct EdgeStyle {
     pub color: egui::Color32,
     pub width: f32,
@@ -184,6 +296,8 @@ pub struct EdgeStyle {
     pub arrow_size: f32,
     pub icon_style: Option<IconStyle>,
     pub edge_font: Option<EdgeFont>,
+    // None uses the global edges force of the layout
+    pub layout_force: Option<EdgeLayoutForce>,
 }
 
 impl Default for EdgeStyle {
@@ -193,6 +307,7 @@ impl Default for EdgeStyle {
             width: 2.0,
             icon_style: None,
             edge_font: None,
+            layout_force: None,
             line_style: LineStyle::Solid,
             target_style: ArrowStyle::Arrow,
             arrow_location: ArrowLocation::Target,
@@ -202,7 +317,25 @@ impl Default for EdgeStyle {
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+/// Preferred length and stiffness of the edges of one predicate in the force layout
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+#[serde(default)]
+pub struct EdgeLayoutForce {
+    // distance between the node borders
+    pub length: f32,
+    pub stiffness: f32,
+}
+
+impl Default for EdgeLayoutForce {
+    fn default() -> Self {
+        Self {
+            length: 50.0,
+            stiffness: 1.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum LineStyle {
     Solid,
@@ -223,12 +356,16 @@ impl TryFrom<u8> for LineStyle {
     }
 }
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone)]
 #[repr(u8)]
 pub enum ArrowStyle {
     Arrow = 0,
     ArrorFilled = 1,
     ArrorTriangle = 2,
+    // filled diamond, as composition in UML
+    Diamond = 3,
+    // diamond outline, as aggregation in UML
+    DiamondOpen = 4,
 }
 
 impl TryFrom<u8> for ArrowStyle {
@@ -239,17 +376,21 @@ impl TryFrom<u8> for ArrowStyle {
             0 => Ok(ArrowStyle::Arrow),
             1 => Ok(ArrowStyle::ArrorFilled),
             2 => Ok(ArrowStyle::ArrorTriangle),
+            3 => Ok(ArrowStyle::Diamond),
+            4 => Ok(ArrowStyle::DiamondOpen),
             _ => Err(()),
         }
     }
 }
 
-#[derive(PartialEq, Copy, Clone, Debug)]
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum ArrowLocation {
     Target = 0,
     Middle = 1,
     None = 2,
+    // arrow heads at the target and at the source
+    Both = 3,
 }
 
 impl TryFrom<u8> for ArrowLocation {
@@ -260,6 +401,7 @@ impl TryFrom<u8> for ArrowLocation {
             0 => Ok(ArrowLocation::Target),
             1 => Ok(ArrowLocation::Middle),
             2 => Ok(ArrowLocation::None),
+            3 => Ok(ArrowLocation::Both),
             _ => Err(()),
         }
     }
@@ -269,6 +411,11 @@ pub struct GVisualizationStyle {
     pub node_styles: HashMap<IriIndex, NodeStyle>,
     pub default_node_style: NodeStyle,
     pub edge_styles: HashMap<IriIndex, EdgeStyle>,
+    // data properties shown in the node tooltip of the graph view for each type
+    pub tooltip_properties: HashMap<IriIndex, Vec<IriIndex>>,
+    // node styles depending on property values
+    pub style_rules: StyleRules,
+    pub size_mapping: SizeMapping,
     pub use_size_overwrite: bool,
     pub use_color_overwrite: bool,
     pub default_label_in_node: bool,
@@ -335,6 +482,72 @@ impl GVisualizationStyle {
         }
     }
 
+    /**
+     * Recolors the generated type styles, so all types of a namespace share a hue family.
+     * The namespaces and the types in a namespace are ordered by iri, so the colors do not depend on the loading order.
+     * Styles changed by the user are kept.
+     */
+    pub fn assign_namespace_colors(&mut self, type_indexer: &StringIndexer, prefix_manager: &PrefixManager, is_dark_mode: bool) {
+        let mut types: Vec<(&str, &str, IriIndex)> = self
+            .node_styles
+            .iter()
+            .filter(|(_, style)| style.is_default)
+            .filter_map(|(type_index, _)| {
+                let iri = type_indexer.index_to_str(*type_index)?;
+                Some((type_namespace(iri, prefix_manager), iri, *type_index))
+            })
+            .collect();
+        types.sort_unstable();
+        let lightness = if is_dark_mode { 0.3 } else { 0.6 };
+        let mut family = 0;
+        let mut member = 0;
+        for (pos, (namespace, _, type_index)) in types.iter().enumerate() {
+            if pos > 0 {
+                if types[pos - 1].0 == *namespace {
+                    member += 1;
+                } else {
+                    family += 1;
+                    member = 0;
+                }
+            }
+            if let Some(style) = self.node_styles.get_mut(type_index) {
+                style.color = family_color(family, member, 0.8, lightness, 200);
+            }
+        }
+    }
+
+    /// Recolors the generated type styles with a distinct color for each type
+    pub fn assign_type_colors(&mut self, is_dark_mode: bool) {
+        let mut types: Vec<IriIndex> = self
+            .node_styles
+            .iter()
+            .filter(|(_, style)| style.is_default)
+            .map(|(type_index, _)| *type_index)
+            .collect();
+        types.sort_unstable();
+        let lightness = if is_dark_mode { 0.3 } else { 0.6 };
+        for (pos, type_index) in types.iter().enumerate() {
+            if let Some(style) = self.node_styles.get_mut(type_index) {
+                style.color = next_distinct_color(pos, 0.8, lightness, 200);
+            }
+        }
+    }
+
+    /// Tooltip properties of all types of the node, in order of the types without duplicates
+    pub fn get_tooltip_properties(&self, types: &[IriIndex]) -> Vec<IriIndex> {
+        let mut properties: Vec<IriIndex> = Vec::new();
+        for type_index in types {
+            if let Some(type_properties) = self.tooltip_properties.get(type_index) {
+                for property in type_properties {
+                    if !properties.contains(property) {
+                        properties.push(*property);
+                    }
+                }
+            }
+        }
+        properties
+    }
+
     pub fn get_type_style(&self, types: &Vec<IriIndex>) -> &NodeStyle {
         let mut style: Option<&NodeStyle> = None;
         for type_iri in types {
@@ -381,6 +594,14 @@ impl GVisualizationStyle {
         })
     }
 
+    /// Layout forces of the predicates that define their own edge length
+    pub fn edge_layout_forces(&self) -> HashMap<IriIndex, EdgeLayoutForce> {
+        self.edge_styles
+            .iter()
+            .filter_map(|(predicate, edge_style)| edge_style.layout_force.map(|force| (*predicate, force)))
+            .collect()
+    }
+
     pub fn update_label(&mut self, iri: IriIndex, label_index: IriIndex) {
         if let Some(type_style) = self.node_styles.get_mut(&iri) {
             type_style.label_index = label_index;
@@ -393,3 +614,80 @@ impl GVisualizationStyle {
     }
 }
 
+// Namespace of the type iri, the prefix for prefixed iris, empty for iris without namespace
+fn type_namespace<'a>(iri: &'a str, prefix_manager: &PrefixManager) -> &'a str {
+    if prefix_manager.has_known_prefix(iri) {
+        iri.split_once(':').map_or("", |(prefix, _)| prefix)
+    } else {
+        namespace_of(iri).unwrap_or("")
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hue(color: Color32) -> f32 {
+        let [r, g, b, _] = color.to_array().map(|c| c as f32);
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let hue = if max == r {
+            (g - b) / (max - min)
+        } else if max == g {
+            2.0 + (b - r) / (max - min)
+        } else {
+            4.0 + (r - g) / (max - min)
+        };
+        (hue * 60.0 + 360.0) % 360.0
+    }
+
+    #[test]
+    fn test_namespace_colors() {
+        let prefix_manager = PrefixManager::new();
+        let mut type_indexer = StringIndexer::new();
+        let mut styles = GVisualizationStyle {
+            node_styles: HashMap::new(),
+            default_node_style: NodeStyle::default(),
+            edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
+            use_size_overwrite: false,
+            use_color_overwrite: false,
+            default_label_in_node: false,
+            min_size: 5.0,
+            max_size: 50.0,
+        };
+        let types: Vec<IriIndex> = ["foaf:Person", "foaf:Organization", "http://example.org/Thing", "foaf:Group"]
+            .iter()
+            .map(|iri| type_indexer.get_index(iri))
+            .collect();
+        for type_index in types.iter() {
+            styles.node_styles.insert(*type_index, NodeStyle::default());
+        }
+        let custom = type_indexer.get_index("http://example.org/Custom");
+        styles.node_styles.insert(
+            custom,
+            NodeStyle {
+                color: Color32::RED,
+                is_default: false,
+                ..NodeStyle::default()
+            },
+        );
+        styles.assign_namespace_colors(&type_indexer, &prefix_manager, false);
+        let color = |styles: &GVisualizationStyle, type_index: IriIndex| styles.node_styles.get(&type_index).unwrap().color;
+        let person = color(&styles, types[0]);
+        let organization = color(&styles, types[1]);
+        let thing = color(&styles, types[2]);
+        // types of one namespace share the hue, but have different shades
+        assert_ne!(person, organization);
+        assert!((hue(person) - hue(organization)).abs() < 1.0);
+        assert!((hue(person) - hue(thing)).abs() > 20.0);
+        assert_eq!(Color32::RED, color(&styles, custom));
+
+        styles.assign_type_colors(false);
+        assert!((hue(color(&styles, types[0])) - hue(color(&styles, types[1]))).abs() > 20.0);
+        assert_eq!(Color32::RED, color(&styles, custom));
+    }
+}
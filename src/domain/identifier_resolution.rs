@@ -0,0 +1,108 @@
+use serde::{Deserialize, Serialize};
+
+/// Resolves values of identifier properties (DOI, ORCID, ...) to web links
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct IdentifierTemplate {
+    // shown as badge next to the link
+    pub name: String,
+    // comma separated local names of predicates (case insensitive), e.g. "doi, P356"
+    pub predicates: String,
+    // {id} is replaced by the identifier value
+    pub url_template: String,
+}
+
+impl IdentifierTemplate {
+    fn new(name: &str, predicates: &str, url_template: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            predicates: predicates.to_string(),
+            url_template: url_template.to_string(),
+        }
+    }
+
+    pub fn matches_predicate(&self, predicate_iri: &str) -> bool {
+        let local_name = local_name(predicate_iri);
+        self.predicates
+            .split(',')
+            .map(str::trim)
+            .any(|predicate| !predicate.is_empty() && predicate.eq_ignore_ascii_case(local_name))
+    }
+
+    pub fn url(&self, value: &str) -> Option<String> {
+        let value = value.trim();
+        if value.starts_with("http://") || value.starts_with("https://") {
+            // already resolved
+            return Some(value.to_string());
+        }
+        // values like "doi:10.1000/182" or "ISBN 978-3-16-148410-0"
+        let id = value
+            .get(..self.name.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(&self.name))
+            .map(|_| value[self.name.len()..].trim_start_matches([':', ' ']))
+            .unwrap_or(value);
+        // isbn is often written with hyphens or spaces between the groups
+        let id: String = if self.name.eq_ignore_ascii_case("isbn") {
+            id.chars().filter(|c| c.is_ascii_alphanumeric()).collect()
+        } else {
+            id.to_string()
+        };
+        if id.is_empty() || id.contains(char::is_whitespace) {
+            return None;
+        }
+        Some(self.url_template.replace("{id}", &id))
+    }
+}
+
+fn local_name(iri: &str) -> &str {
+    iri.rsplit(['/', '#', ':']).next().unwrap_or(iri)
+}
+
+pub fn default_identifier_templates() -> Vec<IdentifierTemplate> {
+    vec![
+        IdentifierTemplate::new("DOI", "doi, P356", "https://doi.org/{id}"),
+        IdentifierTemplate::new("ORCID", "orcid, orcidId, P496", "https://orcid.org/{id}"),
+        IdentifierTemplate::new("ISBN", "isbn, isbn10, isbn13, P212, P957", "https://openlibrary.org/isbn/{id}"),
+        IdentifierTemplate::new("PubMed", "pmid, pubmed, pubmedId, P698", "https://pubmed.ncbi.nlm.nih.gov/{id}/"),
+        IdentifierTemplate::new("GeoNames", "geonames, geonamesId, geonameId, P1566", "https://www.geonames.org/{id}"),
+    ]
+}
+
+/// Finds the first template for the predicate and builds the link, returns (badge, url)
+pub fn resolve_identifier<'a>(
+    templates: &'a [IdentifierTemplate],
+    predicate_iri: &str,
+    value: &str,
+) -> Option<(&'a str, String)> {
+    templates
+        .iter()
+        .find(|template| template.matches_predicate(predicate_iri))
+        .and_then(|template| template.url(value).map(|url| (template.name.as_str(), url)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_identifier() {
+        let templates = default_identifier_templates();
+        assert_eq!(
+            Some(("DOI", "https://doi.org/10.1000/182".to_string())),
+            resolve_identifier(&templates, "dct:doi", "doi:10.1000/182")
+        );
+        assert_eq!(
+            Some(("ORCID", "https://orcid.org/0000-0002-1825-0097".to_string())),
+            resolve_identifier(&templates, "http://www.wikidata.org/prop/direct/P496", "0000-0002-1825-0097")
+        );
+        assert_eq!(
+            Some(("ISBN", "https://openlibrary.org/isbn/9783161484100".to_string())),
+            resolve_identifier(&templates, "schema:isbn", "ISBN 978-3-16-148410-0")
+        );
+        assert_eq!(
+            Some(("PubMed", "https://pubmed.ncbi.nlm.nih.gov/12345/".to_string())),
+            resolve_identifier(&templates, "http://example.org/vocab#pmid", "https://pubmed.ncbi.nlm.nih.gov/12345/")
+        );
+        assert_eq!(None, resolve_identifier(&templates, "rdfs:label", "10.1000/182"));
+        assert_eq!(None, resolve_identifier(&templates, "ex:geonamesId", "not an id"));
+    }
+}
@@ -0,0 +1,143 @@
+use std::{fmt::Write, time::Duration};
+
+// Avoid unbounded memory usage for broken files, only the first errors are kept
+const MAX_STORED_ERRORS: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TripleAddResult {
+    Added,
+    Duplicate,
+    // Literal was skipped because of the configured language filter
+    Filtered,
+    // Triple could not be represented (for example rdf:type with literal object)
+    Ignored,
+}
+
+/**
+ * Statistics and errors of one imported file (or url).
+ */
+#[derive(Default)]
+pub struct ImportReport {
+    pub source: String,
+    pub duration: Duration,
+    pub triples_read: u32,
+    pub triples_merged: u32,
+    pub duplicates_skipped: u32,
+    pub filtered_by_language: u32,
+    pub ignored: u32,
+    pub error_count: u32,
+    // Error messages of the parsers contain the line and column number
+    pub errors: Vec<String>,
+    pub stopped: bool,
+}
+
+impl ImportReport {
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn record(&mut self, result: TripleAddResult) {
+        self.triples_read += 1;
+        match result {
+            TripleAddResult::Added => self.triples_merged += 1,
+            TripleAddResult::Duplicate => self.duplicates_skipped += 1,
+            TripleAddResult::Filtered => self.filtered_by_language += 1,
+            TripleAddResult::Ignored => self.ignored += 1,
+        }
+    }
+
+    pub fn add_error(&mut self, message: String) {
+        self.error_count += 1;
+        if self.errors.len() < MAX_STORED_ERRORS {
+            self.errors.push(message);
+        }
+    }
+
+    pub fn write_text(&self, out: &mut String) {
+        let _ = writeln!(out, "Source: {}", self.source);
+        let _ = writeln!(out, "  Parse duration: {:.3}s", self.duration.as_secs_f64());
+        let _ = writeln!(out, "  Triples read: {}", self.triples_read);
+        let _ = writeln!(out, "  Triples merged: {}", self.triples_merged);
+        let _ = writeln!(out, "  Duplicates skipped: {}", self.duplicates_skipped);
+        let _ = writeln!(out, "  Filtered by language: {}", self.filtered_by_language);
+        let _ = writeln!(out, "  Ignored: {}", self.ignored);
+        let _ = writeln!(out, "  Errors: {}", self.error_count);
+        if self.stopped {
+            let _ = writeln!(out, "  Loading stopped by user");
+        }
+        for error in self.errors.iter() {
+            let _ = writeln!(out, "    {}", error);
+        }
+        if self.errors.len() < self.error_count as usize {
+            let _ = writeln!(
+                out,
+                "    ... {} more errors not stored",
+                self.error_count as usize - self.errors.len()
+            );
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ImportLog {
+    pub reports: Vec<ImportReport>,
+}
+
+impl ImportLog {
+    pub fn add(&mut self, report: ImportReport) {
+        self.reports.push(report);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reports.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.reports.clear();
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        for report in self.reports.iter() {
+            report.write_text(&mut out);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::RwLock};
+
+    use super::*;
+    use crate::{domain::RdfData, integration::rdfwrap::RDFWrap, uistate::DataLoading};
+
+    #[test]
+    fn test_import_report() {
+        let rdf_data = RwLock::new(RdfData::empty());
+        let data = "<http://example.org/a> <http://example.org/name> \"Anna\" .\n\
+                    <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n\
+                    <http://example.org/a> <http://example.org/name> \"Anna\" .\n\
+                    <http://example.org/a> <http://example.org/name> \"Anna\"@en .\n";
+        let data_loading = DataLoading::new();
+        RDFWrap::load_file_reader("repeated.nt", "nt", "repeated", io::Cursor::new(data), &rdf_data, &[], Some(&data_loading))
+            .unwrap();
+        let mut import_log = ImportLog::default();
+        for report in data_loading.take_reports() {
+            import_log.add(report);
+        }
+        let report = &import_log.reports[0];
+        assert_eq!("repeated.nt", report.source);
+        assert_eq!(4, report.triples_read);
+        assert_eq!(3, report.triples_merged);
+        assert_eq!(1, report.duplicates_skipped);
+        assert_eq!(0, report.error_count);
+        let text = import_log.to_text();
+        assert!(text.contains("Triples merged: 3\n"));
+        assert!(text.contains("Duplicates skipped: 1\n"));
+    }
+}
@@ -0,0 +1,240 @@
+use std::{collections::HashMap, fmt};
+
+use egui::{Event, InputState, Key};
+use serde::{Deserialize, Serialize};
+
+/**
+ * Commands of the visual graph that can be triggered by keyboard.
+ * Every command listed in ALL is shown in the settings and can be rebound.
+ */
+#[derive(Serialize, Deserialize, PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum ViewAction {
+    Undo,
+    Redo,
+    ExpandAll,
+    UnexpandAll,
+    CenterGraph,
+    ZoomIn,
+    ZoomOut,
+    SelectAll,
+    ContextMenu,
+    ExpandNode,
+    HideNode,
+    HideOther,
+    HideThisType,
+    BrowseNode,
+}
+
+impl ViewAction {
+    pub const ALL: [ViewAction; 14] = [
+        ViewAction::Undo,
+        ViewAction::Redo,
+        ViewAction::ExpandAll,
+        ViewAction::UnexpandAll,
+        ViewAction::CenterGraph,
+        ViewAction::ZoomIn,
+        ViewAction::ZoomOut,
+        ViewAction::SelectAll,
+        ViewAction::ContextMenu,
+        ViewAction::ExpandNode,
+        ViewAction::HideNode,
+        ViewAction::HideOther,
+        ViewAction::HideThisType,
+        ViewAction::BrowseNode,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ViewAction::Undo => "Undo",
+            ViewAction::Redo => "Redo",
+            ViewAction::ExpandAll => "Expand Nodes",
+            ViewAction::UnexpandAll => "Unexpand Nodes, Remove Leaves",
+            ViewAction::CenterGraph => "Center Graph and reset zoom",
+            ViewAction::ZoomIn => "Zoom In",
+            ViewAction::ZoomOut => "Zoom Out",
+            ViewAction::SelectAll => "Select All",
+            ViewAction::ContextMenu => "Open Context Menu",
+            ViewAction::ExpandNode => "Expand Selected Node",
+            ViewAction::HideNode => "Hide Selected Node",
+            ViewAction::HideOther => "Hide Other Nodes",
+            ViewAction::HideThisType => "Hide Type of Selected Node",
+            ViewAction::BrowseNode => "Browse Selected Node",
+        }
+    }
+
+    pub fn default_binding(&self) -> Option<KeyBinding> {
+        let binding = match self {
+            ViewAction::Undo => KeyBinding::command(Key::Z),
+            ViewAction::Redo => KeyBinding::command(Key::Y),
+            ViewAction::ExpandAll => KeyBinding::key(Key::Plus),
+            ViewAction::UnexpandAll => KeyBinding::key(Key::Minus),
+            ViewAction::CenterGraph => KeyBinding::key(Key::Home),
+            ViewAction::ZoomIn => KeyBinding::key(Key::PageUp),
+            ViewAction::ZoomOut => KeyBinding::key(Key::PageDown),
+            ViewAction::SelectAll => KeyBinding::command(Key::A),
+            // There is currently not defined key for opening context menu so use Shift + F10
+            ViewAction::ContextMenu => KeyBinding {
+                shift: true,
+                ..KeyBinding::key(Key::F10)
+            },
+            ViewAction::ExpandNode => KeyBinding::key(Key::E),
+            ViewAction::HideNode => KeyBinding::key(Key::H),
+            ViewAction::HideOther => KeyBinding::key(Key::O),
+            ViewAction::HideThisType => KeyBinding::key(Key::T),
+            ViewAction::BrowseNode => KeyBinding::key(Key::Enter),
+        };
+        Some(binding)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+pub struct KeyBinding {
+    pub key: Key,
+    // Ctrl or Cmd on Mac
+    pub command: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyBinding {
+    pub fn key(key: Key) -> Self {
+        Self {
+            key,
+            command: false,
+            shift: false,
+            alt: false,
+        }
+    }
+
+    pub fn command(key: Key) -> Self {
+        Self {
+            command: true,
+            ..Self::key(key)
+        }
+    }
+
+    /// Shift is ignored if not part of the binding, because some keys (+) need shift on many keyboard layouts
+    pub fn matches(&self, input: &InputState) -> bool {
+        input.key_pressed(self.key)
+            && input.modifiers.command == self.command
+            && input.modifiers.alt == self.alt
+            && (!self.shift || input.modifiers.shift)
+    }
+
+    /// The first pressed key (with modifiers) of this frame, used to record a new binding
+    pub fn from_input(input: &InputState) -> Option<Self> {
+        input.events.iter().find_map(|event| match event {
+            Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => Some(KeyBinding {
+                key: *key,
+                command: modifiers.command,
+                shift: modifiers.shift,
+                alt: modifiers.alt,
+            }),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.command {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.key.symbol_or_name())
+    }
+}
+
+/**
+ * Keyboard bindings of the view actions.
+ * Only the changed bindings are stored, so the other actions keep their defaults (also new ones).
+ * None as binding means the action has no shortcut.
+ */
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Keymap {
+    pub bindings: HashMap<ViewAction, Option<KeyBinding>>,
+}
+
+impl Keymap {
+    pub fn binding(&self, action: ViewAction) -> Option<KeyBinding> {
+        match self.bindings.get(&action) {
+            Some(binding) => *binding,
+            None => action.default_binding(),
+        }
+    }
+
+    pub fn set_binding(&mut self, action: ViewAction, binding: Option<KeyBinding>) {
+        if binding == action.default_binding() {
+            self.bindings.remove(&action);
+        } else {
+            self.bindings.insert(action, binding);
+        }
+    }
+
+    /// Other actions that use the same binding
+    pub fn conflicts(&self, action: ViewAction) -> Vec<ViewAction> {
+        let Some(binding) = self.binding(action) else {
+            return Vec::new();
+        };
+        ViewAction::ALL
+            .iter()
+            .filter(|other| **other != action && self.binding(**other) == Some(binding))
+            .copied()
+            .collect()
+    }
+
+    /// The actions whose binding was pressed in this frame
+    pub fn pressed(&self, input: &InputState) -> Vec<ViewAction> {
+        ViewAction::ALL
+            .iter()
+            .filter(|action| self.binding(**action).is_some_and(|binding| binding.matches(input)))
+            .copied()
+            .collect()
+    }
+
+    /// Text for tooltips and menus like "Expand Nodes (+)"
+    pub fn hint(&self, action: ViewAction) -> String {
+        match self.binding(action) {
+            Some(binding) => format!("{} ({})", action.label(), binding),
+            None => action.label().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keymap_bindings() {
+        let mut keymap = Keymap::default();
+        assert_eq!(keymap.binding(ViewAction::HideNode), Some(KeyBinding::key(Key::H)));
+        assert_eq!(keymap.hint(ViewAction::Undo), "Undo (Ctrl+Z)");
+        assert!(keymap.conflicts(ViewAction::HideNode).is_empty());
+
+        keymap.set_binding(ViewAction::HideNode, Some(KeyBinding::key(Key::E)));
+        assert_eq!(keymap.conflicts(ViewAction::HideNode), vec![ViewAction::ExpandNode]);
+        keymap.set_binding(ViewAction::ExpandNode, None);
+        assert_eq!(keymap.binding(ViewAction::ExpandNode), None);
+        assert_eq!(keymap.hint(ViewAction::ExpandNode), "Expand Selected Node");
+
+        // default bindings are not stored
+        keymap.set_binding(ViewAction::HideNode, Some(KeyBinding::key(Key::H)));
+        assert_eq!(keymap.bindings.len(), 1);
+
+        let json = serde_json::to_string(&keymap).unwrap();
+        let restored: Keymap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.binding(ViewAction::ExpandNode), None);
+        assert_eq!(restored.binding(ViewAction::HideNode), Some(KeyBinding::key(Key::H)));
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::BTreeSet;
+
+use crate::{
+    IriIndex,
+    domain::{NodeData, statistics::StatisticsData},
+};
+
+/// Value source for one plot axis
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MetricSource {
+    // index of the statistics result
+    Statistic(usize),
+    // numeric data property
+    Property(IriIndex),
+}
+
+impl MetricSource {
+    fn value(&self, statistics_data: &StatisticsData, node_data: &NodeData, node_pos: usize) -> Option<f64> {
+        match self {
+            MetricSource::Statistic(result_index) => statistics_data
+                .results
+                .get(*result_index)
+                .and_then(|result| result.get_data_vec().get(node_pos))
+                .map(|value| *value as f64),
+            MetricSource::Property(predicate_index) => {
+                let (node_index, _) = statistics_data.nodes.get(node_pos)?;
+                property_number(node_data, *node_index, *predicate_index)
+            }
+        }
+    }
+}
+
+/// First numeric value of the data property of the node
+pub fn property_number(node_data: &NodeData, node_index: IriIndex, predicate_index: IriIndex) -> Option<f64> {
+    let (_, node) = node_data.get_node_by_index(node_index)?;
+    node.properties
+        .iter()
+        .filter(|(property_index, _)| *property_index == predicate_index)
+        .find_map(|(_, literal)| parse_number(literal.as_str_ref(&node_data.indexers)))
+}
+
+fn parse_number(value: &str) -> Option<f64> {
+    value.trim().parse::<f64>().ok().filter(|number| number.is_finite())
+}
+
+/// Data properties of the statistics nodes that have at least one numeric value
+pub fn numeric_properties(statistics_data: &StatisticsData, node_data: &NodeData) -> Vec<IriIndex> {
+    numeric_properties_of(statistics_data.nodes.iter().map(|(node_index, _)| *node_index), node_data)
+}
+
+/// Data properties of the nodes that have at least one numeric value
+pub fn numeric_properties_of(node_indexes: impl Iterator<Item = IriIndex>, node_data: &NodeData) -> Vec<IriIndex> {
+    let mut predicates: BTreeSet<IriIndex> = BTreeSet::new();
+    for node_index in node_indexes {
+        if let Some((_, node)) = node_data.get_node_by_index(node_index) {
+            for (predicate_index, literal) in node.properties.iter() {
+                if !predicates.contains(predicate_index)
+                    && parse_number(literal.as_str_ref(&node_data.indexers)).is_some()
+                {
+                    predicates.insert(*predicate_index);
+                }
+            }
+        }
+    }
+    predicates.into_iter().collect()
+}
+
+pub struct CorrelationPoints {
+    pub nodes: Vec<IriIndex>,
+    pub points: Vec<[f64; 2]>,
+}
+
+impl CorrelationPoints {
+    /// Only nodes that have a value for both axes are included
+    pub fn new(statistics_data: &StatisticsData, node_data: &NodeData, x: MetricSource, y: MetricSource) -> Self {
+        let mut nodes: Vec<IriIndex> = Vec::new();
+        let mut points: Vec<[f64; 2]> = Vec::new();
+        for (node_pos, (node_index, _)) in statistics_data.nodes.iter().enumerate() {
+            if let Some(x_value) = x.value(statistics_data, node_data, node_pos)
+                && let Some(y_value) = y.value(statistics_data, node_data, node_pos)
+            {
+                nodes.push(*node_index);
+                points.push([x_value, y_value]);
+            }
+        }
+        Self { nodes, points }
+    }
+
+    /// Pearson correlation coefficient, None if there are too few points or one axis is constant
+    pub fn pearson(&self) -> Option<f64> {
+        let len = self.points.len() as f64;
+        if self.points.len() < 2 {
+            return None;
+        }
+        let mean_x = self.points.iter().map(|point| point[0]).sum::<f64>() / len;
+        let mean_y = self.points.iter().map(|point| point[1]).sum::<f64>() / len;
+        let mut covariance = 0.0;
+        let mut variance_x = 0.0;
+        let mut variance_y = 0.0;
+        for point in self.points.iter() {
+            let dx = point[0] - mean_x;
+            let dy = point[1] - mean_y;
+            covariance += dx * dy;
+            variance_x += dx * dx;
+            variance_y += dy * dy;
+        }
+        if variance_x == 0.0 || variance_y == 0.0 {
+            return None;
+        }
+        Some(covariance / (variance_x.sqrt() * variance_y.sqrt()))
+    }
+
+    /// Nodes whose point lies in the rectangle given by two corners
+    pub fn nodes_in_rect(&self, corner1: [f64; 2], corner2: [f64; 2]) -> Vec<IriIndex> {
+        let (min_x, max_x) = (corner1[0].min(corner2[0]), corner1[0].max(corner2[0]));
+        let (min_y, max_y) = (corner1[1].min(corner2[1]), corner1[1].max(corner2[1]));
+        self.points
+            .iter()
+            .zip(self.nodes.iter())
+            .filter(|(point, _)| point[0] >= min_x && point[0] <= max_x && point[1] >= min_y && point[1] <= max_y)
+            .map(|(_, node_index)| *node_index)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        domain::{RdfData, statistics::StatisticsResult},
+        graph_algorithms::StatisticValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_correlation_points() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA ex: <http://example.org/> .
+A ex:a ex:total "10" .
+A ex:b ex:total "20.5" .
+A ex:c ex:total "30" .
+A ex:c ex:name "c" .
+A ex:d ex:name "d" .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let node = |name: &str| node_data.get_node_index(&format!("http://example.org/{}", name)).unwrap();
+        let mut statistics_data = StatisticsData {
+            nodes: vec![(node("a"), 0), (node("b"), 1), (node("c"), 2), (node("d"), 3)],
+            ..Default::default()
+        };
+        statistics_data.results.push(StatisticsResult::new_for_values(
+            vec![1.0, 2.0, 3.0, 4.0],
+            StatisticValue::DegreeCentrality,
+        ));
+        let total = node_data
+            .find_predicate_index(&rdf_data.prefix_manager, "http://example.org/total")
+            .unwrap();
+        assert_eq!(vec![total], numeric_properties(&statistics_data, node_data));
+        let points = CorrelationPoints::new(
+            &statistics_data,
+            node_data,
+            MetricSource::Statistic(0),
+            MetricSource::Property(total),
+        );
+        // d has no total
+        assert_eq!(3, points.points.len());
+        assert_eq!([2.0, 20.5], points.points[1]);
+        assert!(points.pearson().unwrap() > 0.99);
+        assert_eq!(vec![node("a"), node("b")], points.nodes_in_rect([2.5, 0.0], [0.0, 25.0]));
+    }
+}
@@ -9,6 +9,40 @@ pub mod graph_styles;
 pub mod visual_query;
 pub mod type_index;
 pub mod reference_resolver;
+pub mod import_log;
+pub mod recent_items;
+pub mod session_statistics;
+pub mod node_comparison;
+pub mod data_quality;
+pub mod skos_validation;
+pub mod transitive_closure;
+pub mod metric_correlation;
+pub mod type_metric_summary;
+pub mod edge_metadata;
+pub mod identifier_resolution;
+pub mod semantic_zoom;
+pub mod node_list;
+pub mod app_log;
+pub mod render_mode;
+pub mod wikidata;
+pub mod dcat;
+pub mod commands;
+pub mod social_chart;
+pub mod blank_node_tree;
+pub mod named_graphs;
+pub mod column_chart;
+pub mod dataset_diff;
+pub mod data_change;
+pub mod ontology;
+pub mod geo;
+pub mod type_samples;
+pub mod timeline;
+pub mod graph_snapshot;
+pub mod keymap;
+pub mod rdf_list;
+pub mod style_rules;
+pub mod size_mapping;
+pub mod change_set;
 
 pub use self::graph_model::*;
 pub use self::string_indexer::*;
@@ -0,0 +1,223 @@
+use std::collections::{HashMap, HashSet};
+
+use oxrdf::Triple;
+
+use crate::{
+    IriIndex,
+    domain::{Literal, StringIndexer},
+    support::SortedVec,
+};
+
+pub type GraphIndex = IriIndex;
+
+/// Graph names of deleted triples, so undo can add the triples to their graphs again
+pub type TripleGraphs = Vec<(Triple, Vec<String>)>;
+
+/// Identifies one stored triple independent of its position in the node
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum TripleKey {
+    // subject, type
+    Type(IriIndex, IriIndex),
+    // subject, predicate, object node
+    Reference(IriIndex, IriIndex, IriIndex),
+    // subject, predicate, literal
+    Property(IriIndex, IriIndex, Literal),
+}
+
+impl TripleKey {
+    pub fn subject(&self) -> IriIndex {
+        match self {
+            TripleKey::Type(subject, _) => *subject,
+            TripleKey::Reference(subject, _, _) => *subject,
+            TripleKey::Property(subject, _, _) => *subject,
+        }
+    }
+}
+
+/**
+ * Named graphs of triples read from N-Quads, TriG or JSON-LD.
+ * Only triples of named graphs are stored, all other triples belong to the default graph.
+ * A triple can be part of more graphs.
+ */
+#[derive(Clone, Default)]
+pub struct NamedGraphs {
+    pub graph_indexer: StringIndexer,
+    pub triple_graphs: HashMap<TripleKey, SortedVec>,
+}
+
+impl NamedGraphs {
+    pub fn add(&mut self, key: TripleKey, graph_name: &str) {
+        let graph_index = self.graph_indexer.get_index(graph_name);
+        self.triple_graphs.entry(key).or_insert_with(SortedVec::new).add(graph_index);
+    }
+
+    /// Removes the triple from all graphs, returns the names of the graphs
+    pub fn remove(&mut self, key: &TripleKey) -> Vec<String> {
+        self.triple_graphs
+            .remove(key)
+            .map(|graphs| {
+                graphs
+                    .data
+                    .iter()
+                    .filter_map(|graph_index| self.graph_indexer.index_to_str(*graph_index))
+                    .map(|graph_name| graph_name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triple_graphs.is_empty()
+    }
+
+    pub fn clean(&mut self) {
+        self.graph_indexer = StringIndexer::new();
+        self.triple_graphs.clear();
+    }
+
+    /// Graph names sorted by name
+    pub fn graphs(&self) -> Vec<(GraphIndex, &str)> {
+        let mut graphs: Vec<(GraphIndex, &str)> = (0..self.graph_indexer.map.len() as GraphIndex)
+            .filter_map(|graph_index| {
+                self.graph_indexer
+                    .index_to_str(graph_index)
+                    .map(|graph_name| (graph_index, graph_name))
+            })
+            .collect();
+        graphs.sort_by(|a, b| a.1.cmp(b.1));
+        graphs
+    }
+
+    pub fn graph_name(&self, graph_index: GraphIndex) -> Option<&str> {
+        self.graph_indexer.index_to_str(graph_index)
+    }
+
+    pub fn contains(&self, key: &TripleKey, graph_index: GraphIndex) -> bool {
+        self.triple_graphs
+            .get(key)
+            .is_some_and(|graphs| graphs.contains(graph_index))
+    }
+
+    pub fn triple_graphs(&self, key: &TripleKey) -> Option<&SortedVec> {
+        self.triple_graphs.get(key)
+    }
+
+    /// Subjects and referenced nodes of all triples of the graph
+    pub fn graph_nodes(&self, graph_index: GraphIndex) -> HashSet<IriIndex> {
+        let mut nodes = HashSet::new();
+        for (key, graphs) in self.triple_graphs.iter() {
+            if graphs.contains(graph_index) {
+                nodes.insert(key.subject());
+                if let TripleKey::Reference(_, _, object) = key {
+                    nodes.insert(*object);
+                }
+            }
+        }
+        nodes
+    }
+}
+
+/// Named graph selected in the table and graph views
+pub struct GraphFilter {
+    pub graph_index: GraphIndex,
+    pub nodes: HashSet<IriIndex>,
+}
+
+impl GraphFilter {
+    pub fn new(named_graphs: &NamedGraphs, graph_index: GraphIndex) -> Self {
+        Self {
+            graph_index,
+            nodes: named_graphs.graph_nodes(graph_index),
+        }
+    }
+
+    pub fn contains_node(&self, node_index: IriIndex) -> bool {
+        self.nodes.contains(&node_index)
+    }
+
+    pub fn contains_reference(&self, named_graphs: &NamedGraphs, edge_key: &(IriIndex, IriIndex, IriIndex)) -> bool {
+        named_graphs.contains(
+            &TripleKey::Reference(edge_key.0, edge_key.1, edge_key.2),
+            self.graph_index,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io, sync::RwLock};
+
+    use super::*;
+    use crate::{
+        domain::{RdfData, commands::DataHistory, config::Config},
+        integration::{rdf_patch::RdfPatch, rdfwrap::RDFWrap},
+    };
+
+    #[test]
+    fn test_named_graphs() {
+        let mut named_graphs = NamedGraphs::default();
+        assert!(named_graphs.is_empty());
+        named_graphs.add(TripleKey::Reference(1, 2, 3), "ex:g2");
+        named_graphs.add(TripleKey::Type(1, 7), "ex:g1");
+        named_graphs.add(TripleKey::Reference(1, 2, 3), "ex:g1");
+        named_graphs.add(TripleKey::Property(4, 2, Literal::StringShort(0)), "ex:g2");
+        let graphs = named_graphs.graphs();
+        assert_eq!(vec!["ex:g1", "ex:g2"], graphs.iter().map(|(_, name)| *name).collect::<Vec<_>>());
+        let g1 = graphs[0].0;
+        let g2 = graphs[1].0;
+        assert!(named_graphs.contains(&TripleKey::Reference(1, 2, 3), g1));
+        assert!(named_graphs.contains(&TripleKey::Reference(1, 2, 3), g2));
+        assert!(!named_graphs.contains(&TripleKey::Type(1, 7), g2));
+        assert_eq!(HashSet::from([1, 3, 4]), named_graphs.graph_nodes(g2));
+        let filter = GraphFilter::new(&named_graphs, g1);
+        assert!(filter.contains_node(3));
+        assert!(!filter.contains_node(4));
+        assert!(filter.contains_reference(&named_graphs, &(1, 2, 3)));
+        named_graphs.clean();
+        assert!(named_graphs.graphs().is_empty());
+    }
+
+    #[test]
+    fn test_graphs_after_delete_and_undo() {
+        let rdf_data = RwLock::new(RdfData::empty());
+        let data = r#"
+@prefix ex: <http://example.org/> .
+ex:g1 { ex:a ex:knows ex:c . ex:a ex:name "Anna" . }
+ex:g2 { ex:a ex:knows ex:c . ex:d a ex:Person . }
+"#;
+        RDFWrap::load_file_reader("graphs.trig", "trig", "graphs", io::Cursor::new(data), &rdf_data, &[], None).unwrap();
+        let mut rdf_data = rdf_data.into_inner().unwrap();
+        let graphs: Vec<GraphIndex> = rdf_data.node_data.named_graphs.graphs().iter().map(|(graph, _)| *graph).collect();
+        let c = rdf_data.node_data.get_node_index("ex:c").unwrap();
+        let graph_nodes = |rdf_data: &RdfData| -> Vec<HashSet<IriIndex>> {
+            graphs.iter().map(|graph| rdf_data.node_data.named_graphs.graph_nodes(*graph)).collect()
+        };
+        assert!(graph_nodes(&rdf_data).iter().all(|nodes| nodes.contains(&c)));
+
+        let node_count = rdf_data.node_data.len();
+        let mut result = RdfPatch::parse_rdf_patch(
+            "D <http://example.org/a> <http://example.org/knows> <http://example.org/c> .\n",
+        )
+        .apply(&mut rdf_data, &[]);
+        assert_eq!(1, result.deleted);
+        assert_eq!(vec!["ex:g1".to_string(), "ex:g2".to_string()], result.deleted_graphs[0].1);
+        assert!(graph_nodes(&rdf_data).iter().all(|nodes| !nodes.contains(&c)));
+        assert_eq!(2, rdf_data.node_data.named_graphs.triple_graphs.len());
+
+        let mut history = DataHistory::default();
+        let config = Config::default();
+        history.record_triples(
+            "delete",
+            node_count,
+            std::mem::take(&mut result.applied),
+            std::mem::take(&mut result.deleted_graphs),
+            false,
+            &config,
+        );
+        history.undo(&mut rdf_data);
+        assert!(graph_nodes(&rdf_data).iter().all(|nodes| nodes.contains(&c)));
+        assert_eq!(3, rdf_data.node_data.named_graphs.triple_graphs.len());
+        history.redo(&mut rdf_data);
+        assert!(graph_nodes(&rdf_data).iter().all(|nodes| !nodes.contains(&c)));
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::BTreeSet;
+
+use crate::domain::{IriIndex, Literal, NodeData};
+
+pub const MIN_COMPARED_NODES: usize = 2;
+pub const MAX_COMPARED_NODES: usize = 5;
+
+pub struct ComparisonRow {
+    // None is the row for the types
+    pub predicate: Option<IriIndex>,
+    // Sorted values for every compared node
+    pub values: Vec<Vec<String>>,
+    // all nodes have at least one value
+    pub shared: bool,
+    pub differs: bool,
+}
+
+/**
+ * Compares properties and references of few nodes. One column per node and one row per predicate.
+ */
+pub struct NodeComparison {
+    pub nodes: Vec<IriIndex>,
+    pub rows: Vec<ComparisonRow>,
+    pub only_differences: bool,
+}
+
+impl NodeComparison {
+    pub fn new(node_data: &NodeData, nodes: &[IriIndex]) -> Self {
+        let (nodes, node_objects): (Vec<IriIndex>, Vec<_>) = nodes
+            .iter()
+            .filter_map(|node_index| {
+                node_data
+                    .get_node_by_index(*node_index)
+                    .map(|(_, node)| (*node_index, node))
+            })
+            .take(MAX_COMPARED_NODES)
+            .unzip();
+        let mut predicates: BTreeSet<IriIndex> = BTreeSet::new();
+        for node in node_objects.iter() {
+            predicates.extend(node.properties.iter().map(|(predicate, _)| *predicate));
+            predicates.extend(node.references.iter().map(|(predicate, _)| *predicate));
+        }
+
+        let mut rows = Vec::with_capacity(predicates.len() + 1);
+        let type_values: Vec<Vec<String>> = node_objects
+            .iter()
+            .map(|node| {
+                node.types
+                    .iter()
+                    .filter_map(|type_index| node_data.get_type(*type_index))
+                    .map(|type_iri| type_iri.to_string())
+                    .collect()
+            })
+            .collect();
+        rows.push(ComparisonRow::new(None, type_values));
+        for predicate in predicates.iter() {
+            let values: Vec<Vec<String>> = node_objects
+                .iter()
+                .map(|node| {
+                    let mut values: Vec<String> = node
+                        .properties
+                        .iter()
+                        .filter(|(property_predicate, _)| property_predicate == predicate)
+                        .map(|(_, literal)| literal_value(node_data, literal))
+                        .collect();
+                    values.extend(
+                        node.references
+                            .iter()
+                            .filter(|(reference_predicate, _)| reference_predicate == predicate)
+                            .filter_map(|(_, reference)| node_data.get_node_by_index(*reference))
+                            .map(|(iri, _)| iri.to_string()),
+                    );
+                    values
+                })
+                .collect();
+            rows.push(ComparisonRow::new(Some(*predicate), values));
+        }
+        // Shared predicates are first, so they are aligned at the top of the table
+        rows[1..].sort_by_key(|row| !row.shared);
+        Self {
+            nodes,
+            rows,
+            only_differences: false,
+        }
+    }
+
+    pub fn differing_rows(&self) -> usize {
+        self.rows.iter().filter(|row| row.differs).count()
+    }
+}
+
+impl ComparisonRow {
+    fn new(predicate: Option<IriIndex>, mut values: Vec<Vec<String>>) -> Self {
+        for node_values in values.iter_mut() {
+            node_values.sort();
+        }
+        let shared = values.iter().all(|node_values| !node_values.is_empty());
+        let differs = values.windows(2).any(|pair| pair[0] != pair[1]);
+        Self {
+            predicate,
+            values,
+            shared,
+            differs,
+        }
+    }
+}
+
+fn literal_value(node_data: &NodeData, literal: &Literal) -> String {
+    let value = literal.as_str_ref(&node_data.indexers);
+    match literal {
+        Literal::LangString(language_index, _) => match node_data.get_language(*language_index) {
+            Some(language) => format!("{}@{}", value, language),
+            None => value.to_string(),
+        },
+        _ => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_node_comparison() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA ex: <http://example.org/> .
+A ex:a a ex:Person .
+A ex:a ex:name "Anna" .
+A ex:a ex:knows ex:c .
+A ex:a ex:email "anna@example.org" .
+A ex:b a ex:Person .
+A ex:b ex:name "Anna" .
+A ex:b ex:knows ex:d .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let a = node_data.get_node_index("http://example.org/a").unwrap();
+        let b = node_data.get_node_index("http://example.org/b").unwrap();
+        let comparison = NodeComparison::new(node_data, &[a, b]);
+        assert_eq!(4, comparison.rows.len());
+        assert!(comparison.rows[0].predicate.is_none());
+        assert!(!comparison.rows[0].differs);
+        // not shared email is the last row
+        let last = comparison.rows.last().unwrap();
+        assert!(!last.shared);
+        assert_eq!(vec!["anna@example.org".to_string()], last.values[0]);
+        assert!(last.values[1].is_empty());
+        // knows and email differs
+        assert_eq!(2, comparison.differing_rows());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    IriIndex,
+    domain::{NodeData, prefix_manager::PrefixManager},
+};
+
+/// Splits the text (clipboard content, file with IRIs, query result) into IRI tokens.
+/// Tokens are separated by whitespace, commas or semicolons, IRIs could be written as <iri>,
+/// lines starting with # are comments
+pub fn parse_iri_list(text: &str) -> Vec<&str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ',' || c == ';'))
+        .map(|token| token.trim_matches(|c| c == '<' || c == '>' || c == '"'))
+        .filter(|token| !token.is_empty())
+        .collect()
+}
+
+/// Resolves the IRIs (full or prefixed) to node indexes, returns also the IRIs that are not known
+pub fn resolve_node_list(
+    text: &str,
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+) -> (Vec<IriIndex>, Vec<String>) {
+    let mut nodes: Vec<IriIndex> = Vec::new();
+    let mut unknown: Vec<String> = Vec::new();
+    for iri in parse_iri_list(text) {
+        let node_index = node_data.get_node_index(iri).or_else(|| {
+            prefix_manager
+                .get_full_opt(iri)
+                .and_then(|full_iri| node_data.get_node_index(&full_iri))
+        });
+        match node_index {
+            Some(node_index) => {
+                if !nodes.contains(&node_index) {
+                    nodes.push(node_index);
+                }
+            }
+            None => unknown.push(iri.to_string()),
+        }
+    }
+    (nodes, unknown)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_resolve_node_list() {
+        let mut rdf_data = RdfData::from_patch(
+            r#"
+PA ex: <http://example.org/> .
+A ex:a ex:knows ex:b .
+A ex:b ex:knows ex:c .
+"#,
+        );
+        rdf_data.prefix_manager.add_prefix("ex", "http://example.org/");
+        let text = "# selected nodes\n<http://example.org/a>, ex:c\nhttp://example.org/a ex:unknown\n";
+        assert_eq!(
+            vec!["http://example.org/a", "ex:c", "http://example.org/a", "ex:unknown"],
+            parse_iri_list(text)
+        );
+        let (nodes, unknown) = resolve_node_list(text, &rdf_data.node_data, &rdf_data.prefix_manager);
+        assert_eq!(
+            vec![
+                rdf_data.node_data.get_node_index("http://example.org/a").unwrap(),
+                rdf_data.node_data.get_node_index("http://example.org/c").unwrap()
+            ],
+            nodes
+        );
+        assert_eq!(vec!["ex:unknown".to_string()], unknown);
+    }
+}
@@ -0,0 +1,220 @@
+use std::collections::{HashMap, HashSet};
+
+use string_interner::Symbol;
+
+use crate::{
+    IriIndex,
+    domain::{NodeData, type_index::TypeData},
+};
+
+// predicates are stored prefixed, rdfs is a default prefix
+const RDFS_SUB_CLASS_OF: &str = "rdfs:subClassOf";
+
+/**
+ * Class hierarchy of the types that have instances.
+ * It is build from rdfs:subClassOf references between the class nodes.
+ * Classes without instances are skipped, so a type is linked to the next super class that has instances.
+ */
+pub struct TypeHierarchy {
+    // predicate index of rdfs:subClassOf, used for the inheritance edges
+    pub sub_class_of: IriIndex,
+    pub super_types: HashMap<IriIndex, Vec<IriIndex>>,
+    pub sub_types: HashMap<IriIndex, Vec<IriIndex>>,
+    // instances of the type and all its (transitive) sub types
+    transitive_instances: HashMap<IriIndex, usize>,
+}
+
+impl TypeHierarchy {
+    /// Returns None if the data do not define any sub class relation between the types
+    pub fn new(node_data: &NodeData, types: &HashMap<IriIndex, TypeData>) -> Option<Self> {
+        let sub_class_of = node_data
+            .indexers
+            .predicate_indexer
+            .map
+            .get(RDFS_SUB_CLASS_OF)
+            .map(|symbol| symbol.to_usize() as IriIndex)?;
+        let mut super_types: HashMap<IriIndex, Vec<IriIndex>> = HashMap::new();
+        for type_index in types.keys() {
+            let Some(class_node) = node_data.get_type(*type_index).and_then(|iri| node_data.get_node_index(iri))
+            else {
+                continue;
+            };
+            let mut visited: HashSet<IriIndex> = HashSet::from([class_node]);
+            let mut to_visit = vec![class_node];
+            while let Some(node_index) = to_visit.pop() {
+                let Some((_, node)) = node_data.get_node_by_index(node_index) else {
+                    continue;
+                };
+                for (predicate, super_class) in node.references.iter() {
+                    if *predicate != sub_class_of || !visited.insert(*super_class) {
+                        continue;
+                    }
+                    match class_type(node_data, *super_class) {
+                        Some(super_type) if super_type != *type_index && types.contains_key(&super_type) => {
+                            let type_supers = super_types.entry(*type_index).or_default();
+                            if !type_supers.contains(&super_type) {
+                                type_supers.push(super_type);
+                            }
+                        }
+                        _ => to_visit.push(*super_class),
+                    }
+                }
+            }
+        }
+        if super_types.is_empty() {
+            return None;
+        }
+        let mut sub_types: HashMap<IriIndex, Vec<IriIndex>> = HashMap::new();
+        for (type_index, supers) in super_types.iter() {
+            for super_type in supers {
+                sub_types.entry(*super_type).or_default().push(*type_index);
+            }
+        }
+        let mut hierarchy = Self {
+            sub_class_of,
+            super_types,
+            sub_types,
+            transitive_instances: HashMap::new(),
+        };
+        for (type_index, type_data) in types.iter() {
+            if !hierarchy.sub_types.contains_key(type_index) {
+                continue;
+            }
+            let mut instances: HashSet<IriIndex> = type_data.instances.iter().copied().collect();
+            for sub_type in hierarchy.descendants(*type_index) {
+                if let Some(sub_type_data) = types.get(&sub_type) {
+                    instances.extend(sub_type_data.instances.iter().copied());
+                }
+            }
+            hierarchy.transitive_instances.insert(*type_index, instances.len());
+        }
+        Some(hierarchy)
+    }
+
+    /// All sub types of the type without the type itself
+    pub fn descendants(&self, type_index: IriIndex) -> Vec<IriIndex> {
+        let mut descendants = Vec::new();
+        let mut visited: HashSet<IriIndex> = HashSet::from([type_index]);
+        let mut to_visit = vec![type_index];
+        while let Some(current) = to_visit.pop() {
+            if let Some(sub_types) = self.sub_types.get(&current) {
+                for sub_type in sub_types {
+                    if visited.insert(*sub_type) {
+                        descendants.push(*sub_type);
+                        to_visit.push(*sub_type);
+                    }
+                }
+            }
+        }
+        descendants
+    }
+
+    /// Count of instances including the instances of all sub types, None for types without sub types
+    pub fn transitive_instance_count(&self, type_index: IriIndex) -> Option<usize> {
+        self.transitive_instances.get(&type_index).copied()
+    }
+
+    /**
+     * Orders the types depth first under their super types and returns the depth of each type.
+     * The order of the siblings is kept from the given types.
+     * A type with more super types is placed only under the first one.
+     */
+    pub fn ordered(&self, types: &[IriIndex]) -> Vec<(IriIndex, usize)> {
+        let listed: HashSet<IriIndex> = types.iter().copied().collect();
+        let mut children: HashMap<IriIndex, Vec<IriIndex>> = HashMap::new();
+        let mut roots = Vec::new();
+        for type_index in types {
+            match self
+                .super_types
+                .get(type_index)
+                .and_then(|supers| supers.iter().find(|super_type| listed.contains(super_type)))
+            {
+                Some(super_type) => children.entry(*super_type).or_default().push(*type_index),
+                None => roots.push(*type_index),
+            }
+        }
+        let mut ordered = Vec::with_capacity(types.len());
+        let mut visited: HashSet<IriIndex> = HashSet::with_capacity(types.len());
+        for root in roots {
+            let mut to_visit = vec![(root, 0)];
+            while let Some((type_index, depth)) = to_visit.pop() {
+                if !visited.insert(type_index) {
+                    continue;
+                }
+                ordered.push((type_index, depth));
+                if let Some(type_children) = children.get(&type_index) {
+                    for child in type_children.iter().rev() {
+                        to_visit.push((*child, depth + 1));
+                    }
+                }
+            }
+        }
+        // types in sub class cycles have no root
+        for type_index in types {
+            if !visited.contains(type_index) {
+                ordered.push((*type_index, 0));
+            }
+        }
+        ordered
+    }
+}
+
+fn class_type(node_data: &NodeData, class_node: IriIndex) -> Option<IriIndex> {
+    let (class_iri, _) = node_data.get_node_by_index(class_node)?;
+    node_data
+        .indexers
+        .type_indexer
+        .map
+        .get(class_iri)
+        .map(|symbol| symbol.to_usize() as IriIndex)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        domain::{RdfData, type_index::TypeInstanceIndex},
+        integration::rdf_patch::RdfPatch,
+    };
+
+    #[test]
+    fn test_type_hierarchy() {
+        let mut rdf_data = RdfData::empty();
+        rdf_data.prefix_manager.add_prefix("ex", "http://example.org/");
+        // Mammal has no instances, so Cat is linked directly to Animal
+        let patch = r#"
+A <http://example.org/Cat> <http://www.w3.org/2000/01/rdf-schema#subClassOf> <http://example.org/Mammal> .
+A <http://example.org/Mammal> <http://www.w3.org/2000/01/rdf-schema#subClassOf> <http://example.org/Animal> .
+A <http://example.org/Dog> <http://www.w3.org/2000/01/rdf-schema#subClassOf> <http://example.org/Animal> .
+A <http://example.org/tom> a <http://example.org/Cat> .
+A <http://example.org/rex> a <http://example.org/Dog> .
+A <http://example.org/a> a <http://example.org/Animal> .
+A <http://example.org/both> a <http://example.org/Cat> .
+A <http://example.org/both> a <http://example.org/Dog> .
+A <http://example.org/car> a <http://example.org/Car> .
+"#;
+        RdfPatch::parse_rdf_patch(patch).apply(&mut rdf_data, &[]);
+        let mut type_index = TypeInstanceIndex::new();
+        type_index.update(&rdf_data.node_data);
+        let type_of = |iri: &str| rdf_data.node_data.find_type_index(&rdf_data.prefix_manager, iri).unwrap();
+        let animal = type_of("http://example.org/Animal");
+        let cat = type_of("http://example.org/Cat");
+        let dog = type_of("http://example.org/Dog");
+        let car = type_of("http://example.org/Car");
+        let hierarchy = type_index.type_hierarchy.as_ref().unwrap();
+        assert_eq!(Some(&vec![animal]), hierarchy.super_types.get(&cat));
+        assert_eq!(None, hierarchy.super_types.get(&car));
+        let mut descendants = hierarchy.descendants(animal);
+        descendants.sort();
+        let mut expected = vec![cat, dog];
+        expected.sort();
+        assert_eq!(expected, descendants);
+        // the instance with both types is counted once
+        assert_eq!(Some(4), hierarchy.transitive_instance_count(animal));
+        assert_eq!(None, hierarchy.transitive_instance_count(cat));
+
+        let ordered = hierarchy.ordered(&[cat, car, animal, dog]);
+        assert_eq!(vec![(car, 0), (animal, 0), (cat, 1), (dog, 1)], ordered);
+        // without the super type the sub types are roots
+        assert_eq!(vec![(cat, 0), (dog, 0)], hierarchy.ordered(&[cat, dog]));
+    }
+}
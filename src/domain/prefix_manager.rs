@@ -1,5 +1,109 @@
+use std::collections::HashMap;
+
 use bimap::BiMap;
 
+/// Prefixes of well-known vocabularies, used to suggest prefixes for namespaces without prefix
+const VOCABULARY_CATALOG: &[(&str, &str)] = &[
+    ("acl", "http://www.w3.org/ns/auth/acl#"),
+    ("adms", "http://www.w3.org/ns/adms#"),
+    ("as", "https://www.w3.org/ns/activitystreams#"),
+    ("bibo", "http://purl.org/ontology/bibo/"),
+    ("bf", "http://id.loc.gov/ontologies/bibframe/"),
+    ("cc", "http://creativecommons.org/ns#"),
+    ("csvw", "http://www.w3.org/ns/csvw#"),
+    ("dcam", "http://purl.org/dc/dcam/"),
+    ("dcat", "http://www.w3.org/ns/dcat#"),
+    ("dce", "http://purl.org/dc/elements/1.1/"),
+    ("dcmitype", "http://purl.org/dc/dcmitype/"),
+    ("dcterms", "http://purl.org/dc/terms/"),
+    ("doap", "http://usefulinc.com/ns/doap#"),
+    ("dqv", "http://www.w3.org/ns/dqv#"),
+    ("ebucore", "http://www.ebu.ch/metadata/ontologies/ebucore/ebucore#"),
+    ("event", "http://purl.org/NET/c4dm/event.owl#"),
+    ("fabio", "http://purl.org/spar/fabio/"),
+    ("geo", "http://www.w3.org/2003/01/geo/wgs84_pos#"),
+    ("geof", "http://www.opengis.net/def/function/geosparql/"),
+    ("gn", "http://www.geonames.org/ontology#"),
+    ("gr", "http://purl.org/goodrelations/v1#"),
+    ("gs1", "https://gs1.org/voc/"),
+    ("hydra", "http://www.w3.org/ns/hydra/core#"),
+    ("ldp", "http://www.w3.org/ns/ldp#"),
+    ("locn", "http://www.w3.org/ns/locn#"),
+    ("ma", "http://www.w3.org/ns/ma-ont#"),
+    ("mo", "http://purl.org/ontology/mo/"),
+    ("odrl", "http://www.w3.org/ns/odrl/2/"),
+    ("org", "http://www.w3.org/ns/org#"),
+    ("p", "http://www.wikidata.org/prop/"),
+    ("ps", "http://www.wikidata.org/prop/statement/"),
+    ("pq", "http://www.wikidata.org/prop/qualifier/"),
+    ("qb", "http://purl.org/linked-data/cube#"),
+    ("rdfa", "http://www.w3.org/ns/rdfa#"),
+    ("rel", "http://purl.org/vocab/relationship/"),
+    ("rr", "http://www.w3.org/ns/r2rml#"),
+    ("schema", "http://schema.org/"),
+    ("sh", "http://www.w3.org/ns/shacl#"),
+    ("sioc", "http://rdfs.org/sioc/ns#"),
+    ("sosa", "http://www.w3.org/ns/sosa/"),
+    ("ssn", "http://www.w3.org/ns/ssn/"),
+    ("time", "http://www.w3.org/2006/time#"),
+    ("vann", "http://purl.org/vocab/vann/"),
+    ("vcard", "http://www.w3.org/2006/vcard/ns#"),
+    ("void", "http://rdfs.org/ns/void#"),
+    ("wd", "http://www.wikidata.org/entity/"),
+    ("wdt", "http://www.wikidata.org/prop/direct/"),
+    ("wikibase", "http://wikiba.se/ontology#"),
+    ("xhv", "http://www.w3.org/1999/xhtml/vocab#"),
+];
+
+/// Prefix proposed for a namespace that is used in the data but has no prefix
+#[derive(Clone, PartialEq, Debug)]
+pub struct PrefixSuggestion {
+    pub prefix: String,
+    pub namespace: String,
+    // number of iris (nodes, types, predicates) in the namespace
+    pub count: usize,
+}
+
+/// Namespace of a full iri (up to the last '#' or '/'), None for prefixed iris and blank nodes
+pub fn namespace_of(iri: &str) -> Option<&str> {
+    if !iri.contains("://") {
+        return None;
+    }
+    let delimiter_pos = iri.rfind(&['#', '/'][..])?;
+    Some(&iri[..delimiter_pos + 1])
+}
+
+/// Prefix of the namespace in the bundled catalog of well-known vocabularies
+pub fn catalog_prefix(namespace: &str) -> Option<&'static str> {
+    VOCABULARY_CATALOG
+        .iter()
+        .find(|(_, catalog_namespace)| *catalog_namespace == namespace)
+        .map(|(prefix, _)| *prefix)
+}
+
+/// Looks up the most popular prefix of the namespace on prefix.cc, None if the namespace is not registered
+#[cfg(not(target_arch = "wasm32"))]
+pub fn lookup_prefix_cc(namespace: &str) -> anyhow::Result<Option<String>> {
+    use percent_encoding::{NON_ALPHANUMERIC, utf8_percent_encode};
+
+    let url = format!(
+        "https://prefix.cc/reverse?uri={}&format=json",
+        utf8_percent_encode(namespace, NON_ALPHANUMERIC)
+    );
+    let response = reqwest::blocking::Client::new()
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "RDFGlance")
+        .send()?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let json: serde_json::Value = serde_json::from_slice(&response.error_for_status()?.bytes()?)?;
+    Ok(json
+        .as_object()
+        .and_then(|prefixes| prefixes.keys().next().cloned()))
+}
+
+#[derive(Clone)]
 pub struct PrefixManager {
     // key is the full iri and value is the prefix
     pub prefixes: BiMap<Box<str>, Box<str>>,
@@ -133,6 +237,46 @@ impl PrefixManager {
         }
     }
 
+    /// Namespaces of the full iris (not covered by a prefix) with the number of their iris, most used first
+    pub fn unknown_namespaces<'a>(&self, iris: impl Iterator<Item = &'a str>) -> Vec<(String, usize)> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for iri in iris {
+            if let Some(namespace) = namespace_of(iri)
+                && !self.prefixes.contains_left(namespace)
+            {
+                *counts.entry(namespace).or_insert(0) += 1;
+            }
+        }
+        let mut namespaces: Vec<(String, usize)> =
+            counts.into_iter().map(|(namespace, count)| (namespace.to_string(), count)).collect();
+        namespaces.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        namespaces
+    }
+
+    /**
+     * Suggestions from the catalog for the unknown namespaces.
+     * Prefixes that are already used (also by another suggestion) are not suggested.
+     */
+    pub fn suggest_prefixes(&self, namespaces: &[(String, usize)]) -> Vec<PrefixSuggestion> {
+        let mut suggestions: Vec<PrefixSuggestion> = Vec::new();
+        for (namespace, count) in namespaces {
+            if let Some(prefix) = catalog_prefix(namespace)
+                && self.is_free_prefix(prefix, &suggestions)
+            {
+                suggestions.push(PrefixSuggestion {
+                    prefix: prefix.to_string(),
+                    namespace: namespace.clone(),
+                    count: *count,
+                });
+            }
+        }
+        suggestions
+    }
+
+    pub fn is_free_prefix(&self, prefix: &str, suggestions: &[PrefixSuggestion]) -> bool {
+        !self.prefixes.contains_right(prefix) && !suggestions.iter().any(|suggestion| suggestion.prefix == prefix)
+    }
+
     pub fn clean(&mut self) {
         self.prefixes.clear();
         self.add_defaults();
@@ -159,4 +303,32 @@ mod tests {
         prefix_manager.clean();
         assert_eq!(prefix_manager.get_full_opt("atk:Foo"),None);
     }
+
+    #[test]
+    fn test_suggest_prefixes() {
+        let prefix_manager = PrefixManager::new();
+        assert_eq!(namespace_of("http://www.w3.org/ns/dcat#Dataset"), Some("http://www.w3.org/ns/dcat#"));
+        assert_eq!(namespace_of("dcat:Dataset"), None);
+        let iris = [
+            "http://www.w3.org/ns/dcat#Dataset",
+            "http://www.w3.org/ns/dcat#distribution",
+            "http://example.org/data/1",
+            "http://www.w3.org/ns/org#Organization",
+            "rdfs:label",
+        ];
+        let namespaces = prefix_manager.unknown_namespaces(iris.into_iter());
+        assert_eq!(
+            namespaces,
+            vec![
+                ("http://www.w3.org/ns/dcat#".to_string(), 2),
+                ("http://example.org/data/".to_string(), 1),
+                ("http://www.w3.org/ns/org#".to_string(), 1),
+            ]
+        );
+        let suggestions = prefix_manager.suggest_prefixes(&namespaces);
+        assert_eq!(2, suggestions.len());
+        assert_eq!("dcat", suggestions[0].prefix);
+        assert_eq!(2, suggestions[0].count);
+        assert_eq!("org", suggestions[1].prefix);
+    }
 }
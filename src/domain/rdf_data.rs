@@ -1,12 +1,13 @@
 use fixedbitset::FixedBitSet;
 use std::{cmp::Reverse, collections::{BTreeSet, BinaryHeap, HashSet}};
 
-use crate::{IriIndex, domain::{NodeData, config::Config, prefix_manager::PrefixManager}, 
+use crate::{IriIndex, domain::{NObject, NodeData, blank_node_tree::{blank_node_roots, composite_references, is_blank_node}, config::Config, prefix_manager::PrefixManager}, 
     integration::rdfwrap::RDFAdapter, support::SortedVec, 
     ui::graph_view::{NeighborPos, update_layout_edges}, 
     uistate::layout::SortedNodeLayout,
 };
 
+#[derive(Clone)]
 pub struct RdfData {
     pub node_data: NodeData,
     pub prefix_manager: PrefixManager,
@@ -33,28 +34,13 @@ impl RdfData {
         hidden_predicates: &SortedVec,
     ) -> bool {
         let mut refs_to_expand: Vec<(IriIndex,IriIndex)> = Vec::new();  
+        let flatten_blank_nodes = node_change_context.visible_nodes.flatten_blank_nodes;
         for iri_index in iri_indexes.iter() {
+            self.resolve_node(*iri_index, node_change_context.rdfwrap);
             let nnode = self.node_data.get_node_by_index(*iri_index);
             if let Some((_, nnode)) = nnode {
-                match expand_type {
-                    ExpandType::References | ExpandType::Both => {
-                        for (predicate, ref_iri) in &nnode.references {
-                            if !hidden_predicates.contains(*predicate) {
-                                refs_to_expand.push((*iri_index, *ref_iri));
-                            }
-                        }
-                    }
-                    _ => {}
-                }
-                match expand_type {
-                    ExpandType::ReverseReferences | ExpandType::Both => {
-                        for (predicate, ref_iri) in &nnode.reverse_references {
-                            if !hidden_predicates.contains(*predicate) {
-                                refs_to_expand.push((*iri_index, *ref_iri));
-                            }
-                        }
-                    }
-                    _ => {}
+                for ref_iri in self.expand_neighbors(*iri_index, nnode, &expand_type, hidden_predicates, flatten_blank_nodes) {
+                    refs_to_expand.push((*iri_index, ref_iri));
                 }
             }
         };
@@ -89,7 +75,14 @@ impl RdfData {
     ) -> bool {
         let mut refs_to_expand: HashSet<IriIndex> = HashSet::new();
         let mut parent_ref: Vec<(IriIndex, IriIndex)> = Vec::new();
-        for visible_index in node_change_context.visible_nodes.nodes.read().unwrap().iter() {
+        for visible_index in node_change_context
+            .visible_nodes
+            .layout_data
+            .read()
+            .unwrap()
+            .nodes
+            .iter()
+        {
             if let Some((_, nnode)) = self.node_data.get_node_by_index(visible_index.node_index) {
                 for (predicate, ref_iri) in nnode.references.iter() {
                     if !hidden_predicates.contains(*predicate) {
@@ -214,33 +207,56 @@ impl RdfData {
 
     pub fn load_object_by_index(&mut self, index: IriIndex, node_change_context: &mut NodeChangeContext) -> bool {
         let node = self.node_data.get_node_by_index_mut(index);
-        if let Some((node_iri, node)) = node {
+        if let Some((_, node)) = node {
             if node.has_subject {
                 return node_change_context.visible_nodes.add_by_index(index);
             } else {
-                let node_iri = node_iri.clone();
-                let new_object = node_change_context.rdfwrap.load_object(&node_iri, &mut self.node_data);
-                if let Some(new_object) = new_object {
-                    self.node_data.put_node_replace(&node_iri, new_object);
-                }
+                self.resolve_node(index, node_change_context.rdfwrap);
             }
         }
         false
     }
 
+    /**
+     * Loads the node from the adapter (SPARQL endpoint) if only its iri is known (the node was not subject in the data).
+     * The loaded node replaces the unresolved one, so it is loaded only once.
+     *
+     * Returns true if the node was resolved.
+     */
+    pub fn resolve_node(&mut self, index: IriIndex, rdfwrap: &mut Box<dyn RDFAdapter>) -> bool {
+        let node_iri = match self.node_data.get_node_by_index(index) {
+            Some((node_iri, node)) if !node.has_subject && !node.is_blank_node => node_iri.clone(),
+            _ => return false,
+        };
+        if let Some(new_object) = rdfwrap.load_object(&node_iri, &mut self.node_data) {
+            self.node_data.put_node_replace(&node_iri, new_object);
+            return true;
+        }
+        false
+    }
+
     pub fn expand_all(&mut self, node_change_context: &mut NodeChangeContext, hidden_predicates: &SortedVec) -> bool {
         let mut refs_to_expand: HashSet<IriIndex> = HashSet::new();
         let mut parent_ref: Vec<(IriIndex, IriIndex)> = Vec::new();
-        for visible_index in node_change_context.visible_nodes.nodes.read().unwrap().iter() {
+        let flatten_blank_nodes = node_change_context.visible_nodes.flatten_blank_nodes;
+        for visible_index in node_change_context
+            .visible_nodes
+            .layout_data
+            .read()
+            .unwrap()
+            .nodes
+            .iter()
+        {
             if let Some((_, nnode)) = self.node_data.get_node_by_index(visible_index.node_index) {
-                for (predicate, ref_iri) in nnode.references.iter() {
-                    if !hidden_predicates.contains(*predicate) && refs_to_expand.insert(*ref_iri) {
-                        parent_ref.push((visible_index.node_index, *ref_iri));
-                    }
-                }
-                for (predicate, ref_iri) in nnode.reverse_references.iter() {
-                    if !hidden_predicates.contains(*predicate) && refs_to_expand.insert(*ref_iri) {
-                        parent_ref.push((visible_index.node_index, *ref_iri));
+                for ref_iri in self.expand_neighbors(
+                    visible_index.node_index,
+                    nnode,
+                    &ExpandType::Both,
+                    hidden_predicates,
+                    flatten_blank_nodes,
+                ) {
+                    if refs_to_expand.insert(ref_iri) {
+                        parent_ref.push((visible_index.node_index, ref_iri));
                     }
                 }
             }
@@ -269,15 +285,66 @@ impl RdfData {
         }
     }
 
-    pub fn unexpand_all(&mut self, node_change_context: &mut NodeChangeContext, hidden_predicates: &SortedVec) -> bool {
-        let node_len = node_change_context.visible_nodes.nodes.read().unwrap().len();
-        if node_len == 0 {
-            return false;
+    /**
+     * Neighbor nodes that are added to the visual graph by expanding the node.
+     * If blank node trees are flattened the blank nodes are part of the node,
+     * so the named nodes referenced from the trees or referencing the trees are the neighbors.
+     */
+    fn expand_neighbors(
+        &self,
+        iri_index: IriIndex,
+        nnode: &NObject,
+        expand_type: &ExpandType,
+        hidden_predicates: &SortedVec,
+        flatten_blank_nodes: bool,
+    ) -> Vec<IriIndex> {
+        let mut neighbors = Vec::new();
+        if matches!(expand_type, ExpandType::References | ExpandType::Both) {
+            for (predicate, ref_iri) in &nnode.references {
+                let is_flattened = flatten_blank_nodes && is_blank_node(&self.node_data, *ref_iri);
+                if !hidden_predicates.contains(*predicate) && !is_flattened {
+                    neighbors.push(*ref_iri);
+                }
+            }
+            if flatten_blank_nodes {
+                for (predicate, ref_iri) in composite_references(&self.node_data, nnode) {
+                    if !hidden_predicates.contains(predicate) && ref_iri != iri_index {
+                        neighbors.push(ref_iri);
+                    }
+                }
+            }
         }
-        let mut nodes_bits = FixedBitSet::with_capacity(node_len);
-        for edge in node_change_context.visible_nodes.edges.read().unwrap().iter() {
-            nodes_bits.insert(edge.from);
+        if matches!(expand_type, ExpandType::ReverseReferences | ExpandType::Both) {
+            for (predicate, ref_iri) in &nnode.reverse_references {
+                if hidden_predicates.contains(*predicate) {
+                    continue;
+                }
+                if flatten_blank_nodes && is_blank_node(&self.node_data, *ref_iri) {
+                    neighbors.extend(
+                        blank_node_roots(&self.node_data, *ref_iri)
+                            .into_iter()
+                            .filter(|root_index| *root_index != iri_index),
+                    );
+                } else {
+                    neighbors.push(*ref_iri);
+                }
+            }
         }
+        neighbors
+    }
+
+    pub fn unexpand_all(&mut self, node_change_context: &mut NodeChangeContext, hidden_predicates: &SortedVec) -> bool {
+        let nodes_bits = {
+            let layout = node_change_context.visible_nodes.layout_data.read().unwrap();
+            if layout.nodes.is_empty() {
+                return false;
+            }
+            let mut nodes_bits = FixedBitSet::with_capacity(layout.nodes.len());
+            for edge in layout.edges.iter() {
+                nodes_bits.insert(edge.from);
+            }
+            nodes_bits
+        };
         if nodes_bits.is_full() {
             false
         } else {
@@ -293,4 +360,33 @@ impl RdfData {
     pub fn resolve_rdf_lists(&mut self) {
         self.node_data.resolve_rdf_lists(&self.prefix_manager);
     }
+
+    /// Changes of the data that must be done before the type index is built
+    pub fn prepare_for_index(&mut self, resolve_rdf_lists: bool) {
+        if resolve_rdf_lists {
+            self.resolve_rdf_lists();
+        }
+        for (_iri, node) in self.node_data.iter_mut() {
+            node.references.sort_by_key(|a| a.0);
+            node.reverse_references.sort_by_key(|a| a.0);
+        }
+    }
+}
+
+#[cfg(test)]
+impl RdfData {
+    /// Empty data for the tests
+    pub fn empty() -> Self {
+        RdfData {
+            node_data: NodeData::new(),
+            prefix_manager: PrefixManager::new(),
+        }
+    }
+
+    /// Test data with the triples of the rdf patch text
+    pub fn from_patch(patch: &str) -> Self {
+        let mut rdf_data = Self::empty();
+        crate::integration::rdf_patch::RdfPatch::parse_rdf_patch(patch).apply(&mut rdf_data, &[]);
+        rdf_data
+    }
 }
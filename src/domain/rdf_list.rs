@@ -0,0 +1,251 @@
+use std::collections::{HashMap, HashSet};
+
+use oxrdf::vocab::rdf;
+use string_interner::Symbol;
+
+use crate::{
+    IriIndex,
+    domain::{Literal, NObject, NodeData},
+};
+
+// longer rdf lists are cut
+pub const MAX_LIST_LEN: usize = 1000;
+
+/// Member of a rdf list
+#[derive(Clone, PartialEq)]
+pub enum ListMember {
+    Literal(Literal),
+    Node(IriIndex),
+}
+
+/// rdf list (rdf:first/rdf:rest chain) with its members in order
+#[derive(Clone)]
+pub struct RdfList {
+    // node that references the head of the list
+    pub holder: IriIndex,
+    pub predicate: IriIndex,
+    pub head: IriIndex,
+    pub members: Vec<ListMember>,
+}
+
+impl RdfList {
+    /// Zero based position of the node in the list
+    pub fn position(&self, node_index: IriIndex) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| *member == ListMember::Node(node_index))
+    }
+}
+
+/**
+ * Lists resolved by loading.
+ * The holder references the members directly (the list nodes are skipped),
+ * so the order of the members is only known from here.
+ */
+#[derive(Clone, Default)]
+pub struct RdfLists {
+    lists: Vec<RdfList>,
+    by_holder: HashMap<IriIndex, Vec<usize>>,
+}
+
+impl RdfLists {
+    /// The same list (holder, predicate and head) resolved again replaces the old one
+    pub fn add(&mut self, list: RdfList) {
+        let positions = self.by_holder.entry(list.holder).or_default();
+        if let Some(pos) = positions.iter().find(|pos| {
+            let known = &self.lists[**pos];
+            known.predicate == list.predicate && known.head == list.head
+        }) {
+            self.lists[*pos] = list;
+        } else {
+            positions.push(self.lists.len());
+            self.lists.push(list);
+        }
+    }
+
+    pub fn of_node(&self, holder: IriIndex) -> impl Iterator<Item = &RdfList> {
+        self.by_holder
+            .get(&holder)
+            .into_iter()
+            .flat_map(|positions| positions.iter().map(|pos| &self.lists[*pos]))
+    }
+
+    /// Position of the member in the list of the holder and predicate, used for the edge labels
+    pub fn member_position(&self, holder: IriIndex, predicate: IriIndex, member: IriIndex) -> Option<usize> {
+        self.of_node(holder)
+            .filter(|list| list.predicate == predicate)
+            .find_map(|list| list.position(member))
+    }
+
+    pub fn len(&self) -> usize {
+        self.lists.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lists.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.lists.clear();
+        self.by_holder.clear();
+    }
+}
+
+pub struct ListPredicates {
+    pub first: Option<IriIndex>,
+    pub rest: Option<IriIndex>,
+}
+
+impl ListPredicates {
+    // the predicates are stored prefixed if the rdf prefix is known
+    pub fn new(node_data: &NodeData) -> Self {
+        let find = |names: [&str; 2]| {
+            names.iter().find_map(|name| {
+                node_data
+                    .indexers
+                    .predicate_indexer
+                    .map
+                    .get(*name)
+                    .map(|symbol| symbol.to_usize() as IriIndex)
+            })
+        };
+        Self {
+            first: find(["rdf:first", rdf::FIRST.as_str()]),
+            rest: find(["rdf:rest", rdf::REST.as_str()]),
+        }
+    }
+
+    pub fn is_list_node(&self, node: &NObject) -> bool {
+        node.is_blank_node
+            && (node.properties.iter().any(|(predicate, _)| Some(*predicate) == self.first)
+                || node.references.iter().any(|(predicate, _)| Some(*predicate) == self.first))
+    }
+
+    /// Members of the list by following the rdf:rest chain from the head, cycles are stopped
+    pub fn members(&self, node_data: &NodeData, head: IriIndex) -> Vec<ListMember> {
+        let mut members = Vec::new();
+        let mut visited: HashSet<IriIndex> = HashSet::new();
+        let mut current = Some(head);
+        while let Some(node_index) = current
+            && members.len() < MAX_LIST_LEN
+            && visited.insert(node_index)
+        {
+            let Some((_, node)) = node_data.get_node_by_index(node_index) else {
+                break;
+            };
+            if !self.is_list_node(node) {
+                break;
+            }
+            members.extend(
+                node.properties
+                    .iter()
+                    .filter(|(predicate, _)| Some(*predicate) == self.first)
+                    .map(|(_, literal)| ListMember::Literal(literal.clone())),
+            );
+            members.extend(
+                node.references
+                    .iter()
+                    .filter(|(predicate, _)| Some(*predicate) == self.first)
+                    .map(|(_, member)| ListMember::Node(*member)),
+            );
+            current = node
+                .references
+                .iter()
+                .find(|(predicate, _)| Some(*predicate) == self.rest)
+                .map(|(_, next)| *next);
+        }
+        members
+    }
+}
+
+/**
+ * Lists of the node in order: the lists resolved by loading
+ * and the not resolved chains of list nodes referenced by the node.
+ */
+pub fn node_lists(node_data: &NodeData, node_index: IriIndex) -> Vec<RdfList> {
+    let mut lists: Vec<RdfList> = node_data.rdf_lists.of_node(node_index).cloned().collect();
+    let Some((_, node)) = node_data.get_node_by_index(node_index) else {
+        return lists;
+    };
+    let list_predicates = ListPredicates::new(node_data);
+    if list_predicates.first.is_none() {
+        return lists;
+    }
+    for (predicate, head) in node.references.iter() {
+        // the rest of a list is also a list
+        if Some(*predicate) == list_predicates.rest {
+            continue;
+        }
+        match node_data.get_node_by_index(*head) {
+            Some((_, head_node)) if list_predicates.is_list_node(head_node) => {
+                lists.push(RdfList {
+                    holder: node_index,
+                    predicate: *predicate,
+                    head: *head,
+                    members: list_predicates.members(node_data, *head),
+                });
+            }
+            _ => {}
+        }
+    }
+    lists
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::prefix_manager::PrefixManager;
+
+    fn add_reference(node_data: &mut NodeData, from: IriIndex, predicate: IriIndex, to: IriIndex) {
+        node_data.get_node_by_index_mut(from).unwrap().1.references.push((predicate, to));
+        node_data
+            .get_node_by_index_mut(to)
+            .unwrap()
+            .1
+            .reverse_references
+            .push((predicate, from));
+    }
+
+    #[test]
+    fn test_rdf_lists() {
+        let mut node_data = NodeData::new();
+        let prefix_manager = PrefixManager::new();
+        let holder = node_data.get_node_index_or_insert("ex:holder", false);
+        let members: Vec<IriIndex> = ["ex:a", "ex:b", "ex:c"]
+            .iter()
+            .map(|iri| node_data.get_node_index_or_insert(iri, false))
+            .collect();
+        let cells: Vec<IriIndex> = ["_:l1", "_:l2", "_:l3"]
+            .iter()
+            .map(|iri| node_data.get_node_index_or_insert(iri, true))
+            .collect();
+        let nil = node_data.get_node_index_or_insert(&prefix_manager.get_prefixed(rdf::NIL.as_str()), false);
+        let predicate = node_data.get_predicate_index("ex:members");
+        let first = node_data.get_predicate_index(&prefix_manager.get_prefixed(rdf::FIRST.as_str()));
+        let rest = node_data.get_predicate_index(&prefix_manager.get_prefixed(rdf::REST.as_str()));
+        add_reference(&mut node_data, holder, predicate, cells[0]);
+        for (pos, cell) in cells.iter().enumerate() {
+            add_reference(&mut node_data, *cell, first, members[pos]);
+            add_reference(&mut node_data, *cell, rest, *cells.get(pos + 1).unwrap_or(&nil));
+        }
+
+        // not resolved chain
+        let lists = node_lists(&node_data, holder);
+        assert_eq!(1, lists.len());
+        assert_eq!(cells[0], lists[0].head);
+        assert!(members.iter().map(|member| ListMember::Node(*member)).collect::<Vec<_>>() == lists[0].members);
+        assert!(node_lists(&node_data, cells[1]).is_empty());
+
+        node_data.resolve_rdf_lists(&prefix_manager);
+        assert_eq!(1, node_data.rdf_lists.len());
+        let lists = node_lists(&node_data, holder);
+        assert_eq!(1, lists.len());
+        assert_eq!(3, lists[0].members.len());
+        assert_eq!(Some(2), node_data.rdf_lists.member_position(holder, predicate, members[2]));
+        assert_eq!(None, node_data.rdf_lists.member_position(holder, first, members[2]));
+
+        // resolved again (next loaded file) the list is not duplicated
+        node_data.resolve_rdf_lists(&prefix_manager);
+        assert_eq!(1, node_data.rdf_lists.len());
+    }
+}
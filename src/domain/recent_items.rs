@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+
+use crate::integration::sparql::NodeQuery;
+
+// Maximal number of not pinned items per kind
+const MAX_UNPINNED_ITEMS: usize = 20;
+// Thumbnail coordinates are stored as bytes to keep the persistent data small
+const MAX_THUMBNAIL_NODES: usize = 200;
+const MAX_THUMBNAIL_EDGES: usize = 400;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum RecentKind {
+    File,
+    Url,
+    Endpoint,
+    Project,
+}
+
+impl RecentKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecentKind::File => "File",
+            RecentKind::Url => "URL",
+            RecentKind::Endpoint => "Endpoint",
+            RecentKind::Project => "Project",
+        }
+    }
+}
+
+/**
+ * Simplified picture of the graph view: node positions scaled to 0..255 and edges as pairs of node positions.
+ * It is stored in the recent items and in the project file.
+ */
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Debug)]
+pub struct Thumbnail {
+    pub nodes: Vec<[u8; 2]>,
+    pub edges: Vec<[u16; 2]>,
+    // rgb color of the node type for each node, empty for thumbnails without colors
+    #[serde(default)]
+    pub colors: Vec<[u8; 3]>,
+}
+
+impl Thumbnail {
+    pub fn new(positions: &[(f32, f32)], edges: &[(usize, usize)]) -> Self {
+        let positions = &positions[..positions.len().min(MAX_THUMBNAIL_NODES)];
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (f32::MAX, f32::MAX, f32::MIN, f32::MIN);
+        for (x, y) in positions.iter() {
+            min_x = min_x.min(*x);
+            min_y = min_y.min(*y);
+            max_x = max_x.max(*x);
+            max_y = max_y.max(*y);
+        }
+        // keep the aspect ratio of the graph
+        let size = (max_x - min_x).max(max_y - min_y).max(f32::EPSILON);
+        let nodes = positions
+            .iter()
+            .map(|(x, y)| {
+                [
+                    ((x - min_x) / size * 255.0).round() as u8,
+                    ((y - min_y) / size * 255.0).round() as u8,
+                ]
+            })
+            .collect();
+        let edges = edges
+            .iter()
+            .filter(|(from, to)| *from < positions.len() && *to < positions.len())
+            .take(MAX_THUMBNAIL_EDGES)
+            .map(|(from, to)| [*from as u16, *to as u16])
+            .collect();
+        Self {
+            nodes,
+            edges,
+            colors: Vec::new(),
+        }
+    }
+
+    pub fn with_colors(mut self, colors: impl Iterator<Item = [u8; 3]>) -> Self {
+        self.colors = colors.take(self.nodes.len()).collect();
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RecentItem {
+    pub kind: RecentKind,
+    pub location: Box<str>,
+    #[serde(default)]
+    pub pinned: bool,
+    // Increasing use counter, used for ordering without need of system time (not available in wasm)
+    #[serde(default)]
+    pub last_used: u64,
+    #[serde(default)]
+    pub thumbnail: Option<Thumbnail>,
+    // endpoints are connected again with the same node query
+    #[serde(default)]
+    pub node_query: NodeQuery,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+pub struct RecentItems {
+    pub items: Vec<RecentItem>,
+}
+
+impl RecentItems {
+    /// Registers the usage of the item and returns it, new items are created
+    pub fn touch(&mut self, kind: RecentKind, location: &str) -> &mut RecentItem {
+        let last_used = self.items.iter().map(|item| item.last_used).max().unwrap_or(0) + 1;
+        let position = match self.position(kind, location) {
+            Some(position) => position,
+            None => {
+                self.items.push(RecentItem {
+                    kind,
+                    location: location.into(),
+                    pinned: false,
+                    last_used,
+                    thumbnail: None,
+                    node_query: NodeQuery::default(),
+                });
+                self.trim(kind);
+                self.items.len() - 1
+            }
+        };
+        let item = &mut self.items[position];
+        item.last_used = last_used;
+        item
+    }
+
+    pub fn remove(&mut self, kind: RecentKind, location: &str) {
+        self.items.retain(|item| !(item.kind == kind && *item.location == *location));
+    }
+
+    pub fn toggle_pin(&mut self, kind: RecentKind, location: &str) {
+        if let Some(position) = self.position(kind, location) {
+            self.items[position].pinned = !self.items[position].pinned;
+        }
+    }
+
+    /// Pinned items first, then the most recently used
+    pub fn sorted(&self) -> Vec<&RecentItem> {
+        let mut items: Vec<&RecentItem> = self.items.iter().collect();
+        items.sort_by(|a, b| b.pinned.cmp(&a.pinned).then(b.last_used.cmp(&a.last_used)));
+        items
+    }
+
+    pub fn sorted_by_kind(&self, kinds: &[RecentKind]) -> Vec<&RecentItem> {
+        self.sorted()
+            .into_iter()
+            .filter(|item| kinds.contains(&item.kind))
+            .collect()
+    }
+
+    pub fn locations(&self, kind: RecentKind) -> Vec<Box<str>> {
+        self.sorted_by_kind(&[kind])
+            .into_iter()
+            .map(|item| item.location.clone())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, kind: RecentKind, location: &str) -> Option<&RecentItem> {
+        self.position(kind, location).map(|position| &self.items[position])
+    }
+
+    /// Takes over the plain lists of older versions
+    pub fn migrate(&mut self, kind: RecentKind, locations: &[Box<str>]) {
+        for location in locations.iter().rev() {
+            if !location.is_empty() {
+                self.touch(kind, location);
+            }
+        }
+    }
+
+    fn position(&self, kind: RecentKind, location: &str) -> Option<usize> {
+        self.items
+            .iter()
+            .position(|item| item.kind == kind && *item.location == *location)
+    }
+
+    fn trim(&mut self, kind: RecentKind) {
+        let mut unpinned: Vec<u64> = self
+            .items
+            .iter()
+            .filter(|item| item.kind == kind && !item.pinned)
+            .map(|item| item.last_used)
+            .collect();
+        if unpinned.len() > MAX_UNPINNED_ITEMS {
+            unpinned.sort_unstable_by(|a, b| b.cmp(a));
+            let min_last_used = unpinned[MAX_UNPINNED_ITEMS - 1];
+            self.items
+                .retain(|item| item.kind != kind || item.pinned || item.last_used >= min_last_used);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recent_items() {
+        let mut recent = RecentItems::default();
+        recent.touch(RecentKind::File, "a.ttl");
+        recent.touch(RecentKind::File, "b.ttl");
+        recent.touch(RecentKind::Project, "p.rdfglance");
+        recent.touch(RecentKind::File, "a.ttl");
+        assert_eq!(3, recent.items.len());
+        let files = recent.locations(RecentKind::File);
+        assert_eq!(vec![Box::from("a.ttl"), Box::from("b.ttl")], files);
+
+        recent.toggle_pin(RecentKind::File, "b.ttl");
+        assert_eq!("b.ttl", &*recent.sorted()[0].location);
+
+        for i in 0..MAX_UNPINNED_ITEMS + 5 {
+            recent.touch(RecentKind::File, &format!("f{}.ttl", i));
+        }
+        let files = recent.locations(RecentKind::File);
+        assert_eq!(MAX_UNPINNED_ITEMS + 1, files.len());
+        assert!(files.contains(&Box::from("b.ttl")));
+        assert!(!files.contains(&Box::from("a.ttl")));
+
+        recent.remove(RecentKind::Project, "p.rdfglance");
+        assert!(recent.locations(RecentKind::Project).is_empty());
+
+        recent.touch(RecentKind::Endpoint, "https://example.org/sparql").node_query = NodeQuery::Describe;
+        recent.toggle_pin(RecentKind::Endpoint, "https://example.org/sparql");
+        let json = serde_json::to_string(&recent).unwrap();
+        let restored: RecentItems = serde_json::from_str(&json).unwrap();
+        let endpoint = restored.get(RecentKind::Endpoint, "https://example.org/sparql").unwrap();
+        assert!(endpoint.pinned);
+        assert_eq!(NodeQuery::Describe, endpoint.node_query);
+    }
+
+    #[test]
+    fn test_thumbnail() {
+        let thumbnail = Thumbnail::new(&[(0.0, 0.0), (100.0, 50.0), (50.0, 100.0)], &[(0, 1), (1, 2), (2, 5)]);
+        assert_eq!(vec![[0, 0], [255, 128], [128, 255]], thumbnail.nodes);
+        assert_eq!(2, thumbnail.edges.len());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::domain::config::Config;
+
+/// Drawing simplifications that keep the visual graph responsive for many visible nodes
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct RenderSimplification {
+    // all nodes are drawn as circles without icons
+    pub simple_shapes: bool,
+    // node labels are only drawn for the hovered or selected node
+    pub hide_labels: bool,
+    pub hide_edge_labels: bool,
+    // edges are drawn as straight lines without arrows, nodes and edges outside the view are skipped
+    pub lod: bool,
+}
+
+impl RenderSimplification {
+    pub fn for_node_count(node_count: usize, config: &Config) -> Self {
+        if !config.auto_render_mode {
+            return Self::default();
+        }
+        let exceeds = |threshold: usize| threshold > 0 && node_count > threshold;
+        Self {
+            simple_shapes: exceeds(config.simple_shapes_min_nodes),
+            hide_labels: exceeds(config.hide_labels_min_nodes),
+            hide_edge_labels: exceeds(config.hide_edge_labels_min_nodes),
+            lod: exceeds(config.lod_min_nodes),
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Simplifications that are active in self but not in previous
+    pub fn added_to(&self, previous: &RenderSimplification) -> RenderSimplification {
+        RenderSimplification {
+            simple_shapes: self.simple_shapes && !previous.simple_shapes,
+            hide_labels: self.hide_labels && !previous.hide_labels,
+            hide_edge_labels: self.hide_edge_labels && !previous.hide_edge_labels,
+            lod: self.lod && !previous.lod,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let mut changes = Vec::new();
+        if self.simple_shapes {
+            changes.push("nodes as circles");
+        }
+        if self.hide_labels {
+            changes.push("no node labels");
+        }
+        if self.hide_edge_labels {
+            changes.push("no edge labels");
+        }
+        if self.lod {
+            changes.push("simple edges");
+        }
+        changes.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_node_count() {
+        let mut config = Config::default();
+        config.simple_shapes_min_nodes = 100;
+        config.hide_labels_min_nodes = 200;
+        config.hide_edge_labels_min_nodes = 50;
+        config.lod_min_nodes = 0;
+        assert!(RenderSimplification::for_node_count(50, &config).is_full());
+        let simplification = RenderSimplification::for_node_count(150, &config);
+        assert!(simplification.simple_shapes);
+        assert!(simplification.hide_edge_labels);
+        assert!(!simplification.hide_labels);
+        let more = RenderSimplification::for_node_count(1_000_000, &config);
+        assert!(more.hide_labels);
+        assert!(!more.lod);
+        let added = more.added_to(&simplification);
+        assert_eq!("no node labels", added.describe());
+        config.auto_render_mode = false;
+        assert!(RenderSimplification::for_node_count(1_000_000, &config).is_full());
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{domain::statistics::distribute_to_zoom_layers, graph_algorithms::StatisticValue};
+
+/// Node value that decides up to which zoom magnitude the node stays visible
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ZoomSource {
+    // already computed statistics result
+    Statistic(StatisticValue),
+    // number of visible edges of the node
+    Degree,
+    // priority of the node type style
+    TypePriority,
+}
+
+impl ZoomSource {
+    pub fn label(&self) -> String {
+        match self {
+            ZoomSource::Statistic(statistic_value) => statistic_value.to_string(),
+            ZoomSource::Degree => "Visible Degree".to_string(),
+            ZoomSource::TypePriority => "Type Priority".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ZoomMapping {
+    // few nodes with highest values in top layers, layer size grows geometrically
+    Rank,
+    // value range is mapped linear to the layers
+    Linear,
+}
+
+pub struct SemanticZoomSettings {
+    pub source: ZoomSource,
+    pub mapping: ZoomMapping,
+    // low values stay visible when zoomed out
+    pub invert: bool,
+}
+
+impl Default for SemanticZoomSettings {
+    fn default() -> Self {
+        Self {
+            source: ZoomSource::Degree,
+            mapping: ZoomMapping::Rank,
+            invert: false,
+        }
+    }
+}
+
+/// Zoom layer 1..=10 for each value, nodes with layer 10 are visible in all zoom magnitudes
+pub fn zoom_layers(values: &[f32], mapping: ZoomMapping, invert: bool) -> Vec<u8> {
+    if values.is_empty() {
+        return Vec::new();
+    }
+    let (min, max) = values
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(min, max), value| (min.min(*value), max.max(*value)));
+    let values: Vec<f32> = if invert {
+        values.iter().map(|value| max + min - value).collect()
+    } else {
+        values.to_vec()
+    };
+    match mapping {
+        ZoomMapping::Rank => distribute_to_zoom_layers(&values),
+        ZoomMapping::Linear => {
+            let range = max - min;
+            values
+                .iter()
+                .map(|value| {
+                    let normalized = if range > 0.0 { (value - min) / range } else { 1.0 };
+                    (normalized * 9.0 + 1.0).round() as u8
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_layers() {
+        let values = [0.0, 5.0, 10.0];
+        assert_eq!(vec![1, 6, 10], zoom_layers(&values, ZoomMapping::Linear, false));
+        assert_eq!(vec![10, 6, 1], zoom_layers(&values, ZoomMapping::Linear, true));
+        assert_eq!(vec![10, 10], zoom_layers(&[3.0, 3.0], ZoomMapping::Linear, false));
+        let values: Vec<f32> = (0..100).map(|value| value as f32).collect();
+        let layers = zoom_layers(&values, ZoomMapping::Rank, false);
+        assert_eq!(100, layers.len());
+        // the highest value is in the most zoomed out layer
+        assert!(layers[99] > layers[0]);
+        assert!(zoom_layers(&[], ZoomMapping::Rank, false).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Write,
+};
+
+use crate::IriIndex;
+
+// Longer pauses between two frames are not counted as time spent in view (egui repaints only on input)
+const MAX_FRAME_GAP: f64 = 60.0;
+
+/**
+ * Local statistics of the current session. Nothing is send over network, the data
+ * can only be exported as text report by the user.
+ */
+#[derive(Default)]
+pub struct SessionStatistics {
+    pub explored_nodes: HashSet<IriIndex>,
+    pub node_visits: u32,
+    pub queries_run: u32,
+    pub type_visits: HashMap<IriIndex, u32>,
+    // seconds spent in view, the key is the name of the view
+    pub view_time: HashMap<String, f64>,
+    last_frame_time: Option<f64>,
+}
+
+impl SessionStatistics {
+    pub fn record_node(&mut self, node_index: IriIndex, types: &[IriIndex]) {
+        self.node_visits += 1;
+        self.explored_nodes.insert(node_index);
+        for type_index in types.iter() {
+            *self.type_visits.entry(*type_index).or_insert(0) += 1;
+        }
+    }
+
+    pub fn record_query(&mut self) {
+        self.queries_run += 1;
+    }
+
+    /// Called every frame with egui input time
+    pub fn record_view_time(&mut self, view: &str, time: f64) {
+        if let Some(last_frame_time) = self.last_frame_time {
+            let elapsed = (time - last_frame_time).clamp(0.0, MAX_FRAME_GAP);
+            if let Some(view_time) = self.view_time.get_mut(view) {
+                *view_time += elapsed;
+            } else {
+                self.view_time.insert(view.to_string(), elapsed);
+            }
+        }
+        self.last_frame_time = Some(time);
+    }
+
+    /// Types ordered by number of visits
+    pub fn busiest_types(&self, limit: usize) -> Vec<(IriIndex, u32)> {
+        let mut types: Vec<(IriIndex, u32)> = self.type_visits.iter().map(|(t, c)| (*t, *c)).collect();
+        types.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        types.truncate(limit);
+        types
+    }
+
+    /// Views ordered by spent time
+    pub fn views_by_time(&self) -> Vec<(&str, f64)> {
+        let mut views: Vec<(&str, f64)> = self.view_time.iter().map(|(v, t)| (v.as_str(), *t)).collect();
+        views.sort_by(|a, b| b.1.total_cmp(&a.1));
+        views
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn to_text(&self, type_label: impl Fn(IriIndex) -> String) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Session statistics");
+        let _ = writeln!(out, "  Nodes explored: {}", self.explored_nodes.len());
+        let _ = writeln!(out, "  Node visits: {}", self.node_visits);
+        let _ = writeln!(out, "  Queries run: {}", self.queries_run);
+        let _ = writeln!(out, "Busiest types:");
+        for (type_index, count) in self.busiest_types(20) {
+            let _ = writeln!(out, "  {}: {}", type_label(type_index), count);
+        }
+        let _ = writeln!(out, "Time per view:");
+        for (view, seconds) in self.views_by_time() {
+            let _ = writeln!(out, "  {}: {}", view, format_duration(seconds));
+        }
+        out
+    }
+}
+
+pub fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    format!("{}:{:02}:{:02}", seconds / 3600, (seconds / 60) % 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_statistics() {
+        let mut statistics = SessionStatistics::default();
+        statistics.record_node(1, &[10]);
+        statistics.record_node(2, &[10, 11]);
+        statistics.record_node(1, &[10]);
+        statistics.record_query();
+        statistics.record_view_time("Table", 1.0);
+        statistics.record_view_time("Table", 3.0);
+        statistics.record_view_time("Graph", 1000.0);
+        statistics.record_view_time("Graph", 1010.0);
+        assert_eq!(2, statistics.explored_nodes.len());
+        assert_eq!(3, statistics.node_visits);
+        assert_eq!(vec![(10, 3), (11, 1)], statistics.busiest_types(5));
+        assert_eq!(vec![("Graph", MAX_FRAME_GAP + 10.0), ("Table", 2.0)], statistics.views_by_time());
+        let text = statistics.to_text(|type_index| format!("type{}", type_index));
+        assert!(text.contains("  type10: 3\n"));
+        assert!(text.contains("  Queries run: 1\n"));
+        assert_eq!("1:01:05", format_duration(3665.4));
+    }
+}
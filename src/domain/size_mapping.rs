@@ -0,0 +1,132 @@
+use crate::{IriIndex, graph_algorithms::StatisticValue};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SizeSource {
+    // numeric data property of the node
+    Property(IriIndex),
+    // computed statistic of the visual graph
+    Statistic(StatisticValue),
+}
+
+/**
+ * Maps a numeric value of the nodes to the node size of the types with NodeSize::Mapped.
+ * The values are clamped to the value range, the range is the range of the values if auto_range is set.
+ * The mapping is shared by all types.
+ */
+pub struct SizeMapping {
+    pub source: Option<SizeSource>,
+    pub auto_range: bool,
+    pub clamp_min: f32,
+    pub clamp_max: f32,
+    pub min_size: f32,
+    pub max_size: f32,
+    // value range of the sizes applied to the visible nodes, used for the legend
+    pub applied_range: Option<(f32, f32)>,
+    // (visible nodes epoch, statistics epoch, statistics results, mapping version) of the applied sizes
+    pub applied_key: Option<(u32, u32, usize, u32)>,
+    // incremented on each change of the mapping
+    pub version: u32,
+}
+
+impl Default for SizeMapping {
+    fn default() -> Self {
+        Self {
+            source: None,
+            auto_range: true,
+            clamp_min: 0.0,
+            clamp_max: 100.0,
+            min_size: 8.0,
+            max_size: 60.0,
+            applied_range: None,
+            applied_key: None,
+            version: 0,
+        }
+    }
+}
+
+impl SizeMapping {
+    /// Value range used for the mapping, None if there are no values
+    pub fn value_range(&self, values: &[f32]) -> Option<(f32, f32)> {
+        if !self.auto_range {
+            return Some((self.clamp_min.min(self.clamp_max), self.clamp_max.max(self.clamp_min)));
+        }
+        let mut range: Option<(f32, f32)> = None;
+        for value in values.iter().filter(|value| value.is_finite()) {
+            range = Some(range.map_or((*value, *value), |(min, max)| (min.min(*value), max.max(*value))));
+        }
+        range
+    }
+
+    pub fn size_for(&self, value: f32, (min, max): (f32, f32)) -> f32 {
+        if !value.is_finite() {
+            return f32::NAN;
+        }
+        let normalized = if max > min {
+            (value.clamp(min, max) - min) / (max - min)
+        } else {
+            0.5
+        };
+        self.min_size + normalized * (self.max_size - self.min_size)
+    }
+
+    /// Sizes for the values, NaN for the nodes without value
+    pub fn map_values(&self, values: &[f32]) -> (Vec<f32>, Option<(f32, f32)>) {
+        let Some(range) = self.value_range(values) else {
+            return (vec![f32::NAN; values.len()], None);
+        };
+        (values.iter().map(|value| self.size_for(*value, range)).collect(), Some(range))
+    }
+
+    /// (value, size) for the smallest, middle and biggest node of the legend
+    pub fn legend(&self) -> Vec<(f32, f32)> {
+        let Some((min, max)) = self.applied_range else {
+            return Vec::new();
+        };
+        let mut values = vec![min];
+        if max > min {
+            values.push((min + max) / 2.0);
+            values.push(max);
+        }
+        values.iter().map(|value| (*value, self.size_for(*value, (min, max)))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_values() {
+        let mut size_mapping = SizeMapping {
+            min_size: 10.0,
+            max_size: 50.0,
+            ..SizeMapping::default()
+        };
+        let values = [0.0, 5.0, 10.0, f32::NAN];
+        let (sizes, range) = size_mapping.map_values(&values);
+        assert_eq!(Some((0.0, 10.0)), range);
+        assert_eq!(10.0, sizes[0]);
+        assert_eq!(30.0, sizes[1]);
+        assert_eq!(50.0, sizes[2]);
+        assert!(sizes[3].is_nan());
+
+        // values outside of the range are clamped
+        size_mapping.auto_range = false;
+        size_mapping.clamp_min = 2.0;
+        size_mapping.clamp_max = 6.0;
+        let (sizes, range) = size_mapping.map_values(&values);
+        assert_eq!(Some((2.0, 6.0)), range);
+        assert_eq!(10.0, sizes[0]);
+        assert_eq!(40.0, sizes[1]);
+        assert_eq!(50.0, sizes[2]);
+
+        size_mapping.applied_range = range;
+        assert_eq!(vec![(2.0, 10.0), (4.0, 30.0), (6.0, 50.0)], size_mapping.legend());
+
+        // no values at all
+        size_mapping.auto_range = true;
+        let (sizes, range) = size_mapping.map_values(&[f32::NAN]);
+        assert_eq!(None, range);
+        assert!(sizes[0].is_nan());
+    }
+}
@@ -0,0 +1,288 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    IriIndex,
+    domain::{
+        NodeData,
+        data_quality::{IssueSeverity, QualityIssue},
+        prefix_manager::PrefixManager,
+    },
+};
+
+const SKOS: &str = "http://www.w3.org/2004/02/skos/core#";
+
+const CHECK_EXACT_MATCH: &str = "exactMatch symmetry";
+const CHECK_MATCH_INVERSE: &str = "broadMatch/narrowMatch consistency";
+const CHECK_BROADER_INVERSE: &str = "broader/narrower consistency";
+const CHECK_BROADER_CYCLE: &str = "Cycles in broader hierarchy";
+const CHECK_ORPHAN: &str = "Orphan concepts";
+const CHECK_TOP_CONCEPTS: &str = "Multiple top concepts per scheme";
+
+struct SkosVocabulary {
+    concept: Option<IriIndex>,
+    exact_match: Option<IriIndex>,
+    broad_match: Option<IriIndex>,
+    narrow_match: Option<IriIndex>,
+    broader: Option<IriIndex>,
+    narrower: Option<IriIndex>,
+    related: Option<IriIndex>,
+    has_top_concept: Option<IriIndex>,
+    top_concept_of: Option<IriIndex>,
+}
+
+impl SkosVocabulary {
+    fn new(node_data: &NodeData, prefix_manager: &PrefixManager) -> Self {
+        let predicate = |name: &str| node_data.find_predicate_index(prefix_manager, &format!("{}{}", SKOS, name));
+        Self {
+            concept: node_data.find_type_index(prefix_manager, &format!("{}Concept", SKOS)),
+            exact_match: predicate("exactMatch"),
+            broad_match: predicate("broadMatch"),
+            narrow_match: predicate("narrowMatch"),
+            broader: predicate("broader"),
+            narrower: predicate("narrower"),
+            related: predicate("related"),
+            has_top_concept: predicate("hasTopConcept"),
+            top_concept_of: predicate("topConceptOf"),
+        }
+    }
+
+    fn is_hierarchical(&self, predicate: IriIndex) -> bool {
+        [self.broader, self.narrower, self.related].contains(&Some(predicate))
+    }
+}
+
+/// Validates the mapping relations and the concept hierarchy of SKOS data
+pub fn validate_skos(node_data: &NodeData, prefix_manager: &PrefixManager) -> Vec<QualityIssue> {
+    let vocabulary = SkosVocabulary::new(node_data, prefix_manager);
+    let mut issues: Vec<QualityIssue> = Vec::new();
+    if let Some(exact_match) = vocabulary.exact_match {
+        check_inverse(node_data, exact_match, exact_match, CHECK_EXACT_MATCH, &mut issues);
+    }
+    if let (Some(broad_match), Some(narrow_match)) = (vocabulary.broad_match, vocabulary.narrow_match) {
+        check_inverse(node_data, broad_match, narrow_match, CHECK_MATCH_INVERSE, &mut issues);
+        check_inverse(node_data, narrow_match, broad_match, CHECK_MATCH_INVERSE, &mut issues);
+    }
+    if let (Some(broader), Some(narrower)) = (vocabulary.broader, vocabulary.narrower) {
+        check_inverse(node_data, broader, narrower, CHECK_BROADER_INVERSE, &mut issues);
+        check_inverse(node_data, narrower, broader, CHECK_BROADER_INVERSE, &mut issues);
+    }
+    check_broader_cycles(node_data, &vocabulary, &mut issues);
+    check_orphans(node_data, &vocabulary, &mut issues);
+    check_top_concepts(node_data, &vocabulary, &mut issues);
+    issues
+}
+
+// Reports "a predicate b" without "b inverse a". Targets outside of the loaded data are not reported
+fn check_inverse(
+    node_data: &NodeData,
+    predicate: IriIndex,
+    inverse: IriIndex,
+    check: &'static str,
+    issues: &mut Vec<QualityIssue>,
+) {
+    let predicate_name = node_data.get_predicate(predicate).unwrap_or_default();
+    let inverse_name = node_data.get_predicate(inverse).unwrap_or_default();
+    for (node_index, (iri, node)) in node_data.iter().enumerate() {
+        let node_index = node_index as IriIndex;
+        for (reference_predicate, target_index) in node.references.iter() {
+            if *reference_predicate != predicate || *target_index == node_index {
+                continue;
+            }
+            let Some((target_iri, target)) = node_data.get_node_by_index(*target_index) else {
+                continue;
+            };
+            if !target.has_subject {
+                continue;
+            }
+            let has_inverse = target
+                .references
+                .iter()
+                .any(|(target_predicate, index)| *target_predicate == inverse && *index == node_index);
+            if !has_inverse {
+                issues.push(QualityIssue {
+                    check,
+                    severity: IssueSeverity::Warning,
+                    message: format!(
+                        "{} {} {} but not {} {} {}",
+                        iri, predicate_name, target_iri, target_iri, inverse_name, iri
+                    ),
+                    nodes: vec![node_index, *target_index],
+                });
+            }
+        }
+    }
+}
+
+// a narrower b is handled as b broader a
+fn broader_graph(node_data: &NodeData, vocabulary: &SkosVocabulary) -> HashMap<IriIndex, BTreeSet<IriIndex>> {
+    let mut graph: HashMap<IriIndex, BTreeSet<IriIndex>> = HashMap::new();
+    for (node_index, (_, node)) in node_data.iter().enumerate() {
+        let node_index = node_index as IriIndex;
+        for (predicate, target_index) in node.references.iter() {
+            if Some(*predicate) == vocabulary.broader {
+                graph.entry(node_index).or_default().insert(*target_index);
+            } else if Some(*predicate) == vocabulary.narrower {
+                graph.entry(*target_index).or_default().insert(node_index);
+            }
+        }
+    }
+    graph
+}
+
+fn check_broader_cycles(node_data: &NodeData, vocabulary: &SkosVocabulary, issues: &mut Vec<QualityIssue>) {
+    let graph = broader_graph(node_data, vocabulary);
+    let mut finished: HashSet<IriIndex> = HashSet::new();
+    let mut reported: HashSet<BTreeSet<IriIndex>> = HashSet::new();
+    let mut starts: Vec<IriIndex> = graph.keys().cloned().collect();
+    starts.sort_unstable();
+    for start in starts {
+        if finished.contains(&start) {
+            continue;
+        }
+        // iterative depth first search, the path holds the nodes on the stack
+        let mut path: Vec<IriIndex> = vec![start];
+        let mut on_path: HashSet<IriIndex> = HashSet::from([start]);
+        let mut iterators: Vec<std::collections::btree_set::Iter<IriIndex>> = Vec::new();
+        iterators.push(graph.get(&start).map(|targets| targets.iter()).unwrap_or_default());
+        while let Some(iterator) = iterators.last_mut() {
+            match iterator.next() {
+                Some(next) => {
+                    if on_path.contains(next) {
+                        let position = path.iter().position(|node| node == next).unwrap_or(0);
+                        let cycle: Vec<IriIndex> = path[position..].to_vec();
+                        if reported.insert(cycle.iter().cloned().collect()) {
+                            let names: Vec<&str> = cycle
+                                .iter()
+                                .filter_map(|node_index| node_data.get_node_by_index(*node_index))
+                                .map(|(iri, _)| iri.as_ref())
+                                .collect();
+                            issues.push(QualityIssue {
+                                check: CHECK_BROADER_CYCLE,
+                                severity: IssueSeverity::Error,
+                                message: format!("{} -> {}", names.join(" -> "), names[0]),
+                                nodes: cycle,
+                            });
+                        }
+                    } else if !finished.contains(next) {
+                        path.push(*next);
+                        on_path.insert(*next);
+                        iterators.push(graph.get(next).map(|targets| targets.iter()).unwrap_or_default());
+                    }
+                }
+                None => {
+                    iterators.pop();
+                    if let Some(node) = path.pop() {
+                        on_path.remove(&node);
+                        finished.insert(node);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Concepts without any hierarchical or associative relation that are not top concepts
+fn check_orphans(node_data: &NodeData, vocabulary: &SkosVocabulary, issues: &mut Vec<QualityIssue>) {
+    let Some(concept) = vocabulary.concept else {
+        return;
+    };
+    for (node_index, (iri, node)) in node_data.iter().enumerate() {
+        if !node.types.contains(&concept) {
+            continue;
+        }
+        let is_related = node
+            .references
+            .iter()
+            .chain(node.reverse_references.iter())
+            .any(|(predicate, _)| vocabulary.is_hierarchical(*predicate));
+        let is_top_concept = node
+            .references
+            .iter()
+            .any(|(predicate, _)| Some(*predicate) == vocabulary.top_concept_of)
+            || node
+                .reverse_references
+                .iter()
+                .any(|(predicate, _)| Some(*predicate) == vocabulary.has_top_concept);
+        if !is_related && !is_top_concept {
+            issues.push(QualityIssue {
+                check: CHECK_ORPHAN,
+                severity: IssueSeverity::Warning,
+                message: format!("{} has no broader, narrower or related concept", iri),
+                nodes: vec![node_index as IriIndex],
+            });
+        }
+    }
+}
+
+fn check_top_concepts(node_data: &NodeData, vocabulary: &SkosVocabulary, issues: &mut Vec<QualityIssue>) {
+    let mut top_concepts: HashMap<IriIndex, BTreeSet<IriIndex>> = HashMap::new();
+    for (node_index, (_, node)) in node_data.iter().enumerate() {
+        let node_index = node_index as IriIndex;
+        for (predicate, target_index) in node.references.iter() {
+            if Some(*predicate) == vocabulary.has_top_concept {
+                top_concepts.entry(node_index).or_default().insert(*target_index);
+            } else if Some(*predicate) == vocabulary.top_concept_of {
+                top_concepts.entry(*target_index).or_default().insert(node_index);
+            }
+        }
+    }
+    let mut schemes: Vec<(IriIndex, BTreeSet<IriIndex>)> =
+        top_concepts.into_iter().filter(|(_, concepts)| concepts.len() > 1).collect();
+    schemes.sort_unstable_by_key(|(scheme, _)| *scheme);
+    for (scheme, concepts) in schemes {
+        let scheme_iri = node_data
+            .get_node_by_index(scheme)
+            .map(|(iri, _)| iri.to_string())
+            .unwrap_or_default();
+        let mut nodes = vec![scheme];
+        nodes.extend(concepts.iter());
+        issues.push(QualityIssue {
+            check: CHECK_TOP_CONCEPTS,
+            severity: IssueSeverity::Info,
+            message: format!("{} has {} top concepts", scheme_iri, concepts.len()),
+            nodes,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_skos() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA skos: <http://www.w3.org/2004/02/skos/core#> .
+PA ex: <http://example.org/> .
+A ex:scheme skos:hasTopConcept ex:a .
+A ex:scheme skos:hasTopConcept ex:b .
+A ex:a a skos:Concept .
+A ex:b a skos:Concept .
+A ex:c a skos:Concept .
+A ex:d a skos:Concept .
+A ex:orphan a skos:Concept .
+A ex:a skos:exactMatch ex:b .
+A ex:a skos:exactMatch ex:external .
+A ex:c skos:broader ex:d .
+A ex:d skos:narrower ex:c .
+A ex:d skos:broader ex:c .
+A ex:a skos:broadMatch ex:b .
+A ex:b skos:narrowMatch ex:a .
+"#,
+        );
+        let issues = validate_skos(&rdf_data.node_data, &rdf_data.prefix_manager);
+        let count = |check: &str| issues.iter().filter(|issue| issue.check == check).count();
+        assert_eq!(1, count(CHECK_EXACT_MATCH));
+        assert_eq!(0, count(CHECK_MATCH_INVERSE));
+        // c broader d has inverse, d broader c not
+        assert_eq!(1, count(CHECK_BROADER_INVERSE));
+        assert_eq!(1, count(CHECK_BROADER_CYCLE));
+        assert_eq!(1, count(CHECK_ORPHAN));
+        assert_eq!(1, count(CHECK_TOP_CONCEPTS));
+        let cycle = issues.iter().find(|issue| issue.check == CHECK_BROADER_CYCLE).unwrap();
+        assert_eq!(2, cycle.nodes.len());
+    }
+}
@@ -0,0 +1,265 @@
+use egui::Color32;
+
+use crate::{
+    IriIndex,
+    domain::{
+        NObject, NodeData,
+        graph_styles::{ArrowStyle, GVisualizationStyle, LabelPosition, LineStyle, NodeShape, NodeSize},
+        prefix_manager::PrefixManager,
+    },
+};
+
+const FOAF: &str = "http://xmlns.com/foaf/0.1/";
+const ORG: &str = "http://www.w3.org/ns/org#";
+
+const PERSON_SIZE: f32 = 40.0;
+const PERSON_COLOR: Color32 = Color32::from_rgb(120, 170, 230);
+const ORGANIZATION_COLOR: Color32 = Color32::from_rgb(240, 200, 120);
+
+/**
+ * Classes and predicates of FOAF and the W3C organization ontology (ORG) found in the data.
+ * They are used by the social/org chart preset: people as circles with photos, organizations as rectangles,
+ * reporting lines as hierarchy and the foaf:knows network for the force layout.
+ */
+#[derive(Default)]
+pub struct SocialVocabulary {
+    pub person_types: Vec<IriIndex>,
+    pub organization_types: Vec<IriIndex>,
+    pub knows: Option<IriIndex>,
+    pub membership: Vec<IriIndex>,
+    // true if the object of the triple is the superior (org:reportsTo), false for the opposite direction (org:hasUnit)
+    pub hierarchy: Vec<(IriIndex, bool)>,
+    pub photo_predicates: Vec<IriIndex>,
+    pub name: Option<IriIndex>,
+}
+
+impl SocialVocabulary {
+    pub fn new(node_data: &NodeData, prefix_manager: &PrefixManager) -> Self {
+        let predicate = |namespace: &str, name: &str| {
+            node_data.find_predicate_index(prefix_manager, &format!("{}{}", namespace, name))
+        };
+        let find_type = |namespace: &str, name: &str| {
+            node_data.find_type_index(prefix_manager, &format!("{}{}", namespace, name))
+        };
+        let hierarchy = [
+            (ORG, "reportsTo", true),
+            (ORG, "subOrganizationOf", true),
+            (ORG, "unitOf", true),
+            (ORG, "hasSubOrganization", false),
+            (ORG, "hasUnit", false),
+        ];
+        SocialVocabulary {
+            person_types: [(FOAF, "Person")]
+                .iter()
+                .filter_map(|(namespace, name)| find_type(namespace, name))
+                .collect(),
+            organization_types: [
+                (FOAF, "Organization"),
+                (FOAF, "Group"),
+                (ORG, "Organization"),
+                (ORG, "FormalOrganization"),
+                (ORG, "OrganizationalUnit"),
+                (ORG, "OrganizationalCollaboration"),
+            ]
+            .iter()
+            .filter_map(|(namespace, name)| find_type(namespace, name))
+            .collect(),
+            knows: predicate(FOAF, "knows"),
+            membership: [(ORG, "memberOf"), (ORG, "hasMember"), (ORG, "headOf"), (FOAF, "member")]
+                .iter()
+                .filter_map(|(namespace, name)| predicate(namespace, name))
+                .collect(),
+            hierarchy: hierarchy
+                .iter()
+                .filter_map(|(namespace, name, upward)| predicate(namespace, name).map(|index| (index, *upward)))
+                .collect(),
+            photo_predicates: [(FOAF, "img"), (FOAF, "depiction")]
+                .iter()
+                .filter_map(|(namespace, name)| predicate(namespace, name))
+                .collect(),
+            name: predicate(FOAF, "name"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.person_types.is_empty()
+            && self.organization_types.is_empty()
+            && self.knows.is_none()
+            && self.hierarchy.is_empty()
+    }
+
+    pub fn is_person(&self, node: &NObject) -> bool {
+        node.types.iter().any(|type_index| self.person_types.contains(type_index))
+    }
+
+    /// Sets the node styles of people and organizations and the edge styles of the social predicates
+    pub fn apply_styles(&self, visualization_style: &mut GVisualizationStyle, is_dark_mode: bool) {
+        // the social styles win if a node has other types too
+        let priority = visualization_style
+            .node_styles
+            .values()
+            .map(|style| style.priority)
+            .max()
+            .unwrap_or(0)
+            + 1;
+        let label_color = if is_dark_mode { Color32::WHITE } else { Color32::BLACK };
+        for type_index in self.person_types.iter() {
+            let style = visualization_style.node_styles.entry(*type_index).or_default();
+            style.color = PERSON_COLOR;
+            style.priority = priority;
+            style.node_shape = NodeShape::Circle;
+            style.node_size = NodeSize::Fixed;
+            style.width = PERSON_SIZE;
+            style.height = PERSON_SIZE;
+            style.border_width = 2.0;
+            style.label_position = LabelPosition::Below;
+            style.label_max_width = 120.0;
+            style.max_lines = 2;
+            style.label_color = label_color;
+            style.is_default = false;
+            if let Some(name) = self.name {
+                style.label_index = name;
+            }
+        }
+        for type_index in self.organization_types.iter() {
+            let style = visualization_style.node_styles.entry(*type_index).or_default();
+            style.color = ORGANIZATION_COLOR;
+            style.priority = priority;
+            style.node_shape = NodeShape::Rect;
+            style.node_size = NodeSize::Label;
+            style.width = 20.0;
+            style.height = 12.0;
+            style.corner_radius = 4.0;
+            style.label_position = LabelPosition::Center;
+            style.label_max_width = 160.0;
+            style.max_lines = 2;
+            style.label_color = Color32::BLACK;
+            style.is_default = false;
+        }
+        if let Some(knows) = self.knows {
+            visualization_style.get_edge_syle(knows, is_dark_mode);
+            if let Some(edge_style) = visualization_style.edge_styles.get_mut(&knows) {
+                edge_style.width = 1.0;
+                edge_style.line_style = LineStyle::Solid;
+            }
+        }
+        for predicate in self.membership.iter() {
+            visualization_style.get_edge_syle(*predicate, is_dark_mode);
+            if let Some(edge_style) = visualization_style.edge_styles.get_mut(predicate) {
+                edge_style.width = 1.5;
+                edge_style.line_style = LineStyle::Dashed;
+            }
+        }
+        for (predicate, _) in self.hierarchy.iter() {
+            visualization_style.get_edge_syle(*predicate, is_dark_mode);
+            if let Some(edge_style) = visualization_style.edge_styles.get_mut(predicate) {
+                edge_style.width = 3.0;
+                edge_style.line_style = LineStyle::Solid;
+                edge_style.target_style = ArrowStyle::ArrorFilled;
+            }
+        }
+    }
+
+    /// URL of the photo (foaf:img, foaf:depiction) given as IRI or literal
+    pub fn photo_url(&self, node_data: &NodeData, prefix_manager: &PrefixManager, node: &NObject) -> Option<String> {
+        let url = node
+            .references
+            .iter()
+            .filter(|(predicate, _)| self.photo_predicates.contains(predicate))
+            .find_map(|(_, target_index)| node_data.get_node_by_index(*target_index))
+            .map(|(iri, _)| {
+                prefix_manager
+                    .get_full_opt(iri)
+                    .map(|full_iri| full_iri.to_string())
+                    .unwrap_or_else(|| iri.to_string())
+            })
+            .or_else(|| {
+                node.properties
+                    .iter()
+                    .find(|(predicate, _)| self.photo_predicates.contains(predicate))
+                    .map(|(_, literal)| literal.as_str_ref(&node_data.indexers).to_string())
+            })?;
+        if url.starts_with("http://") || url.starts_with("https://") {
+            Some(url)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        domain::{RdfData, graph_styles::NodeStyle, size_mapping::SizeMapping, style_rules::StyleRules},
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_social_vocabulary() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA foaf: <http://xmlns.com/foaf/0.1/> .
+PA org: <http://www.w3.org/ns/org#> .
+PA ex: <http://example.org/> .
+A ex:alice a foaf:Person .
+A ex:alice foaf:name "Alice" .
+A ex:alice foaf:img <http://example.org/alice.jpg> .
+A ex:bob a foaf:Person .
+A ex:bob foaf:depiction "file:bob.png" .
+A ex:bob org:reportsTo ex:alice .
+A ex:bob foaf:knows ex:alice .
+A ex:bob org:memberOf ex:acme .
+A ex:acme a org:Organization .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let prefix_manager = &rdf_data.prefix_manager;
+        let vocabulary = SocialVocabulary::new(node_data, prefix_manager);
+        assert!(!vocabulary.is_empty());
+        assert_eq!(1, vocabulary.person_types.len());
+        assert_eq!(1, vocabulary.organization_types.len());
+        assert_eq!(1, vocabulary.hierarchy.len());
+        assert!(vocabulary.hierarchy[0].1);
+        assert_eq!(1, vocabulary.membership.len());
+        assert!(vocabulary.knows.is_some());
+        let (_, alice) = node_data
+            .get_node_by_index(node_data.get_node_index("http://example.org/alice").unwrap())
+            .unwrap();
+        assert!(vocabulary.is_person(alice));
+        assert_eq!(
+            Some("http://example.org/alice.jpg".to_string()),
+            vocabulary.photo_url(node_data, prefix_manager, alice)
+        );
+        let (_, bob) = node_data
+            .get_node_by_index(node_data.get_node_index("http://example.org/bob").unwrap())
+            .unwrap();
+        assert_eq!(None, vocabulary.photo_url(node_data, prefix_manager, bob));
+        let (_, acme) = node_data
+            .get_node_by_index(node_data.get_node_index("http://example.org/acme").unwrap())
+            .unwrap();
+        assert!(!vocabulary.is_person(acme));
+
+        let mut visualization_style = GVisualizationStyle {
+            node_styles: HashMap::new(),
+            edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
+            default_node_style: NodeStyle::default(),
+            use_size_overwrite: false,
+            use_color_overwrite: false,
+            default_label_in_node: false,
+            min_size: 5.0,
+            max_size: 50.0,
+        };
+        vocabulary.apply_styles(&mut visualization_style, false);
+        let person_style = visualization_style.get_type_style_one(vocabulary.person_types[0]);
+        assert_eq!(NodeShape::Circle, person_style.node_shape);
+        assert_eq!(vocabulary.name, Some(person_style.label_index));
+        let organization_style = visualization_style.get_type_style_one(vocabulary.organization_types[0]);
+        assert_eq!(NodeShape::Rect, organization_style.node_shape);
+    }
+}
@@ -2,6 +2,7 @@ use string_interner::{backend::StringBackend, symbol::SymbolU32, StringInterner,
 
 use crate::IriIndex;
 
+#[derive(Clone)]
 pub struct StringIndexer {
     pub map: StringInterner<StringBackend>,
 }
@@ -27,10 +28,32 @@ impl StringIndexer {
     pub fn index_to_str(&self, index: IriIndex) -> Option<&str> {
         self.map.resolve(SymbolU32::try_from_usize(index as usize).unwrap())
     }
+
+    /**
+     * Replaces the strings for which rename returns a new value, the indexes stay the same.
+     * A string is kept if its new value is already in the indexer.
+     */
+    pub fn rename<F>(&mut self, rename: F)
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut map: StringInterner<StringBackend> = StringInterner::with_capacity(self.map.len());
+        for (_, s) in self.map.iter() {
+            match rename(s) {
+                Some(new_s) if self.map.get(&new_s).is_none() && map.get(&new_s).is_none() => {
+                    map.get_or_intern(new_s);
+                }
+                _ => {
+                    map.get_or_intern(s);
+                }
+            }
+        }
+        self.map = map;
+    }
     
 }
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct StringCache {
     pub cache: String,
 }
@@ -53,7 +76,7 @@ impl StringCache {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct IndexSpan {
     pub start: u32,
     pub len: u32,
@@ -79,6 +102,20 @@ mod tests {
         assert!(string_indexer.index_to_str(100).is_none());  
     }
 
+    #[test]
+    fn test_string_indexer_rename() {
+        let mut string_indexer = StringIndexer::new();
+        let index1 = string_indexer.get_index("http://ex.org/a");
+        let index2 = string_indexer.get_index("ex:b");
+        let index3 = string_indexer.get_index("http://ex.org/b");
+        string_indexer.rename(|s| s.strip_prefix("http://ex.org/").map(|local| format!("ex:{}", local)));
+        assert_eq!(Some("ex:a"), string_indexer.index_to_str(index1));
+        assert_eq!(Some("ex:b"), string_indexer.index_to_str(index2));
+        // already existing value is not taken
+        assert_eq!(Some("http://ex.org/b"), string_indexer.index_to_str(index3));
+        assert_eq!(index1, string_indexer.get_index("ex:a"));
+    }
+
     #[test]
     fn test_string_cache() {
         let mut string_cache = super::StringCache::default();
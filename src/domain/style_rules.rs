@@ -0,0 +1,226 @@
+use std::collections::HashMap;
+
+use egui::Color32;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    IriIndex,
+    domain::{Indexers, NObject, graph_styles::NodeStyle},
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Copy, Clone, Debug)]
+#[repr(u8)]
+pub enum RuleOperator {
+    Equals = 0,
+    Contains = 1,
+    Greater = 2,
+    Less = 3,
+}
+
+impl RuleOperator {
+    pub const ALL: [RuleOperator; 4] = [
+        RuleOperator::Equals,
+        RuleOperator::Contains,
+        RuleOperator::Greater,
+        RuleOperator::Less,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RuleOperator::Equals => "=",
+            RuleOperator::Contains => "contains",
+            RuleOperator::Greater => ">",
+            RuleOperator::Less => "<",
+        }
+    }
+}
+
+impl TryFrom<u8> for RuleOperator {
+    type Error = ();
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(RuleOperator::Equals),
+            1 => Ok(RuleOperator::Contains),
+            2 => Ok(RuleOperator::Greater),
+            3 => Ok(RuleOperator::Less),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Changes the type style of the instances of a type that have a property value matching the condition
+#[derive(Clone, PartialEq, Debug)]
+pub struct StyleRule {
+    pub type_index: IriIndex,
+    pub predicate: IriIndex,
+    pub operator: RuleOperator,
+    pub value: String,
+    pub color: Option<Color32>,
+    // multiplies the size of the type style
+    pub size_factor: f32,
+}
+
+impl StyleRule {
+    pub fn new(type_index: IriIndex, predicate: IriIndex) -> Self {
+        Self {
+            type_index,
+            predicate,
+            operator: RuleOperator::Equals,
+            value: String::new(),
+            color: Some(Color32::GRAY),
+            size_factor: 1.0,
+        }
+    }
+
+    pub fn apply(&self, type_style: &NodeStyle) -> NodeStyle {
+        NodeStyle {
+            color: self.color.unwrap_or(type_style.color),
+            priority: type_style.priority,
+            label_index: type_style.label_index,
+            node_shape: type_style.node_shape,
+            node_size: type_style.node_size,
+            width: type_style.width * self.size_factor,
+            height: type_style.height * self.size_factor,
+            border_width: type_style.border_width,
+            border_color: type_style.border_color,
+            corner_radius: type_style.corner_radius,
+            max_lines: type_style.max_lines,
+            label_position: type_style.label_position,
+            label_max_width: type_style.label_max_width,
+            font_size: type_style.font_size,
+            label_color: type_style.label_color,
+            icon_style: type_style.icon_style.clone(),
+            is_default: false,
+        }
+    }
+
+    // number is the precomputed numeric value of the rule, NaN if the rule value is not a number
+    fn matches(&self, number: f64, value: &str) -> bool {
+        match self.operator {
+            RuleOperator::Equals => value == self.value,
+            RuleOperator::Contains => value.contains(self.value.as_str()),
+            RuleOperator::Greater => value.trim().parse::<f64>().is_ok_and(|value| value > number),
+            RuleOperator::Less => value.trim().parse::<f64>().is_ok_and(|value| value < number),
+        }
+    }
+}
+
+/**
+ * Ordered list of style rules, the first matching rule wins.
+ * The index of rules by predicate is precomputed, so a node is checked only against the rules
+ * of its properties and nodes are not checked at all if there are no rules.
+ * update_index must be called after the rules are changed.
+ */
+#[derive(Default)]
+pub struct StyleRules {
+    pub rules: Vec<StyleRule>,
+    // predicate -> (rule position, numeric rule value)
+    index: HashMap<IriIndex, Vec<(usize, f64)>>,
+}
+
+impl StyleRules {
+    pub fn update_index(&mut self) {
+        self.index.clear();
+        for (position, rule) in self.rules.iter().enumerate() {
+            let number = rule.value.trim().parse::<f64>().unwrap_or(f64::NAN);
+            self.index.entry(rule.predicate).or_default().push((position, number));
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    pub fn matching(&self, node: &NObject, indexers: &Indexers) -> Option<&StyleRule> {
+        if self.index.is_empty() {
+            return None;
+        }
+        let mut best: Option<usize> = None;
+        for (predicate, literal) in node.properties.iter() {
+            let Some(rule_positions) = self.index.get(predicate) else {
+                continue;
+            };
+            for (position, number) in rule_positions.iter() {
+                if best.is_some_and(|best| best <= *position) {
+                    break;
+                }
+                let rule = &self.rules[*position];
+                if node.types.contains(&rule.type_index) && rule.matches(*number, literal.as_str_ref(indexers)) {
+                    best = Some(*position);
+                }
+            }
+        }
+        best.map(|position| &self.rules[position])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{RdfData, prefix_manager::PrefixManager};
+    use crate::integration::rdf_patch::RdfPatch;
+
+    #[test]
+    fn test_matching_rules() {
+        let mut rdf_data = RdfData {
+            node_data: crate::domain::NodeData::new(),
+            prefix_manager: PrefixManager::new(),
+        };
+        RdfPatch::parse_rdf_patch(
+            "A <http://example.org/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Account> .\n\
+             A <http://example.org/a> <http://example.org/status> \"inactive\" .\n\
+             A <http://example.org/a> <http://example.org/amount> \"2000\" .\n\
+             A <http://example.org/b> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Account> .\n\
+             A <http://example.org/b> <http://example.org/status> \"active\" .\n\
+             A <http://example.org/b> <http://example.org/amount> \"500\" .\n",
+        )
+        .apply(&mut rdf_data, &[]);
+        let account = rdf_data.node_data.indexers.type_indexer.get_index("http://example.org/Account");
+        let status = rdf_data.node_data.indexers.predicate_indexer.get_index("http://example.org/status");
+        let amount = rdf_data.node_data.indexers.predicate_indexer.get_index("http://example.org/amount");
+        let node_data = &rdf_data.node_data;
+        let (_, node_a) = node_data.get_node_by_index(node_data.get_node_index("http://example.org/a").unwrap()).unwrap();
+        let (_, node_b) = node_data.get_node_by_index(node_data.get_node_index("http://example.org/b").unwrap()).unwrap();
+
+        let mut style_rules = StyleRules::default();
+        assert!(style_rules.matching(node_a, &node_data.indexers).is_none());
+        style_rules.rules.push(StyleRule {
+            value: "1000".to_string(),
+            operator: RuleOperator::Greater,
+            color: None,
+            size_factor: 2.0,
+            ..StyleRule::new(account, amount)
+        });
+        style_rules.rules.push(StyleRule {
+            value: "inactive".to_string(),
+            ..StyleRule::new(account, status)
+        });
+        style_rules.update_index();
+        // the first rule wins
+        assert_eq!(Some(&style_rules.rules[0]), style_rules.matching(node_a, &node_data.indexers));
+        assert!(style_rules.matching(node_b, &node_data.indexers).is_none());
+        style_rules.rules.swap(0, 1);
+        style_rules.update_index();
+        assert_eq!(Some(RuleOperator::Equals), style_rules.matching(node_a, &node_data.indexers).map(|rule| rule.operator));
+        style_rules.rules[0].operator = RuleOperator::Contains;
+        style_rules.rules[0].value = "active".to_string();
+        style_rules.update_index();
+        assert!(style_rules.matching(node_b, &node_data.indexers).is_some());
+        // rules are bound to the type
+        style_rules.rules[0].type_index = account + 1;
+        style_rules.rules[1].type_index = account + 1;
+        style_rules.update_index();
+        assert!(style_rules.matching(node_a, &node_data.indexers).is_none());
+
+        let type_style = NodeStyle::default();
+        let rule = StyleRule {
+            size_factor: 2.0,
+            ..StyleRule::new(account, amount)
+        };
+        let style = rule.apply(&type_style);
+        assert_eq!(Color32::GRAY, style.color);
+        assert_eq!(type_style.width * 2.0, style.width);
+        assert_eq!(type_style.node_shape, style.node_shape);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    IriIndex,
+    domain::{
+        NodeData,
+        edge_metadata::{civil_from_days, days_from_civil, days_to_date, parse_date_days},
+        type_index::{TypeData, ValueTypes},
+    },
+};
+
+const DAYS_PER_MONTH: f64 = 30.44;
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// Instance placed on the timeline, the time is in days since 1970-01-01 (fraction for the time of day)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TimelineItem {
+    pub node: IriIndex,
+    pub time: f64,
+}
+
+/// Data properties of the type with xsd:date or xsd:dateTime values
+pub fn date_predicates(type_data: &TypeData) -> Vec<IriIndex> {
+    let mut predicates: Vec<IriIndex> = type_data
+        .properties
+        .iter()
+        .filter(|(_, characteristics)| {
+            characteristics
+                .value_types
+                .intersects(ValueTypes::DATE | ValueTypes::DATE_TIME)
+        })
+        .map(|(predicate, _)| *predicate)
+        .collect();
+    predicates.sort();
+    predicates
+}
+
+/// Instances with a date value of the predicate ordered by time, the first parsable value of an instance is used
+pub fn timeline_items(instances: &[IriIndex], predicate: IriIndex, node_data: &NodeData) -> Vec<TimelineItem> {
+    let mut items: Vec<TimelineItem> = instances
+        .iter()
+        .filter_map(|instance| {
+            let (_, node) = node_data.get_node_by_index(*instance)?;
+            node.properties
+                .iter()
+                .filter(|(property, _)| *property == predicate)
+                .find_map(|(_, literal)| parse_time_days(literal.as_str_ref(&node_data.indexers)))
+                .map(|time| TimelineItem { node: *instance, time })
+        })
+        .collect();
+    items.sort_by(|a, b| a.time.total_cmp(&b.time));
+    items
+}
+
+/// Days since 1970-01-01 of a xsd:date or xsd:dateTime value, the time of day (without time zone) is the fraction
+pub fn parse_time_days(value: &str) -> Option<f64> {
+    let days = parse_date_days(value)? as f64;
+    let Some((_, time)) = value.trim().split_once('T') else {
+        return Some(days);
+    };
+    let mut parts = time.split(':');
+    let hours: f64 = parts.next().and_then(|hours| hours.parse().ok()).unwrap_or(0.0);
+    let minutes: f64 = parts
+        .next()
+        .and_then(|minutes| minutes.get(0..2))
+        .and_then(|minutes| minutes.parse().ok())
+        .unwrap_or(0.0);
+    Some(days + (hours * 60.0 + minutes) / (24.0 * 60.0))
+}
+
+#[derive(Clone, Copy)]
+enum TickStep {
+    Hours(u32),
+    Days(u32),
+    Months(u32),
+    Years(i64),
+}
+
+impl TickStep {
+    fn days(&self) -> f64 {
+        match self {
+            TickStep::Hours(hours) => *hours as f64 / 24.0,
+            TickStep::Days(days) => *days as f64,
+            TickStep::Months(months) => *months as f64 * DAYS_PER_MONTH,
+            TickStep::Years(years) => *years as f64 * DAYS_PER_YEAR,
+        }
+    }
+}
+
+const TICK_STEPS: [TickStep; 20] = [
+    TickStep::Hours(1),
+    TickStep::Hours(6),
+    TickStep::Days(1),
+    TickStep::Days(7),
+    TickStep::Months(1),
+    TickStep::Months(3),
+    TickStep::Months(6),
+    TickStep::Years(1),
+    TickStep::Years(2),
+    TickStep::Years(5),
+    TickStep::Years(10),
+    TickStep::Years(20),
+    TickStep::Years(50),
+    TickStep::Years(100),
+    TickStep::Years(200),
+    TickStep::Years(500),
+    TickStep::Years(1000),
+    TickStep::Years(2000),
+    TickStep::Years(5000),
+    TickStep::Years(10000),
+];
+
+/**
+ * Labeled ticks of the time axis between start and end (days).
+ * The ticks are placed on calendar boundaries (hours, days, first day of months or years),
+ * the smallest step that gives not more than max_ticks ticks is used.
+ */
+pub fn time_ticks(start: f64, end: f64, max_ticks: usize) -> Vec<(f64, String)> {
+    let mut ticks = Vec::new();
+    if end <= start || end.is_nan() || max_ticks == 0 {
+        return ticks;
+    }
+    let span = end - start;
+    let step = TICK_STEPS
+        .iter()
+        .copied()
+        .find(|step| span / step.days() <= max_ticks as f64)
+        .unwrap_or(TickStep::Years(10000));
+    match step {
+        TickStep::Hours(hours) => {
+            let step_days = hours as f64 / 24.0;
+            let mut time = (start / step_days).ceil() * step_days;
+            while time <= end {
+                let day = time.floor();
+                let hour = ((time - day) * 24.0).round() as u32;
+                ticks.push((time, format!("{} {:02}:00", days_to_date(day as i64), hour)));
+                time += step_days;
+            }
+        }
+        TickStep::Days(days) => {
+            let mut time = start.ceil();
+            while time <= end {
+                ticks.push((time, days_to_date(time as i64)));
+                time += days as f64;
+            }
+        }
+        TickStep::Months(months) => {
+            let (mut year, month, _) = civil_from_days(start.floor() as i64);
+            // months counted from 0 and rounded to the step
+            let mut month = (month - 1) / months * months;
+            loop {
+                let time = days_from_civil(year, month + 1, 1) as f64;
+                if time > end {
+                    break;
+                }
+                if time >= start {
+                    ticks.push((time, format!("{:04}-{:02}", year, month + 1)));
+                }
+                month += months;
+                if month >= 12 {
+                    month -= 12;
+                    year += 1;
+                }
+            }
+        }
+        TickStep::Years(years) => {
+            let (year, _, _) = civil_from_days(start.floor() as i64);
+            let mut year = year.div_euclid(years) * years;
+            loop {
+                let time = days_from_civil(year, 1, 1) as f64;
+                if time > end {
+                    break;
+                }
+                if time >= start {
+                    ticks.push((time, year.to_string()));
+                }
+                year += years;
+            }
+        }
+    }
+    ticks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{RdfData, type_index::TypeInstanceIndex};
+
+    #[test]
+    fn test_parse_time_days() {
+        assert_eq!(Some(0.0), parse_time_days("1970-01-01"));
+        assert_eq!(Some(1.5), parse_time_days("1970-01-02T12:00:00Z"));
+        assert_eq!(Some(0.25), parse_time_days("1970-01-01T06:00"));
+        assert_eq!(None, parse_time_days("yesterday"));
+    }
+
+    #[test]
+    fn test_time_ticks() {
+        let start = parse_time_days("2001-06-15").unwrap();
+        let end = parse_time_days("2004-02-01").unwrap();
+        let ticks = time_ticks(start, end, 4);
+        let labels: Vec<&str> = ticks.iter().map(|(_, label)| label.as_str()).collect();
+        assert_eq!(vec!["2002", "2003", "2004"], labels);
+        let ticks = time_ticks(start, start + 100.0, 5);
+        assert_eq!("2001-07", ticks[0].1);
+        assert!(ticks.len() <= 5);
+        let ticks = time_ticks(start, start + 0.5, 4);
+        assert_eq!("2001-06-15 00:00", ticks[0].1);
+        assert_eq!("2001-06-15 06:00", ticks[1].1);
+        assert!(time_ticks(start, start, 10).is_empty());
+    }
+
+    #[test]
+    fn test_timeline_items() {
+        let patch = r#"
+A <http://example.org/e1> a <http://example.org/Event> .
+A <http://example.org/e1> <http://example.org/date> "2020-05-01"^^<http://www.w3.org/2001/XMLSchema#date> .
+A <http://example.org/e2> a <http://example.org/Event> .
+A <http://example.org/e2> <http://example.org/date> "2019-01-01"^^<http://www.w3.org/2001/XMLSchema#date> .
+A <http://example.org/e2> <http://example.org/name> "second" .
+A <http://example.org/e3> a <http://example.org/Event> .
+"#;
+        let rdf_data = RdfData::from_patch(patch);
+        let mut type_index = TypeInstanceIndex::new();
+        type_index.update(&rdf_data.node_data);
+        let node_data = &rdf_data.node_data;
+        let event = node_data
+            .find_type_index(&rdf_data.prefix_manager, "http://example.org/Event")
+            .unwrap();
+        let date = node_data
+            .find_predicate_index(&rdf_data.prefix_manager, "http://example.org/date")
+            .unwrap();
+        let type_data = type_index.types.get(&event).unwrap();
+        assert_eq!(vec![date], date_predicates(type_data));
+        let items = timeline_items(&type_data.instances, date, node_data);
+        let nodes: Vec<IriIndex> = items.iter().map(|item| item.node).collect();
+        assert_eq!(
+            vec![
+                node_data.get_node_index("http://example.org/e2").unwrap(),
+                node_data.get_node_index("http://example.org/e1").unwrap()
+            ],
+            nodes
+        );
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::{HashSet, VecDeque};
+
+use string_interner::Symbol;
+
+use crate::{IriIndex, domain::NodeData};
+
+/// Name of the virtual predicate that holds the materialized closure of the predicate
+pub fn closure_predicate_name(predicate_name: &str) -> String {
+    format!("{}+", predicate_name)
+}
+
+pub fn find_closure_predicate(node_data: &NodeData, predicate: IriIndex) -> Option<IriIndex> {
+    let predicate_name = node_data.get_predicate(predicate)?;
+    node_data
+        .indexers
+        .predicate_indexer
+        .map
+        .get(closure_predicate_name(predicate_name).as_str())
+        .map(|symbol| symbol.to_usize() as IriIndex)
+}
+
+/// All nodes reachable from start over the predicate (e.g. all ancestors for skos:broader) in breadth first order
+pub fn reachable_nodes(node_data: &NodeData, predicate: IriIndex, start: IriIndex) -> Vec<IriIndex> {
+    let mut visited: HashSet<IriIndex> = HashSet::from([start]);
+    let mut result: Vec<IriIndex> = Vec::new();
+    let mut queue: VecDeque<IriIndex> = VecDeque::from([start]);
+    while let Some(node_index) = queue.pop_front() {
+        let Some((_, node)) = node_data.get_node_by_index(node_index) else {
+            continue;
+        };
+        for (reference_predicate, target_index) in node.references.iter() {
+            if *reference_predicate == predicate && visited.insert(*target_index) {
+                result.push(*target_index);
+                queue.push_back(*target_index);
+            }
+        }
+    }
+    result
+}
+
+/**
+ * Adds references with the closure predicate for all pairs that are connected by a path of the predicate
+ * but not directly. Returns the number of added references.
+ */
+pub fn materialize_closure(node_data: &mut NodeData, predicate: IriIndex) -> usize {
+    let Some(predicate_name) = node_data.get_predicate(predicate) else {
+        return 0;
+    };
+    let closure_predicate = node_data.get_predicate_index(&closure_predicate_name(predicate_name));
+    remove_references(node_data, closure_predicate);
+    let mut new_references: Vec<(IriIndex, IriIndex)> = Vec::new();
+    for (node_index, (_, node)) in node_data.iter().enumerate() {
+        let node_index = node_index as IriIndex;
+        if !node.references.iter().any(|(reference_predicate, _)| *reference_predicate == predicate) {
+            continue;
+        }
+        let direct: HashSet<IriIndex> = node
+            .references
+            .iter()
+            .filter(|(reference_predicate, _)| *reference_predicate == predicate)
+            .map(|(_, target_index)| *target_index)
+            .collect();
+        for target_index in reachable_nodes(node_data, predicate, node_index) {
+            if target_index != node_index && !direct.contains(&target_index) {
+                new_references.push((node_index, target_index));
+            }
+        }
+    }
+    for (node_index, target_index) in new_references.iter() {
+        if let Some((_, node)) = node_data.get_node_by_index_mut(*node_index) {
+            node.references.push((closure_predicate, *target_index));
+        }
+        if let Some((_, target_node)) = node_data.get_node_by_index_mut(*target_index) {
+            target_node.reverse_references.push((closure_predicate, *node_index));
+        }
+    }
+    new_references.len()
+}
+
+/// Removes all references of the predicate, returns the number of removed references
+pub fn remove_references(node_data: &mut NodeData, predicate: IriIndex) -> usize {
+    let mut removed = 0;
+    for (_, node) in node_data.iter_mut() {
+        let len = node.references.len();
+        node.references.retain(|(reference_predicate, _)| *reference_predicate != predicate);
+        removed += len - node.references.len();
+        node.reverse_references
+            .retain(|(reference_predicate, _)| *reference_predicate != predicate);
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::RdfData;
+
+    use super::*;
+
+    #[test]
+    fn test_transitive_closure() {
+        let mut rdf_data = RdfData::from_patch(
+            r#"
+PA skos: <http://www.w3.org/2004/02/skos/core#> .
+PA ex: <http://example.org/> .
+A ex:a skos:broader ex:b .
+A ex:b skos:broader ex:c .
+A ex:c skos:broader ex:d .
+A ex:a skos:broader ex:c .
+"#,
+        );
+        let node_data = &mut rdf_data.node_data;
+        let broader = node_data.get_predicate_index("skos:broader");
+        let a = node_data.get_node_index("http://example.org/a").unwrap();
+        let d = node_data.get_node_index("http://example.org/d").unwrap();
+        assert_eq!(3, reachable_nodes(node_data, broader, a).len());
+        assert!(reachable_nodes(node_data, broader, d).is_empty());
+        assert_eq!(None, find_closure_predicate(node_data, broader));
+        // a -> d and b -> d, a -> c is direct
+        assert_eq!(2, materialize_closure(node_data, broader));
+        // materialize again replaces the closure references
+        assert_eq!(2, materialize_closure(node_data, broader));
+        let closure = find_closure_predicate(node_data, broader).unwrap();
+        assert_eq!("skos:broader+", node_data.get_predicate(closure).unwrap());
+        let (_, node_d) = node_data.get_node_by_index(d).unwrap();
+        assert_eq!(
+            2,
+            node_d
+                .reverse_references
+                .iter()
+                .filter(|(predicate, _)| *predicate == closure)
+                .count()
+        );
+        assert_eq!(2, remove_references(node_data, closure));
+        assert!(reachable_nodes(node_data, closure, a).is_empty());
+    }
+}
@@ -1,20 +1,250 @@
 use core::f64;
-use std::{collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::Range,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 #[cfg(not(target_arch = "wasm32"))]
 use std::time::Instant;
 use bitflags::bitflags;
 use ordered_float::OrderedFloat;
+use rand::{Rng, seq::index::sample};
 use egui::Pos2;
 use string_interner::Symbol;
 
-use crate::{IriIndex, domain::{LabelContext, LangIndex, Literal, NodeData, RdfData}, ui::table_view::CHAR_WIDTH, uistate::ref_selection::RefSelection};
+use crate::{IriIndex, domain::{LabelContext, LangIndex, Literal, NodeData, RdfData, column_chart::ColumnChart, named_graphs::GraphIndex, ontology::TypeHierarchy}, ui::table_view::CHAR_WIDTH, uistate::ref_selection::RefSelection};
 
 use rayon::prelude::*;
 
 pub const IRI_WIDTH: f32 = 300.0;
+// default number of random instances added to the visual graph
+pub const DEFAULT_SAMPLE_SIZE: usize = 10;
 pub const REF_COUNT_WIDTH: f32 = 80.0;
 const DEFAULT_COLUMN_WIDTH: f32 = 220.0;
 
+// nodes of one work package of the parallel indexing
+const INDEX_CHUNK_SIZE: usize = 20_000;
+
+/**
+ * Type statistics of all nodes.
+ * The nodes are partitioned in chunks that are indexed in parallel, the results of the chunks are merged in order,
+ * so the instances keep the order of the nodes.
+ * It does not hold any ui state, so it can be computed in the loading thread.
+ */
+pub struct IndexedTypes {
+    pub nodes: usize,
+    pub unresolved_references: usize,
+    pub blank_nodes: usize,
+    pub references: usize,
+    pub properties: usize,
+    pub types: HashMap<IriIndex, TypeData>,
+}
+
+impl IndexedTypes {
+    pub fn new(node_data: &NodeData, progress: &(dyn Fn(usize, usize) + Sync)) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let start = Instant::now();
+        let node_len = node_data.len();
+        let indexed_nodes = AtomicUsize::new(0);
+        let chunks: Vec<IndexedTypes> = (0..node_len.div_ceil(INDEX_CHUNK_SIZE))
+            .into_par_iter()
+            .map(|chunk| {
+                let chunk_start = chunk * INDEX_CHUNK_SIZE;
+                let chunk_end = (chunk_start + INDEX_CHUNK_SIZE).min(node_len);
+                let indexed_chunk = Self::index_nodes(node_data, chunk_start..chunk_end);
+                let indexed = indexed_nodes.fetch_add(chunk_end - chunk_start, Ordering::Relaxed);
+                progress(indexed + chunk_end - chunk_start, node_len);
+                indexed_chunk
+            })
+            .collect();
+        let mut chunks = chunks.into_iter();
+        let mut indexed_types = chunks.next().unwrap_or_else(|| Self::index_nodes(node_data, 0..0));
+        for chunk in chunks {
+            indexed_types.merge(chunk);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let duration = start.elapsed();
+            log::info!(
+                "Indexed {} nodes in {:?} ({:.0} nodes per second)",
+                node_len,
+                duration,
+                node_len as f64 / duration.as_secs_f64()
+            );
+        }
+        indexed_types
+    }
+
+    fn index_nodes(node_data: &NodeData, node_range: Range<usize>) -> Self {
+        let mut indexed_types = IndexedTypes {
+            nodes: 0,
+            unresolved_references: 0,
+            blank_nodes: 0,
+            references: 0,
+            properties: 0,
+            types: HashMap::new(),
+        };
+        for node_index in node_range {
+            let Some((_node_iri, node)) = node_data.get_node_by_index(node_index as IriIndex) else {
+                continue;
+            };
+            if node.has_subject {
+                indexed_types.nodes += 1;
+            } else {
+                indexed_types.unresolved_references += 1;
+            }
+            if node.is_blank_node {
+                indexed_types.blank_nodes += 1;
+            }
+            for type_index in &node.types {
+                let type_data = indexed_types
+                    .types
+                    .entry(*type_index)
+                    .or_insert_with(|| TypeData::new(*type_index));
+                type_data.instances.push(node_index as IriIndex);
+                for (property_index, property_stat) in type_data.properties.iter_mut() {
+                    let mut property_card = 0;
+                    for (predicate_index, value) in &node.properties {
+                        if *property_index == *predicate_index {
+                            property_stat.count += 1;
+                            property_stat.value_types |= value.value_type(&node_data.indexers);
+                            property_card += 1;
+                            property_stat.max_len = property_stat
+                                .max_len
+                                .max(value.as_str_ref(&node_data.indexers).len() as u32);
+                        }
+                    }
+                    property_stat.max_cardinality = property_stat.max_cardinality.max(property_card);
+                    property_stat.min_cardinality = property_stat.min_cardinality.min(property_card);
+                }
+                let mut unknown_properties = vec![];
+                for (predicate_index, _value) in &node.properties {
+                    if !type_data.properties.contains_key(predicate_index) {
+                        unknown_properties.push(*predicate_index);
+                    }
+                }
+                // the instances before this one do not have the property
+                let first_instance = type_data.instances.len() == 1;
+                for predicate_index in unknown_properties {
+                    let mut property_card = 0;
+                    let mut property_stat = DataPropCharacteristics::default();
+                    for (property_index, value) in &node.properties {
+                        if *property_index == predicate_index {
+                            property_stat.count += 1;
+                            property_stat.value_types |= value.value_type(&node_data.indexers);
+                            property_card += 1;
+                            property_stat.max_len = property_stat
+                                .max_len
+                                .max(value.as_str_ref(&node_data.indexers).len() as u32);
+                        }
+                    }
+                    property_stat.max_cardinality = property_card;
+                    property_stat.min_cardinality = if first_instance { property_card } else { 0 };
+                    type_data.properties.insert(predicate_index, property_stat);
+                }
+                count_type_references(&mut type_data.references, &node.references, node_data, first_instance);
+                count_type_references(
+                    &mut type_data.rev_references,
+                    &node.reverse_references,
+                    node_data,
+                    first_instance,
+                );
+            }
+            indexed_types.references += node.references.len();
+            indexed_types.properties += node.properties.len();
+        }
+        indexed_types
+    }
+
+    /// Appends the statistics of the following nodes
+    fn merge(&mut self, other: IndexedTypes) {
+        self.nodes += other.nodes;
+        self.unresolved_references += other.unresolved_references;
+        self.blank_nodes += other.blank_nodes;
+        self.references += other.references;
+        self.properties += other.properties;
+        for (type_index, other_type_data) in other.types {
+            match self.types.get_mut(&type_index) {
+                Some(type_data) => merge_type_data(type_data, other_type_data),
+                None => {
+                    self.types.insert(type_index, other_type_data);
+                }
+            }
+        }
+    }
+}
+
+// both parts have instances, so a property or reference missing in one part has minimal cardinality 0
+fn merge_type_data(type_data: &mut TypeData, other: TypeData) {
+    for (predicate_index, property_stat) in type_data.properties.iter_mut() {
+        if !other.properties.contains_key(predicate_index) {
+            property_stat.min_cardinality = 0;
+        }
+    }
+    for (predicate_index, other_stat) in other.properties {
+        match type_data.properties.get_mut(&predicate_index) {
+            Some(property_stat) => {
+                property_stat.count += other_stat.count;
+                property_stat.max_len = property_stat.max_len.max(other_stat.max_len);
+                property_stat.max_cardinality = property_stat.max_cardinality.max(other_stat.max_cardinality);
+                property_stat.min_cardinality = property_stat.min_cardinality.min(other_stat.min_cardinality);
+                property_stat.value_types |= other_stat.value_types;
+            }
+            None => {
+                type_data.properties.insert(
+                    predicate_index,
+                    DataPropCharacteristics {
+                        min_cardinality: 0,
+                        ..other_stat
+                    },
+                );
+            }
+        }
+    }
+    merge_type_references(&mut type_data.references, other.references);
+    merge_type_references(&mut type_data.rev_references, other.rev_references);
+    type_data.instances.extend(other.instances);
+}
+
+fn merge_type_references(
+    type_references: &mut HashMap<IriIndex, ReferenceCharacteristics>,
+    other: HashMap<IriIndex, ReferenceCharacteristics>,
+) {
+    for (predicate_index, reference_characteristics) in type_references.iter_mut() {
+        if !other.contains_key(predicate_index) {
+            reference_characteristics.min_cardinality = 0;
+        }
+    }
+    for (predicate_index, other_characteristics) in other {
+        match type_references.get_mut(&predicate_index) {
+            Some(reference_characteristics) => {
+                reference_characteristics.count += other_characteristics.count;
+                reference_characteristics.max_cardinality = reference_characteristics
+                    .max_cardinality
+                    .max(other_characteristics.max_cardinality);
+                reference_characteristics.min_cardinality = reference_characteristics
+                    .min_cardinality
+                    .min(other_characteristics.min_cardinality);
+                for type_index in other_characteristics.types {
+                    if !reference_characteristics.types.contains(&type_index) {
+                        reference_characteristics.types.push(type_index);
+                    }
+                }
+            }
+            None => {
+                type_references.insert(
+                    predicate_index,
+                    ReferenceCharacteristics {
+                        min_cardinality: 0,
+                        ..other_characteristics
+                    },
+                );
+            }
+        }
+    }
+}
+
 pub struct TypeInstanceIndex {
     pub nodes: usize,
     pub unique_predicates: usize,
@@ -33,6 +263,12 @@ pub struct TypeInstanceIndex {
     pub types_filter: String,
     pub type_cell_action: TypeCellAction,
     pub value_statistics: Option<ValueStatistics>,
+    // named graph the filtered instances were computed for
+    pub filtered_graph: Option<GraphIndex>,
+    pub column_chart: Option<ColumnChart>,
+    pub type_hierarchy: Option<TypeHierarchy>,
+    pub group_by_hierarchy: bool,
+    pub count_transitive: bool,
 }
 
 pub struct ValueStatistics {
@@ -121,6 +357,8 @@ pub struct InstanceView {
     pub ref_count_width: f32,
     pub selected_idx: Option<(IriIndex, usize)>,
     pub ref_selection: RefSelection,
+    // number of random instances to add to the visual graph
+    pub sample_size: usize,
 }
 
 pub enum InstanceColumnResize {
@@ -155,6 +393,7 @@ impl Default for InstanceView {
             ref_count_width: REF_COUNT_WIDTH,
             selected_idx: None,
             ref_selection: RefSelection::None,
+            sample_size: DEFAULT_SAMPLE_SIZE,
         }
     }
 }
@@ -201,6 +440,17 @@ impl TypeData {
         }
     }
     
+    /// Random filtered instances in the order of the table, all if there are not more than count
+    pub fn sample_instances<R: Rng>(&self, count: usize, rng: &mut R) -> Vec<IriIndex> {
+        let len = self.filtered_instances.len();
+        if count >= len {
+            return self.filtered_instances.clone();
+        }
+        let mut positions = sample(rng, len, count).into_vec();
+        positions.sort_unstable();
+        positions.iter().map(|pos| self.filtered_instances[*pos]).collect()
+    }
+
     pub fn calculate_value_statistics(&self, predicate: IriIndex, node_data: &NodeData) -> ValueStatistics {
         let value_type = self.properties.get(&predicate).map_or(ValueTypes::empty(), |d| d.value_types);
         ValueStatistics::calculate_value_statistics(predicate, value_type, node_data, &self.filtered_instances)
@@ -212,7 +462,7 @@ impl TypeData {
             if prop_desc.value_types == ValueTypes::INTEGER {
                 let row_pred : Vec<(usize, i64)> = self.filtered_instances.iter().enumerate().map(| (row_id,instance_idx) | {
                     if let Some((_, nobject)) = rdf_data.node_data.get_node_by_index(*instance_idx) {
-                        if let Some(literal) = nobject.get_property(predicate_to_sort, language_index) {
+                        if let Some(literal) = nobject.get_property(predicate_to_sort, language_index, &rdf_data.node_data.indexers.language_fallback) {
                             (row_id, literal.as_str_ref(&rdf_data.node_data.indexers).parse::<i64>().unwrap_or(0))
                         } else {
                             (row_id, 0)
@@ -226,7 +476,7 @@ impl TypeData {
             } else if prop_desc.value_types == ValueTypes::DOUBLE {
                 let row_pred : Vec<(usize, OrderedFloat<f64>)> = self.filtered_instances.iter().enumerate().map(| (row_id,instance_idx) | {
                     if let Some((_, nobject)) = rdf_data.node_data.get_node_by_index(*instance_idx) {
-                        if let Some(literal) = nobject.get_property(predicate_to_sort, language_index) {
+                        if let Some(literal) = nobject.get_property(predicate_to_sort, language_index, &rdf_data.node_data.indexers.language_fallback) {
                             (row_id, OrderedFloat(literal.as_str_ref(&rdf_data.node_data.indexers).parse::<f64>().unwrap_or(0.0)))
                         } else {
                             (row_id, OrderedFloat(0.0))
@@ -247,9 +497,9 @@ impl TypeData {
             if let Some((_, node_a)) = node_a {
                 if let Some((_, node_b)) = node_b {
                     let a_value =
-                        &node_a.get_property(predicate_to_sort, language_index);
+                        &node_a.get_property(predicate_to_sort, language_index, &rdf_data.node_data.indexers.language_fallback);
                     let b_value =
-                        &node_b.get_property(predicate_to_sort, language_index);
+                        &node_b.get_property(predicate_to_sort, language_index, &rdf_data.node_data.indexers.language_fallback);
                     if let Some(a_value) = a_value {
                         if let Some(b_value) = b_value {
                             let a_value = a_value.as_str_ref(&rdf_data.node_data.indexers);
@@ -364,7 +614,7 @@ impl ValueStatistics {
     }
 }
 
-fn count_type_references(type_references: &mut HashMap<IriIndex, ReferenceCharacteristics>, references: &Vec<(IriIndex, IriIndex)>, node_data: &NodeData) {
+fn count_type_references(type_references: &mut HashMap<IriIndex, ReferenceCharacteristics>, references: &Vec<(IriIndex, IriIndex)>, node_data: &NodeData, first_instance: bool) {
     let mut ref_counts: Vec<(IriIndex, u32, Vec<IriIndex>)> = Vec::new();
     for (predicate_index, ref_index) in references {
         let ref_node = node_data.get_node_by_index(*ref_index);
@@ -411,7 +661,7 @@ fn count_type_references(type_references: &mut HashMap<IriIndex, ReferenceCharac
                 predicate_index,
                 ReferenceCharacteristics {
                     count,
-                    min_cardinality: count,
+                    min_cardinality: if first_instance { count } else { 0 },
                     max_cardinality: count,
                     types,
                 },
@@ -444,6 +694,71 @@ pub struct ColumnDesc {
     pub predicate_index: IriIndex,
     pub width: f32,
     pub visible: bool,
+    pub abbreviation: ColumnAbbreviation,
+    // values of all languages side by side instead of the value in the table language
+    pub all_languages: bool,
+}
+
+pub const DEFAULT_TRUNCATE_CHARS: u32 = 50;
+
+/// How long literal values are shown in the table cells. The cell popup shows always the full value.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColumnAbbreviation {
+    None,
+    // cut after the number of chars and add ellipsis
+    Truncate(u32),
+    FirstLine,
+    // show the number of chars instead of the value
+    Length,
+}
+
+impl ColumnAbbreviation {
+    pub fn apply<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match self {
+            ColumnAbbreviation::None => Cow::Borrowed(value),
+            ColumnAbbreviation::Truncate(max_chars) => match value.char_indices().nth(*max_chars as usize) {
+                Some((byte_pos, _)) => Cow::Owned(format!("{}…", &value[..byte_pos])),
+                None => Cow::Borrowed(value),
+            },
+            ColumnAbbreviation::FirstLine => match value.find(['\n', '\r']) {
+                Some(line_end) => Cow::Owned(format!("{}…", &value[..line_end])),
+                None => Cow::Borrowed(value),
+            },
+            ColumnAbbreviation::Length => Cow::Owned(format!("[{} chars]", value.chars().count())),
+        }
+    }
+
+    pub fn to_u8(&self) -> u8 {
+        match self {
+            ColumnAbbreviation::None => 0,
+            ColumnAbbreviation::Truncate(_) => 1,
+            ColumnAbbreviation::FirstLine => 2,
+            ColumnAbbreviation::Length => 3,
+        }
+    }
+
+    pub fn from_u8(value: u8, truncate_chars: u32) -> Self {
+        match value {
+            1 => ColumnAbbreviation::Truncate(truncate_chars),
+            2 => ColumnAbbreviation::FirstLine,
+            3 => ColumnAbbreviation::Length,
+            _ => ColumnAbbreviation::None,
+        }
+    }
+}
+
+// Column order, widths and visibility of the table of one type as stored in project files
+pub struct TypeColumnLayout {
+    pub type_index: IriIndex,
+    pub iri_width: f32,
+    pub ref_count_width: f32,
+    pub columns: Vec<ColumnDesc>,
+}
+
+#[derive(Default)]
+pub struct TableLayout {
+    pub selected_type: Option<IriIndex>,
+    pub types: Vec<TypeColumnLayout>,
 }
 
 impl TypeInstanceIndex {
@@ -465,7 +780,12 @@ impl TypeInstanceIndex {
             types_filter: String::new(),
             type_cell_action: TypeCellAction::None,
             value_statistics: None,
+            filtered_graph: None,
+            column_chart: None,
             predicates: Vec::new(),
+            type_hierarchy: None,
+            group_by_hierarchy: true,
+            count_transitive: false,
         }
     }
 
@@ -482,75 +802,30 @@ impl TypeInstanceIndex {
         self.types.clear();
         self.types_order.clear();
         self.predicates.clear();
+        self.filtered_graph = None;
+        self.column_chart = None;
+        self.type_hierarchy = None;
     }
 
     pub fn update(&mut self, node_data: &NodeData) {
+        self.update_with_progress(node_data, &|_, _| {});
+    }
+
+    /// The progress is called with (indexed nodes, all nodes) from the worker threads
+    pub fn update_with_progress(&mut self, node_data: &NodeData, progress: &(dyn Fn(usize, usize) + Sync)) {
+        let indexed_types = IndexedTypes::new(node_data, progress);
+        self.apply_indexed_types(indexed_types, node_data);
+    }
+
+    /// Builds the index from the type statistics computed by IndexedTypes::new (possibly in other thread)
+    pub fn apply_indexed_types(&mut self, indexed_types: IndexedTypes, node_data: &NodeData) {
         self.clean();
-        #[cfg(not(target_arch = "wasm32"))]
-        let start = Instant::now();
-        let node_len = node_data.len();
-        // TODO concurrent optimization
-        // 1. partition the instances in groups (count  rayon::current_num_threads()) in dependency to type
-        // 2. build hash map of each group (there are disjuct)
-        // 3. merge all hash maps
-        for (node_index, (_node_iri, node)) in node_data.iter().enumerate() {
-            if node.has_subject {
-                self.nodes += 1;
-            } else {
-                self.unresolved_references += 1;
-            }
-            if node.is_blank_node {
-                self.blank_nodes += 1;
-            }
-            for type_index in &node.types {
-                let type_data = self
-                    .types
-                    .entry(*type_index)
-                    .or_insert_with(|| TypeData::new(*type_index));
-                type_data.instances.push(node_index as IriIndex);
-                for (property_index, property_stat) in type_data.properties.iter_mut() {
-                    let mut property_card = 0;
-                    for (predicate_index, value) in &node.properties {
-                        if *property_index == *predicate_index {
-                            property_stat.count += 1;
-                            property_stat.value_types |= value.value_type(&node_data.indexers);
-                            property_card += 1;
-                            property_stat.max_len = property_stat
-                                .max_len
-                                .max(value.as_str_ref(&node_data.indexers).len() as u32);
-                        }
-                    }
-                    property_stat.max_cardinality = property_stat.max_cardinality.max(property_card);
-                    property_stat.min_cardinality = property_stat.min_cardinality.min(property_card);
-                }
-                let mut unknown_properties = vec![];
-                for (predicate_index, _value) in &node.properties {
-                    if !type_data.properties.contains_key(predicate_index) {
-                        unknown_properties.push(*predicate_index);
-                    }
-                }
-                for predicate_index in unknown_properties {
-                    let mut property_card = 0;
-                    let mut property_stat = DataPropCharacteristics::default();
-                    for (property_index, value) in &node.properties {
-                        if *property_index == predicate_index {
-                            property_stat.count += 1;
-                            property_card += 1;
-                            property_stat.max_len = property_stat
-                                .max_len
-                                .max(value.as_str_ref(&node_data.indexers).len() as u32);
-                        }
-                    }
-                    property_stat.max_cardinality = property_card;
-                    property_stat.min_cardinality = property_card;
-                    type_data.properties.insert(predicate_index, property_stat);
-                }
-                count_type_references(&mut type_data.references, &node.references, node_data);
-                count_type_references(&mut type_data.rev_references, &node.reverse_references, node_data);
-            }
-            self.references += node.references.len();
-            self.properties += node.properties.len();
-        }
+        self.nodes = indexed_types.nodes;
+        self.unresolved_references = indexed_types.unresolved_references;
+        self.blank_nodes = indexed_types.blank_nodes;
+        self.references = indexed_types.references;
+        self.properties = indexed_types.properties;
+        self.types = indexed_types.types;
         self.unique_predicates = node_data.unique_predicates();
         self.unique_types = node_data.unique_types();
         for (pred_index, _iri) in node_data.indexers.predicate_indexer.map.iter() {
@@ -574,6 +849,8 @@ impl TypeInstanceIndex {
                         width: (((data_characteristics.max_len + 1).max(3) as f32) * CHAR_WIDTH)
                             .min(DEFAULT_COLUMN_WIDTH),
                         visible: true,
+                        abbreviation: ColumnAbbreviation::None,
+                        all_languages: false,
                     };
                     if let Some(predicate_str) = predicate_str {
                         if predicate_str.contains("label") {
@@ -601,11 +878,53 @@ impl TypeInstanceIndex {
         }
         self.types_filter.clear();
         self.types_filtered = self.types_order.clone();
-        #[cfg(not(target_arch = "wasm32"))]
+        self.type_hierarchy = TypeHierarchy::new(node_data, &self.types);
+    }
+
+    pub fn table_layout(&self) -> TableLayout {
+        let types = self
+            .types_order
+            .iter()
+            .filter_map(|type_index| {
+                self.types.get(type_index).map(|type_data| TypeColumnLayout {
+                    type_index: *type_index,
+                    iri_width: type_data.instance_view.iri_width,
+                    ref_count_width: type_data.instance_view.ref_count_width,
+                    columns: type_data.instance_view.display_properties.clone(),
+                })
+            })
+            .collect();
+        TableLayout {
+            selected_type: self.selected_type,
+            types,
+        }
+    }
+
+    /// Restore the column layout after the index was rebuilt from the data.
+    /// Stored columns that do not exist anymore are skipped, new columns are appended after the stored ones.
+    pub fn apply_table_layout(&mut self, table_layout: TableLayout) {
+        for type_layout in table_layout.types {
+            if let Some(type_data) = self.types.get_mut(&type_layout.type_index) {
+                let instance_view = &mut type_data.instance_view;
+                instance_view.iri_width = type_layout.iri_width;
+                instance_view.ref_count_width = type_layout.ref_count_width;
+                let mut columns: Vec<ColumnDesc> = type_layout
+                    .columns
+                    .into_iter()
+                    .filter(|column| instance_view.get_column(column.predicate_index).is_some())
+                    .collect();
+                for column in instance_view.display_properties.iter() {
+                    if !columns.iter().any(|c| c.predicate_index == column.predicate_index) {
+                        columns.push(column.clone());
+                    }
+                }
+                instance_view.display_properties = columns;
+            }
+        }
+        if let Some(selected_type) = table_layout.selected_type
+            && self.types.contains_key(&selected_type)
         {
-            let duration = start.elapsed();
-            println!("Time taken to index {} nodes: {:?}", node_len, duration);
-            println!("Nodes per second: {}", node_len as f64 / duration.as_secs_f64());
+            self.selected_type = Some(selected_type);
         }
     }
 
@@ -625,4 +944,101 @@ impl TypeInstanceIndex {
                 .collect();
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_abbreviation() {
+        let text = "Žluťoučký kůň\nsecond line";
+        assert_eq!(text, ColumnAbbreviation::None.apply(text));
+        assert_eq!("Žluť…", ColumnAbbreviation::Truncate(4).apply(text));
+        assert_eq!("short", ColumnAbbreviation::Truncate(5).apply("short"));
+        assert_eq!("Žluťoučký kůň…", ColumnAbbreviation::FirstLine.apply(text));
+        assert_eq!("one line", ColumnAbbreviation::FirstLine.apply("one line"));
+        assert_eq!("[25 chars]", ColumnAbbreviation::Length.apply(text));
+        for abbreviation in [
+            ColumnAbbreviation::None,
+            ColumnAbbreviation::Truncate(30),
+            ColumnAbbreviation::FirstLine,
+            ColumnAbbreviation::Length,
+        ] {
+            assert_eq!(abbreviation, ColumnAbbreviation::from_u8(abbreviation.to_u8(), 30));
+        }
+    }
+
+    #[test]
+    fn test_sample_instances() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut type_data = TypeData::new(0);
+        type_data.filtered_instances = (100..150).collect();
+        let mut rng = StdRng::seed_from_u64(7);
+        let sample = type_data.sample_instances(10, &mut rng);
+        assert_eq!(10, sample.len());
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(sample.iter().all(|instance| type_data.filtered_instances.contains(instance)));
+        assert_eq!(50, type_data.sample_instances(60, &mut rng).len());
+    }
+
+    #[test]
+    fn test_indexed_types_merge() {
+
+        let patch = r#"
+A <http://example.org/p1> a <http://example.org/Person> .
+A <http://example.org/p1> <http://example.org/name> "Anna" .
+A <http://example.org/p1> <http://example.org/knows> <http://example.org/p2> .
+A <http://example.org/p2> a <http://example.org/Person> .
+A <http://example.org/p2> <http://example.org/name> "Bob" .
+A <http://example.org/p2> <http://example.org/name> "Robert" .
+A <http://example.org/p3> a <http://example.org/Person> .
+A <http://example.org/p3> <http://example.org/age> "30"^^<http://www.w3.org/2001/XMLSchema#integer> .
+A <http://example.org/p3> <http://example.org/knows> <http://example.org/p1> .
+A <http://example.org/p3> <http://example.org/knows> <http://example.org/p2> .
+"#;
+        let rdf_data = RdfData::from_patch(patch);
+        let node_data = &rdf_data.node_data;
+        let whole = IndexedTypes::index_nodes(node_data, 0..node_data.len());
+        // every split must give the same statistics as one sequential pass
+        for split in 1..node_data.len() {
+            let mut merged = IndexedTypes::index_nodes(node_data, 0..split);
+            merged.merge(IndexedTypes::index_nodes(node_data, split..node_data.len()));
+            assert_eq!(whole.nodes, merged.nodes);
+            assert_eq!(whole.properties, merged.properties);
+            assert_eq!(whole.references, merged.references);
+            for (type_index, type_data) in whole.types.iter() {
+                let merged_type_data = merged.types.get(type_index).unwrap();
+                assert_eq!(type_data.instances, merged_type_data.instances);
+                for (predicate, stat) in type_data.properties.iter() {
+                    let merged_stat = merged_type_data.properties.get(predicate).unwrap();
+                    assert_eq!(
+                        (stat.count, stat.max_len, stat.min_cardinality, stat.max_cardinality, stat.value_types),
+                        (
+                            merged_stat.count,
+                            merged_stat.max_len,
+                            merged_stat.min_cardinality,
+                            merged_stat.max_cardinality,
+                            merged_stat.value_types
+                        )
+                    );
+                }
+                for (predicate, characteristics) in type_data.references.iter() {
+                    let merged_characteristics = merged_type_data.references.get(predicate).unwrap();
+                    assert_eq!(
+                        (characteristics.count, characteristics.min_cardinality, characteristics.max_cardinality),
+                        (
+                            merged_characteristics.count,
+                            merged_characteristics.min_cardinality,
+                            merged_characteristics.max_cardinality
+                        )
+                    );
+                }
+            }
+        }
+        let name = node_data.find_predicate_index(&rdf_data.prefix_manager, "http://example.org/name").unwrap();
+        let person = node_data.find_type_index(&rdf_data.prefix_manager, "http://example.org/Person").unwrap();
+        let name_stat = whole.types.get(&person).unwrap().properties.get(&name).unwrap();
+        assert_eq!((3, 0, 2), (name_stat.count, name_stat.min_cardinality, name_stat.max_cardinality));
+    }
+}
@@ -0,0 +1,206 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use crate::{
+    IriIndex,
+    domain::{NodeData, statistics::StatisticsData},
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Aggregate {
+    Mean,
+    Median,
+    Max,
+}
+
+impl Aggregate {
+    pub const ALL: [Aggregate; 3] = [Aggregate::Mean, Aggregate::Median, Aggregate::Max];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Aggregate::Mean => "mean",
+            Aggregate::Median => "median",
+            Aggregate::Max => "max",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SummarySortKey {
+    Type,
+    Count,
+    Metric(usize, Aggregate),
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct MetricAggregates {
+    pub mean: f32,
+    pub median: f32,
+    pub max: f32,
+}
+
+impl MetricAggregates {
+    fn new(values: &mut [f32]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        let len = values.len();
+        let median = if len.is_multiple_of(2) {
+            (values[len / 2 - 1] + values[len / 2]) / 2.0
+        } else {
+            values[len / 2]
+        };
+        Self {
+            mean: values.iter().sum::<f32>() / len as f32,
+            median,
+            max: values[len - 1],
+        }
+    }
+
+    pub fn get(&self, aggregate: Aggregate) -> f32 {
+        match aggregate {
+            Aggregate::Mean => self.mean,
+            Aggregate::Median => self.median,
+            Aggregate::Max => self.max,
+        }
+    }
+}
+
+pub struct TypeMetricRow {
+    // None for nodes without type
+    pub type_index: Option<IriIndex>,
+    pub count: usize,
+    // one entry for each statistics result
+    pub metrics: Vec<MetricAggregates>,
+}
+
+/**
+ * Aggregates of the statistics results grouped by rdf:type of the nodes.
+ * Nodes with several types are counted for each of them.
+ */
+pub struct TypeMetricSummary {
+    pub rows: Vec<TypeMetricRow>,
+    pub sort_key: SummarySortKey,
+    pub ascending: bool,
+}
+
+impl TypeMetricSummary {
+    pub fn new(statistics_data: &StatisticsData, node_data: &NodeData) -> Self {
+        let mut values_by_type: HashMap<Option<IriIndex>, Vec<Vec<f32>>> = HashMap::new();
+        let result_count = statistics_data.results.len();
+        for (node_pos, (node_index, _)) in statistics_data.nodes.iter().enumerate() {
+            let Some((_, node)) = node_data.get_node_by_index(*node_index) else {
+                continue;
+            };
+            let types: Vec<Option<IriIndex>> = if node.types.is_empty() {
+                vec![None]
+            } else {
+                node.types.iter().map(|type_index| Some(*type_index)).collect()
+            };
+            for type_index in types {
+                let values = values_by_type
+                    .entry(type_index)
+                    .or_insert_with(|| vec![Vec::new(); result_count]);
+                for (result_index, result) in statistics_data.results.iter().enumerate() {
+                    if let Some(value) = result.get_data_vec().get(node_pos) {
+                        values[result_index].push(*value);
+                    }
+                }
+            }
+        }
+        let rows = values_by_type
+            .into_iter()
+            .map(|(type_index, mut values)| TypeMetricRow {
+                type_index,
+                count: values.first().map(|values| values.len()).unwrap_or_default(),
+                metrics: values.iter_mut().map(|values| MetricAggregates::new(values)).collect(),
+            })
+            .collect();
+        let mut summary = Self {
+            rows,
+            sort_key: SummarySortKey::Count,
+            ascending: false,
+        };
+        // initial sort by count does not need type labels
+        summary.sort(|_| String::new());
+        summary
+    }
+
+    /// Clicking the same column again toggles the direction, metrics start descending
+    pub fn set_sort_key(&mut self, sort_key: SummarySortKey, type_label: impl Fn(Option<IriIndex>) -> String) {
+        if self.sort_key == sort_key {
+            self.ascending = !self.ascending;
+        } else {
+            self.sort_key = sort_key;
+            self.ascending = sort_key == SummarySortKey::Type;
+        }
+        self.sort(type_label);
+    }
+
+    fn sort(&mut self, type_label: impl Fn(Option<IriIndex>) -> String) {
+        match self.sort_key {
+            SummarySortKey::Type => self.rows.sort_by_cached_key(|row| type_label(row.type_index)),
+            SummarySortKey::Count => self.rows.sort_by_key(|row| row.count),
+            SummarySortKey::Metric(result_index, aggregate) => self.rows.sort_by(|a, b| {
+                let a = a.metrics.get(result_index).map(|metric| metric.get(aggregate));
+                let b = b.metrics.get(result_index).map(|metric| metric.get(aggregate));
+                a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+            }),
+        }
+        if !self.ascending {
+            self.rows.reverse();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        domain::{RdfData, statistics::StatisticsResult},
+        graph_algorithms::StatisticValue,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_type_metric_summary() {
+        let rdf_data = RdfData::from_patch(
+            r#"
+PA ex: <http://example.org/> .
+A ex:a a ex:Person .
+A ex:b a ex:Person .
+A ex:c a ex:Person .
+A ex:d a ex:Company .
+A ex:a ex:worksFor ex:d .
+A ex:e ex:worksFor ex:d .
+"#,
+        );
+        let node_data = &rdf_data.node_data;
+        let node = |name: &str| node_data.get_node_index(&format!("http://example.org/{}", name)).unwrap();
+        let mut statistics_data = StatisticsData {
+            nodes: vec![(node("a"), 0), (node("b"), 1), (node("c"), 2), (node("d"), 3), (node("e"), 4)],
+            ..Default::default()
+        };
+        statistics_data.results.push(StatisticsResult::new_for_values(
+            vec![1.0, 2.0, 6.0, 10.0, 0.5],
+            StatisticValue::DegreeCentrality,
+        ));
+        let mut summary = TypeMetricSummary::new(&statistics_data, node_data);
+        assert_eq!(3, summary.rows.len());
+        let person = node_data
+            .find_type_index(&rdf_data.prefix_manager, "http://example.org/Person")
+            .unwrap();
+        // sorted by count descending
+        let row = &summary.rows[0];
+        assert_eq!(Some(person), row.type_index);
+        assert_eq!(3, row.count);
+        assert_eq!(3.0, row.metrics[0].mean);
+        assert_eq!(2.0, row.metrics[0].median);
+        assert_eq!(6.0, row.metrics[0].max);
+        summary.set_sort_key(SummarySortKey::Metric(0, Aggregate::Max), |_| String::new());
+        assert_eq!(10.0, summary.rows[0].metrics[0].max);
+        assert_eq!(None, summary.rows[2].type_index);
+        summary.set_sort_key(SummarySortKey::Metric(0, Aggregate::Max), |_| String::new());
+        assert_eq!(None, summary.rows[0].type_index);
+    }
+}
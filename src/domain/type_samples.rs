@@ -0,0 +1,172 @@
+use std::collections::HashSet;
+
+use crate::{
+    IriIndex,
+    domain::{NodeData, type_index::TypeData},
+};
+
+// sample instances share the meta graph layout with the type nodes, the flag separates them from the type indexes
+pub const SAMPLE_NODE_FLAG: IriIndex = 1 << 31;
+// predicate of the edge between a sample and its type, it is not a real predicate index
+pub const INSTANCE_OF_EDGE: IriIndex = IriIndex::MAX;
+
+pub fn sample_node_index(instance: IriIndex) -> IriIndex {
+    instance | SAMPLE_NODE_FLAG
+}
+
+/// Instance node index of a sample node in the meta graph, None for type nodes
+pub fn sample_instance(node_index: IriIndex) -> Option<IriIndex> {
+    (node_index & SAMPLE_NODE_FLAG != 0).then_some(node_index & !SAMPLE_NODE_FLAG)
+}
+
+/**
+ * Example instances of the types expanded in the meta graph.
+ * Samples are preferably chosen among the neighbors of the already shown samples,
+ * so the samples of related types are connected by real references.
+ */
+#[derive(Default)]
+pub struct TypeSamples {
+    // (type, sampled instances) in the order of expansion
+    pub samples: Vec<(IriIndex, Vec<IriIndex>)>,
+}
+
+impl TypeSamples {
+    pub fn is_expanded(&self, type_index: IriIndex) -> bool {
+        self.samples.iter().any(|(sampled_type, _)| *sampled_type == type_index)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Types that have the instance as sample
+    pub fn sample_types(&self, instance: IriIndex) -> impl Iterator<Item = IriIndex> + '_ {
+        self.samples
+            .iter()
+            .filter(move |(_, instances)| instances.contains(&instance))
+            .map(|(type_index, _)| *type_index)
+    }
+
+    fn is_sampled(&self, instance: IriIndex) -> bool {
+        self.samples.iter().any(|(_, instances)| instances.contains(&instance))
+    }
+
+    /// Chooses up to count samples of the type and returns them
+    pub fn expand(
+        &mut self,
+        type_index: IriIndex,
+        type_data: &TypeData,
+        node_data: &NodeData,
+        count: usize,
+    ) -> Vec<IriIndex> {
+        if self.is_expanded(type_index) {
+            return Vec::new();
+        }
+        let mut chosen: Vec<IriIndex> = Vec::with_capacity(count);
+        let mut seen: HashSet<IriIndex> = HashSet::new();
+        // neighbors of the current samples first
+        'samples: for (_, instances) in self.samples.iter() {
+            for instance in instances {
+                let Some((_, node)) = node_data.get_node_by_index(*instance) else {
+                    continue;
+                };
+                for (_, neighbor) in node.references.iter().chain(node.reverse_references.iter()) {
+                    if chosen.len() >= count {
+                        break 'samples;
+                    }
+                    if seen.insert(*neighbor)
+                        && let Some((_, neighbor_node)) = node_data.get_node_by_index(*neighbor)
+                        && neighbor_node.types.contains(&type_index)
+                    {
+                        chosen.push(*neighbor);
+                    }
+                }
+            }
+        }
+        for instance in type_data.instances.iter() {
+            if chosen.len() >= count {
+                break;
+            }
+            if seen.insert(*instance) {
+                chosen.push(*instance);
+            }
+        }
+        self.samples.push((type_index, chosen.clone()));
+        chosen
+    }
+
+    /// Removes the samples of the type, returns the instances that are not sample of other type
+    pub fn collapse(&mut self, type_index: IriIndex) -> Vec<IriIndex> {
+        let Some(pos) = self.samples.iter().position(|(sampled_type, _)| *sampled_type == type_index) else {
+            return Vec::new();
+        };
+        let (_, instances) = self.samples.remove(pos);
+        instances.into_iter().filter(|instance| !self.is_sampled(*instance)).collect()
+    }
+
+    /// References between the samples as (from instance, predicate, to instance)
+    pub fn sample_references(&self, node_data: &NodeData) -> Vec<(IriIndex, IriIndex, IriIndex)> {
+        let sampled: HashSet<IriIndex> = self.samples.iter().flat_map(|(_, instances)| instances.iter().copied()).collect();
+        let mut references = Vec::new();
+        for instance in sampled.iter() {
+            if let Some((_, node)) = node_data.get_node_by_index(*instance) {
+                for (predicate, target) in node.references.iter() {
+                    if sampled.contains(target) {
+                        references.push((*instance, *predicate, *target));
+                    }
+                }
+            }
+        }
+        references
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{RdfData, type_index::TypeInstanceIndex};
+
+    #[test]
+    fn test_type_samples() {
+        let patch = r#"
+A <http://example.org/p1> a <http://example.org/Person> .
+A <http://example.org/p2> a <http://example.org/Person> .
+A <http://example.org/c1> a <http://example.org/City> .
+A <http://example.org/c2> a <http://example.org/City> .
+A <http://example.org/c3> a <http://example.org/City> .
+A <http://example.org/p1> <http://example.org/livesIn> <http://example.org/c3> .
+"#;
+        let rdf_data = RdfData::from_patch(patch);
+        let mut type_index = TypeInstanceIndex::new();
+        type_index.update(&rdf_data.node_data);
+        let node_data = &rdf_data.node_data;
+        let type_of = |iri: &str| node_data.find_type_index(&rdf_data.prefix_manager, iri).unwrap();
+        let node = |iri: &str| node_data.get_node_index(iri).unwrap();
+        let person = type_of("http://example.org/Person");
+        let city = type_of("http://example.org/City");
+
+        let mut samples = TypeSamples::default();
+        let persons = samples.expand(person, type_index.types.get(&person).unwrap(), node_data, 1);
+        assert_eq!(vec![node("http://example.org/p1")], persons);
+        // the city referenced by the sampled person comes first
+        let cities = samples.expand(city, type_index.types.get(&city).unwrap(), node_data, 2);
+        assert_eq!(2, cities.len());
+        assert_eq!(node("http://example.org/c3"), cities[0]);
+        assert!(samples.expand(city, type_index.types.get(&city).unwrap(), node_data, 2).is_empty());
+        assert_eq!(vec![city], samples.sample_types(cities[0]).collect::<Vec<_>>());
+
+        let livesin = node_data.find_predicate_index(&rdf_data.prefix_manager, "http://example.org/livesIn").unwrap();
+        assert_eq!(vec![(persons[0], livesin, cities[0])], samples.sample_references(node_data));
+
+        assert_eq!(persons, samples.collapse(person));
+        assert!(!samples.is_expanded(person));
+        assert!(samples.sample_references(node_data).is_empty());
+
+        assert_eq!(Some(5), sample_instance(sample_node_index(5)));
+        assert_eq!(None, sample_instance(5));
+    }
+}
@@ -147,7 +147,7 @@ impl VisualQuery {
             let row_pred : Vec<(usize, i64)> = self.instances.chunks(self.tables_pro_row).enumerate().map(| (row_id,instances) | {
             let instance_idx = instances[table_idx];
             if let Some((_, nobject)) = rdf_data.node_data.get_node_by_index(instance_idx) {
-                if let Some(literal) = nobject.get_property(predicate, lang_index) {
+                if let Some(literal) = nobject.get_property(predicate, lang_index, &rdf_data.node_data.indexers.language_fallback) {
                     (row_id, literal.as_str_ref(&rdf_data.node_data.indexers).parse::<i64>().unwrap_or(0))
                 } else {
                     (row_id, 0)
@@ -162,7 +162,7 @@ impl VisualQuery {
             let row_pred : Vec<(usize, OrderedFloat<f64>)> = self.instances.chunks(self.tables_pro_row).enumerate().map(| (row_id,instances) | {
             let instance_idx = instances[table_idx];
             if let Some((_, nobject)) = rdf_data.node_data.get_node_by_index(instance_idx) {
-                if let Some(literal) = nobject.get_property(predicate, lang_index) {
+                if let Some(literal) = nobject.get_property(predicate, lang_index, &rdf_data.node_data.indexers.language_fallback) {
                     (row_id, OrderedFloat(literal.as_str_ref(&rdf_data.node_data.indexers).parse::<f64>().unwrap_or(0.0)))
                 } else {
                     (row_id, OrderedFloat(0.0))
@@ -177,7 +177,7 @@ impl VisualQuery {
         let mut row_pred : Vec<(usize, Literal)> = self.instances.chunks(self.tables_pro_row).enumerate().map(| (row_id,instances) | {
             let instance_idx = instances[table_idx];
             if let Some((_, nobject)) = rdf_data.node_data.get_node_by_index(instance_idx) {
-                if let Some(literal) = nobject.get_property(predicate, lang_index) {
+                if let Some(literal) = nobject.get_property(predicate, lang_index, &rdf_data.node_data.indexers.language_fallback) {
                     (row_id, literal.clone())
                 } else {
                     (row_id, Literal::NoValue())
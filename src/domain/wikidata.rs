@@ -0,0 +1,364 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use crate::{
+    IriIndex,
+    domain::{LabelContext, NodeData, edge_metadata::EdgeKey, prefix_manager::PrefixManager},
+};
+
+const WD_PROP: &str = "http://www.wikidata.org/prop/";
+const WD_STATEMENT: &str = "http://www.wikidata.org/prop/statement/";
+const WD_QUALIFIER: &str = "http://www.wikidata.org/prop/qualifier/";
+const WD_DIRECT: &str = "http://www.wikidata.org/prop/direct/";
+const WIKIBASE_RANK: &str = "http://wikiba.se/ontology#rank";
+const WIKIBASE_PREFERRED_RANK: &str = "http://wikiba.se/ontology#PreferredRank";
+const WIKIBASE_DEPRECATED_RANK: &str = "http://wikiba.se/ontology#DeprecatedRank";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum StatementRank {
+    Deprecated,
+    Normal,
+    Preferred,
+}
+
+impl StatementRank {
+    fn from_iri(iri: &str) -> Self {
+        match iri {
+            WIKIBASE_PREFERRED_RANK => StatementRank::Preferred,
+            WIKIBASE_DEPRECATED_RANK => StatementRank::Deprecated,
+            _ => StatementRank::Normal,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StatementRank::Deprecated => "deprecated",
+            StatementRank::Normal => "normal",
+            StatementRank::Preferred => "preferred",
+        }
+    }
+}
+
+pub enum QualifierValue {
+    Literal(String),
+    Node(IriIndex),
+}
+
+pub struct WikidataStatement {
+    pub statement: IriIndex,
+    pub rank: StatementRank,
+    pub qualifiers: Vec<(IriIndex, QualifierValue)>,
+}
+
+/**
+ * Wikidata statement nodes (wd:Q p:P statement, statement ps:P value) collapsed to direct edges (wd:Q wdt:P value).
+ * The qualifiers and the rank of the statements are kept as annotations of the direct edges.
+ */
+#[derive(Default)]
+pub struct WikidataStatements {
+    statements: HashMap<EdgeKey, Vec<WikidataStatement>>,
+    // best rank of all statements of one subject and property, only statements with this rank are truthy
+    best_ranks: HashMap<(IriIndex, IriIndex), StatementRank>,
+    pub statement_nodes: HashSet<IriIndex>,
+    // predicates that connect the statement nodes (p:, ps:, pq:, wikibase:rank ...)
+    pub statement_predicates: BTreeSet<IriIndex>,
+    pub direct_predicates: BTreeSet<IriIndex>,
+    // direct edges that were added to the data and are removed on reset
+    added_edges: Vec<EdgeKey>,
+    // statement predicates that were hidden by the mode
+    pub hidden_predicates: Vec<IriIndex>,
+    pub truthy_only: bool,
+    pub show_qualifiers: bool,
+}
+
+enum WikidataPredicate<'a> {
+    StatementLink(&'a str),
+    StatementValue(&'a str),
+    Qualifier,
+    Rank,
+    Other,
+}
+
+fn full_iri(prefix_manager: &PrefixManager, iri: &str) -> String {
+    prefix_manager
+        .get_full_opt(iri)
+        .map(|full_iri| full_iri.to_string())
+        .unwrap_or_else(|| iri.to_string())
+}
+
+fn classify(full_iri: &str) -> WikidataPredicate<'_> {
+    if full_iri == WIKIBASE_RANK {
+        return WikidataPredicate::Rank;
+    }
+    let Some(local_name) = full_iri.strip_prefix(WD_PROP) else {
+        return WikidataPredicate::Other;
+    };
+    let is_property = |name: &str| name.starts_with('P') && name[1..].bytes().all(|b| b.is_ascii_digit());
+    if is_property(local_name) {
+        WikidataPredicate::StatementLink(local_name)
+    } else if let Some(property) = full_iri.strip_prefix(WD_STATEMENT)
+        && is_property(property)
+    {
+        WikidataPredicate::StatementValue(property)
+    } else if full_iri.starts_with(WD_QUALIFIER) {
+        WikidataPredicate::Qualifier
+    } else {
+        WikidataPredicate::Other
+    }
+}
+
+impl WikidataStatements {
+    /// Finds the statement nodes and creates the direct predicates (wdt:) if they are not known yet
+    pub fn new(node_data: &mut NodeData, prefix_manager: &PrefixManager) -> Self {
+        let predicate_iris: HashMap<IriIndex, String> = node_data
+            .iter()
+            .flat_map(|(_, node)| {
+                node.references
+                    .iter()
+                    .map(|(predicate, _)| *predicate)
+                    .chain(node.properties.iter().map(|(predicate, _)| *predicate))
+            })
+            .collect::<HashSet<IriIndex>>()
+            .into_iter()
+            .filter_map(|predicate| {
+                node_data
+                    .get_predicate(predicate)
+                    .map(|predicate_iri| (predicate, full_iri(prefix_manager, predicate_iri)))
+            })
+            .collect();
+        let kind = |predicate: &IriIndex| {
+            predicate_iris
+                .get(predicate)
+                .map(|predicate_iri| classify(predicate_iri))
+                .unwrap_or(WikidataPredicate::Other)
+        };
+        let mut result = WikidataStatements {
+            show_qualifiers: true,
+            ..Default::default()
+        };
+        // (subject, property id, value, statement)
+        let mut found: Vec<(IriIndex, String, IriIndex, WikidataStatement)> = Vec::new();
+        for (subject_index, (_, node)) in node_data.iter().enumerate() {
+            for (link_predicate, statement_index) in node.references.iter() {
+                let WikidataPredicate::StatementLink(property) = kind(link_predicate) else {
+                    continue;
+                };
+                let Some((_, statement_node)) = node_data.get_node_by_index(*statement_index) else {
+                    continue;
+                };
+                let value = statement_node.references.iter().find(|(predicate, _)| {
+                    matches!(kind(predicate), WikidataPredicate::StatementValue(value_property) if value_property == property)
+                });
+                let Some((_, value_index)) = value else {
+                    // literal values (e.g. dates, quantities) are no edges
+                    continue;
+                };
+                let mut rank = StatementRank::Normal;
+                let mut qualifiers: Vec<(IriIndex, QualifierValue)> = Vec::new();
+                for (predicate, target_index) in statement_node.references.iter() {
+                    match kind(predicate) {
+                        WikidataPredicate::Rank => {
+                            if let Some((rank_iri, _)) = node_data.get_node_by_index(*target_index) {
+                                rank = StatementRank::from_iri(&full_iri(prefix_manager, rank_iri));
+                            }
+                        }
+                        WikidataPredicate::Qualifier => qualifiers.push((*predicate, QualifierValue::Node(*target_index))),
+                        _ => {}
+                    }
+                    result.statement_predicates.insert(*predicate);
+                }
+                for (predicate, literal) in statement_node.properties.iter() {
+                    if matches!(kind(predicate), WikidataPredicate::Qualifier) {
+                        qualifiers.push((
+                            *predicate,
+                            QualifierValue::Literal(literal.as_str_ref(&node_data.indexers).to_string()),
+                        ));
+                    }
+                }
+                result.statement_predicates.insert(*link_predicate);
+                result.statement_nodes.insert(*statement_index);
+                found.push((
+                    subject_index as IriIndex,
+                    property.to_string(),
+                    *value_index,
+                    WikidataStatement {
+                        statement: *statement_index,
+                        rank,
+                        qualifiers,
+                    },
+                ));
+            }
+        }
+        for (subject, property, value, statement) in found {
+            let direct_predicate =
+                node_data.get_predicate_index(&prefix_manager.get_prefixed(&format!("{}{}", WD_DIRECT, property)));
+            result.direct_predicates.insert(direct_predicate);
+            if statement.rank != StatementRank::Deprecated {
+                let best_rank = result
+                    .best_ranks
+                    .entry((subject, direct_predicate))
+                    .or_insert(statement.rank);
+                *best_rank = (*best_rank).max(statement.rank);
+            }
+            result
+                .statements
+                .entry((subject, direct_predicate, value))
+                .or_default()
+                .push(statement);
+        }
+        result
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.statements.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.statements.values().map(|statements| statements.len()).sum()
+    }
+
+    /// Adds the direct edges that are not in the data (truthy dumps contain only edges of the best rank)
+    pub fn materialize(&mut self, node_data: &mut NodeData) -> usize {
+        let mut added: Vec<EdgeKey> = Vec::new();
+        for edge_key in self.statements.keys() {
+            let (subject, predicate, value) = *edge_key;
+            let exists = node_data
+                .get_node_by_index(subject)
+                .is_some_and(|(_, node)| node.references.contains(&(predicate, value)));
+            if exists {
+                continue;
+            }
+            if let Some((_, node)) = node_data.get_node_by_index_mut(subject) {
+                node.references.push((predicate, value));
+            }
+            if let Some((_, node)) = node_data.get_node_by_index_mut(value) {
+                node.reverse_references.push((predicate, subject));
+            }
+            added.push(*edge_key);
+        }
+        let count = added.len();
+        self.added_edges.extend(added);
+        count
+    }
+
+    /// Removes the direct edges added by materialize
+    pub fn remove_added(&mut self, node_data: &mut NodeData) {
+        for (subject, predicate, value) in self.added_edges.drain(..) {
+            if let Some((_, node)) = node_data.get_node_by_index_mut(subject) {
+                node.references.retain(|reference| *reference != (predicate, value));
+            }
+            if let Some((_, node)) = node_data.get_node_by_index_mut(value) {
+                node.reverse_references.retain(|reference| *reference != (predicate, subject));
+            }
+        }
+    }
+
+    /// Edges without statements (e.g. from truthy dumps) are truthy
+    pub fn is_truthy(&self, edge_key: &EdgeKey) -> bool {
+        let Some(statements) = self.statements.get(edge_key) else {
+            return true;
+        };
+        let Some(best_rank) = self.best_ranks.get(&(edge_key.0, edge_key.1)) else {
+            return false;
+        };
+        statements.iter().any(|statement| statement.rank == *best_rank)
+    }
+
+    /// Qualifiers and not normal rank of the statements of the edge, e.g. "start time: 2001-01-20, end time: 2009-01-20"
+    pub fn annotation(
+        &self,
+        edge_key: &EdgeKey,
+        node_data: &NodeData,
+        label_context: &LabelContext,
+        node_label: impl Fn(IriIndex) -> String,
+    ) -> Option<String> {
+        let statements = self.statements.get(edge_key)?;
+        let annotations: Vec<String> = statements
+            .iter()
+            .map(|statement| {
+                let mut parts: Vec<String> = statement
+                    .qualifiers
+                    .iter()
+                    .map(|(predicate, value)| {
+                        let value = match value {
+                            QualifierValue::Literal(value) => {
+                                value.strip_suffix("T00:00:00Z").unwrap_or(value).to_string()
+                            }
+                            QualifierValue::Node(node_index) => node_label(*node_index),
+                        };
+                        format!(
+                            "{}: {}",
+                            node_data.predicate_display(*predicate, label_context, &node_data.indexers).as_str(),
+                            value
+                        )
+                    })
+                    .collect();
+                if statement.rank != StatementRank::Normal {
+                    parts.push(statement.rank.as_str().to_string());
+                }
+                parts.join(", ")
+            })
+            .filter(|annotation| !annotation.is_empty())
+            .collect();
+        if annotations.is_empty() {
+            None
+        } else {
+            Some(annotations.join(" | "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::{RdfData, config::IriDisplay};
+
+    use super::*;
+
+    #[test]
+    fn test_wikidata_statements() {
+        let mut rdf_data = RdfData::from_patch(
+            r#"
+PA wd: <http://www.wikidata.org/entity/> .
+PA p: <http://www.wikidata.org/prop/> .
+PA ps: <http://www.wikidata.org/prop/statement/> .
+PA pq: <http://www.wikidata.org/prop/qualifier/> .
+PA wdt: <http://www.wikidata.org/prop/direct/> .
+PA wikibase: <http://wikiba.se/ontology#> .
+A wd:Q1 wdt:P39 wd:Q11 .
+A wd:Q1 p:P39 wd:S1 .
+A wd:S1 ps:P39 wd:Q11 .
+A wd:S1 pq:P580 "2001-01-20T00:00:00Z" .
+A wd:S1 wikibase:rank wikibase:PreferredRank .
+A wd:Q1 p:P39 wd:S2 .
+A wd:S2 ps:P39 wd:Q12 .
+A wd:S2 pq:P582 "1999" .
+A wd:S2 wikibase:rank wikibase:NormalRank .
+A wd:Q1 p:P27 wd:S3 .
+A wd:S3 ps:P27 wd:Q30 .
+A wd:S3 wikibase:rank wikibase:DeprecatedRank .
+"#,
+        );
+        let prefix_manager = &rdf_data.prefix_manager;
+        let node_data = &mut rdf_data.node_data;
+        let mut statements = WikidataStatements::new(node_data, prefix_manager);
+        assert_eq!(3, statements.len());
+        assert_eq!(3, statements.statement_nodes.len());
+        assert_eq!(2, statements.materialize(node_data));
+        let node = |name: &str| node_data.get_node_index(&format!("http://www.wikidata.org/entity/{}", name)).unwrap();
+        let p39 = node_data.find_predicate_index(prefix_manager, "http://www.wikidata.org/prop/direct/P39").unwrap();
+        let p27 = node_data.find_predicate_index(prefix_manager, "http://www.wikidata.org/prop/direct/P27").unwrap();
+        let (_, q1) = node_data.get_node_by_index(node("Q1")).unwrap();
+        assert!(q1.references.contains(&(p39, node("Q12"))));
+        assert!(statements.is_truthy(&(node("Q1"), p39, node("Q11"))));
+        assert!(!statements.is_truthy(&(node("Q1"), p39, node("Q12"))));
+        assert!(!statements.is_truthy(&(node("Q1"), p27, node("Q30"))));
+        let label_context = LabelContext::new(0, IriDisplay::Full, prefix_manager);
+        assert_eq!(
+            Some("http://www.wikidata.org/prop/qualifier/P580: 2001-01-20, preferred".to_string()),
+            statements.annotation(&(node("Q1"), p39, node("Q11")), node_data, &label_context, |_| String::new())
+        );
+        let edge_q12 = (node("Q1"), p39, node("Q12"));
+        statements.remove_added(node_data);
+        let (_, q1) = node_data.get_node_by_index(edge_q12.0).unwrap();
+        assert!(!q1.references.contains(&(edge_q12.1, edge_q12.2)));
+    }
+}
@@ -1,4 +1,7 @@
-use std::collections::VecDeque;
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
 use rayon::prelude::*;
 
 use crate::{uistate::layout::Edge, support::SortedVec};
@@ -17,7 +20,52 @@ impl Default for BetweennessCentralityResult {
 }
 
 pub fn compute_betweenness_centrality(nodes_len: usize, edges: &[Edge], hidden_predicates: &SortedVec) -> Vec<BetweennessCentralityResult> {
-    // Precompute adjacency list
+    let adj = adjacency(nodes_len, edges, hidden_predicates);
+    let sources: Vec<u32> = (0..nodes_len as u32).collect();
+    let centrality = sum_dependencies(&sources, &adj, &AtomicUsize::new(0), &AtomicBool::new(false))
+        .unwrap_or_else(|| vec![0.0; nodes_len]);
+
+    centrality
+        .into_iter()
+        .map(|v| BetweennessCentralityResult { node_betweenness: v })
+        .collect()
+}
+
+/**
+ * Approximation by pivot sampling (Brandes and Pich).
+ * The dependencies are accumulated only from sample_size random source nodes and extrapolated to all nodes.
+ * The result is exact if sample_size is not smaller than number of nodes.
+ * progress counts the processed source nodes. Returns None if cancelled.
+ */
+pub fn compute_betweenness_centrality_sampled(
+    nodes_len: usize,
+    edges: &[Edge],
+    hidden_predicates: &SortedVec,
+    sample_size: usize,
+    progress: &AtomicUsize,
+    cancel: &AtomicBool,
+) -> Option<Vec<BetweennessCentralityResult>> {
+    let adj = adjacency(nodes_len, edges, hidden_predicates);
+    let (sources, scale) = if sample_size == 0 || sample_size >= nodes_len {
+        ((0..nodes_len as u32).collect::<Vec<u32>>(), 1.0)
+    } else {
+        let mut rng = rand::rng();
+        let sources = rand::seq::index::sample(&mut rng, nodes_len, sample_size)
+            .into_iter()
+            .map(|i| i as u32)
+            .collect::<Vec<u32>>();
+        (sources, nodes_len as f32 / sample_size as f32)
+    };
+    let centrality = sum_dependencies(&sources, &adj, progress, cancel)?;
+    Some(
+        centrality
+            .into_iter()
+            .map(|v| BetweennessCentralityResult { node_betweenness: v * scale })
+            .collect(),
+    )
+}
+
+fn adjacency(nodes_len: usize, edges: &[Edge], hidden_predicates: &SortedVec) -> Vec<Vec<u32>> {
     let mut adj: Vec<Vec<u32>> = vec![Vec::new(); nodes_len];
     for e in edges {
         if !hidden_predicates.contains(e.predicate) {
@@ -25,62 +73,73 @@ pub fn compute_betweenness_centrality(nodes_len: usize, edges: &[Edge], hidden_p
             adj[e.to].push(e.from as u32);
         }
     }
+    adj
+}
 
-    let centrality = (0..nodes_len)
-        .into_par_iter()
-        .map(|i| {
-            let mut distances = vec![-1; nodes_len];
-            let mut sigma = vec![0u64; nodes_len];
-            let mut stack = Vec::with_capacity(nodes_len);
-            let mut queue = VecDeque::with_capacity(nodes_len);
-            let mut predecessors: Vec<Vec<u32>> = vec![Vec::new(); nodes_len];
-            let mut delta = vec![0.0f32; nodes_len];
-
-            distances[i] = 0;
-            sigma[i] = 1;
-            queue.push_back(i as u32);
-
-            // BFS
-            while let Some(v) = queue.pop_front() {
-                stack.push(v);
-                for &w in &adj[v as usize] {
-                    if distances[w as usize] < 0 {
-                        distances[w as usize] = distances[v as usize] + 1;
-                        queue.push_back(w);
-                    }
-                    if distances[w as usize] == distances[v as usize] + 1 {
-                        sigma[w as usize] += sigma[v as usize];
-                        predecessors[w as usize].push(v);
-                    }
-                }
-            }
-
-            // Dependency accumulation
-            let mut local = vec![0.0f32; nodes_len];
-            while let Some(w) = stack.pop() {
-                for &v in &predecessors[w as usize] {
-                    delta[v as usize] += (sigma[v as usize] as f32 / sigma[w as usize] as f32)
-                        * (1.0 + delta[w as usize]);
-                }
-                if w != i as u32 {
-                    local[w as usize] += delta[w as usize];
-                }
+// Sum of the dependencies of all nodes on the shortest paths from the sources
+fn sum_dependencies(sources: &[u32], adj: &[Vec<u32>], progress: &AtomicUsize, cancel: &AtomicBool) -> Option<Vec<f32>> {
+    let nodes_len = adj.len();
+    sources
+        .par_iter()
+        .map(|&i| {
+            if cancel.load(Ordering::Relaxed) {
+                return None;
             }
-            local
-        }).reduce(
+            let local = source_dependencies(i as usize, adj);
+            progress.fetch_add(1, Ordering::Relaxed);
+            Some(local)
+        })
+        .try_reduce(
             || vec![0.0; nodes_len],
             |mut a, b| {
                 for (x, y) in a.iter_mut().zip(b) {
                     *x += y;
                 }
-                a
+                Some(a)
             },
-        );
+        )
+}
 
-    centrality
-        .into_iter()
-        .map(|v| BetweennessCentralityResult { node_betweenness: v })
-        .collect()
+fn source_dependencies(i: usize, adj: &[Vec<u32>]) -> Vec<f32> {
+    let nodes_len = adj.len();
+    let mut distances = vec![-1; nodes_len];
+    let mut sigma = vec![0u64; nodes_len];
+    let mut stack = Vec::with_capacity(nodes_len);
+    let mut queue = VecDeque::with_capacity(nodes_len);
+    let mut predecessors: Vec<Vec<u32>> = vec![Vec::new(); nodes_len];
+    let mut delta = vec![0.0f32; nodes_len];
+
+    distances[i] = 0;
+    sigma[i] = 1;
+    queue.push_back(i as u32);
+
+    // BFS
+    while let Some(v) = queue.pop_front() {
+        stack.push(v);
+        for &w in &adj[v as usize] {
+            if distances[w as usize] < 0 {
+                distances[w as usize] = distances[v as usize] + 1;
+                queue.push_back(w);
+            }
+            if distances[w as usize] == distances[v as usize] + 1 {
+                sigma[w as usize] += sigma[v as usize];
+                predecessors[w as usize].push(v);
+            }
+        }
+    }
+
+    // Dependency accumulation
+    let mut local = vec![0.0f32; nodes_len];
+    while let Some(w) = stack.pop() {
+        for &v in &predecessors[w as usize] {
+            delta[v as usize] += (sigma[v as usize] as f32 / sigma[w as usize] as f32)
+                * (1.0 + delta[w as usize]);
+        }
+        if w != i as u32 {
+            local[w as usize] += delta[w as usize];
+        }
+    }
+    local
 }
 
 
@@ -112,4 +171,37 @@ mod tests {
             assert_eq!(should_centrality[i],centrality[i].node_betweenness);
         }       
     }
+
+    #[test]
+    fn test_alg_betweennes_centrality_sampled() {
+        use super::*;
+        let edges = vec![
+            Edge { from: 0, to: 1, predicate: 0, bezier_distance: 0.0 },
+            Edge { from: 1, to: 3, predicate: 0, bezier_distance: 0.0 },
+            Edge { from: 0, to: 2, predicate: 0, bezier_distance: 0.0 },
+            Edge { from: 3, to: 4, predicate: 0, bezier_distance: 0.0 },
+            Edge { from: 2, to: 3, predicate: 0, bezier_distance: 0.0 },
+        ];
+        let hidden_predicates = SortedVec::new();
+        let progress = AtomicUsize::new(0);
+        let cancel = AtomicBool::new(false);
+        // sample size not smaller than nodes is exact
+        let exact = compute_betweenness_centrality(5, &edges, &hidden_predicates);
+        let sampled = compute_betweenness_centrality_sampled(5, &edges, &hidden_predicates, 10, &progress, &cancel).unwrap();
+        assert_eq!(exact, sampled);
+        assert_eq!(progress.load(Ordering::Relaxed), 5);
+
+        // star: only the center is between, leaves stay 0 for any sample
+        let star_edges: Vec<Edge> = (1..11)
+            .map(|leaf| Edge { from: 0, to: leaf, predicate: 0, bezier_distance: 0.0 })
+            .collect();
+        progress.store(0, Ordering::Relaxed);
+        let star = compute_betweenness_centrality_sampled(11, &star_edges, &hidden_predicates, 4, &progress, &cancel).unwrap();
+        assert_eq!(progress.load(Ordering::Relaxed), 4);
+        assert!(star[0].node_betweenness > 0.0);
+        assert!(star[1..].iter().all(|r| r.node_betweenness == 0.0));
+
+        cancel.store(true, Ordering::Relaxed);
+        assert!(compute_betweenness_centrality_sampled(11, &star_edges, &hidden_predicates, 4, &progress, &cancel).is_none());
+    }
 }
\ No newline at end of file
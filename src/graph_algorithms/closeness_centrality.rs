@@ -1,15 +1,23 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
 use rayon::prelude::*;
 
-use crate::{uistate::layout::Edge, support::SortedVec};
+use crate::graph_algorithms::weights::WeightedEdge;
 
-pub fn compute_closeness_centrality(nodes_len: usize, edges: &[Edge], hidden_predicates: &SortedVec) -> Vec<f32> {
+/**
+ * Closeness on the undirected graph.
+ * For weighted edges the shortest paths are computed by Dijkstra with 1/weight as edge length,
+ * so strong connections are short.
+ */
+pub fn compute_closeness_centrality(nodes_len: usize, edges: &[WeightedEdge]) -> Vec<f32> {
+    if edges.iter().any(|e| e.weight != 1.0) {
+        return compute_weighted_closeness_centrality(nodes_len, edges);
+    }
     // Precompute adjacency list
     let mut adj: Vec<Vec<u32>> = vec![Vec::new(); nodes_len];
     for e in edges {
-        if !hidden_predicates.contains(e.predicate) {
-            adj[e.from].push(e.to as u32);
-            adj[e.to].push(e.from as u32);
-        }
+        adj[e.from].push(e.to as u32);
+        adj[e.to].push(e.from as u32);
     }
 
     (0..nodes_len)
@@ -50,3 +58,97 @@ pub fn compute_closeness_centrality(nodes_len: usize, edges: &[Edge], hidden_pre
         })
         .collect()
 }
+
+#[derive(PartialEq)]
+struct Visit {
+    distance: f32,
+    node: u32,
+}
+
+impl Eq for Visit {}
+
+impl Ord for Visit {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // reversed for min heap
+        other.distance.total_cmp(&self.distance).then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn compute_weighted_closeness_centrality(nodes_len: usize, edges: &[WeightedEdge]) -> Vec<f32> {
+    let mut adj: Vec<Vec<(u32, f32)>> = vec![Vec::new(); nodes_len];
+    for e in edges {
+        let length = 1.0 / e.weight;
+        adj[e.from].push((e.to as u32, length));
+        adj[e.to].push((e.from as u32, length));
+    }
+
+    (0..nodes_len)
+        .into_par_iter()
+        .map(|i| {
+            let mut distances = vec![f32::INFINITY; nodes_len];
+            let mut heap = BinaryHeap::new();
+            distances[i] = 0.0;
+            heap.push(Visit { distance: 0.0, node: i as u32 });
+
+            while let Some(Visit { distance, node }) = heap.pop() {
+                if distance > distances[node as usize] {
+                    continue;
+                }
+                for &(w, length) in &adj[node as usize] {
+                    let next = distance + length;
+                    if next < distances[w as usize] {
+                        distances[w as usize] = next;
+                        heap.push(Visit { distance: next, node: w });
+                    }
+                }
+            }
+
+            let mut sum_distances = 0.0f32;
+            let mut reachable = 0;
+            for (j, &d) in distances.iter().enumerate() {
+                if j != i && d.is_finite() {
+                    sum_distances += d;
+                    reachable += 1;
+                }
+            }
+
+            if sum_distances > 0.0 {
+                (reachable as f32) / sum_distances
+            } else {
+                0.0
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_closeness() {
+        // 0 -- 1 -- 2 with strong edge 1 - 2
+        let unweighted = vec![
+            WeightedEdge { from: 0, to: 1, weight: 1.0 },
+            WeightedEdge { from: 1, to: 2, weight: 1.0 },
+        ];
+        let closeness = compute_closeness_centrality(3, &unweighted);
+        assert_eq!(closeness[0], closeness[2]);
+        assert_eq!(1.0, closeness[1]);
+
+        let weighted = vec![
+            WeightedEdge { from: 0, to: 1, weight: 1.0 },
+            WeightedEdge { from: 1, to: 2, weight: 4.0 },
+        ];
+        let closeness = compute_closeness_centrality(3, &weighted);
+        // distances from 2: 0.25 and 1.25
+        assert!((closeness[2] - 2.0 / 1.5).abs() < 1e-6);
+        assert!(closeness[2] > closeness[0]);
+    }
+}
@@ -0,0 +1,249 @@
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::NodeData,
+    support::SortedVec,
+    ui::graph_view::{NeighborPos, update_layout_edges},
+};
+
+// Avoid exponential run time for dense data, the search stops after this number of cycles
+pub const MAX_CYCLES: usize = 1000;
+
+/// Outgoing references of all nodes, only for the given predicate if set
+pub fn successors(node_data: &NodeData, predicate: Option<IriIndex>, hidden_predicates: &SortedVec) -> Vec<Vec<IriIndex>> {
+    node_data
+        .iter()
+        .map(|(_, node)| {
+            let mut targets: Vec<IriIndex> = node
+                .references
+                .iter()
+                .filter(|(reference_predicate, _)| match predicate {
+                    Some(predicate) => *reference_predicate == predicate,
+                    None => !hidden_predicates.contains(*reference_predicate),
+                })
+                .map(|(_, target)| *target)
+                .collect();
+            targets.sort_unstable();
+            targets.dedup();
+            targets
+        })
+        .collect()
+}
+
+/// Tarjan algorithm (iterative), returns the component id for each node
+pub fn strongly_connected_components(successors: &[Vec<IriIndex>]) -> Vec<u32> {
+    const UNVISITED: u32 = u32::MAX;
+    let len = successors.len();
+    let mut index: Vec<u32> = vec![UNVISITED; len];
+    let mut low_link: Vec<u32> = vec![0; len];
+    let mut on_stack: Vec<bool> = vec![false; len];
+    let mut component: Vec<u32> = vec![UNVISITED; len];
+    let mut stack: Vec<usize> = Vec::new();
+    let mut next_index: u32 = 0;
+    let mut next_component: u32 = 0;
+    for root in 0..len {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        // (node, position of the next successor to visit)
+        let mut call_stack: Vec<(usize, usize)> = vec![(root, 0)];
+        while let Some((node, successor_pos)) = call_stack.pop() {
+            if successor_pos == 0 {
+                index[node] = next_index;
+                low_link[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            } else {
+                let child = successors[node][successor_pos - 1] as usize;
+                low_link[node] = low_link[node].min(low_link[child]);
+            }
+            let mut descended = false;
+            for (pos, target) in successors[node].iter().enumerate().skip(successor_pos) {
+                let target = *target as usize;
+                if target >= len {
+                    continue;
+                }
+                if index[target] == UNVISITED {
+                    call_stack.push((node, pos + 1));
+                    call_stack.push((target, 0));
+                    descended = true;
+                    break;
+                } else if on_stack[target] {
+                    low_link[node] = low_link[node].min(index[target]);
+                }
+            }
+            if !descended && low_link[node] == index[node] {
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component[member] = next_component;
+                    if member == node {
+                        break;
+                    }
+                }
+                next_component += 1;
+            }
+        }
+    }
+    component
+}
+
+/**
+ * Elementary cycles up to max_length nodes. Each cycle starts with its smallest node index,
+ * so every cycle is found only once. The search is restricted to strongly connected components.
+ * Returns also true if the search was stopped because of the MAX_CYCLES limit.
+ */
+pub fn find_cycles(successors: &[Vec<IriIndex>], max_length: usize) -> (Vec<Vec<IriIndex>>, bool) {
+    let component = strongly_connected_components(successors);
+    let mut component_size: Vec<u32> = vec![0; successors.len()];
+    for component_id in component.iter() {
+        component_size[*component_id as usize] += 1;
+    }
+    let mut cycles: Vec<Vec<IriIndex>> = Vec::new();
+    let mut path: Vec<IriIndex> = Vec::with_capacity(max_length);
+    let mut on_path: Vec<bool> = vec![false; successors.len()];
+    for start in 0..successors.len() {
+        let has_self_loop = successors[start].contains(&(start as IriIndex));
+        if component_size[component[start] as usize] < 2 && !has_self_loop {
+            continue;
+        }
+        path.push(start as IriIndex);
+        on_path[start] = true;
+        let truncated = search_cycles(
+            successors,
+            &component,
+            max_length,
+            &mut path,
+            &mut on_path,
+            &mut cycles,
+        );
+        on_path[start] = false;
+        path.clear();
+        if truncated {
+            return (cycles, true);
+        }
+    }
+    (cycles, false)
+}
+
+fn search_cycles(
+    successors: &[Vec<IriIndex>],
+    component: &[u32],
+    max_length: usize,
+    path: &mut Vec<IriIndex>,
+    on_path: &mut [bool],
+    cycles: &mut Vec<Vec<IriIndex>>,
+) -> bool {
+    let start = path[0] as usize;
+    let node = *path.last().unwrap() as usize;
+    for target in successors[node].iter() {
+        let target_pos = *target as usize;
+        if target_pos == start {
+            cycles.push(path.clone());
+            if cycles.len() >= MAX_CYCLES {
+                return true;
+            }
+        } else if target_pos > start
+            && target_pos < successors.len()
+            && !on_path[target_pos]
+            && component[target_pos] == component[start]
+            && path.len() < max_length
+        {
+            path.push(*target);
+            on_path[target_pos] = true;
+            let truncated = search_cycles(successors, component, max_length, path, on_path, cycles);
+            on_path[target_pos] = false;
+            path.pop();
+            if truncated {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+pub struct CycleSearch {
+    // None means all references (without hidden predicates)
+    pub predicate: Option<IriIndex>,
+    pub max_length: usize,
+    pub cycles: Vec<Vec<IriIndex>>,
+    pub truncated: bool,
+    pub searched: bool,
+    pub selected: Option<usize>,
+}
+
+impl Default for CycleSearch {
+    fn default() -> Self {
+        Self {
+            predicate: None,
+            max_length: 6,
+            cycles: Vec::new(),
+            truncated: false,
+            searched: false,
+            selected: None,
+        }
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn search_cycles(&mut self) {
+        let Some(cycle_search) = &mut self.cycle_search else {
+            return;
+        };
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let successors = successors(&rdf_data.node_data, cycle_search.predicate, &self.ui_state.hidden_predicates);
+            let (cycles, truncated) = find_cycles(&successors, cycle_search.max_length);
+            cycle_search.cycles = cycles;
+            cycle_search.truncated = truncated;
+            cycle_search.searched = true;
+            cycle_search.selected = None;
+        }
+    }
+
+    /// Adds the nodes to the visual graph (placed around the first one) and selects them
+    pub fn show_and_select_nodes(&mut self, nodes: &[IriIndex]) {
+        let Some(first) = nodes.first() else {
+            return;
+        };
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let mut npos = NeighborPos::new();
+            self.visible_nodes.add_by_index(*first);
+            let nodes_to_add: Vec<(IriIndex, IriIndex)> = nodes.iter().map(|node_index| (*first, *node_index)).collect();
+            npos.add_many(&mut self.visible_nodes, &nodes_to_add, &self.config);
+            if !npos.is_empty() {
+                update_layout_edges(
+                    &npos,
+                    &mut self.visible_nodes,
+                    &rdf_data.node_data,
+                    &self.ui_state.hidden_predicates,
+                );
+                npos.position(&mut self.visible_nodes);
+                self.visible_nodes
+                    .start_layout(&self.config, &self.ui_state.hidden_predicates);
+            }
+            self.visible_nodes.update_node_shapes = true;
+        }
+        self.ui_state.selected_nodes.clear();
+        self.ui_state.selected_nodes.extend(nodes.iter().cloned());
+        self.ui_state.selected_node = Some(*first);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_cycles() {
+        // 0 -> 1 -> 2 -> 0, 1 -> 0, 3 -> 3, 3 -> 4
+        let successors: Vec<Vec<IriIndex>> = vec![vec![1], vec![0, 2], vec![0], vec![3, 4], vec![]];
+        let component = strongly_connected_components(&successors);
+        assert_eq!(component[0], component[1]);
+        assert_eq!(component[0], component[2]);
+        assert_ne!(component[3], component[4]);
+        let (cycles, truncated) = find_cycles(&successors, 10);
+        assert!(!truncated);
+        assert_eq!(vec![vec![0, 1], vec![0, 1, 2], vec![3]], cycles);
+        let (cycles, _) = find_cycles(&successors, 2);
+        assert_eq!(vec![vec![0, 1], vec![3]], cycles);
+    }
+}
@@ -0,0 +1,201 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use egui::Pos2;
+
+use crate::{IriIndex, RdfGlanceApp, domain::NodeData, support::SortedVec, uistate::layout::LayoutData};
+
+/**
+ * Semantic fisheye of the visual graph: only the nodes within some hops of the focus (selected) node
+ * and the landmark nodes with the highest degree stay visible.
+ * All nodes of the visual graph (base) are remembered with their positions, so the hidden nodes return
+ * to their place if they get into the focus again or the filter is switched off.
+ */
+pub struct DegreeOfInterest {
+    pub hops: usize,
+    pub landmarks: usize,
+    base: BTreeMap<IriIndex, Pos2>,
+    // focus of the current filter, None forces new filtering
+    pub focus: Option<IriIndex>,
+}
+
+impl DegreeOfInterest {
+    pub fn new(base: impl IntoIterator<Item = (IriIndex, Pos2)>) -> Self {
+        Self {
+            hops: 2,
+            landmarks: 5,
+            base: base.into_iter().collect(),
+            focus: None,
+        }
+    }
+
+    /// Nodes of the base within the hops from the focus (edges in both directions) and the landmarks
+    pub fn interesting_nodes(
+        &self,
+        node_data: &NodeData,
+        hidden_predicates: &SortedVec,
+        focus: IriIndex,
+    ) -> BTreeSet<IriIndex> {
+        let neighbors = |node_index: IriIndex| -> Vec<IriIndex> {
+            node_data
+                .get_node_by_index(node_index)
+                .map(|(_, node)| {
+                    node.references
+                        .iter()
+                        .chain(node.reverse_references.iter())
+                        .filter(|(predicate, reference)| {
+                            !hidden_predicates.contains(*predicate) && self.base.contains_key(reference)
+                        })
+                        .map(|(_, reference)| *reference)
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let mut result = BTreeSet::new();
+        result.insert(focus);
+        let mut queue = VecDeque::from([(focus, 0)]);
+        while let Some((node_index, distance)) = queue.pop_front() {
+            if distance >= self.hops {
+                continue;
+            }
+            for neighbor in neighbors(node_index) {
+                if result.insert(neighbor) {
+                    queue.push_back((neighbor, distance + 1));
+                }
+            }
+        }
+        if self.landmarks > 0 {
+            let mut degrees: Vec<(usize, IriIndex)> = self
+                .base
+                .keys()
+                .map(|node_index| (neighbors(*node_index).len(), *node_index))
+                .filter(|(degree, _)| *degree > 0)
+                .collect();
+            degrees.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+            result.extend(degrees.iter().take(self.landmarks).map(|(_, node_index)| *node_index));
+        }
+        result
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn set_degree_of_interest(&mut self, enabled: bool) {
+        if enabled {
+            let base: Vec<(IriIndex, Pos2)> = match self.visible_nodes.layout_data.read() {
+                Ok(layout) => layout
+                    .nodes
+                    .iter()
+                    .zip(layout.positions.iter())
+                    .map(|(node_layout, position)| (node_layout.node_index, position.pos))
+                    .collect(),
+                Err(_) => Vec::new(),
+            };
+            self.degree_of_interest = Some(DegreeOfInterest::new(base));
+            self.update_degree_of_interest();
+        } else if let Some(degree_of_interest) = self.degree_of_interest.take() {
+            let base: BTreeSet<IriIndex> = degree_of_interest.base.keys().copied().collect();
+            self.show_interesting_nodes(&base, &degree_of_interest.base);
+        }
+    }
+
+    /// Filters the visual graph again if the selected node has changed
+    pub fn update_degree_of_interest(&mut self) {
+        let Some(focus) = self.ui_state.selected_node else {
+            return;
+        };
+        let Some(degree_of_interest) = &mut self.degree_of_interest else {
+            return;
+        };
+        if degree_of_interest.focus == Some(focus) {
+            return;
+        }
+        // nodes added to the graph (e.g. by expanding) become part of the base, the moved nodes keep the new position
+        if let Ok(layout) = self.visible_nodes.layout_data.read() {
+            for (node_layout, position) in layout.nodes.iter().zip(layout.positions.iter()) {
+                degree_of_interest.base.insert(node_layout.node_index, position.pos);
+            }
+        }
+        degree_of_interest.focus = Some(focus);
+        let interesting_nodes = match self.rdf_data.read() {
+            Ok(rdf_data) => {
+                degree_of_interest.interesting_nodes(&rdf_data.node_data, &self.ui_state.hidden_predicates, focus)
+            }
+            Err(_) => return,
+        };
+        let base = degree_of_interest.base.clone();
+        self.show_interesting_nodes(&interesting_nodes, &base);
+    }
+
+    fn show_interesting_nodes(&mut self, nodes: &BTreeSet<IriIndex>, base: &BTreeMap<IriIndex, Pos2>) {
+        self.visible_nodes
+            .retain(&self.ui_state.hidden_predicates, false, |node_layout| nodes.contains(&node_layout.node_index));
+        let nodes_to_add: Vec<(IriIndex, IriIndex)> = nodes
+            .iter()
+            .filter(|node_index| !self.visible_nodes.contains(**node_index))
+            .map(|node_index| (*node_index, *node_index))
+            .collect();
+        if self.visible_nodes.add_many(&nodes_to_add, &self.config, |_| {}) {
+            if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                let LayoutData { nodes, positions, .. } = &mut *layout;
+                for (node_layout, position) in nodes.iter().zip(positions.iter_mut()) {
+                    if let Some(pos) = base.get(&node_layout.node_index) {
+                        position.pos = *pos;
+                    }
+                }
+            }
+            if let Ok(rdf_data) = self.rdf_data.read() {
+                self.visible_nodes
+                    .refresh_edges(&rdf_data.node_data, &self.ui_state.hidden_predicates);
+            }
+        }
+        self.ui_state
+            .selected_nodes
+            .retain(|node_index| nodes.contains(node_index));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RdfData;
+
+    #[test]
+    fn test_interesting_nodes() {
+        // chain a - b - c - d and hub h referenced by e, f, g and d
+        let rdf_data = RdfData::from_patch(
+            "A <http://example.org/a> <http://example.org/p> <http://example.org/b> .\n\
+             A <http://example.org/b> <http://example.org/p> <http://example.org/c> .\n\
+             A <http://example.org/c> <http://example.org/p> <http://example.org/d> .\n\
+             A <http://example.org/d> <http://example.org/p> <http://example.org/h> .\n\
+             A <http://example.org/e> <http://example.org/p> <http://example.org/h> .\n\
+             A <http://example.org/f> <http://example.org/p> <http://example.org/h> .\n\
+             A <http://example.org/g> <http://example.org/p> <http://example.org/h> .\n",
+        );
+        let node_data = &rdf_data.node_data;
+        let index = |name: &str| node_data.get_node_index(&format!("http://example.org/{}", name)).unwrap();
+        let all: Vec<(IriIndex, Pos2)> = ["a", "b", "c", "d", "e", "f", "g", "h"]
+            .iter()
+            .map(|name| (index(name), Pos2::ZERO))
+            .collect();
+        let mut degree_of_interest = DegreeOfInterest::new(all.clone());
+        degree_of_interest.hops = 2;
+        degree_of_interest.landmarks = 0;
+        let hidden_predicates = SortedVec::new();
+        let expected: BTreeSet<IriIndex> = ["a", "b", "c"].iter().map(|name| index(name)).collect();
+        assert_eq!(expected, degree_of_interest.interesting_nodes(node_data, &hidden_predicates, index("a")));
+
+        // the hub is the landmark
+        degree_of_interest.landmarks = 1;
+        let interesting_nodes = degree_of_interest.interesting_nodes(node_data, &hidden_predicates, index("a"));
+        assert_eq!(4, interesting_nodes.len());
+        assert!(interesting_nodes.contains(&index("h")));
+
+        // only the nodes of the base are used
+        let without_c: Vec<(IriIndex, Pos2)> =
+            all.iter().filter(|(node_index, _)| *node_index != index("c")).copied().collect();
+        let mut degree_of_interest = DegreeOfInterest::new(without_c);
+        degree_of_interest.landmarks = 0;
+        degree_of_interest.hops = 5;
+        let expected: BTreeSet<IriIndex> = ["a", "b"].iter().map(|name| index(name)).collect();
+        assert_eq!(expected, degree_of_interest.interesting_nodes(node_data, &hidden_predicates, index("a")));
+    }
+}
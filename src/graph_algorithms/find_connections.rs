@@ -59,7 +59,7 @@ impl RdfGlanceApp {
                 npos.add_many(
                     &mut self.visible_nodes,
                     &nodes_to_add,
-                    &self.persistent_data.config_data,
+                    &self.config,
                 );
                 if !npos.is_empty() {
                     update_layout_edges(
@@ -70,7 +70,7 @@ impl RdfGlanceApp {
                     );
                     npos.position(&mut self.visible_nodes);
                     self.visible_nodes
-                        .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                        .start_layout(&self.config, &self.ui_state.hidden_predicates);
                 }
             }
         }
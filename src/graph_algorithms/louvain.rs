@@ -1,6 +1,6 @@
 use crate::{
-    domain::config::Config, graph_algorithms::ClusterResult, 
-    uistate::layout::Edge, support::SortedVec
+    domain::config::Config, graph_algorithms::{ClusterResult, weights::WeightedEdge},
+    uistate::layout::Edge
 };
 
 use rand::RngExt;
@@ -30,8 +30,8 @@ pub struct Modularity {
 }
 
 impl Modularity {
-    pub fn louvain(nodes_len: u32, edges: &[Edge], config: &Config, hidden_predicates: &SortedVec) -> ClusterResult {
-        let mut modularity = Self::construct(nodes_len, edges, hidden_predicates);
+    pub fn louvain(nodes_len: u32, edges: &[WeightedEdge], config: &Config) -> ClusterResult {
+        let mut modularity = Self::construct(nodes_len, edges);
         modularity.resolution = config.community_resolution;
         modularity.randomize = config.community_randomize;
         modularity.init_caches();
@@ -73,7 +73,7 @@ impl Modularity {
         }
     }
 
-    fn construct(node_len: u32, edges: &[Edge], hidden_predicates: &SortedVec) -> Self {
+    fn construct(node_len: u32, edges: &[WeightedEdge]) -> Self {
         let resolution = 1.0;
         let origin_nodes_community = (0..node_len).collect();
         let nodes = (0..node_len).map(CNode::init).collect();
@@ -81,14 +81,12 @@ impl Modularity {
         let mut wedges: Vec<Vec<WEdge>> = vec![Vec::new(); node_len as usize];
         let mut m = 0.0;
         for edge in edges {
-            if !hidden_predicates.contains(edge.predicate) {
-                let from = edge.from as u32;
-                let to = edge.to as u32;
-                let weight = 1.0;
-                wedges[from as usize].push(WEdge { from, to, weight });
-                wedges[to as usize].push(WEdge { from: to, to: from, weight });
-                m += 2.0;
-            }
+            let from = edge.from as u32;
+            let to = edge.to as u32;
+            let weight = edge.weight;
+            wedges[from as usize].push(WEdge { from, to, weight });
+            wedges[to as usize].push(WEdge { from: to, to: from, weight });
+            m += 2.0 * weight;
         }
         Self {
             m,
@@ -365,7 +363,7 @@ mod tests {
 
     use crate::{
         domain::config::Config, 
-        graph_algorithms::louvain::{compute_modularity, Modularity}, 
+        graph_algorithms::{louvain::{compute_modularity, Modularity}, weights::{EdgeWeight, weighted_edges}}, 
         uistate::layout::Edge, support::SortedVec
     };
 
@@ -402,7 +400,8 @@ mod tests {
         ];
         let (nodes_len, edges) = convert_edges(&edges);
         let hidden_predicates = SortedVec::new();
-        let mut modularity = Modularity::construct(nodes_len, &edges, &hidden_predicates);
+        let weighted = weighted_edges(&edges, &hidden_predicates, EdgeWeight::Unweighted, |_| None);
+        let mut modularity = Modularity::construct(nodes_len, &weighted);
         modularity.init_caches();
         assert_eq!(modularity.nodes.len(), nodes_len as usize);
         assert_eq!(modularity.communities.len(), nodes_len as usize);
@@ -548,7 +547,8 @@ mod tests {
         let mut config = Config::default();
         config.community_randomize = false;
         let hidden_predicates = SortedVec::new();
-        let result = Modularity::louvain(nodes_len, &edges, &config, &hidden_predicates);
+        let edges = weighted_edges(&edges, &hidden_predicates, EdgeWeight::Unweighted, |_| None);
+        let result = Modularity::louvain(nodes_len, &edges, &config);
         println!("Communities: {:?}", result.cluster_size);
         assert_eq!(3, result.cluster_size);
     }
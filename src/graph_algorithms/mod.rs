@@ -8,10 +8,14 @@ pub mod page_rank;
 pub mod louvain;
 pub mod spectral_clustering;
 pub mod find_connections;
+pub mod cycles;
+pub mod degree_of_interest;
+pub mod topological_sort;
+pub mod weights;
 
 use crate::{
     domain::config::Config, 
-    graph_algorithms::utils::normalize, 
+    graph_algorithms::{utils::normalize, weights::WeightedEdge}, 
     uistate::layout::Edge, support::SortedVec
 };
 use strum_macros::{EnumIter, Display};
@@ -58,7 +62,21 @@ pub enum StatisticValue {
     FiedlerVector,
 }
 
+impl StatisticValue {
+    /// The values are cluster numbers, not centrality
+    pub fn is_clustering(&self) -> bool {
+        matches!(self, StatisticValue::ClusteringLouvain | StatisticValue::ClusteringSpectral)
+    }
+}
+
 impl GraphAlgorithm {
+    /// PageRank, closeness and Louvain use the edge weights chosen in statistics panel
+    pub fn is_weighted(&self) -> bool {
+        matches!(
+            self,
+            GraphAlgorithm::PageRank | GraphAlgorithm::ClosenessCentrality | GraphAlgorithm::ClusteringLouvain
+        )
+    }
     pub fn is_clustering(&self) -> bool {
         matches!(self,GraphAlgorithm::ClusteringLouvain) || matches!(self,GraphAlgorithm::ClusteringSpectral)
     }
@@ -82,7 +100,13 @@ pub struct ClusterResult {
     pub parameters: Option<Vec<f32>>,
 }
 
-pub fn run_algorithm(algorithm: GraphAlgorithm, nodes_len: usize, edges: &[Edge], hidden_predicates: &SortedVec) -> Vec<f32> {
+pub fn run_algorithm(
+    algorithm: GraphAlgorithm,
+    nodes_len: usize,
+    edges: &[Edge],
+    hidden_predicates: &SortedVec,
+    weighted_edges: &[WeightedEdge],
+) -> Vec<f32> {
     match algorithm {
         GraphAlgorithm::BetweennessCentrality => {
             let values = betweenness_centrality::compute_betweenness_centrality(nodes_len, edges, hidden_predicates).into_iter().map(|result| result.node_betweenness).collect::<Vec<f32>>();
@@ -93,7 +117,7 @@ pub fn run_algorithm(algorithm: GraphAlgorithm, nodes_len: usize, edges: &[Edge]
             normalize(values)
         }
         GraphAlgorithm::ClosenessCentrality => {
-            let values = closeness_centrality::compute_closeness_centrality(nodes_len, edges, hidden_predicates);
+            let values = closeness_centrality::compute_closeness_centrality(nodes_len, weighted_edges);
             normalize(values)
         }
         GraphAlgorithm::KCoreCentrality => {
@@ -105,7 +129,7 @@ pub fn run_algorithm(algorithm: GraphAlgorithm, nodes_len: usize, edges: &[Edge]
             normalize(values)
         },
         GraphAlgorithm::PageRank => {
-            let values = page_rank::compute_page_rank(nodes_len, edges, hidden_predicates);
+            let values = page_rank::compute_page_rank(nodes_len, weighted_edges);
             normalize(values)
         },
         GraphAlgorithm::ClusteringLouvain => {
@@ -117,10 +141,17 @@ pub fn run_algorithm(algorithm: GraphAlgorithm, nodes_len: usize, edges: &[Edge]
     }
 }
 
-pub fn run_clustering_algorithm(algorithm: GraphAlgorithm, nodes_len: usize, edges: &[Edge], config: &Config, hidden_predicates: &SortedVec) -> ClusterResult {
+pub fn run_clustering_algorithm(
+    algorithm: GraphAlgorithm,
+    nodes_len: usize,
+    edges: &[Edge],
+    config: &Config,
+    hidden_predicates: &SortedVec,
+    weighted_edges: &[WeightedEdge],
+) -> ClusterResult {
     match algorithm {
         GraphAlgorithm::ClusteringLouvain => {
-            louvain::Modularity::louvain(nodes_len as u32, edges, config, hidden_predicates)
+            louvain::Modularity::louvain(nodes_len as u32, weighted_edges, config)
         },
         GraphAlgorithm::ClusteringSpectral => {
             spectral_clustering::cluster_spectral(nodes_len as u32, edges, config, hidden_predicates)
@@ -1,14 +1,13 @@
-use crate::{
-    uistate::layout::Edge, support::SortedVec
-};
+use crate::graph_algorithms::weights::WeightedEdge;
 
-pub fn compute_page_rank(nodes_len: usize, edges: &[Edge], hidden_predicates: &SortedVec) -> Vec<f32> {
+/// The rank is distributed over outgoing edges in proportion to their weight
+pub fn compute_page_rank(nodes_len: usize, edges: &[WeightedEdge]) -> Vec<f32> {
     // Build adjacency list
-    let mut adj: Vec<Vec<usize>> = vec![Vec::new(); nodes_len];
+    let mut adj: Vec<Vec<(usize, f32)>> = vec![Vec::new(); nodes_len];
+    let mut out_weight = vec![0.0f32; nodes_len];
     for e in edges {
-        if !hidden_predicates.contains(e.predicate) {
-            adj[e.from].push(e.to); // directed: from → to
-        }
+        adj[e.from].push((e.to, e.weight)); // directed: from → to
+        out_weight[e.from] += e.weight;
     }
 
     // Parameters
@@ -35,9 +34,9 @@ pub fn compute_page_rank(nodes_len: usize, edges: &[Edge], hidden_predicates: &S
                     *r += share;
                 }
             } else {
-                let share = damping * rank[i] / adj[i].len() as f32;
-                for &nbr in &adj[i] {
-                    new_rank[nbr] += share;
+                let share = damping * rank[i] / out_weight[i];
+                for &(nbr, weight) in &adj[i] {
+                    new_rank[nbr] += share * weight;
                 }
             }
         }
@@ -0,0 +1,154 @@
+use std::{collections::VecDeque, fmt::Write};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    graph_algorithms::cycles::{strongly_connected_components, successors},
+};
+
+/**
+ * Ordering of the nodes connected by one predicate. Nodes that are part of a cycle
+ * or depend on a cycle can not be ordered and are listed separately.
+ */
+#[derive(Default)]
+pub struct TopologicalOrder {
+    pub order: Vec<IriIndex>,
+    pub cyclic: Vec<IriIndex>,
+    // not in a cycle but reachable only over a cycle
+    pub blocked: Vec<IriIndex>,
+}
+
+impl TopologicalOrder {
+    pub fn is_complete(&self) -> bool {
+        self.cyclic.is_empty() && self.blocked.is_empty()
+    }
+
+    pub fn to_text(&self, iri: impl Fn(IriIndex) -> String) -> String {
+        let mut out = String::new();
+        for (position, node_index) in self.order.iter().enumerate() {
+            let _ = writeln!(out, "{}\t{}", position + 1, iri(*node_index));
+        }
+        if !self.cyclic.is_empty() {
+            let _ = writeln!(out, "# in cycle");
+            for node_index in self.cyclic.iter() {
+                let _ = writeln!(out, "-\t{}", iri(*node_index));
+            }
+        }
+        if !self.blocked.is_empty() {
+            let _ = writeln!(out, "# depends on cycle");
+            for node_index in self.blocked.iter() {
+                let _ = writeln!(out, "-\t{}", iri(*node_index));
+            }
+        }
+        out
+    }
+}
+
+/// Kahn algorithm. With dependencies_first the target of "a requires b" is ordered before the subject.
+pub fn topological_order(successors: &[Vec<IriIndex>], dependencies_first: bool) -> TopologicalOrder {
+    let len = successors.len();
+    let mut edges: Vec<Vec<IriIndex>> = vec![Vec::new(); len];
+    let mut connected: Vec<bool> = vec![false; len];
+    for (node, targets) in successors.iter().enumerate() {
+        for target in targets.iter().filter(|target| (**target as usize) < len) {
+            connected[node] = true;
+            connected[*target as usize] = true;
+            if dependencies_first {
+                edges[*target as usize].push(node as IriIndex);
+            } else {
+                edges[node].push(*target);
+            }
+        }
+    }
+    let mut in_degree: Vec<u32> = vec![0; len];
+    for targets in edges.iter() {
+        for target in targets.iter() {
+            in_degree[*target as usize] += 1;
+        }
+    }
+    let mut queue: VecDeque<IriIndex> = (0..len)
+        .filter(|node| connected[*node] && in_degree[*node] == 0)
+        .map(|node| node as IriIndex)
+        .collect();
+    let mut result = TopologicalOrder::default();
+    while let Some(node) = queue.pop_front() {
+        result.order.push(node);
+        for target in edges[node as usize].iter() {
+            in_degree[*target as usize] -= 1;
+            if in_degree[*target as usize] == 0 {
+                queue.push_back(*target);
+            }
+        }
+    }
+    if result.order.len() < connected.iter().filter(|connected| **connected).count() {
+        let component = strongly_connected_components(successors);
+        let mut component_size: Vec<u32> = vec![0; len];
+        for component_id in component.iter() {
+            component_size[*component_id as usize] += 1;
+        }
+        for node in 0..len {
+            if !connected[node] || in_degree[node] == 0 {
+                continue;
+            }
+            let in_cycle =
+                component_size[component[node] as usize] > 1 || successors[node].contains(&(node as IriIndex));
+            if in_cycle {
+                result.cyclic.push(node as IriIndex);
+            } else {
+                result.blocked.push(node as IriIndex);
+            }
+        }
+    }
+    result
+}
+
+pub struct DependencyOrder {
+    pub predicate: Option<IriIndex>,
+    pub dependencies_first: bool,
+    pub result: Option<TopologicalOrder>,
+}
+
+impl Default for DependencyOrder {
+    fn default() -> Self {
+        Self {
+            predicate: None,
+            dependencies_first: true,
+            result: None,
+        }
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn compute_dependency_order(&mut self) {
+        let Some(dependency_order) = &mut self.dependency_order else {
+            return;
+        };
+        let Some(predicate) = dependency_order.predicate else {
+            return;
+        };
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let successors = successors(&rdf_data.node_data, Some(predicate), &self.ui_state.hidden_predicates);
+            dependency_order.result = Some(topological_order(&successors, dependency_order.dependencies_first));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topological_order() {
+        // 0 requires 1, 1 requires 2, 3 requires 4, 4 requires 3, 5 requires 3
+        let successors: Vec<Vec<IriIndex>> = vec![vec![1], vec![2], vec![], vec![4], vec![3], vec![3], vec![]];
+        let order = topological_order(&successors, true);
+        assert_eq!(vec![2, 1, 0], order.order);
+        assert_eq!(vec![3, 4], order.cyclic);
+        assert_eq!(vec![5], order.blocked);
+        assert!(!order.is_complete());
+        let order = topological_order(&successors[0..3], false);
+        assert_eq!(vec![0, 1, 2], order.order);
+        assert!(order.is_complete());
+        let text = order.to_text(|node_index| format!("n{}", node_index));
+        assert_eq!("1\tn0\n2\tn1\n3\tn2\n", text);
+    }
+}
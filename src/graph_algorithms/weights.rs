@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use crate::{IriIndex, support::SortedVec, uistate::layout::Edge};
+
+/// Source of the edge weights for the weighted algorithms (PageRank, closeness centrality, Louvain)
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum EdgeWeight {
+    #[default]
+    Unweighted,
+    // parallel edges between two nodes are merged into one edge with their count as weight
+    Multiplicity,
+    // numeric value of the data property of the edge target node, 1.0 if the node has no value
+    DataProperty(IriIndex),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct WeightedEdge {
+    pub from: usize,
+    pub to: usize,
+    pub weight: f32,
+}
+
+/**
+ * Builds the weighted edges of not hidden predicates.
+ * node_value returns the property value for a node position (only used for DataProperty).
+ * Edges with not positive weight are skipped.
+ */
+pub fn weighted_edges<F>(
+    edges: &[Edge],
+    hidden_predicates: &SortedVec,
+    edge_weight: EdgeWeight,
+    node_value: F,
+) -> Vec<WeightedEdge>
+where
+    F: Fn(usize) -> Option<f32>,
+{
+    let visible_edges = edges.iter().filter(|edge| !hidden_predicates.contains(edge.predicate));
+    match edge_weight {
+        EdgeWeight::Unweighted => visible_edges
+            .map(|edge| WeightedEdge {
+                from: edge.from,
+                to: edge.to,
+                weight: 1.0,
+            })
+            .collect(),
+        EdgeWeight::Multiplicity => {
+            let mut counts: BTreeMap<(usize, usize), f32> = BTreeMap::new();
+            for edge in visible_edges {
+                *counts.entry((edge.from, edge.to)).or_insert(0.0) += 1.0;
+            }
+            counts
+                .into_iter()
+                .map(|((from, to), weight)| WeightedEdge { from, to, weight })
+                .collect()
+        }
+        EdgeWeight::DataProperty(_) => visible_edges
+            .map(|edge| WeightedEdge {
+                from: edge.from,
+                to: edge.to,
+                weight: node_value(edge.to).unwrap_or(1.0),
+            })
+            .filter(|edge| edge.weight > 0.0 && edge.weight.is_finite())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edge(from: usize, to: usize, predicate: IriIndex) -> Edge {
+        Edge {
+            from,
+            to,
+            predicate,
+            bezier_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_weighted_edges() {
+        let edges = vec![edge(0, 1, 0), edge(0, 1, 1), edge(1, 2, 0), edge(2, 0, 2)];
+        let mut hidden_predicates = SortedVec::new();
+        hidden_predicates.add(2);
+
+        let unweighted = weighted_edges(&edges, &hidden_predicates, EdgeWeight::Unweighted, |_| None);
+        assert_eq!(3, unweighted.len());
+        assert!(unweighted.iter().all(|edge| edge.weight == 1.0));
+
+        let multiplicity = weighted_edges(&edges, &hidden_predicates, EdgeWeight::Multiplicity, |_| None);
+        assert_eq!(
+            vec![
+                WeightedEdge { from: 0, to: 1, weight: 2.0 },
+                WeightedEdge { from: 1, to: 2, weight: 1.0 }
+            ],
+            multiplicity
+        );
+
+        // node 1 has value 5, node 2 negative value, so the edge is skipped
+        let values = [None, Some(5.0), Some(-1.0)];
+        let property = weighted_edges(&edges, &hidden_predicates, EdgeWeight::DataProperty(7), |pos| values[pos]);
+        assert_eq!(2, property.len());
+        assert!(property.iter().all(|edge| edge.to == 1 && edge.weight == 5.0));
+    }
+}
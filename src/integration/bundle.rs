@@ -0,0 +1,133 @@
+use anyhow::Result;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::Compression;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use std::io::{Read, Write};
+
+use crate::integration::persistency::{read_len_string, write_len_string};
+
+// it is just ascii "rdfb"
+const BUNDLE_MAGIC_NUMBER: u32 = 0x62666472;
+const BUNDLE_FORMAT_VERSION: u16 = 0;
+
+pub const PROJECT_ENTRY: &str = "project.rdfglance";
+pub const NOTES_ENTRY: &str = "notes.md";
+pub const QUERY_ENTRY: &str = "query.sparql";
+pub const SOURCES_PREFIX: &str = "sources/";
+
+pub struct BundleEntry {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+/**
+ * Analysis bundle: one archive file with the project (data, styles, layout and settings),
+ * the notes, the SPARQL query and optionally the source data files.
+ * Each entry is stored as name and zlib compressed content.
+ */
+#[derive(Default)]
+pub struct AnalysisBundle {
+    pub entries: Vec<BundleEntry>,
+}
+
+impl AnalysisBundle {
+    pub fn add(&mut self, name: &str, data: Vec<u8>) {
+        self.entries.retain(|entry| entry.name != name);
+        self.entries.push(BundleEntry {
+            name: name.to_string(),
+            data,
+        });
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map(|entry| entry.data.as_slice())
+    }
+
+    pub fn get_str(&self, name: &str) -> Option<&str> {
+        self.get(name).and_then(|data| std::str::from_utf8(data).ok())
+    }
+
+    /// Source data files as (file name, content)
+    pub fn sources(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.entries.iter().filter_map(|entry| {
+            entry
+                .name
+                .strip_prefix(SOURCES_PREFIX)
+                .map(|file_name| (file_name, entry.data.as_slice()))
+        })
+    }
+
+    pub fn write<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_u32::<LittleEndian>(BUNDLE_MAGIC_NUMBER)?;
+        writer.write_u16::<LittleEndian>(BUNDLE_FORMAT_VERSION)?;
+        writer.write_u32::<LittleEndian>(self.entries.len() as u32)?;
+        for entry in self.entries.iter() {
+            write_len_string(&entry.name, writer)?;
+            let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(&entry.data)?;
+            let compressed = encoder.finish()?;
+            writer.write_u64::<LittleEndian>(compressed.len() as u64)?;
+            writer.write_all(&compressed)?;
+        }
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self> {
+        let magic_number = reader.read_u32::<LittleEndian>()?;
+        if magic_number != BUNDLE_MAGIC_NUMBER {
+            return Err(anyhow::anyhow!("This seems not to be RDF Glance analysis bundle. Wrong magic number"));
+        }
+        let version = reader.read_u16::<LittleEndian>()?;
+        if version > BUNDLE_FORMAT_VERSION {
+            return Err(anyhow::anyhow!("The bundle was created by newer version of RDF Glance"));
+        }
+        let entry_count = reader.read_u32::<LittleEndian>()?;
+        let mut bundle = AnalysisBundle::default();
+        for _ in 0..entry_count {
+            let name = read_len_string(reader)?;
+            // entry names are used as file names on import
+            if name.contains("..") || name.starts_with('/') || name.contains('\\') {
+                return Err(anyhow::anyhow!("Invalid entry name in bundle: {}", name));
+            }
+            let compressed_len = reader.read_u64::<LittleEndian>()?;
+            let mut data = Vec::new();
+            ZlibDecoder::new(reader.by_ref().take(compressed_len)).read_to_end(&mut data)?;
+            bundle.entries.push(BundleEntry {
+                name: name.to_string(),
+                data,
+            });
+        }
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_roundtrip() -> Result<()> {
+        let mut bundle = AnalysisBundle::default();
+        bundle.add(PROJECT_ENTRY, vec![1, 2, 3]);
+        bundle.add(NOTES_ENTRY, "# Findings\nall fine".as_bytes().to_vec());
+        bundle.add(&format!("{}data.ttl", SOURCES_PREFIX), "<a> <b> <c> .".as_bytes().to_vec());
+        bundle.add(NOTES_ENTRY, "replaced".as_bytes().to_vec());
+        let mut buffer = Vec::new();
+        bundle.write(&mut buffer)?;
+
+        let read = AnalysisBundle::read(&mut buffer.as_slice())?;
+        assert_eq!(3, read.entries.len());
+        assert_eq!(Some([1u8, 2, 3].as_slice()), read.get(PROJECT_ENTRY));
+        assert_eq!(Some("replaced"), read.get_str(NOTES_ENTRY));
+        assert_eq!(None, read.get(QUERY_ENTRY));
+        let sources: Vec<(&str, &[u8])> = read.sources().collect();
+        assert_eq!(vec![("data.ttl", "<a> <b> <c> .".as_bytes())], sources);
+
+        assert!(AnalysisBundle::read(&mut [0u8; 10].as_slice()).is_err());
+        Ok(())
+    }
+}
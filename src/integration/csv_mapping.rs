@@ -0,0 +1,323 @@
+use std::collections::HashSet;
+
+use csv::ReaderBuilder;
+use oxrdf::{Literal, NamedNode, Triple, vocab::xsd};
+use percent_encoding::{AsciiSet, CONTROLS, utf8_percent_encode};
+use slug::slugify;
+
+use crate::domain::edge_metadata::parse_date_days;
+
+// characters that are not allowed in the local part of the generated IRIs
+const IRI_ENCODE: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'<')
+    .add(b'>')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}')
+    .add(b'|')
+    .add(b'\\')
+    .add(b'^')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'?');
+
+/// Rows of a CSV or TSV file, rows shorter than the header are padded with empty values
+pub struct CsvTable {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+/// Most frequent of the common delimiters in the first line
+pub fn guess_delimiter(text: &str) -> u8 {
+    let first_line = text.lines().next().unwrap_or_default();
+    [b',', b';', b'\t']
+        .into_iter()
+        .max_by_key(|delimiter| first_line.bytes().filter(|c| c == delimiter).count())
+        .unwrap_or(b',')
+}
+
+pub fn read_csv_table(text: &str, delimiter: u8, has_header: bool) -> Result<CsvTable, csv::Error> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes());
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for record in reader.records() {
+        rows.push(record?.iter().map(|value| value.trim().to_string()).collect());
+    }
+    let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let headers = if has_header && !rows.is_empty() {
+        rows.remove(0)
+    } else {
+        Vec::new()
+    };
+    let headers = (0..column_count)
+        .map(|column| match headers.get(column) {
+            Some(header) if !header.is_empty() => header.clone(),
+            _ => format!("column{}", column + 1),
+        })
+        .collect();
+    for row in rows.iter_mut() {
+        row.resize(column_count, String::new());
+    }
+    Ok(CsvTable { headers, rows })
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum SubjectMapping {
+    Column(usize),
+    // IRI from the row number
+    Generated,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ColumnKind {
+    Ignore,
+    DataProperty,
+    Reference,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum ValueDatatype {
+    String,
+    Integer,
+    Double,
+    Date,
+}
+
+impl ValueDatatype {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ValueDatatype::String => "string",
+            ValueDatatype::Integer => "integer",
+            ValueDatatype::Double => "double",
+            ValueDatatype::Date => "date",
+        }
+    }
+
+    /// The most specific datatype that fits all not empty values
+    fn guess<'a>(values: impl Iterator<Item = &'a str>) -> Self {
+        let (mut integer, mut double, mut date, mut any) = (true, true, true, false);
+        for value in values.filter(|value| !value.is_empty()) {
+            any = true;
+            integer = integer && value.parse::<i64>().is_ok();
+            double = double && value.parse::<f64>().is_ok();
+            date = date && value.len() == 10 && parse_date_days(value).is_some();
+        }
+        match (any, integer, double, date) {
+            (false, _, _, _) => ValueDatatype::String,
+            (_, true, _, _) => ValueDatatype::Integer,
+            (_, _, true, _) => ValueDatatype::Double,
+            (_, _, _, true) => ValueDatatype::Date,
+            _ => ValueDatatype::String,
+        }
+    }
+
+    fn literal(&self, value: &str) -> Literal {
+        match self {
+            ValueDatatype::String => Literal::new_simple_literal(value),
+            ValueDatatype::Integer => Literal::new_typed_literal(value, xsd::INTEGER),
+            ValueDatatype::Double => Literal::new_typed_literal(value, xsd::DOUBLE),
+            ValueDatatype::Date => Literal::new_typed_literal(value, xsd::DATE),
+        }
+    }
+}
+
+pub struct ColumnMapping {
+    pub kind: ColumnKind,
+    pub predicate: String,
+    pub datatype: ValueDatatype,
+    // namespace of the referenced nodes, values that are already IRIs are used as they are
+    pub target_namespace: String,
+}
+
+/**
+ * Mapping of the CSV columns to RDF.
+ * Each row becomes a node of the given type, the subject IRI is built from a key column or from the row number.
+ * The other columns become data properties or references to other nodes.
+ */
+pub struct CsvMapping {
+    pub namespace: String,
+    pub type_iri: String,
+    pub subject: SubjectMapping,
+    pub columns: Vec<ColumnMapping>,
+}
+
+fn is_iri(value: &str) -> bool {
+    value.contains("://") || value.starts_with("urn:")
+}
+
+fn local_iri(namespace: &str, value: &str) -> String {
+    if is_iri(value) {
+        value.to_string()
+    } else {
+        format!("{}{}", namespace, utf8_percent_encode(value, IRI_ENCODE))
+    }
+}
+
+impl CsvMapping {
+    /// Default mapping: the first column with unique values is the key, all other columns are data properties
+    pub fn new(table: &CsvTable, file_base: &str) -> Self {
+        let namespace = format!("http://example.org/{}/", slugify(file_base));
+        let key_column = (0..table.headers.len()).find(|column| {
+            let mut values = HashSet::new();
+            !table.rows.is_empty()
+                && table
+                    .rows
+                    .iter()
+                    .all(|row| !row[*column].is_empty() && values.insert(row[*column].as_str()))
+        });
+        let columns = table
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(column, header)| ColumnMapping {
+                kind: if Some(column) == key_column {
+                    ColumnKind::Ignore
+                } else {
+                    ColumnKind::DataProperty
+                },
+                predicate: format!("{}{}", namespace, slugify(header)),
+                datatype: ValueDatatype::guess(table.rows.iter().map(|row| row[column].as_str())),
+                target_namespace: format!("{}{}/", namespace, slugify(header)),
+            })
+            .collect();
+        CsvMapping {
+            type_iri: format!("{}Row", namespace),
+            subject: key_column.map(SubjectMapping::Column).unwrap_or(SubjectMapping::Generated),
+            namespace,
+            columns,
+        }
+    }
+
+    fn subject_iri(&self, row_number: usize, row: &[String]) -> Option<String> {
+        match self.subject {
+            SubjectMapping::Column(column) => {
+                let value = row.get(column)?;
+                (!value.is_empty()).then(|| local_iri(&self.namespace, value))
+            }
+            SubjectMapping::Generated => Some(format!("{}row{}", self.namespace, row_number + 1)),
+        }
+    }
+
+    /// Triples of the rows, invalid IRIs are reported as errors and the affected values skipped
+    pub fn triples(&self, rows: &[Vec<String>], errors: &mut Vec<String>) -> Vec<Triple> {
+        let mut triples = Vec::new();
+        let type_node = match NamedNode::new(&self.type_iri) {
+            Ok(type_node) => Some(type_node),
+            Err(e) => {
+                errors.push(format!("Type IRI {}: {}", self.type_iri, e));
+                None
+            }
+        };
+        let mut predicates: Vec<Option<NamedNode>> = Vec::with_capacity(self.columns.len());
+        for column_mapping in self.columns.iter() {
+            if column_mapping.kind == ColumnKind::Ignore {
+                predicates.push(None);
+                continue;
+            }
+            match NamedNode::new(&column_mapping.predicate) {
+                Ok(predicate) => predicates.push(Some(predicate)),
+                Err(e) => {
+                    errors.push(format!("Predicate IRI {}: {}", column_mapping.predicate, e));
+                    predicates.push(None);
+                }
+            }
+        }
+        for (row_number, row) in rows.iter().enumerate() {
+            let Some(subject_iri) = self.subject_iri(row_number, row) else {
+                errors.push(format!("Row {}: empty key", row_number + 1));
+                continue;
+            };
+            let subject = match NamedNode::new(&subject_iri) {
+                Ok(subject) => subject,
+                Err(e) => {
+                    errors.push(format!("Row {}: {} {}", row_number + 1, subject_iri, e));
+                    continue;
+                }
+            };
+            if let Some(type_node) = &type_node {
+                triples.push(Triple::new(subject.clone(), oxrdf::vocab::rdf::TYPE, type_node.clone()));
+            }
+            for ((column_mapping, predicate), value) in self.columns.iter().zip(predicates.iter()).zip(row.iter()) {
+                let Some(predicate) = predicate else {
+                    continue;
+                };
+                if value.is_empty() {
+                    continue;
+                }
+                match column_mapping.kind {
+                    ColumnKind::Ignore => {}
+                    ColumnKind::DataProperty => {
+                        triples.push(Triple::new(
+                            subject.clone(),
+                            predicate.clone(),
+                            column_mapping.datatype.literal(value),
+                        ));
+                    }
+                    ColumnKind::Reference => {
+                        let target_iri = local_iri(&column_mapping.target_namespace, value);
+                        match NamedNode::new(&target_iri) {
+                            Ok(target) => triples.push(Triple::new(subject.clone(), predicate.clone(), target)),
+                            Err(e) => errors.push(format!("Row {}: {} {}", row_number + 1, target_iri, e)),
+                        }
+                    }
+                }
+            }
+        }
+        triples
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_mapping() -> Result<(), csv::Error> {
+        let text = "id;name;born;city\np1;Anna Maria;1990-01-02;Berlin\np2;Bob;;http://example.org/Paris\n";
+        assert_eq!(b';', guess_delimiter(text));
+        let table = read_csv_table(text, b';', true)?;
+        assert_eq!(vec!["id", "name", "born", "city"], table.headers);
+        assert_eq!(2, table.rows.len());
+
+        let mut mapping = CsvMapping::new(&table, "People List");
+        assert_eq!("http://example.org/people-list/", mapping.namespace);
+        assert!(mapping.subject == SubjectMapping::Column(0));
+        assert!(mapping.columns[0].kind == ColumnKind::Ignore);
+        assert!(mapping.columns[2].datatype == ValueDatatype::Date);
+        mapping.columns[3].kind = ColumnKind::Reference;
+        mapping.columns[3].target_namespace = "http://example.org/city/".to_string();
+
+        let mut errors = Vec::new();
+        let triples: Vec<String> = mapping
+            .triples(&table.rows, &mut errors)
+            .iter()
+            .map(|triple| triple.to_string())
+            .collect();
+        assert!(errors.is_empty());
+        assert_eq!(
+            vec![
+                "<http://example.org/people-list/p1> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/people-list/Row>",
+                "<http://example.org/people-list/p1> <http://example.org/people-list/name> \"Anna Maria\"",
+                "<http://example.org/people-list/p1> <http://example.org/people-list/born> \"1990-01-02\"^^<http://www.w3.org/2001/XMLSchema#date>",
+                "<http://example.org/people-list/p1> <http://example.org/people-list/city> <http://example.org/city/Berlin>",
+                "<http://example.org/people-list/p2> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/people-list/Row>",
+                "<http://example.org/people-list/p2> <http://example.org/people-list/name> \"Bob\"",
+                "<http://example.org/people-list/p2> <http://example.org/people-list/city> <http://example.org/Paris>",
+            ],
+            triples
+        );
+
+        mapping.subject = SubjectMapping::Generated;
+        mapping.type_iri = "not an iri".to_string();
+        let triples = mapping.triples(&table.rows, &mut errors);
+        assert_eq!(1, errors.len());
+        assert_eq!("<http://example.org/people-list/row1>", triples[0].subject.to_string());
+        Ok(())
+    }
+}
@@ -0,0 +1,378 @@
+use percent_encoding::{NON_ALPHANUMERIC, percent_decode_str, utf8_percent_encode};
+
+pub const DEEP_LINK_SCHEME: &str = "rdfglance";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeepLinkView {
+    Browse,
+    Graph,
+}
+
+/**
+ * Link in form rdfglance://open?iri=<percent encoded iri>&view=graph
+ * that focuses a resource of the currently loaded data.
+ */
+#[derive(PartialEq, Debug)]
+pub struct DeepLink {
+    pub iri: String,
+    pub view: DeepLinkView,
+}
+
+impl DeepLink {
+    pub fn parse(link: &str) -> Option<Self> {
+        let rest = link.trim().strip_prefix(DEEP_LINK_SCHEME)?.strip_prefix("://")?;
+        let (action, query) = rest.split_once('?')?;
+        if action.trim_end_matches('/') != "open" {
+            return None;
+        }
+        let mut iri: Option<String> = None;
+        let mut view = DeepLinkView::Browse;
+        for parameter in query.split('&') {
+            let (name, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+            let value = percent_decode_str(value).decode_utf8().ok()?;
+            match name {
+                "iri" => iri = Some(value.into_owned()),
+                "view" if value == "graph" => view = DeepLinkView::Graph,
+                _ => {}
+            }
+        }
+        iri.filter(|iri| !iri.is_empty()).map(|iri| Self { iri, view })
+    }
+
+    pub fn is_deep_link(arg: &str) -> bool {
+        arg.strip_prefix(DEEP_LINK_SCHEME)
+            .is_some_and(|rest| rest.starts_with("://"))
+    }
+
+    pub fn to_link(&self) -> String {
+        let mut link = format!(
+            "{}://open?iri={}",
+            DEEP_LINK_SCHEME,
+            utf8_percent_encode(&self.iri, NON_ALPHANUMERIC)
+        );
+        if self.view == DeepLinkView::Graph {
+            link.push_str("&view=graph");
+        }
+        link
+    }
+}
+
+/**
+ * The operating system starts a new process for each opened link.
+ * The new process forwards the link over local tcp socket to the already running instance
+ * and exits, so the resource is focused in the loaded project.
+ * The running instance listens on a free port, the port and a random token are written to a file
+ * in the storage directory of the user. The link is only taken over if the instance acknowledges it,
+ * otherwise the new process opens the link itself.
+ */
+#[cfg(not(target_arch = "wasm32"))]
+pub mod native {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+        path::{Path, PathBuf},
+        sync::{Arc, OnceLock, mpsc},
+        time::Duration,
+    };
+
+    use super::{DEEP_LINK_SCHEME, DeepLink, DeepLinkView};
+    use crate::{
+        RdfGlanceApp,
+        uistate::{SystemMessage, actions::NodeAction},
+    };
+
+    const LINK_MAGIC: &str = "rdfglance-link";
+    const LINK_ACK: &str = "rdfglance-ok";
+
+    pub struct DeepLinkListener {
+        pub receiver: mpsc::Receiver<DeepLink>,
+        // Set after the first frame, so the listener thread can wake up the ui
+        pub ctx: Arc<OnceLock<egui::Context>>,
+        endpoint_file: PathBuf,
+        token: String,
+    }
+
+    fn endpoint_file() -> Option<PathBuf> {
+        eframe::storage_dir("rdf-glance").map(|dir| dir.join("deep_link.port"))
+    }
+
+    impl DeepLinkListener {
+        /// Returns None if the endpoint file can not be written, the last started instance takes the links
+        pub fn start() -> Option<Self> {
+            Self::start_with_file(endpoint_file()?)
+        }
+
+        pub(super) fn start_with_file(endpoint_file: PathBuf) -> Option<Self> {
+            let listener = TcpListener::bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).ok()?;
+            let port = listener.local_addr().ok()?.port();
+            let token = format!("{:032x}", rand::random::<u128>());
+            if let Err(e) = write_endpoint_file(&endpoint_file, &format!("{} {}\n", port, token)) {
+                log::warn!("Can not write deep link endpoint {}: {}", endpoint_file.display(), e);
+                return None;
+            }
+            let (sender, receiver) = mpsc::channel();
+            let ctx: Arc<OnceLock<egui::Context>> = Arc::new(OnceLock::new());
+            let thread_ctx = ctx.clone();
+            let thread_token = token.clone();
+            std::thread::spawn(move || {
+                for stream in listener.incoming().flatten() {
+                    let sender = sender.clone();
+                    let ctx = thread_ctx.clone();
+                    let token = thread_token.clone();
+                    // an idle client does not block the other ones
+                    std::thread::spawn(move || {
+                        if let Some(deep_link) = receive_link(&stream, &token)
+                            && sender.send(deep_link).is_ok()
+                        {
+                            // the link is acknowledged only if the app takes it
+                            let mut writer = &stream;
+                            let _ = writeln!(writer, "{}", LINK_ACK);
+                            if let Some(ctx) = ctx.get() {
+                                ctx.request_repaint();
+                            }
+                        }
+                    });
+                }
+            });
+            Some(Self {
+                receiver,
+                ctx,
+                endpoint_file,
+                token,
+            })
+        }
+    }
+
+    impl Drop for DeepLinkListener {
+        fn drop(&mut self) {
+            // the file can already belong to a later started instance
+            if std::fs::read_to_string(&self.endpoint_file).is_ok_and(|content| content.contains(&self.token)) {
+                let _ = std::fs::remove_file(&self.endpoint_file);
+            }
+        }
+    }
+
+    fn write_endpoint_file(path: &Path, content: &str) -> std::io::Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        // the token must not be readable by other users
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        options.open(path)?.write_all(content.as_bytes())
+    }
+
+    // Reads the magic line with the token and the link
+    fn receive_link(stream: &TcpStream, token: &str) -> Option<DeepLink> {
+        stream.set_read_timeout(Some(Duration::from_secs(2))).ok()?;
+        let mut reader = BufReader::new(stream);
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        if line.trim_end() != format!("{} {}", LINK_MAGIC, token) {
+            return None;
+        }
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+        DeepLink::parse(&line)
+    }
+
+    impl RdfGlanceApp {
+        /// Takes the link given as program argument or forwarded by other process and focuses its resource
+        pub fn deep_link_action(&mut self, ctx: &egui::Context) -> Option<NodeAction> {
+            let mut deep_link = self.pending_deep_link.take();
+            if let Some(listener) = &self.deep_link_listener {
+                let _ = listener.ctx.set(ctx.clone());
+                while let Ok(link) = listener.receiver.try_recv() {
+                    if self.config.deep_links {
+                        deep_link = Some(link);
+                    }
+                }
+            }
+            let deep_link = deep_link?;
+            ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+            let rdf_data = self.rdf_data.read().ok()?;
+            let node_data = &rdf_data.node_data;
+            let node_index = node_data
+                .get_node_index(&deep_link.iri)
+                .or_else(|| node_data.get_node_index(&rdf_data.prefix_manager.get_prefixed(&deep_link.iri)));
+            match node_index {
+                Some(node_index) => Some(match deep_link.view {
+                    DeepLinkView::Browse => NodeAction::BrowseNode(node_index),
+                    DeepLinkView::Graph => NodeAction::ShowVisual(node_index),
+                }),
+                None => {
+                    self.system_message =
+                        SystemMessage::Info(format!("Linked resource not found in loaded data: {}", deep_link.iri));
+                    None
+                }
+            }
+        }
+
+        pub fn start_deep_link_listener(&mut self) {
+            if self.deep_link_listener.is_none() {
+                self.deep_link_listener = DeepLinkListener::start();
+            }
+        }
+    }
+
+    /// Returns true if the link was taken over by already running instance
+    pub fn forward_to_running_instance(link: &str) -> bool {
+        endpoint_file().is_some_and(|endpoint_file| forward_with_file(&endpoint_file, link))
+    }
+
+    pub(super) fn forward_with_file(endpoint_file: &Path, link: &str) -> bool {
+        send_link(endpoint_file, link).is_some()
+    }
+
+    fn send_link(endpoint_file: &Path, link: &str) -> Option<()> {
+        let content = std::fs::read_to_string(endpoint_file).ok()?;
+        let (port, token) = content.trim().split_once(' ')?;
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port.parse::<u16>().ok()?));
+        let stream = TcpStream::connect_timeout(&address, Duration::from_millis(500)).ok()?;
+        stream.set_read_timeout(Some(Duration::from_secs(2))).ok()?;
+        let mut writer = &stream;
+        write!(writer, "{} {}\n{}\n", LINK_MAGIC, token, link.trim()).ok()?;
+        let mut ack = String::new();
+        BufReader::new(&stream).read_line(&mut ack).ok()?;
+        (ack.trim_end() == LINK_ACK).then_some(())
+    }
+
+    /// Registers the application executable as handler of rdfglance:// links for the current user
+    pub fn register_url_scheme() -> anyhow::Result<String> {
+        let exe = std::env::current_exe()?;
+        register_for_platform(&exe.display().to_string())
+    }
+
+    #[cfg(target_os = "windows")]
+    fn register_for_platform(exe: &str) -> anyhow::Result<String> {
+        let key = format!("HKCU\\Software\\Classes\\{}", DEEP_LINK_SCHEME);
+        let command = format!("\"{}\" \"%1\"", exe);
+        let entries: [(String, &str, String); 3] = [
+            (key.clone(), "/ve", "URL:RDF Glance".to_string()),
+            (key.clone(), "URL Protocol", String::new()),
+            (format!("{}\\shell\\open\\command", key), "/ve", command),
+        ];
+        for (key, value_name, data) in entries.iter() {
+            let mut reg = std::process::Command::new("reg");
+            reg.args(["add", key]);
+            if *value_name == "/ve" {
+                reg.arg("/ve");
+            } else {
+                reg.args(["/v", value_name]);
+            }
+            let status = reg.args(["/d", data, "/f"]).status()?;
+            if !status.success() {
+                anyhow::bail!("reg add {} failed", key);
+            }
+        }
+        Ok(format!("Registered {}:// links in the Windows registry", DEEP_LINK_SCHEME))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn register_for_platform(exe: &str) -> anyhow::Result<String> {
+        let home = std::env::var("HOME")?;
+        let applications = std::path::Path::new(&home).join(".local/share/applications");
+        std::fs::create_dir_all(&applications)?;
+        let desktop_file = "rdfglance-url-handler.desktop";
+        std::fs::write(
+            applications.join(desktop_file),
+            format!(
+                "[Desktop Entry]\nType=Application\nName=RDF Glance\nExec=\"{}\" %u\nNoDisplay=true\nMimeType=x-scheme-handler/{};\n",
+                exe, DEEP_LINK_SCHEME
+            ),
+        )?;
+        let status = std::process::Command::new("xdg-mime")
+            .args(["default", desktop_file, &format!("x-scheme-handler/{}", DEEP_LINK_SCHEME)])
+            .status()?;
+        if !status.success() {
+            anyhow::bail!("xdg-mime failed");
+        }
+        Ok(format!("Registered {}:// links with {}", DEEP_LINK_SCHEME, desktop_file))
+    }
+
+    // On macOS the scheme is declared by CFBundleURLTypes in Info.plist of the application bundle
+    #[cfg(any(target_os = "macos", not(any(unix, target_os = "windows"))))]
+    fn register_for_platform(_exe: &str) -> anyhow::Result<String> {
+        anyhow::bail!(
+            "{}:// links can only be registered by the application bundle on this platform",
+            DEEP_LINK_SCHEME
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_link() {
+        let link = DeepLink {
+            iri: "http://example.org/a#b c".to_string(),
+            view: DeepLinkView::Graph,
+        };
+        let text = link.to_link();
+        assert!(DeepLink::is_deep_link(&text));
+        assert_eq!(Some(link), DeepLink::parse(&text));
+        assert_eq!(
+            Some(DeepLink {
+                iri: "ex:a".to_string(),
+                view: DeepLinkView::Browse
+            }),
+            DeepLink::parse("rdfglance://open/?iri=ex%3Aa&other=1\n")
+        );
+        assert_eq!(None, DeepLink::parse("rdfglance://delete?iri=ex%3Aa"));
+        assert_eq!(None, DeepLink::parse("http://open?iri=ex%3Aa"));
+        assert_eq!(None, DeepLink::parse("rdfglance://open?view=graph"));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_forward_link() {
+        use std::{
+            io::Read,
+            net::{TcpListener, TcpStream},
+            time::Duration,
+        };
+
+        let endpoint_file = std::env::temp_dir().join(format!("rdfglance-{}-deep_link.port", std::process::id()));
+        let _ = std::fs::remove_file(&endpoint_file);
+        let link = DeepLink {
+            iri: "http://example.org/a".to_string(),
+            view: DeepLinkView::Graph,
+        };
+        // no running instance
+        assert!(!native::forward_with_file(&endpoint_file, &link.to_link()));
+
+        let listener = native::DeepLinkListener::start_with_file(endpoint_file.clone()).unwrap();
+        let content = std::fs::read_to_string(&endpoint_file).unwrap();
+        let port = content.split_once(' ').unwrap().0.parse::<u16>().unwrap();
+        // an idle client does not block the forwarding
+        let _idle = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        assert!(native::forward_with_file(&endpoint_file, &link.to_link()));
+        assert_eq!(link, listener.receiver.recv_timeout(Duration::from_secs(5)).unwrap());
+
+        // a wrong token is not acknowledged
+        std::fs::write(&endpoint_file, format!("{} other\n", port)).unwrap();
+        assert!(!native::forward_with_file(&endpoint_file, &link.to_link()));
+        assert!(listener.receiver.try_recv().is_err());
+
+        // a foreign service on the port does not take the link
+        let foreign = TcpListener::bind("127.0.0.1:0").unwrap();
+        let foreign_port = foreign.local_addr().unwrap().port();
+        let foreign_thread = std::thread::spawn(move || {
+            let (mut stream, _) = foreign.accept().unwrap();
+            let mut buffer = [0u8; 64];
+            let _ = stream.read(&mut buffer);
+        });
+        std::fs::write(&endpoint_file, format!("{} token\n", foreign_port)).unwrap();
+        assert!(!native::forward_with_file(&endpoint_file, &link.to_link()));
+        foreign_thread.join().unwrap();
+
+        // the file of another instance is kept
+        drop(listener);
+        assert!(endpoint_file.exists());
+        std::fs::remove_file(&endpoint_file).unwrap();
+    }
+}
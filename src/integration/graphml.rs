@@ -0,0 +1,260 @@
+use std::io;
+
+use egui::Color32;
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+
+use crate::{
+    domain::{
+        LabelContext, NodeData,
+        graph_styles::{GVisualizationStyle, NodeShape},
+    },
+    support::SortedVec,
+    uistate::layout::{LayoutData, SortedNodeLayout},
+};
+
+// (id, for, name, type) the attribute names x, y, size, r, g, b are recognized by Gephi
+const GRAPHML_KEYS: [(&str, &str, &str, &str); 11] = [
+    ("label", "node", "label", "string"),
+    ("iri", "node", "iri", "string"),
+    ("type", "node", "type", "string"),
+    ("x", "node", "x", "double"),
+    ("y", "node", "y", "double"),
+    ("size", "node", "size", "double"),
+    ("r", "node", "r", "int"),
+    ("g", "node", "g", "int"),
+    ("b", "node", "b", "int"),
+    ("edge_label", "edge", "label", "string"),
+    ("predicate", "edge", "predicate", "string"),
+];
+
+/**
+ * Writes the visible nodes and edges of the visual graph as GraphML.
+ * The positions and colors are written as plain attributes (Gephi) and as yFiles graphics (yEd).
+ */
+pub fn write_graphml<W: io::Write>(
+    wtr: &mut W,
+    visible_nodes: &SortedNodeLayout,
+    node_data: &NodeData,
+    label_context: &LabelContext,
+    visualization_style: &GVisualizationStyle,
+    hidden_predicates: &SortedVec,
+    short_iri: bool,
+) -> io::Result<()> {
+    let Ok(layout) = visible_nodes.layout_data.read() else {
+        return Ok(());
+    };
+    let LayoutData {
+        nodes,
+        positions,
+        node_shapes,
+        edges,
+        ..
+    } = &*layout;
+    let mut writer = Writer::new_with_indent(wtr, b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+    let mut graphml = BytesStart::new("graphml");
+    graphml.push_attribute(("xmlns", "http://graphml.graphdrawing.org/xmlns"));
+    graphml.push_attribute(("xmlns:y", "http://www.yworks.com/xml/graphml"));
+    writer.write_event(Event::Start(graphml))?;
+    for (id, key_for, name, key_type) in GRAPHML_KEYS {
+        let mut key = BytesStart::new("key");
+        key.push_attribute(("id", id));
+        key.push_attribute(("for", key_for));
+        key.push_attribute(("attr.name", name));
+        key.push_attribute(("attr.type", key_type));
+        writer.write_event(Event::Empty(key))?;
+    }
+    for (id, key_for, yfiles_type) in [("nodegraphics", "node", "nodegraphics"), ("edgegraphics", "edge", "edgegraphics")] {
+        let mut key = BytesStart::new("key");
+        key.push_attribute(("id", id));
+        key.push_attribute(("for", key_for));
+        key.push_attribute(("yfiles.type", yfiles_type));
+        writer.write_event(Event::Empty(key))?;
+    }
+    let mut graph = BytesStart::new("graph");
+    graph.push_attribute(("id", "G"));
+    graph.push_attribute(("edgedefault", "directed"));
+    writer.write_event(Event::Start(graph))?;
+
+    for (node_pos, node_layout) in nodes.iter().enumerate() {
+        let Some((object_iri, object)) = node_data.get_node_by_index(node_layout.node_index) else {
+            continue;
+        };
+        let pos = positions[node_pos].pos;
+        let size = node_shapes[node_pos].size;
+        let type_style = visualization_style.get_type_style(&object.types);
+        let label = object.node_label(
+            object_iri,
+            visualization_style,
+            short_iri,
+            label_context.language_index,
+            &node_data.indexers,
+        );
+        let types: Vec<String> = object
+            .types
+            .iter()
+            .map(|type_index| {
+                node_data
+                    .type_display(*type_index, label_context, &node_data.indexers)
+                    .as_str()
+                    .to_string()
+            })
+            .collect();
+        let color = type_style.color;
+
+        let mut node = BytesStart::new("node");
+        node.push_attribute(("id", format!("n{}", node_pos).as_str()));
+        writer.write_event(Event::Start(node))?;
+        write_data(&mut writer, "label", label)?;
+        write_data(&mut writer, "iri", object_iri)?;
+        write_data(&mut writer, "type", &types.join(", "))?;
+        write_data(&mut writer, "x", &pos.x.to_string())?;
+        write_data(&mut writer, "y", &pos.y.to_string())?;
+        write_data(&mut writer, "size", &size.x.max(size.y).to_string())?;
+        write_data(&mut writer, "r", &color.r().to_string())?;
+        write_data(&mut writer, "g", &color.g().to_string())?;
+        write_data(&mut writer, "b", &color.b().to_string())?;
+
+        let mut data = BytesStart::new("data");
+        data.push_attribute(("key", "nodegraphics"));
+        writer.write_event(Event::Start(data))?;
+        writer.write_event(Event::Start(BytesStart::new("y:ShapeNode")))?;
+        let mut geometry = BytesStart::new("y:Geometry");
+        // yEd uses the upper left corner
+        geometry.push_attribute(("x", (pos.x - size.x / 2.0).to_string().as_str()));
+        geometry.push_attribute(("y", (pos.y - size.y / 2.0).to_string().as_str()));
+        geometry.push_attribute(("width", size.x.to_string().as_str()));
+        geometry.push_attribute(("height", size.y.to_string().as_str()));
+        writer.write_event(Event::Empty(geometry))?;
+        let mut fill = BytesStart::new("y:Fill");
+        fill.push_attribute(("color", hex_color(color).as_str()));
+        writer.write_event(Event::Empty(fill))?;
+        writer.write_event(Event::Start(BytesStart::new("y:NodeLabel")))?;
+        writer.write_event(Event::Text(BytesText::new(label)))?;
+        writer.write_event(Event::End(BytesEnd::new("y:NodeLabel")))?;
+        let mut shape = BytesStart::new("y:Shape");
+        shape.push_attribute((
+            "type",
+            match node_shapes[node_pos].node_shape {
+                NodeShape::Rect => "rectangle",
+                _ => "ellipse",
+            },
+        ));
+        writer.write_event(Event::Empty(shape))?;
+        writer.write_event(Event::End(BytesEnd::new("y:ShapeNode")))?;
+        writer.write_event(Event::End(BytesEnd::new("data")))?;
+        writer.write_event(Event::End(BytesEnd::new("node")))?;
+    }
+
+    for (edge_pos, edge) in edges.iter().enumerate() {
+        if hidden_predicates.contains(edge.predicate) {
+            continue;
+        }
+        let label = node_data.predicate_display(edge.predicate, label_context, &node_data.indexers);
+        let predicate = node_data.get_predicate(edge.predicate).unwrap_or("");
+        let predicate = label_context
+            .prefix_manager
+            .get_full_opt(predicate)
+            .map(|full_iri| full_iri.to_string())
+            .unwrap_or_else(|| predicate.to_string());
+
+        let mut edge_element = BytesStart::new("edge");
+        edge_element.push_attribute(("id", format!("e{}", edge_pos).as_str()));
+        edge_element.push_attribute(("source", format!("n{}", edge.from).as_str()));
+        edge_element.push_attribute(("target", format!("n{}", edge.to).as_str()));
+        writer.write_event(Event::Start(edge_element))?;
+        write_data(&mut writer, "edge_label", label.as_str())?;
+        write_data(&mut writer, "predicate", &predicate)?;
+
+        let mut data = BytesStart::new("data");
+        data.push_attribute(("key", "edgegraphics"));
+        writer.write_event(Event::Start(data))?;
+        writer.write_event(Event::Start(BytesStart::new("y:PolyLineEdge")))?;
+        let mut arrows = BytesStart::new("y:Arrows");
+        arrows.push_attribute(("source", "none"));
+        arrows.push_attribute(("target", "standard"));
+        writer.write_event(Event::Empty(arrows))?;
+        writer.write_event(Event::Start(BytesStart::new("y:EdgeLabel")))?;
+        writer.write_event(Event::Text(BytesText::new(label.as_str())))?;
+        writer.write_event(Event::End(BytesEnd::new("y:EdgeLabel")))?;
+        writer.write_event(Event::End(BytesEnd::new("y:PolyLineEdge")))?;
+        writer.write_event(Event::End(BytesEnd::new("data")))?;
+        writer.write_event(Event::End(BytesEnd::new("edge")))?;
+    }
+
+    writer.write_event(Event::End(BytesEnd::new("graph")))?;
+    writer.write_event(Event::End(BytesEnd::new("graphml")))?;
+    Ok(())
+}
+
+fn write_data<W: io::Write>(writer: &mut Writer<W>, key: &str, value: &str) -> io::Result<()> {
+    let mut data = BytesStart::new("data");
+    data.push_attribute(("key", key));
+    writer.write_event(Event::Start(data))?;
+    writer.write_event(Event::Text(BytesText::new(value)))?;
+    writer.write_event(Event::End(BytesEnd::new("data")))?;
+    Ok(())
+}
+
+fn hex_color(color: Color32) -> String {
+    format!("#{:02X}{:02X}{:02X}", color.r(), color.g(), color.b())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::{
+        domain::{RdfData, config::IriDisplay, graph_styles::NodeStyle, size_mapping::SizeMapping, style_rules::StyleRules},
+        ui::graph_view::{NeighborPos, update_layout_edges},
+    };
+
+    #[test]
+    fn test_write_graphml() {
+        let rdf_data = RdfData::from_patch(
+            "A <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n\
+             A <http://example.org/a> <http://www.w3.org/2000/01/rdf-schema#label> \"Alice & Co\" .\n",
+        );
+        let hidden_predicates = SortedVec::new();
+        let mut visible_nodes = SortedNodeLayout::new();
+        let mut npos = NeighborPos::new();
+        for iri in ["http://example.org/a", "http://example.org/b"] {
+            let node_index = rdf_data.node_data.get_node_index(iri).unwrap();
+            visible_nodes.add_by_index(node_index);
+            npos.insert(node_index, node_index);
+        }
+        update_layout_edges(&npos, &mut visible_nodes, &rdf_data.node_data, &hidden_predicates);
+        let label_context = LabelContext::new(0, IriDisplay::Full, &rdf_data.prefix_manager);
+        let visualization_style = GVisualizationStyle {
+            node_styles: HashMap::new(),
+            edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
+            default_node_style: NodeStyle::default(),
+            use_size_overwrite: false,
+            use_color_overwrite: false,
+            default_label_in_node: false,
+            min_size: 5.0,
+            max_size: 50.0,
+        };
+        let mut out = Vec::new();
+        write_graphml(
+            &mut out,
+            &visible_nodes,
+            &rdf_data.node_data,
+            &label_context,
+            &visualization_style,
+            &hidden_predicates,
+            true,
+        )
+        .unwrap();
+        let graphml = String::from_utf8(out).unwrap();
+        assert!(graphml.contains(r#"<edge id="e0" source="n0" target="n1">"#));
+        assert!(graphml.contains(r#"<data key="iri">http://example.org/b</data>"#));
+        assert!(graphml.contains(r#"<data key="predicate">http://example.org/knows</data>"#));
+        assert!(graphml.contains("<y:Geometry"));
+    }
+}
@@ -0,0 +1,632 @@
+use std::io::{self, Read};
+
+use oxrdf::{BlankNode, Literal, NamedNode, NamedOrBlankNode, Term, Triple};
+
+// Binary layout as described in https://www.rdfhdt.org/hdt-binary-format/
+// Only the widely used combination of dictionaryFour with plain front coding sections
+// and bitmap triples in SPO order is supported.
+const HDT_COOKIE: &[u8; 4] = b"$HDT";
+const CONTROL_GLOBAL: u8 = 1;
+const CONTROL_HEADER: u8 = 2;
+const CONTROL_DICTIONARY: u8 = 3;
+const CONTROL_TRIPLES: u8 = 4;
+const DICTIONARY_FOUR: &str = "<http://purl.org/HDT/hdt#dictionaryFour>";
+const TRIPLES_BITMAP: &str = "<http://purl.org/HDT/hdt#triplesBitmap>";
+const SECTION_PLAIN_FRONT_CODING: u8 = 2;
+const SEQUENCE_LOG: u8 = 1;
+const BITMAP_PLAIN: u8 = 1;
+const ORDER_SPO: &str = "1";
+
+struct ControlInfo {
+    control_type: u8,
+    format: String,
+    properties: String,
+}
+
+impl ControlInfo {
+    fn property(&self, key: &str) -> Option<&str> {
+        self.properties.split(';').find_map(|property| {
+            property
+                .split_once('=')
+                .and_then(|(name, value)| if name == key { Some(value) } else { None })
+        })
+    }
+}
+
+struct LogSequence {
+    num_bits: usize,
+    num_entries: usize,
+    data: Vec<u8>,
+}
+
+impl LogSequence {
+    fn get(&self, index: usize) -> u64 {
+        if self.num_bits == 0 {
+            return 0;
+        }
+        let bit_pos = index * self.num_bits;
+        let byte_pos = bit_pos / 8;
+        let shift = bit_pos % 8;
+        let mut word: u128 = 0;
+        for k in 0..(shift + self.num_bits).div_ceil(8) {
+            word |= (self.data.get(byte_pos + k).copied().unwrap_or(0) as u128) << (8 * k);
+        }
+        let mask = if self.num_bits >= 64 {
+            u64::MAX as u128
+        } else {
+            (1u128 << self.num_bits) - 1
+        };
+        ((word >> shift) & mask) as u64
+    }
+}
+
+struct Bitmap {
+    data: Vec<u8>,
+}
+
+impl Bitmap {
+    fn get(&self, index: usize) -> bool {
+        self.data.get(index / 8).is_some_and(|byte| (byte >> (index % 8)) & 1 == 1)
+    }
+}
+
+struct HdtData {
+    shared: Vec<NamedOrBlankNode>,
+    subjects: Vec<NamedOrBlankNode>,
+    predicates: Vec<NamedNode>,
+    objects: Vec<Term>,
+    bitmap_y: Bitmap,
+    bitmap_z: Bitmap,
+    array_y: LogSequence,
+    array_z: LogSequence,
+}
+
+/**
+ * Reader for HDT (Header-Dictionary-Triples) files.
+ * The dictionary and the compressed triple arrays are read on the first call of next,
+ * the triples are then decoded one by one from the bitmap triples structure.
+ */
+pub struct HdtRdfParser<R: Read> {
+    reader: Option<R>,
+    data: Option<HdtData>,
+    subject: u64,
+    y_pos: usize,
+    z_pos: usize,
+}
+
+impl<R: Read> HdtRdfParser<R> {
+    pub fn for_reader(reader: R) -> Self {
+        Self {
+            reader: Some(reader),
+            data: None,
+            subject: 1,
+            y_pos: 0,
+            z_pos: 0,
+        }
+    }
+
+    fn next_triple(&mut self) -> Option<Result<Triple, io::Error>> {
+        let data = self.data.as_ref()?;
+        if self.z_pos >= data.array_z.num_entries || self.y_pos >= data.array_y.num_entries {
+            return None;
+        }
+        let predicate = data.array_y.get(self.y_pos);
+        let object = data.array_z.get(self.z_pos);
+        let triple = data.triple(self.subject, predicate, object);
+        if data.bitmap_z.get(self.z_pos) {
+            if data.bitmap_y.get(self.y_pos) {
+                self.subject += 1;
+            }
+            self.y_pos += 1;
+        }
+        self.z_pos += 1;
+        Some(triple)
+    }
+}
+
+impl<R: Read> Iterator for HdtRdfParser<R> {
+    type Item = Result<Triple, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(mut reader) = self.reader.take() {
+            match read_hdt(&mut reader) {
+                Ok(data) => {
+                    self.data = Some(data);
+                }
+                Err(e) => {
+                    return Some(Err(e));
+                }
+            }
+        }
+        self.next_triple()
+    }
+}
+
+impl HdtData {
+    // Ids are 1 based, the shared section (terms used as subject and object) comes first
+    fn triple(&self, subject: u64, predicate: u64, object: u64) -> Result<Triple, io::Error> {
+        let shared_len = self.shared.len() as u64;
+        let subject = if subject <= shared_len {
+            self.shared.get(subject as usize - 1).cloned()
+        } else {
+            self.subjects.get((subject - shared_len) as usize - 1).cloned()
+        };
+        let predicate = if predicate > 0 {
+            self.predicates.get(predicate as usize - 1).cloned()
+        } else {
+            None
+        };
+        let object = if object == 0 {
+            None
+        } else if object <= shared_len {
+            self.shared.get(object as usize - 1).cloned().map(Term::from)
+        } else {
+            self.objects.get((object - shared_len) as usize - 1).cloned()
+        };
+        match (subject, predicate, object) {
+            (Some(subject), Some(predicate), Some(object)) => Ok(Triple::new(subject, predicate, object)),
+            _ => Err(invalid_data("HDT triple references unknown dictionary id")),
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+fn read_hdt<R: Read>(reader: &mut R) -> Result<HdtData, io::Error> {
+    let global = read_control_info(reader)?;
+    if global.control_type != CONTROL_GLOBAL {
+        return Err(invalid_data("Not a HDT file, global control information expected"));
+    }
+    let header = read_control_info(reader)?;
+    if header.control_type != CONTROL_HEADER {
+        return Err(invalid_data("HDT header expected"));
+    }
+    let header_len = header
+        .property("length")
+        .and_then(|length| length.parse::<usize>().ok())
+        .ok_or_else(|| invalid_data("HDT header without length"))?;
+    skip(reader, header_len)?;
+
+    let dictionary = read_control_info(reader)?;
+    if dictionary.control_type != CONTROL_DICTIONARY {
+        return Err(invalid_data("HDT dictionary expected"));
+    }
+    if dictionary.format != DICTIONARY_FOUR {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Unsupported HDT dictionary {}", dictionary.format),
+        ));
+    }
+    let shared = read_section(reader)?
+        .iter()
+        .map(|term| to_subject(term))
+        .collect::<Result<Vec<_>, _>>()?;
+    let subjects = read_section(reader)?
+        .iter()
+        .map(|term| to_subject(term))
+        .collect::<Result<Vec<_>, _>>()?;
+    let predicates = read_section(reader)?
+        .into_iter()
+        .map(NamedNode::new_unchecked)
+        .collect();
+    let objects = read_section(reader)?.iter().map(|term| to_object(term)).collect();
+
+    let triples = read_control_info(reader)?;
+    if triples.control_type != CONTROL_TRIPLES {
+        return Err(invalid_data("HDT triples expected"));
+    }
+    if triples.format != TRIPLES_BITMAP {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            format!("Unsupported HDT triples format {}", triples.format),
+        ));
+    }
+    if triples.property("order").is_some_and(|order| order != ORDER_SPO) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Only HDT triples in SPO order are supported",
+        ));
+    }
+    let bitmap_y = read_bitmap(reader)?;
+    let bitmap_z = read_bitmap(reader)?;
+    let array_y = read_log_sequence(reader)?;
+    let array_z = read_log_sequence(reader)?;
+    Ok(HdtData {
+        shared,
+        subjects,
+        predicates,
+        objects,
+        bitmap_y,
+        bitmap_z,
+        array_y,
+        array_z,
+    })
+}
+
+fn read_u8<R: Read>(reader: &mut R) -> Result<u8, io::Error> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn skip<R: Read>(reader: &mut R, len: usize) -> Result<(), io::Error> {
+    let copied = io::copy(&mut reader.take(len as u64), &mut io::sink())?;
+    if copied < len as u64 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    Ok(())
+}
+
+// The sizes are read from the file, so a corrupt or hostile file must not cause a huge allocation.
+// The buffer grows only with the bytes really read.
+fn read_bytes<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, io::Error> {
+    let mut data = Vec::new();
+    reader.take(len as u64).read_to_end(&mut data)?;
+    if data.len() < len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    Ok(data)
+}
+
+fn read_size<R: Read>(reader: &mut R) -> Result<usize, io::Error> {
+    usize::try_from(read_vbyte(reader)?).map_err(|_| invalid_data("HDT size too large"))
+}
+
+// HDT variable bytes: 7 bits per byte, least significant first, the last byte has the high bit set
+fn read_vbyte<R: Read>(reader: &mut R) -> Result<u64, io::Error> {
+    let mut value: u64 = 0;
+    let mut shift = 0;
+    loop {
+        let byte = read_u8(reader)?;
+        if shift > 63 {
+            return Err(invalid_data("HDT number too large"));
+        }
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 != 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn read_vbyte_slice(data: &[u8], pos: &mut usize) -> Result<usize, io::Error> {
+    let mut value: usize = 0;
+    let mut shift = 0;
+    loop {
+        let byte = *data.get(*pos).ok_or_else(|| invalid_data("HDT dictionary truncated"))?;
+        *pos += 1;
+        if shift >= usize::BITS {
+            return Err(invalid_data("HDT number too large"));
+        }
+        value |= ((byte & 0x7f) as usize) << shift;
+        if byte & 0x80 != 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn read_cstring<R: Read>(reader: &mut R) -> Result<String, io::Error> {
+    let mut bytes = Vec::new();
+    loop {
+        let byte = read_u8(reader)?;
+        if byte == 0 {
+            break;
+        }
+        bytes.push(byte);
+    }
+    String::from_utf8(bytes).map_err(|_| invalid_data("HDT string is not utf-8"))
+}
+
+fn read_control_info<R: Read>(reader: &mut R) -> Result<ControlInfo, io::Error> {
+    let mut cookie = [0u8; 4];
+    reader.read_exact(&mut cookie)?;
+    if &cookie != HDT_COOKIE {
+        return Err(invalid_data("HDT control information expected"));
+    }
+    let control_type = read_u8(reader)?;
+    let format = read_cstring(reader)?;
+    let properties = read_cstring(reader)?;
+    // crc16
+    skip(reader, 2)?;
+    Ok(ControlInfo {
+        control_type,
+        format,
+        properties,
+    })
+}
+
+fn read_log_sequence<R: Read>(reader: &mut R) -> Result<LogSequence, io::Error> {
+    if read_u8(reader)? != SEQUENCE_LOG {
+        return Err(invalid_data("Unsupported HDT sequence type"));
+    }
+    let num_bits = read_u8(reader)? as usize;
+    if num_bits > 64 {
+        return Err(invalid_data("Invalid HDT sequence bit size"));
+    }
+    let num_entries = read_size(reader)?;
+    // crc8
+    skip(reader, 1)?;
+    let data_bits = num_bits
+        .checked_mul(num_entries)
+        .ok_or_else(|| invalid_data("Invalid HDT sequence size"))?;
+    let data = read_bytes(reader, data_bits.div_ceil(8))?;
+    // crc32
+    skip(reader, 4)?;
+    Ok(LogSequence {
+        num_bits,
+        num_entries,
+        data,
+    })
+}
+
+fn read_bitmap<R: Read>(reader: &mut R) -> Result<Bitmap, io::Error> {
+    if read_u8(reader)? != BITMAP_PLAIN {
+        return Err(invalid_data("Unsupported HDT bitmap type"));
+    }
+    let num_bits = read_size(reader)?;
+    // crc8
+    skip(reader, 1)?;
+    let data = read_bytes(reader, num_bits.div_ceil(8))?;
+    // crc32
+    skip(reader, 4)?;
+    Ok(Bitmap { data })
+}
+
+// Plain front coding: strings are grouped in blocks, the first string of a block is stored
+// completely, the following strings only store the length of the common prefix and the suffix.
+fn read_section<R: Read>(reader: &mut R) -> Result<Vec<String>, io::Error> {
+    if read_u8(reader)? != SECTION_PLAIN_FRONT_CODING {
+        return Err(invalid_data("Unsupported HDT dictionary section type"));
+    }
+    let num_strings = read_size(reader)?;
+    let bytes = read_size(reader)?;
+    let block_size = read_size(reader)?;
+    // crc8
+    skip(reader, 1)?;
+    // block start positions are only needed for random access
+    read_log_sequence(reader)?;
+    let data = read_bytes(reader, bytes)?;
+    // crc32
+    skip(reader, 4)?;
+
+    // every string ends with a zero byte, so a corrupt count can not reserve more than the data size
+    let mut strings: Vec<String> = Vec::with_capacity(num_strings.min(data.len()));
+    let mut pos = 0;
+    let mut previous: Vec<u8> = Vec::new();
+    for index in 0..num_strings {
+        let prefix_len = if block_size == 0 || index % block_size == 0 {
+            0
+        } else {
+            read_vbyte_slice(&data, &mut pos)?
+        };
+        let end = data[pos..]
+            .iter()
+            .position(|b| *b == 0)
+            .map(|offset| pos + offset)
+            .ok_or_else(|| invalid_data("HDT dictionary truncated"))?;
+        if prefix_len > previous.len() {
+            return Err(invalid_data("Invalid HDT front coding"));
+        }
+        previous.truncate(prefix_len);
+        previous.extend_from_slice(&data[pos..end]);
+        pos = end + 1;
+        strings.push(String::from_utf8(previous.clone()).map_err(|_| invalid_data("HDT string is not utf-8"))?);
+    }
+    Ok(strings)
+}
+
+fn to_subject(term: &str) -> Result<NamedOrBlankNode, io::Error> {
+    if let Some(blank_node) = term.strip_prefix("_:") {
+        Ok(BlankNode::new_unchecked(blank_node).into())
+    } else if term.starts_with('"') {
+        Err(invalid_data("Literal used as subject in HDT dictionary"))
+    } else {
+        Ok(NamedNode::new_unchecked(term).into())
+    }
+}
+
+// Literals are stored in N-Triples like form: "value", "value"@lang or "value"^^<datatype>
+fn to_object(term: &str) -> Term {
+    if let Some(blank_node) = term.strip_prefix("_:") {
+        return BlankNode::new_unchecked(blank_node).into();
+    }
+    if !term.starts_with('"') {
+        return NamedNode::new_unchecked(term).into();
+    }
+    let Some(end_quote) = term.rfind('"').filter(|end_quote| *end_quote > 0) else {
+        return Literal::new_simple_literal(&term[1..]).into();
+    };
+    let value = &term[1..end_quote];
+    let suffix = &term[end_quote + 1..];
+    if let Some(language) = suffix.strip_prefix('@') {
+        Literal::new_language_tagged_literal_unchecked(value, language.to_ascii_lowercase()).into()
+    } else if let Some(datatype) = suffix.strip_prefix("^^") {
+        let datatype = datatype.trim_start_matches('<').trim_end_matches('>');
+        Literal::new_typed_literal(value, NamedNode::new_unchecked(datatype)).into()
+    } else {
+        Literal::new_simple_literal(value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_vbyte(out: &mut Vec<u8>, mut value: u64) {
+        while value > 127 {
+            out.push((value & 127) as u8);
+            value >>= 7;
+        }
+        out.push(value as u8 | 0x80);
+    }
+
+    fn write_control_info(out: &mut Vec<u8>, control_type: u8, format: &str, properties: &str) {
+        out.extend_from_slice(HDT_COOKIE);
+        out.push(control_type);
+        out.extend_from_slice(format.as_bytes());
+        out.push(0);
+        out.extend_from_slice(properties.as_bytes());
+        out.push(0);
+        out.extend_from_slice(&[0, 0]);
+    }
+
+    fn write_log_sequence(out: &mut Vec<u8>, num_bits: usize, values: &[u64]) {
+        out.push(SEQUENCE_LOG);
+        out.push(num_bits as u8);
+        write_vbyte(out, values.len() as u64);
+        out.push(0);
+        let mut data = vec![0u8; (num_bits * values.len()).div_ceil(8)];
+        for (index, value) in values.iter().enumerate() {
+            for bit in 0..num_bits {
+                if (value >> bit) & 1 == 1 {
+                    let pos = index * num_bits + bit;
+                    data[pos / 8] |= 1 << (pos % 8);
+                }
+            }
+        }
+        out.extend_from_slice(&data);
+        out.extend_from_slice(&[0, 0, 0, 0]);
+    }
+
+    fn write_bitmap(out: &mut Vec<u8>, bits: &[bool]) {
+        out.push(BITMAP_PLAIN);
+        write_vbyte(out, bits.len() as u64);
+        out.push(0);
+        let mut data = vec![0u8; bits.len().div_ceil(8)];
+        for (index, bit) in bits.iter().enumerate() {
+            if *bit {
+                data[index / 8] |= 1 << (index % 8);
+            }
+        }
+        out.extend_from_slice(&data);
+        out.extend_from_slice(&[0, 0, 0, 0]);
+    }
+
+    fn write_section(out: &mut Vec<u8>, strings: &[&str], block_size: usize) {
+        let mut data = Vec::new();
+        let mut blocks = Vec::new();
+        let mut previous = "";
+        for (index, string) in strings.iter().enumerate() {
+            if index % block_size == 0 {
+                blocks.push(data.len() as u64);
+                data.extend_from_slice(string.as_bytes());
+            } else {
+                let prefix_len = previous
+                    .bytes()
+                    .zip(string.bytes())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                write_vbyte(&mut data, prefix_len as u64);
+                data.extend_from_slice(&string.as_bytes()[prefix_len..]);
+            }
+            data.push(0);
+            previous = string;
+        }
+        blocks.push(data.len() as u64);
+        out.push(SECTION_PLAIN_FRONT_CODING);
+        write_vbyte(out, strings.len() as u64);
+        write_vbyte(out, data.len() as u64);
+        write_vbyte(out, block_size as u64);
+        out.push(0);
+        write_log_sequence(out, 8, &blocks);
+        out.extend_from_slice(&data);
+        out.extend_from_slice(&[0, 0, 0, 0]);
+    }
+
+    fn test_hdt() -> Vec<u8> {
+        let header = "<file:///test> <http://purl.org/HDT/hdt#triplesCount> \"4\" .\n";
+        let mut hdt = Vec::new();
+        write_control_info(&mut hdt, CONTROL_GLOBAL, "<http://purl.org/HDT/hdt#HDTv1>", "");
+        write_control_info(&mut hdt, CONTROL_HEADER, "ntriples", &format!("length={};", header.len()));
+        hdt.extend_from_slice(header.as_bytes());
+        write_control_info(&mut hdt, CONTROL_DICTIONARY, DICTIONARY_FOUR, "mapping=1;");
+        write_section(&mut hdt, &["http://example.org/b"], 16);
+        write_section(&mut hdt, &["_:b1", "http://example.org/a"], 16);
+        write_section(&mut hdt, &["http://example.org/knows", "http://example.org/name"], 16);
+        write_section(&mut hdt, &["\"Anna\"@en", "\"Anne\"", "\"42\"^^<http://www.w3.org/2001/XMLSchema#integer>"], 2);
+        write_control_info(&mut hdt, CONTROL_TRIPLES, TRIPLES_BITMAP, "order=1;");
+        // subject 1 (b): name "Anne"; subject 2 (_:b1): name 42; subject 3 (a): knows b, name "Anna"
+        write_bitmap(&mut hdt, &[true, true, false, true]);
+        write_bitmap(&mut hdt, &[true, true, true, true]);
+        write_log_sequence(&mut hdt, 2, &[2, 2, 1, 2]);
+        write_log_sequence(&mut hdt, 3, &[3, 4, 1, 2]);
+        hdt
+    }
+
+    #[test]
+    fn test_read_hdt() {
+        let parser = HdtRdfParser::for_reader(io::Cursor::new(test_hdt()));
+        let triples: Vec<Triple> = parser.map(|triple| triple.unwrap()).collect();
+        assert_eq!(4, triples.len());
+        assert_eq!(
+            "<http://example.org/b> <http://example.org/name> \"Anne\"",
+            triples[0].to_string()
+        );
+        assert_eq!(
+            "_:b1 <http://example.org/name> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer>",
+            triples[1].to_string()
+        );
+        assert_eq!(
+            "<http://example.org/a> <http://example.org/knows> <http://example.org/b>",
+            triples[2].to_string()
+        );
+        assert_eq!(
+            "<http://example.org/a> <http://example.org/name> \"Anna\"@en",
+            triples[3].to_string()
+        );
+    }
+
+    #[test]
+    fn test_read_corrupt_sizes() {
+        // the number of entries overflows the bit size
+        let mut sequence = vec![SEQUENCE_LOG, 64];
+        write_vbyte(&mut sequence, u64::MAX >> 4);
+        sequence.push(0);
+        assert!(read_log_sequence(&mut io::Cursor::new(sequence)).is_err());
+        // the size is far beyond the file length
+        let mut sequence = vec![SEQUENCE_LOG, 32];
+        write_vbyte(&mut sequence, 1 << 40);
+        sequence.extend_from_slice(&[0, 1, 2, 3]);
+        assert!(read_log_sequence(&mut io::Cursor::new(sequence)).is_err());
+        let mut bitmap = vec![BITMAP_PLAIN];
+        write_vbyte(&mut bitmap, 1 << 50);
+        bitmap.push(0);
+        assert!(read_bitmap(&mut io::Cursor::new(bitmap)).is_err());
+    }
+
+    #[test]
+    fn test_read_corrupt_vbyte() {
+        // the last byte of a number has the high bit set
+        let truncated = [0x05, 0x7f];
+        assert!(read_vbyte_slice(&truncated, &mut 0).is_err());
+        assert!(read_vbyte(&mut io::Cursor::new(truncated)).is_err());
+        // more continuation bytes than the number can hold
+        let mut overlong = vec![0x7f; 12];
+        overlong.push(0x81);
+        assert!(read_vbyte_slice(&overlong, &mut 0).is_err());
+        assert!(read_vbyte(&mut io::Cursor::new(overlong)).is_err());
+        let mut pos = 0;
+        assert_eq!(300, read_vbyte_slice(&[0x2c, 0x82], &mut pos).unwrap());
+        assert_eq!(2, pos);
+    }
+
+    #[test]
+    fn test_read_truncated_hdt() {
+        let hdt = test_hdt();
+        for len in 0..hdt.len() {
+            let parser = HdtRdfParser::for_reader(io::Cursor::new(hdt[..len].to_vec()));
+            let triples: Vec<Result<Triple, _>> = parser.collect();
+            assert!(triples.iter().any(|triple| triple.is_err()), "no error for {} bytes", len);
+        }
+    }
+
+    #[test]
+    fn test_read_no_hdt() {
+        let mut parser = HdtRdfParser::for_reader(io::Cursor::new(b"@prefix ex: <http://example.org/> .".to_vec()));
+        assert!(parser.next().unwrap().is_err());
+        assert!(parser.next().is_none());
+    }
+}
@@ -0,0 +1,147 @@
+use std::{
+    collections::HashMap,
+    error::Error,
+    io::Read,
+    sync::{Mutex, OnceLock},
+};
+
+use oxjsonld::{JsonLdLoadDocumentOptions, JsonLdParser, JsonLdRemoteDocument, ReaderJsonLdParser};
+
+// document content and final url (after redirects)
+type ContextDocument = (Vec<u8>, String);
+
+// remote contexts (e.g. schema.org) are used by many documents, so they are loaded only once per session
+static CONTEXT_CACHE: OnceLock<Mutex<HashMap<String, ContextDocument>>> = OnceLock::new();
+
+/// JSON-LD parser that loads remote contexts and resolves relative IRIs against the url of the document
+pub fn jsonld_parser<R: Read>(source: &str, reader: R) -> ReaderJsonLdParser<R> {
+    let mut parser = JsonLdParser::new();
+    if source.starts_with("http://") || source.starts_with("https://") {
+        match parser.clone().with_base_iri(source) {
+            Ok(parser_with_base) => parser = parser_with_base,
+            Err(e) => log::warn!("Can not use {} as base IRI: {}", source, e),
+        }
+    }
+    parser.for_reader(reader).with_load_document_callback(load_context)
+}
+
+fn load_context(
+    url: &str,
+    _options: &JsonLdLoadDocumentOptions,
+) -> Result<JsonLdRemoteDocument, Box<dyn Error + Send + Sync>> {
+    let cache = CONTEXT_CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(cache) = cache.lock()
+        && let Some((document, document_url)) = cache.get(url)
+    {
+        return Ok(JsonLdRemoteDocument {
+            document: document.clone(),
+            document_url: document_url.clone(),
+        });
+    }
+    let (document, document_url) = fetch_context(url)?;
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert(url.to_string(), (document.clone(), document_url.clone()));
+    }
+    Ok(JsonLdRemoteDocument { document, document_url })
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_context(url: &str) -> Result<ContextDocument, Box<dyn Error + Send + Sync>> {
+    use reqwest::blocking::Client;
+
+    if let Some(path) = url.strip_prefix("file://") {
+        return Ok((std::fs::read(path)?, url.to_string()));
+    }
+    log::info!("Loading JSON-LD context {}", url);
+    let client = Client::new();
+    let response = client
+        .get(url)
+        .header("Accept", "application/ld+json, application/json;q=0.9")
+        .send()?;
+    if !response.status().is_success() {
+        return Err(format!("Failed to load JSON-LD context {}: HTTP {}", url, response.status()).into());
+    }
+    let document_url = response.url().to_string();
+    let is_json = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("json"));
+    if !is_json {
+        // servers like schema.org answer with html and point to the context with a link header
+        let alternate = response
+            .headers()
+            .get(reqwest::header::LINK)
+            .and_then(|value| value.to_str().ok())
+            .and_then(alternate_link)
+            .map(|link| response.url().join(link));
+        if let Some(Ok(alternate_url)) = alternate
+            && alternate_url.as_str() != url
+        {
+            return fetch_context(alternate_url.as_str());
+        }
+    }
+    Ok((response.bytes()?.to_vec(), document_url))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn fetch_context(url: &str) -> Result<ContextDocument, Box<dyn Error + Send + Sync>> {
+    Err(format!(
+        "Remote JSON-LD context {} can not be loaded in the web version, embed the context in the document",
+        url
+    )
+    .into())
+}
+
+/// Target of the link with rel="alternate" and type="application/ld+json"
+pub fn alternate_link(link_header: &str) -> Option<&str> {
+    link_header.split(',').find_map(|link| {
+        let mut parts = link.split(';').map(str::trim);
+        let target = parts.next()?.strip_prefix('<')?.strip_suffix('>')?;
+        let mut alternate = false;
+        let mut json_ld = false;
+        for param in parts {
+            let (name, value) = param.split_once('=')?;
+            let value = value.trim().trim_matches('"');
+            match name.trim() {
+                "rel" => alternate = value.split_whitespace().any(|rel| rel == "alternate"),
+                "type" => json_ld = value == "application/ld+json",
+                _ => {}
+            }
+        }
+        if alternate && json_ld { Some(target) } else { None }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_alternate_link() {
+        assert_eq!(
+            Some("/docs/jsonldcontext.jsonld"),
+            alternate_link(r#"</docs/jsonldcontext.jsonld>; rel="alternate"; type="application/ld+json""#)
+        );
+        assert_eq!(
+            Some("context.jsonld"),
+            alternate_link(r#"<style.css>; rel="stylesheet", <context.jsonld>; rel="alternate"; type="application/ld+json""#)
+        );
+        assert_eq!(None, alternate_link(r#"<data.ttl>; rel="alternate"; type="text/turtle""#));
+    }
+
+    #[test]
+    fn test_relative_iris() {
+        let document = r#"{
+            "@context": {"schema": "http://schema.org/", "knows": {"@id": "schema:knows", "@type": "@id"}},
+            "@id": "person/1",
+            "@type": "schema:Person",
+            "knows": "person/2"
+        }"#;
+        let parser = jsonld_parser("http://example.org/data/people.jsonld", document.as_bytes());
+        let quads: Vec<_> = parser.collect::<Result<_, _>>().unwrap();
+        assert_eq!(2, quads.len());
+        assert_eq!("<http://example.org/data/person/1>", quads[0].subject.to_string());
+        assert!(quads.iter().any(|quad| quad.object.to_string() == "<http://example.org/data/person/2>"));
+    }
+}
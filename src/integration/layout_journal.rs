@@ -0,0 +1,538 @@
+use std::{
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use egui::{Pos2, Vec2};
+use indexmap::IndexMap;
+use string_interner::Symbol;
+
+use crate::{
+    IriIndex,
+    domain::NodeData,
+    support::SortedVec,
+    uistate::layout::{EdgeMemo, IndividualNodeStyleData, LayoutData, NodeCommand, NodeLayout, NodeMemo, NodePosition, NodeShapeData, SortedNodeLayout},
+    ui::graph_view::{NeighborPos, update_layout_edges},
+};
+
+// it is just ascii "rdgj"
+const JOURNAL_MAGIC: u32 = 0x6A676472;
+
+const RECORD_SOURCE: u8 = 1;
+const RECORD_POSITIONS: u8 = 2;
+const RECORD_REMOVED: u8 = 3;
+const RECORD_UNDO: u8 = 4;
+
+const COMMAND_ADD: u8 = 0;
+const COMMAND_REMOVE: u8 = 1;
+
+/// Minimal time between two journal writes
+pub const JOURNAL_INTERVAL: Duration = Duration::from_secs(3);
+// moved nodes are appended again and again, so the journal is rewritten as one snapshot if it grows too much
+const MAX_JOURNAL_SIZE: u64 = 8 * 1024 * 1024;
+// small moves (e.g. rounding of the layout) are not worth a journal entry
+const MIN_MOVE_DISTANCE: f32 = 0.5;
+
+/**
+ * Append-only journal of the visual graph layout (node positions and undo stack).
+ * It is written in short intervals during the layout work so a hand-tuned arrangement survives a crash.
+ * Nodes are stored by IRI, because the node indexes depend on the loading order of the data.
+ * Each record has its length as prefix, so a record truncated by crash is ignored on reading.
+ */
+pub struct LayoutJournal {
+    path: PathBuf,
+    writer: Option<BufWriter<File>>,
+    sources: Vec<String>,
+    journaled: HashMap<IriIndex, Pos2>,
+    undo_len: usize,
+    last_write: Instant,
+}
+
+/// Content of the journal with the nodes given by IRIs
+#[derive(Default)]
+pub struct JournalContent {
+    pub sources: Vec<String>,
+    pub positions: IndexMap<String, Pos2>,
+    pub undo_stack: Vec<JournalCommand>,
+}
+
+pub enum JournalCommand {
+    AddElements(Vec<String>),
+    // removed nodes (iri, position, hidden references) and removed edges (from iri, to iri, predicate)
+    RemoveElements(Vec<(String, Pos2, u32)>, Vec<(String, String, String)>),
+}
+
+impl LayoutJournal {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            writer: None,
+            sources: Vec::new(),
+            journaled: HashMap::new(),
+            undo_len: 0,
+            last_write: Instant::now(),
+        }
+    }
+
+    /// The journal is stored beside the persisted application settings
+    pub fn default_path() -> Option<PathBuf> {
+        eframe::storage_dir("rdf-glance").map(|dir| dir.join("layout.journal"))
+    }
+
+    /// Removes the journal, called if the layout is not needed anymore (new data, exit)
+    pub fn reset(&mut self) {
+        self.sources.clear();
+        self.journaled.clear();
+        self.undo_len = 0;
+        self.remove_file();
+    }
+
+    /// The layout is stored in the project, so only later changes need to be journaled
+    pub fn saved(&mut self, project: &str, visible_nodes: &SortedNodeLayout) {
+        self.sources = vec![project.to_string()];
+        self.journaled.clear();
+        if let Ok(layout) = visible_nodes.layout_data.read() {
+            for (node, position) in layout.nodes.iter().zip(layout.positions.iter()) {
+                self.journaled.insert(node.node_index, position.pos);
+            }
+        }
+        self.undo_len = visible_nodes.undo_stack.len();
+        self.remove_file();
+    }
+
+    // the next write creates the journal with a complete snapshot
+    fn remove_file(&mut self) {
+        self.writer = None;
+        if self.path.exists()
+            && let Err(e) = fs::remove_file(&self.path)
+        {
+            log::warn!("Can not remove layout journal {}: {}", self.path.display(), e);
+        }
+    }
+
+    pub fn discard(&mut self) {
+        self.remove_file();
+    }
+
+    /// Remembers the loaded file, url or project so the data can be loaded again before restoring the layout
+    pub fn add_source(&mut self, source: &str) {
+        if self.sources.iter().any(|known| known == source) {
+            return;
+        }
+        self.sources.push(source.to_string());
+        if self.writer.is_some() {
+            let mut payload = Vec::new();
+            let result = write_string(&mut payload, source).and_then(|_| self.append(RECORD_SOURCE, &payload));
+            if let Err(e) = result {
+                log::warn!("Can not write layout journal: {}", e);
+            }
+        }
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.last_write.elapsed() >= JOURNAL_INTERVAL
+    }
+
+    /// Appends the positions changed since the last call and the undo stack if the node set has changed.
+    /// Returns true if something was written.
+    pub fn write_changes(&mut self, visible_nodes: &SortedNodeLayout, node_data: &NodeData) -> Result<bool> {
+        self.last_write = Instant::now();
+        let Ok(layout) = visible_nodes.layout_data.read() else {
+            return Ok(false);
+        };
+        let LayoutData { nodes, positions, .. } = &*layout;
+        let mut moved: Vec<(IriIndex, Pos2)> = Vec::new();
+        for (node, position) in nodes.iter().zip(positions.iter()) {
+            let is_moved = self
+                .journaled
+                .get(&node.node_index)
+                .is_none_or(|journaled| journaled.distance(position.pos) > MIN_MOVE_DISTANCE);
+            if is_moved {
+                moved.push((node.node_index, position.pos));
+            }
+        }
+        let removed: Vec<IriIndex> = self
+            .journaled
+            .keys()
+            .filter(|node_index| nodes.binary_search_by(|node| node.node_index.cmp(node_index)).is_err())
+            .cloned()
+            .collect();
+        let node_set_changed = !removed.is_empty() || moved.iter().any(|(index, _)| !self.journaled.contains_key(index));
+        let undo_changed = node_set_changed || self.undo_len != visible_nodes.undo_stack.len();
+        if moved.is_empty() && removed.is_empty() && !undo_changed {
+            return Ok(false);
+        }
+        let compact = self.writer.is_none()
+            || fs::metadata(&self.path).map(|metadata| metadata.len()).unwrap_or(0) > MAX_JOURNAL_SIZE;
+        if compact {
+            self.create()?;
+            self.journaled.clear();
+            moved = nodes.iter().zip(positions.iter()).map(|(node, position)| (node.node_index, position.pos)).collect();
+        } else if !removed.is_empty() {
+            let mut payload = Vec::new();
+            leb128::write::unsigned(&mut payload, removed.len() as u64)?;
+            for node_index in removed.iter() {
+                write_string(&mut payload, node_iri(node_data, *node_index))?;
+                self.journaled.remove(node_index);
+            }
+            self.append(RECORD_REMOVED, &payload)?;
+        }
+        if !moved.is_empty() {
+            let mut payload = Vec::new();
+            leb128::write::unsigned(&mut payload, moved.len() as u64)?;
+            for (node_index, pos) in moved.iter() {
+                write_string(&mut payload, node_iri(node_data, *node_index))?;
+                payload.write_f32::<LittleEndian>(pos.x)?;
+                payload.write_f32::<LittleEndian>(pos.y)?;
+                self.journaled.insert(*node_index, *pos);
+            }
+            self.append(RECORD_POSITIONS, &payload)?;
+        }
+        if undo_changed || compact {
+            let current: Vec<IriIndex> = nodes.iter().map(|node| node.node_index).collect();
+            let mut payload = Vec::new();
+            write_undo_stack(&mut payload, &visible_nodes.undo_stack, &current, node_data)?;
+            self.append(RECORD_UNDO, &payload)?;
+            self.undo_len = visible_nodes.undo_stack.len();
+        }
+        if let Some(writer) = &mut self.writer {
+            writer.flush()?;
+            writer.get_ref().sync_data()?;
+        }
+        Ok(true)
+    }
+
+    fn create(&mut self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).write(true).truncate(true).open(&self.path)?;
+        let mut writer = BufWriter::new(file);
+        writer.write_u32::<LittleEndian>(JOURNAL_MAGIC)?;
+        self.writer = Some(writer);
+        for source in self.sources.clone() {
+            let mut payload = Vec::new();
+            write_string(&mut payload, &source)?;
+            self.append(RECORD_SOURCE, &payload)?;
+        }
+        Ok(())
+    }
+
+    fn append(&mut self, record_type: u8, payload: &[u8]) -> std::io::Result<()> {
+        if let Some(writer) = &mut self.writer {
+            writer.write_u8(record_type)?;
+            leb128::write::unsigned(writer, payload.len() as u64)?;
+            writer.write_all(payload)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the journal left by a previous session, None if there is no journal with positions
+    pub fn read(path: &Path) -> Result<Option<JournalContent>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut reader = BufReader::new(File::open(path)?);
+        if reader.read_u32::<LittleEndian>()? != JOURNAL_MAGIC {
+            return Err(anyhow::anyhow!("This is not a layout journal"));
+        }
+        let mut content = JournalContent::default();
+        while let Ok(record_type) = reader.read_u8() {
+            let Ok(len) = leb128::read::unsigned(&mut reader) else {
+                break;
+            };
+            let mut payload = vec![0u8; len as usize];
+            if reader.read_exact(&mut payload).is_err() {
+                log::warn!("Layout journal ends with incomplete record");
+                break;
+            }
+            let mut payload = payload.as_slice();
+            match record_type {
+                RECORD_SOURCE => content.sources.push(read_string(&mut payload)?),
+                RECORD_POSITIONS => {
+                    let count = leb128::read::unsigned(&mut payload)?;
+                    for _ in 0..count {
+                        let iri = read_string(&mut payload)?;
+                        let x = payload.read_f32::<LittleEndian>()?;
+                        let y = payload.read_f32::<LittleEndian>()?;
+                        content.positions.insert(iri, Pos2::new(x, y));
+                    }
+                }
+                RECORD_REMOVED => {
+                    let count = leb128::read::unsigned(&mut payload)?;
+                    for _ in 0..count {
+                        content.positions.shift_remove(&read_string(&mut payload)?);
+                    }
+                }
+                RECORD_UNDO => content.undo_stack = read_undo_stack(&mut payload)?,
+                _ => log::warn!("Unknown layout journal record {}", record_type),
+            }
+        }
+        if content.positions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(content))
+        }
+    }
+}
+
+/// Rebuilds the visual graph from the journal, nodes not found in the data are skipped.
+/// Returns the number of restored nodes.
+pub fn restore_layout(
+    content: &JournalContent,
+    visible_nodes: &mut SortedNodeLayout,
+    node_data: &NodeData,
+    hidden_predicates: &SortedVec,
+) -> usize {
+    let mut restored: Vec<(IriIndex, Pos2)> = content
+        .positions
+        .iter()
+        .filter_map(|(iri, pos)| node_data.get_node_index(iri).map(|node_index| (node_index, *pos)))
+        .collect();
+    restored.sort_by_key(|(node_index, _)| *node_index);
+    visible_nodes.clean_all();
+    visible_nodes.mut_nodes(|nodes, positions, _edges, node_shapes, individual_node_styles| {
+        for (node_index, pos) in restored.iter() {
+            nodes.push(NodeLayout::new(*node_index));
+            positions.push(NodePosition {
+                pos: *pos,
+                vel: Vec2::ZERO,
+                locked: false,
+            });
+            node_shapes.push(NodeShapeData::default());
+            individual_node_styles.push(IndividualNodeStyleData::default());
+        }
+    });
+    let mut npos = NeighborPos::new();
+    for (node_index, _) in restored.iter() {
+        npos.insert(*node_index, *node_index);
+    }
+    update_layout_edges(&npos, visible_nodes, node_data, hidden_predicates);
+    let current: Vec<IriIndex> = restored.iter().map(|(node_index, _)| *node_index).collect();
+    visible_nodes.undo_stack = resolve_undo_stack(&content.undo_stack, &current, node_data);
+    visible_nodes.update_node_shapes = true;
+    visible_nodes.compute_layout = false;
+    restored.len()
+}
+
+fn node_iri(node_data: &NodeData, node_index: IriIndex) -> &str {
+    node_data.get_node_by_index(node_index).map(|(iri, _)| iri.as_ref()).unwrap_or("")
+}
+
+fn write_string<W: Write>(writer: &mut W, value: &str) -> std::io::Result<()> {
+    leb128::write::unsigned(writer, value.len() as u64)?;
+    writer.write_all(value.as_bytes())
+}
+
+fn read_string<R: Read>(reader: &mut R) -> Result<String> {
+    let len = leb128::read::unsigned(reader)?;
+    let mut buf = vec![0u8; len as usize];
+    reader.read_exact(&mut buf)?;
+    Ok(String::from_utf8(buf)?)
+}
+
+/**
+ * The edges of removed nodes are stored as positions in the node list that exists after the undo.
+ * This node list is reconstructed by applying the commands from the top of the stack to the current nodes.
+ */
+fn write_undo_stack<W: Write>(
+    writer: &mut W,
+    undo_stack: &[NodeCommand],
+    current: &[IriIndex],
+    node_data: &NodeData,
+) -> std::io::Result<()> {
+    let mut node_set: Vec<IriIndex> = current.to_vec();
+    let mut commands: Vec<Vec<u8>> = Vec::with_capacity(undo_stack.len());
+    for command in undo_stack.iter().rev() {
+        let mut payload = Vec::new();
+        match command {
+            NodeCommand::AddElements(added) => {
+                payload.write_u8(COMMAND_ADD)?;
+                leb128::write::unsigned(&mut payload, added.len() as u64)?;
+                for node_index in added.iter() {
+                    write_string(&mut payload, node_iri(node_data, *node_index))?;
+                }
+                node_set.retain(|node_index| !added.contains(node_index));
+            }
+            NodeCommand::RemoveElements(removed_nodes, removed_edges) => {
+                payload.write_u8(COMMAND_REMOVE)?;
+                leb128::write::unsigned(&mut payload, removed_nodes.len() as u64)?;
+                for node in removed_nodes.iter() {
+                    write_string(&mut payload, node_iri(node_data, node.index))?;
+                    payload.write_f32::<LittleEndian>(node.position.x)?;
+                    payload.write_f32::<LittleEndian>(node.position.y)?;
+                    leb128::write::unsigned(&mut payload, node.hidden_references as u64)?;
+                }
+                node_set.extend(removed_nodes.iter().map(|node| node.index));
+                node_set.sort_unstable();
+                node_set.dedup();
+                let edge_node = |pos: usize| node_set.get(pos).map(|node_index| node_iri(node_data, *node_index)).unwrap_or("");
+                leb128::write::unsigned(&mut payload, removed_edges.len() as u64)?;
+                for edge in removed_edges.iter() {
+                    write_string(&mut payload, edge_node(edge.from))?;
+                    write_string(&mut payload, edge_node(edge.to))?;
+                    write_string(&mut payload, node_data.get_predicate(edge.predicate).unwrap_or(""))?;
+                }
+            }
+        }
+        commands.push(payload);
+    }
+    // stored from the bottom of the stack
+    leb128::write::unsigned(writer, commands.len() as u64)?;
+    for payload in commands.iter().rev() {
+        writer.write_all(payload)?;
+    }
+    Ok(())
+}
+
+fn read_undo_stack<R: Read>(reader: &mut R) -> Result<Vec<JournalCommand>> {
+    let count = leb128::read::unsigned(reader)?;
+    let mut undo_stack = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        match reader.read_u8()? {
+            COMMAND_ADD => {
+                let len = leb128::read::unsigned(reader)?;
+                let mut added = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    added.push(read_string(reader)?);
+                }
+                undo_stack.push(JournalCommand::AddElements(added));
+            }
+            COMMAND_REMOVE => {
+                let len = leb128::read::unsigned(reader)?;
+                let mut removed_nodes = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    let iri = read_string(reader)?;
+                    let x = reader.read_f32::<LittleEndian>()?;
+                    let y = reader.read_f32::<LittleEndian>()?;
+                    let hidden_references = leb128::read::unsigned(reader)? as u32;
+                    removed_nodes.push((iri, Pos2::new(x, y), hidden_references));
+                }
+                let len = leb128::read::unsigned(reader)?;
+                let mut removed_edges = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    removed_edges.push((read_string(reader)?, read_string(reader)?, read_string(reader)?));
+                }
+                undo_stack.push(JournalCommand::RemoveElements(removed_nodes, removed_edges));
+            }
+            command => return Err(anyhow::anyhow!("Unknown undo command {} in layout journal", command)),
+        }
+    }
+    Ok(undo_stack)
+}
+
+fn resolve_undo_stack(undo_stack: &[JournalCommand], current: &[IriIndex], node_data: &NodeData) -> Vec<NodeCommand> {
+    let mut node_set: Vec<IriIndex> = current.to_vec();
+    let mut commands: Vec<NodeCommand> = Vec::with_capacity(undo_stack.len());
+    for command in undo_stack.iter().rev() {
+        match command {
+            JournalCommand::AddElements(added) => {
+                let added: Vec<IriIndex> = added.iter().filter_map(|iri| node_data.get_node_index(iri)).collect();
+                node_set.retain(|node_index| !added.contains(node_index));
+                commands.push(NodeCommand::AddElements(added));
+            }
+            JournalCommand::RemoveElements(removed_nodes, removed_edges) => {
+                let mut nodes: Vec<NodeMemo> = removed_nodes
+                    .iter()
+                    .filter_map(|(iri, position, hidden_references)| {
+                        node_data.get_node_index(iri).map(|index| NodeMemo {
+                            index,
+                            position: *position,
+                            hidden_references: *hidden_references,
+                        })
+                    })
+                    .collect();
+                nodes.sort_by_key(|node| node.index);
+                node_set.extend(nodes.iter().map(|node| node.index));
+                node_set.sort_unstable();
+                node_set.dedup();
+                let edge_pos = |iri: &str| {
+                    node_data
+                        .get_node_index(iri)
+                        .and_then(|node_index| node_set.binary_search(&node_index).ok())
+                };
+                let edges: Vec<EdgeMemo> = removed_edges
+                    .iter()
+                    .filter_map(|(from, to, predicate)| {
+                        let predicate = node_data.indexers.predicate_indexer.map.get(predicate.as_str())?;
+                        Some(EdgeMemo {
+                            from: edge_pos(from)?,
+                            to: edge_pos(to)?,
+                            predicate: predicate.to_usize() as IriIndex,
+                        })
+                    })
+                    .collect();
+                commands.push(NodeCommand::RemoveElements(nodes, edges));
+            }
+        }
+    }
+    commands.reverse();
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::RdfData;
+
+    #[test]
+    fn test_layout_journal() -> Result<()> {
+        let rdf_data = RdfData::from_patch(
+            "A <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n\
+             A <http://example.org/b> <http://example.org/knows> <http://example.org/c> .\n",
+        );
+        let node_data = &rdf_data.node_data;
+        let index = |iri: &str| node_data.get_node_index(iri).unwrap();
+        let hidden_predicates = SortedVec::new();
+
+        let mut visible_nodes = SortedNodeLayout::new();
+        for iri in ["http://example.org/a", "http://example.org/b", "http://example.org/c"] {
+            visible_nodes.add_by_index(index(iri));
+        }
+        let mut npos = NeighborPos::new();
+        for iri in ["http://example.org/a", "http://example.org/b", "http://example.org/c"] {
+            npos.insert(index(iri), index(iri));
+        }
+        update_layout_edges(&npos, &mut visible_nodes, node_data, &hidden_predicates);
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            layout.positions[0].pos = Pos2::new(10.0, 20.0);
+        }
+        let path = std::env::temp_dir().join(format!("rdfglance-test-{}.journal", std::process::id()));
+        let mut journal = LayoutJournal::new(path.clone());
+        journal.add_source("data.ttl");
+        assert!(journal.write_changes(&visible_nodes, node_data)?);
+        assert!(!journal.write_changes(&visible_nodes, node_data)?);
+
+        visible_nodes.remove(index("http://example.org/c"), &hidden_predicates);
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            layout.positions[1].pos = Pos2::new(-5.0, 7.0);
+        }
+        assert!(journal.write_changes(&visible_nodes, node_data)?);
+
+        let content = LayoutJournal::read(&path)?.unwrap();
+        assert_eq!(vec!["data.ttl".to_string()], content.sources);
+        assert_eq!(2, content.positions.len());
+        assert_eq!(4, content.undo_stack.len());
+
+        let mut restored_nodes = SortedNodeLayout::new();
+        assert_eq!(2, restore_layout(&content, &mut restored_nodes, node_data, &hidden_predicates));
+        assert_eq!(1, restored_nodes.layout_data.read().unwrap().edges.len());
+        assert_eq!(
+            Pos2::new(-5.0, 7.0),
+            restored_nodes.layout_data.read().unwrap().positions[1].pos
+        );
+        // undo of the removal restores the node c with its edge
+        restored_nodes.undo(&crate::domain::config::Config::default(), &hidden_predicates);
+        assert_eq!(3, restored_nodes.layout_data.read().unwrap().nodes.len());
+        assert_eq!(2, restored_nodes.layout_data.read().unwrap().edges.len());
+
+        journal.reset();
+        assert!(LayoutJournal::read(&path)?.is_none());
+        Ok(())
+    }
+}
@@ -1,11 +1,25 @@
 pub mod persistency;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod sparql;
+pub mod rdf_patch;
 pub mod rdfwrap;
+pub mod shacl;
+pub mod remote;
+pub mod stress_data;
+pub mod png;
 pub mod svg;
+pub mod turtle;
 pub mod visual_query;
 pub mod csv2rdf;
+pub mod deep_link;
+pub mod graphml;
+pub mod hdt2rdf;
+pub mod jsonld;
 pub mod json2rdf;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod layout_journal;
 pub mod xml2rdf;
+pub mod bundle;
+pub mod csv_mapping;
 
 pub use self::persistency::*;
\ No newline at end of file
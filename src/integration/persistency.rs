@@ -1,6 +1,6 @@
 use anyhow::{Error, Result};
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use egui::{Pos2, Vec2};
+use egui::{Pos2, Rect, Vec2};
 use flate2::Compression;
 use flate2::read::ZlibDecoder;
 use flate2::write::ZlibEncoder;
@@ -12,12 +12,19 @@ use std::path::Path;
 use std::sync::{Arc, RwLock};
 
 use crate::domain::graph_styles::{
-    ArrowLocation, ArrowStyle, EdgeFont, IconStyle, LabelPosition, LineStyle, NodeShape, NodeSize, GVisualizationStyle
+    ArrowLocation, ArrowStyle, EdgeFont, EdgeLayoutForce, IconImage, IconStyle, LabelPosition, LineStyle, NodeShape, NodeSize, GVisualizationStyle
 };
-use crate::uistate::UIState;
-use crate::uistate::layout::{Edge, IndividualNodeStyleData, NodeLayout, NodePosition, NodeShapeData, SortedNodeLayout, update_edges_groups};
+use crate::domain::config::{ConfigOverrides, IriDisplay};
+use crate::domain::style_rules::{RuleOperator, StyleRule, StyleRules};
+use crate::domain::size_mapping::SizeMapping;
+use crate::uistate::{GraphState, UIState};
+use crate::uistate::project_template::{ProjectTemplate, TypeSeed};
+use crate::uistate::layout::{Edge, IndividualNodeStyleData, LayoutData, NodeLayout, NodePosition, NodeShapeData, SortedNodeLayout, update_edges_groups};
 use crate::domain::{DataTypeIndex, IriIndex, LangIndex, Literal, NObject, NodeCache, PredicateLiteral};
+use crate::domain::named_graphs::{NamedGraphs, TripleKey};
 use crate::domain::prefix_manager::PrefixManager;
+use crate::domain::type_index::{ColumnAbbreviation, ColumnDesc, DEFAULT_TRUNCATE_CHARS, TableLayout, TypeColumnLayout};
+use crate::domain::recent_items::Thumbnail;
 use crate::domain::string_indexer::{IndexSpan, StringCache, StringIndexer};
 use crate::{EdgeStyle, RdfGlanceApp, support::SortedVec};
 
@@ -25,6 +32,8 @@ use crate::{EdgeStyle, RdfGlanceApp, support::SortedVec};
 const MAGIC_NUMBER: u32 = 0x47464452;
 const FORMAT_VERSION: u16 = 0;
 const FORMAT_FLAGS: u16 = 0;
+// Project templates use the same block format but contain no data
+const FORMAT_FLAG_TEMPLATE: u16 = 0x01;
 
 const BLOCK_PRELUDE_SIZE: u32 = 5;
 
@@ -41,6 +50,12 @@ pub enum HeaderType {
     Literals = 9,
     ShortLiterals = 10,
     UIState = 11,
+    TemplateSeeds = 12,
+    ConfigOverrides = 13,
+    Thumbnail = 14,
+    TableLayout = 15,
+    GraphView = 16,
+    NamedGraphs = 17,
 }
 
 impl HeaderType {
@@ -57,6 +72,12 @@ impl HeaderType {
             9 => Some(HeaderType::Literals),
             10 => Some(HeaderType::ShortLiterals),
             11 => Some(HeaderType::UIState),
+            12 => Some(HeaderType::TemplateSeeds),
+            13 => Some(HeaderType::ConfigOverrides),
+            14 => Some(HeaderType::Thumbnail),
+            15 => Some(HeaderType::TableLayout),
+            16 => Some(HeaderType::GraphView),
+            17 => Some(HeaderType::NamedGraphs),
             _ => None,
         }
     }
@@ -123,11 +144,16 @@ impl RdfGlanceApp {
                 .store(HeaderType::ShortLiterals, &mut file)?;
             rdf_data.node_data.indexers.literal_cache.store(&mut file)?;
             rdf_data.node_data.node_cache.store(&mut file)?;
+            rdf_data.node_data.named_graphs.store(&mut file)?;
             rdf_data.prefix_manager.store(&mut file)?;
         }
         self.visible_nodes.store(&mut file)?;
         self.visualization_style.store(&mut file)?;
         self.ui_state.store(&mut file)?;
+        self.project_config.store(&mut file)?;
+        self.graph_thumbnail().store(&mut file)?;
+        self.type_index.table_layout().store(&mut file)?;
+        self.graph_state.store(&mut file)?;
 
         // Is some cases flush will take a long time, probably if os is trying to sync the file to disk 
         // and make virus check. But all data are written to file, because buffer drop make also the flush
@@ -148,24 +174,30 @@ impl RdfGlanceApp {
             ));
         }
         let _version = reader.read_u16::<LittleEndian>()?;
-        let _flags = reader.read_u16::<LittleEndian>()?;
+        let flags = reader.read_u16::<LittleEndian>()?;
+        if flags & FORMAT_FLAG_TEMPLATE != 0 {
+            return Err(anyhow::anyhow!(
+                "This is a project template. Use Apply Project Template",
+            ));
+        }
         let header_length = reader.read_u16::<LittleEndian>()?;
-        println!("header lenght {}", header_length);
+        log::debug!("header length {}", header_length);
         reader.seek(SeekFrom::Start(header_length as u64))?;
 
         loop {
             match reader.read_u8() {
                 Ok(header_type_u8) => {
-                    println!("reading header type {}", header_type_u8);
+                    log::debug!("reading header type {}", header_type_u8);
                     let header_type = HeaderType::from_u8(header_type_u8);
                     let block_size = reader.read_u32::<LittleEndian>()?;
-                    println!("block size {}", block_size);
+                    log::debug!("block size {}", block_size);
                     if let Some(header_type) = header_type {
                         match header_type {
                             HeaderType::DataTypes => {
                                 app.mut_rdf_data(|rdf_data| {
                                     rdf_data.node_data.indexers.datatype_indexer =
                                         StringIndexer::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                                    rdf_data.node_data.indexers.rebuild_value_types();
                                     Ok::<(), Error>(())
                                 })
                                 .unwrap()?;
@@ -238,20 +270,229 @@ impl RdfGlanceApp {
                                 })
                                 .unwrap()?;
                             }
+                            HeaderType::TemplateSeeds | HeaderType::Thumbnail => {
+                                reader.seek(SeekFrom::Current((block_size - BLOCK_PRELUDE_SIZE) as i64))?;
+                            }
+                            HeaderType::ConfigOverrides => {
+                                app.project_config =
+                                    ConfigOverrides::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                            }
+                            HeaderType::TableLayout => {
+                                // the type index is built from the data, so the layout is applied after loading
+                                app.pending_table_layout =
+                                    Some(TableLayout::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?);
+                            }
+                            HeaderType::GraphView => {
+                                app.graph_state = GraphState::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                            }
+                            HeaderType::NamedGraphs => {
+                                app.mut_rdf_data(|rdf_data| {
+                                    rdf_data.node_data.named_graphs =
+                                        NamedGraphs::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                                    Ok::<(), Error>(())
+                                })
+                                .unwrap()?;
+                            }
                         }
                     } else {
-                        println!("unknown header type {} ignoring block", header_type_u8);
+                        log::warn!("unknown header type {} ignoring block", header_type_u8);
                         reader.seek(SeekFrom::Current((block_size - 5) as i64))?;
                     }
                 }
                 Err(_) => {
-                    println!("End of file");
+                    log::debug!("End of file");
                     break;
                 }
             }
         }
         Ok(app)
     }
+    /**
+     * Store only the parts of the project that are independent of the data:
+     * prefixes, styles, hidden predicates and the types and nodes that were visible in graph.
+     * The indexes are stored together with the type and predicate indexers so they can be mapped
+     * to the indexes of other data
+     */
+    pub fn store_template(&self, path: &Path) -> std::io::Result<()> {
+        let mut file = BufWriter::new(File::create(path)?);
+
+        file.write_u32::<LittleEndian>(MAGIC_NUMBER)?;
+        file.write_u16::<LittleEndian>(FORMAT_VERSION)?;
+        file.write_u16::<LittleEndian>(FORMAT_FLAGS | FORMAT_FLAG_TEMPLATE)?;
+        // header size
+        file.write_u16::<LittleEndian>(10)?;
+
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            rdf_data
+                .node_data
+                .indexers
+                .predicate_indexer
+                .store(HeaderType::Predicates, &mut file)?;
+            rdf_data
+                .node_data
+                .indexers
+                .type_indexer
+                .store(HeaderType::Types, &mut file)?;
+            rdf_data.prefix_manager.store(&mut file)?;
+            let mut type_seeds: HashMap<IriIndex, u32> = HashMap::new();
+            if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                let nodes = &layout.nodes;
+                for node_layout in nodes.iter() {
+                    if let Some((_, node)) = rdf_data.node_data.get_node_by_index(node_layout.node_index) {
+                        for type_index in node.types.iter() {
+                            *type_seeds.entry(*type_index).or_insert(0) += 1;
+                        }
+                    }
+                }
+            }
+            let mut type_seeds: Vec<TypeSeed> = type_seeds
+                .into_iter()
+                .map(|(type_index, count)| TypeSeed { type_index, count })
+                .collect();
+            type_seeds.sort_by_key(|seed| seed.type_index);
+            let seed_nodes: Vec<Box<str>> = self
+                .ui_state
+                .selected_nodes
+                .iter()
+                .filter_map(|node_index| rdf_data.node_data.get_node_by_index(*node_index))
+                .map(|(iri, _)| iri.clone())
+                .collect();
+            let starting_query = self.sparql_query.as_ref().map(|sparql_query| sparql_query.query());
+            store_template_seeds(
+                &mut file,
+                &self.ui_state.hidden_predicates,
+                &type_seeds,
+                &seed_nodes,
+                starting_query,
+            )?;
+        }
+        self.visualization_style.store(&mut file)?;
+        Ok(())
+    }
+}
+
+fn store_template_seeds(
+    file: &mut BufWriter<File>,
+    hidden_predicates: &SortedVec,
+    type_seeds: &[TypeSeed],
+    seed_nodes: &[Box<str>],
+    starting_query: Option<&str>,
+) -> std::io::Result<()> {
+    with_header_len(file, HeaderType::TemplateSeeds, &|file| {
+        leb128::write::unsigned(file, hidden_predicates.data.len() as u64)?;
+        for predicate in hidden_predicates.data.iter() {
+            leb128::write::unsigned(file, *predicate as u64)?;
+        }
+        leb128::write::unsigned(file, type_seeds.len() as u64)?;
+        for seed in type_seeds.iter() {
+            leb128::write::unsigned(file, seed.type_index as u64)?;
+            leb128::write::unsigned(file, seed.count as u64)?;
+        }
+        leb128::write::unsigned(file, seed_nodes.len() as u64)?;
+        for iri in seed_nodes.iter() {
+            write_len_string(iri, file)?;
+        }
+        // number of optional fields
+        leb128::write::unsigned(file, starting_query.is_some() as u64)?;
+        if let Some(query) = starting_query {
+            write_var_field(file, 1, &|file| write_len_string(query, file))?;
+        }
+        Ok(())
+    })
+}
+
+impl ProjectTemplate {
+    pub fn restore(path: &Path) -> Result<Self> {
+        let mut template = ProjectTemplate::default();
+        let file = File::open(path)?;
+        let mut reader = BufReader::new(&file);
+        let magic_number = reader.read_u32::<LittleEndian>()?;
+        let _version = reader.read_u16::<LittleEndian>()?;
+        let flags = reader.read_u16::<LittleEndian>()?;
+        if magic_number != MAGIC_NUMBER || flags & FORMAT_FLAG_TEMPLATE == 0 {
+            return Err(anyhow::anyhow!("This seems not to be RDF Glance project template"));
+        }
+        let header_length = reader.read_u16::<LittleEndian>()?;
+        reader.seek(SeekFrom::Start(header_length as u64))?;
+
+        while let Ok(header_type_u8) = reader.read_u8() {
+            let block_size = reader.read_u32::<LittleEndian>()?;
+            match HeaderType::from_u8(header_type_u8) {
+                Some(HeaderType::Predicates) => {
+                    template.predicate_indexer = StringIndexer::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                }
+                Some(HeaderType::Types) => {
+                    template.type_indexer = StringIndexer::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                }
+                Some(HeaderType::Prefixes) => {
+                    template.prefix_manager = PrefixManager::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                }
+                Some(HeaderType::VisualStyles) => {
+                    let styles = GVisualizationStyle::restore(&mut reader, block_size - BLOCK_PRELUDE_SIZE)?;
+                    template.node_styles = styles.node_styles;
+                    template.edge_styles = styles.edge_styles;
+                }
+                Some(HeaderType::TemplateSeeds) => {
+                    template.restore_seeds(&mut reader)?;
+                }
+                _ => {
+                    reader.seek(SeekFrom::Current((block_size - BLOCK_PRELUDE_SIZE) as i64))?;
+                }
+            }
+        }
+        Ok(template)
+    }
+
+    fn restore_seeds(&mut self, reader: &mut BufReader<&File>) -> Result<()> {
+        let hidden_len = leb128::read::unsigned(reader)?;
+        for _ in 0..hidden_len {
+            let predicate_index = leb128::read::unsigned(reader)? as IriIndex;
+            self.hidden_predicates.add(predicate_index);
+        }
+        let type_seeds_len = leb128::read::unsigned(reader)?;
+        for _ in 0..type_seeds_len {
+            let type_index = leb128::read::unsigned(reader)? as IriIndex;
+            let count = leb128::read::unsigned(reader)? as u32;
+            self.type_seeds.push(TypeSeed { type_index, count });
+        }
+        let seed_nodes_len = leb128::read::unsigned(reader)?;
+        for _ in 0..seed_nodes_len {
+            self.seed_nodes.push(read_len_string(reader)?);
+        }
+        let field_number = leb128::read::unsigned(reader)?;
+        for _ in 0..field_number {
+            let (field_type, field_index) = read_field_index(reader)?;
+            match field_index {
+                1 if field_type == FieldType::LENGTHDELIMITED => {
+                    let _field_length = leb128::read::unsigned(reader)?;
+                    self.starting_query = Some(read_len_string(reader)?.into());
+                }
+                _ => skip_field(reader, field_type)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads only the thumbnail block of a project file, projects saved by older versions have none
+pub fn read_project_thumbnail(path: &Path) -> Result<Option<Thumbnail>> {
+    let file = File::open(path)?;
+    let mut reader = BufReader::new(&file);
+    if reader.read_u32::<LittleEndian>()? != MAGIC_NUMBER {
+        return Err(anyhow::anyhow!("This seems not to be RDF Glance file. Wrong magic number"));
+    }
+    let _version = reader.read_u16::<LittleEndian>()?;
+    let _flags = reader.read_u16::<LittleEndian>()?;
+    let header_length = reader.read_u16::<LittleEndian>()?;
+    reader.seek(SeekFrom::Start(header_length as u64))?;
+    while let Ok(header_type_u8) = reader.read_u8() {
+        let block_size = reader.read_u32::<LittleEndian>()?;
+        if let Some(HeaderType::Thumbnail) = HeaderType::from_u8(header_type_u8) {
+            return Ok(Some(Thumbnail::restore(&mut reader)?));
+        }
+        reader.seek(SeekFrom::Current((block_size - BLOCK_PRELUDE_SIZE) as i64))?;
+    }
+    Ok(None)
 }
 
 fn with_header_len(
@@ -271,7 +512,7 @@ fn with_header_len(
     Ok(())
 }
 
-fn write_var_field<W: Write>(
+fn write_var_field<W: Write + ?Sized>(
     file: &mut W,
     field_id: u32,
     f: &dyn Fn(&mut dyn Write) -> std::io::Result<()>,
@@ -321,7 +562,7 @@ impl StringIndexer {
                     0xC0..=0xDF => {
                         let read = decoder.read(&mut byte)?;
                         if read == 0 {
-                            println!("Expect 1 addtional byte for utf8");
+                            log::debug!("Expect 1 addtional byte for utf8");
                             break;
                         }
                         buffer.push(byte[0]);
@@ -330,7 +571,7 @@ impl StringIndexer {
                         let mut byte2 = [0u8; 2];
                         let read = decoder.read(&mut byte2)?;
                         if read != 2 {
-                            println!("Expect 2 addtional bytes for utf8");
+                            log::debug!("Expect 2 addtional bytes for utf8");
                             break;
                         }
                         buffer.extend_from_slice(&byte2[0..2]);
@@ -339,13 +580,13 @@ impl StringIndexer {
                         let mut byte3 = [0u8; 3];
                         let read = decoder.read(&mut byte3)?;
                         if read != 3 {
-                            println!("Expect 3 additional bytes for utf8");
+                            log::debug!("Expect 3 additional bytes for utf8");
                             break;
                         }
                         buffer.extend_from_slice(&byte3[0..3]);
                     }
                     _ => {
-                        println!("Invalid UTF-8 byte detected: 0x{:X}", byte[0]);
+                        log::warn!("Invalid UTF-8 byte detected: 0x{:X}", byte[0]);
                     }
                 };
             }
@@ -437,7 +678,7 @@ impl NodeCache {
     }
 }
 
-fn read_len_string<R: Read>(reader: &mut R) -> Result<Box<str>> {
+pub(crate) fn read_len_string<R: Read>(reader: &mut R) -> Result<Box<str>> {
     let str_len = leb128::read::unsigned(reader)?;
     let mut buffer = vec![0; str_len as usize];
     reader.read_exact(&mut buffer)?;
@@ -445,14 +686,14 @@ fn read_len_string<R: Read>(reader: &mut R) -> Result<Box<str>> {
     Ok(str.into())
 }
 
-fn write_len_string<W: Write>(str: &str, writer: &mut W) -> std::io::Result<()> {
+pub(crate) fn write_len_string<W: Write + ?Sized>(str: &str, writer: &mut W) -> std::io::Result<()> {
     let iri_bytes = str.as_bytes();
     leb128::write::unsigned(writer, iri_bytes.len() as u64)?;
     writer.write_all(iri_bytes)?;
     Ok(())
 }
 
-fn write_field_index<W: Write>(writer: &mut W, field_type: FieldType, field_index: u32) -> std::io::Result<()> {
+fn write_field_index<W: Write + ?Sized>(writer: &mut W, field_type: FieldType, field_index: u32) -> std::io::Result<()> {
     let field_encoded = (field_index << 3) as u64 | field_type as u64;
     leb128::write::unsigned(writer, field_encoded)?;
     Ok(())
@@ -586,22 +827,20 @@ impl PrefixManager {
 impl SortedNodeLayout {
     pub fn store(&self, writer: &mut BufWriter<File>) -> std::io::Result<()> {
         with_header_len(writer, HeaderType::VisualNodes, &|writer| {
-            if let Ok(nodes) = self.nodes.read() {
+            if let Ok(layout) = self.layout_data.read() {
+                let nodes = &layout.nodes;
                 leb128::write::unsigned(writer, nodes.len() as u64)?;
-                if let Ok(positions) = self.positions.read() {
-                    for (node_layout, node_pos) in nodes.iter().zip(positions.iter()) {
-                        leb128::write::unsigned(writer, node_layout.node_index as u64)?;
-                        writer.write_f32::<LittleEndian>(node_pos.pos.x)?;
-                        writer.write_f32::<LittleEndian>(node_pos.pos.y)?;
-                        // Write number of fields                        
-                        leb128::write::unsigned(writer, if node_pos.locked {1} else {0})?;
-                        if node_pos.locked {
-                            write_field_index(writer, FieldType::FLAG, 1)?;
-                        }
+                for (node_layout, node_pos) in nodes.iter().zip(layout.positions.iter()) {
+                    leb128::write::unsigned(writer, node_layout.node_index as u64)?;
+                    writer.write_f32::<LittleEndian>(node_pos.pos.x)?;
+                    writer.write_f32::<LittleEndian>(node_pos.pos.y)?;
+                    // Write number of fields                        
+                    leb128::write::unsigned(writer, if node_pos.locked {1} else {0})?;
+                    if node_pos.locked {
+                        write_field_index(writer, FieldType::FLAG, 1)?;
                     }
                 }
-            }
-            if let Ok(edges) = self.edges.read() {
+                let edges = &layout.edges;
                 leb128::write::unsigned(writer, edges.len() as u64)?;
                 for edge in edges.iter() {
                     leb128::write::unsigned(writer, edge.from as u64)?;
@@ -669,11 +908,13 @@ impl SortedNodeLayout {
         let hidden_predicates = SortedVec::new();
         update_edges_groups(&mut edges, &hidden_predicates);
         Ok(SortedNodeLayout {
-            nodes: Arc::new(RwLock::new(nodes)),
-            positions: Arc::new(RwLock::new(positions)),
-            edges: Arc::new(RwLock::new(edges)),
-            node_shapes: Arc::new(RwLock::new(node_shapes)),
-            individual_node_styles: Arc::new(RwLock::new(indivudual_node_styles)),
+            layout_data: Arc::new(RwLock::new(LayoutData {
+                nodes,
+                edges,
+                positions,
+                node_shapes,
+                individual_node_styles: indivudual_node_styles,
+            })),
             ..SortedNodeLayout::default()
         })
     }
@@ -703,14 +944,48 @@ impl GVisualizationStyle {
                 writer.write_f32::<LittleEndian>(style.font_size)?;
                 writer.write_f32::<LittleEndian>(style.corner_radius)?;
                 writer.write_f32::<LittleEndian>(style.label_max_width)?;
+                let tooltip_properties = self
+                    .tooltip_properties
+                    .get(type_index)
+                    .filter(|tooltip_properties| !tooltip_properties.is_empty());
+                let style_rules: Vec<&StyleRule> = self
+                    .style_rules
+                    .rules
+                    .iter()
+                    .filter(|rule| rule.type_index == *type_index)
+                    .collect();
+                let field_count = style.icon_style.is_some() as u64
+                    + tooltip_properties.is_some() as u64
+                    + !style_rules.is_empty() as u64;
+                leb128::write::unsigned(writer, field_count)?;
                 if let Some(icon_style) = &style.icon_style {
-                    leb128::write::unsigned(writer, 1)?;
                     write_var_field(writer, 1, &|file| {
                         icon_style.store(file)?;
                         Ok(())
                     })?;
-                } else {
-                    leb128::write::unsigned(writer, 0)?;
+                }
+                if let Some(tooltip_properties) = tooltip_properties {
+                    write_var_field(writer, 2, &|file| {
+                        leb128::write::unsigned(file, tooltip_properties.len() as u64)?;
+                        for predicate_index in tooltip_properties.iter() {
+                            leb128::write::unsigned(file, *predicate_index as u64)?;
+                        }
+                        Ok(())
+                    })?;
+                }
+                if !style_rules.is_empty() {
+                    write_var_field(writer, 3, &|file| {
+                        leb128::write::unsigned(file, style_rules.len() as u64)?;
+                        for rule in style_rules.iter() {
+                            leb128::write::unsigned(file, rule.predicate as u64)?;
+                            file.write_u8(rule.operator as u8)?;
+                            write_len_string(&rule.value, file)?;
+                            file.write_u8(rule.color.is_some() as u8)?;
+                            let _written = file.write(&rule.color.unwrap_or_default().to_array())?;
+                            file.write_f32::<LittleEndian>(rule.size_factor)?;
+                        }
+                        Ok(())
+                    })?;
                 }
             }
             leb128::write::unsigned(writer, self.edge_styles.len() as u64)?;
@@ -731,6 +1006,9 @@ impl GVisualizationStyle {
                 if style.edge_font.is_some() {
                     field_count += 1;
                 }
+                if style.layout_force.is_some() {
+                    field_count += 1;
+                }
                 leb128::write::unsigned(writer, field_count)?;
                 if let Some(icon_style) = &style.icon_style {
                     write_var_field(writer, 1, &|file| {
@@ -744,6 +1022,13 @@ impl GVisualizationStyle {
                         Ok(())
                     })?;
                 }
+                if let Some(layout_force) = &style.layout_force {
+                    write_var_field(writer, 3, &|file| {
+                        file.write_f32::<LittleEndian>(layout_force.length)?;
+                        file.write_f32::<LittleEndian>(layout_force.stiffness)?;
+                        Ok(())
+                    })?;
+                }
             }
             Ok(())
         })
@@ -753,6 +1038,9 @@ impl GVisualizationStyle {
         let mut styles = GVisualizationStyle {
             node_styles: HashMap::new(),
             edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
             default_node_style: crate::NodeStyle::default(),
             use_size_overwrite: false,
             use_color_overwrite: false,
@@ -802,6 +1090,41 @@ impl GVisualizationStyle {
                             skip_field(reader, field_type)?;
                         }
                     }
+                    2 if field_type == FieldType::LENGTHDELIMITED => {
+                        let _field_length = leb128::read::unsigned(reader)?;
+                        let len = leb128::read::unsigned(reader)?;
+                        let mut tooltip_properties: Vec<IriIndex> = Vec::with_capacity(len as usize);
+                        for _ in 0..len {
+                            tooltip_properties.push(leb128::read::unsigned(reader)? as IriIndex);
+                        }
+                        styles.tooltip_properties.insert(type_index, tooltip_properties);
+                    }
+                    3 if field_type == FieldType::LENGTHDELIMITED => {
+                        let _field_length = leb128::read::unsigned(reader)?;
+                        let len = leb128::read::unsigned(reader)?;
+                        for _ in 0..len {
+                            let predicate = leb128::read::unsigned(reader)? as IriIndex;
+                            let operator: RuleOperator = reader
+                                .read_u8()?
+                                .try_into()
+                                .map_err(|_| anyhow::anyhow!("Invalid rule operator value"))?;
+                            let value = read_len_string(reader)?.into();
+                            let has_color = reader.read_u8()? != 0;
+                            let mut color = [0u8; 4];
+                            reader.read_exact(&mut color)?;
+                            let size_factor = reader.read_f32::<LittleEndian>()?;
+                            styles.style_rules.rules.push(StyleRule {
+                                type_index,
+                                predicate,
+                                operator,
+                                value,
+                                color: has_color.then(|| {
+                                    egui::Color32::from_rgba_premultiplied(color[0], color[1], color[2], color[3])
+                                }),
+                                size_factor,
+                            });
+                        }
+                    }
                     _ => {
                         skip_field(reader, field_type)?;
                     }
@@ -860,6 +1183,7 @@ impl GVisualizationStyle {
                 .map_err(|_| anyhow::anyhow!("Invalid target_style value"))?;
             let mut icon_style: Option<IconStyle> = None;
             let mut edge_font: Option<EdgeFont> = None;
+            let mut layout_force: Option<EdgeLayoutForce> = None;
 
             let field_number = leb128::read::unsigned(reader)?;
             for _ in 0..field_number {
@@ -879,6 +1203,12 @@ impl GVisualizationStyle {
                             skip_field(reader, field_type)?;
                         }
                     }
+                    3 if field_type == FieldType::LENGTHDELIMITED => {
+                        let _field_length = leb128::read::unsigned(reader)?;
+                        let length = reader.read_f32::<LittleEndian>()?;
+                        let stiffness = reader.read_f32::<LittleEndian>()?;
+                        layout_force = Some(EdgeLayoutForce { length, stiffness });
+                    }
                     _ => {
                         skip_field(reader, field_type)?;
                     }
@@ -894,10 +1224,12 @@ impl GVisualizationStyle {
                 target_style,
                 icon_style,
                 edge_font,
+                layout_force,
             };
 
             styles.edge_styles.insert(reference_index, style);
         }
+        styles.style_rules.update_index();
 
         Ok(styles)
     }
@@ -911,7 +1243,10 @@ impl IconStyle {
         let _written = writer.write(&col)?;
         let character = self.icon_character as u32;
         leb128::write::unsigned(writer, character as u64)?;
-        leb128::write::unsigned(writer, 0)?;
+        leb128::write::unsigned(writer, self.image.is_some() as u64)?;
+        if let Some(image) = &self.image {
+            write_var_field(writer, 1, &|writer| write_len_string(&image.path, writer))?;
+        }
         Ok(())
     }
 
@@ -931,8 +1266,14 @@ impl IconStyle {
             char::from_u32(character).ok_or_else(|| anyhow::anyhow!("Invalid icon character value"))?;
         let field_number = leb128::read::unsigned(reader)?;
         for _ in 0..field_number {
-            let (field_type, _field_index) = read_field_index(reader)?;
-            skip_field(reader, field_type)?;
+            let (field_type, field_index) = read_field_index(reader)?;
+            match field_index {
+                1 if field_type == FieldType::LENGTHDELIMITED => {
+                    let _field_length = leb128::read::unsigned(reader)?;
+                    icon_style.image = Some(IconImage::new(read_len_string(reader)?.into()));
+                }
+                _ => skip_field(reader, field_type)?,
+            }
         }
         Ok(icon_style)
     }
@@ -944,7 +1285,28 @@ impl EdgeFont {
         let col = self.font_color.to_array();
         let _written = writer.write(&col)?;
         // num of fields
-        leb128::write::unsigned(writer, 0)?;
+        let mut field_count = 1;
+        if !self.label_visible {
+            field_count += 1;
+        }
+        if !self.label_rotate {
+            field_count += 1;
+        }
+        if self.label_background {
+            field_count += 1;
+        }
+        leb128::write::unsigned(writer, field_count)?;
+        write_field_index(writer, FieldType::VARINT, 1)?;
+        leb128::write::unsigned(writer, self.label_position as u64)?;
+        if !self.label_visible {
+            write_field_index(writer, FieldType::FLAG, 2)?;
+        }
+        if !self.label_rotate {
+            write_field_index(writer, FieldType::FLAG, 3)?;
+        }
+        if self.label_background {
+            write_field_index(writer, FieldType::FLAG, 4)?;
+        }
         Ok(())
     }
 
@@ -954,15 +1316,28 @@ impl EdgeFont {
         let mut color = [0u8; 4];
         reader.read_exact(&mut color)?;
         let font_color = egui::Color32::from_rgba_premultiplied(color[0], color[1], color[2], color[3]);
+        let mut edge_font = EdgeFont {
+            font_size,
+            font_color,
+            ..EdgeFont::default()
+        };
         let field_number = leb128::read::unsigned(reader)?;
         for _ in 0..field_number {
-            let (field_type, _field_index) = read_field_index(reader)?;
-            skip_field(reader, field_type)?;
+            let (field_type, field_index) = read_field_index(reader)?;
+            match (field_index, field_type) {
+                (1, FieldType::VARINT) => {
+                    let label_position = leb128::read::unsigned(reader)? as u8;
+                    edge_font.label_position = label_position
+                        .try_into()
+                        .map_err(|_| anyhow::anyhow!("Invalid edge label position value"))?;
+                }
+                (2, FieldType::FLAG) => edge_font.label_visible = false,
+                (3, FieldType::FLAG) => edge_font.label_rotate = false,
+                (4, FieldType::FLAG) => edge_font.label_background = true,
+                (_, field_type) => skip_field(reader, field_type)?,
+            }
         }
-        Ok(EdgeFont {
-            font_size,
-            font_color
-        })
+        Ok(edge_font)
     }
 }
 
@@ -1044,6 +1419,371 @@ impl UIState {
     }
 }
 
+impl ConfigOverrides {
+    pub fn store(&self, writer: &mut BufWriter<File>) -> std::io::Result<()> {
+        with_header_len(writer, HeaderType::ConfigOverrides, &|file| {
+            let num_fields = [
+                self.language_filter.is_some(),
+                self.suppress_other_language_data.is_some(),
+                self.iri_display.is_some(),
+                self.short_iri.is_some(),
+                self.m_repulsion_constant.is_some(),
+                self.m_attraction_factor.is_some(),
+                self.gravity_effect_radius.is_some(),
+                self.color_by_namespace.is_some(),
+            ]
+            .iter()
+            .filter(|is_set| **is_set)
+            .count();
+            leb128::write::unsigned(file, num_fields as u64)?;
+            if let Some(language_filter) = &self.language_filter {
+                write_field_index(file, FieldType::LENGTHDELIMITED, 1)?;
+                write_len_string(language_filter, file)?;
+            }
+            if let Some(suppress_other_language_data) = self.suppress_other_language_data {
+                write_field_index(file, FieldType::VARINT, 2)?;
+                leb128::write::unsigned(file, suppress_other_language_data as u64)?;
+            }
+            if let Some(iri_display) = self.iri_display {
+                write_field_index(file, FieldType::VARINT, 3)?;
+                leb128::write::unsigned(file, iri_display as u64)?;
+            }
+            if let Some(short_iri) = self.short_iri {
+                write_field_index(file, FieldType::VARINT, 4)?;
+                leb128::write::unsigned(file, short_iri as u64)?;
+            }
+            for (field_index, value) in [
+                (5, self.m_repulsion_constant),
+                (6, self.m_attraction_factor),
+                (7, self.gravity_effect_radius),
+            ] {
+                if let Some(value) = value {
+                    write_field_index(file, FieldType::FIX32, field_index)?;
+                    file.write_f32::<LittleEndian>(value)?;
+                }
+            }
+            if let Some(color_by_namespace) = self.color_by_namespace {
+                write_field_index(file, FieldType::VARINT, 8)?;
+                leb128::write::unsigned(file, color_by_namespace as u64)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn restore(reader: &mut BufReader<&File>, _size: u32) -> Result<Self> {
+        let mut overrides = ConfigOverrides::default();
+        let field_number = leb128::read::unsigned(reader)?;
+        for _ in 0..field_number {
+            let (field_type, field_index) = read_field_index(reader)?;
+            match (field_index, field_type) {
+                (1, FieldType::LENGTHDELIMITED) => {
+                    overrides.language_filter = Some(read_len_string(reader)?.into());
+                }
+                (2, FieldType::VARINT) => {
+                    overrides.suppress_other_language_data = Some(leb128::read::unsigned(reader)? != 0);
+                }
+                (3, FieldType::VARINT) => {
+                    overrides.iri_display = IriDisplay::from_u8(leb128::read::unsigned(reader)? as u8);
+                }
+                (4, FieldType::VARINT) => {
+                    overrides.short_iri = Some(leb128::read::unsigned(reader)? != 0);
+                }
+                (5, FieldType::FIX32) => {
+                    overrides.m_repulsion_constant = Some(reader.read_f32::<LittleEndian>()?);
+                }
+                (6, FieldType::FIX32) => {
+                    overrides.m_attraction_factor = Some(reader.read_f32::<LittleEndian>()?);
+                }
+                (7, FieldType::FIX32) => {
+                    overrides.gravity_effect_radius = Some(reader.read_f32::<LittleEndian>()?);
+                }
+                (8, FieldType::VARINT) => {
+                    overrides.color_by_namespace = Some(leb128::read::unsigned(reader)? != 0);
+                }
+                _ => {
+                    skip_field(reader, field_type)?;
+                }
+            }
+        }
+        Ok(overrides)
+    }
+}
+
+impl NamedGraphs {
+    pub fn store(&self, writer: &mut BufWriter<File>) -> std::io::Result<()> {
+        with_header_len(writer, HeaderType::NamedGraphs, &|file| {
+            leb128::write::unsigned(file, self.graph_indexer.map.len() as u64)?;
+            for (_index, graph_name) in self.graph_indexer.map.iter() {
+                write_len_string(graph_name, file)?;
+            }
+            leb128::write::unsigned(file, self.triple_graphs.len() as u64)?;
+            for (triple_key, graphs) in self.triple_graphs.iter() {
+                match triple_key {
+                    TripleKey::Type(subject, type_index) => {
+                        file.write_u8(1)?;
+                        leb128::write::unsigned(file, *subject as u64)?;
+                        leb128::write::unsigned(file, *type_index as u64)?;
+                    }
+                    TripleKey::Reference(subject, predicate_index, object) => {
+                        file.write_u8(2)?;
+                        leb128::write::unsigned(file, *subject as u64)?;
+                        leb128::write::unsigned(file, *predicate_index as u64)?;
+                        leb128::write::unsigned(file, *object as u64)?;
+                    }
+                    TripleKey::Property(subject, predicate_index, literal) => {
+                        file.write_u8(3)?;
+                        leb128::write::unsigned(file, *subject as u64)?;
+                        leb128::write::unsigned(file, *predicate_index as u64)?;
+                        literal.store(file)?;
+                    }
+                }
+                leb128::write::unsigned(file, graphs.data.len() as u64)?;
+                for graph_index in graphs.data.iter() {
+                    leb128::write::unsigned(file, *graph_index as u64)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn restore<R: Read>(reader: &mut R, _size: u32) -> Result<Self> {
+        let mut named_graphs = NamedGraphs::default();
+        let graphs_len = leb128::read::unsigned(reader)?;
+        for _ in 0..graphs_len {
+            let graph_name = read_len_string(reader)?;
+            named_graphs.graph_indexer.get_index(&graph_name);
+        }
+        let triples_len = leb128::read::unsigned(reader)?;
+        for _ in 0..triples_len {
+            let key_type = reader.read_u8()?;
+            let subject = leb128::read::unsigned(reader)? as IriIndex;
+            let triple_key = match key_type {
+                1 => TripleKey::Type(subject, leb128::read::unsigned(reader)? as IriIndex),
+                2 => {
+                    let predicate_index = leb128::read::unsigned(reader)? as IriIndex;
+                    let object = leb128::read::unsigned(reader)? as IriIndex;
+                    TripleKey::Reference(subject, predicate_index, object)
+                }
+                3 => {
+                    let predicate_index = leb128::read::unsigned(reader)? as IriIndex;
+                    TripleKey::Property(subject, predicate_index, Literal::restore(reader)?)
+                }
+                _ => return Err(anyhow::anyhow!("Unknown named graph triple type {}", key_type)),
+            };
+            let graphs_len = leb128::read::unsigned(reader)?;
+            let mut graphs = SortedVec::new();
+            for _ in 0..graphs_len {
+                graphs.add(leb128::read::unsigned(reader)? as IriIndex);
+            }
+            named_graphs.triple_graphs.insert(triple_key, graphs);
+        }
+        Ok(named_graphs)
+    }
+}
+
+impl TableLayout {
+    pub fn store(&self, writer: &mut BufWriter<File>) -> std::io::Result<()> {
+        with_header_len(writer, HeaderType::TableLayout, &|file| {
+            leb128::write::unsigned(file, self.types.len() as u64)?;
+            for type_layout in self.types.iter() {
+                leb128::write::unsigned(file, type_layout.type_index as u64)?;
+                file.write_f32::<LittleEndian>(type_layout.iri_width)?;
+                file.write_f32::<LittleEndian>(type_layout.ref_count_width)?;
+                leb128::write::unsigned(file, type_layout.columns.len() as u64)?;
+                for column in type_layout.columns.iter() {
+                    leb128::write::unsigned(file, column.predicate_index as u64)?;
+                    file.write_f32::<LittleEndian>(column.width)?;
+                    file.write_u8(column.visible as u8)?;
+                    let truncate_chars = match column.abbreviation {
+                        ColumnAbbreviation::Truncate(max_chars) => Some(max_chars),
+                        _ => None,
+                    };
+                    let num_fields = (column.abbreviation != ColumnAbbreviation::None) as u64
+                        + truncate_chars.is_some() as u64
+                        + column.all_languages as u64;
+                    leb128::write::unsigned(file, num_fields)?;
+                    if column.abbreviation != ColumnAbbreviation::None {
+                        write_field_index(file, FieldType::VARINT, 1)?;
+                        leb128::write::unsigned(file, column.abbreviation.to_u8() as u64)?;
+                    }
+                    if let Some(max_chars) = truncate_chars {
+                        write_field_index(file, FieldType::VARINT, 2)?;
+                        leb128::write::unsigned(file, max_chars as u64)?;
+                    }
+                    if column.all_languages {
+                        write_field_index(file, FieldType::VARINT, 3)?;
+                        leb128::write::unsigned(file, 1)?;
+                    }
+                }
+            }
+            let num_fields = if self.selected_type.is_some() { 1 } else { 0 };
+            leb128::write::unsigned(file, num_fields)?;
+            if let Some(selected_type) = self.selected_type {
+                write_field_index(file, FieldType::VARINT, 1)?;
+                leb128::write::unsigned(file, selected_type as u64)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn restore(reader: &mut BufReader<&File>, _size: u32) -> Result<Self> {
+        let mut table_layout = TableLayout::default();
+        let types_len = leb128::read::unsigned(reader)?;
+        for _ in 0..types_len {
+            let type_index = leb128::read::unsigned(reader)? as IriIndex;
+            let iri_width = reader.read_f32::<LittleEndian>()?;
+            let ref_count_width = reader.read_f32::<LittleEndian>()?;
+            let columns_len = leb128::read::unsigned(reader)?;
+            let mut columns = Vec::with_capacity(columns_len as usize);
+            for _ in 0..columns_len {
+                let predicate_index = leb128::read::unsigned(reader)? as IriIndex;
+                let width = reader.read_f32::<LittleEndian>()?;
+                let visible = reader.read_u8()? != 0;
+                let mut abbreviation_kind = 0;
+                let mut truncate_chars = DEFAULT_TRUNCATE_CHARS;
+                let mut all_languages = false;
+                let field_number = leb128::read::unsigned(reader)?;
+                for _ in 0..field_number {
+                    let (field_type, field_index) = read_field_index(reader)?;
+                    match (field_index, field_type) {
+                        (1, FieldType::VARINT) => {
+                            abbreviation_kind = leb128::read::unsigned(reader)? as u8;
+                        }
+                        (2, FieldType::VARINT) => {
+                            truncate_chars = leb128::read::unsigned(reader)? as u32;
+                        }
+                        (3, FieldType::VARINT) => {
+                            all_languages = leb128::read::unsigned(reader)? != 0;
+                        }
+                        _ => {
+                            skip_field(reader, field_type)?;
+                        }
+                    }
+                }
+                columns.push(ColumnDesc {
+                    predicate_index,
+                    width,
+                    visible,
+                    abbreviation: ColumnAbbreviation::from_u8(abbreviation_kind, truncate_chars),
+                    all_languages,
+                });
+            }
+            table_layout.types.push(TypeColumnLayout {
+                type_index,
+                iri_width,
+                ref_count_width,
+                columns,
+            });
+        }
+        let field_number = leb128::read::unsigned(reader)?;
+        for _ in 0..field_number {
+            let (field_type, field_index) = read_field_index(reader)?;
+            match (field_index, field_type) {
+                (1, FieldType::VARINT) => {
+                    table_layout.selected_type = Some(leb128::read::unsigned(reader)? as IriIndex);
+                }
+                _ => {
+                    skip_field(reader, field_type)?;
+                }
+            }
+        }
+        Ok(table_layout)
+    }
+}
+
+fn write_rect<W: Write>(writer: &mut W, rect: &Rect) -> std::io::Result<()> {
+    writer.write_f32::<LittleEndian>(rect.min.x)?;
+    writer.write_f32::<LittleEndian>(rect.min.y)?;
+    writer.write_f32::<LittleEndian>(rect.max.x)?;
+    writer.write_f32::<LittleEndian>(rect.max.y)?;
+    Ok(())
+}
+
+fn read_rect<R: Read>(reader: &mut R) -> Result<Rect> {
+    let min_x = reader.read_f32::<LittleEndian>()?;
+    let min_y = reader.read_f32::<LittleEndian>()?;
+    let max_x = reader.read_f32::<LittleEndian>()?;
+    let max_y = reader.read_f32::<LittleEndian>()?;
+    Ok(Rect::from_min_max(Pos2::new(min_x, min_y), Pos2::new(max_x, max_y)))
+}
+
+impl GraphState {
+    pub fn store(&self, writer: &mut BufWriter<File>) -> std::io::Result<()> {
+        with_header_len(writer, HeaderType::GraphView, &|file| {
+            write_rect(file, &self.scene_rect)?;
+            let bookmarks = self.bookmarks.iter().filter(|bookmark| bookmark.is_some()).count();
+            leb128::write::unsigned(file, bookmarks as u64)?;
+            for (slot, bookmark) in self.bookmarks.iter().enumerate() {
+                if let Some(bookmark) = bookmark {
+                    leb128::write::unsigned(file, slot as u64)?;
+                    write_rect(file, bookmark)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    pub fn restore(reader: &mut BufReader<&File>, _size: u32) -> Result<Self> {
+        let mut graph_state = GraphState {
+            scene_rect: read_rect(reader)?,
+            ..GraphState::default()
+        };
+        let bookmarks = leb128::read::unsigned(reader)?;
+        for _ in 0..bookmarks {
+            let slot = leb128::read::unsigned(reader)? as usize;
+            let rect = read_rect(reader)?;
+            if let Some(bookmark) = graph_state.bookmarks.get_mut(slot) {
+                *bookmark = Some(rect);
+            }
+        }
+        Ok(graph_state)
+    }
+}
+
+impl Thumbnail {
+    pub fn store(&self, writer: &mut BufWriter<File>) -> std::io::Result<()> {
+        with_header_len(writer, HeaderType::Thumbnail, &|file| {
+            leb128::write::unsigned(file, self.nodes.len() as u64)?;
+            for node in self.nodes.iter() {
+                file.write_all(node)?;
+            }
+            leb128::write::unsigned(file, self.edges.len() as u64)?;
+            for edge in self.edges.iter() {
+                file.write_u16::<LittleEndian>(edge[0])?;
+                file.write_u16::<LittleEndian>(edge[1])?;
+            }
+            leb128::write::unsigned(file, self.colors.len() as u64)?;
+            for color in self.colors.iter() {
+                file.write_all(color)?;
+            }
+            Ok(())
+        })
+    }
+
+    pub fn restore<R: Read>(reader: &mut R) -> Result<Self> {
+        let mut thumbnail = Thumbnail::default();
+        let node_count = leb128::read::unsigned(reader)?;
+        for _ in 0..node_count {
+            let mut node = [0u8; 2];
+            reader.read_exact(&mut node)?;
+            thumbnail.nodes.push(node);
+        }
+        let edge_count = leb128::read::unsigned(reader)?;
+        for _ in 0..edge_count {
+            let from = reader.read_u16::<LittleEndian>()?;
+            let to = reader.read_u16::<LittleEndian>()?;
+            thumbnail.edges.push([from, to]);
+        }
+        let color_count = leb128::read::unsigned(reader)?;
+        for _ in 0..color_count {
+            let mut color = [0u8; 3];
+            reader.read_exact(&mut color)?;
+            thumbnail.colors.push(color);
+        }
+        Ok(thumbnail)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::{fs, path::PathBuf, time::Instant};
@@ -1104,7 +1844,7 @@ mod tests {
             let hidden_predicates = SortedVec::new();
             rdf_data.expand_all(&mut node_change_context, &hidden_predicates);
         }
-        assert_eq!(true, vs.visible_nodes.nodes.read().unwrap().len() > 0);
+        assert!(!vs.visible_nodes.layout_data.read().unwrap().nodes.is_empty());
         if let Ok(rdf_data) = vs.rdf_data.read() {
             let (node_iri, node_object) = rdf_data.node_data.get_node_by_index(node_index.unwrap()).unwrap();
             assert_eq!("dbr:Rust_(programming_language)", node_iri.to_string());
@@ -1129,8 +1869,17 @@ mod tests {
                     icon_character: 'R',
                     icon_size: 20.0,
                     icon_position: IconPosition::Above,
+                    image: Some(IconImage::new("icons/person.png".to_string())),
                 }
             });
+            vs.visualization_style.tooltip_properties.insert(*type_index, vec![3, 1]);
+            vs.visualization_style.style_rules.rules.push(StyleRule {
+                operator: RuleOperator::Greater,
+                value: "1000".to_string(),
+                color: None,
+                size_factor: 2.0,
+                ..StyleRule::new(*type_index, 3)
+            });
             let edge_index = node_object.references.get(0).unwrap().0;
             vs.visualization_style.get_edge_syle(edge_index, true);
             let edge = vs.visualization_style.edge_styles.get_mut(&edge_index).unwrap();
@@ -1143,6 +1892,7 @@ mod tests {
             edge.edge_font = Some(EdgeFont {
                 font_color: Color32::GRAY,
                 font_size: 20.0,
+                ..EdgeFont::default()
             });
             edge.icon_style = Some({
                 IconStyle {
@@ -1150,13 +1900,37 @@ mod tests {
                     icon_character: 'R',
                     icon_size: 20.0,
                     icon_position: IconPosition::Above,
+                    image: None,
                 }
             });
+            edge.layout_force = Some(EdgeLayoutForce {
+                length: 120.0,
+                stiffness: 2.0,
+            });
         }
 
+        vs.project_config.language_filter = Some("de".to_string());
+        vs.project_config.iri_display = Some(IriDisplay::Prefixed);
+        vs.project_config.m_attraction_factor = Some(0.7);
+        let table_type = vs.type_index.types_order[0];
+        if let Some(type_data) = vs.type_index.types.get_mut(&table_type) {
+            type_data.instance_view.iri_width = 123.0;
+            type_data.instance_view.display_properties.reverse();
+            type_data.instance_view.display_properties[0].visible = false;
+            type_data.instance_view.display_properties[0].width = 55.0;
+            type_data.instance_view.display_properties[1].abbreviation = ColumnAbbreviation::Truncate(12);
+            type_data.instance_view.display_properties[1].all_languages = true;
+        }
+        let scene_rect = egui::Rect::from_min_max(Pos2::new(-10.0, -20.0), Pos2::new(300.0, 200.0));
+        vs.graph_state.scene_rect = scene_rect;
+        vs.graph_state.bookmarks[2] = Some(scene_rect);
         vs.store(&store_path)?;
 
         assert!(store_path.exists(), "file does not exists");
+        let thumbnail = read_project_thumbnail(&store_path).unwrap().unwrap();
+        assert!(!thumbnail.is_empty());
+        assert_eq!(thumbnail.nodes.len(), thumbnail.colors.len());
+        assert!(vs.graph_thumbnail() == thumbnail);
         let start = Instant::now();
         let mut restored = RdfGlanceApp::restore(&store_path).unwrap();
         let duration = start.elapsed();
@@ -1210,6 +1984,13 @@ mod tests {
                 });
                 assert_eq!(1, rust_node.types.len());
                 let type_index = rust_node.types.get(0).unwrap();
+                assert_eq!(Some(&vec![3, 1]), restored.visualization_style.tooltip_properties.get(type_index));
+                let style_rules = &restored.visualization_style.style_rules.rules;
+                assert_eq!(1, style_rules.len());
+                assert_eq!(RuleOperator::Greater, style_rules[0].operator);
+                assert_eq!("1000", style_rules[0].value);
+                assert_eq!(None, style_rules[0].color);
+                assert_eq!(2.0, style_rules[0].size_factor);
                 let type_style = restored.visualization_style.node_styles.get_mut(type_index).unwrap();
                 assert_eq!(type_style.max_lines, 2);
                 assert_eq!(type_style.node_shape, NodeShape::Rect);
@@ -1229,6 +2010,10 @@ mod tests {
                     assert_eq!(icon_style.icon_character, 'R');
                     assert_eq!(icon_style.icon_size, 20.0);
                     assert_eq!(icon_style.icon_position, IconPosition::Above);
+                    assert_eq!(
+                        icon_style.image.as_ref().map(|image| image.path.as_str()),
+                        Some("icons/person.png")
+                    );
                 } else {
                     panic!("Icon style not found");
                 }
@@ -1258,6 +2043,13 @@ mod tests {
                 panic!("Edge font not found");
             }
             */
+            assert!(
+                restored
+                    .visualization_style
+                    .edge_layout_forces()
+                    .values()
+                    .any(|force| force.length == 120.0 && force.stiffness == 2.0)
+            );
         }
         let predicates = vec!["rdf:type"];
         for pred_val in &predicates {
@@ -1287,14 +2079,36 @@ mod tests {
         );
 
         assert_eq!(
-            vs.visible_nodes.nodes.read().unwrap().len(),
-            restored.visible_nodes.nodes.read().unwrap().len()
+            vs.visible_nodes.layout_data.read().unwrap().nodes.len(),
+            restored.visible_nodes.layout_data.read().unwrap().nodes.len()
         );
         assert_eq!(
             vs.visualization_style.node_styles.len(),
             restored.visualization_style.node_styles.len()
         );
-        assert_eq!(true, vs.visible_nodes.nodes.read().unwrap().len() > 0);
+        assert!(!vs.visible_nodes.layout_data.read().unwrap().nodes.is_empty());
+        assert_eq!(Some("de".to_string()), restored.project_config.language_filter);
+        assert_eq!(Some(IriDisplay::Prefixed), restored.project_config.iri_display);
+        assert_eq!(Some(0.7), restored.project_config.m_attraction_factor);
+        assert_eq!(None, restored.project_config.short_iri);
+        assert_eq!(scene_rect, restored.graph_state.scene_rect);
+        assert_eq!(Some(scene_rect), restored.graph_state.bookmarks[2]);
+        assert_eq!(None, restored.graph_state.bookmarks[0]);
+
+        let table_layout = restored.pending_table_layout.take().unwrap();
+        restored.update_data_indexes(true);
+        restored.type_index.apply_table_layout(table_layout);
+        let stored_view = &vs.type_index.types.get(&table_type).unwrap().instance_view;
+        let restored_view = &restored.type_index.types.get(&table_type).unwrap().instance_view;
+        assert_eq!(123.0, restored_view.iri_width);
+        assert_eq!(stored_view.display_properties.len(), restored_view.display_properties.len());
+        for (stored, restored) in stored_view.display_properties.iter().zip(restored_view.display_properties.iter()) {
+            assert_eq!(stored.predicate_index, restored.predicate_index);
+            assert_eq!(stored.width, restored.width);
+            assert_eq!(stored.visible, restored.visible);
+            assert_eq!(stored.abbreviation, restored.abbreviation);
+            assert_eq!(stored.all_languages, restored.all_languages);
+        }
 
         Ok(())
     }
@@ -0,0 +1,213 @@
+use std::io::Cursor;
+
+use egui::{
+    Color32, ColorImage, Pos2, Rect, TextureId, Vec2,
+    emath::TSTransform,
+    epaint::{ClippedPrimitive, ClippedShape, Primitive, Vertex},
+};
+
+// the biggest exported image, the scale is reduced for bigger graphs
+pub const MAX_PNG_PIXELS: f32 = 64_000_000.0;
+// space around the graph for the whole graph export
+const EXTENT_MARGIN: f32 = 10.0;
+
+#[derive(Clone, Copy)]
+pub struct PngExport {
+    // the whole graph or only the visible area of the graph view
+    pub full_extent: bool,
+    // image pixels per graph point
+    pub scale: f32,
+    pub transparent: bool,
+}
+
+impl Default for PngExport {
+    fn default() -> Self {
+        Self {
+            full_extent: true,
+            scale: 2.0,
+            transparent: false,
+        }
+    }
+}
+
+/// Area of the painted graph shapes
+pub fn shapes_extent(shapes: &[ClippedShape]) -> Rect {
+    let mut extent = Rect::NOTHING;
+    for clipped_shape in shapes.iter() {
+        let bounds = clipped_shape.shape.visual_bounding_rect();
+        if bounds.is_positive() {
+            extent = extent.union(bounds);
+        }
+    }
+    extent.expand(EXTENT_MARGIN)
+}
+
+/// Scale of the image, reduced if the image would be too big
+pub fn png_scale(region: Rect, scale: f32) -> f32 {
+    let area = region.area() * scale * scale;
+    if area > MAX_PNG_PIXELS {
+        scale * (MAX_PNG_PIXELS / area).sqrt()
+    } else {
+        scale
+    }
+}
+
+/**
+ * Renders the shapes painted by the graph view into an image.
+ * The shapes are tessellated by egui as for the screen and the meshes are rasterized in software,
+ * so the image looks the same as the graph view. The node images are not exported,
+ * only the font texture is available outside of the gpu.
+ */
+pub fn render_png(
+    ctx: &egui::Context,
+    shapes: Vec<ClippedShape>,
+    region: Rect,
+    scale: f32,
+    background: Color32,
+) -> ColorImage {
+    let size = [
+        (region.width() * scale).ceil().max(1.0) as usize,
+        (region.height() * scale).ceil().max(1.0) as usize,
+    ];
+    let image_rect = Rect::from_min_size(Pos2::ZERO, Vec2::new(size[0] as f32, size[1] as f32));
+    let transform = TSTransform::new(-region.min.to_vec2() * scale, scale);
+    let shapes: Vec<ClippedShape> = shapes
+        .into_iter()
+        .map(|mut clipped_shape| {
+            clipped_shape.shape.transform(transform);
+            clipped_shape.clip_rect = image_rect;
+            clipped_shape
+        })
+        .collect();
+    let primitives = ctx.tessellate(shapes, 1.0);
+    let font_image = ctx.fonts(|fonts| fonts.image());
+    rasterize(&primitives, &font_image, size, background)
+}
+
+pub fn encode_png(image: &ColorImage) -> image::ImageResult<Vec<u8>> {
+    let raw: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|pixel| pixel.to_srgba_unmultiplied())
+        .collect();
+    let rgba_image = image::RgbaImage::from_raw(image.size[0] as u32, image.size[1] as u32, raw)
+        .expect("image buffer matches the image size");
+    let mut buf = Vec::new();
+    rgba_image.write_to(&mut Cursor::new(&mut buf), image::ImageFormat::Png)?;
+    Ok(buf)
+}
+
+fn rasterize(primitives: &[ClippedPrimitive], font_image: &ColorImage, size: [usize; 2], background: Color32) -> ColorImage {
+    let mut image = ColorImage::new(size, vec![background; size[0] * size[1]]);
+    for clipped_primitive in primitives.iter() {
+        let Primitive::Mesh(mesh) = &clipped_primitive.primitive else {
+            continue;
+        };
+        // all shapes besides images use the white pixel of the font texture
+        if mesh.texture_id != TextureId::default() {
+            continue;
+        }
+        let clip = clipped_primitive.clip_rect;
+        for triangle in mesh.indices.chunks_exact(3) {
+            let a = &mesh.vertices[triangle[0] as usize];
+            let b = &mesh.vertices[triangle[1] as usize];
+            let c = &mesh.vertices[triangle[2] as usize];
+            rasterize_triangle(&mut image, font_image, clip, [a, b, c]);
+        }
+    }
+    image
+}
+
+fn edge_function(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+fn rasterize_triangle(image: &mut ColorImage, texture: &ColorImage, clip: Rect, [a, b, c]: [&Vertex; 3]) {
+    let area = edge_function(a.pos, b.pos, c.pos);
+    if area.abs() < f32::EPSILON {
+        return;
+    }
+    let bounds = Rect::from_points(&[a.pos, b.pos, c.pos]).intersect(clip);
+    if !bounds.is_positive() {
+        return;
+    }
+    let min_x = bounds.min.x.floor().max(0.0) as usize;
+    let min_y = bounds.min.y.floor().max(0.0) as usize;
+    let max_x = (bounds.max.x.ceil() as usize).min(image.size[0]);
+    let max_y = (bounds.max.y.ceil() as usize).min(image.size[1]);
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let wa = edge_function(b.pos, c.pos, p) / area;
+            let wb = edge_function(c.pos, a.pos, p) / area;
+            let wc = edge_function(a.pos, b.pos, p) / area;
+            if wa < 0.0 || wb < 0.0 || wc < 0.0 {
+                continue;
+            }
+            let uv = Pos2::new(
+                wa * a.uv.x + wb * b.uv.x + wc * c.uv.x,
+                wa * a.uv.y + wb * b.uv.y + wc * c.uv.y,
+            );
+            let texel = sample(texture, uv);
+            let mut src = [0.0f32; 4];
+            for (channel, value) in src.iter_mut().enumerate() {
+                let color = wa * a.color[channel] as f32 + wb * b.color[channel] as f32 + wc * c.color[channel] as f32;
+                *value = color * texel[channel] as f32 / 255.0;
+            }
+            let pixel = &mut image.pixels[y * image.size[0] + x];
+            *pixel = blend(*pixel, src);
+        }
+    }
+}
+
+fn sample(texture: &ColorImage, uv: Pos2) -> Color32 {
+    let x = ((uv.x * texture.size[0] as f32) as usize).min(texture.size[0] - 1);
+    let y = ((uv.y * texture.size[1] as f32) as usize).min(texture.size[1] - 1);
+    texture.pixels[y * texture.size[0] + x]
+}
+
+// premultiplied source over the destination
+fn blend(dst: Color32, src: [f32; 4]) -> Color32 {
+    let inverse_alpha = 1.0 - src[3] / 255.0;
+    let channel = |idx: usize| (src[idx] + dst[idx] as f32 * inverse_alpha).round().clamp(0.0, 255.0) as u8;
+    Color32::from_rgba_premultiplied(channel(0), channel(1), channel(2), channel(3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::epaint::{Mesh, WHITE_UV};
+
+    #[test]
+    fn test_rasterize() {
+        let font_image = ColorImage::new([1, 1], vec![Color32::WHITE]);
+        let mut mesh = Mesh::default();
+        mesh.add_colored_rect(
+            Rect::from_min_max(Pos2::new(2.0, 2.0), Pos2::new(6.0, 6.0)),
+            Color32::RED,
+        );
+        assert_eq!(WHITE_UV, mesh.vertices[0].uv);
+        let mut half_transparent = Mesh::default();
+        half_transparent.add_colored_rect(
+            Rect::from_min_max(Pos2::new(4.0, 4.0), Pos2::new(8.0, 8.0)),
+            Color32::from_rgba_premultiplied(0, 0, 128, 128),
+        );
+        let primitives: Vec<ClippedPrimitive> = [mesh, half_transparent]
+            .into_iter()
+            .map(|mesh| ClippedPrimitive {
+                clip_rect: Rect::EVERYTHING,
+                primitive: Primitive::Mesh(mesh),
+            })
+            .collect();
+        let image = rasterize(&primitives, &font_image, [10, 10], Color32::TRANSPARENT);
+        let pixel = |x: usize, y: usize| image.pixels[y * 10 + x];
+        assert_eq!(Color32::TRANSPARENT, pixel(0, 0));
+        assert_eq!(Color32::RED, pixel(3, 3));
+        assert_eq!(Color32::from_rgba_premultiplied(127, 0, 128, 255), pixel(5, 5));
+        assert_eq!(Color32::from_rgba_premultiplied(0, 0, 128, 128), pixel(7, 7));
+        assert_eq!(Color32::TRANSPARENT, pixel(9, 9));
+
+        let png = encode_png(&image).unwrap();
+        assert_eq!(b"\x89PNG", &png[0..4]);
+    }
+}
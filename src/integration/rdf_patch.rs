@@ -0,0 +1,431 @@
+use std::path::Path;
+
+use oxrdf::Triple;
+use oxttl::TurtleParser;
+
+use crate::{
+    domain::{RdfData, import_log::TripleAddResult, named_graphs::TripleGraphs},
+    integration::rdfwrap::{IndexCache, add_triple, remove_triple},
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PatchFormat {
+    RdfPatch,
+    LdPatch,
+}
+
+#[derive(Clone, Debug)]
+pub enum PatchOperation {
+    Add(Triple),
+    Delete(Triple),
+}
+
+/**
+ * Parsed delta file. Supported are RDF Patch (https://afs.github.io/rdf-patch/)
+ * and the Add/Delete operations of LD Patch (https://www.w3.org/TR/ldpatch/).
+ * The operations are kept in file order so the user can preview them before applying.
+ */
+pub struct RdfPatch {
+    pub format: PatchFormat,
+    pub operations: Vec<PatchOperation>,
+    // Parse problems, the valid operations can still be applied
+    pub errors: Vec<String>,
+}
+
+#[derive(Default, Debug)]
+pub struct PatchResult {
+    pub added: u32,
+    pub duplicates: u32,
+    pub filtered: u32,
+    pub deleted: u32,
+    pub not_found: u32,
+    // the operations that changed the data, used for undo
+    pub applied: Vec<PatchOperation>,
+    // named graphs of the deleted triples, used for undo
+    pub deleted_graphs: TripleGraphs,
+}
+
+impl RdfPatch {
+    pub fn parse(file_name: &str, text: &str) -> Self {
+        let extension = Path::new(file_name)
+            .extension()
+            .and_then(|s| s.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        match extension.as_str() {
+            "ldpatch" | "ldp" => Self::parse_ld_patch(text),
+            _ => Self::parse_rdf_patch(text),
+        }
+    }
+
+    pub fn parse_rdf_patch(text: &str) -> Self {
+        let mut patch = RdfPatch {
+            format: PatchFormat::RdfPatch,
+            operations: Vec::new(),
+            errors: Vec::new(),
+        };
+        let mut prefixes: Vec<(String, String)> = Vec::new();
+        let mut transaction: Vec<PatchOperation> = Vec::new();
+        let mut in_transaction = false;
+        for (line_index, line) in text.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (code, rest) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let rest = rest.trim();
+            match code {
+                "H" => {}
+                "TX" => {
+                    in_transaction = true;
+                }
+                "TC" => {
+                    patch.operations.append(&mut transaction);
+                    in_transaction = false;
+                }
+                "TA" => {
+                    transaction.clear();
+                    in_transaction = false;
+                }
+                "PA" => {
+                    let declaration = rest.trim_end_matches('.').trim();
+                    if let Some((name, iri)) = declaration.split_once(char::is_whitespace) {
+                        let name = name.trim_end_matches(':');
+                        let iri = iri.trim().trim_start_matches('<').trim_end_matches('>');
+                        prefixes.retain(|(prefix, _)| prefix != name);
+                        prefixes.push((name.to_string(), iri.to_string()));
+                    } else {
+                        patch.errors.push(format!("Line {}: invalid prefix declaration", line_number));
+                    }
+                }
+                "PD" => {
+                    let name = rest.trim_end_matches('.').trim().trim_end_matches(':');
+                    prefixes.retain(|(prefix, _)| prefix != name);
+                }
+                "A" | "D" => {
+                    let statement = if rest.ends_with('.') {
+                        rest.to_string()
+                    } else {
+                        format!("{} .", rest)
+                    };
+                    for triple in parse_triples(&statement, &prefixes, line_number, &mut patch.errors) {
+                        let operation = if code == "A" {
+                            PatchOperation::Add(triple)
+                        } else {
+                            PatchOperation::Delete(triple)
+                        };
+                        if in_transaction {
+                            transaction.push(operation);
+                        } else {
+                            patch.operations.push(operation);
+                        }
+                    }
+                }
+                _ => {
+                    patch
+                        .errors
+                        .push(format!("Line {}: unknown operation '{}'", line_number, code));
+                }
+            }
+        }
+        if in_transaction && !transaction.is_empty() {
+            patch.errors.push(format!(
+                "Transaction not committed, {} operations ignored",
+                transaction.len()
+            ));
+        }
+        patch
+    }
+
+    pub fn parse_ld_patch(text: &str) -> Self {
+        let mut patch = RdfPatch {
+            format: PatchFormat::LdPatch,
+            operations: Vec::new(),
+            errors: Vec::new(),
+        };
+        let mut prefixes: Vec<(String, String)> = Vec::new();
+        let mut pos = 0;
+        loop {
+            pos = skip_whitespace_and_comments(text, pos);
+            if pos >= text.len() {
+                break;
+            }
+            let line_number = text[..pos].matches('\n').count() + 1;
+            let word_end = text[pos..]
+                .find(|c: char| c.is_whitespace() || c == '{')
+                .map(|offset| pos + offset)
+                .unwrap_or(text.len());
+            let word = &text[pos..word_end];
+            match word {
+                "@prefix" | "PREFIX" | "prefix" => {
+                    let Some(iri_end) = text[word_end..].find('>').map(|offset| word_end + offset) else {
+                        patch.errors.push(format!("Line {}: invalid prefix declaration", line_number));
+                        break;
+                    };
+                    let declaration = text[word_end..iri_end].trim();
+                    if let Some((name, iri)) = declaration.split_once('<') {
+                        let name = name.trim().trim_end_matches(':');
+                        prefixes.retain(|(prefix, _)| prefix != name);
+                        prefixes.push((name.to_string(), iri.to_string()));
+                    }
+                    pos = iri_end + 1;
+                    if word == "@prefix" {
+                        pos = skip_whitespace_and_comments(text, pos);
+                        if text[pos..].starts_with('.') {
+                            pos += 1;
+                        }
+                    }
+                }
+                "Add" | "A" | "AddNew" | "AN" | "Delete" | "D" | "DeleteExisting" | "DE" => {
+                    let block_start = skip_whitespace_and_comments(text, word_end);
+                    if !text[block_start..].starts_with('{') {
+                        patch.errors.push(format!("Line {}: expected '{{' after {}", line_number, word));
+                        break;
+                    }
+                    let Some(block_end) = find_block_end(text, block_start + 1) else {
+                        patch.errors.push(format!("Line {}: missing '}}' for {}", line_number, word));
+                        break;
+                    };
+                    let is_add = word.starts_with('A');
+                    // The last triple in a block does not need to be followed by a dot
+                    let block = text[block_start + 1..block_end].trim();
+                    let statements = if block.ends_with('.') {
+                        block.to_string()
+                    } else {
+                        format!("{} .", block)
+                    };
+                    for triple in parse_triples(
+                        &statements,
+                        &prefixes,
+                        line_number,
+                        &mut patch.errors,
+                    ) {
+                        patch.operations.push(if is_add {
+                            PatchOperation::Add(triple)
+                        } else {
+                            PatchOperation::Delete(triple)
+                        });
+                    }
+                    pos = skip_whitespace_and_comments(text, block_end + 1);
+                    if text[pos..].starts_with('.') {
+                        pos += 1;
+                    }
+                }
+                _ => {
+                    // Bind, Cut and UpdateList need variable and path support which is not available
+                    patch.errors.push(format!(
+                        "Line {}: unsupported LD Patch operation '{}', remaining operations ignored",
+                        line_number, word
+                    ));
+                    break;
+                }
+            }
+        }
+        patch
+    }
+
+    pub fn add_count(&self) -> usize {
+        self.operations
+            .iter()
+            .filter(|operation| matches!(operation, PatchOperation::Add(_)))
+            .count()
+    }
+
+    pub fn delete_count(&self) -> usize {
+        self.operations.len() - self.add_count()
+    }
+
+    pub fn apply(&self, rdf_data: &mut RdfData, language_filter: &[String]) -> PatchResult {
+        let mut result = PatchResult::default();
+        let mut triples_count: u32 = 0;
+        let mut index_cache = IndexCache::new();
+        let (indexer, cache, named_graphs) = rdf_data.node_data.split_graphs_mut();
+        for operation in self.operations.iter() {
+            match operation {
+                PatchOperation::Add(triple) => {
+                    match add_triple(
+                        &mut triples_count,
+                        indexer,
+                        cache,
+                        triple.clone(),
+                        &mut index_cache,
+                        language_filter,
+                        &rdf_data.prefix_manager,
+                    ) {
+                        TripleAddResult::Added => {
+                            result.added += 1;
+                            result.applied.push(operation.clone());
+                        }
+                        TripleAddResult::Duplicate => result.duplicates += 1,
+                        TripleAddResult::Filtered | TripleAddResult::Ignored => result.filtered += 1,
+                    }
+                }
+                PatchOperation::Delete(triple) => {
+                    if let Some(graph_names) = remove_triple(indexer, cache, named_graphs, triple, &rdf_data.prefix_manager) {
+                        result.deleted += 1;
+                        result.applied.push(operation.clone());
+                        if !graph_names.is_empty() {
+                            result.deleted_graphs.push((triple.clone(), graph_names));
+                        }
+                    } else {
+                        result.not_found += 1;
+                    }
+                }
+            }
+        }
+        result
+    }
+}
+
+fn parse_triples(
+    content: &str,
+    prefixes: &[(String, String)],
+    line_number: usize,
+    errors: &mut Vec<String>,
+) -> Vec<Triple> {
+    let mut document = String::with_capacity(content.len() + prefixes.len() * 40);
+    for (prefix, iri) in prefixes {
+        document.push_str(&format!("@prefix {}: <{}> .\n", prefix, iri));
+    }
+    document.push_str(content);
+    let mut triples = Vec::new();
+    for triple in TurtleParser::new().for_slice(document.as_bytes()) {
+        match triple {
+            Ok(triple) => triples.push(triple),
+            Err(e) => {
+                errors.push(format!("Line {}: {}", line_number, e));
+                break;
+            }
+        }
+    }
+    triples
+}
+
+fn skip_whitespace_and_comments(text: &str, mut pos: usize) -> usize {
+    loop {
+        let rest = &text[pos..];
+        let trimmed = rest.trim_start();
+        pos += rest.len() - trimmed.len();
+        if trimmed.starts_with('#') {
+            pos += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else {
+            return pos;
+        }
+    }
+}
+
+// Find the closing brace, braces inside of strings and IRIs are ignored
+fn find_block_end(text: &str, start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string: Option<char> = None;
+    let mut in_iri = false;
+    let mut escaped = false;
+    for (offset, c) in text[start..].char_indices() {
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                in_string = None;
+            }
+            continue;
+        }
+        if in_iri {
+            if c == '>' {
+                in_iri = false;
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => in_string = Some(c),
+            '<' => in_iri = true,
+            '{' => depth += 1,
+            '}' => {
+                if depth == 0 {
+                    return Some(start + offset);
+                }
+                depth -= 1;
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    fn test_data() -> RdfData {
+        RdfData::from_patch(
+            r#"
+A <http://example.org/a> <http://example.org/knows> <http://example.org/b> .
+A <http://example.org/a> <http://example.org/name> "A" .
+"#,
+        )
+    }
+
+    #[test]
+    fn test_rdf_patch() {
+        let mut rdf_data = test_data();
+        let patch = RdfPatch::parse_rdf_patch(
+            r#"
+H id <uuid:0001> .
+TX .
+PA ex: <http://example.org/> .
+D ex:a ex:knows ex:b .
+D ex:a ex:name "A" .
+A ex:a ex:name "B" .
+A ex:a ex:name "B" .
+D ex:a ex:name "C" .
+TC .
+TX .
+A ex:c ex:name "aborted" .
+TA .
+"#,
+        );
+        assert!(patch.errors.is_empty(), "{:?}", patch.errors);
+        assert_eq!(5, patch.operations.len());
+        let result = patch.apply(&mut rdf_data, &[]);
+        assert_eq!(1, result.added);
+        assert_eq!(1, result.duplicates);
+        assert_eq!(2, result.deleted);
+        assert_eq!(1, result.not_found);
+        let a = rdf_data.node_data.get_node("http://example.org/a").unwrap();
+        assert!(a.references.is_empty());
+        assert_eq!(1, a.properties.len());
+        let b = rdf_data.node_data.get_node("http://example.org/b").unwrap();
+        assert!(b.reverse_references.is_empty());
+        assert!(rdf_data.node_data.get_node("http://example.org/c").is_none());
+    }
+
+    #[test]
+    fn test_ld_patch() {
+        let mut rdf_data = test_data();
+        let patch = RdfPatch::parse_ld_patch(
+            r#"
+@prefix ex: <http://example.org/> .
+# comment with { brace
+Delete { ex:a ex:name "A" } .
+Add {
+    ex:a ex:name "{braced}" ;
+        ex:knows ex:c .
+} .
+Cut ?x .
+Add { ex:a ex:name "ignored" } .
+"#,
+        );
+        assert_eq!(PatchFormat::LdPatch, patch.format);
+        assert_eq!(3, patch.operations.len());
+        assert_eq!(1, patch.delete_count());
+        assert_eq!(1, patch.errors.len());
+        let result = patch.apply(&mut rdf_data, &[]);
+        assert_eq!(2, result.added);
+        assert_eq!(1, result.deleted);
+        let a = rdf_data.node_data.get_node("http://example.org/a").unwrap();
+        assert_eq!(2, a.references.len());
+    }
+}
@@ -1,24 +1,38 @@
-use oxrdf::{NamedNode, NamedOrBlankNode};
+use oxrdf::{GraphName, NamedNode, NamedOrBlankNode, Quad};
 use oxrdf::vocab::xsd;
 use oxrdf::{NamedNodeRef, Term, Triple, vocab::rdf};
 use oxrdfxml::RdfXmlParser;
 use oxttl::TurtleParser;
 
 use crate::IriIndex;
-use crate::domain::{Literal, NObject, NodeData, PredicateReference};
+use crate::domain::{DataTypeIndex, Indexers, LangIndex, Literal, NObject, NodeData, PredicateLiteral, PredicateReference};
+use crate::domain::import_log::{ImportReport, TripleAddResult};
+use crate::domain::named_graphs::{NamedGraphs, TripleGraphs, TripleKey};
 use crate::domain::prefix_manager::PrefixManager;
 use crate::domain::RdfData;
-use crate::uistate::{DataLoading, ImportFormat};
+use crate::integration::jsonld::jsonld_parser;
+use crate::uistate::DataLoading;
+use std::collections::HashSet;
 use std::fs::{self, File};
+use std::hash::{BuildHasherDefault, Hasher};
 use std::io::{self, BufReader, Read};
 use std::path::Path;
+use std::ops::{Deref, DerefMut};
 use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock, RwLockWriteGuard};
 
 use anyhow::{Context, Result};
+use string_interner::Symbol;
+use xxhash_rust::xxh3::{xxh3_64, xxh3_64_with_seed};
 use std::time::Instant;
 
 const SHORT_STR_LITERAL_LEN: usize = 32;
+// parsed items are passed and merged in chunks, the data lock is released between the chunks
+#[cfg(not(target_arch = "wasm32"))]
+const PARSE_CHUNK_SIZE: usize = 10_000;
+// bounds the memory used by parsed but not yet merged triples
+#[cfg(not(target_arch = "wasm32"))]
+const PARSE_CHUNKS_IN_FLIGHT: usize = 4;
 
 pub trait RDFAdapter {
     fn load_object(&mut self, iri: &str, node_data: &mut NodeData) -> Option<NObject>;
@@ -29,6 +43,130 @@ pub struct RDFWrap {}
 pub struct IndexCache {
     pub index: IriIndex,
     pub iri: String,
+    hashes: SubjectHashes,
+}
+
+impl Default for IndexCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IndexCache {
+    pub fn new() -> Self {
+        IndexCache {
+            index: 0,
+            iri: String::with_capacity(100),
+            hashes: SubjectHashes::default(),
+        }
+    }
+}
+
+// subjects with fewer values are checked for duplicates by comparing the values
+const HASHED_SUBJECT_VALUES: usize = 32;
+
+/**
+ * Hashes of the triples of the subject that is currently added to by a load or merge.
+ * A new hash means a new triple, so the values of a big subject are only compared for the rare seen hashes.
+ * Without it the duplicate check costs O(k²) for a subject with k values (e.g. concept schemes or big lists).
+ * Only one subject is hashed, the memory is bounded by the values of the biggest subject and not by the file size.
+ * The triples of a subject mostly come one after another, a subject is hashed again if it is continued later.
+ */
+#[derive(Default)]
+struct SubjectHashes {
+    subject: Option<IriIndex>,
+    hashes: HashSet<u64, BuildHasherDefault<HashIdentity>>,
+}
+
+impl SubjectHashes {
+    /// Remembers the hash, returns true if the triple can be a duplicate and the values have to be compared
+    fn seen(&mut self, indexer: &Indexers, cache: &crate::domain::NodeCache, node_index: IriIndex, hash: u64) -> bool {
+        if self.subject != Some(node_index) {
+            let Some((_iri, node)) = cache.get_node_by_index(node_index) else {
+                return true;
+            };
+            if node.references.len() + node.properties.len() < HASHED_SUBJECT_VALUES {
+                return true;
+            }
+            self.subject = Some(node_index);
+            self.hashes.clear();
+            for reference in node.references.iter() {
+                self.hashes.insert(reference_hash(node_index, *reference));
+            }
+            for (predicate_index, literal) in node.properties.iter() {
+                self.hashes.insert(property_hash(
+                    node_index,
+                    *predicate_index,
+                    LiteralKind::of(literal),
+                    literal.as_str_ref(indexer),
+                ));
+            }
+        }
+        !self.hashes.insert(hash)
+    }
+}
+
+// the keys are already xxh3 hashes
+#[derive(Default)]
+struct HashIdentity(u64);
+
+impl Hasher for HashIdentity {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ *byte as u64;
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.0 = value;
+    }
+}
+
+// literals with the same value are duplicates only if they have the same kind
+#[derive(Clone, Copy, PartialEq)]
+enum LiteralKind {
+    Plain,
+    Lang(LangIndex),
+    Typed(DataTypeIndex),
+    NoValue,
+}
+
+impl LiteralKind {
+    fn of(literal: &Literal) -> Self {
+        match literal {
+            Literal::StringShort(_) | Literal::String(_) => LiteralKind::Plain,
+            Literal::LangString(language_index, _) => LiteralKind::Lang(*language_index),
+            Literal::TypedString(data_type_index, _) => LiteralKind::Typed(*data_type_index),
+            Literal::NoValue() => LiteralKind::NoValue,
+        }
+    }
+}
+
+fn reference_hash(node_index: IriIndex, (predicate_index, reference_index): PredicateReference) -> u64 {
+    let mut key = [0u8; 12];
+    key[0..4].copy_from_slice(&node_index.to_le_bytes());
+    key[4..8].copy_from_slice(&predicate_index.to_le_bytes());
+    key[8..12].copy_from_slice(&reference_index.to_le_bytes());
+    xxh3_64(&key)
+}
+
+fn property_hash(node_index: IriIndex, predicate_index: IriIndex, kind: LiteralKind, value: &str) -> u64 {
+    let (tag, qualifier) = match kind {
+        LiteralKind::Plain => (1u8, 0),
+        LiteralKind::Lang(language_index) => (2, language_index),
+        LiteralKind::Typed(data_type_index) => (3, data_type_index),
+        LiteralKind::NoValue => (4, 0),
+    };
+    let mut key = [0u8; 11];
+    key[0..4].copy_from_slice(&node_index.to_le_bytes());
+    key[4..8].copy_from_slice(&predicate_index.to_le_bytes());
+    key[8] = tag;
+    key[9..11].copy_from_slice(&qualifier.to_le_bytes());
+    xxh3_64_with_seed(value.as_bytes(), xxh3_64(&key))
 }
 
 pub struct CountingReader<R> {
@@ -55,14 +193,108 @@ impl<R: Read> Read for CountingReader<R> {
 
 pub enum ParseItem {
     Triple(Result<Triple, io::Error>),
+    Quad(Quad),
     Prefix(String, String),
 }
 
+/**
+ * Data the triples are loaded into.
+ * Shared data are locked only for merging one chunk of triples, so other threads can read the data during a long import.
+ */
+pub enum LoadTarget<'a> {
+    Data(&'a mut RdfData),
+    Shared(&'a RwLock<RdfData>),
+}
+
+pub enum LoadGuard<'a> {
+    Data(&'a mut RdfData),
+    Shared(RwLockWriteGuard<'a, RdfData>),
+}
+
+impl<'a> From<&'a mut RdfData> for LoadTarget<'a> {
+    fn from(rdf_data: &'a mut RdfData) -> Self {
+        LoadTarget::Data(rdf_data)
+    }
+}
+
+impl<'a> From<&'a RwLock<RdfData>> for LoadTarget<'a> {
+    fn from(rdf_data: &'a RwLock<RdfData>) -> Self {
+        LoadTarget::Shared(rdf_data)
+    }
+}
+
+impl LoadTarget<'_> {
+    pub fn reborrow(&mut self) -> LoadTarget<'_> {
+        match self {
+            LoadTarget::Data(rdf_data) => LoadTarget::Data(rdf_data),
+            LoadTarget::Shared(rdf_data) => LoadTarget::Shared(rdf_data),
+        }
+    }
+
+    pub fn lock(&mut self) -> Result<LoadGuard<'_>> {
+        match self {
+            LoadTarget::Data(rdf_data) => Ok(LoadGuard::Data(rdf_data)),
+            LoadTarget::Shared(rdf_data) => rdf_data
+                .write()
+                .map(LoadGuard::Shared)
+                .map_err(|_| anyhow::anyhow!("RDF data are not accessible (poisoned lock)")),
+        }
+    }
+}
+
+impl Deref for LoadGuard<'_> {
+    type Target = RdfData;
+
+    fn deref(&self) -> &RdfData {
+        match self {
+            LoadGuard::Data(rdf_data) => rdf_data,
+            LoadGuard::Shared(rdf_data) => rdf_data,
+        }
+    }
+}
+
+impl DerefMut for LoadGuard<'_> {
+    fn deref_mut(&mut self) -> &mut RdfData {
+        match self {
+            LoadGuard::Data(rdf_data) => rdf_data,
+            LoadGuard::Shared(rdf_data) => rdf_data,
+        }
+    }
+}
+
+/// Collects the parse items and sends them in chunks, the rest is sent on drop
+#[cfg(not(target_arch = "wasm32"))]
+struct ChunkSender {
+    sender: std::sync::mpsc::SyncSender<Vec<ParseItem>>,
+    chunk: Vec<ParseItem>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ChunkSender {
+    fn send(&mut self, parse_item: ParseItem) -> std::result::Result<(), std::sync::mpsc::SendError<Vec<ParseItem>>> {
+        self.chunk.push(parse_item);
+        if self.chunk.len() >= PARSE_CHUNK_SIZE {
+            let chunk = std::mem::replace(&mut self.chunk, Vec::with_capacity(PARSE_CHUNK_SIZE));
+            self.sender.send(chunk)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for ChunkSender {
+    fn drop(&mut self) {
+        if !self.chunk.is_empty() {
+            let _ = self.sender.send(std::mem::take(&mut self.chunk));
+        }
+    }
+}
+
 fn collect_rdf_files(dir_name: &str, files: &mut Vec<String>) -> Result<()> {
     let entries = fs::read_dir(dir_name).with_context(|| format!("Failed to read directory {}", dir_name));
     match entries {
         Err(e) => {
-            eprintln!("Error reading dir {}: {}", dir_name, e)
+            log::error!("Error reading dir {}: {}", dir_name, e)
         }
         Ok(entries) => {
             for entry in entries {
@@ -72,7 +304,7 @@ fn collect_rdf_files(dir_name: &str, files: &mut Vec<String>) -> Result<()> {
                 if path.is_dir() {
                     collect_rdf_files(path_name, files)?;
                 } else if let Some(extension) = path.extension() {
-                    if ["ttl", "rdf", "xml", "nt", "nq", "trig","jsonld"].contains(&extension.to_str().unwrap()) {
+                    if ["ttl", "rdf", "xml", "nt", "nq", "trig","jsonld","hdt"].contains(&extension.to_str().unwrap()) {
                         files.push(path_name.to_string());
                     }
                 }
@@ -87,10 +319,22 @@ impl RDFWrap {
         RDFWrap {}
     }
 
-    pub fn load_from_dir(dir_name: &str, rdf_data: &mut RdfData, language_filter: &[String], data_loading: Option<&DataLoading>) -> Result<u32> {
+    pub fn load_from_dir<'a>(dir_name: &str, rdf_data: impl Into<LoadTarget<'a>>, language_filter: &[String], data_loading: Option<&DataLoading>) -> Result<u32> {
+        Self::load_paths(&[dir_name.to_string()], rdf_data, language_filter, data_loading)
+    }
+
+    /// Loads the files one after another into the same data, directories are searched for rdf files
+    pub fn load_paths<'a>(paths: &[String], rdf_data: impl Into<LoadTarget<'a>>, language_filter: &[String], data_loading: Option<&DataLoading>) -> Result<u32> {
+        let mut target = rdf_data.into();
         let mut total_triples = 0;
         let mut files = Vec::new();
-        collect_rdf_files(dir_name, &mut files)?;
+        for path in paths {
+            if Path::new(path).is_dir() {
+                collect_rdf_files(path, &mut files)?;
+            } else {
+                files.push(path.clone());
+            }
+        }
         if let Some(data_loading) = data_loading {
             let mut size_total = 0;
             for file in &files {
@@ -100,21 +344,34 @@ impl RDFWrap {
             data_loading.total_size.store(size_total, std::sync::atomic::Ordering::Relaxed);
         }   
         for file in &files {
-            match RDFWrap::load_file(file, rdf_data, language_filter, data_loading) {
+            match RDFWrap::load_file(file, target.reborrow(), language_filter, data_loading) {
                 Ok(triples) => {
                     total_triples += triples;
                 }
                 Err(e) => {
-                    eprintln!("Error processing file {}: {}", file, e);
+                    log::error!("Error processing file {}: {}", file, e);
+                    if let Some(data_loading) = data_loading {
+                        // Files that could not be opened at all have no report yet
+                        let has_report = data_loading
+                            .import_reports
+                            .lock()
+                            .map(|reports| reports.iter().any(|report| report.source == *file))
+                            .unwrap_or(true);
+                        if !has_report {
+                            let mut report = ImportReport::new(file);
+                            report.add_error(e.to_string());
+                            data_loading.add_report(report);
+                        }
+                    }
                 }
             }
         }
         Ok(total_triples)
     }
 
-    pub fn load_file<P: AsRef<Path>>(
+    pub fn load_file<'a, P: AsRef<Path>>(
         file_name: P,
-        rdf_data: &mut RdfData,
+        rdf_data: impl Into<LoadTarget<'a>>,
         language_filter: &[String],
         data_loading: Option<&DataLoading>,
     ) -> Result<u32> {
@@ -140,102 +397,61 @@ impl RDFWrap {
         let file_base_name = file_name.file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("data");
-        Self::load_file_reader(file_extension, file_base_name, reader, rdf_data, language_filter, data_loading)
-    }
-
-    #[cfg(not(target_arch = "wasm32"))]
-    pub fn load_from_url(
-        url: &str,
-        rdf_data: &mut RdfData,
-        language_filter: &[String],
-        format: ImportFormat,
-        data_loading: Option<&DataLoading>,
-    ) -> Result<u32> {
-        use reqwest::{Url, blocking::Client};
-
-        let client = Client::new();
-        let response = client
-            .get(url)
-            .header("Accept", format.mime_type())
-            .send()
-            .with_context(|| format!("Failed to fetch URL {}", url))?;
-        if !response.status().is_success() {
-            return Err(anyhow::anyhow!(
-                "Failed to fetch URL {}: HTTP {}",
-                url,
-                response.status()
-            ));
-        }
-        if let Some(data_loading) = data_loading {
-            if data_loading.total_size.load(std::sync::atomic::Ordering::Relaxed) == 0 {
-                if let Some(content_length) = response.content_length() {
-                    data_loading
-                        .total_size
-                        .store(content_length as usize, std::sync::atomic::Ordering::Relaxed);
-                } else {
-                    println!("Content-Length header not found for URL {}", url);
-                }
-            }
-        }
-        let reader = BufReader::new(response);
-        let url = Url::parse(url).unwrap();
-        let last_segment = url
-            .path_segments()
-            .and_then(|segments| segments.last()).unwrap_or("url");
-
-        Self::load_file_reader(format.file_extension(),last_segment, reader, rdf_data, language_filter, data_loading)
-    }
-
-    #[cfg(target_arch = "wasm32")]
-    pub fn load_from_url(
-        url: &str,
-        rdf_data: &mut RdfData,
-        language_filter: &[String],
-        format: ImportFormat,
-        data_loading: Option<&DataLoading>,
-    ) -> Result<u32> {
-        Ok(0)
+        Self::load_file_reader(
+            &file_name.display().to_string(),
+            file_extension,
+            file_base_name,
+            reader,
+            rdf_data,
+            language_filter,
+            data_loading,
+        )
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub fn load_file_data(
+    pub fn load_file_data<'a>(
         file_name: &str,
         data: &Vec<u8>,
-        rdf_data: &mut RdfData,
+        rdf_data: impl Into<LoadTarget<'a>>,
         language_filter: &[String],
+        data_loading: Option<&DataLoading>,
     ) -> Result<u32> {
+        let source = file_name;
         let file_name = Path::new(file_name);
         let reader = io::Cursor::new(data);
         let file_extension = file_name.extension().and_then(|s| s.to_str()).unwrap_or("");
         let file_base_name = file_name.file_stem()
             .and_then(|s| s.to_str())
             .unwrap_or("data");
-        Self::load_file_reader(file_extension, file_base_name, reader, rdf_data, language_filter, None)
+        Self::load_file_reader(source, file_extension, file_base_name, reader, rdf_data, language_filter, data_loading)
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub fn load_file_reader<R: std::io::Read>(
+    pub fn load_file_reader<'a, R: std::io::Read>(
+        source: &str,
         file_extension: &str,
         file_base: &str,
         reader: R,
-        rdf_data: &mut RdfData,
+        rdf_data: impl Into<LoadTarget<'a>>,
         language_filter: &[String],
         data_loading: Option<&DataLoading>,
     ) -> Result<u32> {
+        // there is no other thread in the web version, the data are locked for the whole import
+        let mut target = rdf_data.into();
+        let mut rdf_data = target.lock()?;
+        let rdf_data = &mut *rdf_data;
         let mut triples_count: u32 = 0;
-        let (indexer, cache) = rdf_data.node_data.split_mut();
+        let (indexer, cache, named_graphs) = rdf_data.node_data.split_graphs_mut();
         #[cfg(not(target_arch = "wasm32"))]
         let start = Instant::now();
-        let mut index_cache = IndexCache {
-            index: 0,
-            iri: String::with_capacity(100),
-        };
+        let mut index_cache = IndexCache::new();
         let bytes_read = Arc::new(AtomicUsize::new(if let Some(data_loading) = data_loading {
             data_loading.read_pos.load(std::sync::atomic::Ordering::Relaxed)
         } else {
             0
         }));
         let counting_reader = reader;
+        let mut report = ImportReport::new(source);
         match file_extension {
             "ttl" => {
                 let mut parser = TurtleParser::new().for_reader(counting_reader);
@@ -249,7 +465,7 @@ impl RDFWrap {
                     }
                     match triple {
                         Ok(triple) => {
-                            add_triple(
+                            report.record(add_triple(
                                 &mut triples_count,
                                 indexer,
                                 cache,
@@ -257,10 +473,11 @@ impl RDFWrap {
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
@@ -271,6 +488,7 @@ impl RDFWrap {
                 while let Some(triple) = parser.next() {
                     if let Some(data_loading) = data_loading {
                         if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
                             break;
                         }
                         data_loading
@@ -288,7 +506,7 @@ impl RDFWrap {
                     }
                     match triple {
                         Ok(triple) => {
-                            add_triple(
+                            report.record(add_triple(
                                 &mut triples_count,
                                 indexer,
                                 cache,
@@ -296,10 +514,11 @@ impl RDFWrap {
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
@@ -309,6 +528,7 @@ impl RDFWrap {
                 for triple in parser {
                     if let Some(data_loading) = data_loading {
                         if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
                             break;
                         }
                         data_loading
@@ -320,7 +540,7 @@ impl RDFWrap {
                     }
                     match triple {
                         Ok(triple) => {
-                            add_triple(
+                            report.record(add_triple(
                                 &mut triples_count,
                                 indexer,
                                 cache,
@@ -328,10 +548,11 @@ impl RDFWrap {
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
@@ -342,6 +563,7 @@ impl RDFWrap {
                 while let Some(quad) = parser.next() {
                     if let Some(data_loading) = data_loading {
                         if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
                             break;
                         }
                         data_loading
@@ -359,18 +581,20 @@ impl RDFWrap {
                     }
                     match quad {
                         Ok(quad) => {
-                            add_triple(
+                            report.record(add_quad(
                                 &mut triples_count,
                                 indexer,
                                 cache,
-                                Triple::from(quad),
+                                named_graphs,
+                                quad,
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
@@ -380,6 +604,7 @@ impl RDFWrap {
                 for quad in parser {
                     if let Some(data_loading) = data_loading {
                         if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
                             break;
                         }
                         data_loading
@@ -391,27 +616,37 @@ impl RDFWrap {
                     }
                     match quad {
                         Ok(quad) => {
-                            add_triple(
+                            report.record(add_quad(
                                 &mut triples_count,
                                 indexer,
                                 cache,
-                                Triple::from(quad),
+                                named_graphs,
+                                quad,
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
             }
             "jsonld" => {
-                let parser = oxjsonld::JsonLdParser::new().for_reader(counting_reader);
-                                for quad in parser {
+                let mut parser = jsonld_parser(source, counting_reader);
+                let mut prefix_read = false;
+                while let Some(quad) = parser.next() {
+                    if !prefix_read {
+                        for (prefix, iri) in parser.prefixes() {
+                            rdf_data.prefix_manager.add_prefix(prefix, iri);
+                        }
+                        prefix_read = true;
+                    }
                     if let Some(data_loading) = data_loading {
                         if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
                             break;
                         }
                         data_loading
@@ -423,18 +658,20 @@ impl RDFWrap {
                     }
                     match quad {
                         Ok(quad) => {
-                            add_triple(
+                            report.record(add_quad(
                                 &mut triples_count,
                                 indexer,
                                 cache,
-                                Triple::from(quad),
+                                named_graphs,
+                                quad,
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
@@ -445,6 +682,42 @@ impl RDFWrap {
                 for triple in parser {
                     if let Some(data_loading) = data_loading {
                         if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
+                            break;
+                        }
+                        data_loading
+                            .total_triples
+                            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        data_loading
+                            .read_pos
+                            .store(bytes_read.load(Ordering::Relaxed), std::sync::atomic::Ordering::Relaxed);
+                    }
+                    match triple {
+                        Ok(triple) => {
+                            report.record(add_triple(
+                                &mut triples_count,
+                                indexer,
+                                cache,
+                                triple,
+                                &mut index_cache,
+                                language_filter,
+                                &rdf_data.prefix_manager,
+                            ));
+                        }
+                        Err(e) => {
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
+                        }
+                    }
+                }
+            },
+            "hdt" => {
+                use crate::integration::hdt2rdf::HdtRdfParser;
+                let parser = HdtRdfParser::for_reader(counting_reader);
+                for triple in parser {
+                    if let Some(data_loading) = data_loading {
+                        if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
+                            report.stopped = true;
                             break;
                         }
                         data_loading
@@ -456,7 +729,7 @@ impl RDFWrap {
                     }
                     match triple {
                         Ok(triple) => {
-                            add_triple(
+                            report.record(add_triple(
                                 &mut triples_count,
                                 indexer,
                                 cache,
@@ -464,10 +737,11 @@ impl RDFWrap {
                                 &mut index_cache,
                                 language_filter,
                                 &rdf_data.prefix_manager,
-                            );
+                            ));
                         }
                         Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
+                            log::warn!("Error parsing triple: {}", e);
+                            report.add_error(e.to_string());
                         }
                     }
                 }
@@ -480,7 +754,7 @@ impl RDFWrap {
                         ParseItem::Triple(triple) => {
                             match triple {
                                 Ok(triple) => {
-                                    add_triple(
+                                    report.record(add_triple(
                                         &mut triples_count,
                                         indexer,
                                         cache,
@@ -488,10 +762,11 @@ impl RDFWrap {
                                         &mut index_cache,
                                         language_filter,
                                         &rdf_data.prefix_manager,
-                                    );
+                                    ));
                                 }
                                 Err(e) => {
-                                    eprintln!("Error parsing json: {}", e);
+                                    log::warn!("Error parsing json: {}", e);
+                                    report.add_error(e.to_string());
                                 }
                             }
                         }
@@ -502,7 +777,8 @@ impl RDFWrap {
                 });
                 match res {
                     Err(e) => {
-                        eprintln!("Error parsing json: {}", e);
+                        log::warn!("Error parsing json: {}", e);
+                        report.add_error(e.to_string());
                     }
                     Ok(_) => {
 
@@ -518,7 +794,7 @@ impl RDFWrap {
                         ParseItem::Triple(triple) => {
                             match triple {
                                 Ok(triple) => {
-                                    add_triple(
+                                    report.record(add_triple(
                                         &mut triples_count,
                                         indexer,
                                         cache,
@@ -526,10 +802,11 @@ impl RDFWrap {
                                         &mut index_cache,
                                         language_filter,
                                         &rdf_data.prefix_manager,
-                                    );
+                                    ));
                                 }
                                 Err(e) => {
-                                    eprintln!("Error parsing json: {}", e);
+                                    log::warn!("Error parsing json: {}", e);
+                                    report.add_error(e.to_string());
                                 }
                             }
                         }
@@ -540,7 +817,8 @@ impl RDFWrap {
                 });
                 match res {
                     Err(e) => {
-                        eprintln!("Error parsing xml: {}", e);
+                        log::warn!("Error parsing xml: {}", e);
+                        report.add_error(e.to_string());
                     }
                     Ok(_) => {
 
@@ -555,7 +833,7 @@ impl RDFWrap {
                         ParseItem::Triple(triple) => {
                             match triple {
                                 Ok(triple) => {
-                                    add_triple(
+                                    report.record(add_triple(
                                         &mut triples_count,
                                         indexer,
                                         cache,
@@ -563,10 +841,11 @@ impl RDFWrap {
                                         &mut index_cache,
                                         language_filter,
                                         &rdf_data.prefix_manager,
-                                    );
+                                    ));
                                 }
                                 Err(e) => {
-                                    eprintln!("Error parsing json: {}", e);
+                                    log::warn!("Error parsing json: {}", e);
+                                    report.add_error(e.to_string());
                                 }
                             }
                         }
@@ -577,7 +856,8 @@ impl RDFWrap {
                 });
                 match res {
                     Err(e) => {
-                        eprintln!("Error parsing ndjson: {}", e);
+                        log::warn!("Error parsing ndjson: {}", e);
+                        report.add_error(e.to_string());
                     }
                     Ok(_) => {
 
@@ -594,47 +874,57 @@ impl RDFWrap {
         #[cfg(not(target_arch = "wasm32"))]
         {
             let duration = start.elapsed();
-            println!("Time taken to read the file {:?}", duration);
-            println!(
-                "Triples read per second: {}",
+            report.duration = duration;
+            log::info!(
+                "Read {} triples from {} in {:?} ({:.0} triples per second)",
+                triples_count,
+                source,
+                duration,
                 triples_count as f64 / duration.as_secs_f64()
             );
         }
+        if let Some(data_loading) = data_loading {
+            data_loading.add_report(report);
+        }
         Ok(triples_count)
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn load_file_reader<R: std::io::Read + std::marker::Send + 'static>(
+    pub fn load_file_reader<'a, R: std::io::Read + std::marker::Send + 'static>(
+        source: &str,
         file_extension: &str,
         file_base: &str,
         reader: R,
-        rdf_data: &mut RdfData,
+        rdf_data: impl Into<LoadTarget<'a>>,
         language_filter: &[String],
         data_loading: Option<&DataLoading>,
     ) -> Result<u32> {
         // This function uses 2 stages to parse and process RDF data
         // The parsing is done in a separate thread and parse items are send to main thread via a channel.
+        // The items are streamed in chunks, so the memory does not depend on the file size
+        // and shared data are only locked while a chunk is merged.
         use std::{sync::mpsc, thread};
 
-
+        let mut target = rdf_data.into();
         let mut triples_count: u32 = 0;
-        let (indexer, cache) = rdf_data.node_data.split_mut();
         let start = Instant::now();
-        let mut index_cache = IndexCache {
-            index: 0,
-            iri: String::with_capacity(100),
-        };
+        let mut index_cache = IndexCache::new();
         let bytes_read= Arc::new(AtomicUsize::new(if let Some(data_loading) = data_loading {
             data_loading.read_pos.load(std::sync::atomic::Ordering::Relaxed)
         } else {
             0
         }));
-        let (tx, rx) = mpsc::sync_channel(1000);
+        let (sender, rx) = mpsc::sync_channel(PARSE_CHUNKS_IN_FLIGHT);
 
         let bytes_read_tx = Arc::clone(&bytes_read);
         let file_extension = file_extension.to_string();
         let file_base = file_base.to_string();
+        let base_source = source.to_string();
         let handle = thread::spawn(move || {
+            let mut tx = ChunkSender {
+                sender,
+                chunk: Vec::with_capacity(PARSE_CHUNK_SIZE),
+            };
             let counting_reader = CountingReader::new(reader, bytes_read_tx);
             match file_extension.as_str() {
                 "ttl" => {
@@ -720,7 +1010,7 @@ impl RDFWrap {
                         }
                         match quad {
                             Ok(quad) => {
-                                if tx.send(ParseItem::Triple(Ok(Triple::from(quad)))).is_err() {
+                                if tx.send(ParseItem::Quad(quad)).is_err() {
                                     break;
                                 }
                             }
@@ -737,7 +1027,7 @@ impl RDFWrap {
                     for quad in parser {
                         match quad {
                             Ok(quad) => {
-                                if tx.send(ParseItem::Triple(Ok(Triple::from(quad)))).is_err() {
+                                if tx.send(ParseItem::Quad(quad)).is_err() {
                                     break;
                                 }
                             }
@@ -750,11 +1040,20 @@ impl RDFWrap {
                     }
                 },
                 "jsonld" => {
-                    let parser = oxjsonld::JsonLdParser::new().for_reader(counting_reader);
-                    for quad in parser {
+                    let mut parser = jsonld_parser(&base_source, counting_reader);
+                    let mut prefix_read = false;
+                    while let Some(quad) = parser.next() {
+                        if !prefix_read {
+                            for (prefix, iri) in parser.prefixes() {
+                                if tx.send(ParseItem::Prefix(prefix.to_string(), iri.to_string())).is_err() {
+                                    break;
+                                }
+                            }
+                            prefix_read = true;
+                        }
                         match quad {
                             Ok(quad) => {
-                                if tx.send(ParseItem::Triple(Ok(Triple::from(quad)))).is_err() {
+                                if tx.send(ParseItem::Quad(quad)).is_err() {
                                     break;
                                 }
                             }
@@ -784,6 +1083,15 @@ impl RDFWrap {
                         }
                     }
                 },
+                "hdt" => {
+                    use crate::integration::hdt2rdf::HdtRdfParser;
+                    let parser = HdtRdfParser::for_reader(BufReader::new(counting_reader));
+                    for triple in parser {
+                        if tx.send(ParseItem::Triple(triple)).is_err() {
+                            break;
+                        }
+                    }
+                },
                 "json" => {
                     use crate::integration::json2rdf::JsonRdfParser;
                     let mut parser = JsonRdfParser::for_reader(counting_reader, file_base);
@@ -840,50 +1148,95 @@ impl RDFWrap {
             Ok(())
         });
 
-        for parse_item in rx {
-            if let Some(data_loading) = data_loading {
-                if data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed) {
-                    println!("Stopping loading due to user request");
+        let mut report = ImportReport::new(source);
+        let mut lock_error = None;
+        for chunk in rx {
+            if let Some(data_loading) = data_loading
+                && data_loading.stop_loading.load(std::sync::atomic::Ordering::Relaxed)
+            {
+                log::info!("Stopping loading due to user request");
+                report.stopped = true;
+                break;
+            }
+            let mut rdf_data = match target.lock() {
+                Ok(rdf_data) => rdf_data,
+                Err(e) => {
+                    lock_error = Some(e);
                     break;
                 }
+            };
+            let rdf_data = &mut *rdf_data;
+            let (indexer, cache, named_graphs) = rdf_data.node_data.split_graphs_mut();
+            let chunk_len = chunk.len();
+            for parse_item in chunk {
+                match parse_item {
+                    ParseItem::Prefix(prefix, iri) => {
+                        rdf_data.prefix_manager.add_prefix(&prefix, &iri);
+                    }
+                    ParseItem::Triple(triple) => {
+                        match triple {
+                            Ok(triple) => {
+                                report.record(add_triple(
+                                    &mut triples_count,
+                                    indexer,
+                                    cache,
+                                    triple,
+                                    &mut index_cache,
+                                    language_filter,
+                                    &rdf_data.prefix_manager,
+                                ));
+                            }
+                            Err(e) => {
+                                log::warn!("Error parsing triple: {}", e);
+                                report.add_error(e.to_string());
+                            }
+                        }
+                    }
+                    ParseItem::Quad(quad) => {
+                        report.record(add_quad(
+                            &mut triples_count,
+                            indexer,
+                            cache,
+                            named_graphs,
+                            quad,
+                            &mut index_cache,
+                            language_filter,
+                            &rdf_data.prefix_manager,
+                        ));
+                    }
+                }
+            }
+            if let Some(data_loading) = data_loading {
                 data_loading
                     .total_triples
-                    .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    .fetch_add(chunk_len, std::sync::atomic::Ordering::Relaxed);
+                data_loading
+                    .total_nodes
+                    .store(rdf_data.node_data.len(), std::sync::atomic::Ordering::Relaxed);
                 let readed = bytes_read.load(Ordering::Relaxed);
                 data_loading
                     .read_pos
                     .store(readed, std::sync::atomic::Ordering::Relaxed);
             }
-            match parse_item {
-                ParseItem::Prefix(prefix, iri) => {
-                    rdf_data.prefix_manager.add_prefix(&prefix, &iri);
-                }
-                ParseItem::Triple(triple) => {
-                    match triple {
-                        Ok(triple) => {
-                            add_triple(
-                                &mut triples_count,
-                                indexer,
-                                cache,
-                                triple,
-                                &mut index_cache,
-                                language_filter,
-                                &rdf_data.prefix_manager,
-                            );
-                        }
-                        Err(e) => {
-                            eprintln!("Error parsing triple: {}", e);
-                        }
-                    }
-                }
-            }
         }
-        let thread_res = handle.join();
-        thread_res.unwrap()?;
+        let thread_res = handle.join().unwrap();
         let duration = start.elapsed();
-        println!("Time taken to read the file {:?}", duration);
-        println!(
-            "Triples read per second: {}",
+        report.duration = duration;
+        if let Err(e) = &thread_res {
+            report.add_error(e.to_string());
+        }
+        if let Some(data_loading) = data_loading {
+            data_loading.add_report(report);
+        }
+        thread_res?;
+        if let Some(lock_error) = lock_error {
+            return Err(lock_error);
+        }
+        log::info!(
+            "Read {} triples from {} in {:?} ({:.0} triples per second)",
+            triples_count,
+            source,
+            duration,
             triples_count as f64 / duration.as_secs_f64()
         );
         Ok(triples_count)
@@ -909,7 +1262,7 @@ impl RDFWrap {
                         }
                         _ => {
                             // types.push(triple.object.to_string());
-                            println!("type is not named node {}", triple.object);
+                            log::debug!("type is not named node {}", triple.object);
                         }
                     }
                 } else {
@@ -949,13 +1302,13 @@ impl RDFWrap {
                     }
                     _ => {
                         // reverse_references.push((node_data.get_predicate_index(triple.predicate.as_str()), triple.subject.to_string()));
-                        println!("reverse reference is not named node {}", triple.subject);
+                        log::debug!("reverse reference is not named node {}", triple.subject);
                     }
                 }
             }
         }
         if !found {
-            println!("Object not found: {}", iri);
+            log::debug!("Object not found: {}", iri);
             return None;
         }
         Some(NObject {
@@ -977,8 +1330,8 @@ pub fn add_triple(
     index_cache: &mut IndexCache,
     language_filter: &[String],
     prefix_manager: &PrefixManager,
-) {
-    match &triple.subject {
+) -> TripleAddResult {
+    let node_index = match &triple.subject {
         NamedOrBlankNode::BlankNode(blank_node) => {
             let iri = blank_node.as_str();
             if index_cache.iri != iri {
@@ -986,17 +1339,7 @@ pub fn add_triple(
                 index_cache.iri.clear();
                 index_cache.iri.push_str(iri);
             }
-            let node_index = index_cache.index;
-            add_predicate_object(
-                triples_count,
-                indexer,
-                cache,
-                node_index,
-                triple.predicate,
-                triple.object,
-                language_filter,
-                prefix_manager,
-            );
+            index_cache.index
         }
         NamedOrBlankNode::NamedNode(named_subject) => {
             let iri = prefix_manager.get_prefixed(named_subject.as_str());
@@ -1005,45 +1348,102 @@ pub fn add_triple(
                 index_cache.iri.clear();
                 index_cache.iri.push_str(&iri);
             }
-            let node_index = index_cache.index;
-            add_predicate_object(
-                triples_count,
-                indexer,
-                cache,
-                node_index,
-                triple.predicate,
-                triple.object,
-                language_filter,
-                prefix_manager,
-            );
+            index_cache.index
         }
+    };
+    let result = add_predicate_object(
+        &mut index_cache.hashes,
+        indexer,
+        cache,
+        node_index,
+        triple.predicate,
+        triple.object,
+        language_filter,
+        prefix_manager,
+    );
+    // duplicates are counted as read triples, the import report shows them separately
+    if matches!(result, TripleAddResult::Added | TripleAddResult::Duplicate) {
+        *triples_count += 1;
     }
+    result
 }
 
-fn add_predicate_object(
+/**
+ * Adds the triple of the quad and remembers the graph name if the quad is not part of the default graph.
+ * Duplicate triples are recorded too, so the same triple can be part of more named graphs.
+ */
+#[allow(clippy::too_many_arguments)]
+pub fn add_quad(
     triples_count: &mut u32,
     indexer: &mut crate::domain::Indexers,
     cache: &mut crate::domain::NodeCache,
+    named_graphs: &mut NamedGraphs,
+    quad: Quad,
+    index_cache: &mut IndexCache,
+    language_filter: &[String],
+    prefix_manager: &PrefixManager,
+) -> TripleAddResult {
+    let graph_name = match &quad.graph_name {
+        GraphName::DefaultGraph => None,
+        GraphName::NamedNode(named_graph) => Some(prefix_manager.get_prefixed(named_graph.as_str())),
+        GraphName::BlankNode(blank_node) => Some(blank_node.to_string()),
+    };
+    let Some(graph_name) = graph_name else {
+        return add_triple(
+            triples_count,
+            indexer,
+            cache,
+            Triple::from(quad),
+            index_cache,
+            language_filter,
+            prefix_manager,
+        );
+    };
+    let triple = Triple::from(quad);
+    let result = add_triple(
+        triples_count,
+        indexer,
+        cache,
+        triple.clone(),
+        index_cache,
+        language_filter,
+        prefix_manager,
+    );
+    if matches!(result, TripleAddResult::Added | TripleAddResult::Duplicate)
+        && let Some(triple_key) = triple_key(indexer, cache, &triple, prefix_manager)
+    {
+        named_graphs.add(triple_key, &graph_name);
+    }
+    result
+}
+
+fn add_predicate_object(
+    hashes: &mut SubjectHashes,
+    indexer: &mut crate::domain::Indexers,
+    cache: &mut crate::domain::NodeCache,
     node_index: IriIndex,
     predicate: NamedNode,
     object: Term,
     language_filter: &[String],
     prefix_manager: &PrefixManager,
-) {
+) -> TripleAddResult {
     if predicate == rdf::TYPE {
         match &object {
             Term::NamedNode(named_object) => {
                 let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
                 node.has_subject = true;
-                *triples_count += 1;
                 let type_iri = prefix_manager.get_prefixed(named_object.as_str());
                 let type_index = indexer.get_type_index(&type_iri);
                 if !node.types.contains(&type_index) {
                     node.types.push(type_index);
+                    TripleAddResult::Added
+                } else {
+                    TripleAddResult::Duplicate
                 }
             }
             _ => {
-                println!("type is not named node {}", object);
+                log::debug!("type is not named node {}", object);
+                TripleAddResult::Ignored
             }
         }
     } else {
@@ -1053,23 +1453,11 @@ fn add_predicate_object(
             Term::NamedNode(named_object) => {
                 let reference_iri = prefix_manager.get_prefixed(named_object.as_str());
                 let reference_index = cache.get_node_index_or_insert(&reference_iri, false);
-                let predicate_literal: PredicateReference = (predicate_index, reference_index);
-                let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
-                node.references.push(predicate_literal);
-                node.has_subject = true;
-                let (_riri, ref_node) = cache.get_node_by_index_mut(reference_index).unwrap();
-                ref_node.reverse_references.push((predicate_index, node_index));
-                *triples_count += 1;
+                add_reference(hashes, indexer, cache, node_index, predicate_index, reference_index)
             }
             Term::BlankNode(blank_node) => {
                 let reference_index = cache.get_node_index_or_insert(blank_node.as_str(), true);
-                let predicate_literal: PredicateReference = (predicate_index, reference_index);
-                let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
-                node.references.push(predicate_literal);
-                node.has_subject = true;
-                let (_riri, ref_node) = cache.get_node_by_index_mut(reference_index).unwrap();
-                ref_node.reverse_references.push((predicate_index, node_index));
-                *triples_count += 1;
+                add_reference(hashes, indexer, cache, node_index, predicate_index, reference_index)
             }
             Term::Literal(literal) => {
                 let mut skip = false;
@@ -1080,35 +1468,202 @@ fn add_predicate_object(
                         }
                     }
                 }
-                if !skip {
-                    let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
-                    node.has_subject = true;
-                    let value = literal.value();
-                    let language = literal.language();
-                    let datatype = literal.datatype();
-                    if let Some(language) = language {
-                        let language_index = indexer.get_language_index(language);
-                        let span = indexer.literal_cache.push_str(value);
-                        node.properties
-                            .push((predicate_index, Literal::LangString(language_index, span)));
-                    } else if datatype == xsd::STRING {
-                        let literal = if value.len() < SHORT_STR_LITERAL_LEN {
-                            let index = indexer.short_literal_indexer.get_index(value);
-                            Literal::StringShort(index)
-                        } else {
-                            let span = indexer.literal_cache.push_str(value);
-                            Literal::String(span)
-                        };
-                        node.properties.push((predicate_index, literal));
-                    } else {
-                        let datatype_prefixed = prefix_manager.get_prefixed(datatype.as_str());
-                        let data_type_index = indexer.get_data_type_index(&datatype_prefixed);
-                        let span = indexer.literal_cache.push_str(value);
-                        node.properties
-                            .push((predicate_index, Literal::TypedString(data_type_index, span)));
-                    }
-                    *triples_count += 1;
+                if skip {
+                    return TripleAddResult::Filtered;
                 }
+                let value = literal.value();
+                let language = literal.language();
+                let datatype = literal.datatype();
+                let kind = if let Some(language) = language {
+                    LiteralKind::Lang(indexer.get_language_index(language))
+                } else if datatype == xsd::STRING {
+                    LiteralKind::Plain
+                } else {
+                    let datatype_prefixed = prefix_manager.get_prefixed(datatype.as_str());
+                    LiteralKind::Typed(indexer.get_data_type_index(&datatype_prefixed))
+                };
+                let hash = property_hash(node_index, predicate_index, kind, value);
+                let seen = hashes.seen(indexer, cache, node_index, hash);
+                let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
+                node.has_subject = true;
+                // the values are only compared if the triple can be a duplicate
+                if seen
+                    && property_value_position(&node.properties, predicate_index, value, indexer, |literal| {
+                        LiteralKind::of(literal) == kind
+                    })
+                    .is_some()
+                {
+                    return TripleAddResult::Duplicate;
+                }
+                let literal = match kind {
+                    LiteralKind::Lang(language_index) => {
+                        Literal::LangString(language_index, indexer.literal_cache.push_str(value))
+                    }
+                    LiteralKind::Plain if value.len() < SHORT_STR_LITERAL_LEN => {
+                        Literal::StringShort(indexer.short_literal_indexer.get_index(value))
+                    }
+                    LiteralKind::Plain => Literal::String(indexer.literal_cache.push_str(value)),
+                    LiteralKind::Typed(data_type_index) => {
+                        Literal::TypedString(data_type_index, indexer.literal_cache.push_str(value))
+                    }
+                    LiteralKind::NoValue => Literal::NoValue(),
+                };
+                node.properties.push((predicate_index, literal));
+                TripleAddResult::Added
+            }
+        }
+    }
+}
+
+fn add_reference(
+    hashes: &mut SubjectHashes,
+    indexer: &crate::domain::Indexers,
+    cache: &mut crate::domain::NodeCache,
+    node_index: IriIndex,
+    predicate_index: IriIndex,
+    reference_index: IriIndex,
+) -> TripleAddResult {
+    let predicate_literal: PredicateReference = (predicate_index, reference_index);
+    let seen = hashes.seen(indexer, cache, node_index, reference_hash(node_index, predicate_literal));
+    let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
+    node.has_subject = true;
+    // The reverse reference is only added together with the reference, so it is enough to check one side
+    if seen && node.references.contains(&predicate_literal) {
+        return TripleAddResult::Duplicate;
+    }
+    node.references.push(predicate_literal);
+    let (_riri, ref_node) = cache.get_node_by_index_mut(reference_index).unwrap();
+    ref_node.reverse_references.push((predicate_index, node_index));
+    TripleAddResult::Added
+}
+
+fn property_value_position(
+    properties: &[PredicateLiteral],
+    predicate_index: IriIndex,
+    value: &str,
+    indexers: &Indexers,
+    same_kind: impl Fn(&Literal) -> bool,
+) -> Option<usize> {
+    properties.iter().position(|(predicate, literal)| {
+        *predicate == predicate_index && same_kind(literal) && literal.as_str_ref(indexers) == value
+    })
+}
+
+/**
+ * Finds the stored form of the triple. Returns None if the triple is not part of the data.
+ */
+pub fn triple_key(
+    indexer: &crate::domain::Indexers,
+    cache: &crate::domain::NodeCache,
+    triple: &Triple,
+    prefix_manager: &PrefixManager,
+) -> Option<TripleKey> {
+    let node_index = match &triple.subject {
+        NamedOrBlankNode::BlankNode(blank_node) => cache.get_node_index(blank_node.as_str()),
+        NamedOrBlankNode::NamedNode(named_subject) => {
+            cache.get_node_index(&prefix_manager.get_prefixed(named_subject.as_str()))
+        }
+    }?;
+    let (_iri, node) = cache.get_node_by_index(node_index)?;
+    if triple.predicate == rdf::TYPE {
+        let Term::NamedNode(named_object) = &triple.object else {
+            return None;
+        };
+        let type_iri = prefix_manager.get_prefixed(named_object.as_str());
+        let type_index = indexer.type_indexer.map.get(type_iri.as_str())?.to_usize() as IriIndex;
+        return node
+            .types
+            .contains(&type_index)
+            .then_some(TripleKey::Type(node_index, type_index));
+    }
+    let predicate_iri = prefix_manager.get_prefixed(triple.predicate.as_str());
+    let predicate_index = indexer.predicate_indexer.map.get(predicate_iri.as_str())?.to_usize() as IriIndex;
+    let reference_index = match &triple.object {
+        Term::NamedNode(named_object) => cache.get_node_index(&prefix_manager.get_prefixed(named_object.as_str())),
+        Term::BlankNode(blank_node) => cache.get_node_index(blank_node.as_str()),
+        Term::Literal(literal) => {
+            let value = literal.value();
+            let position = if let Some(language) = literal.language() {
+                let language_index = indexer.language_indexer.map.get(language)?.to_usize() as LangIndex;
+                property_value_position(&node.properties, predicate_index, value, indexer, |literal| {
+                    matches!(literal, Literal::LangString(lang, _) if *lang == language_index)
+                })
+            } else if literal.datatype() == xsd::STRING {
+                property_value_position(&node.properties, predicate_index, value, indexer, |literal| {
+                    matches!(literal, Literal::StringShort(_) | Literal::String(_))
+                })
+            } else {
+                let datatype_prefixed = prefix_manager.get_prefixed(literal.datatype().as_str());
+                let data_type_index =
+                    indexer.datatype_indexer.map.get(datatype_prefixed.as_str())?.to_usize() as DataTypeIndex;
+                property_value_position(&node.properties, predicate_index, value, indexer, |literal| {
+                    matches!(literal, Literal::TypedString(data_type, _) if *data_type == data_type_index)
+                })
+            }?;
+            let (_predicate, literal) = &node.properties[position];
+            return Some(TripleKey::Property(node_index, predicate_index, literal.clone()));
+        }
+    }?;
+    node.references
+        .contains(&(predicate_index, reference_index))
+        .then_some(TripleKey::Reference(node_index, predicate_index, reference_index))
+}
+
+/**
+ * Removes one triple from the data. It is the counterpart of add_triple.
+ * Nodes are never removed, so all node indexes stay valid.
+ * Returns the named graphs the triple was removed from, None if the triple was not found.
+ */
+pub fn remove_triple(
+    indexer: &crate::domain::Indexers,
+    cache: &mut crate::domain::NodeCache,
+    named_graphs: &mut NamedGraphs,
+    triple: &Triple,
+    prefix_manager: &PrefixManager,
+) -> Option<Vec<String>> {
+    let triple_key = triple_key(indexer, cache, triple, prefix_manager)?;
+    let graph_names = named_graphs.remove(&triple_key);
+    match triple_key {
+        TripleKey::Type(node_index, type_index) => {
+            let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
+            node.types.retain(|t| *t != type_index);
+        }
+        TripleKey::Property(node_index, predicate_index, literal) => {
+            // The literal value stays in the literal cache, only the property entry is removed
+            let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
+            if let Some(position) = node
+                .properties
+                .iter()
+                .position(|(predicate, value)| *predicate == predicate_index && *value == literal)
+            {
+                node.properties.remove(position);
+            }
+        }
+        TripleKey::Reference(node_index, predicate_index, reference_index) => {
+            let (_iri, node) = cache.get_node_by_index_mut(node_index).unwrap();
+            if let Some(position) = node.references.iter().position(|r| *r == (predicate_index, reference_index)) {
+                node.references.remove(position);
+            }
+            let (_riri, ref_node) = cache.get_node_by_index_mut(reference_index).unwrap();
+            if let Some(position) = ref_node
+                .reverse_references
+                .iter()
+                .position(|r| *r == (predicate_index, node_index))
+            {
+                ref_node.reverse_references.remove(position);
+            }
+        }
+    }
+    Some(graph_names)
+}
+
+/// Adds the triple to the named graphs again, used by undo of deletes
+pub fn restore_triple_graphs(rdf_data: &mut RdfData, triple_graphs: &TripleGraphs) {
+    let (indexer, cache, named_graphs) = rdf_data.node_data.split_graphs_mut();
+    for (triple, graph_names) in triple_graphs.iter() {
+        if let Some(triple_key) = triple_key(indexer, cache, triple, &rdf_data.prefix_manager) {
+            for graph_name in graph_names.iter() {
+                named_graphs.add(triple_key.clone(), graph_name);
             }
         }
     }
@@ -1146,6 +1701,168 @@ use super::*;
         Ok(())
     }
 
+    #[test]
+    fn test_load_shared_in_chunks() {
+        let rdf_data = RwLock::new(RdfData::empty());
+        let mut data = String::new();
+        for i in 0..25_000 {
+            data.push_str(&format!("<http://example.org/n{}> <http://example.org/next> <http://example.org/n{}> .\n", i, i + 1));
+        }
+        let data_loading = DataLoading::new();
+        let triples_count = RDFWrap::load_file_reader(
+            "chunks.nt",
+            "nt",
+            "chunks",
+            io::Cursor::new(data),
+            &rdf_data,
+            &[],
+            Some(&data_loading),
+        )
+        .unwrap();
+        assert_eq!(25_000, triples_count);
+        assert_eq!(25_000, data_loading.total_triples.load(Ordering::Relaxed));
+        assert_eq!(25_001, data_loading.total_nodes.load(Ordering::Relaxed));
+        assert_eq!(25_001, rdf_data.read().unwrap().node_data.len());
+
+        let stopped_loading = DataLoading::new();
+        stopped_loading.stop_loading.store(true, Ordering::Relaxed);
+        let triples_count = RDFWrap::load_file_reader(
+            "stopped.nt",
+            "nt",
+            "stopped",
+            io::Cursor::new("<http://example.org/a> <http://example.org/p> <http://example.org/b> .\n"),
+            &rdf_data,
+            &[],
+            Some(&stopped_loading),
+        )
+        .unwrap();
+        assert_eq!(0, triples_count);
+        assert!(stopped_loading.take_reports()[0].stopped);
+    }
+
+    #[test]
+    fn test_load_duplicates_across_chunks() {
+        let rdf_data = RwLock::new(RdfData::empty());
+        let big_value = |i: usize| format!("<http://example.org/big> <http://example.org/value> \"v{}\" .\n", i);
+        let mut data = String::new();
+        for i in 0..40 {
+            data.push_str(&big_value(i));
+        }
+        data.push_str("<http://example.org/small> <http://example.org/value> \"s\" .\n");
+        data.push_str("<http://example.org/small> <http://example.org/next> <http://example.org/n0> .\n");
+        // the repeated triples are parsed in later chunks, after other subjects
+        for i in 0..PARSE_CHUNK_SIZE + 10 {
+            data.push_str(&format!("<http://example.org/n{}> <http://example.org/next> <http://example.org/n{}> .\n", i, i + 1));
+        }
+        data.push_str(&big_value(3));
+        data.push_str(&big_value(40));
+        data.push_str("<http://example.org/small> <http://example.org/value> \"s\" .\n");
+        data.push_str("<http://example.org/small> <http://example.org/next> <http://example.org/n0> .\n");
+        data.push_str("<http://example.org/n5> <http://example.org/next> <http://example.org/n6> .\n");
+        data.push_str(&big_value(39));
+        let data_loading = DataLoading::new();
+        let triples_count = RDFWrap::load_file_reader(
+            "duplicates.nt",
+            "nt",
+            "duplicates",
+            io::Cursor::new(data),
+            &rdf_data,
+            &[],
+            Some(&data_loading),
+        )
+        .unwrap();
+        let read = 40 + 2 + PARSE_CHUNK_SIZE as u32 + 10 + 6;
+        assert_eq!(read, triples_count);
+        let reports = data_loading.take_reports();
+        assert_eq!(read, reports[0].triples_read);
+        assert_eq!(5, reports[0].duplicates_skipped);
+        assert_eq!(read - 5, reports[0].triples_merged);
+        let rdf_data = rdf_data.read().unwrap();
+        let node_data = &rdf_data.node_data;
+        let node = |iri: &str| node_data.get_node_by_index(node_data.get_node_index(iri).unwrap()).unwrap().1;
+        assert_eq!(41, node("http://example.org/big").properties.len());
+        assert_eq!(1, node("http://example.org/small").properties.len());
+        assert_eq!(1, node("http://example.org/small").references.len());
+        assert_eq!(1, node("http://example.org/n5").references.len());
+        assert_eq!(1, node("http://example.org/n0").reverse_references.len());
+    }
+
+    #[test]
+    fn test_load_named_graphs() {
+        let rdf_data = RwLock::new(RdfData::empty());
+        let data = r#"
+@prefix ex: <http://example.org/> .
+ex:a ex:knows ex:b .
+ex:g1 { ex:a ex:knows ex:c . ex:a ex:name "Anna" . }
+ex:g2 { ex:a ex:knows ex:c . ex:d a ex:Person . }
+"#;
+        let triples_count = RDFWrap::load_file_reader("graphs.trig", "trig", "graphs", io::Cursor::new(data), &rdf_data, &[], None)
+            .unwrap();
+        // the triple of both graphs is stored once but counted as read triple
+        assert_eq!(5, triples_count);
+        let rdf_data = rdf_data.read().unwrap();
+        let node_data = &rdf_data.node_data;
+        let named_graphs = &node_data.named_graphs;
+        let graphs = named_graphs.graphs();
+        assert_eq!(vec!["ex:g1", "ex:g2"], graphs.iter().map(|(_, name)| *name).collect::<Vec<_>>());
+        let a = node_data.get_node_index("ex:a").unwrap();
+        let b = node_data.get_node_index("ex:b").unwrap();
+        let c = node_data.get_node_index("ex:c").unwrap();
+        let d = node_data.get_node_index("ex:d").unwrap();
+        let g1_nodes = named_graphs.graph_nodes(graphs[0].0);
+        assert!(g1_nodes.contains(&a) && g1_nodes.contains(&c) && !g1_nodes.contains(&b));
+        let g2_nodes = named_graphs.graph_nodes(graphs[1].0);
+        assert!(g2_nodes.contains(&d) && !g2_nodes.contains(&b));
+        assert_eq!(3, named_graphs.triple_graphs.len());
+
+        let nquads = RwLock::new(RdfData::empty());
+        let data = "<http://example.org/a> <http://example.org/p> \"v\" <http://example.org/g> .\n<http://example.org/a> <http://example.org/p> <http://example.org/b> .\n";
+        RDFWrap::load_file_reader("graphs.nq", "nq", "graphs", io::Cursor::new(data), &nquads, &[], None).unwrap();
+        let nquads = nquads.read().unwrap();
+        assert_eq!(1, nquads.node_data.named_graphs.triple_graphs.len());
+        assert_eq!(Some("http://example.org/g"), nquads.node_data.named_graphs.graph_name(0));
+    }
+
+    #[test]
+    fn test_import_report_duplicates() {
+        let mut rdf_data = RdfData::empty();
+        let language_filter: Vec<String> = Vec::new();
+        let data_loading = DataLoading::new();
+        let file_name = "sample-rdf-data/programming_languages.ttl";
+        let first = RDFWrap::load_file(file_name, &mut rdf_data, &language_filter, Some(&data_loading)).unwrap();
+        let second = RDFWrap::load_file(file_name, &mut rdf_data, &language_filter, Some(&data_loading)).unwrap();
+        assert!(first > 0);
+        // the duplicates are counted as read triples
+        assert_eq!(first, second);
+        let reports = data_loading.take_reports();
+        assert_eq!(2, reports.len());
+        assert_eq!(file_name, reports[0].source);
+        assert_eq!(first, reports[0].triples_merged + reports[0].duplicates_skipped);
+        assert_eq!(reports[0].triples_read, reports[1].triples_read);
+        assert_eq!(0, reports[1].triples_merged);
+        assert_eq!(reports[1].triples_read, reports[1].duplicates_skipped);
+        assert_eq!(0, reports[1].error_count);
+    }
+
+    #[test]
+    fn test_load_paths_merge() {
+        let mut rdf_data = RdfData::empty();
+        let languages = "sample-rdf-data/programming_languages.ttl";
+        let philosophers = "sample-rdf-data/philosophers.ttl";
+        let language_triples = RDFWrap::load_file(languages, &mut rdf_data, &[], None).unwrap();
+        let node_count = rdf_data.node_data.len();
+        let (first_iri, _) = rdf_data.node_data.get_node_by_index(0).unwrap();
+        let first_iri = first_iri.clone();
+        let predicates = rdf_data.node_data.unique_predicates();
+        let philosopher_triples = RDFWrap::load_file(philosophers, &mut RdfData::empty(), &[], None).unwrap();
+        // the already loaded file adds no nodes, the iris keep their indexes
+        let merged = RDFWrap::load_paths(&[philosophers.to_string(), languages.to_string()], &mut rdf_data, &[], None).unwrap();
+        assert_eq!(philosopher_triples + language_triples, merged);
+        assert!(rdf_data.node_data.len() > node_count);
+        assert_eq!(Some(0), rdf_data.node_data.get_node_index(&first_iri));
+        assert!(rdf_data.node_data.unique_predicates() >= predicates);
+    }
+
     #[test]
     fn test_load_jsonld() -> std::io::Result<()> {
         
@@ -0,0 +1,162 @@
+use crate::uistate::ImportFormat;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::{integration::rdfwrap::{LoadTarget, RDFWrap}, uistate::DataLoading};
+
+use anyhow::Result;
+#[cfg(not(target_arch = "wasm32"))]
+use anyhow::Context;
+
+/// Accept header for content negotiation, turtle is preferred because it is the most compact format
+pub const ACCEPT_RDF: &str =
+    "text/turtle, application/n-triples;q=0.9, application/rdf+xml;q=0.8, application/ld+json;q=0.7, */*;q=0.1";
+
+/// File extension (used to choose the parser) for the content type of the response
+pub fn content_type_extension(content_type: &str) -> Option<&'static str> {
+    let mime_type = content_type.split(';').next().unwrap_or("").trim().to_lowercase();
+    match mime_type.as_str() {
+        "text/turtle" | "application/x-turtle" => Some("ttl"),
+        "application/n-triples" => Some("nt"),
+        "application/rdf+xml" => Some("rdf"),
+        "application/ld+json" => Some("jsonld"),
+        "application/trig" => Some("trig"),
+        "application/n-quads" => Some("nq"),
+        _ => None,
+    }
+}
+
+/// File extension of the last path segment of the url (without query or fragment)
+pub fn url_extension(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next().unwrap_or(url).to_lowercase();
+    let extension = path.rsplit('/').next()?.rsplit_once('.')?.1.to_string();
+    match extension.as_str() {
+        "ttl" => Some("ttl"),
+        "nt" => Some("nt"),
+        "rdf" | "owl" | "xml" => Some("rdf"),
+        "jsonld" => Some("jsonld"),
+        "trig" => Some("trig"),
+        "nq" => Some("nq"),
+        _ => None,
+    }
+}
+
+/// Servers often send text/plain or application/octet-stream for static files, so the url extension is the fallback
+pub fn response_extension(format: ImportFormat, content_type: Option<&str>, url: &str) -> &'static str {
+    if let Some(extension) = format.file_extension() {
+        return extension;
+    }
+    content_type
+        .and_then(content_type_extension)
+        .or_else(|| url_extension(url))
+        .unwrap_or("ttl")
+}
+
+/// Name of the loaded data used as base for generated IRIs
+pub fn url_base_name(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = path.split_once("://").map(|(_, rest)| rest).unwrap_or(path);
+    let base_name = path
+        .trim_end_matches('/')
+        .split_once('/')
+        .and_then(|(_host, path)| path.rsplit('/').next())
+        .and_then(|last_segment| last_segment.split('.').next())
+        .unwrap_or("");
+    if base_name.is_empty() { "url" } else { base_name }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_from_url<'a>(
+    url: &str,
+    rdf_data: impl Into<LoadTarget<'a>>,
+    language_filter: &[String],
+    format: ImportFormat,
+    data_loading: Option<&DataLoading>,
+) -> Result<u32> {
+    use reqwest::blocking::Client;
+    use std::io::BufReader;
+
+    log::info!("Fetching {}", url);
+    let client = Client::new();
+    let response = client
+        .get(url)
+        .header("Accept", format.mime_type())
+        .send()
+        .with_context(|| format!("Failed to fetch URL {}", url))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Failed to fetch URL {}: HTTP {}", url, response.status()));
+    }
+    if let Some(data_loading) = data_loading
+        && data_loading.total_size.load(std::sync::atomic::Ordering::Relaxed) == 0
+    {
+        if let Some(content_length) = response.content_length() {
+            data_loading
+                .total_size
+                .store(content_length as usize, std::sync::atomic::Ordering::Relaxed);
+        } else {
+            log::debug!("Content-Length header not found for URL {}", url);
+        }
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let extension = response_extension(format, content_type.as_deref(), url);
+    let reader = BufReader::new(response);
+    RDFWrap::load_file_reader(
+        url,
+        extension,
+        url_base_name(url),
+        reader,
+        rdf_data,
+        language_filter,
+        data_loading,
+    )
+}
+
+/// Fetches the url content, the returned file name has the extension of the negotiated format
+#[cfg(target_arch = "wasm32")]
+pub async fn fetch_rdf(url: &str, format: ImportFormat) -> Result<crate::uistate::File> {
+    log::info!("Fetching {}", url);
+    let client = reqwest::Client::new();
+    let response = client
+        .get(url)
+        .header("Accept", format.mime_type())
+        .send()
+        .await
+        .map_err(|err| anyhow::anyhow!("Error downloading from URL {}", err))?;
+    if !response.status().is_success() {
+        return Err(anyhow::anyhow!("Failed to fetch URL {}: HTTP {}", url, response.status()));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    let extension = response_extension(format, content_type.as_deref(), url);
+    let path = format!("{}.{}", url_base_name(url), extension);
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|err| anyhow::anyhow!("Error downloading from URL {}", err))?;
+    Ok(crate::uistate::File {
+        path,
+        data: bytes.to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_extension() {
+        assert_eq!("ttl", response_extension(ImportFormat::Auto, Some("text/turtle; charset=utf-8"), "http://example.org/data"));
+        assert_eq!("jsonld", response_extension(ImportFormat::Auto, Some("application/ld+json"), "http://example.org/data.ttl"));
+        assert_eq!("rdf", response_extension(ImportFormat::Auto, Some("text/plain"), "http://example.org/onto.owl?version=2"));
+        assert_eq!("ttl", response_extension(ImportFormat::Auto, None, "http://example.org/resource/1"));
+        assert_eq!("nt", response_extension(ImportFormat::NTriples, Some("text/turtle"), "http://example.org/data.ttl"));
+        assert_eq!("onto", url_base_name("http://example.org/onto.owl?version=2"));
+        assert_eq!("url", url_base_name("http://example.org"));
+        assert_eq!("data", url_base_name("http://example.org/data/"));
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    io,
+};
+
+use crate::{
+    IriIndex,
+    domain::{
+        Literal, NodeData,
+        config::TurtleStyle,
+        prefix_manager::PrefixManager,
+        type_index::{DataPropCharacteristics, ReferenceCharacteristics, TypeData, TypeInstanceIndex},
+    },
+    integration::turtle::is_valid_local_name,
+};
+
+const SH: &str = "http://www.w3.org/ns/shacl#";
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const INDENT: &str = "    ";
+
+/**
+ * Writes a SHACL node shape for each type of the index with property shapes
+ * for the data properties and references found in the instances.
+ * The constraints describe the loaded data, so the data conforms to the generated shapes.
+ * They are meant as a starting point for hand written shapes.
+ * The turtle style decides the prefix usage of the data iris (the SHACL vocabulary is always prefixed),
+ * the order of the property shapes and which property shapes fit on one line.
+ */
+pub fn write_shacl<W: io::Write>(
+    out: &mut W,
+    type_index: &TypeInstanceIndex,
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    style: &TurtleStyle,
+) -> io::Result<()> {
+    let mut writer = ShaclWriter {
+        node_data,
+        prefix_manager,
+        style,
+        used_prefixes: BTreeMap::new(),
+    };
+    writer.used_prefixes.insert("sh".to_string(), SH.to_string());
+    let mut body = String::new();
+    for type_iri_index in type_index.types_order.iter() {
+        if let Some(type_data) = type_index.types.get(type_iri_index) {
+            writer.write_node_shape(&mut body, *type_iri_index, type_data);
+        }
+    }
+    for (prefix, iri) in writer.used_prefixes.iter() {
+        writeln!(out, "@prefix {}: <{}> .", prefix, iri)?;
+    }
+    writeln!(out)?;
+    out.write_all(body.as_bytes())
+}
+
+struct ShaclWriter<'a> {
+    node_data: &'a NodeData,
+    prefix_manager: &'a PrefixManager,
+    style: &'a TurtleStyle,
+    used_prefixes: BTreeMap<String, String>,
+}
+
+impl ShaclWriter<'_> {
+    fn write_node_shape(&mut self, body: &mut String, type_index: IriIndex, type_data: &TypeData) {
+        let Some(type_iri) = self.node_data.get_type(type_index) else {
+            return;
+        };
+        let type_iri = self.full_iri(type_iri);
+        let shape = self.iri_term(&format!("{}Shape", type_iri));
+        let target_class = self.iri_term(&type_iri);
+        let _ = write!(
+            body,
+            "{}\n{}a sh:NodeShape ;\n{}sh:targetClass {}",
+            shape, INDENT, INDENT, target_class
+        );
+        let mut property_shapes: Vec<(String, Vec<String>)> = Vec::new();
+        for (predicate_index, characteristics) in type_data.properties.iter() {
+            // a predicate used for literals and references can have its count split between both shapes
+            let with_min_count = !type_data.references.contains_key(predicate_index);
+            if let Some(path) = self.predicate_term(*predicate_index) {
+                let constraints = self.literal_constraints(type_data, *predicate_index, characteristics, with_min_count);
+                property_shapes.push((path, constraints));
+            }
+        }
+        for (predicate_index, characteristics) in type_data.references.iter() {
+            let with_min_count = !type_data.properties.contains_key(predicate_index);
+            if let Some(path) = self.predicate_term(*predicate_index) {
+                let constraints = self.reference_constraints(characteristics, with_min_count);
+                property_shapes.push((path, constraints));
+            }
+        }
+        if self.style.sort_predicates {
+            property_shapes.sort_by(|a, b| a.0.cmp(&b.0));
+        }
+        for (path, constraints) in property_shapes {
+            let mut inline = format!("{}sh:property [ sh:path {}", INDENT, path);
+            for constraint in constraints.iter() {
+                let _ = write!(inline, " ; {}", constraint);
+            }
+            inline.push_str(" ]");
+            if inline.len() <= self.style.line_width {
+                let _ = write!(body, " ;\n{}", inline);
+                continue;
+            }
+            let _ = write!(body, " ;\n{}sh:property [\n{}{}sh:path {}", INDENT, INDENT, INDENT, path);
+            for constraint in constraints {
+                let _ = write!(body, " ;\n{}{}{}", INDENT, INDENT, constraint);
+            }
+            let _ = write!(body, "\n{}]", INDENT);
+        }
+        body.push_str(" .\n\n");
+    }
+
+    fn literal_constraints(
+        &mut self,
+        type_data: &TypeData,
+        predicate_index: IriIndex,
+        characteristics: &DataPropCharacteristics,
+        with_min_count: bool,
+    ) -> Vec<String> {
+        let mut constraints = Vec::new();
+        let datatypes = self.datatypes(type_data, predicate_index);
+        if datatypes.len() == 1 {
+            let datatype = datatypes.first().unwrap().clone();
+            let is_string = datatype == format!("{}string", XSD) || datatype == format!("{}langString", RDF);
+            constraints.push(format!("sh:datatype {}", self.iri_term(&datatype)));
+            if is_string && characteristics.max_len > 0 {
+                // max_len counts bytes, so it is an upper bound of the length in chars
+                constraints.push(format!("sh:maxLength {}", characteristics.max_len));
+            }
+        } else {
+            constraints.push("sh:nodeKind sh:Literal".to_string());
+        }
+        add_count_constraints(
+            &mut constraints,
+            characteristics.min_cardinality,
+            characteristics.max_cardinality,
+            with_min_count,
+        );
+        constraints
+    }
+
+    fn reference_constraints(&mut self, characteristics: &ReferenceCharacteristics, with_min_count: bool) -> Vec<String> {
+        let mut constraints = vec!["sh:nodeKind sh:BlankNodeOrIRI".to_string()];
+        let classes: Vec<String> = characteristics
+            .types
+            .iter()
+            .filter_map(|type_index| self.node_data.get_type(*type_index))
+            .map(|type_iri| self.full_iri(type_iri))
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .map(|type_iri| self.iri_term(&type_iri))
+            .collect();
+        if classes.len() == 1 {
+            constraints.push(format!("sh:class {}", classes[0]));
+        } else if classes.len() > 1 {
+            let alternatives: Vec<String> = classes.iter().map(|class| format!("[ sh:class {} ]", class)).collect();
+            constraints.push(format!("sh:or ( {} )", alternatives.join(" ")));
+        }
+        add_count_constraints(
+            &mut constraints,
+            characteristics.min_cardinality,
+            characteristics.max_cardinality,
+            with_min_count,
+        );
+        constraints
+    }
+
+    // The value types of the index do not distinguish e.g. xsd:int and xsd:integer, so the datatypes are taken from the values
+    fn datatypes(&self, type_data: &TypeData, predicate_index: IriIndex) -> Vec<String> {
+        let indexers = &self.node_data.indexers;
+        let mut datatypes = BTreeSet::new();
+        for instance_index in type_data.instances.iter() {
+            let Some((_iri, node)) = self.node_data.get_node_by_index(*instance_index) else {
+                continue;
+            };
+            for (property_index, literal) in node.properties.iter() {
+                if *property_index != predicate_index {
+                    continue;
+                }
+                let datatype = match literal {
+                    Literal::StringShort(_) | Literal::String(_) => format!("{}string", XSD),
+                    Literal::LangString(_, _) => format!("{}langString", RDF),
+                    Literal::TypedString(data_type_index, _) => {
+                        match indexers.datatype_indexer.index_to_str(*data_type_index as IriIndex) {
+                            Some(data_type) => self.full_iri(data_type),
+                            None => continue,
+                        }
+                    }
+                    Literal::NoValue() => continue,
+                };
+                datatypes.insert(datatype);
+            }
+        }
+        datatypes.into_iter().collect()
+    }
+
+    fn predicate_term(&mut self, predicate_index: IriIndex) -> Option<String> {
+        let predicate_iri = self.node_data.get_predicate(predicate_index)?;
+        let predicate_iri = self.full_iri(predicate_iri);
+        Some(self.iri_term(&predicate_iri))
+    }
+
+    // The iris in the indexers are stored already prefixed if the prefix is known
+    fn full_iri(&self, iri: &str) -> String {
+        match self.prefix_manager.get_full_opt(iri) {
+            Some(full_iri) => full_iri.into(),
+            None => iri.to_string(),
+        }
+    }
+
+    fn iri_term(&mut self, full_iri: &str) -> String {
+        for (prefix, base_iri) in [("sh", SH), ("xsd", XSD), ("rdf", RDF)] {
+            if let Some(local_name) = full_iri.strip_prefix(base_iri)
+                && is_valid_local_name(local_name)
+            {
+                self.used_prefixes.insert(prefix.to_string(), base_iri.to_string());
+                return format!("{}:{}", prefix, local_name);
+            }
+        }
+        if self.style.use_prefixes
+            && let Some(prefixed) = self.prefix_manager.get_prefixed_opt(full_iri)
+            && let Some((prefix, local_name)) = prefixed.split_once(':')
+            && is_valid_local_name(local_name)
+            && let Some(base_iri) = self.prefix_manager.prefixes.get_by_right(prefix)
+            && !matches!(prefix, "sh" | "xsd" | "rdf")
+        {
+            self.used_prefixes.insert(prefix.to_string(), base_iri.to_string());
+            return prefixed;
+        }
+        format!("<{}>", full_iri)
+    }
+}
+
+fn add_count_constraints(constraints: &mut Vec<String>, min_cardinality: u32, max_cardinality: u32, with_min_count: bool) {
+    if with_min_count && min_cardinality > 0 && min_cardinality != u32::MAX {
+        constraints.push(format!("sh:minCount {}", min_cardinality));
+    }
+    if max_cardinality > 0 {
+        constraints.push(format!("sh:maxCount {}", max_cardinality));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oxttl::TurtleParser;
+
+    use crate::{domain::RdfData, integration::rdf_patch::RdfPatch};
+
+    use super::*;
+
+    const DATA: &str = r#"
+PA ex: <http://example.org/> .
+A ex:a a ex:Person .
+A ex:a ex:name "Anna" .
+A ex:a ex:age "42"^^<http://www.w3.org/2001/XMLSchema#integer> .
+A ex:a ex:knows ex:b .
+A ex:a ex:knows ex:c .
+A ex:b a ex:Person .
+A ex:b ex:name "Bob" .
+A ex:b ex:nick "Bobby" .
+A ex:b ex:nick "B" .
+A ex:c a ex:Robot .
+"#;
+
+    #[test]
+    fn test_write_shacl() {
+        let mut rdf_data = RdfData::empty();
+        rdf_data.prefix_manager.add_prefix("ex", "http://example.org/");
+        RdfPatch::parse_rdf_patch(DATA).apply(&mut rdf_data, &[]);
+        let mut type_index = TypeInstanceIndex::new();
+        type_index.update(&rdf_data.node_data);
+        // the property shapes are written over more lines if they do not fit the line width
+        let narrow = TurtleStyle {
+            line_width: 40,
+            ..TurtleStyle::default()
+        };
+        let mut out = Vec::new();
+        write_shacl(&mut out, &type_index, &rdf_data.node_data, &rdf_data.prefix_manager, &narrow).unwrap();
+        let shacl = String::from_utf8(out).unwrap();
+
+        assert!(shacl.contains("ex:PersonShape\n    a sh:NodeShape ;\n    sh:targetClass ex:Person"));
+        assert!(shacl.contains("sh:path ex:name ;\n        sh:datatype xsd:string ;\n        sh:maxLength 4 ;\n        sh:minCount 1 ;\n        sh:maxCount 1\n"));
+        // ex:b has no age and ex:a has no nick
+        assert!(shacl.contains("sh:path ex:age ;\n        sh:datatype xsd:integer ;\n        sh:maxCount 1\n"));
+        assert!(shacl.contains("sh:path ex:nick ;\n        sh:datatype xsd:string ;\n        sh:maxLength 5 ;\n        sh:maxCount 2\n"));
+        assert!(shacl.contains("sh:path ex:knows ;\n        sh:nodeKind sh:BlankNodeOrIRI ;\n        sh:or ( [ sh:class ex:Person ] [ sh:class ex:Robot ] ) ;\n        sh:maxCount 2\n"));
+        assert!(shacl.contains("sh:targetClass ex:Robot .\n"));
+
+        let triples = TurtleParser::new()
+            .for_slice(shacl.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let node_shapes = triples
+            .iter()
+            .filter(|triple| triple.object.to_string() == format!("<{}NodeShape>", SH))
+            .count();
+        assert_eq!(2, node_shapes);
+
+        let mut out = Vec::new();
+        write_shacl(&mut out, &type_index, &rdf_data.node_data, &rdf_data.prefix_manager, &TurtleStyle::default()).unwrap();
+        let shacl = String::from_utf8(out).unwrap();
+        assert!(shacl.contains("    sh:property [ sh:path ex:age ; sh:datatype xsd:integer ; sh:maxCount 1 ] ;\n"));
+        assert!(TurtleParser::new().for_slice(shacl.as_bytes()).all(|triple| triple.is_ok()));
+    }
+}
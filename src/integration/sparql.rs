@@ -1,25 +1,82 @@
-use std::io::BufReader;
+use std::{
+    collections::HashSet,
+    io::{BufReader, Read},
+};
 
-use crate::domain::{NObject, NodeData};
-use super::rdfwrap::{RDFAdapter, RDFWrap};
+use crate::domain::{NObject, NodeData, RdfData, import_log::{ImportReport, TripleAddResult}};
+use super::{
+    rdf_patch::PatchOperation,
+    rdfwrap::{IndexCache, RDFAdapter, RDFWrap, add_triple},
+};
+use oxrdf::{NamedNodeRef, Triple};
 use oxttl::TurtleParser;
 use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
 
+/// Query used to resolve one node from the endpoint
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum NodeQuery {
+    // properties, references and reverse references of the node
+    #[default]
+    Construct,
+    // the endpoint decides which triples describe the node
+    Describe,
+}
+
+impl NodeQuery {
+    pub fn label(&self) -> &'static str {
+        match self {
+            NodeQuery::Construct => "CONSTRUCT (properties and references)",
+            NodeQuery::Describe => "DESCRIBE",
+        }
+    }
+}
+
+const NODE_QUERY_LIMIT: usize = 500;
+
+/// Query that fetches the triples of one node
+pub fn node_query(iri: &str, node_query: NodeQuery) -> String {
+    match node_query {
+        NodeQuery::Construct => format!(
+            r#"construct {{
+   ?o ?p ?v.
+   ?a ?b ?o.
+}}
+where {{
+    BIND (<{}> as ?o)
+    ?o ?p ?v.
+    OPTIONAL {{?a ?b ?o.}}
+}} limit {}"#,
+            iri, NODE_QUERY_LIMIT
+        ),
+        NodeQuery::Describe => format!("describe <{}>", iri),
+    }
+}
+
+/**
+ * Resolves nodes lazily from a SPARQL endpoint, one query per node.
+ * The resolved nodes are stored in NodeData, so every node is queried only once.
+ */
 pub struct SparqlAdapter {
     endpoint: String,
     client: Client,
+    node_query: NodeQuery,
+    // nodes the endpoint does not know, they are not queried again
+    not_found: HashSet<String>,
 }
 
 impl SparqlAdapter {
-    pub fn new(endpoint: &str) -> Self {
+    pub fn new(endpoint: &str, node_query: NodeQuery) -> Self {
         Self {
             endpoint: endpoint.to_string(),
             client: Client::new(),
             // client: Client::builder().no_proxy().build().unwrap(),
+            node_query,
+            not_found: HashSet::new(),
         }
     }
 
-    fn encode_form_component(value: &str) -> String {
+    pub fn encode_form_component(value: &str) -> String {
         let mut encoded = String::with_capacity(value.len());
         for b in value.bytes() {
             match b {
@@ -40,25 +97,19 @@ impl SparqlAdapter {
 
 impl RDFAdapter for SparqlAdapter {
     fn load_object(&mut self, iri: &str, node_data: &mut NodeData) -> Option<NObject> {
+        // blank nodes and prefixed iris can not be queried
+        if NamedNodeRef::new(iri).is_err() || self.not_found.contains(iri) {
+            return None;
+        }
         let triples = {
             let response = {
-                let query = format!(
-                    r#"construct {{
-   ?o ?p ?v.
-   ?a ?b ?o.
-}}
-where {{
-    BIND (<{}> as ?o)
-    ?o ?p ?v.
-    OPTIONAL {{?a ?b ?o.}}
-}} limit 500"#,
-                    iri
-                );
+                let query = node_query(iri, self.node_query);
                 let form_body = format!(
-                    "limit=500&infer=false&offset=0&query={}",
+                    "limit={}&infer=false&offset=0&query={}",
+                    NODE_QUERY_LIMIT,
                     SparqlAdapter::encode_form_component(&query)
                 );
-                // println!("Query: {}", query);
+                log::info!("SPARQL query for {} at {}", iri, self.endpoint);
                 match self.client
                     .post(&self.endpoint)
                     .header(
@@ -71,13 +122,13 @@ where {{
                 {
                     Ok(response) => response,
                     Err(e) => {
-                        eprintln!("Error SPARQL Call: {}", e);
+                        log::error!("Error SPARQL call: {}", e);
                         return None;
                     }
                 }
             };
             if response.status() != 200 {
-                eprintln!("Error SPARQL call: {}", response.status());
+                log::error!("Error SPARQL call: {}", response.status());
                 return None;
             }
             let buf_reader = BufReader::new(response);
@@ -89,11 +140,282 @@ where {{
             match parser.collect::<Result<Vec<_>, _>>() {
                 Ok(triples) => triples,
                 Err(e) => {
-                    eprintln!("Error parsing Turtle: {}", e);
+                    log::error!("Error parsing Turtle: {}", e);
                     Vec::new()
                 }
             }
         };
-        RDFWrap::load_from_triples(&triples, iri, node_data)
+        let object = RDFWrap::load_from_triples(&triples, iri, node_data);
+        if object.is_none() {
+            self.not_found.insert(iri.to_string());
+        }
+        object
+    }
+}
+
+/// Query form decides the format of the result
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum QueryForm {
+    Select,
+    Ask,
+    Construct,
+    Describe,
+}
+
+/// Value of a variable in a SELECT result
+#[derive(Clone, PartialEq, Debug)]
+pub enum SparqlTerm {
+    Iri(String),
+    Literal(String),
+    BlankNode(String),
+}
+
+pub enum SparqlResult {
+    Table {
+        variables: Vec<String>,
+        rows: Vec<Vec<Option<SparqlTerm>>>,
+    },
+    Boolean(bool),
+    Graph {
+        triples: Vec<Triple>,
+        prefixes: Vec<(String, String)>,
+    },
+}
+
+/// Finds the query form keyword, prologue (PREFIX, BASE), comments and IRIs are skipped
+pub fn query_form(query: &str) -> Option<QueryForm> {
+    let mut chars = query.chars();
+    let mut word = String::new();
+    while let Some(c) = chars.next() {
+        if c.is_alphanumeric() || c == '_' || c == '-' {
+            word.push(c.to_ascii_uppercase());
+            continue;
+        }
+        // a word followed by ':' is a prefix name
+        if c != ':'
+            && let Some(form) = keyword_form(&word)
+        {
+            return Some(form);
+        }
+        word.clear();
+        match c {
+            '#' => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            '<' => {
+                for c in chars.by_ref() {
+                    if c == '>' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    keyword_form(&word)
+}
+
+fn keyword_form(word: &str) -> Option<QueryForm> {
+    match word {
+        "SELECT" => Some(QueryForm::Select),
+        "ASK" => Some(QueryForm::Ask),
+        "CONSTRUCT" => Some(QueryForm::Construct),
+        "DESCRIBE" => Some(QueryForm::Describe),
+        _ => None,
+    }
+}
+
+/// Runs an arbitrary query, SELECT and ASK results are requested as JSON, CONSTRUCT and DESCRIBE as Turtle
+pub fn run_query(endpoint: &str, query: &str) -> anyhow::Result<SparqlResult> {
+    let Some(form) = query_form(query) else {
+        return Err(anyhow::anyhow!("Unknown query form, expected SELECT, ASK, CONSTRUCT or DESCRIBE"));
+    };
+    let accept = match form {
+        QueryForm::Select | QueryForm::Ask => "application/sparql-results+json",
+        QueryForm::Construct | QueryForm::Describe => "text/turtle, application/n-triples;q=0.9",
+    };
+    log::info!("SPARQL {:?} query at {}", form, endpoint);
+    let response = Client::new()
+        .post(endpoint)
+        .header("Content-Type", "application/x-www-form-urlencoded;charset=UTF-8")
+        .header("Accept", accept)
+        .body(format!("query={}", SparqlAdapter::encode_form_component(query)))
+        .send()?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = response.text().unwrap_or_default();
+        return Err(anyhow::anyhow!("SPARQL endpoint answered HTTP {}: {}", status, message.trim()));
+    }
+    match form {
+        QueryForm::Select | QueryForm::Ask => parse_results_json(&response.bytes()?),
+        QueryForm::Construct | QueryForm::Describe => parse_graph(BufReader::new(response)),
+    }
+}
+
+/// Sends a SPARQL update (e.g. INSERT DATA, DELETE DATA) to the update endpoint of the store
+pub fn run_update(endpoint: &str, update: &str) -> anyhow::Result<()> {
+    log::info!("SPARQL update at {}", endpoint);
+    let response = Client::new()
+        .post(endpoint)
+        .header("Content-Type", "application/x-www-form-urlencoded;charset=UTF-8")
+        .body(format!("update={}", SparqlAdapter::encode_form_component(update)))
+        .send()?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let message = response.text().unwrap_or_default();
+        return Err(anyhow::anyhow!("SPARQL endpoint answered HTTP {}: {}", status, message.trim()));
+    }
+    Ok(())
+}
+
+/// Parses the SPARQL 1.1 Query Results JSON Format
+pub fn parse_results_json(data: &[u8]) -> anyhow::Result<SparqlResult> {
+    let json: serde_json::Value = serde_json::from_slice(data)?;
+    if let Some(boolean) = json.get("boolean").and_then(|value| value.as_bool()) {
+        return Ok(SparqlResult::Boolean(boolean));
+    }
+    let variables: Vec<String> = json["head"]["vars"]
+        .as_array()
+        .map(|vars| vars.iter().filter_map(|var| var.as_str().map(str::to_string)).collect())
+        .unwrap_or_default();
+    let Some(bindings) = json["results"]["bindings"].as_array() else {
+        return Err(anyhow::anyhow!("No results in SPARQL response"));
+    };
+    let rows = bindings
+        .iter()
+        .map(|binding| {
+            variables
+                .iter()
+                .map(|variable| {
+                    let term = &binding[variable.as_str()];
+                    let value = term["value"].as_str()?.to_string();
+                    match term["type"].as_str()? {
+                        "uri" => Some(SparqlTerm::Iri(value)),
+                        "bnode" => Some(SparqlTerm::BlankNode(value)),
+                        _ => Some(SparqlTerm::Literal(value)),
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    Ok(SparqlResult::Table { variables, rows })
+}
+
+fn parse_graph<R: Read>(reader: R) -> anyhow::Result<SparqlResult> {
+    let mut parser = TurtleParser::new().for_reader(reader);
+    let mut triples = Vec::new();
+    for triple in parser.by_ref() {
+        triples.push(triple?);
+    }
+    let prefixes = parser
+        .prefixes()
+        .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+        .collect();
+    Ok(SparqlResult::Graph { triples, prefixes })
+}
+
+/// Adds the triples of a CONSTRUCT result to the loaded data
+pub fn merge_triples(
+    source: &str,
+    triples: &[Triple],
+    prefixes: &[(String, String)],
+    rdf_data: &mut RdfData,
+    language_filter: &[String],
+    applied: &mut Vec<PatchOperation>,
+) -> ImportReport {
+    let mut report = ImportReport::new(source);
+    for (prefix, iri) in prefixes {
+        rdf_data.prefix_manager.add_prefix(prefix, iri);
+    }
+    let mut triples_count: u32 = 0;
+    let mut index_cache = IndexCache::new();
+    let (indexer, cache) = rdf_data.node_data.split_mut();
+    for triple in triples {
+        let result = add_triple(
+            &mut triples_count,
+            indexer,
+            cache,
+            triple.clone(),
+            &mut index_cache,
+            language_filter,
+            &rdf_data.prefix_manager,
+        );
+        if result == TripleAddResult::Added {
+            applied.push(PatchOperation::Add(triple.clone()));
+        }
+        report.record(result);
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_node_query() {
+        let construct = node_query("http://example.org/a", NodeQuery::Construct);
+        assert_eq!(Some(QueryForm::Construct), query_form(&construct));
+        assert!(construct.contains("BIND (<http://example.org/a> as ?o)"));
+        let describe = node_query("http://example.org/a", NodeQuery::Describe);
+        assert_eq!(Some(QueryForm::Describe), query_form(&describe));
+        assert_eq!("describe <http://example.org/a>", describe);
+    }
+
+    #[test]
+    fn test_query_form() {
+        assert_eq!(Some(QueryForm::Select), query_form("select * where { ?s ?p ?o }"));
+        assert_eq!(
+            Some(QueryForm::Construct),
+            query_form(
+                "# select all\nPREFIX ask: <http://example.org/select/>\nCONSTRUCT { ?s ?p ?o } WHERE { ?s ?p ?o }"
+            )
+        );
+        assert_eq!(Some(QueryForm::Ask), query_form("BASE <http://example.org/>\nASK { ?s ?p ?o }"));
+        assert_eq!(Some(QueryForm::Describe), query_form("DESCRIBE<http://example.org/a>"));
+        assert_eq!(None, query_form("INSERT DATA { <a> <b> <c> }"));
+    }
+
+    #[test]
+    fn test_parse_results_json() {
+        let json = r#"{
+            "head": {"vars": ["s", "label"]},
+            "results": {"bindings": [
+                {"s": {"type": "uri", "value": "http://example.org/a"}, "label": {"type": "literal", "value": "A", "xml:lang": "en"}},
+                {"s": {"type": "bnode", "value": "b0"}}
+            ]}
+        }"#;
+        let SparqlResult::Table { variables, rows } = parse_results_json(json.as_bytes()).unwrap() else {
+            panic!("table expected");
+        };
+        assert_eq!(vec!["s", "label"], variables);
+        assert_eq!(Some(SparqlTerm::Iri("http://example.org/a".to_string())), rows[0][0]);
+        assert_eq!(Some(SparqlTerm::Literal("A".to_string())), rows[0][1]);
+        assert_eq!(Some(SparqlTerm::BlankNode("b0".to_string())), rows[1][0]);
+        assert_eq!(None, rows[1][1]);
+        assert!(matches!(
+            parse_results_json(br#"{"head": {}, "boolean": true}"#).unwrap(),
+            SparqlResult::Boolean(true)
+        ));
+    }
+
+    #[test]
+    fn test_merge_triples() {
+        let SparqlResult::Graph { triples, prefixes } = parse_graph(
+            "@prefix ex: <http://example.org/> .\nex:a ex:knows ex:b ; ex:name \"A\" .".as_bytes(),
+        )
+        .unwrap() else {
+            panic!("graph expected");
+        };
+        let mut rdf_data = RdfData::empty();
+        let report = merge_triples("query", &triples, &prefixes, &mut rdf_data, &[], &mut Vec::new());
+        assert_eq!(2, report.triples_merged);
+        assert!(rdf_data.node_data.get_node_index("ex:a").is_some());
+        let report = merge_triples("query", &triples, &prefixes, &mut rdf_data, &[], &mut Vec::new());
+        assert_eq!(2, report.duplicates_skipped);
     }
 }
@@ -0,0 +1,170 @@
+use std::io::Write;
+
+use anyhow::{Result, bail};
+use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+const STRESS_NS: &str = "http://example.org/stress/";
+const LITERAL_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz ";
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DegreeDistribution {
+    // out degree is uniform between 0 and 2*average, targets are chosen randomly
+    Uniform,
+    // targets are chosen by preferential attachment so few nodes get most references (scale free)
+    PowerLaw,
+}
+
+/// Parameters of the synthetic dataset, the same seed produces the same data
+#[derive(Clone, Debug)]
+pub struct StressDataConfig {
+    pub node_count: u32,
+    pub type_count: u32,
+    pub predicate_count: u32,
+    pub average_degree: u32,
+    pub distribution: DegreeDistribution,
+    pub literal_count: u32,
+    pub literal_size: u32,
+    pub seed: u64,
+}
+
+impl Default for StressDataConfig {
+    fn default() -> Self {
+        Self {
+            node_count: 10_000,
+            type_count: 10,
+            predicate_count: 5,
+            average_degree: 3,
+            distribution: DegreeDistribution::PowerLaw,
+            literal_count: 2,
+            literal_size: 20,
+            seed: 42,
+        }
+    }
+}
+
+impl StressDataConfig {
+    /// Parses key=value arguments (nodes, types, predicates, degree, distribution, literals, literal_size, seed)
+    pub fn parse_args(args: &[String]) -> Result<Self> {
+        let mut config = Self::default();
+        for arg in args {
+            let Some((key, value)) = arg.split_once('=') else {
+                bail!("Expected key=value but got: {}", arg);
+            };
+            match key {
+                "nodes" => config.node_count = value.parse()?,
+                "types" => config.type_count = value.parse()?,
+                "predicates" => config.predicate_count = value.parse()?,
+                "degree" => config.average_degree = value.parse()?,
+                "distribution" => {
+                    config.distribution = match value {
+                        "uniform" => DegreeDistribution::Uniform,
+                        "power" => DegreeDistribution::PowerLaw,
+                        _ => bail!("Unknown distribution: {} (use uniform or power)", value),
+                    }
+                }
+                "literals" => config.literal_count = value.parse()?,
+                "literal_size" => config.literal_size = value.parse()?,
+                "seed" => config.seed = value.parse()?,
+                _ => bail!("Unknown parameter: {}", key),
+            }
+        }
+        Ok(config)
+    }
+
+    /// Writes the dataset as turtle, returns the number of triples
+    pub fn write_turtle<W: Write>(&self, writer: &mut W) -> Result<u64> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let type_count = self.type_count.max(1);
+        let predicate_count = self.predicate_count.max(1);
+        let mut triples: u64 = 0;
+        writeln!(writer, "@prefix st: <{}> .", STRESS_NS)?;
+        writeln!(writer, "@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .")?;
+        writeln!(writer)?;
+        // all targets of references so far, choosing from it prefers nodes with high in degree
+        let mut endpoints: Vec<u32> = Vec::new();
+        let mut literal = String::with_capacity(self.literal_size as usize);
+        // references of the current node, duplicate triples are skipped
+        let mut references: Vec<(u32, u32)> = Vec::new();
+        for node in 0..self.node_count {
+            writeln!(writer, "st:n{} a st:Type{} ;", node, rng.random_range(0..type_count))?;
+            write!(writer, "    rdfs:label \"Node {}\"", node)?;
+            triples += 2;
+            for literal_index in 0..self.literal_count {
+                literal.clear();
+                for _ in 0..self.literal_size {
+                    literal.push(LITERAL_CHARS[rng.random_range(0..LITERAL_CHARS.len())] as char);
+                }
+                write!(writer, " ;\n    st:text{} \"{}\"", literal_index, literal)?;
+                triples += 1;
+            }
+            let degree = match self.distribution {
+                DegreeDistribution::Uniform => rng.random_range(0..=self.average_degree * 2),
+                DegreeDistribution::PowerLaw => self.average_degree,
+            };
+            if node > 0 {
+                references.clear();
+                for _ in 0..degree {
+                    let target = match self.distribution {
+                        DegreeDistribution::PowerLaw if !endpoints.is_empty() && rng.random_bool(0.8) => {
+                            endpoints[rng.random_range(0..endpoints.len())]
+                        }
+                        DegreeDistribution::PowerLaw => rng.random_range(0..node),
+                        DegreeDistribution::Uniform => rng.random_range(0..self.node_count),
+                    };
+                    let predicate = rng.random_range(0..predicate_count);
+                    if target == node || references.contains(&(predicate, target)) {
+                        continue;
+                    }
+                    references.push((predicate, target));
+                    write!(writer, " ;\n    st:ref{} st:n{}", predicate, target)?;
+                    endpoints.push(target);
+                    triples += 1;
+                }
+            }
+            writeln!(writer, " .")?;
+        }
+        Ok(triples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        domain::RdfData,
+        integration::rdfwrap::RDFWrap,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_stress_data() -> Result<()> {
+        let config = StressDataConfig::parse_args(&[
+            "nodes=200".to_string(),
+            "types=4".to_string(),
+            "literals=1".to_string(),
+            "literal_size=10".to_string(),
+            "distribution=power".to_string(),
+        ])?;
+        assert!(StressDataConfig::parse_args(&["nodes".to_string()]).is_err());
+        assert!(StressDataConfig::parse_args(&["distribution=normal".to_string()]).is_err());
+        let mut data: Vec<u8> = Vec::new();
+        let triples = config.write_turtle(&mut data)?;
+        let mut same_seed: Vec<u8> = Vec::new();
+        config.write_turtle(&mut same_seed)?;
+        assert_eq!(data, same_seed);
+        let mut rdf_data = RdfData::empty();
+        let loaded = RDFWrap::load_file_reader(
+            "stress.ttl",
+            "ttl",
+            "stress",
+            std::io::Cursor::new(data),
+            &mut rdf_data,
+            &[],
+            None,
+        )?;
+        assert_eq!(triples, loaded as u64);
+        assert_eq!(4, rdf_data.node_data.unique_types());
+        assert_eq!(200, rdf_data.node_data.iter().filter(|(_, node)| node.has_subject).count());
+        Ok(())
+    }
+}
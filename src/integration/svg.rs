@@ -1,20 +1,32 @@
 use std::io;
 
 use crate::{
-    RdfGlanceApp,
+    IriIndex, RdfGlanceApp,
     domain::{
         Indexers, LabelContext, NObject, NodeData,
-        config::Config,
-        graph_styles::{ArrowLocation, EdgeStyle, GVisualizationStyle, LabelPosition, NodeShape, NodeSize, NodeStyle},
+        column_chart::ColumnChart,
+        config::{Config, IriDisplay},
+        graph_styles::{
+            ArrowLocation, ArrowStyle, EdgeStyle, GVisualizationStyle, LabelPosition, LineStyle, NodeShape, NodeSize, NodeStyle,
+        },
+        type_samples::{INSTANCE_OF_EDGE, sample_instance},
     },
     support::distinct_colors::next_distinct_color,
-    uistate::{UIState, layout::IndividualNodeStyleData},
+    uistate::{UIState, layout::{IndividualNodeStyleData, LayoutData}},
 };
 use egui::{Align2, Color32, Pos2, Rect, Vec2};
 use quick_xml::Writer;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 
 const POS_SPACE: f32 = 3.0;
+const LEGEND_ROW: f32 = 20.0;
+const LEGEND_FONT_SIZE: f32 = 12.0;
+// size of the plot area of the exported charts
+const CHART_WIDTH: f32 = 600.0;
+const CHART_HEIGHT: f32 = 300.0;
+const CHART_MARGIN: f32 = 40.0;
+// space for the rotated category labels
+const CHART_LABELS_HEIGHT: f32 = 120.0;
 
 impl RdfGlanceApp {
     pub fn export_svg<W: io::Write>(
@@ -23,185 +35,585 @@ impl RdfGlanceApp {
         node_data: &NodeData,
         label_context: &LabelContext,
     ) -> std::io::Result<()> {
-        if let Ok(positions) = self.visible_nodes.positions.read() {
-            if let Ok(nodes) = self.visible_nodes.nodes.read() {
-                if let Ok(individual_node_style) = self.visible_nodes.individual_node_styles.read() {
-                    if let Ok(node_shapes) = self.visible_nodes.node_shapes.read() {
-                        let mut view_rect = Rect::NOTHING;
-                        for (position, shape) in positions.iter().zip(node_shapes.iter()) {
-                            view_rect.extend_with(position.pos - shape.size);
-                            view_rect.extend_with(position.pos + shape.size);
-                        }
-                        let mut writer = Writer::new_with_indent(wtr, b' ', 2);
-
-                        // XML declaration (optional but recommended)
-                        writer.write_event(Event::Decl(quick_xml::events::BytesDecl::new(
-                            "1.0",
-                            Some("UTF-8"),
-                            None,
-                        )))?;
-
-                        // <svg ...>
-                        let mut svg = BytesStart::new("svg");
-                        svg.push_attribute(("xmlns", "http://www.w3.org/2000/svg"));
-                        svg.push_attribute(("version", "1.1"));
-                        svg.push_attribute((
-                            "viewBox",
-                            format!(
-                                "{} {} {} {}",
-                                view_rect.min.x,
-                                view_rect.min.y,
-                                view_rect.width(),
-                                view_rect.height()
-                            )
-                            .as_str(),
-                        ));
-                        writer.write_event(Event::Start(svg))?;
-
-                        writer.write_event(Event::Start(BytesStart::new("defs")))?;
-                        let mut marker = BytesStart::new("marker");
-                        marker.push_attribute(("id", "arrow"));
-                        marker.push_attribute(("viewBox", "0 0 10 10"));
-                        marker.push_attribute(("refX", "10"));
-                        marker.push_attribute(("refY", "5"));
-                        marker.push_attribute(("markerWidth", "6"));
-                        marker.push_attribute(("markerHeight", "6"));
-                        marker.push_attribute(("orient", "auto"));
-
-                        writer.write_event(Event::Start(marker))?;
-
-                        let mut path = BytesStart::new("path");
-                        path.push_attribute(("d", "M 0 0 L 10 5 L 0 10 z"));
-                        path.push_attribute(("fill", "context-stroke"));
-
-                        writer.write_event(Event::Empty(path))?;
-
-                        writer.write_event(Event::End(BytesEnd::new("marker")))?;
-                        writer.write_event(Event::End(BytesEnd::new("defs")))?;
-
-                        let default_edge_style = EdgeStyle::default();
-                        if self.visible_nodes.show_orthogonal
-                            && let Some(orth_edges) = &self.visible_nodes.orth_edges
-                        {
-                            for orth_edge in orth_edges.edges.iter() {
-                                if self.visible_nodes.has_semantic_zoom {
-                                    if !individual_node_style[orth_edge.from_node]
-                                        .semantic_zoom_interval
-                                        .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                        || !individual_node_style[orth_edge.to_node]
-                                            .semantic_zoom_interval
-                                            .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                    {
-                                        continue;
-                                    }
-                                }
-                                draw_orth_edge_svg(
-                                    &mut writer,
-                                    self.visualization_style
-                                        .edge_styles
-                                        .get(&orth_edge.predicate)
-                                        .unwrap_or(&default_edge_style),
-                                    &orth_edge.control_points,
-                                )?;
-                            }
-                        } else if let Ok(edges) = self.visible_nodes.edges.read() {
-                            for edge in edges.iter() {
-                                if self.ui_state.hidden_predicates.contains(edge.predicate) {
-                                    continue;
-                                }
-                                if self.visible_nodes.has_semantic_zoom {
-                                    if !individual_node_style[edge.from]
-                                        .semantic_zoom_interval
-                                        .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                        || !individual_node_style[edge.to]
-                                            .semantic_zoom_interval
-                                            .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                    {
-                                        continue;
-                                    }
-                                }
-
-                                let node_label = || {
-                                    let reference_label = node_data.predicate_display(
-                                        edge.predicate,
-                                        &label_context,
-                                        &node_data.indexers,
-                                    );
-                                    reference_label.as_str().to_owned()
-                                };
-                                let pos1 = positions[edge.from].pos;
-                                if edge.from != edge.to {
-                                    let node_shape_from = &node_shapes[edge.from];
-                                    let node_shape_to = &node_shapes[edge.to];
-                                    let pos2 = positions[edge.to].pos;
-                                    draw_edge_svg(
-                                        &mut writer,
-                                        pos1,
-                                        node_shape_from.size,
-                                        node_shape_from.node_shape,
-                                        pos2,
-                                        node_shape_to.size,
-                                        node_shape_to.node_shape,
-                                        self.visualization_style
-                                            .edge_styles
-                                            .get(&edge.predicate)
-                                            .unwrap_or(&default_edge_style),
-                                        node_label,
-                                        edge.bezier_distance,
-                                    )?;
-                                } else {
-                                    let node_shape_from = &node_shapes[edge.from];
-                                    draw_self_edge_svg(
-                                        &mut writer,
-                                        pos1,
-                                        node_shape_from.size,
-                                        edge.bezier_distance,
-                                        node_shape_from.node_shape,
-                                        self.visualization_style
-                                            .edge_styles
-                                            .get(&edge.predicate)
-                                            .unwrap_or(&default_edge_style),
-                                        node_label,
-                                    )?;
-                                }
-                            }
-                        }
-
-                        for ((node_pos, node_layout), node_position) in nodes.iter().enumerate().zip(positions.iter()) {
-                            if let Some((object_iri, object)) = node_data.get_node_by_index(node_layout.node_index) {
-                                if self.visible_nodes.has_semantic_zoom && !self.visible_nodes.update_node_shapes {
-                                    if !individual_node_style[node_pos]
-                                        .semantic_zoom_interval
-                                        .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                    {
-                                        continue;
-                                    }
-                                }
-                                draw_node_svg(
-                                    &self.visualization_style,
-                                    individual_node_style.get(node_pos),
-                                    &node_data.indexers,
-                                    &self.ui_state,
-                                    &self.persistent_data.config_data,
-                                    &mut writer,
-                                    object,
-                                    object_iri,
-                                    node_position.pos,
-                                    node_shapes[node_pos].size,
-                                )?;
-                            }
-                        }
-                        // </svg>
-                        writer.write_event(Event::End(BytesEnd::new("svg")))?;
+        if let Ok(layout) = self.visible_nodes.layout_data.read() {
+            let LayoutData {
+                nodes,
+                edges,
+                positions,
+                node_shapes,
+                individual_node_styles: individual_node_style,
+            } = &*layout;
+            let mut view_rect = Rect::NOTHING;
+            for (position, shape) in positions.iter().zip(node_shapes.iter()) {
+                view_rect.extend_with(position.pos - shape.size);
+                view_rect.extend_with(position.pos + shape.size);
+            }
+            let legend = type_legend(
+                &self.visualization_style,
+                nodes.iter().filter_map(|node_layout| {
+                    node_data.get_node_by_index(node_layout.node_index).map(|(_, object)| &object.types)
+                }),
+                node_data,
+                label_context,
+            );
+            let legend_pos = extend_with_legend(&mut view_rect, &legend);
+            let mut writer = Writer::new_with_indent(wtr, b' ', 2);
+            write_svg_start(&mut writer, view_rect)?;
+
+            let default_edge_style = EdgeStyle::default();
+            let orth_edges =
+                self.visible_nodes.orth_edges.as_ref().filter(|_| self.visible_nodes.show_orthogonal);
+            if let Some(orth_edges) = orth_edges {
+                for orth_edge in orth_edges.edges.iter() {
+                    if self.visible_nodes.has_semantic_zoom
+                        && (!individual_node_style[orth_edge.from_node]
+                            .semantic_zoom_interval
+                            .is_visible(self.ui_state.semantic_zoom_magnitude)
+                            || !individual_node_style[orth_edge.to_node]
+                                .semantic_zoom_interval
+                                .is_visible(self.ui_state.semantic_zoom_magnitude))
+                    {
+                        continue;
+                    }
+                    draw_orth_edge_svg(
+                        &mut writer,
+                        self.visualization_style
+                            .edge_styles
+                            .get(&orth_edge.predicate)
+                            .unwrap_or(&default_edge_style),
+                        &orth_edge.control_points,
+                    )?;
+                }
+            }
+            if orth_edges.is_none_or(|orth_edges| orth_edges.is_partial()) {
+                for edge in edges.iter() {
+                    if self.ui_state.hidden_predicates.contains(edge.predicate) {
+                        continue;
+                    }
+                    if let Some(orth_edges) = orth_edges
+                        && orth_edges.is_routed(edge.from, edge.to)
+                    {
+                        continue;
+                    }
+                    if self.visible_nodes.has_semantic_zoom
+                        && (!individual_node_style[edge.from]
+                            .semantic_zoom_interval
+                            .is_visible(self.ui_state.semantic_zoom_magnitude)
+                            || !individual_node_style[edge.to]
+                                .semantic_zoom_interval
+                                .is_visible(self.ui_state.semantic_zoom_magnitude))
+                    {
+                        continue;
+                    }
+
+                    let node_label = || {
+                        let reference_label = node_data.predicate_display(
+                            edge.predicate,
+                            label_context,
+                            &node_data.indexers,
+                        );
+                        reference_label.as_str().to_owned()
+                    };
+                    let pos1 = positions[edge.from].pos;
+                    if edge.from != edge.to {
+                        let node_shape_from = &node_shapes[edge.from];
+                        let node_shape_to = &node_shapes[edge.to];
+                        let pos2 = positions[edge.to].pos;
+                        draw_edge_svg(
+                            &mut writer,
+                            pos1,
+                            node_shape_from.size,
+                            node_shape_from.node_shape,
+                            pos2,
+                            node_shape_to.size,
+                            node_shape_to.node_shape,
+                            self.visualization_style
+                                .edge_styles
+                                .get(&edge.predicate)
+                                .unwrap_or(&default_edge_style),
+                            node_label,
+                            edge.bezier_distance,
+                        )?;
+                    } else {
+                        let node_shape_from = &node_shapes[edge.from];
+                        draw_self_edge_svg(
+                            &mut writer,
+                            pos1,
+                            node_shape_from.size,
+                            edge.bezier_distance,
+                            node_shape_from.node_shape,
+                            self.visualization_style
+                                .edge_styles
+                                .get(&edge.predicate)
+                                .unwrap_or(&default_edge_style),
+                            node_label,
+                        )?;
                     }
                 }
             }
+
+            for ((node_pos, node_layout), node_position) in nodes.iter().enumerate().zip(positions.iter()) {
+                if let Some((object_iri, object)) = node_data.get_node_by_index(node_layout.node_index) {
+                    if self.visible_nodes.has_semantic_zoom
+                        && !self.visible_nodes.update_node_shapes
+                        && !individual_node_style[node_pos]
+                            .semantic_zoom_interval
+                            .is_visible(self.ui_state.semantic_zoom_magnitude)
+                    {
+                        continue;
+                    }
+                    draw_node_svg(
+                        &self.visualization_style,
+                        individual_node_style.get(node_pos),
+                        &node_data.indexers,
+                        &self.ui_state,
+                        &self.config,
+                        &mut writer,
+                        object,
+                        object_iri,
+                        node_position.pos,
+                        node_shapes[node_pos].size,
+                    )?;
+                }
+            }
+            draw_legend_svg(&mut writer, &legend, legend_pos)?;
+            // </svg>
+            writer.write_event(Event::End(BytesEnd::new("svg")))?;
+        }
+
+        Ok(())
+    }
+
+    /// The meta graph (types and their references) with the current layout and type colors
+    pub fn export_meta_svg<W: io::Write>(
+        &mut self,
+        wtr: &mut W,
+        node_data: &NodeData,
+        label_context: &LabelContext,
+    ) -> std::io::Result<()> {
+        let Ok(layout) = self.meta_nodes.layout_data.read() else {
+            return Ok(());
+        };
+        let LayoutData {
+            nodes,
+            edges,
+            positions,
+            node_shapes,
+            ..
+        } = &*layout;
+        let mut view_rect = Rect::NOTHING;
+        for (position, shape) in positions.iter().zip(node_shapes.iter()) {
+            view_rect.extend_with(position.pos - shape.size);
+            view_rect.extend_with(position.pos + shape.size);
+        }
+        let type_lists: Vec<Vec<IriIndex>> = nodes
+            .iter()
+            .filter(|node_layout| sample_instance(node_layout.node_index).is_none())
+            .map(|node_layout| vec![node_layout.node_index])
+            .collect();
+        let legend = type_legend(&self.visualization_style, type_lists.iter(), node_data, label_context);
+        let legend_pos = extend_with_legend(&mut view_rect, &legend);
+        let mut writer = Writer::new_with_indent(wtr, b' ', 2);
+        write_svg_start(&mut writer, view_rect)?;
+
+        let instance_of_style = EdgeStyle {
+            color: Color32::GRAY,
+            ..EdgeStyle::default()
+        };
+        for edge in edges.iter() {
+            let edge_style = if edge.predicate == INSTANCE_OF_EDGE {
+                &instance_of_style
+            } else {
+                self.visualization_style.get_edge_syle(edge.predicate, false)
+            };
+            let pos1 = positions[edge.from].pos;
+            let node_shape_from = &node_shapes[edge.from];
+            if edge.from != edge.to {
+                let node_shape_to = &node_shapes[edge.to];
+                draw_edge_svg(
+                    &mut writer,
+                    pos1,
+                    node_shape_from.size,
+                    node_shape_from.node_shape,
+                    positions[edge.to].pos,
+                    node_shape_to.size,
+                    node_shape_to.node_shape,
+                    edge_style,
+                    String::new,
+                    edge.bezier_distance,
+                )?;
+            } else {
+                draw_self_edge_svg(
+                    &mut writer,
+                    pos1,
+                    node_shape_from.size,
+                    edge.bezier_distance,
+                    node_shape_from.node_shape,
+                    edge_style,
+                    String::new,
+                )?;
+            }
+        }
+
+        let should_short_iri = matches!(self.config.iri_display, IriDisplay::Shorten);
+        for ((node_layout, node_position), node_shape) in nodes.iter().zip(positions.iter()).zip(node_shapes.iter()) {
+            // sample instances are drawn as labels like in the meta graph view
+            if let Some(instance) = sample_instance(node_layout.node_index) {
+                let Some((iri, node)) = node_data.get_node_by_index(instance) else {
+                    continue;
+                };
+                let sample_type = self.meta_samples.sample_types(instance).next().unwrap_or_default();
+                let sample_style = NodeStyle {
+                    color: self.visualization_style.get_type_style_one(sample_type).color,
+                    node_shape: NodeShape::Rect,
+                    node_size: NodeSize::Label,
+                    label_position: LabelPosition::Center,
+                    font_size: 12.0,
+                    ..NodeStyle::default()
+                };
+                let instance_label = node.node_label(
+                    iri,
+                    &self.visualization_style,
+                    should_short_iri,
+                    self.ui_state.display_language,
+                    &node_data.indexers,
+                );
+                draw_node_label_svg(
+                    &mut writer,
+                    instance_label,
+                    &sample_style,
+                    node_position.pos,
+                    node_shape.size,
+                    true,
+                    0,
+                )?;
+                continue;
+            }
+            let type_style = NodeStyle {
+                color: self.visualization_style.get_type_style_one(node_layout.node_index).color,
+                node_shape: node_shape.node_shape,
+                node_size: NodeSize::Label,
+                ..NodeStyle::default()
+            };
+            let type_display = node_data.type_display(node_layout.node_index, label_context, &node_data.indexers);
+            draw_node_label_svg(
+                &mut writer,
+                type_display.as_str(),
+                &type_style,
+                node_position.pos,
+                node_shape.size,
+                true,
+                0,
+            )?;
+        }
+        draw_legend_svg(&mut writer, &legend, legend_pos)?;
+        writer.write_event(Event::End(BytesEnd::new("svg")))?;
+        Ok(())
+    }
+}
+
+impl ColumnChart {
+    /// The bar chart as standalone svg image with title and axis labels
+    pub fn export_svg<W: io::Write>(&self, wtr: &mut W, bar_color: Color32) -> std::io::Result<()> {
+        let plot_rect = Rect::from_min_size(
+            Pos2::new(CHART_MARGIN, CHART_MARGIN),
+            Vec2::new(CHART_WIDTH, CHART_HEIGHT),
+        );
+        let view_rect = Rect::from_min_max(
+            Pos2::ZERO,
+            plot_rect.max + Vec2::new(CHART_MARGIN, CHART_LABELS_HEIGHT),
+        );
+        let mut writer = Writer::new_with_indent(wtr, b' ', 2);
+        write_svg_start(&mut writer, view_rect)?;
+        let values: u32 = self.bars.iter().map(|bar| bar.count).sum();
+        draw_text_svg(
+            &mut writer,
+            &format!("{} (values: {}, missing: {})", self.title, values, self.missing),
+            Pos2::new(view_rect.center().x, CHART_MARGIN / 2.0),
+            "middle",
+            LEGEND_FONT_SIZE + 2.0,
+        )?;
+
+        let min_x = self
+            .bars
+            .iter()
+            .map(|bar| bar.position - bar.width / 2.0)
+            .fold(f64::INFINITY, f64::min);
+        let max_x = self
+            .bars
+            .iter()
+            .map(|bar| bar.position + bar.width / 2.0)
+            .fold(f64::NEG_INFINITY, f64::max);
+        let max_count = self.bars.iter().map(|bar| bar.count).max().unwrap_or(0).max(1);
+        let x_range = (max_x - min_x).max(f64::EPSILON);
+        let to_x = |value: f64| plot_rect.min.x + ((value - min_x) / x_range) as f32 * plot_rect.width();
+        for bar in self.bars.iter() {
+            let left = to_x(bar.position - bar.width / 2.0);
+            let right = to_x(bar.position + bar.width / 2.0);
+            let height = bar.count as f32 / max_count as f32 * plot_rect.height();
+            let mut rect = BytesStart::new("rect");
+            rect.push_attribute(("x", left.to_string().as_str()));
+            rect.push_attribute(("y", (plot_rect.max.y - height).to_string().as_str()));
+            rect.push_attribute(("width", (right - left).to_string().as_str()));
+            rect.push_attribute(("height", height.to_string().as_str()));
+            add_color(&mut rect, "fill", bar_color);
+            rect.push_attribute(("stroke", "white"));
+            writer.write_event(Event::Start(rect))?;
+            writer.write_event(Event::Start(BytesStart::new("title")))?;
+            let title = format!("{}: {}", bar.label, bar.count);
+            writer.write_event(Event::Text(BytesText::new(title.as_str())))?;
+            writer.write_event(Event::End(BytesEnd::new("title")))?;
+            writer.write_event(Event::End(BytesEnd::new("rect")))?;
+            if !self.numeric {
+                // category labels are rotated below the bars
+                let label_pos = Pos2::new((left + right) / 2.0, plot_rect.max.y + POS_SPACE * 2.0);
+                let mut text = BytesStart::new("text");
+                text.push_attribute(("x", label_pos.x.to_string().as_str()));
+                text.push_attribute(("y", label_pos.y.to_string().as_str()));
+                text.push_attribute(("font-size", LEGEND_FONT_SIZE.to_string().as_str()));
+                text.push_attribute(("text-anchor", "end"));
+                text.push_attribute(("dominant-baseline", "middle"));
+                text.push_attribute((
+                    "transform",
+                    format!("rotate(-45 {} {})", label_pos.x, label_pos.y).as_str(),
+                ));
+                writer.write_event(Event::Start(text))?;
+                writer.write_event(Event::Text(BytesText::new(bar.label.as_str())))?;
+                writer.write_event(Event::End(BytesEnd::new("text")))?;
+            }
+        }
+        if self.numeric && !self.bars.is_empty() {
+            let label_y = plot_rect.max.y + LEGEND_FONT_SIZE;
+            draw_text_svg(&mut writer, &min_x.to_string(), Pos2::new(plot_rect.min.x, label_y), "start", LEGEND_FONT_SIZE)?;
+            draw_text_svg(&mut writer, &max_x.to_string(), Pos2::new(plot_rect.max.x, label_y), "end", LEGEND_FONT_SIZE)?;
         }
+        // axes with the count scale
+        let mut axes = BytesStart::new("polyline");
+        let points = format!(
+            "{},{} {},{} {},{}",
+            plot_rect.min.x, plot_rect.min.y, plot_rect.min.x, plot_rect.max.y, plot_rect.max.x, plot_rect.max.y
+        );
+        axes.push_attribute(("points", points.as_str()));
+        axes.push_attribute(("fill", "none"));
+        axes.push_attribute(("stroke", "black"));
+        writer.write_event(Event::Empty(axes))?;
+        let count_x = plot_rect.min.x - POS_SPACE;
+        draw_text_svg(&mut writer, "0", Pos2::new(count_x, plot_rect.max.y), "end", LEGEND_FONT_SIZE)?;
+        draw_text_svg(&mut writer, &max_count.to_string(), Pos2::new(count_x, plot_rect.min.y), "end", LEGEND_FONT_SIZE)?;
 
+        writer.write_event(Event::End(BytesEnd::new("svg")))?;
         Ok(())
     }
 }
 
+fn write_svg_start<W: io::Write>(writer: &mut Writer<W>, view_rect: Rect) -> std::io::Result<()> {
+    // XML declaration (optional but recommended)
+    writer.write_event(Event::Decl(quick_xml::events::BytesDecl::new(
+        "1.0",
+        Some("UTF-8"),
+        None,
+    )))?;
+
+    // <svg ...>
+    let mut svg = BytesStart::new("svg");
+    svg.push_attribute(("xmlns", "http://www.w3.org/2000/svg"));
+    svg.push_attribute(("version", "1.1"));
+    svg.push_attribute((
+        "viewBox",
+        format!(
+            "{} {} {} {}",
+            view_rect.min.x,
+            view_rect.min.y,
+            view_rect.width(),
+            view_rect.height()
+        )
+        .as_str(),
+    ));
+    writer.write_event(Event::Start(svg))?;
+
+    writer.write_event(Event::Start(BytesStart::new("defs")))?;
+    for arrow_style in ARROW_STYLES {
+        write_arrow_marker(writer, arrow_style)?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("defs")))?;
+    Ok(())
+}
+
+const ARROW_STYLES: [ArrowStyle; 5] = [
+    ArrowStyle::Arrow,
+    ArrowStyle::ArrorFilled,
+    ArrowStyle::ArrorTriangle,
+    ArrowStyle::Diamond,
+    ArrowStyle::DiamondOpen,
+];
+
+fn arrow_marker_id(arrow_style: ArrowStyle) -> &'static str {
+    match arrow_style {
+        ArrowStyle::Arrow => "arrow-open",
+        ArrowStyle::ArrorFilled => "arrow",
+        ArrowStyle::ArrorTriangle => "arrow-triangle",
+        ArrowStyle::Diamond => "diamond",
+        ArrowStyle::DiamondOpen => "diamond-open",
+    }
+}
+
+// the markers take the color of the edge, auto-start-reverse turns the marker at the edge start
+fn write_arrow_marker<W: io::Write>(writer: &mut Writer<W>, arrow_style: ArrowStyle) -> std::io::Result<()> {
+    let (width, d, filled) = match arrow_style {
+        ArrowStyle::Arrow => (10, "M 0 0 L 10 5 L 0 10", false),
+        ArrowStyle::ArrorFilled => (10, "M 0 0 L 10 5 L 0 10 z", true),
+        ArrowStyle::ArrorTriangle => (10, "M 0 0 L 10 5 L 0 10 z", false),
+        ArrowStyle::Diamond => (20, "M 0 5 L 10 0 L 20 5 L 10 10 z", true),
+        ArrowStyle::DiamondOpen => (20, "M 0 5 L 10 0 L 20 5 L 10 10 z", false),
+    };
+    let mut marker = BytesStart::new("marker");
+    marker.push_attribute(("id", arrow_marker_id(arrow_style)));
+    marker.push_attribute(("viewBox", format!("-1 -1 {} 12", width + 2).as_str()));
+    marker.push_attribute(("refX", width.to_string().as_str()));
+    marker.push_attribute(("refY", "5"));
+    marker.push_attribute(("markerWidth", (width * 6 / 10).to_string().as_str()));
+    marker.push_attribute(("markerHeight", "6"));
+    marker.push_attribute(("orient", "auto-start-reverse"));
+    writer.write_event(Event::Start(marker))?;
+
+    let mut path = BytesStart::new("path");
+    path.push_attribute(("d", d));
+    if filled {
+        path.push_attribute(("fill", "context-stroke"));
+    } else {
+        path.push_attribute(("fill", "none"));
+        path.push_attribute(("stroke", "context-stroke"));
+        path.push_attribute(("stroke-width", "1.5"));
+    }
+    writer.write_event(Event::Empty(path))?;
+
+    writer.write_event(Event::End(BytesEnd::new("marker")))?;
+    Ok(())
+}
+
+/// Stroke, line pattern and arrow markers of the edge style
+fn add_edge_stroke(element: &mut BytesStart, edge_style: &EdgeStyle) {
+    element.push_attribute(("stroke-width", edge_style.width.to_string().as_str()));
+    add_color(element, "stroke", edge_style.color);
+    match edge_style.line_style {
+        LineStyle::Solid => {}
+        LineStyle::Dashed => {
+            let dasharray = format!("{} {}", edge_style.width * 5.0, edge_style.line_gap);
+            element.push_attribute(("stroke-dasharray", dasharray.as_str()));
+        }
+        LineStyle::Dotted => {
+            element.push_attribute(("stroke-dasharray", format!("0 {}", edge_style.line_gap).as_str()));
+            element.push_attribute(("stroke-linecap", "round"));
+        }
+    }
+    let marker = format!("url(#{})", arrow_marker_id(edge_style.target_style));
+    if !matches!(edge_style.arrow_location, ArrowLocation::None) {
+        element.push_attribute(("marker-end", marker.as_str()));
+    }
+    if matches!(edge_style.arrow_location, ArrowLocation::Both) {
+        element.push_attribute(("marker-start", marker.as_str()));
+    }
+}
+
+/**
+ * Type labels and colors for the legend, one entry for each type that decides the color of a node
+ * (the type with the highest style priority), in order of the first node.
+ */
+fn type_legend<'a>(
+    visualization_style: &GVisualizationStyle,
+    type_lists: impl Iterator<Item = &'a Vec<IriIndex>>,
+    node_data: &NodeData,
+    label_context: &LabelContext,
+) -> Vec<(String, Color32)> {
+    let mut legend_types: Vec<IriIndex> = Vec::new();
+    for types in type_lists {
+        let style_type = types
+            .iter()
+            .filter(|type_index| visualization_style.node_styles.contains_key(type_index))
+            .max_by_key(|type_index| visualization_style.get_type_style_one(**type_index).priority);
+        if let Some(style_type) = style_type
+            && !legend_types.contains(style_type)
+        {
+            legend_types.push(*style_type);
+        }
+    }
+    legend_types
+        .iter()
+        .map(|type_index| {
+            (
+                node_data.type_display(*type_index, label_context, &node_data.indexers).as_str().to_owned(),
+                visualization_style.get_type_style_one(*type_index).color,
+            )
+        })
+        .collect()
+}
+
+/// Makes space for the legend on the right side of the graph, returns the top left corner of the legend
+fn extend_with_legend(view_rect: &mut Rect, legend: &[(String, Color32)]) -> Pos2 {
+    let legend_pos = Pos2::new(view_rect.max.x + LEGEND_ROW, view_rect.min.y);
+    if !legend.is_empty() {
+        let max_chars = legend.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        // the width is estimated, the text is rendered by the svg viewer
+        let size = Vec2::new(
+            LEGEND_ROW + max_chars as f32 * LEGEND_FONT_SIZE * 0.6,
+            legend.len() as f32 * LEGEND_ROW,
+        );
+        view_rect.extend_with(legend_pos + size + Vec2::splat(LEGEND_ROW));
+    }
+    legend_pos
+}
+
+fn draw_legend_svg<W: io::Write>(
+    writer: &mut Writer<W>,
+    legend: &[(String, Color32)],
+    pos: Pos2,
+) -> std::io::Result<()> {
+    if legend.is_empty() {
+        return Ok(());
+    }
+    let mut group = BytesStart::new("g");
+    group.push_attribute(("id", "legend"));
+    writer.write_event(Event::Start(group))?;
+    for (row, (label, color)) in legend.iter().enumerate() {
+        let center = pos + Vec2::new(LEGEND_ROW / 2.0, (row as f32 + 0.5) * LEGEND_ROW);
+        let mut circle = BytesStart::new("circle");
+        circle.push_attribute(("cx", center.x.to_string().as_str()));
+        circle.push_attribute(("cy", center.y.to_string().as_str()));
+        circle.push_attribute(("r", (LEGEND_ROW * 0.3).to_string().as_str()));
+        add_color(&mut circle, "fill", *color);
+        circle.push_attribute(("stroke", "black"));
+        writer.write_event(Event::Empty(circle))?;
+        draw_text_svg(
+            writer,
+            label,
+            center + Vec2::new(LEGEND_ROW / 2.0 + POS_SPACE, 0.0),
+            "start",
+            LEGEND_FONT_SIZE,
+        )?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("g")))?;
+    Ok(())
+}
+
+fn draw_text_svg<W: io::Write>(
+    writer: &mut Writer<W>,
+    label: &str,
+    pos: Pos2,
+    text_anchor: &str,
+    font_size: f32,
+) -> std::io::Result<()> {
+    let mut text = BytesStart::new("text");
+    text.push_attribute(("x", pos.x.to_string().as_str()));
+    text.push_attribute(("y", pos.y.to_string().as_str()));
+    text.push_attribute(("font-size", font_size.to_string().as_str()));
+    text.push_attribute(("text-anchor", text_anchor));
+    text.push_attribute(("dominant-baseline", "middle"));
+    writer.write_event(Event::Start(text))?;
+    writer.write_event(Event::Text(BytesText::new(label)))?;
+    writer.write_event(Event::End(BytesEnd::new("text")))?;
+    Ok(())
+}
+
 fn draw_node_svg<W: io::Write>(
     visualization_style: &GVisualizationStyle,
     individual_node_style: Option<&IndividualNodeStyleData>,
@@ -215,6 +627,23 @@ fn draw_node_svg<W: io::Write>(
     size: Vec2,
 ) -> std::io::Result<()> {
     let node_type_style = visualization_style.get_type_style(&node_object.types);
+    let mapped_style;
+    let node_type_style = if node_type_style.node_size == NodeSize::Mapped
+        && let Some(mapped_size) = individual_node_style.map(|style| style.mapped_size)
+        && !mapped_size.is_nan()
+    {
+        mapped_style = node_type_style.with_mapped_size(mapped_size);
+        &mapped_style
+    } else {
+        node_type_style
+    };
+    let rule_style;
+    let node_type_style = if let Some(style_rule) = visualization_style.style_rules.matching(node_object, indexers) {
+        rule_style = style_rule.apply(node_type_style);
+        &rule_style
+    } else {
+        node_type_style
+    };
     let type_style = if (visualization_style.use_size_overwrite || visualization_style.use_color_overwrite)
         && individual_node_style.is_some()
     {
@@ -259,6 +688,13 @@ fn draw_node_svg<W: io::Write>(
     } else {
         node_type_style
     };
+    let override_style;
+    let type_style = if let Some(style_override) = individual_node_style.and_then(|f| f.style_override) {
+        override_style = style_override.apply(type_style);
+        &override_style
+    } else {
+        type_style
+    };
     let node_label = node_object.node_label(
         object_iri,
         visualization_style,
@@ -293,7 +729,7 @@ fn draw_node_label_svg<W: io::Write>(
 ) -> std::io::Result<()> {
     let node_rect = {
         let node_rect = match type_style.node_size {
-            NodeSize::Fixed => Rect::from_center_size(pos, Vec2::new(type_style.width, type_style.height)),
+            NodeSize::Fixed | NodeSize::Mapped => Rect::from_center_size(pos, Vec2::new(type_style.width, type_style.height)),
             NodeSize::Label => Rect::from_center_size(pos, size),
         };
         match type_style.node_shape {
@@ -500,11 +936,7 @@ where
         let mut path = BytesStart::new("path");
         path.push_attribute(("d", d.as_str()));
         path.push_attribute(("fill", "none"));
-        path.push_attribute(("stroke-width", edge_style.width.to_string().as_str()));
-        add_color(&mut path, "stroke", edge_style.color);
-        if !matches!(edge_style.arrow_location, ArrowLocation::None) {
-            path.push_attribute(("marker-end", "url(#arrow)"));
-        }
+        add_edge_stroke(&mut path, edge_style);
         writer.write_event(Event::Empty(path))?;
     } else {
         let mut s_line: BytesStart<'_> = BytesStart::new("line");
@@ -512,11 +944,7 @@ where
         s_line.push_attribute(("y1", edge_from.y.to_string().as_str()));
         s_line.push_attribute(("x2", edge_to.x.to_string().as_str()));
         s_line.push_attribute(("y2", edge_to.y.to_string().as_str()));
-        s_line.push_attribute(("stroke-width", edge_style.width.to_string().as_str()));
-        add_color(&mut s_line, "stroke", edge_style.color);
-        if !matches!(edge_style.arrow_location, ArrowLocation::None) {
-            s_line.push_attribute(("marker-end", "url(#arrow)"));
-        }
+        add_edge_stroke(&mut s_line, edge_style);
         writer.write_event(Event::Empty(s_line))?;
     }
 
@@ -555,11 +983,7 @@ where
     let mut path = BytesStart::new("path");
     path.push_attribute(("d", d.as_str()));
     path.push_attribute(("fill", "none"));
-    path.push_attribute(("stroke-width", edge_style.width.to_string().as_str()));
-    add_color(&mut path, "stroke", edge_style.color);
-    if !matches!(edge_style.arrow_location, ArrowLocation::None) {
-        path.push_attribute(("marker-end", "url(#arrow)"));
-    }
+    add_edge_stroke(&mut path, edge_style);
     writer.write_event(Event::Empty(path))?;
 
     Ok(())
@@ -579,12 +1003,136 @@ fn draw_orth_edge_svg<W: io::Write>(
         .join(" ");
     polyline.push_attribute(("points", points.as_str()));
     polyline.push_attribute(("fill", "none"));
-    polyline.push_attribute(("stroke-width", edge_style.width.to_string().as_str()));
-    add_color(&mut polyline, "stroke", edge_style.color);
-    if !matches!(edge_style.arrow_location, ArrowLocation::None) {
-        polyline.push_attribute(("marker-end", "url(#arrow)"));
-    }
+    add_edge_stroke(&mut polyline, edge_style);
     writer.write_event(Event::Empty(polyline))?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::domain::{
+        RdfData,
+        column_chart::ChartBar,
+        config::IriDisplay,
+        size_mapping::SizeMapping, style_rules::StyleRules,
+    };
+
+    #[test]
+    fn test_column_chart_svg() -> std::io::Result<()> {
+        let chart = ColumnChart {
+            title: "colors".to_string(),
+            numeric: false,
+            bars: vec![
+                ChartBar {
+                    label: "red".to_string(),
+                    position: 0.0,
+                    width: 0.8,
+                    count: 4,
+                },
+                ChartBar {
+                    label: "blue".to_string(),
+                    position: 1.0,
+                    width: 0.8,
+                    count: 2,
+                },
+            ],
+            missing: 1,
+        };
+        let mut buf = Vec::new();
+        chart.export_svg(&mut buf, Color32::RED)?;
+        let svg = String::from_utf8(buf).unwrap();
+        assert!(svg.contains("colors (values: 6, missing: 1)"));
+        assert_eq!(2, svg.matches("<rect").count());
+        assert!(svg.contains("<title>blue: 2</title>"));
+        // the highest bar fills the plot
+        assert!(svg.contains(&format!("height=\"{}\"", CHART_HEIGHT)));
+        assert!(svg.contains(&format!("height=\"{}\"", CHART_HEIGHT / 2.0)));
+        assert!(svg.ends_with("</svg>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_type_legend() -> std::io::Result<()> {
+        let mut rdf_data = RdfData::empty();
+        let person = rdf_data.node_data.indexers.type_indexer.get_index("http://example.org/Person");
+        let student = rdf_data.node_data.indexers.type_indexer.get_index("http://example.org/Student");
+        let unstyled = rdf_data.node_data.indexers.type_indexer.get_index("http://example.org/Thing");
+        let mut node_styles = HashMap::new();
+        node_styles.insert(
+            person,
+            NodeStyle {
+                color: Color32::RED,
+                ..NodeStyle::default()
+            },
+        );
+        node_styles.insert(
+            student,
+            NodeStyle {
+                color: Color32::BLUE,
+                priority: 10,
+                ..NodeStyle::default()
+            },
+        );
+        let visualization_style = GVisualizationStyle {
+            node_styles,
+            edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
+            default_node_style: NodeStyle::default(),
+            use_size_overwrite: false,
+            use_color_overwrite: false,
+            default_label_in_node: false,
+            min_size: 5.0,
+            max_size: 50.0,
+        };
+        let label_context = LabelContext::new(0, IriDisplay::Full, &rdf_data.prefix_manager);
+        let type_lists = [vec![person, student], vec![unstyled], vec![person], vec![student]];
+        let legend = type_legend(&visualization_style, type_lists.iter(), &rdf_data.node_data, &label_context);
+        assert_eq!(
+            vec![
+                ("http://example.org/Student".to_string(), Color32::BLUE),
+                ("http://example.org/Person".to_string(), Color32::RED),
+            ],
+            legend
+        );
+
+        let mut view_rect = Rect::from_min_max(Pos2::ZERO, Pos2::new(100.0, 10.0));
+        let legend_pos = extend_with_legend(&mut view_rect, &legend);
+        assert_eq!(Pos2::new(100.0 + LEGEND_ROW, 0.0), legend_pos);
+        assert!(view_rect.max.x > legend_pos.x + 26.0 * LEGEND_FONT_SIZE * 0.6);
+        assert!(view_rect.max.y >= 2.0 * LEGEND_ROW);
+        let mut buf = Vec::new();
+        draw_legend_svg(&mut Writer::new(&mut buf), &legend, legend_pos)?;
+        let svg = String::from_utf8(buf).unwrap();
+        assert_eq!(2, svg.matches("<circle").count());
+        assert!(svg.contains("fill=\"#0000FF\""));
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_stroke() -> std::io::Result<()> {
+        let edge_style = EdgeStyle {
+            line_style: LineStyle::Dashed,
+            target_style: ArrowStyle::Diamond,
+            arrow_location: ArrowLocation::Both,
+            ..EdgeStyle::default()
+        };
+        let mut buf = Vec::new();
+        let mut writer = Writer::new(&mut buf);
+        write_svg_start(&mut writer, Rect::from_min_max(Pos2::ZERO, Pos2::new(100.0, 100.0)))?;
+        let mut line = BytesStart::new("line");
+        add_edge_stroke(&mut line, &edge_style);
+        writer.write_event(Event::Empty(line))?;
+        let svg = String::from_utf8(buf).unwrap();
+        assert_eq!(ARROW_STYLES.len(), svg.matches("<marker").count());
+        assert!(svg.contains("stroke-dasharray=\"10 10\""));
+        assert!(svg.contains("marker-start=\"url(#diamond)\""));
+        assert!(svg.contains("marker-end=\"url(#diamond)\""));
+        Ok(())
+    }
+}
@@ -0,0 +1,608 @@
+use std::{collections::{BTreeMap, HashMap}, fmt::Write as _, io};
+
+use oxrdf::{NamedOrBlankNode, Term, Triple, vocab::xsd};
+
+use crate::{
+    IriIndex,
+    domain::{Literal, NObject, NodeData, config::TurtleStyle, prefix_manager::PrefixManager},
+};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const LABEL_PREDICATES: [&str; 6] = [
+    "http://www.w3.org/2000/01/rdf-schema#label",
+    "http://www.w3.org/2004/02/skos/core#prefLabel",
+    "http://xmlns.com/foaf/0.1/name",
+    "http://schema.org/name",
+    "http://purl.org/dc/terms/title",
+    "http://purl.org/dc/elements/1.1/title",
+];
+const INDENT: &str = "    ";
+
+/**
+ * Writes the given nodes with all their types, data properties and references as turtle.
+ * Only prefixes that are really used are declared.
+ */
+pub fn write_turtle<W: io::Write>(
+    out: &mut W,
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    nodes: &[IriIndex],
+    style: &TurtleStyle,
+) -> io::Result<()> {
+    let mut writer = TurtleWriter {
+        node_data,
+        prefix_manager,
+        style,
+        used_prefixes: BTreeMap::new(),
+        blank_nodes: HashMap::new(),
+    };
+    let mut body = String::new();
+    for node_index in nodes {
+        if let Some((iri, node)) = node_data.get_node_by_index(*node_index) {
+            writer.write_node(&mut body, iri, node);
+        }
+    }
+    for (prefix, iri) in writer.used_prefixes.iter() {
+        writeln!(out, "@prefix {}: <{}> .", prefix, iri)?;
+    }
+    if !writer.used_prefixes.is_empty() {
+        writeln!(out)?;
+    }
+    out.write_all(body.as_bytes())
+}
+
+/**
+ * Pretty prints triples that are not taken from the nodes, e.g. the added or deleted triples of a change set.
+ * The subjects keep the order of their first triple.
+ * Returns the used prefixes (prefix, iri) and the written triples.
+ */
+pub fn turtle_triples<'a>(
+    node_data: &'a NodeData,
+    prefix_manager: &'a PrefixManager,
+    triples: &[&Triple],
+    style: &'a TurtleStyle,
+) -> (Vec<(&'a str, &'a str)>, String) {
+    let mut writer = TurtleWriter {
+        node_data,
+        prefix_manager,
+        style,
+        used_prefixes: BTreeMap::new(),
+        blank_nodes: HashMap::new(),
+    };
+    let mut subjects: Vec<(String, Vec<PredicateObjects>)> = Vec::new();
+    let mut subject_positions: HashMap<String, usize> = HashMap::new();
+    for triple in triples {
+        let subject = match &triple.subject {
+            NamedOrBlankNode::NamedNode(named_node) => writer.iri_term(named_node.as_str()),
+            NamedOrBlankNode::BlankNode(blank_node) => writer.node_term(blank_node.as_str(), true),
+        };
+        let object = writer.term(&triple.object);
+        let position = *subject_positions.entry(subject.clone()).or_insert_with(|| {
+            subjects.push((subject, Vec::new()));
+            subjects.len() - 1
+        });
+        writer.add_object(&mut subjects[position].1, triple.predicate.as_str(), object);
+    }
+    let mut body = String::new();
+    for (subject, predicates) in subjects.iter_mut() {
+        writer.sort_predicates(predicates);
+        write_subject(&mut body, subject, predicates, style);
+    }
+    (writer.used_prefixes.into_iter().collect(), body)
+}
+
+/// Format of the RDF export
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum RdfExportFormat {
+    Turtle,
+    // one triple per line with full iris
+    NTriples,
+}
+
+impl RdfExportFormat {
+    pub const ALL: [RdfExportFormat; 2] = [RdfExportFormat::Turtle, RdfExportFormat::NTriples];
+
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            RdfExportFormat::Turtle => "ttl",
+            RdfExportFormat::NTriples => "nt",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RdfExportFormat::Turtle => "Turtle",
+            RdfExportFormat::NTriples => "N-Triples",
+        }
+    }
+}
+
+/// Writes the nodes as turtle (pretty printed with the given style) or as N-Triples
+pub fn write_rdf<W: io::Write>(
+    out: &mut W,
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    nodes: &[IriIndex],
+    format: RdfExportFormat,
+    style: &TurtleStyle,
+) -> io::Result<()> {
+    match format {
+        RdfExportFormat::Turtle => write_turtle(out, node_data, prefix_manager, nodes, style),
+        RdfExportFormat::NTriples => {
+            let ntriples_style = TurtleStyle {
+                group_by_subject: false,
+                types_first: false,
+                labels_first: false,
+                sort_predicates: false,
+                use_prefixes: false,
+                ..TurtleStyle::default()
+            };
+            write_turtle(out, node_data, prefix_manager, nodes, &ntriples_style)
+        }
+    }
+}
+
+pub enum TripleObject<'a> {
+    Literal(&'a Literal),
+    Node(IriIndex),
+}
+
+/// Single triple with full iris, so it can be pasted without prefix declarations
+pub fn turtle_triple(
+    node_data: &NodeData,
+    prefix_manager: &PrefixManager,
+    subject_index: IriIndex,
+    predicate_index: IriIndex,
+    object: TripleObject,
+) -> Option<String> {
+    let style = TurtleStyle {
+        use_prefixes: false,
+        ..TurtleStyle::default()
+    };
+    let mut writer = TurtleWriter {
+        node_data,
+        prefix_manager,
+        style: &style,
+        used_prefixes: BTreeMap::new(),
+        blank_nodes: HashMap::new(),
+    };
+    let (subject_iri, subject_node) = node_data.get_node_by_index(subject_index)?;
+    let subject = writer.node_term(subject_iri, subject_node.is_blank_node);
+    let predicate = writer.iri_term(node_data.get_predicate(predicate_index)?);
+    let object = match object {
+        TripleObject::Literal(literal) => writer.literal_term(literal)?,
+        TripleObject::Node(object_index) => {
+            let (object_iri, object_node) = node_data.get_node_by_index(object_index)?;
+            writer.node_term(object_iri, object_node.is_blank_node)
+        }
+    };
+    Some(format!("{} {} {} .", subject, predicate, object))
+}
+
+struct PredicateObjects {
+    predicate: String,
+    rank: u8,
+    objects: Vec<String>,
+}
+
+struct TurtleWriter<'a> {
+    node_data: &'a NodeData,
+    prefix_manager: &'a PrefixManager,
+    style: &'a TurtleStyle,
+    used_prefixes: BTreeMap<&'a str, &'a str>,
+    // the labels of the data are not always valid turtle, so blank nodes are numbered per export
+    blank_nodes: HashMap<String, usize>,
+}
+
+impl<'a> TurtleWriter<'a> {
+    fn write_node(&mut self, body: &mut String, iri: &str, node: &NObject) {
+        let subject = self.node_term(iri, node.is_blank_node);
+        let predicates = self.predicate_objects(node);
+        write_subject(body, &subject, &predicates, self.style);
+    }
+
+    fn predicate_objects(&mut self, node: &NObject) -> Vec<PredicateObjects> {
+        let mut predicates: Vec<PredicateObjects> = Vec::new();
+        let node_data = self.node_data;
+        for type_index in node.types.iter() {
+            if let Some(type_iri) = node_data.get_type(*type_index) {
+                let object = self.iri_term(type_iri);
+                self.add_object(&mut predicates, RDF_TYPE, object);
+            }
+        }
+        for (predicate_index, literal) in node.properties.iter() {
+            if let Some(object) = self.literal_term(literal)
+                && let Some(predicate_iri) = node_data.get_predicate(*predicate_index)
+            {
+                self.add_object(&mut predicates, predicate_iri, object);
+            }
+        }
+        for (predicate_index, reference_index) in node.references.iter() {
+            if let Some((reference_iri, reference_node)) = node_data.get_node_by_index(*reference_index)
+                && let Some(predicate_iri) = node_data.get_predicate(*predicate_index)
+            {
+                let object = self.node_term(reference_iri, reference_node.is_blank_node);
+                self.add_object(&mut predicates, predicate_iri, object);
+            }
+        }
+        self.sort_predicates(&mut predicates);
+        predicates
+    }
+
+    fn add_object(&mut self, predicates: &mut Vec<PredicateObjects>, predicate_iri: &str, object: String) {
+        let full_iri = self.full_iri(predicate_iri);
+        let (predicate, rank) = if full_iri == RDF_TYPE {
+            if self.style.types_first {
+                ("a".to_string(), 0)
+            } else {
+                (self.iri_term(RDF_TYPE), 2)
+            }
+        } else if self.style.labels_first && LABEL_PREDICATES.contains(&full_iri.as_str()) {
+            (self.iri_term(predicate_iri), 1)
+        } else {
+            (self.iri_term(predicate_iri), 2)
+        };
+        if let Some(entry) = predicates.iter_mut().find(|entry| entry.predicate == predicate) {
+            entry.objects.push(object);
+        } else {
+            predicates.push(PredicateObjects {
+                predicate,
+                rank,
+                objects: vec![object],
+            });
+        }
+    }
+
+    fn sort_predicates(&self, predicates: &mut [PredicateObjects]) {
+        if self.style.sort_predicates {
+            predicates.sort_by(|a, b| a.rank.cmp(&b.rank).then_with(|| a.predicate.cmp(&b.predicate)));
+        } else {
+            predicates.sort_by_key(|entry| entry.rank);
+        }
+    }
+
+    fn term(&mut self, term: &Term) -> String {
+        match term {
+            Term::NamedNode(named_node) => self.iri_term(named_node.as_str()),
+            Term::BlankNode(blank_node) => self.node_term(blank_node.as_str(), true),
+            Term::Literal(literal) => {
+                let value = quote(literal.value());
+                if let Some(language) = literal.language() {
+                    format!("{}@{}", value, language)
+                } else if literal.datatype() == xsd::STRING {
+                    value
+                } else {
+                    format!("{}^^{}", value, self.iri_term(literal.datatype().as_str()))
+                }
+            }
+        }
+    }
+
+    fn node_term(&mut self, iri: &str, is_blank_node: bool) -> String {
+        if is_blank_node {
+            let count = self.blank_nodes.len();
+            let number = *self.blank_nodes.entry(iri.to_string()).or_insert(count);
+            format!("_:b{}", number)
+        } else {
+            self.iri_term(iri)
+        }
+    }
+
+    fn full_iri(&self, iri: &str) -> String {
+        match self.prefix_manager.get_full_opt(iri) {
+            Some(full_iri) => full_iri.into(),
+            None => iri.to_string(),
+        }
+    }
+
+    // The iris in the indexers are stored already prefixed if the prefix is known
+    fn iri_term(&mut self, iri: &str) -> String {
+        if self.style.use_prefixes {
+            let prefixes = &self.prefix_manager.prefixes;
+            let full_iri = self.full_iri(iri);
+            if let Some(prefixed) = self.prefix_manager.get_prefixed_opt(&full_iri)
+                && let Some((prefix, local_name)) = prefixed.split_once(':')
+                && is_valid_local_name(local_name)
+                && let Some(base_iri) = prefixes.get_by_right(prefix)
+                && let Some(prefix) = prefixes.get_by_left(base_iri)
+            {
+                self.used_prefixes.insert(prefix, base_iri);
+                return prefixed;
+            }
+            format!("<{}>", full_iri)
+        } else {
+            format!("<{}>", self.full_iri(iri))
+        }
+    }
+
+    fn literal_term(&mut self, literal: &Literal) -> Option<String> {
+        let indexers = &self.node_data.indexers;
+        match literal {
+            Literal::NoValue() => None,
+            Literal::StringShort(_) | Literal::String(_) => Some(quote(literal.as_str_ref(indexers))),
+            Literal::LangString(language_index, _) => {
+                let value = quote(literal.as_str_ref(indexers));
+                match self.node_data.get_language(*language_index) {
+                    Some(language) => Some(format!("{}@{}", value, language)),
+                    None => Some(value),
+                }
+            }
+            Literal::TypedString(data_type_index, _) => {
+                let value = quote(literal.as_str_ref(indexers));
+                match indexers.datatype_indexer.index_to_str(*data_type_index as IriIndex) {
+                    Some(data_type) => {
+                        let data_type = self.iri_term(data_type);
+                        Some(format!("{}^^{}", value, data_type))
+                    }
+                    None => Some(value),
+                }
+            }
+        }
+    }
+}
+
+/// Writes one subject with its predicates and objects, as block or as one line per triple
+fn write_subject(body: &mut String, subject: &str, predicates: &[PredicateObjects], style: &TurtleStyle) {
+    if predicates.is_empty() {
+        return;
+    }
+    if !style.group_by_subject {
+        for predicate in predicates.iter() {
+            for object in predicate.objects.iter() {
+                let _ = writeln!(body, "{} {} {} .", subject, predicate.predicate, object);
+            }
+        }
+        return;
+    }
+    body.push_str(subject);
+    let last = predicates.len() - 1;
+    for (index, predicate) in predicates.iter().enumerate() {
+        let line_start = if index == 0 {
+            body.push(' ');
+            subject.len() + 1
+        } else {
+            body.push_str(INDENT);
+            INDENT.len()
+        };
+        body.push_str(&predicate.predicate);
+        let line_len = line_start
+            + predicate.predicate.len()
+            + predicate.objects.iter().map(|object| object.len() + 2).sum::<usize>();
+        let separator = if line_len > style.line_width && predicate.objects.len() > 1 {
+            format!(",\n{}{}", INDENT, INDENT)
+        } else {
+            ", ".to_string()
+        };
+        body.push(' ');
+        body.push_str(&predicate.objects.join(&separator));
+        body.push_str(if index == last { " .\n\n" } else { " ;\n" });
+    }
+}
+
+// Conservative subset of the turtle PN_LOCAL production, other names are written as full iri
+pub(crate) fn is_valid_local_name(local_name: &str) -> bool {
+    !local_name.starts_with(['-', '.'])
+        && !local_name.ends_with('.')
+        && local_name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+fn quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            // other control characters are not allowed in turtle strings
+            '\u{0}'..='\u{1f}' | '\u{7f}' => {
+                let _ = write!(quoted, "\\u{:04X}", c as u32);
+            }
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use oxttl::{NTriplesParser, TurtleParser};
+
+    use crate::{domain::RdfData, integration::rdf_patch::RdfPatch};
+
+    use super::*;
+
+    const DATA: &str = r#"
+PA ex: <http://example.org/> .
+A ex:a ex:knows ex:c .
+A ex:a ex:knows ex:b .
+A ex:a <http://www.w3.org/2000/01/rdf-schema#label> "A \"quoted\""@en .
+A ex:a a ex:Person .
+A ex:a ex:age "42"^^<http://www.w3.org/2001/XMLSchema#integer> .
+A ex:b a ex:Person .
+"#;
+
+    fn test_data() -> RdfData {
+        let mut rdf_data = RdfData::empty();
+        rdf_data.prefix_manager.add_prefix("ex", "http://example.org/");
+        RdfPatch::parse_rdf_patch(DATA).apply(&mut rdf_data, &[]);
+        rdf_data
+    }
+
+    fn export(rdf_data: &RdfData, style: &TurtleStyle) -> String {
+        let nodes: Vec<IriIndex> = (0..rdf_data.node_data.len() as IriIndex).collect();
+        let mut out = Vec::new();
+        write_turtle(&mut out, &rdf_data.node_data, &rdf_data.prefix_manager, &nodes, style).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_pretty_print() {
+        let rdf_data = test_data();
+        let turtle = export(&rdf_data, &TurtleStyle::default());
+        assert_eq!(
+            r#"@prefix ex: <http://example.org/> .
+@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+
+ex:a a ex:Person ;
+    rdfs:label "A \"quoted\""@en ;
+    ex:age "42"^^xsd:integer ;
+    ex:knows ex:c, ex:b .
+
+ex:b a ex:Person .
+
+"#,
+            turtle
+        );
+        let mut narrow = TurtleStyle::default();
+        narrow.line_width = 20;
+        narrow.use_prefixes = false;
+        let turtle = export(&rdf_data, &narrow);
+        assert!(turtle.contains("<http://example.org/knows> <http://example.org/c>,\n        <http://example.org/b> .\n"));
+    }
+
+    #[test]
+    fn test_turtle_triple() {
+        let rdf_data = test_data();
+        let node_data = &rdf_data.node_data;
+        let a = node_data.get_node_index("ex:a").unwrap();
+        let (_, node) = node_data.get_node_by_index(a).unwrap();
+        let (predicate_index, reference_index) = node.references[0];
+        assert_eq!(
+            Some("<http://example.org/a> <http://example.org/knows> <http://example.org/c> .".to_string()),
+            turtle_triple(
+                node_data,
+                &rdf_data.prefix_manager,
+                a,
+                predicate_index,
+                TripleObject::Node(reference_index)
+            )
+        );
+        let (predicate_index, literal) = node
+            .properties
+            .iter()
+            .find(|(_, literal)| literal.as_str_ref(&node_data.indexers) == "42")
+            .unwrap();
+        assert_eq!(
+            Some(
+                "<http://example.org/a> <http://example.org/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> ."
+                    .to_string()
+            ),
+            turtle_triple(
+                node_data,
+                &rdf_data.prefix_manager,
+                a,
+                *predicate_index,
+                TripleObject::Literal(literal)
+            )
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let rdf_data = test_data();
+        for group_by_subject in [true, false] {
+            let mut style = TurtleStyle::default();
+            style.group_by_subject = group_by_subject;
+            style.types_first = !group_by_subject;
+            let turtle = export(&rdf_data, &style);
+            let triples = TurtleParser::new()
+                .for_slice(turtle.as_bytes())
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(6, triples.len());
+        }
+    }
+
+    #[test]
+    fn test_quote_control_chars() {
+        let quoted = quote("a\u{0}b\u{8}c\u{1b}d\u{7f}\n");
+        assert_eq!("\"a\\u0000b\\u0008c\\u001Bd\\u007F\\n\"", quoted);
+        let turtle = format!("<http://example.org/a> <http://example.org/name> {} .", quoted);
+        let triple = TurtleParser::new().for_slice(turtle.as_bytes()).next().unwrap().unwrap();
+        let Term::Literal(literal) = triple.object else {
+            panic!("literal expected");
+        };
+        assert_eq!("a\u{0}b\u{8}c\u{1b}d\u{7f}\n", literal.value());
+    }
+
+    #[test]
+    fn test_turtle_triples() {
+        let rdf_data = test_data();
+        let triples: Vec<Triple> = TurtleParser::new()
+            .for_slice(
+                r#"@prefix ex: <http://example.org/> .
+ex:c ex:name "C" .
+ex:a ex:knows ex:c .
+ex:c a ex:Person .
+"#
+                .as_bytes(),
+            )
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let triples: Vec<&Triple> = triples.iter().collect();
+        let style = TurtleStyle::default();
+        let (prefixes, body) = turtle_triples(&rdf_data.node_data, &rdf_data.prefix_manager, &triples, &style);
+        assert_eq!(vec![("ex", "http://example.org/")], prefixes);
+        assert_eq!("ex:c a ex:Person ;\n    ex:name \"C\" .\n\nex:a ex:knows ex:c .\n\n", body);
+    }
+
+    #[test]
+    fn test_blank_node_labels() {
+        // the labels were both written as _:x_y before
+        let rdf_data = RdfData::from_patch(
+            "A _:x.y <http://example.org/name> \"first\" .\n\
+             A _:x_y <http://example.org/name> \"second\" .\n\
+             A <http://example.org/a> <http://example.org/knows> _:x.y .\n",
+        );
+        let turtle = export(&rdf_data, &TurtleStyle::default());
+        let triples = TurtleParser::new()
+            .for_slice(turtle.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(3, triples.len());
+        let subject_of = |name: &str| {
+            triples
+                .iter()
+                .find(|triple| matches!(&triple.object, Term::Literal(literal) if literal.value() == name))
+                .map(|triple| triple.subject.clone())
+                .unwrap()
+        };
+        let first = subject_of("first");
+        assert!(first.is_blank_node());
+        assert_ne!(first, subject_of("second"));
+        let knows = triples.iter().find(|triple| triple.predicate.as_str() == "http://example.org/knows").unwrap();
+        assert_eq!(Term::from(first), knows.object);
+    }
+
+    #[test]
+    fn test_ntriples() {
+        let rdf_data = test_data();
+        let nodes: Vec<IriIndex> = (0..rdf_data.node_data.len() as IriIndex).collect();
+        let mut out = Vec::new();
+        write_rdf(
+            &mut out,
+            &rdf_data.node_data,
+            &rdf_data.prefix_manager,
+            &nodes,
+            RdfExportFormat::NTriples,
+            &TurtleStyle::default(),
+        )
+        .unwrap();
+        let ntriples = String::from_utf8(out).unwrap();
+        assert!(ntriples.contains(
+            "<http://example.org/b> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .\n"
+        ));
+        let triples = NTriplesParser::new()
+            .for_slice(ntriples.as_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(6, triples.len());
+    }
+}
@@ -29,7 +29,7 @@ impl VisualQuery {
                         for column_desc in table_query.visible_predicates
                             .iter()
                             .filter(|p| p.visible) {
-                                let property = node.get_property_count(column_desc.predicate_index, label_context.language_index);
+                                let property = node.get_property_count(column_desc.predicate_index, label_context.language_index, &node_data.indexers.language_fallback);
                                 if let Some((property, _count)) = property {
                                     let value = property.as_str_ref(&node_data.indexers);
                                     row.push(value.to_string());
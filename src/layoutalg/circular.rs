@@ -24,7 +24,8 @@ pub fn circular_layout(
     selected_nodes: &BTreeSet<IriIndex>,
     hidden_predicates: &SortedVec,
 ) {
-    let node_indexes: Vec<usize> = if let Ok(nodes) = visible_nodes.nodes.read() {
+    let node_indexes: Vec<usize> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let nodes = &layout.nodes;
         if selected_nodes.len() < 3 {
             (0..nodes.len()).collect()
         } else {
@@ -36,7 +37,8 @@ pub fn circular_layout(
     } else {
         return;
     };
-    let edges: Vec<GEdge> = if let Ok(edges) = visible_nodes.edges.read() {
+    let edges: Vec<GEdge> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = &layout.edges;
         edges
             .iter()
             .filter(|e| {
@@ -48,7 +50,8 @@ pub fn circular_layout(
     } else {
         return;
     };
-    let node_positions: Vec<Pos2> = if let Ok(positions) = visible_nodes.positions.read() {
+    let node_positions: Vec<Pos2> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let positions = &layout.positions;
         node_indexes.iter().map(|idx| positions[*idx].pos).collect()
     } else {
         return;
@@ -72,10 +75,11 @@ pub fn circular_layout(
     }
 
     // let best_order = genetic_opt(&edges, 100, 1, 0.0, 0.0);
-    if let Ok(mut positions) = visible_nodes.positions.write() {
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let positions = &mut layout.positions;
         let circle_positions = circle_positions(circle_center, circle_radius, node_indexes.len());
         for (index, position) in circle_positions.iter().enumerate() {
-            positions[order[index]].pos = *position;
+            positions[order[index]].move_to(*position);
         }
     }
 }
@@ -419,6 +423,25 @@ mod tests {
         assert_eq!(8, best_order.len());
     }
 
+    #[test]
+    fn test_circular_keeps_locked() {
+        let mut visible_nodes = SortedNodeLayout::default();
+        for node_index in 0..5 {
+            visible_nodes.add(crate::uistate::layout::NodeLayout::new(node_index));
+        }
+        let locked_pos = Pos2::new(1000.0, 1000.0);
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let positions = &mut layout.positions;
+            positions[2].pos = locked_pos;
+            positions[2].locked = true;
+        }
+        circular_layout(&mut visible_nodes, &BTreeSet::new(), &SortedVec::new());
+        let layout = visible_nodes.layout_data.read().unwrap();
+        let positions = &layout.positions;
+        assert_eq!(locked_pos, positions[2].pos);
+        assert!(positions.iter().any(|position| !position.locked && position.pos != locked_pos));
+    }
+
     #[test]
     fn test_find_components() {
         let edges = vec![
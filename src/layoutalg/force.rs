@@ -1,9 +1,10 @@
 use crate::{
+    domain::graph_styles::EdgeLayoutForce,
     support::{
         SortedVec, quad_tree::{BHQuadtree, WeightedPoint}
     }, 
     uistate::{
-        layout::{Edge, LayoutConfig, NodeLayout, NodePosition, NodeShapeData}
+        layout::{Edge, IndividualNodeStyleData, LayoutConfig, NodeLayout, NodePosition, NodeShapeData}
     }
 };
 use atomic_float::AtomicF32;
@@ -11,11 +12,14 @@ use eframe::egui::Vec2;
 use rayon::prelude::*;
 use std::sync::atomic::Ordering;
 
+/// individual_node_styles are only used to group the nodes by cluster (config.cluster_attraction > 0)
+#[allow(clippy::too_many_arguments)]
 pub fn layout_graph_nodes(
     nodes: &[NodeLayout],
     node_shapes: &[NodeShapeData],
     positions: &[NodePosition],
     edges: &[Edge],
+    individual_node_styles: &[IndividualNodeStyleData],
     config: &LayoutConfig,
     hidden_predicates: &SortedVec,
     temperature: f32,
@@ -84,13 +88,25 @@ pub fn layout_graph_nodes(
             let position_to = &positions[edge.to];
             let direction = position_from.pos - position_to.pos;
             let distance = direction.length() - node_from.size.x / 2.0 - node_to.size.x / 2.0 - 4.0;
-            let force = distance.powi(2) / attraction;
-            let force_v = (direction / distance) * force;
+            let force_v = if let Some(edge_force) = config.edge_forces.get(&edge.predicate) {
+                let length = direction.length();
+                if length == 0.0 {
+                    continue;
+                }
+                (direction / length) * edge_spring_force(distance, edge_force, attraction)
+            } else {
+                let force = distance.powi(2) / attraction;
+                (direction / distance) * force
+            };
             forces[edge.from] -= force_v;
             forces[edge.to] += force_v;
         }
     }
 
+    if config.cluster_attraction > 0.0 {
+        add_cluster_forces(&mut forces, positions, individual_node_styles, attraction / config.cluster_attraction);
+    }
+
     let max_move = AtomicF32::new(0.0);
 
     let positions = forces
@@ -123,6 +139,48 @@ pub fn layout_graph_nodes(
     (max_move.load(Ordering::Relaxed), positions)
 }
 
+/// Attraction of an edge with own preferred length, negative (pushes the nodes apart) if the edge is shorter
+fn edge_spring_force(distance: f32, edge_force: &EdgeLayoutForce, attraction: f32) -> f32 {
+    let stretch = distance - edge_force.length;
+    stretch * stretch.abs() / attraction * edge_force.stiffness
+}
+
+/// Pulls the nodes of the same cluster (color_overwrite set by clustering) to the center of their cluster
+fn add_cluster_forces(
+    forces: &mut [Vec2],
+    positions: &[NodePosition],
+    individual_node_styles: &[IndividualNodeStyleData],
+    attraction: f32,
+) {
+    let cluster_of = |index: usize| {
+        individual_node_styles
+            .get(index)
+            .map_or(0, |style| style.color_overwrite as usize)
+    };
+    let clusters_len = (0..positions.len()).map(cluster_of).max().unwrap_or(0);
+    if clusters_len == 0 {
+        return;
+    }
+    // sum of positions and count of nodes for each cluster, 0 means no cluster
+    let mut centers = vec![(Vec2::ZERO, 0.0f32); clusters_len + 1];
+    for (index, position) in positions.iter().enumerate() {
+        let center = &mut centers[cluster_of(index)];
+        center.0 += position.pos.to_vec2();
+        center.1 += 1.0;
+    }
+    for (index, position) in positions.iter().enumerate() {
+        let cluster = cluster_of(index);
+        if cluster == 0 || centers[cluster].1 < 2.0 {
+            continue;
+        }
+        let direction = centers[cluster].0 / centers[cluster].1 - position.pos.to_vec2();
+        let distance = direction.length();
+        if distance > 0.0 {
+            forces[index] += (direction / distance) * (distance.powi(2) / attraction);
+        }
+    }
+}
+
 fn smooth_invert(x: f32) -> f32 {
     if x <= 0.0 {
         return 1.0;
@@ -137,3 +195,46 @@ fn smooth_invert(x: f32) -> f32 {
     let s = 6.0 * x5 - 15.0 * x4 + 10.0 * x3;
     1.0 - s
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eframe::egui::Pos2;
+
+    #[test]
+    fn test_cluster_forces() {
+        let position = |x: f32, y: f32| NodePosition {
+            pos: Pos2::new(x, y),
+            vel: Vec2::ZERO,
+            locked: false,
+        };
+        let positions = vec![position(-100.0, 0.0), position(100.0, 0.0), position(0.0, 50.0)];
+        let mut individual_node_styles = vec![IndividualNodeStyleData::default(); 3];
+        individual_node_styles[0].set_cluster(0);
+        individual_node_styles[1].set_cluster(0);
+        individual_node_styles[2].set_cluster(1);
+        let mut forces = vec![Vec2::ZERO; 3];
+        add_cluster_forces(&mut forces, &positions, &individual_node_styles, 100.0);
+        // the nodes of cluster 0 are pulled together, single node of cluster 1 stays
+        assert!(forces[0].x > 0.0 && forces[0].y == 0.0);
+        assert!(forces[1].x < 0.0 && forces[1].y == 0.0);
+        assert_eq!(Vec2::ZERO, forces[2]);
+    }
+
+    #[test]
+    fn test_edge_spring_force() {
+        let edge_force = EdgeLayoutForce {
+            length: 100.0,
+            stiffness: 1.0,
+        };
+        assert_eq!(0.0, edge_spring_force(100.0, &edge_force, 100.0));
+        // stretched edge pulls, compressed edge pushes
+        assert_eq!(1.0, edge_spring_force(110.0, &edge_force, 100.0));
+        assert_eq!(-1.0, edge_spring_force(90.0, &edge_force, 100.0));
+        let stiff = EdgeLayoutForce {
+            stiffness: 3.0,
+            ..edge_force
+        };
+        assert_eq!(3.0, edge_spring_force(110.0, &stiff, 100.0));
+    }
+}
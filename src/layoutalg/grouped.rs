@@ -0,0 +1,213 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, RwLock},
+};
+
+use egui::{Pos2, Rect, Vec2};
+
+use crate::{
+    IriIndex,
+    domain::{RdfData, config::Config, graph_styles::GVisualizationStyle},
+    layoutalg::force::layout_graph_nodes,
+    support::SortedVec,
+    uistate::layout::{Edge, LayoutConfig, NodePosition, SortedNodeLayout},
+};
+
+// space between the regions of two groups
+const GROUP_SPACING: f32 = 150.0;
+const MAX_ITERATIONS: usize = 300;
+
+/**
+ * Nodes are partitioned by their highest priority type and every group gets its own region in a grid.
+ * The force layout runs inside each group only with the edges between the nodes of the group,
+ * so the neighborhoods of the instances can be compared by type. Untyped nodes form the last group.
+ */
+pub fn grouped_layout(
+    visible_nodes: &mut SortedNodeLayout,
+    selected_nodes: &BTreeSet<IriIndex>,
+    hidden_predicates: &SortedVec,
+    visualization_style: &GVisualizationStyle,
+    rdf_data: Arc<RwLock<RdfData>>,
+    config: &Config,
+) {
+    let (node_indexes, node_types): (Vec<usize>, Vec<Option<IriIndex>>) =
+        if let Some((rdf_data, layout)) = visible_nodes.read_with_data(&rdf_data) {
+            let nodes = &layout.nodes;
+            let node_indexes: Vec<usize> = if selected_nodes.len() < 3 {
+                (0..nodes.len()).collect()
+            } else {
+                selected_nodes
+                    .iter()
+                    .filter_map(|selected_node| nodes.binary_search_by(|e| e.node_index.cmp(selected_node)).ok())
+                    .collect()
+            };
+            let node_types = node_indexes
+                .iter()
+                .map(|node_idx| {
+                    rdf_data
+                        .node_data
+                        .get_node_by_index(nodes[*node_idx].node_index)
+                        .and_then(|(_, node)| node.highest_priority_types(visualization_style).first().copied())
+                })
+                .collect();
+            (node_indexes, node_types)
+        } else {
+            return;
+        };
+    if node_indexes.len() < 2 {
+        return;
+    }
+    let mut groups: HashMap<Option<IriIndex>, Vec<usize>> = HashMap::new();
+    for (node_idx, node_type) in node_indexes.iter().zip(node_types) {
+        groups.entry(node_type).or_default().push(*node_idx);
+    }
+    // biggest groups first, untyped nodes last
+    let mut groups: Vec<(Option<IriIndex>, Vec<usize>)> = groups.into_iter().collect();
+    groups.sort_by(|a, b| {
+        a.0.is_none()
+            .cmp(&b.0.is_none())
+            .then(b.1.len().cmp(&a.1.len()))
+            .then(a.0.cmp(&b.0))
+    });
+
+    let layout_config = LayoutConfig {
+        cluster_attraction: 0.0,
+        ..visible_nodes.layout_config(config)
+    };
+    let mut group_positions: Vec<Vec<Pos2>> = Vec::with_capacity(groups.len());
+    let mut group_rects: Vec<Rect> = Vec::with_capacity(groups.len());
+    let mut old_rect = Rect::NOTHING;
+    if let Ok(layout) = visible_nodes.layout_data.read() {
+        for node_idx in node_indexes.iter() {
+            old_rect.extend_with(layout.positions[*node_idx].pos);
+        }
+        for (_, group) in groups.iter() {
+            let group_pos: HashMap<usize, usize> =
+                group.iter().enumerate().map(|(pos, node_idx)| (*node_idx, pos)).collect();
+            let nodes: Vec<_> = group.iter().map(|node_idx| layout.nodes[*node_idx]).collect();
+            let node_shapes: Vec<_> = group.iter().map(|node_idx| layout.node_shapes[*node_idx]).collect();
+            let edges: Vec<Edge> = layout
+                .edges
+                .iter()
+                .filter_map(|edge| {
+                    Some(Edge {
+                        from: *group_pos.get(&edge.from)?,
+                        to: *group_pos.get(&edge.to)?,
+                        predicate: edge.predicate,
+                        bezier_distance: 0.0,
+                    })
+                })
+                .collect();
+            // random start positions, the layout of the group does not depend on the positions in the whole graph
+            let mut positions: Vec<NodePosition> = group.iter().map(|_| NodePosition::default()).collect();
+            let mut temperature = 100.0;
+            for _ in 0..MAX_ITERATIONS {
+                let (max_move, new_positions) = layout_graph_nodes(
+                    &nodes,
+                    &node_shapes,
+                    &positions,
+                    &edges,
+                    &[],
+                    &layout_config,
+                    hidden_predicates,
+                    temperature,
+                );
+                positions = new_positions;
+                if max_move < 0.5 {
+                    break;
+                }
+                temperature *= 0.98;
+            }
+            let mut rect = Rect::NOTHING;
+            for (position, node_shape) in positions.iter().zip(node_shapes.iter()) {
+                rect = rect.union(Rect::from_center_size(position.pos, node_shape.size));
+            }
+            group_positions.push(positions.iter().map(|position| position.pos).collect());
+            group_rects.push(rect);
+        }
+    } else {
+        return;
+    }
+    let sizes: Vec<Vec2> = group_rects.iter().map(|rect| rect.size()).collect();
+    let (centers, grid_size) = grid_centers(&sizes, GROUP_SPACING);
+    let origin = old_rect.center() - grid_size * 0.5;
+    // a running force layout of the whole graph would mix the groups again
+    visible_nodes.stop_layout();
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let positions = &mut layout.positions;
+        for (group_idx, (_, group)) in groups.iter().enumerate() {
+            let offset = origin + centers[group_idx] - group_rects[group_idx].center();
+            for (node_idx, pos) in group.iter().zip(group_positions[group_idx].iter()) {
+                positions[*node_idx].move_to(*pos + offset);
+            }
+        }
+    }
+}
+
+/// Centers of the group regions in a grid with the size of the whole grid,
+/// every column is as wide as its widest group and every row as high as its highest group
+fn grid_centers(sizes: &[Vec2], spacing: f32) -> (Vec<Vec2>, Vec2) {
+    let columns = (sizes.len() as f32).sqrt().ceil().max(1.0) as usize;
+    let rows = sizes.len().div_ceil(columns);
+    let mut column_widths = vec![0.0f32; columns];
+    let mut row_heights = vec![0.0f32; rows];
+    for (index, size) in sizes.iter().enumerate() {
+        column_widths[index % columns] = column_widths[index % columns].max(size.x);
+        row_heights[index / columns] = row_heights[index / columns].max(size.y);
+    }
+    let offsets = |lengths: &[f32]| {
+        let mut offset = 0.0;
+        lengths
+            .iter()
+            .map(|length| {
+                let center = offset + length * 0.5;
+                offset += length + spacing;
+                center
+            })
+            .collect::<Vec<f32>>()
+    };
+    let column_centers = offsets(&column_widths);
+    let row_centers = offsets(&row_heights);
+    let centers = (0..sizes.len())
+        .map(|index| Vec2::new(column_centers[index % columns], row_centers[index / columns]))
+        .collect();
+    let grid_size = Vec2::new(
+        column_widths.iter().sum::<f32>() + spacing * (columns - 1) as f32,
+        row_heights.iter().sum::<f32>() + spacing * (rows.max(1) - 1) as f32,
+    );
+    (centers, grid_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_centers() {
+        let sizes = [
+            Vec2::new(200.0, 100.0),
+            Vec2::new(100.0, 300.0),
+            Vec2::new(50.0, 50.0),
+        ];
+        let (centers, grid_size) = grid_centers(&sizes, 10.0);
+        // 2 columns and 2 rows
+        assert_eq!(Vec2::new(100.0, 150.0), centers[0]);
+        assert_eq!(Vec2::new(260.0, 150.0), centers[1]);
+        assert_eq!(Vec2::new(100.0, 335.0), centers[2]);
+        assert_eq!(Vec2::new(310.0, 360.0), grid_size);
+        // the regions do not overlap
+        let rects: Vec<Rect> = centers
+            .iter()
+            .zip(sizes.iter())
+            .map(|(center, size)| Rect::from_center_size(center.to_pos2(), *size))
+            .collect();
+        for (index, rect) in rects.iter().enumerate() {
+            for other in rects.iter().skip(index + 1) {
+                assert!(!rect.intersects(*other));
+            }
+        }
+        let (centers, grid_size) = grid_centers(&[Vec2::new(40.0, 20.0)], 10.0);
+        assert_eq!(vec![Vec2::new(20.0, 10.0)], centers);
+        assert_eq!(Vec2::new(40.0, 20.0), grid_size);
+    }
+}
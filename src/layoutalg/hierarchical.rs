@@ -1,64 +1,74 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
-use egui::Pos2;
+use egui::{Pos2, Vec2};
 
-use crate::{IriIndex, layoutalg::LayoutOrientation, support::SortedVec, uistate::layout::SortedNodeLayout};
+use crate::{IriIndex, layoutalg::LayoutOrientation, support::SortedVec, uistate::layout::{LayoutData, SortedNodeLayout}};
 use rust_sugiyama::{configure::Config, from_vertices_and_edges};
 
+const ORG_CHART_MIN_WIDTH: f32 = 120.0;
+const ORG_CHART_MIN_HEIGHT: f32 = 60.0;
+const HIERARCHY_SPACING: f32 = 30.0;
+
+/**
+ * Layered layout of the visible (or selected) nodes.
+ * Without hierarchy the layers are inferred from all visible edges.
+ * With hierarchy only the edges of the predicate define the layers (the bool is true if the object
+ * of the triple is the superior, e.g. rdfs:subClassOf, skos:broader), all other edges are just drawn.
+ * Cycles of the hierarchy are broken and the nodes without hierarchy edges are placed in rows below.
+ */
 pub fn hierarchical_layout(
     visible_nodes: &mut SortedNodeLayout,
     selected_nodes: &BTreeSet<IriIndex>,
     hidden_predicates: &SortedVec,
     layout_orientation: LayoutOrientation,
+    hierarchy: Option<(IriIndex, bool)>,
 ) {
-    let node_indexes: Vec<(u32,(f64,f64))> = if let Ok(nodes) = visible_nodes.nodes.read() {
+    let node_indexes: Vec<(u32,(f64,f64))> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let LayoutData { nodes, node_shapes, .. } = &*layout;
         if selected_nodes.len() < 2 {
-            if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
-                node_shapes.iter().enumerate()
-                    .map(|(idx, node_shape)| {
-                        let size = match layout_orientation {
-                            LayoutOrientation::Horizontal => {
-                                (node_shape.size.x as f64,node_shape.size.y as f64)
-                            },
-                            LayoutOrientation::Vertical => {
-                                (node_shape.size.y as f64,node_shape.size.x as f64)
-                            }
-                        };
-                        (idx as u32,size)
-                    })
-                    .collect()
-            } else {
-                return;
-            }
-        } else {
-            if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
-                selected_nodes
-                    .iter()
-                    .filter_map(|selected_node| {
-                        match nodes.binary_search_by(|e| e.node_index.cmp(&selected_node)) {
-                            Ok(idx) => {
-                                let size = match layout_orientation {
-                                    LayoutOrientation::Horizontal => {
-                                        (node_shapes[idx].size.x as f64,node_shapes[idx].size.y as f64)
-                                    },
-                                    LayoutOrientation::Vertical => {
-                                        (node_shapes[idx].size.y as f64,node_shapes[idx].size.x as f64)
-                                    }
-                                };
-                                Some((idx as u32,size))
-                            },
-                            Err(_) => None,
+            node_shapes.iter().enumerate()
+                .map(|(idx, node_shape)| {
+                    let size = match layout_orientation {
+                        LayoutOrientation::Horizontal => {
+                            (node_shape.size.x as f64,node_shape.size.y as f64)
+                        },
+                        LayoutOrientation::Vertical => {
+                            (node_shape.size.y as f64,node_shape.size.x as f64)
                         }
-                    })
-                    .collect()
-            } else {
-                return;
-            }
+                    };
+                    (idx as u32,size)
+                })
+                .collect()
+        } else {
+            selected_nodes
+                .iter()
+                .filter_map(|selected_node| {
+                    match nodes.binary_search_by(|e| e.node_index.cmp(selected_node)) {
+                        Ok(idx) => {
+                            let size = match layout_orientation {
+                                LayoutOrientation::Horizontal => {
+                                    (node_shapes[idx].size.x as f64,node_shapes[idx].size.y as f64)
+                                },
+                                LayoutOrientation::Vertical => {
+                                    (node_shapes[idx].size.y as f64,node_shapes[idx].size.x as f64)
+                                }
+                            };
+                            Some((idx as u32,size))
+                        },
+                        Err(_) => None,
+                    }
+                })
+                .collect()
         }
     } else {
         return;
     };
-    let edges: Vec<(u32,u32)> = if let Ok(edges) = visible_nodes.edges.read() {
+    if let Some(hierarchy) = hierarchy {
+        predicate_hierarchy_layout(visible_nodes, &node_indexes, hierarchy, layout_orientation);
+        return;
+    }
+    let edges: Vec<(u32,u32)> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = &layout.edges;
         edges
             .iter()
             .filter(|e| {
@@ -79,7 +89,8 @@ pub fn hierarchical_layout(
         },
     );
     for (layout, _width, _height) in layouts {
-        if let Ok(mut positions) = visible_nodes.positions.write() {
+        if let Ok(mut layout_data) = visible_nodes.layout_data.write() {
+            let positions = &mut layout_data.positions;
             for (node_index, (x, y)) in layout {
                 let position = match layout_orientation {
                     LayoutOrientation::Horizontal => {
@@ -89,16 +100,324 @@ pub fn hierarchical_layout(
                         Pos2::new(-y as f32, x as f32)
                     }
                 };
-                positions[node_index].pos = position;
+                positions[node_index].move_to(position);
+            }
+        }
+    }
+}
+
+fn predicate_hierarchy_layout(
+    visible_nodes: &mut SortedNodeLayout,
+    node_indexes: &[(u32, (f64, f64))],
+    (predicate, upward): (IriIndex, bool),
+    layout_orientation: LayoutOrientation,
+) {
+    let included: HashMap<u32, (f64, f64)> = node_indexes.iter().copied().collect();
+    // the superior is the target of the edge, so it gets the last layer and is drawn on top
+    let hierarchy_edges: Vec<(u32, u32)> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = &layout.edges;
+        edges
+            .iter()
+            .filter(|e| {
+                e.predicate == predicate
+                    && e.from != e.to
+                    && included.contains_key(&(e.from as u32))
+                    && included.contains_key(&(e.to as u32))
+            })
+            .map(|e| {
+                if upward {
+                    (e.from as u32, e.to as u32)
+                } else {
+                    (e.to as u32, e.from as u32)
+                }
+            })
+            .collect()
+    } else {
+        return;
+    };
+    let hierarchy_edges = remove_cycle_edges(&hierarchy_edges);
+    let placed: BTreeSet<u32> = hierarchy_edges.iter().flat_map(|(from, to)| [*from, *to]).collect();
+    let placed_nodes: Vec<(u32, (f64, f64))> = placed.iter().map(|idx| (*idx, included[idx])).collect();
+    let layouts = if placed_nodes.is_empty() {
+        Vec::new()
+    } else {
+        from_vertices_and_edges(
+            &placed_nodes,
+            &hierarchy_edges,
+            &Config {
+                vertex_spacing: HIERARCHY_SPACING as f64,
+                ..Default::default()
+            },
+        )
+    };
+    let Ok(mut layout_data) = visible_nodes.layout_data.write() else {
+        return;
+    };
+    let positions = &mut layout_data.positions;
+    let mut offset = 0.0;
+    let mut min_pos = Pos2::new(f32::MAX, f32::MAX);
+    let mut max_pos = Pos2::new(f32::MIN, f32::MIN);
+    for (layout, width, _height) in layouts {
+        for (node_index, (x, y)) in layout {
+            let position = match layout_orientation {
+                LayoutOrientation::Horizontal => Pos2::new((x + offset) as f32, -y as f32),
+                LayoutOrientation::Vertical => Pos2::new(-y as f32, (x + offset) as f32),
+            };
+            positions[node_index].move_to(position);
+            let (size_x, size_y) = included[&(node_index as u32)];
+            let half_size = match layout_orientation {
+                LayoutOrientation::Horizontal => Vec2::new(size_x as f32, size_y as f32) / 2.0,
+                LayoutOrientation::Vertical => Vec2::new(size_y as f32, size_x as f32) / 2.0,
+            };
+            min_pos = min_pos.min(position - half_size);
+            max_pos = max_pos.max(position + half_size);
+        }
+        offset += width + HIERARCHY_SPACING as f64 * 2.0;
+    }
+    if placed.is_empty() {
+        min_pos = Pos2::ZERO;
+        max_pos = Pos2::ZERO;
+    }
+    // orphans in rows below the hierarchy, the rows are at least as wide as the hierarchy
+    let row_width = (max_pos.x - min_pos.x).max(600.0);
+    let mut x = min_pos.x;
+    let mut y = max_pos.y + HIERARCHY_SPACING * 2.0;
+    let mut row_height: f32 = 0.0;
+    for (node_index, (size_x, size_y)) in node_indexes.iter().filter(|(idx, _)| !placed.contains(idx)) {
+        let size = match layout_orientation {
+            LayoutOrientation::Horizontal => Vec2::new(*size_x as f32, *size_y as f32),
+            LayoutOrientation::Vertical => Vec2::new(*size_y as f32, *size_x as f32),
+        };
+        if x > min_pos.x && x + size.x > min_pos.x + row_width {
+            x = min_pos.x;
+            y += row_height + HIERARCHY_SPACING;
+            row_height = 0.0;
+        }
+        positions[*node_index as usize].move_to(Pos2::new(x, y) + size / 2.0);
+        x += size.x + HIERARCHY_SPACING;
+        row_height = row_height.max(size.y);
+    }
+}
+
+/// Drops the edges that close a cycle (back edges of a depth first search) and parallel edges
+fn remove_cycle_edges(edges: &[(u32, u32)]) -> Vec<(u32, u32)> {
+    let mut adjacency: HashMap<u32, Vec<u32>> = HashMap::new();
+    for (from, to) in edges.iter() {
+        let targets = adjacency.entry(*from).or_default();
+        if !targets.contains(to) {
+            targets.push(*to);
+        }
+    }
+    let mut starts: Vec<u32> = adjacency.keys().copied().collect();
+    starts.sort_unstable();
+    // 1 - on the current path, 2 - done
+    let mut state: HashMap<u32, u8> = HashMap::new();
+    let mut result: Vec<(u32, u32)> = Vec::with_capacity(edges.len());
+    for start in starts {
+        if state.contains_key(&start) {
+            continue;
+        }
+        state.insert(start, 1);
+        let mut stack: Vec<(u32, usize)> = vec![(start, 0)];
+        while let Some((node, next)) = stack.last_mut() {
+            let node = *node;
+            let targets = adjacency.get(&node).map(|targets| targets.as_slice()).unwrap_or_default();
+            if let Some(target) = targets.get(*next) {
+                *next += 1;
+                match state.get(target) {
+                    Some(1) => {}
+                    Some(_) => result.push((node, *target)),
+                    None => {
+                        result.push((node, *target));
+                        state.insert(*target, 1);
+                        stack.push((*target, 0));
+                    }
+                }
+            } else {
+                state.insert(node, 2);
+                stack.pop();
+            }
+        }
+    }
+    result
+}
+
+/**
+ * Org chart layout: only the nodes connected by the hierarchy predicates (reporting lines, organization units)
+ * are placed in layers with the superior above. The placed nodes are pinned so the force layout
+ * arranges the remaining nodes (e.g. the foaf:knows network) around them.
+ * Nodes pinned by the user keep their position.
+ * The bool of the hierarchy predicate is true if the object of the triple is the superior (org:reportsTo).
+ * Returns the nodes pinned by the layout, so the pins can be released later.
+ */
+pub fn org_chart_layout(visible_nodes: &mut SortedNodeLayout, hierarchy: &[(IriIndex, bool)]) -> Vec<IriIndex> {
+    let edges: Vec<(u32, u32)> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = &layout.edges;
+        edges
+            .iter()
+            .filter(|e| e.from != e.to)
+            .filter_map(|e| {
+                hierarchy
+                    .iter()
+                    .find(|(predicate, _)| *predicate == e.predicate)
+                    .map(|(_, upward)| {
+                        if *upward {
+                            (e.to as u32, e.from as u32)
+                        } else {
+                            (e.from as u32, e.to as u32)
+                        }
+                    })
+            })
+            .collect()
+    } else {
+        return Vec::new();
+    };
+    let placed: BTreeSet<u32> = edges.iter().flat_map(|(from, to)| [*from, *to]).collect();
+    if placed.is_empty() {
+        return Vec::new();
+    }
+    let node_indexes: Vec<(u32, (f64, f64))> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let node_shapes = &layout.node_shapes;
+        placed
+            .iter()
+            .map(|idx| {
+                // labels below the nodes need space too, the shapes may be not recomputed for the new styles yet
+                let size = node_shapes
+                    .get(*idx as usize)
+                    .map(|node_shape| node_shape.size)
+                    .unwrap_or_default();
+                (*idx, (size.x.max(ORG_CHART_MIN_WIDTH) as f64, size.y.max(ORG_CHART_MIN_HEIGHT) as f64))
+            })
+            .collect()
+    } else {
+        return Vec::new();
+    };
+    let layouts = from_vertices_and_edges(
+        &node_indexes,
+        &edges,
+        &Config {
+            vertex_spacing: 40.0,
+            ..Default::default()
+        },
+    );
+    let mut offset_x = 0.0;
+    let mut pinned: Vec<IriIndex> = Vec::new();
+    if let Ok(mut layout_data) = visible_nodes.layout_data.write() {
+        let LayoutData { nodes, positions, .. } = &mut *layout_data;
+        for (layout, width, _height) in layouts {
+            for (node_index, (x, y)) in layout {
+                let position = &mut positions[node_index];
+                if !position.locked {
+                    position.move_to(Pos2::new((x + offset_x) as f32, y as f32));
+                    position.locked = true;
+                    pinned.push(nodes[node_index].node_index);
+                }
             }
+            offset_x += width + ORG_CHART_MIN_WIDTH as f64;
         }
     }
+    pinned
 }
 
 #[cfg(test)]
 mod tests {
     use rust_sugiyama::{configure::Config, from_edges};
 
+    use crate::uistate::layout::{Edge, NodeShapeData, SortedNodeLayout};
+
+    #[test]
+    fn test_org_chart_layout() {
+        let reports_to = 7;
+        let knows = 8;
+        let mut visible_nodes = SortedNodeLayout::new();
+        for node_index in 0..4 {
+            visible_nodes.add_by_index(node_index);
+        }
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let edges = &mut layout.edges;
+            for (from, to, predicate) in [(1, 0, reports_to), (2, 0, reports_to), (3, 1, knows)] {
+                edges.push(Edge {
+                    from,
+                    to,
+                    predicate,
+                    bezier_distance: 0.0,
+                });
+            }
+        }
+        // the node pinned by the user keeps its position and pin
+        let user_pin = {
+            let mut layout = visible_nodes.layout_data.write().unwrap();
+            let positions = &mut layout.positions;
+            positions[2].locked = true;
+            positions[2].pos
+        };
+        let mut pinned = super::org_chart_layout(&mut visible_nodes, &[(reports_to, true)]);
+        // the positions of a layout are not ordered by the node
+        pinned.sort();
+        assert_eq!(vec![0, 1], pinned);
+        let layout = visible_nodes.layout_data.read().unwrap();
+        let positions = &layout.positions;
+        // the superior is above the people reporting to
+        assert!(positions[0].pos.y < positions[1].pos.y);
+        assert_eq!(user_pin, positions[2].pos);
+        assert!(positions[0].locked && positions[1].locked && positions[2].locked);
+        assert!(!positions[3].locked);
+    }
+
+    #[test]
+    fn test_remove_cycle_edges() {
+        // cycle 0 -> 1 -> 2 -> 0 and parallel edge 2 -> 3
+        let edges = super::remove_cycle_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (2, 3), (4, 3)]);
+        assert_eq!(vec![(0, 1), (1, 2), (2, 3), (4, 3)], edges);
+    }
+
+    #[test]
+    fn test_predicate_hierarchy_layout() {
+        let broader = 7;
+        let related = 8;
+        let mut visible_nodes = SortedNodeLayout::new();
+        for node_index in 0..5 {
+            visible_nodes.add_by_index(node_index);
+        }
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let node_shapes = &mut layout.node_shapes;
+            *node_shapes = vec![
+                NodeShapeData {
+                    size: egui::Vec2::new(40.0, 20.0),
+                    ..Default::default()
+                };
+                5
+            ];
+        }
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let edges = &mut layout.edges;
+            // 1 and 2 are narrower than 0, 3 is related only, 4 is orphan
+            for (from, to, predicate) in [(1, 0, broader), (2, 0, broader), (0, 1, related), (3, 1, related)] {
+                edges.push(Edge {
+                    from,
+                    to,
+                    predicate,
+                    bezier_distance: 0.0,
+                });
+            }
+        }
+        super::hierarchical_layout(
+            &mut visible_nodes,
+            &std::collections::BTreeSet::new(),
+            &crate::support::SortedVec::new(),
+            crate::layoutalg::LayoutOrientation::Horizontal,
+            Some((broader, true)),
+        );
+        let layout = visible_nodes.layout_data.read().unwrap();
+        let positions = &layout.positions;
+        assert!(positions[0].pos.y < positions[1].pos.y);
+        assert_eq!(positions[1].pos.y, positions[2].pos.y);
+        // not layered nodes are below the hierarchy
+        assert!(positions[3].pos.y > positions[1].pos.y);
+        assert_eq!(positions[3].pos.y, positions[4].pos.y);
+        assert!(positions[3].pos.x < positions[4].pos.x);
+    }
+
     #[test]
     fn test_sugiyama_lib() {
         let edges = [
@@ -11,7 +11,7 @@ use crate::{
         circular::{GEdge, find_components, gen_adj_start_node, random_dfs},
     },
     support::SortedVec,
-    uistate::layout::SortedNodeLayout,
+    uistate::layout::{LayoutData, SortedNodeLayout},
 };
 
 pub fn linear_layout(
@@ -20,7 +20,8 @@ pub fn linear_layout(
     hidden_predicates: &SortedVec,
     layout_orientation: LayoutOrientation,
 ) {
-    let node_indexes: Vec<usize> = if let Ok(nodes) = visible_nodes.nodes.read() {
+    let node_indexes: Vec<usize> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let nodes = &layout.nodes;
         if selected_nodes.len() < 3 {
             (0..nodes.len()).collect()
         } else {
@@ -36,8 +37,9 @@ pub fn linear_layout(
         return;
     }
     let mut edge_indexes: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
-    let edges: Vec<GEdge> = if let Ok(edges) = visible_nodes.edges.read() {
-        edges
+    let edges: Vec<GEdge> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        layout
+            .edges
             .iter()
             .enumerate()
             .filter(|(_e_index, e)| {
@@ -53,7 +55,8 @@ pub fn linear_layout(
         return;
     };
     let mut rect = Rect::NOTHING;
-    if let Ok(positions) = visible_nodes.positions.read() {
+    if let Ok(layout) = visible_nodes.layout_data.read() {
+        let positions = &layout.positions;
         for node_idx in node_indexes.iter() {
             let pos = positions[*node_idx];
             rect.extend_with(pos.pos);
@@ -71,7 +74,8 @@ pub fn linear_layout(
     let components = find_components(&edges, &node_indexes);
     for component in components.iter() {
         if component.len() > 2 {
-            if let Ok(mut edges) = visible_nodes.edges.write() {
+            if let Ok(mut layout) = visible_nodes.layout_data.write() {
+                let edges = &mut layout.edges;
                 let comp_edges = edges
                     .iter()
                     .filter(|e| component.contains(&e.from) || component.contains(&e.to))
@@ -101,26 +105,23 @@ pub fn linear_layout(
             order.extend(component);
         }
     }
-    if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
-        if let Ok(mut positions) = visible_nodes.positions.write() {
-            for node_idx in order.iter() {
-                if let Some(pos) = positions.get_mut(*node_idx) {
-                    match layout_orientation {
-                        LayoutOrientation::Horizontal => {
-                            let node_size = node_shapes[*node_idx].size.x;
-                            pos.pos = Pos2::new(start_pos + node_size*0.5, center.y);
-                            start_pos += node_size + spacing;
-                        }
-                        LayoutOrientation::Vertical => {
-                            let node_size = node_shapes[*node_idx].size.y;
-                            pos.pos = Pos2::new(center.x, start_pos + node_size * 0.5);
-                            start_pos += node_size + spacing;
-                        }
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let LayoutData { positions, node_shapes, .. } = &mut *layout;
+        for node_idx in order.iter() {
+            if let Some(pos) = positions.get_mut(*node_idx) {
+                match layout_orientation {
+                    LayoutOrientation::Horizontal => {
+                        let node_size = node_shapes[*node_idx].size.x;
+                        pos.move_to(Pos2::new(start_pos + node_size*0.5, center.y));
+                        start_pos += node_size + spacing;
+                    }
+                    LayoutOrientation::Vertical => {
+                        let node_size = node_shapes[*node_idx].size.y;
+                        pos.move_to(Pos2::new(center.x, start_pos + node_size * 0.5));
+                        start_pos += node_size + spacing;
                     }
                 }
             }
-        } else {
-            return;
         }
     } else {
         return;
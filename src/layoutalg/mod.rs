@@ -6,12 +6,14 @@ pub mod overlap;
 pub mod ortho;
 pub mod linear;
 pub mod multipartite;
+pub mod radial;
+pub mod grouped;
 
 use std::{collections::BTreeSet, sync::{Arc, RwLock}};
 
 use strum_macros::{EnumIter, Display};
 
-use crate::{IriIndex, domain::{RdfData, graph_styles::GVisualizationStyle}, support::SortedVec, uistate::layout::SortedNodeLayout};
+use crate::{IriIndex, domain::{RdfData, config::Config, graph_styles::GVisualizationStyle}, support::SortedVec, uistate::layout::SortedNodeLayout};
 
 #[derive(Debug, Clone, Copy, EnumIter, Display, PartialEq)]
 pub enum LayoutAlgorithm {
@@ -27,6 +29,10 @@ pub enum LayoutAlgorithm {
     LinearVertical,
     #[strum(to_string = "Multipartite")]
     Multipartite,
+    #[strum(to_string = "Grouped by Type")]
+    GroupedByType,
+    #[strum(to_string = "Radial (around selected node)")]
+    Radial,
     #[strum(to_string = "Spectral")]
     Spectral,
     #[strum(to_string = "Node Overlap Removal")]
@@ -35,14 +41,19 @@ pub enum LayoutAlgorithm {
     Orthogonal,
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn run_layout_algorithm(algorithm: LayoutAlgorithm, 
     visible_nodes: &mut SortedNodeLayout,
+    selected_node: Option<IriIndex>,
     selected_nodes: &BTreeSet<IriIndex>,
     hidden_predicates: &SortedVec,
     visualization_style: &GVisualizationStyle,
     rdf_data: Arc<RwLock<RdfData>>,
-) {
+    hierarchy_predicate: Option<(IriIndex, bool)>,
+    config: &Config,
+) -> Option<String> {
     let mut remove_orth = true;
+    let mut warning = None;
     match algorithm {
         LayoutAlgorithm::Circular => {
             circular::circular_layout(visible_nodes, selected_nodes,hidden_predicates);
@@ -53,6 +64,7 @@ pub fn run_layout_algorithm(algorithm: LayoutAlgorithm,
                 selected_nodes,
                 hidden_predicates,
                 LayoutOrientation::Horizontal,
+                hierarchy_predicate,
             );
         },
         LayoutAlgorithm::HierarchicalVertical => {
@@ -61,6 +73,7 @@ pub fn run_layout_algorithm(algorithm: LayoutAlgorithm,
                 selected_nodes,
                 hidden_predicates,
                 LayoutOrientation::Vertical,
+                hierarchy_predicate,
             );
         },
         LayoutAlgorithm::LinearHorizontal => {
@@ -88,6 +101,19 @@ pub fn run_layout_algorithm(algorithm: LayoutAlgorithm,
                 rdf_data
             );
         },
+        LayoutAlgorithm::GroupedByType => {
+            grouped::grouped_layout(
+                visible_nodes,
+                selected_nodes,
+                hidden_predicates,
+                visualization_style,
+                rdf_data,
+                config,
+            );
+        },
+        LayoutAlgorithm::Radial => {
+            radial::radial_layout(visible_nodes, selected_node, selected_nodes, hidden_predicates);
+        },
         LayoutAlgorithm::Spectral => {
             spectral::spectral_layout(visible_nodes, selected_nodes, hidden_predicates);
         },
@@ -95,14 +121,15 @@ pub fn run_layout_algorithm(algorithm: LayoutAlgorithm,
             overlap::nachmanson_layout(visible_nodes, selected_nodes);
         },
         LayoutAlgorithm::Orthogonal => {
-            ortho::orthogonal_edge_routing(visible_nodes, selected_nodes, hidden_predicates);
-            remove_orth = false;
+            warning = ortho::orthogonal_edge_routing(visible_nodes, selected_nodes, hidden_predicates);
+            remove_orth = warning.is_some();
         },
     }
     if remove_orth {
         visible_nodes.show_orthogonal = false;
         visible_nodes.orth_edges = None;
     }
+    warning
 }
 
 pub enum LayoutOrientation {
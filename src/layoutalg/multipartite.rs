@@ -8,7 +8,7 @@ use crate::{
     IriIndex,
     domain::{RdfData, graph_styles::GVisualizationStyle},
     support::SortedVec,
-    uistate::layout::SortedNodeLayout,
+    uistate::layout::{LayoutData, SortedNodeLayout},
 };
 
 pub fn multipartite_layout(
@@ -18,7 +18,8 @@ pub fn multipartite_layout(
     visualization_style: &GVisualizationStyle,
     rdf_data: Arc<RwLock<RdfData>>,
 ) {
-    let node_indexes: Vec<usize> = if let Ok(nodes) = visible_nodes.nodes.read() {
+    let node_indexes: Vec<usize> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let nodes = &layout.nodes;
         if selected_nodes.len() < 3 {
             (0..nodes.len()).collect()
         } else {
@@ -34,7 +35,8 @@ pub fn multipartite_layout(
         return;
     }
     let mut adj_map: HashMap<usize, Vec<usize>> = HashMap::with_capacity(node_indexes.len());
-    if let Ok(edges) = visible_nodes.edges.read() {
+    if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = &layout.edges;
         edges
             .iter()
             .filter(| e| {
@@ -49,7 +51,8 @@ pub fn multipartite_layout(
         return;
     };
     let mut rect = Rect::NOTHING;
-    if let Ok(positions) = visible_nodes.positions.read() {
+    if let Ok(layout) = visible_nodes.layout_data.read() {
+        let positions = &layout.positions;
         for node_idx in node_indexes.iter() {
             let pos = positions[*node_idx];
             rect.extend_with(pos.pos);
@@ -64,14 +67,13 @@ pub fn multipartite_layout(
     // If there are more then 3 types. Analyse possible connections between or types and build connection graph
     // Arrange connected types in circle and other place on side
     let mut node_types: HashMap<IriIndex, Vec<usize>> = HashMap::new();
-    if let Ok(rdf_data) = rdf_data.read() {
-        if let Ok(nodes) = visible_nodes.nodes.read() {
-            for node_idx in node_indexes.iter() {
-                if let Some((_, nnode)) = rdf_data.node_data.get_node_by_index(nodes[*node_idx].node_index) {
-                    let htypes = nnode.highest_priority_types(visualization_style);
-                    if let Some(first_type) = htypes.first() {
-                        node_types.entry(*first_type).or_default().push(*node_idx);
-                    }
+    if let Some((rdf_data, layout)) = visible_nodes.read_with_data(&rdf_data) {
+        let nodes = &layout.nodes;
+        for node_idx in node_indexes.iter() {
+            if let Some((_, nnode)) = rdf_data.node_data.get_node_by_index(nodes[*node_idx].node_index) {
+                let htypes = nnode.highest_priority_types(visualization_style);
+                if let Some(first_type) = htypes.first() {
+                    node_types.entry(*first_type).or_default().push(*node_idx);
                 }
             }
         }
@@ -89,7 +91,8 @@ pub fn multipartite_layout(
     // needed to align types to center
     for (ntype, nodes) in node_types.iter() {
         let mut start_pos: f32 = 0.0;
-        if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
+        if let Ok(layout) = visible_nodes.layout_data.read() {
+            let node_shapes = &layout.node_shapes;
             for node_idx in nodes.iter() {
                 let node_size = node_shapes[*node_idx].size.y;
                 start_pos += node_size + spacing;
@@ -103,22 +106,21 @@ pub fn multipartite_layout(
         let nodes = node_types.get(ntype).unwrap();
         let mut start_pos: f32 = rect.top() + (max_height - types_height[ntype]) * 0.5;
         let mut max_width: f32 = 0.0;
-        if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
-            if let Ok(mut positions) = visible_nodes.positions.write() {
-                for node_idx in nodes.iter() {
-                    if let Some(pos) = positions.get_mut(*node_idx) {
-                        let node_size = node_shapes[*node_idx].size.y;
-                        let node_xpos = if type_pos == 0 {
-                            xpos - node_shapes[*node_idx].size.x * 0.5
-                        } else if type_pos == type_order.len() -1 {
-                            xpos + node_shapes[*node_idx].size.x * 0.5
-                        } else {
-                            xpos
-                        };
-                        pos.pos = Pos2::new(node_xpos, start_pos + node_size * 0.5);
-                        start_pos += node_size + spacing;
-                        max_width = max_width.max(node_shapes[*node_idx].size.x);
-                    }
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let LayoutData { positions, node_shapes, .. } = &mut *layout;
+            for node_idx in nodes.iter() {
+                if let Some(pos) = positions.get_mut(*node_idx) {
+                    let node_size = node_shapes[*node_idx].size.y;
+                    let node_xpos = if type_pos == 0 {
+                        xpos - node_shapes[*node_idx].size.x * 0.5
+                    } else if type_pos == type_order.len() -1 {
+                        xpos + node_shapes[*node_idx].size.x * 0.5
+                    } else {
+                        xpos
+                    };
+                    pos.move_to(Pos2::new(node_xpos, start_pos + node_size * 0.5));
+                    start_pos += node_size + spacing;
+                    max_width = max_width.max(node_shapes[*node_idx].size.x);
                 }
             }
         }
@@ -100,6 +100,16 @@ struct AreaLimit {
     node_id: usize,
 }
 
+// The limits are sorted by coord, so the search can start at the first candidate (binary search)
+// instead of skipping all limits before it
+fn upto(limits: &[AreaLimit], coord: f32) -> usize {
+    limits.partition_point(|limit| limit.coord <= coord)
+}
+
+fn from(limits: &[AreaLimit], coord: f32) -> usize {
+    limits.partition_point(|limit| limit.coord < coord)
+}
+
 pub fn build_channels(boxes: &[ERect]) -> (Vec<RChannel>,Vec<RChannel>) {
     let bounding_box = boxes.iter().fold(
         ERect::from_min_max(egui::pos2(f32::INFINITY, f32::INFINITY), egui::pos2(f32::NEG_INFINITY, f32::NEG_INFINITY)),
@@ -134,22 +144,13 @@ pub fn build_channels(boxes: &[ERect]) -> (Vec<RChannel>,Vec<RChannel>) {
     for right in limits_vl.iter() {
         // find to
         // search first area that intersect line vertical lien from left edge corner
-        'next_area: for top in limits_hb.iter().rev() {
-            if top.coord > right.min {
-                continue;
-            }
+        'next_area: for top in limits_hb[..upto(&limits_hb, right.min)].iter().rev() {
             if right.coord >= top.min && right.coord <= top.max {
                 // find bottom
-                for bottom in limits_ht.iter() {
-                    if bottom.coord < right.max {
-                        continue;
-                    }
+                for bottom in limits_ht[from(&limits_ht, right.max)..].iter() {
                     if right.coord >= bottom.min && right.coord <= bottom.max {
                         // find left
-                        for left in limits_vr.iter().rev() {
-                            if left.coord > right.coord {
-                                continue;
-                            }
+                        for left in limits_vr[..upto(&limits_vr, right.coord)].iter().rev() {
                             // check if left min and max intersect with top and bottom
                             if left.min <= bottom.coord && top.coord <= left.max {
                                 let mut channel = RChannel::from_min_max(
@@ -189,22 +190,13 @@ pub fn build_channels(boxes: &[ERect]) -> (Vec<RChannel>,Vec<RChannel>) {
     for left in limits_vr.iter() {
         // find to
         // search first area that intersect line vertical lien from left edge corner
-        'next_area: for top in limits_hb.iter().rev() {
-            if top.coord > left.min {
-                continue;
-            }
+        'next_area: for top in limits_hb[..upto(&limits_hb, left.min)].iter().rev() {
             if left.coord >= top.min && left.coord <= top.max {
                 // find bottom
-                for bottom in limits_ht.iter() {
-                    if bottom.coord < left.max {
-                        continue;
-                    }
+                for bottom in limits_ht[from(&limits_ht, left.max)..].iter() {
                     if left.coord >= bottom.min && left.coord <= bottom.max {
                         // find left
-                        for right in limits_vl.iter() {
-                            if left.coord > right.coord {
-                                continue;
-                            }
+                        for right in limits_vl[from(&limits_vl, left.coord)..].iter() {
                             // check if left min and max intersect with top and bottom
                             if right.min <= bottom.coord && top.coord <= right.max {
                                 let mut channel = RChannel::from_min_max(
@@ -245,22 +237,13 @@ pub fn build_channels(boxes: &[ERect]) -> (Vec<RChannel>,Vec<RChannel>) {
     for bottom in limits_ht.iter() {
         // find to
         // search first area that intersect line vertical line from left edge corner
-        'next_area: for left in limits_vr.iter().rev() {
-            if left.coord > bottom.min {
-                continue;
-            }
+        'next_area: for left in limits_vr[..upto(&limits_vr, bottom.min)].iter().rev() {
             if bottom.coord >= left.min && bottom.coord <= left.max {
                 // find right
-                for right in limits_vl.iter() {
-                    if right.coord < bottom.max {
-                        continue;
-                    }
+                for right in limits_vl[from(&limits_vl, bottom.max)..].iter() {
                     if bottom.coord >= right.min && bottom.coord <= right.max {
                         // find top
-                        for top in limits_hb.iter().rev() {
-                            if top.coord > bottom.coord {
-                                continue;
-                            }
+                        for top in limits_hb[..upto(&limits_hb, bottom.coord)].iter().rev() {
                             // check if left min and max intersect with top and bottom
                             if top.min <= right.coord && left.coord <= top.max {
                                 let mut channel = RChannel::from_min_max(
@@ -299,22 +282,13 @@ pub fn build_channels(boxes: &[ERect]) -> (Vec<RChannel>,Vec<RChannel>) {
     for top in limits_hb.iter() {
         // find to
         // search first area that intersect line vertical line from left edge corner
-        'next_area: for left in limits_vr.iter().rev() {
-            if left.coord > top.min {
-                continue;
-            }
+        'next_area: for left in limits_vr[..upto(&limits_vr, top.min)].iter().rev() {
             if top.coord >= left.min && top.coord <= left.max {
                 // find right
-                for right in limits_vl.iter() {
-                    if right.coord < top.max {
-                        continue;
-                    }
+                for right in limits_vl[from(&limits_vl, top.max)..].iter() {
                     if top.coord >= right.min && top.coord <= right.max {
                         // find bottom
-                        for bottom in limits_ht.iter() {
-                            if top.coord > bottom.coord {
-                                continue;
-                            }
+                        for bottom in limits_ht[from(&limits_ht, top.coord)..].iter() {
                             // check if left min and max intersect with top and bottom
                             if bottom.min <= right.coord && left.coord <= bottom.max {
                                 let mut channel = RChannel::from_min_max(
@@ -9,7 +9,7 @@ use crate::{IriIndex,
         sizelayout::resize_channels,
     }, 
     support::SortedVec, 
-    uistate::layout::{Edge, OrthEdge, OrthEdges, SortedNodeLayout}};
+    uistate::layout::{Edge, LayoutData, OrthEdge, OrthEdges, SortedNodeLayout, node_pos}};
 
 pub mod routing;
 pub mod sizelayout;
@@ -29,67 +29,200 @@ macro_rules! dbgorth {
     ($($arg:tt)*) => {};
 }
 
+// the routing graph grows too much for bigger graphs
+pub const MAX_ORTHOGONAL_NODES: usize = 5000;
+
+/// Returns a warning if the graph has too many nodes for the routing.
+/// If more nodes are selected only the edges between the selected nodes are routed,
+/// the other edges stay bezier curves.
 pub fn orthogonal_edge_routing(
     visible_nodes: &mut SortedNodeLayout,
-    _selected_nodes: &BTreeSet<IriIndex>,
+    selected_nodes: &BTreeSet<IriIndex>,
     hidden_predicates: &SortedVec,
-) {
-    if let Ok(edges) = visible_nodes.edges.read() {
-        if let Ok(mut positions) = visible_nodes.positions.write() {
-            if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
-                /*
-                let boxes: Vec<Rect> = nodes.iter().zip(positions.iter().zip(node_shapes.iter()))
-                    .filter(|(node,_c)| selected_nodes.contains(&node.node_index))
-                    .map(|(_node,(pos, shape))| 
-                        Rect::from_center_size(pos.pos, shape.size)                    
-                    ).collect();
-                    */
-                // We just take all nodes not only the selected ones for now
-                let mut boxes: Vec<Rect> = positions.iter().zip(node_shapes.iter())
-                    .map(|(pos, shape)| 
-                        Rect::from_center_size(pos.pos, shape.size)                    
-                    ).collect();
-                let g_edges: Vec<Edge> = edges
-                            .iter()
-                            .filter(|e| {
-                                !hidden_predicates.contains(e.predicate) && e.from != e.to
-                            })
-                            .map(|e| Edge {
-                                from: e.from,
-                                to: e.to,
-                                predicate: e.predicate,
-                                bezier_distance: 0.0,
-                            })
-                            .collect();
-                
-                let mut routing_graph = create_routing_graph(&boxes);                
-                let mut channel_connectors = create_channel_connectors(&routing_graph, &boxes);
-                let routes = route_edges(&routing_graph, &g_edges, &boxes);
-                let graph_edge_routes = calculate_edge_routes(&routing_graph, &mut channel_connectors, &g_edges, &routes, &boxes);
-                                    
-                let min_channel_sizes_vertical: Vec<f32> = graph_edge_routes.channel_slots.iter().take(routing_graph.vchannels.len()).map(|c| 20.0+(*c as f32)*8.0).collect();
-                let min_channel_sizes_horizontal: Vec<f32> = graph_edge_routes.channel_slots.iter().skip(routing_graph.vchannels.len()).map(|c| 20.0+(*c as f32)*8.0).collect();
-                resize_channels(&mut routing_graph, &mut boxes, &min_channel_sizes_vertical, &min_channel_sizes_horizontal);
-
-                for (pos, rect) in positions.iter_mut().zip(boxes.iter()) {
-                    pos.pos = rect.center();
+) -> Option<String> {
+    let routed_nodes = if selected_nodes.len() > 1 {
+        visible_nodes.layout_data.read().ok().and_then(|layout| {
+            let mut routed_nodes: Vec<usize> =
+                selected_nodes.iter().filter_map(|node_index| node_pos(&layout.nodes, *node_index)).collect();
+            routed_nodes.sort_unstable();
+            (routed_nodes.len() > 1).then_some(routed_nodes)
+        })
+    } else {
+        None
+    };
+    visible_nodes.orth_outdated = false;
+    route_nodes(visible_nodes, routed_nodes, hidden_predicates, true)
+}
+
+/// Routes the same nodes again, used after the nodes were moved.
+/// Without resize the nodes keep their positions even if the channels are too narrow for all edges.
+pub fn reroute_orthogonal_edges(visible_nodes: &mut SortedNodeLayout, hidden_predicates: &SortedVec, resize: bool) -> Option<String> {
+    let routed_nodes = visible_nodes.orth_edges.as_ref().and_then(|orth_edges| orth_edges.routed_nodes.clone());
+    route_nodes(visible_nodes, routed_nodes, hidden_predicates, resize)
+}
+
+fn route_nodes(
+    visible_nodes: &mut SortedNodeLayout,
+    routed_nodes: Option<Vec<usize>>,
+    hidden_predicates: &SortedVec,
+    resize: bool,
+) -> Option<String> {
+    let nodes_len = match &routed_nodes {
+        Some(routed_nodes) => routed_nodes.len(),
+        None => visible_nodes.layout_data.read().map_or(0, |layout| layout.nodes.len()),
+    };
+    if nodes_len > MAX_ORTHOGONAL_NODES {
+        return Some(format!(
+            "Orthogonal edge routing is limited to {} nodes, the visual graph has {} nodes. Hide some nodes first.",
+            MAX_ORTHOGONAL_NODES, nodes_len
+        ));
+    }
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let LayoutData { edges, positions, node_shapes, .. } = &mut *layout;
+        // node positions of the routing boxes, all nodes or only the routed subgraph
+        let box_nodes: Vec<usize> = routed_nodes.clone().unwrap_or_else(|| (0..positions.len()).collect());
+        let box_index = |node: usize| match &routed_nodes {
+            Some(routed_nodes) => routed_nodes.binary_search(&node).ok(),
+            None => Some(node),
+        };
+        let mut boxes: Vec<Rect> = box_nodes.iter()
+            .map(|node| 
+                Rect::from_center_size(positions[*node].pos, node_shapes[*node].size)                    
+            ).collect();
+        let g_edges: Vec<Edge> = edges
+                    .iter()
+                    .filter(|e| {
+                        !hidden_predicates.contains(e.predicate) && e.from != e.to
+                    })
+                    .filter_map(|e| Some(Edge {
+                        from: box_index(e.from)?,
+                        to: box_index(e.to)?,
+                        predicate: e.predicate,
+                        bezier_distance: 0.0,
+                    }))
+                    .collect();
+        
+        let mut routing_graph = create_routing_graph(&boxes);                
+        let mut channel_connectors = create_channel_connectors(&routing_graph, &boxes);
+        let routes = route_edges(&routing_graph, &g_edges, &boxes);
+        let graph_edge_routes = calculate_edge_routes(&routing_graph, &mut channel_connectors, &g_edges, &routes, &boxes);
+                            
+        if resize {
+            let min_channel_sizes_vertical: Vec<f32> = graph_edge_routes.channel_slots.iter().take(routing_graph.vchannels.len()).map(|c| 20.0+(*c as f32)*8.0).collect();
+            let min_channel_sizes_horizontal: Vec<f32> = graph_edge_routes.channel_slots.iter().skip(routing_graph.vchannels.len()).map(|c| 20.0+(*c as f32)*8.0).collect();
+            resize_channels(&mut routing_graph, &mut boxes, &min_channel_sizes_vertical, &min_channel_sizes_horizontal);
+
+            for (node, rect) in box_nodes.iter().zip(boxes.iter_mut()) {
+                positions[*node].move_to(rect.center());
+                // pinned nodes keep their position, the edges are attached to it
+                *rect = Rect::from_center_size(positions[*node].pos, rect.size());
+            }
+        }
+
+        let route_segments = map_routes_to_segments(&routing_graph, &boxes, &routes, &graph_edge_routes);
+        let orth_edges = OrthEdges {
+            edges: route_segments.into_iter().enumerate().map(|(i, segs)| {
+                OrthEdge {
+                    from_node: box_nodes[g_edges[i].from],
+                    to_node: box_nodes[g_edges[i].to],
+                    predicate: g_edges[i].predicate,
+                    control_points: segs,
                 }
+            }).collect(),
+            routed_nodes,
+        };
+        visible_nodes.orth_edges = Some(orth_edges);
+        visible_nodes.show_orthogonal = true;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::uistate::layout::NodeShapeData;
 
-                let route_segments = map_routes_to_segments(&routing_graph, &boxes, &routes, &graph_edge_routes);
-                let orth_edges = OrthEdges {
-                    edges: route_segments.into_iter().enumerate().map(|(i, segs)| {
-                        OrthEdge {
-                            from_node: g_edges[i].from,
-                            to_node: g_edges[i].to,
-                            predicate: g_edges[i].predicate,
-                            control_points: segs,
-                        }
-                    }).collect()
+    fn grid_layout() -> SortedNodeLayout {
+        let mut visible_nodes = SortedNodeLayout::new();
+        for node_index in 0..4 {
+            visible_nodes.add_by_index(node_index);
+        }
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let node_shapes = &mut layout.node_shapes;
+            *node_shapes = vec![
+                NodeShapeData {
+                    size: egui::Vec2::new(40.0, 20.0),
+                    ..Default::default()
                 };
-                visible_nodes.orth_edges = Some(orth_edges);
-                visible_nodes.show_orthogonal = true;
+                4
+            ];
+        }
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let positions = &mut layout.positions;
+            for (idx, position) in positions.iter_mut().enumerate() {
+                position.pos = egui::pos2((idx % 2) as f32 * 200.0, (idx / 2) as f32 * 200.0);
             }
         }
-    }    
-}
+        if let Ok(mut layout) = visible_nodes.layout_data.write() {
+            let edges = &mut layout.edges;
+            for (from, to) in [(0, 1), (1, 2), (2, 3)] {
+                edges.push(Edge {
+                    from,
+                    to,
+                    predicate: 0,
+                    bezier_distance: 0.0,
+                });
+            }
+        }
+        visible_nodes
+    }
+
+    #[test]
+    fn test_partial_routing() {
+        let mut visible_nodes = grid_layout();
+        let not_routed_pos = visible_nodes.layout_data.read().unwrap().positions[3].pos;
+        let selected_nodes: BTreeSet<IriIndex> = [0, 1].into_iter().collect();
+        let warning = orthogonal_edge_routing(&mut visible_nodes, &selected_nodes, &SortedVec::new());
+        assert!(warning.is_none());
+        assert!(visible_nodes.is_partial_orthogonal());
+        let orth_edges = visible_nodes.orth_edges.as_ref().unwrap();
+        assert_eq!(1, orth_edges.edges.len());
+        assert_eq!((0, 1), (orth_edges.edges[0].from_node, orth_edges.edges[0].to_node));
+        assert!(orth_edges.is_routed(0, 1));
+        assert!(!orth_edges.is_routed(1, 2));
+        assert_eq!(not_routed_pos, visible_nodes.layout_data.read().unwrap().positions[3].pos);
 
+        // without multi selection all edges are routed
+        let warning = orthogonal_edge_routing(&mut visible_nodes, &BTreeSet::new(), &SortedVec::new());
+        assert!(warning.is_none());
+        assert!(!visible_nodes.is_partial_orthogonal());
+        assert_eq!(3, visible_nodes.orth_edges.as_ref().unwrap().edges.len());
+    }
+
+    #[test]
+    fn test_reroute_moved_node() {
+        let mut visible_nodes = grid_layout();
+        orthogonal_edge_routing(&mut visible_nodes, &BTreeSet::new(), &SortedVec::new());
+        let control_points = |visible_nodes: &SortedNodeLayout| visible_nodes.orth_edges.as_ref().unwrap().edges[0].control_points.clone();
+        let routed_points = control_points(&visible_nodes);
+        let moved_pos = {
+            let mut layout = visible_nodes.layout_data.write().unwrap();
+            let positions = &mut layout.positions;
+            positions[0].pos.y -= 300.0;
+            positions[0].pos
+        };
+        visible_nodes.invalidate_orthogonal();
+        // throttled while dragging
+        visible_nodes.update_orthogonal(&SortedVec::new(), 0.1, true);
+        assert_eq!(routed_points, control_points(&visible_nodes));
+        // routed without moving the dragged node
+        visible_nodes.update_orthogonal(&SortedVec::new(), 1.0, true);
+        assert_ne!(routed_points, control_points(&visible_nodes));
+        assert_eq!(moved_pos, visible_nodes.layout_data.read().unwrap().positions[0].pos);
+        assert!(visible_nodes.orth_outdated);
+        // drag end
+        visible_nodes.update_orthogonal(&SortedVec::new(), 1.1, false);
+        assert!(!visible_nodes.orth_outdated);
+        assert!(visible_nodes.show_orthogonal);
+    }
+}
@@ -2,12 +2,19 @@ use std::collections::VecDeque;
 
 use crate::dbgorth;
 
+// Maximal number of routes visited by the cycle check. On big graphs the check gives up and the
+// ordering is skipped (as if it would create a cycle), which costs only some avoidable crossings
+const MAX_CYCLE_SEARCH: usize = 64;
+
 // Module for sorting routes based on topology
 // Internally directed graph is used to represent ordering
 // After adding all ordering relations, a topological sort can be performed
 pub struct TopologyRouting {
     pub routes_order_graph: Vec<Vec<usize>>,
     pub detected_cycles: usize,
+    // generation stamps of visited routes, so the cycle check does not allocate for each added ordering
+    visited: Vec<u32>,
+    visit_generation: u32,
 }
 
 impl TopologyRouting {
@@ -15,6 +22,8 @@ impl TopologyRouting {
         TopologyRouting {
             routes_order_graph: vec![Vec::new(); routes_count],
             detected_cycles: 0,
+            visited: vec![0; routes_count],
+            visit_generation: 0,
         }
     }
 
@@ -24,6 +33,9 @@ impl TopologyRouting {
         assert!(route_greater < self.routes_order_graph.len());
         assert!(route_less < self.routes_order_graph.len());
         dbgorth!("Adding route order: {} over {}", route_greater, route_less);
+        if self.routes_order_graph[route_greater].contains(&route_less) {
+            return true;
+        }
         // Check for potential cycle
         if self.has_path_bfs(route_less,route_greater) {
             self.detected_cycles += 1;
@@ -33,25 +45,31 @@ impl TopologyRouting {
         true
     }
 
-    fn has_path_bfs(&self, start: usize, end: usize) -> bool {
+    fn has_path_bfs(&mut self, start: usize, end: usize) -> bool {
         if start == end {
             return true;
         }
 
-        let mut visited = vec![false; self.routes_order_graph.len()];
+        self.visit_generation += 1;
+        let generation = self.visit_generation;
         let mut queue = VecDeque::from([start]);
+        let mut visited_count = 0;
 
         while let Some(node) = queue.pop_front() {
-            if visited[node] {
+            if self.visited[node] == generation {
                 continue;
             }
-            visited[node] = true;
+            self.visited[node] = generation;
+            visited_count += 1;
+            if visited_count > MAX_CYCLE_SEARCH {
+                return true;
+            }
 
             for &neighbor in &self.routes_order_graph[node] {
                 if neighbor == end {
                     return true;
                 }
-                if !visited[neighbor] {
+                if self.visited[neighbor] != generation {
                     queue.push_back(neighbor);
                 }
             }
@@ -1,8 +1,10 @@
-use std::{collections::VecDeque, iter, usize};
+use std::{collections::VecDeque, usize};
 
 use egui::{Pos2, Rect as ERect};
+use rayon::prelude::*;
 use crate::dbgorth;
 use crate::layoutalg::ortho::channels::{ChannelPort, ChannelPortType};
+use crate::support::quad_tree::RectQuadtree;
 use crate::uistate::layout::Edge;
 
 use super::channels::{RChannel, build_channels};
@@ -262,27 +264,11 @@ impl RoutingGraph {
         }
     }
 
+    /// Crossing channels of the channel, the bends are registered as ports of the channel
     pub fn bend_iterator(&self, channel_idx: usize, orientation: Orientation) -> impl Iterator<Item=usize> {
-        let mut start_idx = self.nodes_len + self.nodes_len * 4;
-        iter::from_fn(move || {
-            while start_idx < self.nodes.len() {
-                let idx = start_idx;
-                let node_type = &self.nodes[idx].node_type;
-                match node_type {
-                    RNodeType::BendPoint(v_idx,h_idx) => {
-                        start_idx += 1;
-                        match orientation {
-                            Orientation::Vertical if *v_idx == channel_idx => return Some(*h_idx),
-                            Orientation::Horizontal if *h_idx == channel_idx => return Some(*v_idx),
-                            _ => {}
-                        }
-                    },
-                    _ => {
-                        start_idx = self.nodes.len();
-                    }
-                }
-            }
-            None
+        self.channel(channel_idx, orientation).ports.iter().filter_map(|port| match port.port_type {
+            ChannelPortType::Bend { channel_id } => Some(channel_id),
+            _ => None,
         })
     }
 
@@ -374,27 +360,28 @@ pub fn create_routing_graph(boxes: &[ERect]) -> RoutingGraph {
             }
         }
     }
+    // only the horizontal channels near to the vertical channel are tested for crossing
+    let hchannel_tree = RectQuadtree::new(hchannels.iter().map(|c| c.rect).collect(), 8);
     for (vindex,vchannel) in vchannels.iter_mut().enumerate() {
-        for (hindex, hchannel) in hchannels.iter_mut().enumerate() {
-            if vchannel.rect.intersects(hchannel.rect) {
-                let intersection = vchannel.rect.intersect(hchannel.rect);
-                let bend_node_idx = rnodes.len();
-                vchannel.ports.push(ChannelPort {
-                    position: intersection.center().y,
-                    port_type: ChannelPortType::Bend{ channel_id: hindex },
-                    rnode_id: bend_node_idx,
-                });
-                hchannel.ports.push(ChannelPort {
-                    position: intersection.center().x,
-                    port_type: ChannelPortType::Bend{ channel_id: vindex },
-                    rnode_id: bend_node_idx
-                });
-                cross_points.push((vindex,hindex, intersection.center()));
-                let rnode = RNode::from_type(RNodeType::BendPoint(vindex,hindex));
-                rnodes.push(rnode);
-                // redges.push(REdge{from: bend_node_idx, to: vchannel_offset + vindex});
-                // redges.push(REdge{from: bend_node_idx, to: hchannel_offset + hindex});
-            }
+        for hindex in hchannel_tree.query(vchannel.rect) {
+            let hchannel = &mut hchannels[hindex];
+            let intersection = vchannel.rect.intersect(hchannel.rect);
+            let bend_node_idx = rnodes.len();
+            vchannel.ports.push(ChannelPort {
+                position: intersection.center().y,
+                port_type: ChannelPortType::Bend{ channel_id: hindex },
+                rnode_id: bend_node_idx,
+            });
+            hchannel.ports.push(ChannelPort {
+                position: intersection.center().x,
+                port_type: ChannelPortType::Bend{ channel_id: vindex },
+                rnode_id: bend_node_idx
+            });
+            cross_points.push((vindex,hindex, intersection.center()));
+            let rnode = RNode::from_type(RNodeType::BendPoint(vindex,hindex));
+            rnodes.push(rnode);
+            // redges.push(REdge{from: bend_node_idx, to: vchannel_offset + vindex});
+            // redges.push(REdge{from: bend_node_idx, to: hchannel_offset + hindex});
         }
     }
     // First create edges along the channels, because they straight forward should be visited first
@@ -438,7 +425,6 @@ pub fn create_routing_graph(boxes: &[ERect]) -> RoutingGraph {
  * The routes are abstract because they have no port and channel slots assigned
  */
 pub fn route_edges(routing_graph: &RoutingGraph, edges: &[Edge], boxes: &[ERect]) -> Vec<AbstractEdgeRoute> {
-    let mut edges_routes: Vec<AbstractEdgeRoute> = Vec::new();
     let mut edges: Vec<(usize,usize)> = edges.into_iter()
         .filter(|edge| edge.from != edge.to)
         .map(|edge| {
@@ -452,130 +438,188 @@ pub fn route_edges(routing_graph: &RoutingGraph, edges: &[Edge], boxes: &[ERect]
         from_a.cmp(from_b).then(to_a.cmp(to_b))
     });
     edges.dedup();
-    let mut targets: Vec<usize> = Vec::new();
-    let mut last_from = usize::MAX;
+    let mut edges_from: Vec<(usize, Vec<usize>)> = Vec::new();
     for (from, to) in edges.iter() {
-        if last_from != *from {
-            if !targets.is_empty() {
-                route_edges_from(routing_graph, last_from, &targets, &mut edges_routes, boxes);
-            }
-            last_from = *from;
-            targets.clear();
+        match edges_from.last_mut() {
+            Some((last_from, targets)) if last_from == from => targets.push(*to),
+            _ => edges_from.push((*from, vec![*to])),
         }
-        targets.push(*to);
     }
-    route_edges_from(routing_graph, last_from, &targets, &mut edges_routes, boxes);
+    // The searches from different source nodes are independent, each thread has own search state
+    let positions = node_positions(routing_graph, boxes);
+    let mut edges_routes: Vec<AbstractEdgeRoute> = edges_from
+        .par_iter()
+        .with_min_len(64)
+        .map_init(
+            || RouteSearch::new(routing_graph, &positions),
+            |search, (from, targets)| {
+                let mut edges_routes: Vec<AbstractEdgeRoute> = Vec::new();
+                search.route_edges_from(routing_graph, *from, targets, &mut edges_routes, boxes);
+                edges_routes
+            },
+        )
+        .flatten()
+        .collect();
     edges_routes.sort_unstable_by(|a,b| a.from.cmp(&b.from).then(a.to.cmp(&b.to)));
     edges_routes
 }
 
-fn route_edges_from(routing_graph: &RoutingGraph, from: usize, to: &[usize], edges_routes: &mut Vec<AbstractEdgeRoute>, boxes: &[ERect]) {
-    // make bfs from from node to all to nodes.
-    let mut visited: Vec<bool> = vec![false; routing_graph.nodes.len()];
-    let mut predecessor: Vec<usize> = vec![usize::MAX; routing_graph.nodes.len()];
-    let mut queue: VecDeque<(usize,Orientation)> = VecDeque::new();
-    visited[from] = true;
-    for &n in routing_graph.nodes[from].neighbors.iter() {
-        let n_route = &routing_graph.nodes[n];
-        match n_route.node_type {
-            RNodeType::Port(_node_id, _channel_id, side) => {
-                let orientation = side.orientation();
-                predecessor[n] = from;
-                queue.push_back((n,orientation));
-            },
-            _ => {
-                panic!("invalid routing graph structure, node should be connected only to ports");
-            }
+/**
+ * State of the breadth first route search shared by the searches of one thread, so big routing graphs
+ * are not allocated for every source node.
+ * The search is first limited to the area around the source and target nodes (spatial pruning),
+ * only if not all targets are reachable inside it the whole graph is searched.
+ */
+struct RouteSearch<'a> {
+    // the node is visited if it has the generation of the current search
+    visited: Vec<u32>,
+    predecessor: Vec<usize>,
+    generation: u32,
+    // approximate position of the routing nodes used for the pruning
+    positions: &'a [Pos2],
+}
+
+// margin of the search area around the source and target nodes
+const ROUTE_SEARCH_MARGIN: f32 = 200.0;
+
+fn node_positions(routing_graph: &RoutingGraph, boxes: &[ERect]) -> Vec<Pos2> {
+    routing_graph
+        .nodes
+        .iter()
+        .map(|node| match node.node_type {
+            RNodeType::Node(node_id) | RNodeType::Port(node_id, _, _) => boxes[node_id].center(),
+            RNodeType::BendPoint(v_idx, h_idx) => Pos2::new(
+                routing_graph.vchannels[v_idx].rect.center().x,
+                routing_graph.hchannels[h_idx].rect.center().y,
+            ),
+        })
+        .collect()
+}
+
+impl<'a> RouteSearch<'a> {
+    fn new(routing_graph: &RoutingGraph, positions: &'a [Pos2]) -> Self {
+        Self {
+            visited: vec![0; routing_graph.nodes.len()],
+            predecessor: vec![usize::MAX; routing_graph.nodes.len()],
+            generation: 0,
+            positions,
+        }
+    }
+
+    fn route_edges_from(&mut self, routing_graph: &RoutingGraph, from: usize, to: &[usize], edges_routes: &mut Vec<AbstractEdgeRoute>, boxes: &[ERect]) {
+        let area = to.iter().fold(boxes[from], |area, target| area.union(boxes[*target]));
+        let area = area.expand2(area.size() * 0.5 + egui::Vec2::splat(ROUTE_SEARCH_MARGIN));
+        let routes_len = edges_routes.len();
+        if self.search(routing_graph, from, to, edges_routes, boxes, Some(area)) {
+            return;
+        }
+        edges_routes.truncate(routes_len);
+        if !self.search(routing_graph, from, to, edges_routes, boxes, None) {
+            panic!("not all routes found");
         }
     }
-    let mut to_find = to.len();
-    // This could be optimized be using A* search with heuristic
-    // The heuristic could be vector distance between current and target node (because we are in 2D space)
-    while let Some((node_idx,orientation)) = queue.pop_front() {
-        visited[node_idx] = true;
-        let node = routing_graph.nodes.get(node_idx).unwrap();
-        match node.node_type {
-            RNodeType::Node(node_index) if node_index != from => {
-                if to.contains(&node_index) {
-                    // found route
-                    let mut route: Vec<usize> = Vec::new();
-                    let mut current = node_idx;
-                    while let Some(&prev) = predecessor.get(current) {
-                        current = prev;
-                        if prev == from {
+
+    fn is_visited(&self, node_idx: usize) -> bool {
+        self.visited[node_idx] == self.generation
+    }
+
+    fn visit(&mut self, node_idx: usize, predecessor: usize) {
+        self.visited[node_idx] = self.generation;
+        self.predecessor[node_idx] = predecessor;
+    }
+
+    /// Returns false if not all targets were found
+    fn search(&mut self, routing_graph: &RoutingGraph, from: usize, to: &[usize], edges_routes: &mut Vec<AbstractEdgeRoute>, boxes: &[ERect], area: Option<ERect>) -> bool {
+        // make bfs from from node to all to nodes.
+        self.generation += 1;
+        let mut queue: VecDeque<(usize,Orientation)> = VecDeque::new();
+        self.visit(from, usize::MAX);
+        for &n in routing_graph.nodes[from].neighbors.iter() {
+            let n_route = &routing_graph.nodes[n];
+            match n_route.node_type {
+                RNodeType::Port(_node_id, _channel_id, side) => {
+                    let orientation = side.orientation();
+                    self.visit(n, from);
+                    queue.push_back((n,orientation));
+                },
+                _ => {
+                    panic!("invalid routing graph structure, node should be connected only to ports");
+                }
+            }
+        }
+        let mut to_find = to.len();
+        // This could be optimized be using A* search with heuristic
+        // The heuristic could be vector distance between current and target node (because we are in 2D space)
+        while let Some((node_idx,orientation)) = queue.pop_front() {
+            let node = routing_graph.nodes.get(node_idx).unwrap();
+            match node.node_type {
+                RNodeType::Node(node_index) if node_index != from => {
+                    if to.contains(&node_index) {
+                        // found route
+                        let mut route: Vec<usize> = Vec::new();
+                        let mut current = node_idx;
+                        while let Some(&prev) = self.predecessor.get(current) {
+                            current = prev;
+                            if prev == from {
+                                break;
+                            }
+                            route.push(current);
+                        }
+                        route.reverse();
+                        remove_no_bend_edges(&mut route, routing_graph);
+                        let bend_directions: Vec<BendDirection> = compute_bend_directions(&route, routing_graph, boxes);
+                        edges_routes.push(AbstractEdgeRoute {
+                            from,
+                            to: node_index,
+                            route,
+                            bend_directions,
+                        });
+                        // If already all routes found exit
+                        to_find -= 1;
+                        if to_find == 0 {
                             break;
                         }
-                        route.push(current);
-                    }
-                    route.reverse();
-                    /*
-                    println!("route from {} to {} before cleanup",from, node_idx);
-                    for r in route.iter() {
-                        let node = &routing_graph.nodes[*r];
-                        println!(" elem {:?}",node.node_type);
-                    }
-                     */
-                    remove_no_bend_edges(&mut route, routing_graph);
-                    /*
-                    println!("route from {} to {}",from, node_idx);
-                    for r in route.iter() {
-                        let node = &routing_graph.nodes[*r];
-                        println!(" elem {:?}",node.node_type);
                     }
-                     */
-
-                    let bend_directions: Vec<BendDirection> = compute_bend_directions(&route, routing_graph, boxes);
-                    edges_routes.push(AbstractEdgeRoute {
-                        from,
-                        to: node_index,
-                        route,
-                        bend_directions,
-                    });
-                    // If already all routes found exit
-                    to_find -= 1;
-                    if to_find == 0 {
-                        break;
-                    }
-                }
-            },
-            _ => {
-                let mut was_skip = false;
-                let current_channel_orientation = node.node_type.get_channel_id(orientation);
-                for target in node.neighbors.iter() {
-                    if !visited[*target] {
-                        let target_node = &routing_graph.nodes[*target];
-                        // First chose the straight channel continuation for bend nodes
-                        let visit_first = match target_node.node_type {
-                            RNodeType::BendPoint(_,_) | RNodeType::Port(_,_,_) => {
-                                let new_channel_orientation = target_node.node_type.get_channel_id(orientation);
-                                current_channel_orientation.channel_idx == new_channel_orientation.channel_idx
-                            },
-                            _ => { true }
-                        };
-                        if visit_first { 
-                            visited[*target] = true;
-                            predecessor[*target] = node_idx;
-                            queue.push_back((*target,orientation));
-                        } else {
-                            was_skip = true;
+                },
+                _ => {
+                    let mut was_skip = false;
+                    let current_channel_orientation = node.node_type.get_channel_id(orientation);
+                    for target in node.neighbors.iter() {
+                        if !self.is_visited(*target) && !self.is_outside(*target, area) {
+                            let target_node = &routing_graph.nodes[*target];
+                            // First chose the straight channel continuation for bend nodes
+                            let visit_first = match target_node.node_type {
+                                RNodeType::BendPoint(_,_) | RNodeType::Port(_,_,_) => {
+                                    let new_channel_orientation = target_node.node_type.get_channel_id(orientation);
+                                    current_channel_orientation.channel_idx == new_channel_orientation.channel_idx
+                                },
+                                _ => { true }
+                            };
+                            if visit_first {
+                                self.visit(*target, node_idx);
+                                queue.push_back((*target,orientation));
+                            } else {
+                                was_skip = true;
+                            }
                         }
                     }
-                }
-                if was_skip {
-                    let orientation = orientation.opposite();
-                    for target in node.neighbors.iter() {
-                        if !visited[*target] {
-                            visited[*target] = true;
-                            predecessor[*target] = node_idx;
-                            queue.push_back((*target,orientation));
+                    if was_skip {
+                        let orientation = orientation.opposite();
+                        for target in node.neighbors.iter() {
+                            if !self.is_visited(*target) && !self.is_outside(*target, area) {
+                                self.visit(*target, node_idx);
+                                queue.push_back((*target,orientation));
+                            }
                         }
                     }
                 }
-            }            
+            }
         }
+        to_find == 0
     }
-    if to_find != 0 {
-        panic!("not all routes found");
+
+    fn is_outside(&self, node_idx: usize, area: Option<ERect>) -> bool {
+        area.is_some_and(|area| !area.contains(self.positions[node_idx]))
     }
 }
 
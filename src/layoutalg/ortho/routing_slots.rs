@@ -170,6 +170,20 @@ impl PartialOrd for ChannelLeg {
 }
 
 impl ChannelLeg {
+    fn connector_range(&self) -> (usize, usize) {
+        if self.start_connector < self.end_connector {
+            (self.start_connector, self.end_connector)
+        } else {
+            (self.end_connector, self.start_connector)
+        }
+    }
+
+    fn overlaps(&self, other: &Self) -> bool {
+        let (self_from, self_to) = self.connector_range();
+        let (other_from, other_to) = other.connector_range();
+        self_from <= other_to && other_from <= self_to
+    }
+
     /**
      * global orders of legs in one channel.
      * It is almost the same order as in local order trait cmp but in exception of BothRightOrBottom legs
@@ -361,6 +375,9 @@ impl RouteIterator<'_> {
     }
 }
 
+// Maximal number of following overlapping legs in channel, the leg gets the route ordering to
+const MAX_ORDERED_LEGS: usize = 32;
+
 // Abstract route gives the nodes in the routing graph that connect one node to another
 // In the concrete route for each edge the lines can not overlap so they need to be assigned to port and channel slots
 // First we need to create all legs pro channel, sort them and assign to slots
@@ -620,9 +637,19 @@ pub fn calculate_edge_routes(routing_graph: &RoutingGraph,
         // So the routes do not cross if possible
         if channel_legs.len() > 1 {
             for i in 0..channel_legs.len()-1 {
+                let mut ordered_legs = 0;
                 for j in i+1..channel_legs.len() {
                     let ri = &channel_legs[i];
                     let rj = &channel_legs[j];
+                    // Only legs sharing part of the channel compete for the slots
+                    if ri.edge_idx == rj.edge_idx || !ri.overlaps(rj) {
+                        continue;
+                    }
+                    // In crowded channels the order to more distant legs is mostly given transitively
+                    ordered_legs += 1;
+                    if ordered_legs > MAX_ORDERED_LEGS {
+                        break;
+                    }
                     let mut is_order_ij: bool = match ri.leg_relative_order(&rj) {
                         std::cmp::Ordering::Less => true,
                         std::cmp::Ordering::Greater => false,
@@ -1,6 +1,6 @@
 // Adapt position of nodes and channels to minimal channels with
 
-use std::collections::{HashMap, VecDeque};
+use std::{cmp::Reverse, collections::{BinaryHeap, HashMap}};
 
 use egui::{Rect as ERect};
 
@@ -9,11 +9,12 @@ use crate::layoutalg::ortho::channels::ChannelPortType;
 use super::routing::{RoutingGraph, NodePort, Side, RNodeType};
 use super::channels::{RChannel};
 
-#[derive(Debug)]
-enum PosMove {
-    MoveBox(f32, usize),
-    AddChannelWidth(f32, usize),
-    MoveChannel(f32, usize),
+// Item that can be moved by resizing, the moves are processed ordered by the original position
+// so each box and channel is moved once after all moves from left (or top) are known
+#[derive(Debug, Clone, Copy)]
+enum PosItem {
+    Channel(usize),
+    Box(usize),
 }
 
 struct DirectionX;
@@ -160,13 +161,34 @@ pub fn resize_channels(routing_graph: &mut RoutingGraph, nodes: &mut [ERect], mi
 }
 
 fn resize_direction<D: Direction>(routing_graph: &mut RoutingGraph, nodes: &mut [ERect], min_with: &[f32]) {
-    let mut pos_moves: VecDeque<PosMove> = VecDeque::new();
+    let channels_len = D::get_channels(routing_graph).len();
+    // Ordering of channels and boxes by position, channel is before box on the same position
+    let mut items: Vec<(f32, PosItem)> = D::get_channels(routing_graph).iter().enumerate()
+        .map(|(channel_id, channel)| (D::get_rect_min(&channel.rect), PosItem::Channel(channel_id)))
+        .chain(nodes.iter().enumerate().map(|(node_id, rect)| (D::get_rect_min(rect), PosItem::Box(node_id))))
+        .collect();
+    items.sort_by(|(a_pos, a_item), (b_pos, b_item)| a_pos.total_cmp(b_pos)
+        .then(matches!(a_item, PosItem::Box(_)).cmp(&matches!(b_item, PosItem::Box(_)))));
+    let mut channel_rank: Vec<usize> = vec![0; channels_len];
+    let mut box_rank: Vec<usize> = vec![0; nodes.len()];
+    for (rank, (_pos, item)) in items.iter().enumerate() {
+        match item {
+            PosItem::Channel(channel_id) => channel_rank[*channel_id] = rank,
+            PosItem::Box(node_id) => box_rank[*node_id] = rank,
+        }
+    }
+    let mut channel_grow: Vec<f32> = vec![0.0; channels_len];
+    let mut channel_new_min: Vec<f32> = vec![f32::NEG_INFINITY; channels_len];
+    let mut box_new_min: Vec<f32> = vec![f32::NEG_INFINITY; nodes.len()];
+    let mut queued: Vec<bool> = vec![false; items.len()];
+    let mut pos_moves: BinaryHeap<Reverse<usize>> = BinaryHeap::new();
     for (channel_idx,min_with) in min_with.iter().enumerate() {
         let channel = D::get_channel_by_id(routing_graph, channel_idx);
         let delta = min_with - channel.width();
         if delta>0.0 {
-            let pos_move = PosMove::AddChannelWidth(delta, channel_idx);
-            pos_moves.push_back(pos_move);
+            channel_grow[channel_idx] = delta;
+            queued[channel_rank[channel_idx]] = true;
+            pos_moves.push(Reverse(channel_rank[channel_idx]));
         }
     }
     // All right channels for all nodes (right ports)
@@ -193,12 +215,12 @@ fn resize_direction<D: Direction>(routing_graph: &mut RoutingGraph, nodes: &mut
             orth_margin_channels.entry(*channel_id).or_default().push(*orth_channel_id);
         }       
     }
-    while let Some(pos_move) = pos_moves.pop_front() {
-        // println!("apply pos_move {:?}", pos_move);
-        match pos_move {
-            PosMove::MoveBox(new_min, node_idx) => {
+    while let Some(Reverse(rank)) = pos_moves.pop() {
+        queued[rank] = false;
+        match items[rank].1 {
+            PosItem::Box(node_idx) => {
                 let mut rect = &mut nodes[node_idx];
-                let delta = new_min - D::get_rect_min(&rect);
+                let delta = box_new_min[node_idx] - D::get_rect_min(&rect);
                 if delta>0.0 {
                     let new_max_pos = D::move_rect(&mut rect, delta);
                     let right_channel_id = right_channels[node_idx];
@@ -206,38 +228,45 @@ fn resize_direction<D: Direction>(routing_graph: &mut RoutingGraph, nodes: &mut
                         let rect = &D::get_channel_by_id(routing_graph, right_channel_id).rect;
                         let delta = new_max_pos - D::get_rect_min(&rect);
                         if delta > 0.0 {
-                            pos_moves.push_front(PosMove::MoveChannel(new_max_pos, right_channel_id));
+                            channel_new_min[right_channel_id] = channel_new_min[right_channel_id].max(new_max_pos);
+                            let right_rank = channel_rank[right_channel_id];
+                            if !queued[right_rank] {
+                                queued[right_rank] = true;
+                                pos_moves.push(Reverse(right_rank));
+                            }
                         }
                     }
                 }
             },
-            PosMove::MoveChannel(new_min, channel_idx) => {
+            PosItem::Channel(channel_idx) => {
                 let channel = D::get_channel_by_id(routing_graph, channel_idx);
-                let delta = new_min - D::get_rect_min(&channel.rect);
+                let delta = channel_new_min[channel_idx] - D::get_rect_min(&channel.rect);
+                let mut changed = false;
                 if delta>0.0 {
-                    let new_max_pos = D::move_rect(&mut channel.rect, delta);
+                    D::move_rect(&mut channel.rect, delta);
+                    changed = true;
+                }
+                if channel_grow[channel_idx] > 0.0 {
+                    D::add_max(&mut channel.rect, channel_grow[channel_idx]);
+                    channel_grow[channel_idx] = 0.0;
+                    changed = true;
+                }
+                if changed {
+                    let new_max_pos = D::get_rect_max(&channel.rect);
                     for port in D::max_side_ports(&channel) {
                         let rect = &nodes[port.node_id];
                         let delta = new_max_pos - D::get_rect_min(&rect);
                         if delta>0.0 {
-                            pos_moves.push_front(PosMove::MoveBox(new_max_pos, port.node_id));
+                            box_new_min[port.node_id] = box_new_min[port.node_id].max(new_max_pos);
+                            let port_rank = box_rank[port.node_id];
+                            if !queued[port_rank] {
+                                queued[port_rank] = true;
+                                pos_moves.push(Reverse(port_rank));
+                            }
                         }
                     }
                 }
             },
-            PosMove::AddChannelWidth(delta, channel_idx) => {
-                let channel = D::get_channel_by_id(routing_graph, channel_idx);
-                // println!("resize channel {} by {}",channel_idx, delta);
-                let new_max_pos = D::add_max(&mut channel.rect, delta);
-                for port in D::max_side_ports(&channel) {
-                    let rect = &nodes[port.node_id];
-                    let delta = new_max_pos - D::get_rect_min(&rect);
-                    // println!("max port {:?} min-port {} new_max_port {}",port,D::get_rect_min(&rect),new_max_pos);
-                    if delta>0.0 {
-                        pos_moves.push_front(PosMove::MoveBox(new_max_pos, port.node_id));
-                    }
-                }
-            },
         }
     }
     for (channel_id, margin_channels) in orth_margin_channels.iter() {
@@ -334,4 +363,4 @@ mod tests {
 
         Ok(())
     }
-}
\ No newline at end of file
+}
\ No newline at end of file
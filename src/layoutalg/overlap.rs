@@ -355,7 +355,8 @@ fn check_node_overlap(rects: &[Rect]) -> bool {
 // ---------- Main function with same signature as your circular_layout ----------
 pub fn nachmanson_layout(visible_nodes: &mut SortedNodeLayout, selected_nodes: &BTreeSet<IriIndex>) {
     // 1) collect node_indexes (same pattern as your circular layout)
-    let node_indexes: Vec<usize> = if let Ok(nodes) = visible_nodes.nodes.read() {
+    let node_indexes: Vec<usize> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let nodes = &layout.nodes;
         if selected_nodes.is_empty() {
             (0..nodes.len()).collect()
         } else {
@@ -375,19 +376,15 @@ pub fn nachmanson_layout(visible_nodes: &mut SortedNodeLayout, selected_nodes: &
     let gap = Vec2::new(12.0, 12.0); // optional gap between nodes after alignment
 
     // 3) node rects, positions +size
-    let rects: Vec<Rect> = if let Ok(node_shapes) = visible_nodes.node_shapes.read() {
-        if let Ok(positions) = visible_nodes.positions.read() {
-            node_indexes
-                .iter()
-                .map(|&idx| {
-                    let pos = positions[idx].pos;
-                    let size = node_shapes[idx].size + gap;
-                    Rect::from_center_size(pos, size)
-                })
-                .collect()
-        } else {
-            return;
-        }
+    let rects: Vec<Rect> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        node_indexes
+            .iter()
+            .map(|&idx| {
+                let pos = layout.positions[idx].pos;
+                let size = layout.node_shapes[idx].size + gap;
+                Rect::from_center_size(pos, size)
+            })
+            .collect()
     } else {
         return;
     };
@@ -405,9 +402,10 @@ pub fn nachmanson_layout(visible_nodes: &mut SortedNodeLayout, selected_nodes: &
     // Write back aligned centers into visible_nodes.positions
     // NOTE: You need to map aligned rects back to the corresponding positions.
     // We assumed node_indexes[i] corresponds to aligned[i]
-    if let Ok(mut positions) = visible_nodes.positions.write() {
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let positions = &mut layout.positions;
         for (i, &node_idx) in node_indexes.iter().enumerate() {
-            positions[node_idx].pos = aligned[i].center();
+            positions[node_idx].move_to(aligned[i].center());
         }
     } else {
         // could not acquire write lock; bail out
@@ -511,7 +509,8 @@ mod tests {
         });
         let selected: BTreeSet<IriIndex> = vec![0, 1, 2].into_iter().collect();
         nachmanson_layout(&mut nl, &selected);
-        let positions = nl.positions.read().unwrap();
+        let layout = nl.layout_data.read().unwrap();
+        let positions = &layout.positions;
         for (idx, pos) in positions.iter().enumerate() {
             println!("{} : {:?}", idx, pos.pos);
         }
@@ -544,7 +543,8 @@ mod tests {
         let len32: IriIndex = len.unwrap() as IriIndex;
         let selected: BTreeSet<IriIndex> = (0..len32).collect();
         nachmanson_layout(&mut nl, &selected);
-        let positions = nl.positions.read().unwrap();
+        let layout = nl.layout_data.read().unwrap();
+        let positions = &layout.positions;
         for (idx, pos) in positions.iter().enumerate() {
             println!("{} : {:?}", idx, pos.pos);
         }
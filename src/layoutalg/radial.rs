@@ -0,0 +1,130 @@
+use std::{
+    collections::{BTreeSet, VecDeque},
+    f32::consts::PI,
+};
+
+use egui::Vec2;
+
+use crate::{IriIndex, support::SortedVec, uistate::layout::SortedNodeLayout};
+
+// minimal distance between two rings
+const RING_DISTANCE: f32 = 150.0;
+const NODE_SPACING: f32 = 20.0;
+
+/**
+ * Ego network layout: the nodes are placed in concentric rings by hop distance (over visible edges
+ * in both directions) from the center node. The center is the selected node or the first of the selected nodes.
+ * Nodes not connected to the center are placed on the outermost ring.
+ */
+pub fn radial_layout(
+    visible_nodes: &mut SortedNodeLayout,
+    selected_node: Option<IriIndex>,
+    selected_nodes: &BTreeSet<IriIndex>,
+    hidden_predicates: &SortedVec,
+) {
+    let Some(center_index) = selected_node.or_else(|| selected_nodes.first().copied()) else {
+        return;
+    };
+    let Some(center) = visible_nodes.get_pos(center_index) else {
+        return;
+    };
+    let (edges, node_sizes): (Vec<(usize, usize)>, Vec<f32>) = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = layout
+            .edges
+            .iter()
+            .filter(|e| e.from != e.to && !hidden_predicates.contains(e.predicate))
+            .map(|e| (e.from, e.to))
+            .collect();
+        let node_sizes = (0..layout.nodes.len())
+            .map(|idx| layout.node_shapes.get(idx).map_or(0.0, |node_shape| node_shape.size.max_elem()))
+            .collect();
+        (edges, node_sizes)
+    } else {
+        return;
+    };
+    let offsets = radial_positions(center, &node_sizes, &edges);
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let positions = &mut layout.positions;
+        let center_pos = positions[center].pos;
+        for (idx, offset) in offsets.iter().enumerate() {
+            if idx != center {
+                positions[idx].move_to(center_pos + *offset);
+            }
+        }
+    }
+}
+
+/// Positions of the nodes relative to the center node
+fn radial_positions(center: usize, node_sizes: &[f32], edges: &[(usize, usize)]) -> Vec<Vec2> {
+    let node_count = node_sizes.len();
+    let mut neighbors: Vec<Vec<usize>> = vec![Vec::new(); node_count];
+    for (from, to) in edges.iter() {
+        neighbors[*from].push(*to);
+        neighbors[*to].push(*from);
+    }
+    // breadth first search, parent is used to keep the children near to their parent on the ring
+    let mut ring: Vec<Option<usize>> = vec![None; node_count];
+    let mut parent: Vec<usize> = (0..node_count).collect();
+    let mut queue: VecDeque<usize> = VecDeque::new();
+    ring[center] = Some(0);
+    queue.push_back(center);
+    while let Some(node) = queue.pop_front() {
+        let next_ring = ring[node].unwrap_or_default() + 1;
+        for neighbor in neighbors[node].iter() {
+            if ring[*neighbor].is_none() {
+                ring[*neighbor] = Some(next_ring);
+                parent[*neighbor] = node;
+                queue.push_back(*neighbor);
+            }
+        }
+    }
+    let max_ring = ring.iter().flatten().max().copied().unwrap_or_default();
+    let unreachable_ring = max_ring + 1;
+    let mut rings: Vec<Vec<usize>> = vec![Vec::new(); unreachable_ring + 1];
+    for (idx, node_ring) in ring.iter().enumerate() {
+        rings[node_ring.unwrap_or(unreachable_ring)].push(idx);
+    }
+    let mut angles: Vec<f32> = vec![0.0; node_count];
+    let mut offsets: Vec<Vec2> = vec![Vec2::ZERO; node_count];
+    let mut radius: f32 = 0.0;
+    for ring_nodes in rings.iter_mut().skip(1) {
+        if ring_nodes.is_empty() {
+            continue;
+        }
+        // BFS order is already sorted by parent, the unreachable nodes have no parent so keep their order
+        ring_nodes.sort_by(|a, b| angles[parent[*a]].total_cmp(&angles[parent[*b]]).then(a.cmp(b)));
+        let circumference: f32 = ring_nodes.iter().map(|idx| node_sizes[*idx] + NODE_SPACING).sum();
+        radius = (radius + RING_DISTANCE).max(circumference / (2.0 * PI));
+        for (pos, idx) in ring_nodes.iter().enumerate() {
+            // 0 is at the top of the circle
+            let angle = 2.0 * PI * (pos as f32) / (ring_nodes.len() as f32) - PI / 2.0;
+            angles[*idx] = angle;
+            offsets[*idx] = Vec2::new(radius * angle.cos(), radius * angle.sin());
+        }
+    }
+    offsets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_radial_positions() {
+        // 0 is center, 1 and 2 are neighbors, 3 is neighbor of 2, 4 is not connected
+        let edges = [(0, 1), (2, 0), (2, 3)];
+        let offsets = radial_positions(0, &[10.0; 5], &edges);
+        assert_eq!(Vec2::ZERO, offsets[0]);
+        let distance = |idx: usize| offsets[idx].length();
+        assert!((distance(1) - RING_DISTANCE).abs() < 0.01);
+        assert!((distance(2) - RING_DISTANCE).abs() < 0.01);
+        assert!((distance(3) - 2.0 * RING_DISTANCE).abs() < 0.01);
+        assert!((distance(4) - 3.0 * RING_DISTANCE).abs() < 0.01);
+
+        // many nodes in one ring need bigger radius
+        let edges: Vec<(usize, usize)> = (1..101).map(|idx| (0, idx)).collect();
+        let offsets = radial_positions(0, &[30.0; 101], &edges);
+        assert!(offsets[1].length() > RING_DISTANCE);
+        assert!((offsets[1] - offsets[2]).length() >= 49.0);
+    }
+}
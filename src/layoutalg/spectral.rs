@@ -13,7 +13,8 @@ pub fn spectral_layout(
     selected_nodes: &BTreeSet<IriIndex>,
     hidden_predicates: &SortedVec,
 ) {
-    let node_indexes: Vec<usize> = if let Ok(nodes) = visible_nodes.nodes.read() {
+    let node_indexes: Vec<usize> = if let Ok(layout) = visible_nodes.layout_data.read() {
+        let nodes = &layout.nodes;
         if selected_nodes.is_empty() {
             (0..nodes.len()).collect()
         } else {
@@ -30,7 +31,8 @@ pub fn spectral_layout(
         return;
     }
     let mut adj = DMatrix::<f64>::zeros(n, n);
-    if let Ok(edges) = visible_nodes.edges.read() {
+    if let Ok(layout) = visible_nodes.layout_data.read() {
+        let edges = &layout.edges;
         for edge in edges.iter().filter(|e| !hidden_predicates.contains(e.predicate)) {
             if let (Some(i), Some(j)) = (
                 node_indexes.iter().position(|&idx| idx == edge.from),
@@ -50,11 +52,12 @@ pub fn spectral_layout(
     };
     let coords = rescale_layout(coords, 1.0);
     let scale = 800.0;
-    if let Ok(mut positions) = visible_nodes.positions.write() {
+    if let Ok(mut layout) = visible_nodes.layout_data.write() {
+        let positions = &mut layout.positions;
         for (i, &node_idx) in node_indexes.iter().enumerate() {
             let x = coords[(i, 0)] * scale;
             let y = coords[(i, 1)] * scale;
-            positions[node_idx].pos = Pos2::new(x as f32, y as f32);
+            positions[node_idx].move_to(Pos2::new(x as f32, y as f32));
         }
     }
 }
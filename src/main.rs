@@ -5,19 +5,59 @@ use rdf_glance::RdfGlanceApp;
 
 #[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<(), eframe::Error> {
-    use rdf_glance::support::uitools::load_icon;
+    use rdf_glance::{
+        uistate::app::SAFE_MODE_ARG,
+        integration::{
+            deep_link::{DeepLink, native::forward_to_running_instance},
+            stress_data::StressDataConfig,
+        },
+        support::uitools::load_icon,
+    };
 
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let safe_mode = args.iter().any(|arg| arg == SAFE_MODE_ARG);
     let options = eframe::NativeOptions {
         viewport: ViewportBuilder::default()
             .with_icon(load_icon()),
+        // the persisted window size could be broken too
+        persist_window: !safe_mode,
         ..eframe::NativeOptions::default()
     };
-    let args = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--generate-stress-data") {
+        // rdf-glance --generate-stress-data out.ttl nodes=100000 types=20 degree=3 distribution=power
+        let Some(file_name) = args.get(1) else {
+            eprintln!("Usage: rdf-glance --generate-stress-data <file.ttl> [nodes=N] [types=N] [predicates=N] [degree=N] [distribution=power|uniform] [literals=N] [literal_size=N] [seed=N]");
+            std::process::exit(1);
+        };
+        let result = StressDataConfig::parse_args(&args[2..]).and_then(|config| {
+            let mut writer = std::io::BufWriter::new(std::fs::File::create(file_name)?);
+            config.write_turtle(&mut writer)
+        });
+        match result {
+            Ok(triples) => println!("Generated {} triples into {}", triples, file_name),
+            Err(e) => {
+                eprintln!("Can not generate stress data: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    if let Some(first_arg) = args.first()
+        && DeepLink::is_deep_link(first_arg)
+        && forward_to_running_instance(first_arg)
+    {
+        return Ok(());
+    }
     eframe::run_native(
         "rdf-glance",
         options,
-        Box::new(|cc| 
-            Ok(Box::new(RdfGlanceApp::new(cc.storage, args)))),
+        Box::new(move |cc| {
+            if safe_mode {
+                // egui memory (window and panel state) is already restored from storage
+                cc.egui_ctx.memory_mut(|memory| *memory = egui::Memory::default());
+            }
+            Ok(Box::new(RdfGlanceApp::new(cc.storage, args)))
+        }),
     )
 }
 
@@ -6,6 +6,17 @@ pub fn next_distinct_color(taken_colors: usize, saturation: f32, lightness: f32,
     Color32::from_rgba_premultiplied(r, g, b, alpha)
 }
 
+/// Color of a member of a hue family, the members differ in lightness and after all shades are used slightly in hue
+pub fn family_color(family: usize, member: usize, saturation: f32, lightness: f32, alpha: u8) -> Color32 {
+    let hue = (family as f32 / PHI) * 360.0 + (member / FAMILY_SHADES.len()) as f32 * 12.0;
+    let lightness = (lightness + FAMILY_SHADES[member % FAMILY_SHADES.len()]).clamp(0.15, 0.85);
+    let (r, g, b) = hsl_to_rgb(hue % 360.0, saturation, lightness);
+    Color32::from_rgba_premultiplied(r, g, b, alpha)
+}
+
+// Lightness offsets of the members of a hue family
+const FAMILY_SHADES: [f32; 5] = [0.0, 0.12, -0.12, 0.24, -0.24];
+
 /// Convert HSL to RGB (values 0-255)
 fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
     let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
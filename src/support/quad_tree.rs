@@ -180,6 +180,93 @@ impl BHQuadtree {
     }
 }
 
+// deeper nodes do not pay off for the rectangles spanning the quarters
+const RECT_TREE_MAX_DEPTH: usize = 12;
+
+#[derive(Debug)]
+struct RectNode {
+    bound: Rect,
+    // index of the first of 4 children, 0 for leaf
+    children: usize,
+    // rectangles that do not fit into one quarter stay in the node
+    items: Vec<usize>,
+}
+
+/// Static quadtree of rectangles for fast queries of the rectangles that intersect an area.
+/// Used by the orthogonal edge routing to find the crossing channels without testing all pairs.
+#[derive(Debug)]
+pub struct RectQuadtree {
+    nodes: Vec<RectNode>,
+    rects: Vec<Rect>,
+}
+
+impl RectQuadtree {
+    pub fn new(rects: Vec<Rect>, node_capacity: usize) -> Self {
+        let bound = rects.iter().fold(Rect::NOTHING, |acc, rect| acc.union(*rect));
+        let mut tree = Self {
+            nodes: vec![RectNode {
+                bound,
+                children: 0,
+                items: (0..rects.len()).collect(),
+            }],
+            rects,
+        };
+        let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+        while let Some((n, depth)) = stack.pop() {
+            if tree.nodes[n].items.len() <= node_capacity || depth >= RECT_TREE_MAX_DEPTH {
+                continue;
+            }
+            let bounds = quarter(&tree.nodes[n].bound);
+            let mut quarter_items: [Vec<usize>; 4] = Default::default();
+            let mut rest: Vec<usize> = Vec::new();
+            for item in tree.nodes[n].items.iter() {
+                let rect = tree.rects[*item];
+                match bounds.iter().position(|bound| bound.contains_rect(rect)) {
+                    Some(q) => quarter_items[q].push(*item),
+                    None => rest.push(*item),
+                }
+            }
+            if rest.len() == tree.nodes[n].items.len() {
+                continue;
+            }
+            let c = tree.nodes.len();
+            tree.nodes[n].children = c;
+            tree.nodes[n].items = rest;
+            for (i, items) in quarter_items.into_iter().enumerate() {
+                tree.nodes.push(RectNode {
+                    bound: bounds[i],
+                    children: 0,
+                    items,
+                });
+                stack.push((c + i, depth + 1));
+            }
+        }
+        tree
+    }
+
+    /// Indexes of the rectangles intersecting the area in ascending order
+    pub fn query(&self, area: Rect) -> Vec<usize> {
+        let mut found: Vec<usize> = Vec::new();
+        if self.rects.is_empty() {
+            return found;
+        }
+        let mut stack: Vec<usize> = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            found.extend(node.items.iter().filter(|item| self.rects[**item].intersects(area)));
+            if node.children != 0 {
+                for c in node.children..node.children + 4 {
+                    if self.nodes[c].bound.intersects(area) {
+                        stack.push(c);
+                    }
+                }
+            }
+        }
+        found.sort_unstable();
+        found
+    }
+}
+
 pub(crate) trait Partition<T> {
     fn partition<F: Fn(&T) -> bool>(&mut self, predicate: F) -> usize;
 }
@@ -286,6 +373,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rect_quad_tree() {
+        use super::*;
+        let mut rects: Vec<Rect> = (0..20)
+            .flat_map(|x| {
+                (0..20).map(move |y| Rect::from_min_size(Pos2::new(x as f32 * 10.0, y as f32 * 10.0), Vec2::new(5.0, 5.0)))
+            })
+            .collect();
+        // long rectangle over all quarters
+        rects.push(Rect::from_min_max(Pos2::new(0.0, 52.0), Pos2::new(200.0, 53.0)));
+        let tree = RectQuadtree::new(rects.clone(), 4);
+        for area in [
+            Rect::from_min_max(Pos2::new(12.0, 12.0), Pos2::new(33.0, 55.0)),
+            Rect::from_min_max(Pos2::new(150.0, 0.0), Pos2::new(151.0, 300.0)),
+            Rect::from_min_max(Pos2::new(-10.0, -10.0), Pos2::new(-5.0, -5.0)),
+        ] {
+            let expected: Vec<usize> = (0..rects.len()).filter(|i| rects[*i].intersects(area)).collect();
+            assert_eq!(expected, tree.query(area));
+        }
+    }
+
     #[test]
     fn test_quad_tree_problem() {
         use super::*;
@@ -0,0 +1,302 @@
+use std::collections::BTreeSet;
+
+use oxrdf::{Literal, NamedNode, Triple, vocab::{rdf, rdfs}};
+
+use crate::{
+    IriIndex, RdfGlanceApp, SystemMessage,
+    domain::{
+        LabelContext, RdfData,
+        change_set::{iri_node, mint_iri, reference_triple},
+        data_change::DataChange,
+    },
+    integration::rdf_patch::PatchOperation,
+    uistate::{NewLink, NewNode, layout::node_pos},
+};
+
+enum AuthoringAction {
+    None,
+    CreateNode,
+    CreateLink,
+    Cancel,
+}
+
+impl RdfGlanceApp {
+    /// Dialogs for the new node and the new reference of the authoring mode in the graph view
+    pub fn show_authoring_dialogs(&mut self, ctx: &egui::Context) {
+        self.show_new_node_dialog(ctx);
+        self.show_new_link_dialog(ctx);
+    }
+
+    fn show_new_node_dialog(&mut self, ctx: &egui::Context) {
+        let Some(new_node) = &mut self.ui_state.new_node else {
+            return;
+        };
+        let mut action = AuthoringAction::None;
+        egui::Window::new("New Node")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    let node_data = &rdf_data.node_data;
+                    egui::Grid::new("new_node").num_columns(3).show(ui, |ui| {
+                        ui.label("Type");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut new_node.type_iri)
+                                .hint_text("prefix:name or iri")
+                                .desired_width(250.0),
+                        );
+                        egui::ComboBox::from_id_salt("new_node_type")
+                            .selected_text("Known types")
+                            .width(200.0)
+                            .show_ui(ui, |ui| {
+                                for type_index in self.type_index.types_order.iter() {
+                                    let label = node_data.type_display(*type_index, &label_context, &node_data.indexers);
+                                    if ui.selectable_label(false, label.as_str()).clicked()
+                                        && let Some(type_iri) = node_data.indexers.type_indexer.index_to_str(*type_index)
+                                    {
+                                        new_node.type_iri = type_iri.to_string();
+                                    }
+                                }
+                            });
+                        ui.end_row();
+                        ui.label("IRI");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut new_node.iri)
+                                .hint_text(format!("empty - new iri in {}", self.config.new_node_namespace))
+                                .desired_width(250.0),
+                        );
+                        ui.end_row();
+                        ui.label("Label");
+                        ui.add(egui::TextEdit::singleline(&mut new_node.label).desired_width(250.0));
+                        ui.end_row();
+                    });
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !new_node.type_iri.trim().is_empty() || !new_node.label.trim().is_empty(),
+                            egui::Button::new("Create"),
+                        )
+                        .clicked()
+                    {
+                        action = AuthoringAction::CreateNode;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        action = AuthoringAction::Cancel;
+                    }
+                });
+            });
+        match action {
+            AuthoringAction::CreateNode if self.create_new_node() => {
+                self.ui_state.new_node = None;
+            }
+            AuthoringAction::Cancel => {
+                self.ui_state.new_node = None;
+            }
+            _ => {}
+        }
+    }
+
+    fn show_new_link_dialog(&mut self, ctx: &egui::Context) {
+        let Some(new_link) = &self.ui_state.new_link else {
+            return;
+        };
+        let mut action = AuthoringAction::None;
+        let link_predicate = &mut self.ui_state.link_predicate;
+        egui::Window::new("New Reference")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    let node_data = &rdf_data.node_data;
+                    let node_label = |node_index: IriIndex| {
+                        node_data
+                            .get_node_by_index(node_index)
+                            .map(|(iri, _)| iri.to_string())
+                            .unwrap_or_default()
+                    };
+                    ui.label(format!("{} \u{2192} {}", node_label(new_link.from), node_label(new_link.to)));
+                    let reference_predicates: BTreeSet<IriIndex> = self
+                        .type_index
+                        .types
+                        .values()
+                        .flat_map(|type_data| type_data.references.keys().cloned())
+                        .collect();
+                    ui.horizontal(|ui| {
+                        ui.label("Predicate");
+                        ui.add(
+                            egui::TextEdit::singleline(link_predicate)
+                                .hint_text("prefix:name or iri")
+                                .desired_width(250.0),
+                        );
+                        egui::ComboBox::from_id_salt("new_link_predicate")
+                            .selected_text("Known predicates")
+                            .width(200.0)
+                            .show_ui(ui, |ui| {
+                                for predicate_index in reference_predicates {
+                                    let label =
+                                        node_data.predicate_display(predicate_index, &label_context, &node_data.indexers);
+                                    if ui.selectable_label(false, label.as_str()).clicked()
+                                        && let Some(predicate_iri) =
+                                            node_data.indexers.predicate_indexer.index_to_str(predicate_index)
+                                    {
+                                        *link_predicate = predicate_iri.to_string();
+                                    }
+                                }
+                            });
+                    });
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!link_predicate.trim().is_empty(), egui::Button::new("Create"))
+                        .clicked()
+                    {
+                        action = AuthoringAction::CreateLink;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        action = AuthoringAction::Cancel;
+                    }
+                });
+            });
+        match action {
+            AuthoringAction::CreateLink if self.create_new_link() => {
+                self.ui_state.new_link = None;
+            }
+            AuthoringAction::Cancel => {
+                self.ui_state.new_link = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds the triples of the new node to the data and the node to the visual graph, false on error
+    fn create_new_node(&mut self) -> bool {
+        let Some(new_node) = &self.ui_state.new_node else {
+            return false;
+        };
+        let pos = new_node.pos;
+        let new_triples = match self.rdf_data.read() {
+            Ok(rdf_data) => new_node_triples(new_node, &self.config.new_node_namespace, &rdf_data),
+            Err(_) => return false,
+        };
+        let (subject, operations) = match new_triples {
+            Ok(new_triples) => new_triples,
+            Err(error) => {
+                self.system_message = SystemMessage::Error(error);
+                return false;
+            }
+        };
+        self.edit_data_triples("New Node", operations);
+        let node_index = self
+            .read_rdf_data(|rdf_data| {
+                rdf_data
+                    .node_data
+                    .get_node_index(&rdf_data.prefix_manager.get_prefixed(subject.as_str()))
+            })
+            .flatten();
+        self.notify_data_change(DataChange::TriplesMerged);
+        if let Some(node_index) = node_index {
+            self.visible_nodes.add_by_index(node_index);
+            if let Ok(mut layout) = self.visible_nodes.layout_data.write()
+                && let Some(node_pos) = node_pos(&layout.nodes, node_index)
+            {
+                layout.positions[node_pos].pos = pos;
+            }
+            self.visible_nodes.update_node_shapes = true;
+            self.ui_state.selected_node = Some(node_index);
+            self.ui_state.selected_nodes.clear();
+            self.ui_state.selected_nodes.insert(node_index);
+        }
+        true
+    }
+
+    /// Adds the reference between the nodes of the new link dialog, false on error
+    fn create_new_link(&mut self) -> bool {
+        let Some(NewLink { from, to }) = self.ui_state.new_link else {
+            return false;
+        };
+        let predicate = self.ui_state.link_predicate.clone();
+        let triple = self
+            .read_rdf_data(|rdf_data| {
+                let predicate = iri_node(&predicate, &rdf_data.prefix_manager)?;
+                reference_triple(&rdf_data.node_data, &rdf_data.prefix_manager, from, predicate, to)
+            })
+            .flatten();
+        let Some(triple) = triple else {
+            self.system_message = SystemMessage::Error(format!(
+                "Predicate {} is not a valid iri or uses an unknown prefix",
+                predicate
+            ));
+            return false;
+        };
+        let result = self.edit_data_triples("New Reference", vec![PatchOperation::Add(triple)]);
+        if result.is_some_and(|result| result.duplicates > 0) {
+            self.set_status_message("The reference exists already");
+        }
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            self.visible_nodes.refresh_edges(&rdf_data.node_data, &self.ui_state.hidden_predicates);
+        }
+        self.notify_data_change(DataChange::TriplesMerged);
+        true
+    }
+}
+
+/// Iri and triples (type and label) of the new node, the iri is minted in the namespace if not given
+fn new_node_triples(
+    new_node: &NewNode,
+    namespace: &str,
+    rdf_data: &RdfData,
+) -> Result<(NamedNode, Vec<PatchOperation>), String> {
+    let prefix_manager = &rdf_data.prefix_manager;
+    let node_type = if new_node.type_iri.trim().is_empty() {
+        None
+    } else {
+        let node_type = iri_node(&new_node.type_iri, prefix_manager).ok_or_else(|| {
+            format!("Type {} is not a valid iri or uses an unknown prefix", new_node.type_iri)
+        })?;
+        Some(node_type)
+    };
+    let subject = if new_node.iri.trim().is_empty() {
+        let name = node_type
+            .as_ref()
+            .and_then(|node_type| node_type.as_str().rsplit(['/', '#', ':']).next())
+            .unwrap_or("node");
+        NamedNode::new(mint_iri(namespace, name, &rdf_data.node_data, prefix_manager))
+            .map_err(|e| format!("Can not create iri in namespace {}: {}", namespace, e))?
+    } else {
+        let subject = iri_node(&new_node.iri, prefix_manager)
+            .ok_or_else(|| format!("{} is not a valid iri or uses an unknown prefix", new_node.iri))?;
+        if rdf_data
+            .node_data
+            .get_node_index(&prefix_manager.get_prefixed(subject.as_str()))
+            .is_some()
+        {
+            return Err(format!("Node {} exists already", subject.as_str()));
+        }
+        subject
+    };
+    let mut operations = Vec::new();
+    if let Some(node_type) = node_type {
+        operations.push(PatchOperation::Add(Triple::new(subject.clone(), rdf::TYPE, node_type)));
+    }
+    if !new_node.label.trim().is_empty() {
+        operations.push(PatchOperation::Add(Triple::new(
+            subject.clone(),
+            rdfs::LABEL,
+            Literal::new_simple_literal(new_node.label.trim()),
+        )));
+    }
+    Ok((subject, operations))
+}
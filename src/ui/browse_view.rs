@@ -1,5 +1,5 @@
 use const_format::concatcp;
-use egui::Key;
+use egui::{Key, Response, UiKind};
 use egui_extras::{Column, StripBuilder, TableBuilder};
 
 use crate::{
@@ -8,13 +8,27 @@ use crate::{
     uistate::UIState, 
     domain::{
         LabelContext, Literal, NObject, NodeData,
+        blank_node_tree::{BlankNodeTree, ListItem, blank_node_trees},
         graph_styles::GVisualizationStyle,
+        rdf_list::{ListMember, RdfList, node_lists},
+        identifier_resolution::resolve_identifier,
     }, 
+    integration::{
+        deep_link::{DeepLink, DeepLinkView},
+        turtle::{TripleObject, turtle_triple},
+    },
     support::uitools::primary_color, 
     ui::style::{ICON_FILTER, ICON_GRAPH}, 
     uistate::actions::ReferenceAction
 };
 
+// Subject of the shown references, needed for the value context menu
+#[derive(Clone, Copy)]
+pub struct RefMenuContext {
+    pub subject_index: IriIndex,
+    pub reverse: bool,
+}
+
 impl RdfGlanceApp {
     pub fn show_table(&mut self, ui: &mut egui::Ui) -> NodeAction {
         let mut action_type_index: NodeAction = NodeAction::None;
@@ -43,7 +57,7 @@ impl RdfGlanceApp {
             });
             egui::TextEdit::singleline(&mut self.object_iri).show(ui);
             if ui.button("Load Object").clicked() {
-                println!("load object: {}", self.object_iri);
+                log::debug!("load object: {}", self.object_iri);
                 self.show_object();
             }
         });
@@ -55,7 +69,8 @@ impl RdfGlanceApp {
                     let full_iri = rdf_data.prefix_manager.get_full_opt(iri).unwrap_or(iri.clone());
                     ui.horizontal(|ui| {
                         ui.strong("full iri:");
-                        ui.label(full_iri);
+                        let response = ui.label(&*full_iri);
+                        iri_context_menu(&response, &full_iri, current_node.is_blank_node);
                     });
                     let button_text = egui::RichText::new(concatcp!(ICON_GRAPH, " See in Visual Graph (G)")).size(16.0);
                     let nav_but = egui::Button::new(button_text).fill(primary_color(ui.visuals()));
@@ -66,7 +81,7 @@ impl RdfGlanceApp {
                     b_resp.on_hover_text("This will add the node to the visual graph and switch to visual graph view. The node will be selected.");
                     let label_context = LabelContext::new(
                         self.ui_state.display_language,
-                        self.persistent_data.config_data.iri_display,
+                        self.config.iri_display,
                         &rdf_data.prefix_manager,
                     );
                     ui.horizontal(|ui| {
@@ -82,7 +97,17 @@ impl RdfGlanceApp {
                             }
                         }
                     });
-                    if current_node.properties.is_empty() {
+                    let blank_node_trees = if self.config.flatten_blank_nodes {
+                        blank_node_trees(&rdf_data.node_data, current_node)
+                    } else {
+                        Vec::new()
+                    };
+                    let mut rdf_lists = node_lists(&rdf_data.node_data, current_iri_index);
+                    if self.config.flatten_blank_nodes {
+                        // not resolved lists are already part of the blank node trees
+                        rdf_lists.retain(|list| !current_node.references.contains(&(list.predicate, list.head)));
+                    }
+                    if current_node.properties.is_empty() && blank_node_trees.is_empty() && rdf_lists.is_empty() {
                         let h = (ui.available_height() - 40.0).max(300.0);
                         node_to_click = show_refs_table(
                             ui,
@@ -93,6 +118,7 @@ impl RdfGlanceApp {
                             h,
                             &label_context,
                             &mut self.ref_selection,
+                            current_iri_index,
                         );
                     } else {
                         egui::ScrollArea::vertical()
@@ -105,7 +131,7 @@ impl RdfGlanceApp {
                                     .max_col_width(avialable_width)
                                     .show(ui, |ui| {
                                         for (predicate_index, prop_value) in &current_node.properties {
-                                            if self.persistent_data.config_data.suppress_other_language_data {
+                                            if self.config.suppress_other_language_data {
                                                 if let Literal::LangString(lang, _) = prop_value {
                                                     if *lang != self.ui_state.display_language {
                                                         if *lang == 0 && self.ui_state.display_language != 0 {
@@ -140,12 +166,104 @@ impl RdfGlanceApp {
                                                 &rdf_data.node_data.indexers,
                                             );
                                             ui.label(predicate_label.as_str());
-                                            ui.label(prop_value.as_str_ref(&rdf_data.node_data.indexers));
+                                            let value = prop_value.as_str_ref(&rdf_data.node_data.indexers);
+                                            let identifier = rdf_data
+                                                .node_data
+                                                .indexers
+                                                .predicate_indexer
+                                                .index_to_str(*predicate_index)
+                                                .and_then(|predicate_iri| {
+                                                    resolve_identifier(
+                                                        &self.config.identifier_templates,
+                                                        predicate_iri,
+                                                        value,
+                                                    )
+                                                });
+                                            let response = match identifier {
+                                                Some((badge, url)) => {
+                                                    ui.horizontal(|ui| {
+                                                        let response = ui.hyperlink_to(value, url);
+                                                        identifier_badge(ui, badge);
+                                                        response
+                                                    })
+                                                    .inner
+                                                }
+                                                None => ui.label(value),
+                                            };
+                                            response.context_menu(|ui| {
+                                                if ui.button("Copy Value").clicked() {
+                                                    ui.ctx().copy_text(value.to_string());
+                                                    ui.close_kind(UiKind::Menu);
+                                                }
+                                                if ui.button("Copy as Turtle Triple").clicked() {
+                                                    if let Some(triple) = turtle_triple(
+                                                        &rdf_data.node_data,
+                                                        &rdf_data.prefix_manager,
+                                                        current_iri_index,
+                                                        *predicate_index,
+                                                        TripleObject::Literal(prop_value),
+                                                    ) {
+                                                        ui.ctx().copy_text(triple);
+                                                    }
+                                                    ui.close_kind(UiKind::Menu);
+                                                }
+                                                if is_web_link(value) && ui.button("Open in Browser").clicked() {
+                                                    ui.ctx().open_url(egui::OpenUrl::new_tab(value));
+                                                    ui.close_kind(UiKind::Menu);
+                                                }
+                                                let search_type = current_node.types.first();
+                                                ui.add_enabled_ui(search_type.is_some(), |ui| {
+                                                    if ui.button("Search Nodes with this Value").clicked()
+                                                        && let Some(search_type) = search_type
+                                                        && let Some(type_data) = self.type_index.types.get(search_type)
+                                                    {
+                                                        let instances: Vec<IriIndex> = type_data
+                                                            .instances
+                                                            .iter()
+                                                            .cloned()
+                                                            .filter(|instance_index| {
+                                                                has_property_value(
+                                                                    &rdf_data.node_data,
+                                                                    *instance_index,
+                                                                    *predicate_index,
+                                                                    value,
+                                                                )
+                                                            })
+                                                            .collect();
+                                                        node_to_click = ReferenceAction::Filter(*search_type, instances);
+                                                        ui.close_kind(UiKind::Menu);
+                                                    }
+                                                });
+                                            });
                                             ui.end_row();
                                         }
                                     });
+                                if !blank_node_trees.is_empty() {
+                                    ui.heading("Blank Node Trees");
+                                    show_blank_node_trees(
+                                        ui,
+                                        &blank_node_trees,
+                                        &rdf_data.node_data,
+                                        &self.visualization_style,
+                                        self.config.short_iri,
+                                        &label_context,
+                                        &mut node_to_click,
+                                    );
+                                }
+                                if !rdf_lists.is_empty() {
+                                    ui.heading("Lists");
+                                    show_rdf_lists(
+                                        ui,
+                                        &rdf_lists,
+                                        &rdf_data.node_data,
+                                        &self.visualization_style,
+                                        self.config.short_iri,
+                                        &label_context,
+                                        &mut node_to_click,
+                                    );
+                                }
                                 let h = (ui.available_height() - 40.0).max(300.0);
-                                node_to_click = show_refs_table(
+                                let ref_result = show_refs_table(
                                     ui,
                                     current_node,
                                     &rdf_data.node_data,
@@ -154,7 +272,11 @@ impl RdfGlanceApp {
                                     h,
                                     &label_context,
                                     &mut self.ref_selection,
+                                    current_iri_index,
                                 );
+                                if ref_result != ReferenceAction::None {
+                                    node_to_click = ref_result;
+                                }
                             });
                     }
                 }
@@ -175,6 +297,148 @@ impl RdfGlanceApp {
     }
 }
 
+/// Nested blank nodes as indented property groups
+pub fn show_blank_node_trees(
+    ui: &mut egui::Ui,
+    trees: &[BlankNodeTree],
+    node_data: &NodeData,
+    visualization_style: &GVisualizationStyle,
+    short_iri: bool,
+    label_context: &LabelContext,
+    node_to_click: &mut ReferenceAction,
+) {
+    for tree in trees {
+        let mut header = node_data
+            .predicate_display(tree.predicate, label_context, &node_data.indexers)
+            .as_str()
+            .to_string();
+        if tree.is_list() {
+            header = format!("{} (list of {})", header, tree.list_items.len());
+        } else if let Some(type_index) = tree.node.types.first() {
+            let type_label = node_data.type_display(*type_index, label_context, &node_data.indexers);
+            header = format!("{} ({})", header, type_label.as_str());
+        }
+        egui::CollapsingHeader::new(header)
+            .id_salt(("blank_node_tree", tree.node_index))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new(("blank_node_properties", tree.node_index))
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (position, list_item) in tree.list_items.iter().enumerate() {
+                            ui.label(format!("{}.", position + 1));
+                            match list_item {
+                                ListItem::Literal(literal) => {
+                                    ui.label(literal.as_str_ref(&node_data.indexers));
+                                }
+                                ListItem::Node(item_index) => {
+                                    if let Some((item_iri, item)) = node_data.get_node_by_index(*item_index) {
+                                        if item.is_blank_node {
+                                            ui.weak("blank node (see below)");
+                                        } else {
+                                            let item_label = item.node_label(
+                                                item_iri,
+                                                visualization_style,
+                                                short_iri,
+                                                label_context.language_index,
+                                                &node_data.indexers,
+                                            );
+                                            if ui.link(item_label).clicked() {
+                                                *node_to_click = ReferenceAction::ShowNode(*item_index);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                        for (predicate_index, literal) in tree.properties.iter() {
+                            let predicate_label =
+                                node_data.predicate_display(*predicate_index, label_context, &node_data.indexers);
+                            ui.label(predicate_label.as_str());
+                            ui.label(literal.as_str_ref(&node_data.indexers));
+                            ui.end_row();
+                        }
+                        // the named members of a list are already shown as list items
+                        let references = if tree.is_list() { &[][..] } else { &tree.references[..] };
+                        for (predicate_index, target_index) in references.iter() {
+                            let Some((target_iri, target)) = node_data.get_node_by_index(*target_index) else {
+                                continue;
+                            };
+                            let predicate_label =
+                                node_data.predicate_display(*predicate_index, label_context, &node_data.indexers);
+                            ui.label(predicate_label.as_str());
+                            let target_label = target.node_label(
+                                target_iri,
+                                visualization_style,
+                                short_iri,
+                                label_context.language_index,
+                                &node_data.indexers,
+                            );
+                            if ui.link(target_label).clicked() {
+                                *node_to_click = ReferenceAction::ShowNode(*target_index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                show_blank_node_trees(
+                    ui,
+                    &tree.children,
+                    node_data,
+                    visualization_style,
+                    short_iri,
+                    label_context,
+                    node_to_click,
+                );
+            });
+    }
+}
+
+/// rdf lists of the node as numbered members
+pub fn show_rdf_lists(
+    ui: &mut egui::Ui,
+    lists: &[RdfList],
+    node_data: &NodeData,
+    visualization_style: &GVisualizationStyle,
+    short_iri: bool,
+    label_context: &LabelContext,
+    node_to_click: &mut ReferenceAction,
+) {
+    for list in lists {
+        let predicate_label = node_data.predicate_display(list.predicate, label_context, &node_data.indexers);
+        egui::CollapsingHeader::new(format!("{} (list of {})", predicate_label.as_str(), list.members.len()))
+            .id_salt(("rdf_list", list.holder, list.predicate, list.head))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new(("rdf_list_members", list.head)).striped(true).show(ui, |ui| {
+                    for (position, member) in list.members.iter().enumerate() {
+                        ui.label(format!("{}.", position + 1));
+                        match member {
+                            ListMember::Literal(literal) => {
+                                ui.label(literal.as_str_ref(&node_data.indexers));
+                            }
+                            ListMember::Node(member_index) => {
+                                if let Some((member_iri, member_node)) = node_data.get_node_by_index(*member_index) {
+                                    let member_label = member_node.node_label(
+                                        member_iri,
+                                        visualization_style,
+                                        short_iri,
+                                        label_context.language_index,
+                                        &node_data.indexers,
+                                    );
+                                    if ui.link(member_label).clicked() {
+                                        *node_to_click = ReferenceAction::ShowNode(*member_index);
+                                    }
+                                }
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
+
 pub fn show_refs_table(
     ui: &mut egui::Ui,
     current_node: &NObject,
@@ -184,6 +448,7 @@ pub fn show_refs_table(
     h: f32,
     label_context: &LabelContext,
     ref_selection: &mut RefSelection,
+    current_index: IriIndex,
 ) -> ReferenceAction {
     let mut node_to_click: ReferenceAction = ReferenceAction::None;
     if !matches!(ref_selection, RefSelection::None) {
@@ -215,6 +480,10 @@ pub fn show_refs_table(
                     "ref",
                     label_context,
                     ref_selection.ref_index(false),
+                    Some(RefMenuContext {
+                        subject_index: current_index,
+                        reverse: false,
+                    }),
                 );
                 if ref_result != ReferenceAction::None {
                     node_to_click = ref_result;
@@ -233,6 +502,10 @@ pub fn show_refs_table(
                         "ref_by",
                         label_context,
                         ref_selection.ref_index(true),
+                        Some(RefMenuContext {
+                            subject_index: current_index,
+                            reverse: true,
+                        }),
                     );
                     if ref_result != ReferenceAction::None {
                         node_to_click = ref_result;
@@ -254,6 +527,7 @@ pub fn show_references(
     id_salt: &str,
     label_context: &LabelContext,
     selected_idx: Option<usize>,
+    menu_context: Option<RefMenuContext>,
 ) -> ReferenceAction {
     let mut node_to_click: ReferenceAction = ReferenceAction::None;
     if !references.is_empty() {
@@ -322,9 +596,23 @@ pub fn show_references(
                     }
                     if let Some((ref_iri, ref_node)) = node_data.get_node_by_index(*ref_index) {
                         row.col(|ui| {
-                            if ui.link(ref_iri).clicked() {
+                            let response = ui.link(ref_iri);
+                            if response.clicked() {
                                 node_to_click = ReferenceAction::ShowNode(*ref_index);
                             }
+                            if let Some(menu_context) = menu_context {
+                                let menu_action = reference_context_menu(
+                                    &response,
+                                    node_data,
+                                    label_context,
+                                    menu_context,
+                                    *predicate_index,
+                                    *ref_index,
+                                );
+                                if menu_action != ReferenceAction::None {
+                                    node_to_click = menu_action;
+                                }
+                            }
                         });
                         row.col(|ui| {
                             let mut types_label: String = String::new();
@@ -380,3 +668,111 @@ pub fn show_references(
     }
     node_to_click
 }
+
+fn reference_context_menu(
+    response: &Response,
+    node_data: &NodeData,
+    label_context: &LabelContext,
+    menu_context: RefMenuContext,
+    predicate_index: IriIndex,
+    ref_index: IriIndex,
+) -> ReferenceAction {
+    let mut action = ReferenceAction::None;
+    let Some((ref_iri, ref_node)) = node_data.get_node_by_index(ref_index) else {
+        return action;
+    };
+    response.context_menu(|ui| {
+        let full_iri = label_context.prefix_manager.get_full_opt(ref_iri).unwrap_or(ref_iri.clone());
+        iri_menu_buttons(ui, &full_iri, ref_node.is_blank_node);
+        if ui.button("Copy as Turtle Triple").clicked() {
+            let (subject_index, object_index) = if menu_context.reverse {
+                (ref_index, menu_context.subject_index)
+            } else {
+                (menu_context.subject_index, ref_index)
+            };
+            if let Some(triple) = turtle_triple(
+                node_data,
+                label_context.prefix_manager,
+                subject_index,
+                predicate_index,
+                TripleObject::Node(object_index),
+            ) {
+                ui.ctx().copy_text(triple);
+            }
+            ui.close_kind(UiKind::Menu);
+        }
+        if !menu_context.reverse {
+            let search_type = node_data
+                .get_node_by_index(menu_context.subject_index)
+                .and_then(|(_, subject)| subject.types.first().cloned());
+            ui.add_enabled_ui(search_type.is_some(), |ui| {
+                if ui.button("Search Nodes with this Value").clicked()
+                    && let Some(search_type) = search_type
+                {
+                    // all nodes of the same type referencing the node with the same predicate
+                    let instances: Vec<IriIndex> = ref_node
+                        .reverse_references
+                        .iter()
+                        .filter(|(predicate, subject_index)| {
+                            *predicate == predicate_index
+                                && node_data
+                                    .get_node_by_index(*subject_index)
+                                    .is_some_and(|(_, subject)| subject.types.contains(&search_type))
+                        })
+                        .map(|(_, subject_index)| *subject_index)
+                        .collect();
+                    action = ReferenceAction::Filter(search_type, instances);
+                    ui.close_kind(UiKind::Menu);
+                }
+            });
+        }
+    });
+    action
+}
+
+fn iri_context_menu(response: &Response, full_iri: &str, is_blank_node: bool) {
+    response.context_menu(|ui| {
+        iri_menu_buttons(ui, full_iri, is_blank_node);
+        // blank node ids are not stable between loads
+        if !is_blank_node && ui.button("Copy Deep Link").clicked() {
+            let deep_link = DeepLink {
+                iri: full_iri.to_string(),
+                view: DeepLinkView::Browse,
+            };
+            ui.ctx().copy_text(deep_link.to_link());
+            ui.close_kind(UiKind::Menu);
+        }
+    });
+}
+
+fn iri_menu_buttons(ui: &mut egui::Ui, full_iri: &str, is_blank_node: bool) {
+    if ui.button("Copy IRI").clicked() {
+        ui.ctx().copy_text(full_iri.to_string());
+        ui.close_kind(UiKind::Menu);
+    }
+    if !is_blank_node && is_web_link(full_iri) && ui.button("Open IRI in Browser").clicked() {
+        ui.ctx().open_url(egui::OpenUrl::new_tab(full_iri));
+        ui.close_kind(UiKind::Menu);
+    }
+}
+
+fn identifier_badge(ui: &mut egui::Ui, badge: &str) {
+    ui.label(
+        egui::RichText::new(badge)
+            .small()
+            .color(ui.visuals().strong_text_color())
+            .background_color(ui.visuals().faint_bg_color),
+    );
+}
+
+fn is_web_link(value: &str) -> bool {
+    value.starts_with("http://") || value.starts_with("https://")
+}
+
+fn has_property_value(node_data: &NodeData, node_index: IriIndex, predicate_index: IriIndex, value: &str) -> bool {
+    node_data.get_node_by_index(node_index).is_some_and(|(_, node)| {
+        node.properties
+            .iter()
+            .any(|(predicate, literal)| *predicate == predicate_index && literal.as_str_ref(&node_data.indexers) == value)
+    })
+}
@@ -0,0 +1,211 @@
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+use egui::ScrollArea;
+use rfd::FileDialog;
+
+use crate::{
+    RdfGlanceApp,
+    integration::bundle::{AnalysisBundle, NOTES_ENTRY, PROJECT_ENTRY, QUERY_ENTRY, SOURCES_PREFIX},
+    uistate::SystemMessage,
+};
+
+pub struct BundleExport {
+    pub include_sources: bool,
+    pub include_query: bool,
+}
+
+impl RdfGlanceApp {
+    pub fn open_bundle_export(&mut self) {
+        if self.bundle_export.is_none() {
+            self.bundle_export = Some(BundleExport {
+                include_sources: false,
+                include_query: true,
+            });
+        }
+    }
+
+    /// Distinct local files the current data was imported from
+    fn bundle_source_files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = Vec::new();
+        for report in self.import_log.reports.iter() {
+            let path = PathBuf::from(&report.source);
+            if path.is_file() && !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        files
+    }
+
+    pub fn show_bundle_export(&mut self, ui: &mut egui::Ui) {
+        if self.bundle_export.is_none() {
+            return;
+        }
+        let source_files = self.bundle_source_files();
+        let Some(bundle_export) = &mut self.bundle_export else {
+            return;
+        };
+        let has_query = self.sparql_query.is_some();
+        let mut close = false;
+        let mut export = false;
+        egui::Window::new("Export Analysis Bundle")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(500.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label("The bundle contains the project with data, styles, layout and settings");
+                ui.add_enabled(
+                    !source_files.is_empty(),
+                    egui::Checkbox::new(
+                        &mut bundle_export.include_sources,
+                        format!("Include source data files ({})", source_files.len()),
+                    ),
+                )
+                .on_hover_text(
+                    source_files
+                        .iter()
+                        .map(|path| path.display().to_string())
+                        .collect::<Vec<_>>()
+                        .join("\n"),
+                );
+                ui.add_enabled(
+                    has_query,
+                    egui::Checkbox::new(&mut bundle_export.include_query, "Include SPARQL query"),
+                );
+                ui.label("Notes");
+                ScrollArea::vertical().max_height(250.0).show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.analysis_notes)
+                            .desired_rows(8)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Export...").clicked() {
+                        export = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if export
+            && let Some(path) = FileDialog::new()
+                .add_filter("RDF Glance analysis bundle", &["rdfgbundle"])
+                .set_file_name("analysis.rdfgbundle")
+                .save_file()
+        {
+            let include_sources = bundle_export.include_sources;
+            let include_query = bundle_export.include_query && has_query;
+            match self.export_bundle(&path, include_sources, include_query) {
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not export analysis bundle: {}", e));
+                }
+                Ok(_) => {
+                    self.set_status_message("Analysis bundle exported");
+                    close = true;
+                }
+            }
+        }
+        if close {
+            self.bundle_export = None;
+        }
+    }
+
+    fn export_bundle(&self, path: &Path, include_sources: bool, include_query: bool) -> anyhow::Result<()> {
+        let mut bundle = AnalysisBundle::default();
+        // the project format is written by seeking in a file
+        let project_path = std::env::temp_dir().join(format!("rdfglance-bundle-{}.rdfglance", std::process::id()));
+        let project_store = self.store(&project_path);
+        let project_data = project_store.and_then(|_| std::fs::read(&project_path));
+        let _ = std::fs::remove_file(&project_path);
+        bundle.add(PROJECT_ENTRY, project_data?);
+        if include_sources {
+            for source in self.bundle_source_files() {
+                if let Some(file_name) = source.file_name() {
+                    let name = format!("{}{}", SOURCES_PREFIX, file_name.to_string_lossy());
+                    bundle.add(&name, std::fs::read(&source)?);
+                }
+            }
+        }
+        if include_query && let Some(sparql_query) = &self.sparql_query {
+            bundle.add(QUERY_ENTRY, sparql_query.query().as_bytes().to_vec());
+        }
+        if !self.analysis_notes.trim().is_empty() {
+            bundle.add(NOTES_ENTRY, self.analysis_notes.as_bytes().to_vec());
+        }
+        bundle.write(&mut BufWriter::new(File::create(path)?))?;
+        Ok(())
+    }
+
+    pub fn import_bundle_dialog(&mut self, is_dark_mode: bool) {
+        if let Some(path) = FileDialog::new()
+            .add_filter("RDF Glance analysis bundle", &["rdfgbundle"])
+            .pick_file()
+            && let Err(e) = self.import_bundle(&path, is_dark_mode)
+        {
+            self.system_message = SystemMessage::Error(format!("Can not import analysis bundle: {}", e));
+        }
+    }
+
+    /**
+     * The bundle is extracted into a directory next to the bundle file (named as the bundle without extension),
+     * so the project and the source files stay available for later work.
+     */
+    fn import_bundle(&mut self, path: &Path, is_dark_mode: bool) -> anyhow::Result<()> {
+        let bundle = AnalysisBundle::read(&mut BufReader::new(File::open(path)?))?;
+        let Some(project_data) = bundle.get(PROJECT_ENTRY) else {
+            return Err(anyhow::anyhow!("The bundle contains no project"));
+        };
+        let target_dir = path.with_extension("");
+        std::fs::create_dir_all(&target_dir)?;
+        let project_path = target_dir.join(PROJECT_ENTRY);
+        std::fs::write(&project_path, project_data)?;
+        let mut source_count = 0;
+        for (file_name, data) in bundle.sources() {
+            let sources_dir = target_dir.join(SOURCES_PREFIX);
+            std::fs::create_dir_all(&sources_dir)?;
+            std::fs::write(sources_dir.join(file_name), data)?;
+            source_count += 1;
+        }
+        self.load_project(&project_path, is_dark_mode);
+        if let Some(query) = bundle.get_str(QUERY_ENTRY) {
+            self.open_sparql_query();
+            if let Some(sparql_query) = &mut self.sparql_query {
+                sparql_query.set_query(query);
+            }
+        }
+        self.analysis_notes = bundle.get_str(NOTES_ENTRY).unwrap_or_default().to_string();
+        self.show_analysis_notes = !self.analysis_notes.is_empty();
+        self.set_status_message(&format!(
+            "Analysis bundle extracted to {} ({} source files)",
+            target_dir.display(),
+            source_count
+        ));
+        Ok(())
+    }
+
+    pub fn show_analysis_notes(&mut self, ui: &mut egui::Ui) {
+        if !self.show_analysis_notes {
+            return;
+        }
+        egui::Window::new("Analysis Notes")
+            .open(&mut self.show_analysis_notes)
+            .resizable(true)
+            .default_width(500.0)
+            .show(ui.ctx(), |ui| {
+                ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.analysis_notes)
+                            .desired_rows(12)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+            });
+    }
+}
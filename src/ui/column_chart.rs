@@ -0,0 +1,129 @@
+use const_format::concatcp;
+use egui_plot::{Bar, BarChart, Plot};
+
+use crate::{
+    domain::column_chart::ColumnChart,
+    support::uitools::primary_color,
+    ui::style::{ICON_CLOSE, ICON_EXPORT},
+};
+
+impl ColumnChart {
+    /**
+     * Shows the chart in a window.
+     * Returns true if the window should be closed.
+     */
+    pub fn show_window(&self, ctx: &egui::Context) -> bool {
+        let mut close = false;
+        egui::Window::new(format!("Chart: {}", self.title))
+            .id(egui::Id::new("column_chart"))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                self.show_plot(ui, "column_chart", 300.0);
+                ui.horizontal(|ui| {
+                    self.export_button(ui);
+                    if ui.button(concatcp!(ICON_CLOSE, " Close")).clicked() {
+                        close = true;
+                    }
+                });
+            });
+        close
+    }
+
+    /// Value counts and the bar plot
+    pub fn show_plot(&self, ui: &mut egui::Ui, plot_id: &str, height: f32) {
+        let values: u32 = self.bars.iter().map(|bar| bar.count).sum();
+        ui.label(format!("Values: {}, Missing: {}", values, self.missing));
+        let bars: Vec<Bar> = self
+            .bars
+            .iter()
+            .map(|bar| Bar::new(bar.position, bar.count as f64).width(bar.width).name(&bar.label))
+            .collect();
+        let chart = BarChart::new(self.title.as_str(), bars)
+            .color(primary_color(ui.visuals()))
+            .element_formatter(Box::new(|bar, _chart| format!("{}: {}", bar.name, bar.value)));
+        let mut plot = Plot::new(plot_id).height(height).y_axis_label("count");
+        if !self.numeric {
+            // categories are placed at integer positions
+            plot = plot.x_axis_formatter(|mark, _range| {
+                if mark.value.fract() != 0.0 || mark.value < 0.0 {
+                    return String::new();
+                }
+                self.bars
+                    .get(mark.value as usize)
+                    .map(|bar| bar.label.clone())
+                    .unwrap_or_default()
+            });
+        }
+        plot.show(ui, |plot_ui| {
+            plot_ui.bar_chart(chart);
+        });
+    }
+
+    pub fn export_button(&self, ui: &mut egui::Ui) {
+        if ui
+            .button(concatcp!(ICON_EXPORT, " Export CSV"))
+            .on_hover_text("Export the bar counts as CSV file")
+            .clicked()
+        {
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("CSV File", &["csv"])
+                .set_file_name("chart.csv")
+                .save_file()
+            {
+                match csv::Writer::from_path(path) {
+                    Ok(mut wtr) => {
+                        if let Err(e) = self.export_csv(&mut wtr) {
+                            log::error!("Can not export chart: {}", e);
+                        }
+                    }
+                    Err(e) => log::error!("Can not export chart: {}", e),
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            {
+                use crate::support::uitools::web_download;
+
+                let mut wtr = csv::Writer::from_writer(Vec::new());
+                if self.export_csv(&mut wtr).is_ok()
+                    && let Ok(buf) = wtr.into_inner()
+                {
+                    let _ = web_download("chart.csv", &buf);
+                }
+            }
+        }
+        if ui
+            .button(concatcp!(ICON_EXPORT, " Export SVG"))
+            .on_hover_text("Export the chart as SVG image")
+            .clicked()
+        {
+            let bar_color = primary_color(ui.visuals());
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("SVG", &["svg"])
+                .set_file_name("chart.svg")
+                .save_file()
+            {
+                match std::fs::File::create(path) {
+                    Ok(mut file) => {
+                        if let Err(e) = self.export_svg(&mut file, bar_color) {
+                            log::error!("Can not export chart: {}", e);
+                        }
+                    }
+                    Err(e) => log::error!("Can not export chart: {}", e),
+                }
+            }
+            #[cfg(target_arch = "wasm32")]
+            {
+                use crate::support::uitools::web_download;
+
+                let mut buf = Vec::new();
+                if self.export_svg(&mut buf, bar_color).is_ok() {
+                    let _ = web_download("chart.svg", &buf);
+                }
+            }
+        }
+    }
+}
@@ -3,64 +3,256 @@ use egui::{Align, Layout, Slider};
 use crate::{
     uistate::actions::NodeAction, 
     RdfGlanceApp, 
-    domain::config::IriDisplay
+    domain::{config::IriDisplay, identifier_resolution::{IdentifierTemplate, default_identifier_templates}}
 };
 
 impl RdfGlanceApp {
     pub fn show_config(&mut self, ui: &mut egui::Ui) -> NodeAction {
-        ui.horizontal(|ui| {
+        ui.label("Checked settings are overwritten for the current project and stored in the project file, the others are global.");
+        ui.separator();
+        // the effective config is computed again only if a global or project value was changed
+        let mut changed = false;
+        let color_by_namespace = self.config.color_by_namespace;
+        let global = &mut self.persistent_data.config_data;
+        let project = &mut self.project_config;
+        changed |= with_level(ui, &mut global.language_filter, &mut project.language_filter, |ui, language_filter| {
             ui.label("language filter (comma separated):");
-            ui.text_edit_singleline(&mut self.persistent_data.config_data.language_filter);
+            ui.text_edit_singleline(language_filter).changed()
         });
-        ui.checkbox(
-            &mut self.persistent_data.config_data.suppress_other_language_data,
-            "Supress data in not display language",
-        );
-        ui.label("Predicate and Type display:");
-        ui.radio_value(
-            &mut self.persistent_data.config_data.iri_display,
-            IriDisplay::Label,
-            "Label",
-        );
-        ui.radio_value(
-            &mut self.persistent_data.config_data.iri_display,
-            IriDisplay::LabelOrShorten,
-            "Label or IRI Shorten",
+        changed |= with_level(
+            ui,
+            &mut global.suppress_other_language_data,
+            &mut project.suppress_other_language_data,
+            |ui, suppress| ui.checkbox(suppress, "Supress data in not display language").changed(),
         );
-        ui.radio_value(
-            &mut self.persistent_data.config_data.iri_display,
-            IriDisplay::Prefixed,
-            "IRI Prefixed",
+        changed |= with_level(ui, &mut global.iri_display, &mut project.iri_display, |ui, iri_display| {
+            ui.vertical(|ui| {
+                ui.label("Predicate and Type display:");
+                [
+                    ui.radio_value(iri_display, IriDisplay::Label, "Label"),
+                    ui.radio_value(iri_display, IriDisplay::LabelOrShorten, "Label or IRI Shorten"),
+                    ui.radio_value(iri_display, IriDisplay::Prefixed, "IRI Prefixed"),
+                    ui.radio_value(iri_display, IriDisplay::Shorten, "IRI Shorten"),
+                    ui.radio_value(iri_display, IriDisplay::Full, "Full IRI"),
+                ]
+                .iter()
+                .any(|response| response.changed())
+            })
+            .inner
+        });
+        changed |= with_level(ui, &mut global.short_iri, &mut project.short_iri, |ui, short_iri| {
+            ui.checkbox(short_iri, "Short IRI for nodes without label").changed()
+        });
+        changed |= with_level(
+            ui,
+            &mut global.m_repulsion_constant,
+            &mut project.m_repulsion_constant,
+            |ui, repulsion_constant| ui.add(Slider::new(repulsion_constant, 0.1..=8.0).text("Nodes force")).changed(),
         );
-        ui.radio_value(
-            &mut self.persistent_data.config_data.iri_display,
-            IriDisplay::Shorten,
-            "IRI Shorten",
+        changed |= with_level(
+            ui,
+            &mut global.m_attraction_factor,
+            &mut project.m_attraction_factor,
+            |ui, attraction_factor| ui.add(Slider::new(attraction_factor, 0.02..=3.0).text("Edges force")).changed(),
         );
-        ui.radio_value(
-            &mut self.persistent_data.config_data.iri_display,
-            IriDisplay::Full,
-            "Full IRI",
+        changed |= with_level(
+            ui,
+            &mut global.gravity_effect_radius,
+            &mut project.gravity_effect_radius,
+            |ui, gravity_effect_radius| {
+                ui.add(Slider::new(gravity_effect_radius, 50.0..=1000.0).text("Gravity effect radius for layout"))
+                    .changed()
+            },
         );
-        ui.checkbox(
-            &mut self.persistent_data.config_data.resolve_rdf_lists,
-            "Resolve rdf lists",
+        changed |= with_level(
+            ui,
+            &mut global.color_by_namespace,
+            &mut project.color_by_namespace,
+            |ui, color_by_namespace| {
+                ui.checkbox(color_by_namespace, "Color types by namespace")
+                    .on_hover_text("Types of the same namespace (prefix) get shades of the same color")
+                    .changed()
+            },
         );
-        //ui.text_edit_singleline(text)
+        ui.separator();
+        ui.label("Global settings:");
+        let config_data = &mut self.persistent_data.config_data;
+        changed |= ui.checkbox(&mut config_data.resolve_rdf_lists, "Resolve rdf lists").changed();
+        changed |= ui
+            .checkbox(&mut config_data.flatten_blank_nodes, "Flatten blank node trees")
+            .on_hover_text("Show nested blank nodes as property groups of the referencing node and as one composite node in the visual graph")
+            .changed();
         ui.horizontal(|ui| {
             ui.label("Community resolution:");
-            ui.add(
-                egui::DragValue::new(&mut self.persistent_data.config_data.community_resolution)
-                    .speed(0.01)
-                    .range(0.10..=3.0),
-            );
+            changed |= ui
+                .add(
+                    egui::DragValue::new(&mut config_data.community_resolution)
+                        .speed(0.01)
+                        .range(0.10..=3.0),
+                )
+                .changed();
         });
-        ui.checkbox(
-            &mut self.persistent_data.config_data.community_randomize,
-            "community detection randomize",
-        );
-        ui.add(Slider::new(&mut self.persistent_data.config_data.max_visible_nodes, 1000..=200_000).text("Max nodes in visual graph"));
-        ui.add(Slider::new(&mut self.persistent_data.config_data.gravity_effect_radius, 50.0..=1000.0).text("Gravity effect radius for layout"));
+        changed |= ui
+            .checkbox(&mut config_data.community_randomize, "community detection randomize")
+            .changed();
+        changed |= ui
+            .add(Slider::new(&mut config_data.max_visible_nodes, 1000..=200_000).text("Max nodes in visual graph"))
+            .changed();
+        ui.separator();
+        ui.label("Edge labels in visual graph:");
+        changed |= ui
+            .add(Slider::new(&mut config_data.edge_label_min_zoom, 0.1..=4.0).text("Minimal zoom for edge labels"))
+            .changed();
+        changed |= ui
+            .add(
+                Slider::new(&mut config_data.edge_label_max_edges, 0..=50_000)
+                    .text("Max visible edges for edge labels (0 - no limit)"),
+            )
+            .changed();
+        changed |= ui
+            .checkbox(&mut config_data.edge_labels_selected_only, "Edge labels only for edges of selected nodes")
+            .changed();
+        changed |= ui
+            .add(Slider::new(&mut config_data.edge_bundle_threshold, 1..=20).text("Bundle more parallel edges than"))
+            .changed();
+        changed |= ui
+            .checkbox(&mut config_data.node_tooltips, "Show tooltip with properties of hovered node")
+            .changed();
+        ui.horizontal(|ui| {
+            ui.label("Namespace of new nodes:");
+            changed |= ui.text_edit_singleline(&mut config_data.new_node_namespace).changed();
+        });
+        ui.separator();
+        ui.label("Large graphs (number of visible nodes, 0 - never):");
+        changed |= ui
+            .checkbox(&mut config_data.auto_render_mode, "Simplify drawing automatically")
+            .changed();
+        ui.add_enabled_ui(config_data.auto_render_mode, |ui| {
+            changed |= ui
+                .add(Slider::new(&mut config_data.simple_shapes_min_nodes, 0..=100_000).text("Nodes as circles from"))
+                .changed();
+            changed |= ui
+                .add(Slider::new(&mut config_data.hide_labels_min_nodes, 0..=100_000).text("No node labels from"))
+                .changed();
+            changed |= ui
+                .add(
+                    Slider::new(&mut config_data.hide_edge_labels_min_nodes, 0..=100_000).text("No edge labels from"),
+                )
+                .changed();
+            changed |= ui
+                .add(
+                    Slider::new(&mut config_data.lod_min_nodes, 0..=100_000)
+                        .text("Simple edges and only nodes in view from"),
+                )
+                .changed();
+        });
+        ui.separator();
+        ui.label("Data undo:");
+        changed |= ui
+            .add(Slider::new(&mut config_data.data_undo_levels, 0..=20).text("Undo levels"))
+            .changed();
+        changed |= ui
+            .checkbox(&mut config_data.data_undo_snapshots, "Copy the data for undo of load, merge and clean")
+            .on_hover_text("Patches, SPARQL merges and edits are undone by the inverse triples, other operations need a copy of all data")
+            .changed();
+        ui.add_enabled_ui(config_data.data_undo_snapshots, |ui| {
+            changed |= ui
+                .add(
+                    Slider::new(&mut config_data.data_undo_max_nodes, 0..=500_000)
+                        .text("Only for data with nodes up to (0 - no limit)"),
+                )
+                .changed();
+        });
+        ui.separator();
+        ui.label("Turtle export:");
+        let turtle_style = &mut config_data.turtle_style;
+        changed |= ui.checkbox(&mut turtle_style.group_by_subject, "Group by subject").changed();
+        changed |= ui.checkbox(&mut turtle_style.types_first, "Types first").changed();
+        changed |= ui.checkbox(&mut turtle_style.labels_first, "Labels after types").changed();
+        changed |= ui.checkbox(&mut turtle_style.sort_predicates, "Sort predicates").changed();
+        changed |= ui.checkbox(&mut turtle_style.use_prefixes, "Use prefixes").changed();
+        changed |= ui
+            .add(Slider::new(&mut turtle_style.line_width, 40..=200).text("Line width"))
+            .changed();
+        ui.separator();
+        ui.label("Identifier links ({id} in URL template is replaced by the value):");
+        let identifier_templates = &mut config_data.identifier_templates;
+        let mut remove_template: Option<usize> = None;
+        egui::Grid::new("identifier_templates").num_columns(4).striped(true).show(ui, |ui| {
+            ui.strong("Badge");
+            ui.strong("Predicate local names");
+            ui.strong("URL template");
+            ui.end_row();
+            for (template_index, template) in identifier_templates.iter_mut().enumerate() {
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut template.name).desired_width(70.0))
+                    .changed();
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut template.predicates).desired_width(250.0))
+                    .changed();
+                changed |= ui
+                    .add(egui::TextEdit::singleline(&mut template.url_template).desired_width(300.0))
+                    .changed();
+                if ui.button("Remove").clicked() {
+                    remove_template = Some(template_index);
+                }
+                ui.end_row();
+            }
+        });
+        if let Some(template_index) = remove_template {
+            identifier_templates.remove(template_index);
+            changed = true;
+        }
+        ui.horizontal(|ui| {
+            if ui.button("Add Identifier").clicked() {
+                identifier_templates.push(IdentifierTemplate {
+                    name: String::new(),
+                    predicates: String::new(),
+                    url_template: "https://example.org/{id}".to_string(),
+                });
+                changed = true;
+            }
+            if ui.button("Reset to Defaults").clicked() {
+                *identifier_templates = default_identifier_templates();
+                changed = true;
+            }
+        });
+        ui.separator();
+        changed |= self.show_keymap_config(ui);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use crate::{integration::deep_link::native::register_url_scheme, uistate::SystemMessage};
+
+            ui.separator();
+            ui.label("Deep links:");
+            if ui
+                .checkbox(
+                    &mut self.persistent_data.config_data.deep_links,
+                    "Focus resources opened by rdfglance:// links in this window",
+                )
+                .changed()
+            {
+                changed = true;
+                if self.persistent_data.config_data.deep_links {
+                    self.start_deep_link_listener();
+                } else {
+                    // other instances open the links themselves
+                    self.deep_link_listener = None;
+                }
+            }
+            if ui.button("Register rdfglance:// Links").clicked() {
+                self.system_message = match register_url_scheme() {
+                    Ok(message) => SystemMessage::Info(message),
+                    Err(e) => SystemMessage::Error(format!("Can not register links: {}", e)),
+                };
+            }
+        }
+        if changed {
+            self.update_config();
+            if self.config.color_by_namespace != color_by_namespace {
+                self.update_type_colors(ui.visuals().dark_mode);
+            }
+        }
         NodeAction::None
     }
 
@@ -90,3 +282,37 @@ impl RdfGlanceApp {
         }
     }
 }
+
+// Edits the project value if the setting is overwritten for the project, otherwise the global value.
+// Returns true if the value or its level was changed
+fn with_level<T: Clone>(
+    ui: &mut egui::Ui,
+    global: &mut T,
+    project: &mut Option<T>,
+    add_contents: impl FnOnce(&mut egui::Ui, &mut T) -> bool,
+) -> bool {
+    ui.horizontal(|ui| {
+        let mut is_project = project.is_some();
+        let mut changed = false;
+        if ui
+            .checkbox(&mut is_project, "")
+            .on_hover_text("Overwrite for current project")
+            .changed()
+        {
+            *project = if is_project { Some(global.clone()) } else { None };
+            changed = true;
+        }
+        match project {
+            Some(value) => {
+                changed |= add_contents(ui, value);
+                ui.weak("(project)");
+            }
+            None => {
+                changed |= add_contents(ui, global);
+                ui.weak("(global)");
+            }
+        }
+        changed
+    })
+    .inner
+}
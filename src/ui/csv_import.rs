@@ -0,0 +1,272 @@
+use egui::{Color32, ScrollArea};
+
+use crate::{
+    RdfGlanceApp,
+    domain::data_change::DataChange,
+    integration::{
+        csv_mapping::{
+            ColumnKind, CsvMapping, CsvTable, SubjectMapping, ValueDatatype, guess_delimiter, read_csv_table,
+        },
+        rdf_patch::{PatchFormat, PatchOperation, RdfPatch},
+    },
+    uistate::SystemMessage,
+};
+
+const PREVIEW_ROWS: usize = 3;
+const DELIMITERS: [(u8, &str); 3] = [(b',', "Comma"), (b';', "Semicolon"), (b'\t', "Tab")];
+
+/// State of the CSV import wizard, the file content is kept so the table can be read again with other options
+pub struct CsvImportWizard {
+    pub file_name: String,
+    file_base: String,
+    text: String,
+    pub delimiter: u8,
+    pub has_header: bool,
+    pub table: CsvTable,
+    pub mapping: CsvMapping,
+    pub read_error: Option<String>,
+}
+
+impl CsvImportWizard {
+    pub fn new(file_name: &str, text: String) -> Self {
+        let path = std::path::Path::new(file_name);
+        let file_base = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or("data").to_string();
+        let delimiter = if path.extension().is_some_and(|extension| extension == "tsv") {
+            b'\t'
+        } else {
+            guess_delimiter(&text)
+        };
+        let mut wizard = Self {
+            file_name: file_name.to_string(),
+            file_base,
+            text,
+            delimiter,
+            has_header: true,
+            table: CsvTable {
+                headers: Vec::new(),
+                rows: Vec::new(),
+            },
+            mapping: CsvMapping {
+                namespace: String::new(),
+                type_iri: String::new(),
+                subject: SubjectMapping::Generated,
+                columns: Vec::new(),
+            },
+            read_error: None,
+        };
+        wizard.read_table();
+        wizard
+    }
+
+    /// Reads the table with the current options, the mapping is created again
+    fn read_table(&mut self) {
+        match read_csv_table(&self.text, self.delimiter, self.has_header) {
+            Ok(table) => {
+                self.mapping = CsvMapping::new(&table, &self.file_base);
+                self.table = table;
+                self.read_error = None;
+            }
+            Err(e) => {
+                self.read_error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+impl RdfGlanceApp {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open_csv_import_dialog(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("CSV Files", &["csv", "tsv", "txt"])
+            .pick_file()
+        {
+            let file_name = path.display().to_string();
+            match std::fs::read_to_string(&path) {
+                Ok(text) => self.csv_import = Some(CsvImportWizard::new(&file_name, text)),
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not read CSV file {}: {}", file_name, e));
+                }
+            }
+        }
+    }
+
+    pub fn show_csv_import(&mut self, ui: &mut egui::Ui) {
+        let Some(wizard) = &mut self.csv_import else {
+            return;
+        };
+        let mut import_clicked = false;
+        let mut cancel_clicked = false;
+        egui::Window::new("Import CSV")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(900.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(&wizard.file_name);
+                let mut read_again = false;
+                ui.horizontal(|ui| {
+                    for (delimiter, label) in DELIMITERS {
+                        read_again |= ui.radio_value(&mut wizard.delimiter, delimiter, label).changed();
+                    }
+                    read_again |= ui.checkbox(&mut wizard.has_header, "First row is header").changed();
+                });
+                if read_again {
+                    wizard.read_table();
+                }
+                if let Some(error) = &wizard.read_error {
+                    ui.colored_label(Color32::RED, error);
+                }
+                let mapping = &mut wizard.mapping;
+                let table = &wizard.table;
+                egui::Grid::new("csv_mapping_options").num_columns(2).show(ui, |ui| {
+                    ui.label("Namespace");
+                    ui.add(egui::TextEdit::singleline(&mut mapping.namespace).desired_width(500.0));
+                    ui.end_row();
+                    ui.label("Type IRI");
+                    ui.add(egui::TextEdit::singleline(&mut mapping.type_iri).desired_width(500.0));
+                    ui.end_row();
+                    ui.label("Subject IRI");
+                    let subject_label = match mapping.subject {
+                        SubjectMapping::Column(column) => format!("Namespace + column {}", table.headers[column]),
+                        SubjectMapping::Generated => "Namespace + row number".to_string(),
+                    };
+                    egui::ComboBox::from_id_salt("csv_subject")
+                        .selected_text(subject_label)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut mapping.subject,
+                                SubjectMapping::Generated,
+                                "Namespace + row number",
+                            );
+                            for (column, header) in table.headers.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut mapping.subject,
+                                    SubjectMapping::Column(column),
+                                    format!("Namespace + column {}", header),
+                                );
+                            }
+                        });
+                    ui.end_row();
+                });
+                ui.separator();
+                ScrollArea::vertical().id_salt("csv_columns").max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("csv_columns_grid").striped(true).num_columns(5).show(ui, |ui| {
+                        ui.strong("Column");
+                        ui.strong("Mapping");
+                        ui.strong("Predicate IRI");
+                        ui.strong("Datatype / Target namespace");
+                        ui.strong("First value");
+                        ui.end_row();
+                        for (column, column_mapping) in mapping.columns.iter_mut().enumerate() {
+                            ui.label(&table.headers[column]);
+                            egui::ComboBox::from_id_salt(("csv_kind", column))
+                                .selected_text(match column_mapping.kind {
+                                    ColumnKind::Ignore => "Ignore",
+                                    ColumnKind::DataProperty => "Data property",
+                                    ColumnKind::Reference => "Reference",
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut column_mapping.kind, ColumnKind::Ignore, "Ignore");
+                                    ui.selectable_value(
+                                        &mut column_mapping.kind,
+                                        ColumnKind::DataProperty,
+                                        "Data property",
+                                    );
+                                    ui.selectable_value(&mut column_mapping.kind, ColumnKind::Reference, "Reference");
+                                });
+                            ui.add_enabled(
+                                column_mapping.kind != ColumnKind::Ignore,
+                                egui::TextEdit::singleline(&mut column_mapping.predicate).desired_width(300.0),
+                            );
+                            match column_mapping.kind {
+                                ColumnKind::Ignore => {
+                                    ui.label("");
+                                }
+                                ColumnKind::DataProperty => {
+                                    egui::ComboBox::from_id_salt(("csv_datatype", column))
+                                        .selected_text(column_mapping.datatype.label())
+                                        .show_ui(ui, |ui| {
+                                            for datatype in [
+                                                ValueDatatype::String,
+                                                ValueDatatype::Integer,
+                                                ValueDatatype::Double,
+                                                ValueDatatype::Date,
+                                            ] {
+                                                ui.selectable_value(
+                                                    &mut column_mapping.datatype,
+                                                    datatype,
+                                                    datatype.label(),
+                                                );
+                                            }
+                                        });
+                                }
+                                ColumnKind::Reference => {
+                                    ui.add(
+                                        egui::TextEdit::singleline(&mut column_mapping.target_namespace)
+                                            .desired_width(250.0),
+                                    )
+                                    .on_hover_text("Values that are IRIs are used as they are");
+                                }
+                            }
+                            ui.label(table.rows.first().map(|row| row[column].as_str()).unwrap_or_default());
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                let mut errors = Vec::new();
+                let preview_rows = &table.rows[..table.rows.len().min(PREVIEW_ROWS)];
+                let preview = mapping.triples(preview_rows, &mut errors);
+                ui.label(format!("Rows: {}, preview of the first {} rows", table.rows.len(), preview_rows.len()));
+                ScrollArea::both().id_salt("csv_preview").max_height(150.0).show(ui, |ui| {
+                    for triple in preview.iter() {
+                        ui.label(triple.to_string());
+                    }
+                    for error in errors.iter() {
+                        ui.colored_label(Color32::RED, error);
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!table.rows.is_empty(), egui::Button::new("Import"))
+                        .clicked()
+                    {
+                        import_clicked = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel_clicked = true;
+                    }
+                });
+            });
+        if cancel_clicked {
+            self.csv_import = None;
+        }
+        if import_clicked && let Some(wizard) = self.csv_import.take() {
+            let mut errors = Vec::new();
+            let triples = wizard.mapping.triples(&wizard.table.rows, &mut errors);
+            let patch = RdfPatch {
+                format: PatchFormat::RdfPatch,
+                operations: triples.into_iter().map(PatchOperation::Add).collect(),
+                errors: Vec::new(),
+            };
+            let language_filter = self.config.language_filter();
+            let result = if let Ok(mut rdf_data) = self.rdf_data.write() {
+                let node_count = rdf_data.node_data.len();
+                Some((node_count, patch.apply(&mut rdf_data, &language_filter)))
+            } else {
+                None
+            };
+            if let Some((node_count, mut result)) = result {
+                self.record_triple_change("Import CSV", node_count, &mut result, false);
+                self.set_status_message(&format!(
+                    "CSV imported: {} rows, {} triples added, {} skipped values",
+                    wizard.table.rows.len(),
+                    result.added,
+                    errors.len()
+                ));
+                self.notify_data_change(DataChange::TriplesMerged);
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeSet;
+
+use egui::{ScrollArea, Slider};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{LabelContext, RdfData, type_index::TypeInstanceIndex},
+    graph_algorithms::cycles::{CycleSearch, MAX_CYCLES},
+    uistate::DisplayType,
+};
+
+impl RdfGlanceApp {
+    pub fn open_cycle_search(&mut self) {
+        if self.cycle_search.is_none() {
+            self.cycle_search = Some(CycleSearch::default());
+        }
+    }
+
+    pub fn show_cycle_search(&mut self, ui: &mut egui::Ui) {
+        let Some(cycle_search) = &mut self.cycle_search else {
+            return;
+        };
+        let mut close = false;
+        let mut search = false;
+        let mut cycle_to_show: Option<Vec<IriIndex>> = None;
+        egui::Window::new("Cycle Detection")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    predicate_combo(
+                        ui,
+                        "Predicate",
+                        &mut cycle_search.predicate,
+                        "All references",
+                        &label_context,
+                        &rdf_data,
+                        &self.type_index,
+                    );
+                    ui.add(Slider::new(&mut cycle_search.max_length, 1..=12).text("Max cycle length"));
+                    if ui.button("Find Cycles").clicked() {
+                        search = true;
+                    }
+                    if cycle_search.searched {
+                        ui.separator();
+                        if cycle_search.truncated {
+                            ui.label(format!("First {} cycles found (search stopped)", MAX_CYCLES));
+                        } else {
+                            ui.label(format!("Cycles found: {}", cycle_search.cycles.len()));
+                        }
+                        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                            for (index, cycle) in cycle_search.cycles.iter().enumerate() {
+                                let mut names: Vec<&str> = cycle
+                                    .iter()
+                                    .filter_map(|node_index| rdf_data.node_data.get_node_by_index(*node_index))
+                                    .map(|(iri, _)| iri.as_ref())
+                                    .collect();
+                                if let Some(first) = names.first().cloned() {
+                                    names.push(first);
+                                }
+                                let text = names.join(" \u{2192} ");
+                                if ui
+                                    .selectable_label(cycle_search.selected == Some(index), text)
+                                    .on_hover_text("Show and select the cycle in the visual graph")
+                                    .clicked()
+                                {
+                                    cycle_search.selected = Some(index);
+                                    cycle_to_show = Some(cycle.clone());
+                                }
+                            }
+                        });
+                    }
+                }
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        if search {
+            self.search_cycles();
+        }
+        if let Some(cycle) = cycle_to_show {
+            self.show_and_select_nodes(&cycle);
+            self.display_type = DisplayType::Graph;
+        }
+        if close {
+            self.cycle_search = None;
+        }
+    }
+}
+
+/// Combo box with all predicates used for references, the None value has its own label
+pub fn predicate_combo(
+    ui: &mut egui::Ui,
+    label: &str,
+    predicate: &mut Option<IriIndex>,
+    none_label: &str,
+    label_context: &LabelContext,
+    rdf_data: &RdfData,
+    type_index: &TypeInstanceIndex,
+) {
+    let node_data = &rdf_data.node_data;
+    let selected_text = match predicate {
+        Some(predicate_index) => node_data
+            .predicate_display(*predicate_index, label_context, &node_data.indexers)
+            .as_str()
+            .to_string(),
+        None => none_label.to_string(),
+    };
+    let reference_predicates: BTreeSet<IriIndex> = type_index
+        .types
+        .values()
+        .flat_map(|type_data| type_data.references.keys().cloned())
+        .collect();
+    let mut predicates: Vec<(IriIndex, String)> = reference_predicates
+        .into_iter()
+        .map(|predicate_index| {
+            let label = node_data
+                .predicate_display(predicate_index, label_context, &node_data.indexers)
+                .as_str()
+                .to_string();
+            (predicate_index, label)
+        })
+        .collect();
+    predicates.sort_by(|a, b| a.1.cmp(&b.1));
+    egui::ComboBox::from_label(label)
+        .width(200.0)
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(predicate, None, none_label);
+            for (predicate_index, predicate_label) in predicates {
+                ui.selectable_value(predicate, Some(predicate_index), predicate_label);
+            }
+        });
+}
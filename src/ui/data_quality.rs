@@ -0,0 +1,131 @@
+use egui::{Color32, RichText, ScrollArea};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        data_quality::{DataQualityReport, IssueSeverity},
+        skos_validation::validate_skos,
+    },
+    uistate::{DisplayType, SystemMessage},
+};
+
+impl RdfGlanceApp {
+    pub fn validate_skos(&mut self) {
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let issues = validate_skos(&rdf_data.node_data, &rdf_data.prefix_manager);
+            if issues.is_empty() {
+                self.system_message = SystemMessage::Info("No SKOS issues found".to_string());
+                return;
+            }
+            self.data_quality = Some(DataQualityReport::new("SKOS validation", issues));
+        }
+    }
+
+    pub fn show_data_quality(&mut self, ui: &mut egui::Ui) {
+        let Some(report) = &self.data_quality else {
+            return;
+        };
+        let mut close = false;
+        let mut save = false;
+        let mut node_to_browse: Option<IriIndex> = None;
+        egui::Window::new(format!("Data Quality: {}", report.title))
+            .collapsible(false)
+            .resizable(true)
+            .default_width(800.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "Errors: {}, Warnings: {}, Info: {}",
+                    report.count(IssueSeverity::Error),
+                    report.count(IssueSeverity::Warning),
+                    report.count(IssueSeverity::Info)
+                ));
+                ui.separator();
+                ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for (check, count) in report.checks() {
+                        egui::CollapsingHeader::new(format!("{} ({})", check, count))
+                            .id_salt(("data_quality", check))
+                            .show(ui, |ui| {
+                                for issue in report.issues.iter().filter(|issue| issue.check == check) {
+                                    ui.horizontal(|ui| {
+                                        ui.label(severity_text(issue.severity, ui.visuals().dark_mode));
+                                        ui.label(&issue.message);
+                                        if let Some(node_index) = issue.nodes.first()
+                                            && ui.small_button("Browse").clicked()
+                                        {
+                                            node_to_browse = Some(*node_index);
+                                        }
+                                    });
+                                }
+                            });
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Save as Text").clicked() {
+                        save = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if save {
+            self.save_data_quality();
+        }
+        if let Some(node_to_browse) = node_to_browse {
+            self.show_object_by_index(node_to_browse, true);
+            self.display_type = DisplayType::Browse;
+        }
+        if close {
+            self.data_quality = None;
+        }
+    }
+
+    fn save_data_quality(&mut self) {
+        let Some(report) = &self.data_quality else {
+            return;
+        };
+        let text = report.to_text();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter("Text", &["txt"])
+                .set_file_name("data_quality.txt")
+                .save_file()
+                && let Err(e) = std::fs::write(&path, text)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save data quality report: {}", e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = web_download("data_quality.txt", text.as_bytes());
+        }
+    }
+}
+
+fn severity_text(severity: IssueSeverity, dark_mode: bool) -> RichText {
+    let color = match severity {
+        IssueSeverity::Error => Color32::from_rgb(220, 50, 50),
+        IssueSeverity::Warning => {
+            if dark_mode {
+                Color32::YELLOW
+            } else {
+                Color32::from_rgb(180, 120, 0)
+            }
+        }
+        IssueSeverity::Info => {
+            if dark_mode {
+                Color32::LIGHT_BLUE
+            } else {
+                Color32::DARK_BLUE
+            }
+        }
+    };
+    RichText::new(severity.label()).color(color)
+}
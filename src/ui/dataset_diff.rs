@@ -0,0 +1,177 @@
+use egui::{RichText, ScrollArea};
+use egui_extras::{Column, TableBuilder};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::dataset_diff::{DatasetDiff, DiffStatus},
+    uistate::{DisplayType, SystemMessage},
+};
+
+impl RdfGlanceApp {
+    /// Loads a second file (e.g. the previous release) and compares it with the current data
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn compare_with_file_dialog(&mut self) {
+        use crate::{
+            domain::{NodeData, RdfData, prefix_manager::PrefixManager},
+            integration::rdfwrap::RDFWrap,
+        };
+
+        let Some(path) = rfd::FileDialog::new()
+            .add_filter("RDF Files", &["ttl", "rdf", "xml", "nt", "trig", "nq", "jsonld"])
+            .pick_file()
+        else {
+            return;
+        };
+        let mut compared = RdfData {
+            node_data: NodeData::new(),
+            prefix_manager: PrefixManager::new(),
+        };
+        let language_filter = self.config.language_filter();
+        if let Err(e) = RDFWrap::load_file(&path, &mut compared, &language_filter, None) {
+            self.system_message = SystemMessage::Error(format!("Can not load {}: {}", path.display(), e));
+            return;
+        }
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let source = path.file_name().map_or(path.display().to_string(), |name| name.to_string_lossy().to_string());
+            self.dataset_diff = Some(DatasetDiff::new(&source, &rdf_data, &compared));
+        }
+    }
+
+    pub fn show_dataset_diff(&mut self, ui: &mut egui::Ui) {
+        let Some(dataset_diff) = &mut self.dataset_diff else {
+            return;
+        };
+        let mut close = false;
+        let mut node_to_browse: Option<IriIndex> = None;
+        let mut nodes_to_visualize: Option<Vec<IriIndex>> = None;
+        egui::Window::new(format!("Compare with {}", dataset_diff.source))
+            .collapsible(true)
+            .resizable(true)
+            .default_width(800.0)
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "Nodes added: {}, removed: {}, changed: {}. Triples added: {}, removed: {}",
+                    dataset_diff.count(DiffStatus::Added),
+                    dataset_diff.count(DiffStatus::Removed),
+                    dataset_diff.count(DiffStatus::Changed),
+                    dataset_diff.added_triples,
+                    dataset_diff.removed_triples
+                ));
+                ui.horizontal(|ui| {
+                    ui.label("Status:");
+                    ui.selectable_value(&mut dataset_diff.status_filter, None, "all");
+                    for status in [DiffStatus::Added, DiffStatus::Removed, DiffStatus::Changed] {
+                        ui.selectable_value(
+                            &mut dataset_diff.status_filter,
+                            Some(status),
+                            RichText::new(status.label()).color(status.color()),
+                        );
+                    }
+                    ui.label("IRI:");
+                    ui.text_edit_singleline(&mut dataset_diff.iri_filter);
+                });
+                ui.separator();
+                let visible_rows: Vec<usize> = (0..dataset_diff.nodes.len())
+                    .filter(|row| dataset_diff.is_visible(&dataset_diff.nodes[*row]))
+                    .collect();
+                let text_height = egui::TextStyle::Body.resolve(ui.style()).size + 4.0;
+                ui.push_id("dataset_diff_nodes", |ui| {
+                    TableBuilder::new(ui)
+                        .striped(true)
+                        .max_scroll_height(250.0)
+                        .column(Column::auto().at_least(70.0))
+                        .column(Column::remainder().at_least(300.0))
+                        .column(Column::auto().at_least(50.0))
+                        .column(Column::auto().at_least(50.0))
+                        .header(text_height, |mut header| {
+                            header.col(|ui| {
+                                ui.strong("Status");
+                            });
+                            header.col(|ui| {
+                                ui.strong("IRI");
+                            });
+                            header.col(|ui| {
+                                ui.strong("+");
+                            });
+                            header.col(|ui| {
+                                ui.strong("-");
+                            });
+                        })
+                        .body(|body| {
+                            body.rows(text_height, visible_rows.len(), |mut row| {
+                                let node_row = visible_rows[row.index()];
+                                let node_diff = &dataset_diff.nodes[node_row];
+                                row.col(|ui| {
+                                    ui.label(RichText::new(node_diff.status.label()).color(node_diff.status.color()));
+                                });
+                                row.col(|ui| {
+                                    if ui
+                                        .selectable_label(dataset_diff.selected == Some(node_row), &node_diff.iri)
+                                        .clicked()
+                                    {
+                                        dataset_diff.selected = Some(node_row);
+                                    }
+                                });
+                                row.col(|ui| {
+                                    ui.label(node_diff.added.len().to_string());
+                                });
+                                row.col(|ui| {
+                                    ui.label(node_diff.removed.len().to_string());
+                                });
+                            });
+                        });
+                });
+                if let Some(node_diff) = dataset_diff.selected.and_then(|row| dataset_diff.nodes.get(row)) {
+                    ui.separator();
+                    ui.horizontal(|ui| {
+                        ui.strong(&node_diff.iri);
+                        if let Some(node_index) = node_diff.node_index
+                            && ui.button("Browse").clicked()
+                        {
+                            node_to_browse = Some(node_index);
+                        }
+                    });
+                    ScrollArea::vertical().id_salt("dataset_diff_triples").max_height(200.0).show(ui, |ui| {
+                        for triple in node_diff.removed.iter() {
+                            ui.label(RichText::new(format!("- {}", triple)).color(DiffStatus::Removed.color()));
+                        }
+                        for triple in node_diff.added.iter() {
+                            ui.label(RichText::new(format!("+ {}", triple)).color(DiffStatus::Added.color()));
+                        }
+                    });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Show in Graph")
+                        .on_hover_text("Visualize the listed nodes that are part of the current data")
+                        .clicked()
+                    {
+                        nodes_to_visualize = Some(
+                            visible_rows
+                                .iter()
+                                .filter_map(|row| dataset_diff.nodes[*row].node_index)
+                                .collect(),
+                        );
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if let Some(node_to_browse) = node_to_browse {
+            self.show_object_by_index(node_to_browse, true);
+            self.display_type = DisplayType::Browse;
+        }
+        if let Some(nodes_to_visualize) = nodes_to_visualize {
+            if nodes_to_visualize.is_empty() {
+                self.system_message = SystemMessage::Info("No listed node is part of the current data".to_string());
+            } else {
+                self.visualize_nodes(&nodes_to_visualize);
+            }
+        }
+        if close {
+            self.dataset_diff = None;
+        }
+    }
+}
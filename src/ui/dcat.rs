@@ -0,0 +1,162 @@
+use egui::ScrollArea;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::dcat::DcatCatalog,
+    uistate::{DisplayType, ImportFormat, SystemMessage},
+};
+
+// longer descriptions are cut in the dataset list
+const MAX_DESCRIPTION_LEN: usize = 300;
+
+#[derive(Default)]
+pub struct DcatBrowser {
+    pub catalog: DcatCatalog,
+    pub filter: String,
+    // index in catalog.catalogs, None for all datasets
+    pub selected_catalog: Option<usize>,
+}
+
+impl RdfGlanceApp {
+    pub fn open_dcat_browser(&mut self) {
+        let catalog = if let Ok(rdf_data) = self.rdf_data.read() {
+            DcatCatalog::new(
+                &rdf_data.node_data,
+                &rdf_data.prefix_manager,
+                self.ui_state.display_language,
+            )
+        } else {
+            return;
+        };
+        if catalog.is_empty() {
+            self.system_message = SystemMessage::Info(
+                "No DCAT datasets found. Datasets are nodes of type dcat:Dataset or with dcat:distribution".to_string(),
+            );
+            return;
+        }
+        self.dcat_browser = Some(DcatBrowser {
+            catalog,
+            ..Default::default()
+        });
+    }
+
+    pub fn show_dcat_browser(&mut self, ui: &mut egui::Ui) {
+        let Some(dcat_browser) = &mut self.dcat_browser else {
+            return;
+        };
+        let mut close = false;
+        let mut node_to_browse: Option<IriIndex> = None;
+        let mut url_to_load: Option<(String, ImportFormat)> = None;
+        egui::Window::new("DCAT Catalog")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(700.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                let catalog = &dcat_browser.catalog;
+                ui.horizontal(|ui| {
+                    if !catalog.catalogs.is_empty() {
+                        ui.label("Catalog");
+                        let selected_text = dcat_browser
+                            .selected_catalog
+                            .map(|selected| catalog.catalogs[selected].title.as_str())
+                            .unwrap_or("<All>");
+                        egui::ComboBox::from_id_salt("dcat_catalog")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut dcat_browser.selected_catalog, None, "<All>");
+                                for (catalog_pos, catalog_entry) in catalog.catalogs.iter().enumerate() {
+                                    ui.selectable_value(
+                                        &mut dcat_browser.selected_catalog,
+                                        Some(catalog_pos),
+                                        &catalog_entry.title,
+                                    );
+                                }
+                            });
+                    }
+                    ui.label("Filter");
+                    ui.text_edit_singleline(&mut dcat_browser.filter);
+                });
+                let filter = dcat_browser.filter.to_lowercase();
+                let datasets: Vec<_> = catalog
+                    .datasets
+                    .iter()
+                    .filter(|dataset| {
+                        dcat_browser.selected_catalog.is_none() || dataset.catalog == dcat_browser.selected_catalog
+                    })
+                    .filter(|dataset| filter.is_empty() || dataset.title.to_lowercase().contains(&filter))
+                    .collect();
+                ui.label(format!("{} of {} datasets", datasets.len(), catalog.datasets.len()));
+                ui.separator();
+                ScrollArea::vertical().max_height(500.0).show(ui, |ui| {
+                    for dataset in datasets {
+                        egui::CollapsingHeader::new(format!(
+                            "{} ({} distributions)",
+                            dataset.title,
+                            dataset.distributions.len()
+                        ))
+                        .id_salt(("dcat_dataset", dataset.node_index))
+                        .show(ui, |ui| {
+                            if ui.link("Browse dataset").clicked() {
+                                node_to_browse = Some(dataset.node_index);
+                            }
+                            if let Some(description) = &dataset.description {
+                                if description.chars().count() > MAX_DESCRIPTION_LEN {
+                                    let short: String = description.chars().take(MAX_DESCRIPTION_LEN).collect();
+                                    ui.label(format!("{}…", short)).on_hover_text(description);
+                                } else {
+                                    ui.label(description);
+                                }
+                            }
+                            egui::Grid::new(("dcat_distributions", dataset.node_index))
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for distribution in dataset.distributions.iter() {
+                                        let title = distribution.title.as_deref().unwrap_or("Distribution");
+                                        if ui.link(title).clicked() {
+                                            node_to_browse = Some(distribution.node_index);
+                                        }
+                                        ui.label(distribution.format.as_deref().unwrap_or(""));
+                                        match &distribution.url {
+                                            Some(url) => {
+                                                ui.hyperlink_to(url, url);
+                                            }
+                                            None => {
+                                                ui.label("");
+                                            }
+                                        }
+                                        let loadable = distribution.url.is_some() && distribution.import_format.is_some();
+                                        if ui
+                                            .add_enabled(loadable, egui::Button::new("Load"))
+                                            .on_hover_text("Load the distribution into the current data")
+                                            .on_disabled_hover_text("Only distributions in RDF formats can be loaded")
+                                            .clicked()
+                                            && let (Some(url), Some(import_format)) =
+                                                (&distribution.url, distribution.import_format)
+                                        {
+                                            url_to_load = Some((url.clone(), import_format));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                });
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        if let Some(node_to_browse) = node_to_browse {
+            self.display_type = DisplayType::Browse;
+            self.show_object_by_index(node_to_browse, true);
+        }
+        if let Some((url, import_format)) = url_to_load {
+            self.load_ttl_from_url(&url, import_format, ui.visuals().dark_mode);
+            close = true;
+        }
+        if close {
+            self.dcat_browser = None;
+        }
+    }
+}
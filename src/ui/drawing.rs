@@ -1,6 +1,6 @@
 use eframe::egui::{Color32, Painter, Pos2, Stroke};
 use egui::{
-    epaint::{CubicBezierShape, EllipseShape, QuadraticBezierShape, TextShape}, text::LayoutJob, Align2, FontId, Rect, Shape, StrokeKind, Vec2
+    epaint::{CubicBezierShape, EllipseShape, QuadraticBezierShape, TextShape, Vertex}, text::LayoutJob, Align2, FontId, Mesh, Rect, Shape, StrokeKind, TextureId, Vec2
 };
 
 use crate::{
@@ -10,6 +10,8 @@ use crate::{
 use crate::domain::graph_styles::{ArrowLocation, ArrowStyle, IconPosition, LabelPosition, LineStyle, NodeShape, NodeSize};
 
 const POS_SPACE: f32 = 3.0;
+const CIRCLE_IMAGE_SEGMENTS: u32 = 32;
+const COMPOSITE_OUTLINE_GAP: f32 = 3.0;
 
 pub fn draw_edge<F>(
     painter: &Painter,
@@ -21,6 +23,7 @@ pub fn draw_edge<F>(
     shape_to: NodeShape,
     edge_style: &EdgeStyle,
     label_cb: F,
+    show_label: bool,
     faded: bool,
     bezier_distance: f32,
     visuals: &egui::Visuals,
@@ -90,92 +93,54 @@ pub fn draw_edge<F>(
 
     // Draw arrow (line + head)
     let stroke = Stroke::new(edge_style.width, fade_color(edge_style.color, faded));
-    match edge_style.line_style {
-        LineStyle::Solid =>{
-            if bezier_distance != 0.0 {
-                let middle = (edge_from + edge_to.to_vec2()) / 2.0;
-                let ctrl_pos = middle + unit.rot90() * bezier_distance;
-                painter.add(Shape::QuadraticBezier(
-                    QuadraticBezierShape::from_points_stroke(
-                        [edge_from, ctrl_pos, edge_to],
-                        false,
-                        Color32::TRANSPARENT,
-                        stroke,
-                    ),
-                ));
-                arrow_unit = (edge_to - ctrl_pos).normalized();
-            } else {
-                painter.line_segment([edge_from, edge_to], stroke);
-            }
-        }
-        LineStyle::Dashed => {
-            painter.add(Shape::dashed_line(
-                &[edge_from, edge_to],
-                stroke,
-                edge_style.line_gap,
-                edge_style.width * 5.0,
-            ));
-        }
-        LineStyle::Dotted => {
-            painter.add(Shape::dotted_line(
-                &[edge_from, edge_to],
-                fade_color(edge_style.color, faded),
-                edge_style.line_gap,
-                edge_style.width,
+    let mut start_unit = -unit;
+    if bezier_distance != 0.0 {
+        let middle = (edge_from + edge_to.to_vec2()) / 2.0;
+        let ctrl_pos = middle + unit.rot90() * bezier_distance;
+        arrow_unit = (edge_to - ctrl_pos).normalized();
+        start_unit = (edge_from - ctrl_pos).normalized();
+        if edge_style.line_style == LineStyle::Solid {
+            painter.add(Shape::QuadraticBezier(
+                QuadraticBezierShape::from_points_stroke(
+                    [edge_from, ctrl_pos, edge_to],
+                    false,
+                    Color32::TRANSPARENT,
+                    stroke,
+                ),
             ));
+        } else {
+            let points = QuadraticBezierShape::from_points_stroke(
+                [edge_from, ctrl_pos, edge_to],
+                false,
+                Color32::TRANSPARENT,
+                stroke,
+            )
+            .flatten(Some(0.5));
+            draw_edge_line(painter, &points, edge_style, faded);
         }
+    } else {
+        draw_edge_line(painter, &[edge_from, edge_to], edge_style, faded);
     }
 
-    if !matches!(edge_style.arrow_location, ArrowLocation::None) {
-        // Draw arrowhead
-        let arrow_size = edge_style.arrow_size; // Size of the arrowhead
-        let arrow_angle = std::f32::consts::PI / 6.0; // 30 degrees
-
-        let arrow_pos = match edge_style.arrow_location {
-            ArrowLocation::Middle => (edge_from + edge_to.to_vec2()) / 2.0,
-            _ => edge_to,
-        };
-
-        // Rotate vector by ±arrow_angle to get arrowhead points
-        let cos_theta = arrow_angle.cos();
-        let sin_theta = arrow_angle.sin();
-
-        let left = arrow_pos
-            - arrow_size
-                * Vec2::new(
-                    cos_theta * arrow_unit.x - sin_theta * arrow_unit.y,
-                    sin_theta * arrow_unit.x + cos_theta * arrow_unit.y,
-                );
-        let right = arrow_pos
-            - arrow_size
-                * Vec2::new(
-                    cos_theta * arrow_unit.x + sin_theta * arrow_unit.y,
-                    -sin_theta * arrow_unit.x + cos_theta * arrow_unit.y,
-                );
-
-        // Draw arrowhead lines
-        match edge_style.target_style {
-            ArrowStyle::Arrow => {
-                painter.line_segment([arrow_pos, left], stroke);
-                painter.line_segment([arrow_pos, right], stroke);
-            }
-            ArrowStyle::ArrorTriangle => {
-                painter.line_segment([arrow_pos, left], stroke);
-                painter.line_segment([arrow_pos, right], stroke);
-                painter.line_segment([left, right], stroke);
-            }
-            ArrowStyle::ArrorFilled => {
-                let shape = Shape::convex_polygon(vec![arrow_pos, left, right], fade_color(edge_style.color, faded), Stroke::NONE);
-                painter.add(shape);
-            }
+    match edge_style.arrow_location {
+        ArrowLocation::None => {}
+        ArrowLocation::Middle => {
+            draw_arrow_head(painter, (edge_from + edge_to.to_vec2()) / 2.0, arrow_unit, edge_style, faded);
+        }
+        ArrowLocation::Target => {
+            draw_arrow_head(painter, edge_to, arrow_unit, edge_style, faded);
+        }
+        ArrowLocation::Both => {
+            draw_arrow_head(painter, edge_to, arrow_unit, edge_style, faded);
+            draw_arrow_head(painter, edge_from, start_unit, edge_style, faded);
         }
     }
 
-    if let Some(edge_font) = &edge_style.edge_font {
-        let line_midle = (edge_from + edge_to.to_vec2()) / 2.0;
+    if show_label && let Some(edge_font) = &edge_style.edge_font && edge_font.label_visible {
+        let line_point = edge_from + (edge_to - edge_from) * edge_font.label_position.fraction();
         let label_font = FontId::proportional(edge_font.font_size);
         let unit_ort = -unit.rot90() * (edge_font.font_size + bezier_distance/2.0);
-        let label_pos = line_midle + unit_ort;
+        let label_pos = line_point + unit_ort;
         let label = label_cb();
         let mut job = LayoutJob::default();
         job.append(
@@ -194,12 +159,15 @@ pub fn draw_edge<F>(
         } else {
             unit
         };
-        let angle = unit_adapted.angle();
+        let angle = if edge_font.label_rotate { unit_adapted.angle() } else { 0.0 };
         let gallay_center = galley.rect.center();
         let gallay_center = Vec2::new(
             gallay_center.x * angle.cos() - gallay_center.y * angle.sin(),
             gallay_center.x * angle.sin() + gallay_center.y * angle.cos(),
         );
+        if edge_font.label_background {
+            draw_label_background(painter, label_pos, galley.rect.size(), angle, visuals, faded);
+        }
         painter.add(Shape::Text(
             TextShape::new(label_pos - gallay_center, galley, Color32::BLACK).with_angle(angle),
         ));
@@ -220,6 +188,76 @@ pub fn draw_edge<F>(
     }
 }
 
+/// Draws the edge line along the points with the line pattern of the edge style
+pub fn draw_edge_line(painter: &Painter, points: &[Pos2], edge_style: &EdgeStyle, faded: bool) {
+    let color = fade_color(edge_style.color, faded);
+    match edge_style.line_style {
+        LineStyle::Solid => {
+            painter.add(Shape::line(points.to_vec(), Stroke::new(edge_style.width, color)));
+        }
+        LineStyle::Dashed => {
+            painter.add(Shape::dashed_line(
+                points,
+                Stroke::new(edge_style.width, color),
+                edge_style.line_gap,
+                edge_style.width * 5.0,
+            ));
+        }
+        LineStyle::Dotted => {
+            painter.add(Shape::dotted_line(points, color, edge_style.line_gap, edge_style.width));
+        }
+    }
+}
+
+/**
+ * Draws the arrow head of the edge style with the tip at arrow_pos.
+ * unit is the direction of the edge at the tip.
+ */
+pub fn draw_arrow_head(painter: &Painter, arrow_pos: Pos2, unit: Vec2, edge_style: &EdgeStyle, faded: bool) {
+    let arrow_size = edge_style.arrow_size; // Size of the arrowhead
+    let arrow_angle = std::f32::consts::PI / 6.0; // 30 degrees
+
+    // Rotate vector by ±arrow_angle to get arrowhead points
+    let cos_theta = arrow_angle.cos();
+    let sin_theta = arrow_angle.sin();
+
+    let left = arrow_pos
+        - arrow_size
+            * Vec2::new(
+                cos_theta * unit.x - sin_theta * unit.y,
+                sin_theta * unit.x + cos_theta * unit.y,
+            );
+    let right = arrow_pos
+        - arrow_size
+            * Vec2::new(
+                cos_theta * unit.x + sin_theta * unit.y,
+                -sin_theta * unit.x + cos_theta * unit.y,
+            );
+    // the diamond has the double length of the arrow
+    let back = arrow_pos - unit * (2.0 * arrow_size * cos_theta);
+
+    let color = fade_color(edge_style.color, faded);
+    let stroke = Stroke::new(edge_style.width, color);
+    match edge_style.target_style {
+        ArrowStyle::Arrow => {
+            painter.line_segment([arrow_pos, left], stroke);
+            painter.line_segment([arrow_pos, right], stroke);
+        }
+        ArrowStyle::ArrorTriangle => {
+            painter.add(Shape::closed_line(vec![arrow_pos, left, right], stroke));
+        }
+        ArrowStyle::ArrorFilled => {
+            painter.add(Shape::convex_polygon(vec![arrow_pos, left, right], color, Stroke::NONE));
+        }
+        ArrowStyle::Diamond => {
+            painter.add(Shape::convex_polygon(vec![arrow_pos, left, back, right], color, Stroke::NONE));
+        }
+        ArrowStyle::DiamondOpen => {
+            painter.add(Shape::closed_line(vec![arrow_pos, left, back, right], stroke));
+        }
+    }
+}
+
 pub fn draw_self_edge<F>(
     painter: &Painter,
     point: Pos2,
@@ -229,6 +267,7 @@ pub fn draw_self_edge<F>(
     edge_style: &EdgeStyle,
     faded: bool,
     label_cb: F,
+    show_label: bool,
     visuals: &egui::Visuals,
 ) where
 F: Fn() -> String,
@@ -244,58 +283,29 @@ F: Fn() -> String,
     let ctrl_pos_distance = 100.0;
     let ctrl_pos1 = point + direction_1 * (radius + ctrl_pos_distance);
     let ctrl_pos2 = point +direction_2 * (radius + ctrl_pos_distance);
-    painter.add(Shape::CubicBezier(
-        CubicBezierShape::from_points_stroke([pos1, ctrl_pos1, ctrl_pos2, pos2], false, Color32::TRANSPARENT, stroke),
-    ));
-
-    if !matches!(edge_style.arrow_location, ArrowLocation::None) {
-        // Draw arrowhead
-        let arrow_size = edge_style.arrow_size; // Size of the arrowhead
-        let arrow_angle = std::f32::consts::PI / 6.0; // 30 degrees
-
-        let arrow_pos = match edge_style.arrow_location {
-            ArrowLocation::Middle => bezier_middle_point(pos1, ctrl_pos1, ctrl_pos2, pos2),
-            _ => pos2,
-        };
-
-        let unit = (pos2 - ctrl_pos2).normalized();
-
-        // Rotate vector by ±arrow_angle to get arrowhead points
-        let cos_theta = arrow_angle.cos();
-        let sin_theta = arrow_angle.sin();
-
-        let left = arrow_pos
-            - arrow_size
-                * Vec2::new(
-                    cos_theta * unit.x - sin_theta * unit.y,
-                    sin_theta * unit.x + cos_theta * unit.y,
-                );
-        let right = arrow_pos
-            - arrow_size
-                * Vec2::new(
-                    cos_theta * unit.x + sin_theta * unit.y,
-                    -sin_theta * unit.x + cos_theta * unit.y,
-                );
+    let curve = CubicBezierShape::from_points_stroke([pos1, ctrl_pos1, ctrl_pos2, pos2], false, Color32::TRANSPARENT, stroke);
+    if edge_style.line_style == LineStyle::Solid {
+        painter.add(Shape::CubicBezier(curve));
+    } else {
+        draw_edge_line(painter, &curve.flatten(Some(0.5)), edge_style, faded);
+    }
 
-        // Draw arrowhead lines
-        match edge_style.target_style {
-            ArrowStyle::Arrow => {
-                painter.line_segment([arrow_pos, left], stroke);
-                painter.line_segment([arrow_pos, right], stroke);
-            }
-            ArrowStyle::ArrorTriangle => {
-                painter.line_segment([arrow_pos, left], stroke);
-                painter.line_segment([arrow_pos, right], stroke);
-                painter.line_segment([left, right], stroke);
-            }
-            ArrowStyle::ArrorFilled => {
-                let shape = Shape::convex_polygon(vec![arrow_pos, left, right], fade_color(edge_style.color, faded), Stroke::NONE);
-                painter.add(shape);
-            }
+    match edge_style.arrow_location {
+        ArrowLocation::None => {}
+        ArrowLocation::Middle => {
+            let arrow_pos = bezier_middle_point(pos1, ctrl_pos1, ctrl_pos2, pos2);
+            draw_arrow_head(painter, arrow_pos, (pos2 - ctrl_pos2).normalized(), edge_style, faded);
+        }
+        ArrowLocation::Target => {
+            draw_arrow_head(painter, pos2, (pos2 - ctrl_pos2).normalized(), edge_style, faded);
+        }
+        ArrowLocation::Both => {
+            draw_arrow_head(painter, pos2, (pos2 - ctrl_pos2).normalized(), edge_style, faded);
+            draw_arrow_head(painter, pos1, (pos1 - ctrl_pos1).normalized(), edge_style, faded);
         }
     }
 
-    if let Some(edge_font) = &edge_style.edge_font {
+    if show_label && let Some(edge_font) = &edge_style.edge_font && edge_font.label_visible {
         let curve_midle = bezier_middle_point(pos1, ctrl_pos1, ctrl_pos2, pos2);
         let label_font = FontId::proportional(edge_font.font_size);
         let label = label_cb();
@@ -310,13 +320,86 @@ F: Fn() -> String,
             },
         );
         let galley = painter.layout_job(job);
-        // change the direction if pointing to left side to ensure the text is left to right
+        if edge_font.label_background {
+            draw_label_background(painter, curve_midle + galley.rect.center().to_vec2(), galley.rect.size(), 0.0, visuals, faded);
+        }
         painter.add(Shape::Text(
             TextShape::new(curve_midle, galley, Color32::BLACK),
         ));
     }
 }
 
+/**
+ * Draws a filled box (rotated by angle around its center) behind an edge label.
+ */
+fn draw_label_background(painter: &Painter, center: Pos2, size: Vec2, angle: f32, visuals: &egui::Visuals, faded: bool) {
+    let half = size / 2.0 + Vec2::splat(2.0);
+    let (sin, cos) = angle.sin_cos();
+    let corners = [
+        Vec2::new(-half.x, -half.y),
+        Vec2::new(half.x, -half.y),
+        Vec2::new(half.x, half.y),
+        Vec2::new(-half.x, half.y),
+    ]
+    .map(|v| center + Vec2::new(v.x * cos - v.y * sin, v.x * sin + v.y * cos));
+    let fill = fade_color(visuals.extreme_bg_color, faded);
+    painter.add(Shape::convex_polygon(
+        corners.to_vec(),
+        fill,
+        Stroke::new(1.0, fade_color(visuals.weak_text_color(), faded)),
+    ));
+}
+
+/**
+ * Draws parallel edges as one thick straight edge with a count badge in the middle.
+ * Returns the rect of the badge for click detection.
+ */
+pub fn draw_edge_bundle(
+    painter: &Painter,
+    point_from: Pos2,
+    size_from: Vec2,
+    shape_from: NodeShape,
+    point_to: Pos2,
+    size_to: Vec2,
+    shape_to: NodeShape,
+    count: usize,
+    visuals: &egui::Visuals,
+) -> Rect {
+    let edge_style = EdgeStyle {
+        color: visuals.weak_text_color(),
+        width: 4.0,
+        arrow_location: ArrowLocation::None,
+        ..EdgeStyle::default()
+    };
+    draw_edge(
+        painter,
+        point_from,
+        size_from,
+        shape_from,
+        point_to,
+        size_to,
+        shape_to,
+        &edge_style,
+        String::new,
+        false,
+        false,
+        0.0,
+        visuals,
+    );
+    let middle = point_from + (point_to - point_from) * 0.5;
+    let text = painter.layout_no_wrap(count.to_string(), FontId::proportional(12.0), visuals.strong_text_color());
+    let badge_rect = Rect::from_center_size(middle, text.size() + Vec2::new(10.0, 4.0));
+    painter.rect(
+        badge_rect,
+        badge_rect.height() / 2.0,
+        visuals.extreme_bg_color,
+        Stroke::new(1.0, visuals.weak_text_color()),
+        StrokeKind::Outside,
+    );
+    painter.galley(middle - text.size() / 2.0, text, visuals.strong_text_color());
+    badge_rect
+}
+
 pub fn bezier_middle_point(pos1: Pos2, ctrl_pos1: Pos2, ctrl_pos2: Pos2, pos2: Pos2) -> Pos2 {
     let t = 0.5;
     let u = 1.0 - t;
@@ -338,6 +421,65 @@ pub fn fade_color(color: Color32, fade: bool) -> Color32 {
     }
 }
 
+/// Texture (photo of a node) clipped to a circle
+pub fn draw_circle_image(painter: &Painter, center: Pos2, radius: f32, texture_id: TextureId, faded: bool) {
+    let tint = fade_color(Color32::WHITE, faded);
+    let mut mesh = Mesh::with_texture(texture_id);
+    mesh.vertices.push(Vertex {
+        pos: center,
+        uv: Pos2::new(0.5, 0.5),
+        color: tint,
+    });
+    for segment in 0..=CIRCLE_IMAGE_SEGMENTS {
+        let direction = Vec2::angled(segment as f32 * std::f32::consts::TAU / CIRCLE_IMAGE_SEGMENTS as f32);
+        mesh.vertices.push(Vertex {
+            pos: center + direction * radius,
+            uv: Pos2::new(0.5, 0.5) + direction * 0.5,
+            color: tint,
+        });
+        if segment > 0 {
+            mesh.add_triangle(0, segment, segment + 1);
+        }
+    }
+    painter.add(Shape::mesh(mesh));
+}
+
+/// Second outline of a node that contains other nodes (flattened blank node trees)
+/// Number of nodes of a collapsed cluster drawn at the top right corner of the meta-node
+pub fn draw_cluster_badge(painter: &Painter, node_rect: Rect, cluster_size: u32, visuals: &egui::Visuals) {
+    let text = painter.layout_no_wrap(cluster_size.to_string(), FontId::proportional(11.0), visuals.strong_text_color());
+    let badge_rect = Rect::from_center_size(node_rect.right_top(), text.size() + Vec2::new(8.0, 2.0));
+    painter.rect(
+        badge_rect,
+        badge_rect.height() / 2.0,
+        visuals.extreme_bg_color,
+        Stroke::new(1.0, visuals.strong_text_color()),
+        StrokeKind::Outside,
+    );
+    painter.galley(badge_rect.center() - text.size() / 2.0, text, visuals.strong_text_color());
+}
+
+pub fn draw_composite_outline(painter: &Painter, node_rect: Rect, node_shape: NodeShape, color: Color32) {
+    let stroke = Stroke::new(1.5, color);
+    match node_shape {
+        NodeShape::Circle => {
+            painter.circle_stroke(node_rect.center(), node_rect.width() / 2.0 + COMPOSITE_OUTLINE_GAP, stroke);
+        }
+        NodeShape::Ellipse => {
+            painter.add(egui::Shape::Ellipse(EllipseShape {
+                center: node_rect.center(),
+                radius: node_rect.size() / 2.0 + Vec2::splat(COMPOSITE_OUTLINE_GAP),
+                fill: Color32::TRANSPARENT,
+                stroke,
+                angle: 0.0,
+            }));
+        }
+        NodeShape::Rect | NodeShape::None => {
+            painter.rect_stroke(node_rect.expand(COMPOSITE_OUTLINE_GAP), 3.0, stroke, StrokeKind::Outside);
+        }
+    }
+}
+
 pub fn fg_color_mode(color: Color32, visuals: &egui::Visuals) -> Color32 {
     if visuals.dark_mode {
         if Color32::BLACK == color {
@@ -411,7 +553,7 @@ pub fn draw_node_label(
             Stroke::NONE
         };
         let node_rect = match type_style.node_size {
-            NodeSize::Fixed => Rect::from_center_size(pos, Vec2::new(type_style.width, type_style.height)),
+            NodeSize::Fixed | NodeSize::Mapped => Rect::from_center_size(pos, Vec2::new(type_style.width, type_style.height)),
             NodeSize::Label => Rect::from_center_size(
                 pos,
                 Vec2::new(
@@ -483,14 +625,26 @@ pub fn draw_node_label(
                     text_pos + Vec2::new(text_rect.width() + icon_style.icon_size / 2.0, text_rect.height() / 2.0)
                 }
             };
-            let icon_font = FontId::proportional(icon_style.icon_size);
-            painter.text(
-                icon_pos,
-                Align2::CENTER_CENTER,
-                icon_style.icon_character.to_string(),
-                icon_font,
-                fade_color(icon_style.icon_color, faded),
-            );
+            if let Some(texture) = icon_style.image.as_ref().and_then(|image| image.texture.as_ref()) {
+                // the image keeps its aspect ratio inside the square of the icon size
+                let texture_size = texture.size_vec2();
+                let image_size = texture_size * (icon_style.icon_size / texture_size.max_elem());
+                painter.image(
+                    texture.id(),
+                    Rect::from_center_size(icon_pos, image_size),
+                    Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                    fade_color(Color32::WHITE, faded),
+                );
+            } else {
+                let icon_font = FontId::proportional(icon_style.icon_size);
+                painter.text(
+                    icon_pos,
+                    Align2::CENTER_CENTER,
+                    icon_style.icon_character.to_string(),
+                    icon_font,
+                    fade_color(icon_style.icon_color, faded),
+                );
+            }
         }
     }
     if num_hidden_references>0 {
@@ -0,0 +1,98 @@
+use std::path::Path;
+
+use crate::RdfGlanceApp;
+
+impl RdfGlanceApp {
+    /**
+     * Loads the files and directories dropped onto the window.
+     * If data are already loaded, the user decides whether they are replaced or the files are merged into them.
+     */
+    pub fn handle_dropped_files(&mut self, ctx: &egui::Context) {
+        let paths: Vec<String> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.as_ref())
+                .filter(|path| path.exists())
+                .filter_map(|path| match path.to_str() {
+                    Some(path) => Some(path.to_string()),
+                    None => {
+                        log::warn!("File dropped path is not valid UTF-8: {:?}", path);
+                        None
+                    }
+                })
+                .collect()
+        });
+        if paths.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            self.load_dropped_files(paths);
+        } else {
+            self.dropped_files = Some(paths);
+        }
+    }
+
+    fn load_dropped_files(&mut self, paths: Vec<String>) {
+        match paths.as_slice() {
+            [path] if !Path::new(path).is_dir() => {
+                let path = path.clone();
+                self.load_ttl(&path, false);
+            }
+            _ => self.load_ttl_paths(paths),
+        }
+    }
+
+    pub fn show_dropped_files_dialog(&mut self, ui: &mut egui::Ui) {
+        let Some(paths) = &self.dropped_files else {
+            return;
+        };
+        let mut replace = false;
+        let mut merge = false;
+        let mut close = false;
+        egui::Window::new("Load Dropped Files")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                egui::ScrollArea::vertical().max_height(150.0).show(ui, |ui| {
+                    for path in paths.iter() {
+                        ui.label(path);
+                    }
+                });
+                ui.label("Data are already loaded.");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Replace Current Data")
+                        .on_hover_text("The loaded data, visual graph and styles are removed")
+                        .clicked()
+                    {
+                        replace = true;
+                    }
+                    if ui
+                        .add_enabled(!self.ui_state.read_only, egui::Button::new("Merge into Current Data"))
+                        .on_hover_text("Nodes with the same IRI are merged, the visual graph is kept")
+                        .on_disabled_hover_text("The data are opened read-only")
+                        .clicked()
+                    {
+                        merge = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if (replace || merge)
+            && let Some(paths) = self.dropped_files.take()
+        {
+            if replace {
+                self.clean_data();
+                self.ui_state.read_only = false;
+            }
+            self.load_dropped_files(paths);
+        } else if close {
+            self.dropped_files = None;
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use egui::Slider;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext, NodeData,
+        edge_metadata::{EdgeMetadataStyle, days_to_date},
+    },
+    uistate::SystemMessage,
+};
+
+impl RdfGlanceApp {
+    pub fn open_edge_metadata(&mut self) {
+        if self.edge_metadata_style.is_none()
+            && let Ok(rdf_data) = self.rdf_data.read()
+        {
+            let edge_metadata_style = EdgeMetadataStyle::new(&rdf_data.node_data, &rdf_data.prefix_manager);
+            if edge_metadata_style.metadata.is_empty() {
+                self.system_message = SystemMessage::Info(
+                    "No edge metadata found. Edges can be described by rdf:Statement nodes with rdf:subject, rdf:predicate and rdf:object".to_string(),
+                );
+                return;
+            }
+            self.edge_metadata_style = Some(edge_metadata_style);
+        }
+        self.ui_state.edge_metadata_window = true;
+    }
+
+    pub fn show_edge_metadata(&mut self, ui: &mut egui::Ui) {
+        if !self.ui_state.edge_metadata_window {
+            return;
+        }
+        let Some(edge_metadata_style) = &mut self.edge_metadata_style else {
+            return;
+        };
+        let mut close = false;
+        let mut reset = false;
+        egui::Window::new("Edge Metadata")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(500.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let node_data = &rdf_data.node_data;
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    let metadata_predicates = edge_metadata_style.metadata.metadata_predicates(node_data);
+                    ui.label(format!(
+                        "{} edges described by rdf:Statement",
+                        edge_metadata_style.metadata.len()
+                    ));
+                    ui.separator();
+                    let numeric_predicates: Vec<IriIndex> = metadata_predicates
+                        .iter()
+                        .filter(|(_, numeric, _)| *numeric)
+                        .map(|(predicate_index, _, _)| *predicate_index)
+                        .collect();
+                    if metadata_combo(
+                        ui,
+                        "Weight",
+                        &mut edge_metadata_style.weight_predicate,
+                        &numeric_predicates,
+                        node_data,
+                        &label_context,
+                    ) {
+                        edge_metadata_style.update_weights(node_data);
+                    }
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut edge_metadata_style.weight_to_width, "Edge width");
+                        ui.checkbox(&mut edge_metadata_style.weight_to_color, "Edge color");
+                    });
+                    if !edge_metadata_style.weights.is_empty() {
+                        ui.label(format!(
+                            "{} edges with weight from {} to {}",
+                            edge_metadata_style.weights.len(),
+                            edge_metadata_style.weight_range.0,
+                            edge_metadata_style.weight_range.1
+                        ));
+                    }
+                    ui.separator();
+                    let date_predicates: Vec<IriIndex> = metadata_predicates
+                        .iter()
+                        .filter(|(_, _, date)| *date)
+                        .map(|(predicate_index, _, _)| *predicate_index)
+                        .collect();
+                    if metadata_combo(
+                        ui,
+                        "Date",
+                        &mut edge_metadata_style.date_predicate,
+                        &date_predicates,
+                        node_data,
+                        &label_context,
+                    ) {
+                        edge_metadata_style.update_dates(node_data);
+                    }
+                    if !edge_metadata_style.dates.is_empty() {
+                        let (min, max) = edge_metadata_style.date_range;
+                        let date_filter = &mut edge_metadata_style.date_filter;
+                        ui.label("Show only edges with date in interval (edges without date are always shown)");
+                        ui.add(
+                            Slider::new(&mut date_filter.0, min..=max)
+                                .text("From")
+                                .custom_formatter(|days, _| days_to_date(days as i64)),
+                        );
+                        ui.add(
+                            Slider::new(&mut date_filter.1, min..=max)
+                                .text("To")
+                                .custom_formatter(|days, _| days_to_date(days as i64)),
+                        );
+                        if date_filter.0 > date_filter.1 {
+                            date_filter.1 = date_filter.0;
+                        }
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Reset").on_hover_text("Display edges without metadata").clicked() {
+                        reset = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if reset {
+            self.edge_metadata_style = None;
+            close = true;
+        }
+        if close {
+            self.ui_state.edge_metadata_window = false;
+        }
+    }
+}
+
+/// Returns true if the selection was changed
+fn metadata_combo(
+    ui: &mut egui::Ui,
+    label: &str,
+    predicate: &mut Option<IriIndex>,
+    predicates: &[IriIndex],
+    node_data: &NodeData,
+    label_context: &LabelContext,
+) -> bool {
+    let predicate_label = |predicate_index: IriIndex| {
+        node_data
+            .predicate_display(predicate_index, label_context, &node_data.indexers)
+            .as_str()
+            .to_string()
+    };
+    let mut changed = false;
+    ui.horizontal(|ui| {
+        ui.label(label);
+        egui::ComboBox::from_id_salt(format!("edge_metadata_{}", label))
+            .selected_text(predicate.map(predicate_label).unwrap_or_else(|| "<None>".to_string()))
+            .show_ui(ui, |ui| {
+                changed |= ui.selectable_value(predicate, None, "<None>").changed();
+                for predicate_index in predicates {
+                    changed |= ui
+                        .selectable_value(predicate, Some(*predicate_index), predicate_label(*predicate_index))
+                        .changed();
+                }
+            });
+    });
+    changed
+}
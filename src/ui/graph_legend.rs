@@ -0,0 +1,190 @@
+use egui::{Align2, Id, Order, Pos2, Rect, Sense, Stroke, StrokeKind, Vec2};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext,
+        graph_styles::{ArrowLocation, EdgeStyle, NodeShape, NodeStyle},
+    },
+    ui::{draw_arrow_head, draw_edge_line},
+    uistate::{
+        graph_legend::{GraphLegend, legend_type},
+        layout::update_edges_groups,
+    },
+};
+
+const LEGEND_MARGIN: f32 = 8.0;
+const SWATCH_SIZE: f32 = 14.0;
+const EDGE_SWATCH_LENGTH: f32 = 28.0;
+
+enum LegendAction {
+    HideType(IriIndex),
+    HidePredicate(IriIndex),
+    ShowPredicate(IriIndex),
+}
+
+impl RdfGlanceApp {
+    fn update_graph_legend(&mut self) {
+        if self.graph_legend.data_epoch == self.visible_nodes.data_epoch {
+            return;
+        }
+        if let Some((rdf_data, layout)) = self.visible_nodes.read_with_data(&self.rdf_data) {
+            self.graph_legend = GraphLegend::new(
+                &layout.nodes,
+                &layout.edges,
+                &rdf_data.node_data,
+                &self.visualization_style,
+                self.visible_nodes.data_epoch,
+            );
+        }
+    }
+
+    /// Legend of the node types and predicates of the visual graph in the top right corner of the graph view
+    pub fn show_graph_legend(&mut self, ctx: &egui::Context, graph_rect: Rect) {
+        if !self.ui_state.show_legend {
+            return;
+        }
+        self.update_graph_legend();
+        let mut legend_action: Option<LegendAction> = None;
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let label_context =
+                LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
+            let indexers = &rdf_data.node_data.indexers;
+            egui::Area::new(Id::new("graph_legend"))
+                .order(Order::Foreground)
+                .pivot(Align2::RIGHT_TOP)
+                .fixed_pos(graph_rect.right_top() + Vec2::new(-LEGEND_MARGIN, LEGEND_MARGIN))
+                .show(ctx, |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(graph_rect.height() * 0.6)
+                            .show(ui, |ui| {
+                                egui::Grid::new("graph_legend_grid").num_columns(4).show(ui, |ui| {
+                                    if !self.graph_legend.types.is_empty() || self.graph_legend.untyped > 0 {
+                                        ui.label("");
+                                        ui.strong("Types");
+                                        ui.end_row();
+                                    }
+                                    for (type_index, count) in self.graph_legend.types.iter() {
+                                        node_swatch(ui, self.visualization_style.get_type_style_one(*type_index));
+                                        ui.label(
+                                            rdf_data
+                                                .node_data
+                                                .type_display(*type_index, &label_context, indexers)
+                                                .as_str(),
+                                        );
+                                        ui.label(count.to_string());
+                                        if ui.small_button("❌").on_hover_text("Hide the nodes of this type").clicked() {
+                                            legend_action = Some(LegendAction::HideType(*type_index));
+                                        }
+                                        ui.end_row();
+                                    }
+                                    if self.graph_legend.untyped > 0 {
+                                        node_swatch(ui, &self.visualization_style.default_node_style);
+                                        ui.label("Untyped");
+                                        ui.label(self.graph_legend.untyped.to_string());
+                                        ui.end_row();
+                                    }
+                                    if !self.graph_legend.predicates.is_empty() {
+                                        ui.label("");
+                                        ui.strong("Relations");
+                                        ui.end_row();
+                                    }
+                                    let default_edge_style = EdgeStyle::default();
+                                    for (predicate_index, count) in self.graph_legend.predicates.iter() {
+                                        let hidden = self.ui_state.hidden_predicates.contains(*predicate_index);
+                                        let edge_style = self
+                                            .visualization_style
+                                            .edge_styles
+                                            .get(predicate_index)
+                                            .unwrap_or(&default_edge_style);
+                                        edge_swatch(ui, edge_style, hidden);
+                                        ui.label(
+                                            rdf_data
+                                                .node_data
+                                                .predicate_display(*predicate_index, &label_context, indexers)
+                                                .as_str(),
+                                        );
+                                        ui.label(count.to_string());
+                                        if hidden {
+                                            if ui.small_button("👁").on_hover_text("Show the edges of this predicate").clicked() {
+                                                legend_action = Some(LegendAction::ShowPredicate(*predicate_index));
+                                            }
+                                        } else if ui
+                                            .small_button("❌")
+                                            .on_hover_text("Hide the edges of this predicate")
+                                            .clicked()
+                                        {
+                                            legend_action = Some(LegendAction::HidePredicate(*predicate_index));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                            });
+                    });
+                });
+        }
+        match legend_action {
+            Some(LegendAction::HideType(type_index)) => {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    self.visible_nodes.retain(&self.ui_state.hidden_predicates, false, |node_layout| {
+                        rdf_data
+                            .node_data
+                            .get_node_by_index(node_layout.node_index)
+                            .is_none_or(|(_, node)| legend_type(node, &self.visualization_style) != Some(type_index))
+                    });
+                }
+                self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+                if let Some(selected_node) = self.ui_state.selected_node
+                    && self.visible_nodes.get_pos(selected_node).is_none()
+                {
+                    self.ui_state.selected_node = None;
+                }
+                self.ui_state
+                    .selected_nodes
+                    .retain(|node_index| self.visible_nodes.get_pos(*node_index).is_some());
+            }
+            Some(LegendAction::HidePredicate(predicate_index)) => {
+                self.ui_state.hidden_predicates.add(predicate_index);
+                self.update_hidden_predicates();
+            }
+            Some(LegendAction::ShowPredicate(predicate_index)) => {
+                self.ui_state.hidden_predicates.remove(predicate_index);
+                self.update_hidden_predicates();
+            }
+            None => {}
+        }
+    }
+
+    fn update_hidden_predicates(&mut self) {
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            update_edges_groups(&mut layout.edges, &self.ui_state.hidden_predicates);
+        }
+        self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+    }
+}
+
+fn node_swatch(ui: &mut egui::Ui, node_style: &NodeStyle) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::splat(SWATCH_SIZE), Sense::hover());
+    let stroke = Stroke::new(node_style.border_width.min(2.0), node_style.border_color);
+    match node_style.node_shape {
+        NodeShape::Rect | NodeShape::None => {
+            ui.painter()
+                .rect(rect.shrink(1.0), 2.0, node_style.color, stroke, StrokeKind::Inside);
+        }
+        NodeShape::Circle | NodeShape::Ellipse => {
+            ui.painter().circle(rect.center(), SWATCH_SIZE / 2.0 - 1.0, node_style.color, stroke);
+        }
+    }
+}
+
+fn edge_swatch(ui: &mut egui::Ui, edge_style: &EdgeStyle, hidden: bool) {
+    let (rect, _) = ui.allocate_exact_size(Vec2::new(EDGE_SWATCH_LENGTH, SWATCH_SIZE), Sense::hover());
+    let from = Pos2::new(rect.min.x, rect.center().y);
+    let to = Pos2::new(rect.max.x - 2.0, rect.center().y);
+    let painter = ui.painter();
+    draw_edge_line(painter, &[from, to], edge_style, hidden);
+    if edge_style.arrow_location != ArrowLocation::None {
+        draw_arrow_head(painter, to, Vec2::X, edge_style, hidden);
+    }
+}
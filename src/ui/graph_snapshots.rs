@@ -0,0 +1,240 @@
+use egui::{ScrollArea, Vec2};
+use string_interner::Symbol;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::graph_snapshot::{GraphSnapshot, SnapshotNode},
+    support::SortedVec,
+    ui::graph_view::{NeighborPos, update_layout_edges},
+    uistate::layout::{IndividualNodeStyleData, NodeLayout, NodePosition, NodeShapeData},
+};
+
+pub struct SnapshotPanel {
+    pub name: String,
+}
+
+impl RdfGlanceApp {
+    pub fn toggle_graph_snapshots(&mut self) {
+        if self.graph_snapshots.is_some() {
+            self.graph_snapshots = None;
+        } else {
+            self.graph_snapshots = Some(SnapshotPanel {
+                name: self.persistent_data.graph_snapshots.unique_name("View"),
+            });
+        }
+    }
+
+    fn snapshot_sources(&self) -> Vec<String> {
+        let mut sources: Vec<String> = Vec::new();
+        for report in self.import_log.reports.iter() {
+            if !sources.contains(&report.source) {
+                sources.push(report.source.clone());
+            }
+        }
+        sources
+    }
+
+    pub fn capture_graph_snapshot(&self, name: &str) -> Option<GraphSnapshot> {
+        let (rdf_data, layout) = self.visible_nodes.read_with_data(&self.rdf_data)?;
+        let nodes = layout
+            .nodes
+            .iter()
+            .zip(layout.positions.iter())
+            .filter_map(|(node_layout, position)| {
+                // full iris, the prefixes could be different when the data are loaded again
+                rdf_data
+                    .node_data
+                    .get_node_by_index(node_layout.node_index)
+                    .map(|(iri, _)| SnapshotNode {
+                        iri: rdf_data
+                            .prefix_manager
+                            .get_full_opt(iri)
+                            .map_or_else(|| iri.to_string(), |f| f.to_string()),
+                        x: position.pos.x,
+                        y: position.pos.y,
+                        locked: position.locked,
+                    })
+            })
+            .collect();
+        let hidden_predicates = self
+            .ui_state
+            .hidden_predicates
+            .data
+            .iter()
+            .filter_map(|predicate_index| {
+                rdf_data
+                    .node_data
+                    .indexers
+                    .predicate_indexer
+                    .index_to_str(*predicate_index)
+                    .map(|iri| {
+                        rdf_data
+                            .prefix_manager
+                            .get_full_opt(iri)
+                            .map_or_else(|| iri.to_string(), |f| f.to_string())
+                    })
+            })
+            .collect();
+        Some(GraphSnapshot {
+            name: name.to_string(),
+            sources: self.snapshot_sources(),
+            nodes,
+            hidden_predicates,
+        })
+    }
+
+    /// Replaces the visual graph by the snapshot, returns the number of nodes that are not in the data
+    pub fn restore_graph_snapshot(&mut self, snapshot: &GraphSnapshot) -> usize {
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return 0;
+        };
+        let node_data = &rdf_data.node_data;
+        let prefix_manager = &rdf_data.prefix_manager;
+        let mut restored: Vec<(IriIndex, &SnapshotNode)> = snapshot
+            .nodes
+            .iter()
+            .filter_map(|node| {
+                node_data
+                    .get_node_index(&prefix_manager.get_prefixed(&node.iri))
+                    .map(|node_index| (node_index, node))
+            })
+            .collect();
+        restored.sort_by_key(|(node_index, _)| *node_index);
+        restored.dedup_by_key(|(node_index, _)| *node_index);
+        let missing = snapshot.nodes.len() - restored.len();
+        let mut hidden_predicates = SortedVec::new();
+        for iri in snapshot.hidden_predicates.iter() {
+            if let Some(symbol) = node_data
+                .indexers
+                .predicate_indexer
+                .map
+                .get(prefix_manager.get_prefixed(iri))
+            {
+                hidden_predicates.add(symbol.to_usize() as IriIndex);
+            }
+        }
+        self.ui_state.hidden_predicates = hidden_predicates;
+        self.ui_state.selected_node = None;
+        self.ui_state.selected_nodes.clear();
+        self.visible_nodes.clean_all();
+        self.visible_nodes
+            .mut_nodes(|nodes, positions, _edges, node_shapes, individual_node_styles| {
+                for (node_index, node) in restored.iter() {
+                    nodes.push(NodeLayout::new(*node_index));
+                    positions.push(NodePosition {
+                        pos: egui::Pos2::new(node.x, node.y),
+                        vel: Vec2::ZERO,
+                        locked: node.locked,
+                    });
+                    node_shapes.push(NodeShapeData::default());
+                    individual_node_styles.push(IndividualNodeStyleData::default());
+                }
+            });
+        let mut npos = NeighborPos::new();
+        for (node_index, _) in restored.iter() {
+            npos.insert(*node_index, *node_index);
+        }
+        update_layout_edges(
+            &npos,
+            &mut self.visible_nodes,
+            node_data,
+            &self.ui_state.hidden_predicates,
+        );
+        self.visible_nodes.update_node_shapes = true;
+        self.visible_nodes.compute_layout = false;
+        missing
+    }
+
+    pub fn show_graph_snapshots(&mut self, ui: &mut egui::Ui) {
+        if self.graph_snapshots.is_none() {
+            return;
+        }
+        let sources = self.snapshot_sources();
+        let Some(panel) = &mut self.graph_snapshots else {
+            return;
+        };
+        let mut close = false;
+        let mut save: Option<String> = None;
+        let mut restore: Option<String> = None;
+        let mut remove: Option<String> = None;
+        egui::Window::new("Graph Snapshots")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(350.0)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut panel.name);
+                    if ui
+                        .add_enabled(!panel.name.trim().is_empty(), egui::Button::new("Save Current View"))
+                        .on_hover_text(
+                            "Store visible nodes, positions and hidden predicates, the same name is replaced",
+                        )
+                        .clicked()
+                    {
+                        save = Some(panel.name.trim().to_string());
+                    }
+                });
+                ui.separator();
+                if self.persistent_data.graph_snapshots.is_empty() {
+                    ui.label("No snapshots saved");
+                }
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    egui::Grid::new("graph_snapshots")
+                        .striped(true)
+                        .num_columns(4)
+                        .show(ui, |ui| {
+                            for snapshot in self.persistent_data.graph_snapshots.snapshots.iter() {
+                                let label = if snapshot.is_same_data(&sources) {
+                                    egui::RichText::new(&snapshot.name).strong()
+                                } else {
+                                    egui::RichText::new(&snapshot.name)
+                                };
+                                ui.label(label)
+                                    .on_hover_text(format!("Data: {}", snapshot.sources.join(", ")));
+                                ui.label(format!("{} nodes", snapshot.nodes.len()));
+                                if ui.button("Restore").clicked() {
+                                    restore = Some(snapshot.name.clone());
+                                }
+                                if ui.button("Delete").clicked() {
+                                    remove = Some(snapshot.name.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        if let Some(name) = save
+            && let Some(snapshot) = self.capture_graph_snapshot(&name)
+        {
+            self.persistent_data.graph_snapshots.put(snapshot);
+            if let Some(panel) = &mut self.graph_snapshots {
+                panel.name = self.persistent_data.graph_snapshots.unique_name("View");
+            }
+            self.set_status_message(&format!("Snapshot {} saved", name));
+        }
+        if let Some(name) = restore
+            && let Some(snapshot) = self.persistent_data.graph_snapshots.get(&name).cloned()
+        {
+            let missing = self.restore_graph_snapshot(&snapshot);
+            if missing > 0 {
+                self.set_status_message(&format!(
+                    "Snapshot {} restored, {} nodes not found in data",
+                    name, missing
+                ));
+            } else {
+                self.set_status_message(&format!("Snapshot {} restored", name));
+            }
+        }
+        if let Some(name) = remove {
+            self.persistent_data.graph_snapshots.remove(&name);
+        }
+        if close {
+            self.graph_snapshots = None;
+        }
+    }
+}
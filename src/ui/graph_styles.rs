@@ -1,13 +1,19 @@
 use egui::{Color32, Popup, RichText, Sense, Slider, Vec2};
 
+// the icon images are scaled down to this size on loading
+#[cfg(not(target_arch = "wasm32"))]
+const ICON_IMAGE_SIZE: u32 = 128;
+
 use crate:: {
     IriIndex, RdfGlanceApp, domain::{
         LabelContext,
+        data_change::DataChange,
         graph_styles::{
-            ArrowLocation, ArrowStyle, EdgeFont, IconPosition, IconStyle, LabelPosition,
+            ArrowLocation, ArrowStyle, EdgeFont, EdgeLabelPosition, EdgeLayoutForce, IconImage, IconPosition, IconStyle, LabelPosition,
             LineStyle, NodeShape, NodeSize,
         },
-    }, ui::{draw_edge, draw_node_label}, uistate::StyleEdit    
+        style_rules::{RuleOperator, StyleRule, StyleRules},
+    }, graph_algorithms::StatisticValue, ui::{draw_edge, draw_node_label, size_mapping::display_size_mapping, style::ICON_DELETE}, uistate::StyleEdit    
 };
 
 
@@ -18,7 +24,7 @@ impl RdfGlanceApp {
             if let Ok(rdf_data) = self.rdf_data.read() {
                 let label_context = LabelContext::new(
                     self.ui_state.display_language,
-                    self.persistent_data.config_data.iri_display,
+                    self.config.iri_display,
                     &rdf_data.prefix_manager,
                 );
                 let type_label = rdf_data
@@ -28,7 +34,7 @@ impl RdfGlanceApp {
                 if ui.button("Close Style Edit").clicked() {
                     type_style.is_default = false;
                     self.ui_state.style_edit = StyleEdit::None;
-                    self.visible_nodes.update_node_shapes = true;
+                    self.data_changes.notify(DataChange::StyleChanged);
                 }
                 ui.horizontal(|ui| {
                     ui.label("Priority:");
@@ -53,6 +59,8 @@ impl RdfGlanceApp {
                     ui.label("Sizing:");
                     ui.selectable_value(&mut type_style.node_size, NodeSize::Fixed, "Fixed");
                     ui.selectable_value(&mut type_style.node_size, NodeSize::Label, "Label Dependant");
+                    ui.selectable_value(&mut type_style.node_size, NodeSize::Mapped, "Mapped")
+                        .on_hover_text("Size from a numeric property or a computed statistic of the node");
                 });
                 ui.horizontal(|ui| {
                     ui.label("Width:");
@@ -95,6 +103,64 @@ impl RdfGlanceApp {
                     ui.add(Slider::new(&mut type_style.label_max_width, 0.0..=300.0));
                 });
                 display_icon_style(ui, &mut type_style.icon_style, &mut self.ui_state.icon_name_filter);
+                if let Some(type_data) = self.type_index.types.get(&type_style_edit) {
+                    let mut properties: Vec<(IriIndex, String)> = type_data
+                        .properties
+                        .keys()
+                        .map(|predicate_index| {
+                            let label = rdf_data.node_data.predicate_display(
+                                *predicate_index,
+                                &label_context,
+                                &rdf_data.node_data.indexers,
+                            );
+                            (*predicate_index, label.as_str().to_string())
+                        })
+                        .collect();
+                    properties.sort_by(|a, b| a.1.cmp(&b.1));
+                    let tooltip_properties =
+                        self.visualization_style.tooltip_properties.entry(type_style_edit).or_default();
+                    ui.collapsing("Tooltip Properties", |ui| {
+                        ui.label("Data properties shown in the tooltip of the node in visual graph");
+                        for (predicate_index, label) in properties.iter() {
+                            let mut selected = tooltip_properties.contains(predicate_index);
+                            if ui.checkbox(&mut selected, label).changed() {
+                                if selected {
+                                    tooltip_properties.push(*predicate_index);
+                                } else {
+                                    tooltip_properties.retain(|property| property != predicate_index);
+                                }
+                            }
+                        }
+                    });
+                    if !properties.is_empty() {
+                        display_style_rules(
+                            ui,
+                            &mut self.visualization_style.style_rules,
+                            type_style_edit,
+                            &properties,
+                        );
+                    }
+                    if type_style.node_size == NodeSize::Mapped {
+                        let statistic_values: Vec<StatisticValue> = self
+                            .statistics_data
+                            .as_ref()
+                            .map(|statistics_data| {
+                                statistics_data
+                                    .results
+                                    .iter()
+                                    .map(|result| result.statistics_value())
+                                    .filter(|statistic_value| !statistic_value.is_clustering())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        display_size_mapping(
+                            ui,
+                            &mut self.visualization_style.size_mapping,
+                            &properties,
+                            &statistic_values,
+                        );
+                    }
+                }
                 let desired_size = Vec2::new(800.0, 300.0); // width, height
                 let (response, painter) = ui.allocate_painter(desired_size, Sense::empty());
                 let node_label = "Test Label";
@@ -120,7 +186,7 @@ impl RdfGlanceApp {
             if let Ok(rdf_data) = self.rdf_data.read() {
                 let label_context = LabelContext::new(
                     self.ui_state.display_language,
-                    self.persistent_data.config_data.iri_display,
+                    self.config.iri_display,
                     &rdf_data.prefix_manager,
                 );
                 let predicate_label =
@@ -130,7 +196,7 @@ impl RdfGlanceApp {
                 ui.heading(format!("Edge Style for: {}", predicate_label.as_str()));
                 if ui.button("Close Style Edit").clicked() {
                     self.ui_state.style_edit = StyleEdit::None;
-                    self.visible_nodes.update_node_shapes = true;
+                    self.data_changes.notify(DataChange::StyleChanged);
                 }
                 ui.horizontal(|ui| {
                     ui.label("Color:");
@@ -155,6 +221,7 @@ impl RdfGlanceApp {
                 ui.horizontal(|ui| {
                     ui.label("Arrow Location:");
                     ui.selectable_value(&mut edge_style.arrow_location, ArrowLocation::Target, "Target");
+                    ui.selectable_value(&mut edge_style.arrow_location, ArrowLocation::Both, "Both Ends");
                     ui.selectable_value(&mut edge_style.arrow_location, ArrowLocation::Middle, "Middle");
                     ui.selectable_value(&mut edge_style.arrow_location, ArrowLocation::None, "None");
                 });
@@ -169,6 +236,8 @@ impl RdfGlanceApp {
                     ui.selectable_value(&mut edge_style.target_style, ArrowStyle::Arrow, "Arrow");
                     ui.selectable_value(&mut edge_style.target_style, ArrowStyle::ArrorFilled, "Filled Triangle");
                     ui.selectable_value(&mut edge_style.target_style, ArrowStyle::ArrorTriangle, "Triangle");
+                    ui.selectable_value(&mut edge_style.target_style, ArrowStyle::Diamond, "Diamond");
+                    ui.selectable_value(&mut edge_style.target_style, ArrowStyle::DiamondOpen, "Open Diamond");
                 });
                 if edge_style.edge_font.is_some() {
                     if ui.button("Clear Label").clicked() {
@@ -183,10 +252,53 @@ impl RdfGlanceApp {
                             ui.label("Font Color:");
                             ui.color_edit_button_srgba(&mut edge_font.font_color);
                         });
+                        ui.checkbox(&mut edge_font.label_visible, "Show Label");
+                        ui.horizontal(|ui| {
+                            ui.label("Label Position:");
+                            ui.selectable_value(&mut edge_font.label_position, EdgeLabelPosition::Start, "Start");
+                            ui.selectable_value(&mut edge_font.label_position, EdgeLabelPosition::Middle, "Middle");
+                            ui.selectable_value(&mut edge_font.label_position, EdgeLabelPosition::End, "End");
+                        });
+                        ui.checkbox(&mut edge_font.label_rotate, "Rotate with Edge");
+                        ui.checkbox(&mut edge_font.label_background, "Label Background");
                     }
                 } else if ui.button("Add Label").clicked() {
                     edge_style.edge_font = Some(EdgeFont::default());
                 }
+                let mut layout_force_changed = false;
+                if edge_style.layout_force.is_some() {
+                    if ui
+                        .button("Clear Layout Force")
+                        .on_hover_text("The edges use the edges force of the layout")
+                        .clicked()
+                    {
+                        edge_style.layout_force = None;
+                        layout_force_changed = true;
+                    }
+                    if let Some(layout_force) = &mut edge_style.layout_force {
+                        ui.horizontal(|ui| {
+                            ui.label("Edge Length:");
+                            layout_force_changed |=
+                                ui.add(Slider::new(&mut layout_force.length, 0.0..=500.0)).changed();
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Stiffness:");
+                            layout_force_changed |= ui
+                                .add(Slider::new(&mut layout_force.stiffness, 0.05..=10.0).logarithmic(true))
+                                .changed();
+                        });
+                    }
+                } else if ui
+                    .button("Add Layout Force")
+                    .on_hover_text("Preferred length and stiffness of the edges in the force layout")
+                    .clicked()
+                {
+                    edge_style.layout_force = Some(EdgeLayoutForce::default());
+                    layout_force_changed = true;
+                }
+                if layout_force_changed {
+                    self.data_changes.notify(DataChange::StyleChanged);
+                }
 
                 display_icon_style(ui, &mut edge_style.icon_style, &mut self.ui_state.icon_name_filter);
 
@@ -203,6 +315,7 @@ impl RdfGlanceApp {
                     NodeShape::Circle,
                     edge_style,
                     node_label,
+                    true,
                     false,
                     0.0,
                     ui.visuals()
@@ -212,6 +325,68 @@ impl RdfGlanceApp {
     }
 }
 
+fn display_style_rules(
+    ui: &mut egui::Ui,
+    style_rules: &mut StyleRules,
+    type_index: IriIndex,
+    properties: &[(IriIndex, String)],
+) {
+    ui.collapsing("Style Rules", |ui| {
+        ui.label("Change the color or size of instances by property value, the first matching rule is used");
+        let mut changed = false;
+        let mut remove: Option<usize> = None;
+        for (position, rule) in style_rules.rules.iter_mut().enumerate() {
+            if rule.type_index != type_index {
+                continue;
+            }
+            ui.horizontal(|ui| {
+                let property_label = properties
+                    .iter()
+                    .find(|(predicate_index, _)| *predicate_index == rule.predicate)
+                    .map_or("?", |(_, label)| label.as_str());
+                egui::ComboBox::from_id_salt(("rule_property", position))
+                    .selected_text(property_label)
+                    .show_ui(ui, |ui| {
+                        for (predicate_index, label) in properties.iter() {
+                            changed |= ui.selectable_value(&mut rule.predicate, *predicate_index, label).changed();
+                        }
+                    });
+                egui::ComboBox::from_id_salt(("rule_operator", position))
+                    .width(70.0)
+                    .selected_text(rule.operator.label())
+                    .show_ui(ui, |ui| {
+                        for operator in RuleOperator::ALL {
+                            changed |= ui.selectable_value(&mut rule.operator, operator, operator.label()).changed();
+                        }
+                    });
+                changed |= ui.add(egui::TextEdit::singleline(&mut rule.value).desired_width(100.0)).changed();
+                let mut use_color = rule.color.is_some();
+                if ui.checkbox(&mut use_color, "Color").changed() {
+                    rule.color = use_color.then_some(Color32::GRAY);
+                }
+                if let Some(color) = &mut rule.color {
+                    ui.color_edit_button_srgba(color);
+                }
+                ui.add(egui::DragValue::new(&mut rule.size_factor).range(0.2..=5.0).speed(0.05).prefix("size ×"));
+                if ui.button(ICON_DELETE).on_hover_text("Remove rule").clicked() {
+                    remove = Some(position);
+                }
+            });
+        }
+        if let Some(position) = remove {
+            style_rules.rules.remove(position);
+            changed = true;
+        }
+        if ui.button("Add Rule").clicked() {
+            style_rules.rules.push(StyleRule::new(type_index, properties[0].0));
+            changed = true;
+        }
+        if changed {
+            style_rules.update_index();
+        }
+    });
+}
+
 fn display_icon_style(ui: &mut egui::Ui, icon_style: &mut Option<IconStyle>, icon_name_fitler: &mut String) {
     if icon_style.is_some() {
         ui.heading("Additional Icon:");
@@ -228,6 +403,9 @@ fn display_icon_style(ui: &mut egui::Ui, icon_style: &mut Option<IconStyle>, ico
                 ui.selectable_value(&mut icon_style_val.icon_position, IconPosition::Left, "Left");
             });
             icon_edit_button(ui, &mut icon_style_val.icon_character, icon_name_fitler);
+            display_icon_set(ui, &mut icon_style_val.icon_character);
+            #[cfg(not(target_arch = "wasm32"))]
+            display_icon_image(ui, &mut icon_style_val.image);
             ui.horizontal(|ui| {
                 ui.label("Icon Size:");
                 ui.add(Slider::new(&mut icon_style_val.icon_size, 5.0..=80.0));
@@ -238,15 +416,122 @@ fn display_icon_style(ui: &mut egui::Ui, icon_style: &mut Option<IconStyle>, ico
             });
         }
     } else if ui.button("Add additional Icon").clicked() {
-        *icon_style = Some(IconStyle {
-            icon_character: '\u{2606}',
-            icon_position: IconPosition::Center,
-            icon_size: 20.0,
-            icon_color: Color32::BLACK,
-        });
+        *icon_style = Some(IconStyle::default());
     }
 }
 
+// Icons of the emoji font for common node types, the icon picker offers all characters
+const ICON_SET: [(char, &str); 20] = [
+    ('\u{1F464}', "Person"),
+    ('\u{1F465}', "Group"),
+    ('\u{1F3E2}', "Organization"),
+    ('\u{1F3E0}', "Building"),
+    ('\u{1F4CD}', "Place"),
+    ('\u{1F310}', "Web"),
+    ('\u{1F4C5}', "Event"),
+    ('\u{1F552}', "Time"),
+    ('\u{1F4C4}', "Document"),
+    ('\u{1F4D6}', "Book"),
+    ('\u{1F5BC}', "Image"),
+    ('\u{1F3F7}', "Concept"),
+    ('\u{1F4E6}', "Product"),
+    ('\u{1F4CA}', "Dataset"),
+    ('\u{1F517}', "Link"),
+    ('\u{2699}', "Service"),
+    ('\u{1F511}', "Key"),
+    ('\u{2709}', "Message"),
+    ('\u{1F4B0}', "Money"),
+    ('\u{2605}', "Star"),
+];
+
+fn display_icon_set(ui: &mut egui::Ui, icon_character: &mut char) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Icon Set:");
+        for (icon, name) in ICON_SET.iter() {
+            let button = egui::Button::selectable(*icon_character == *icon, RichText::new(icon.to_string()).size(18.0));
+            if ui.add(button).on_hover_text(*name).clicked() {
+                *icon_character = *icon;
+            }
+        }
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn display_icon_image(ui: &mut egui::Ui, image: &mut Option<IconImage>) {
+    ui.horizontal(|ui| {
+        ui.label("Image:");
+        if let Some(icon_image) = image.as_ref() {
+            if icon_image.load_failed {
+                ui.colored_label(ui.visuals().error_fg_color, format!("Can not load {}", icon_image.path));
+            } else {
+                ui.label(icon_image.path.as_str());
+            }
+        }
+        if ui
+            .button("Choose Image...")
+            .on_hover_text("PNG, JPEG, GIF, BMP or WebP file drawn instead of the icon character")
+            .clicked()
+            && let Some(path) = rfd::FileDialog::new()
+                .add_filter("Image", &["png", "jpg", "jpeg", "gif", "bmp", "webp"])
+                .pick_file()
+        {
+            *image = Some(IconImage::new(path.display().to_string()));
+        }
+        if image.is_some() && ui.button("Remove Image").clicked() {
+            *image = None;
+        }
+    });
+}
+
+impl RdfGlanceApp {
+    /**
+     * Creates the textures of the icon images of the node and edge styles that are not loaded yet.
+     * An image that can not be loaded is marked, so the file is not read in each frame.
+     */
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_icon_images(&mut self, ctx: &egui::Context) {
+        let icon_styles = self
+            .visualization_style
+            .node_styles
+            .values_mut()
+            .filter_map(|node_style| node_style.icon_style.as_mut())
+            .chain(
+                self.visualization_style
+                    .edge_styles
+                    .values_mut()
+                    .filter_map(|edge_style| edge_style.icon_style.as_mut()),
+            );
+        for icon_style in icon_styles {
+            let Some(icon_image) = &mut icon_style.image else {
+                continue;
+            };
+            if icon_image.texture.is_some() || icon_image.load_failed {
+                continue;
+            }
+            match load_icon_image(&icon_image.path) {
+                Ok(image) => {
+                    icon_image.texture = Some(ctx.load_texture(
+                        format!("icon_{}", icon_image.path),
+                        image,
+                        egui::TextureOptions::LINEAR,
+                    ));
+                }
+                Err(err) => {
+                    log::warn!("Can not load icon image {}: {}", icon_image.path, err);
+                    icon_image.load_failed = true;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn load_icon_image(path: &str) -> anyhow::Result<egui::ColorImage> {
+    let image = image::open(path)?.thumbnail(ICON_IMAGE_SIZE, ICON_IMAGE_SIZE).to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(size, image.as_flat_samples().as_slice()))
+}
+
 // Chosing icon character
 // Code partly from egui demo
 // https://github.com/emilk/egui/blob/master/crates/egui_demo_lib/src/demo/font_book.rs
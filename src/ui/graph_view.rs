@@ -4,15 +4,20 @@ use std::{
 };
 
 use super::style::{
-    ICON_CENTER, ICON_CLEAN_ALL, ICON_EXPAND, ICON_GRAPH, ICON_HELP, ICON_HIGHLIGHT, ICON_KEY, ICON_LABEL, ICON_NUMBER,
-    ICON_PROPERTIES, ICON_REDO, ICON_UNDO, ICON_UNEXPAND, ICON_WRENCH,
+    ICON_BOOKMARK, ICON_CENTER, ICON_CLEAN_ALL, ICON_EXPAND, ICON_GRAPH, ICON_HELP, ICON_HIGHLIGHT, ICON_LABEL, ICON_NUMBER,
+    ICON_PIN, ICON_PROPERTIES, ICON_REDO, ICON_REV_LINK, ICON_UNDO, ICON_UNEXPAND, ICON_WRENCH,
 };
 use crate::{
     IriIndex, NodeChangeContext, RdfGlanceApp,
     domain::{
-        ExpandType, Indexers, LabelContext, Literal, NObject, NodeData,
+        ExpandType, Indexers, LabelContext, Literal, NObject, NodeData, RdfData,
+        blank_node_tree::{blank_node_roots, blank_node_trees, composite_references, has_blank_node_tree, is_blank_node},
         config::Config,
-        graph_styles::{ArrowStyle, GVisualizationStyle, NodeShape, NodeSize, NodeStyle},
+        dataset_diff::DiffStatus,
+        edge_metadata::EdgeDisplay,
+        graph_styles::{ArrowLocation, GVisualizationStyle, LabelPosition, NodeShape, NodeSize, NodeStyle},
+        keymap::{Keymap, ViewAction},
+        render_mode::RenderSimplification,
     },
     support::{
         SortedVec,
@@ -20,46 +25,79 @@ use crate::{
         uitools::{popup_at, primary_color},
     },
     ui::{
-        draw_edge, draw_node_label, draw_self_edge, fade_color,
+        draw_arrow_head, draw_circle_image, draw_cluster_badge, draw_composite_outline, draw_edge, draw_edge_bundle, draw_edge_line, draw_node_label, draw_self_edge, fade_color,
+        browse_view::show_blank_node_trees,
+        minimap::{Minimap, points_bounds},
+        named_graphs::show_graph_filter,
+        node_edit::{NodeEditAction, add_property_ui, change_set_ui, edit_property_value},
         style::{ICON_EXPORT, ICON_ROCKET},
     },
     uistate::{
-        StyleEdit, UIState,
-        actions::{NodeAction, NodeContextAction},
+        EdgeBundle, NewLink, NewNode, NodeEdit, StyleEdit, UIState,
+        actions::{NodeAction, NodeContextAction, ReferenceAction},
         layout::{
-            Edge, IndividualNodeStyleData, LayoutConfUpdate, NodeCommand, NodeShapeData, SortedNodeLayout,
-            update_edges_groups,
+            Edge, IndividualNodeStyleData, LayoutConfUpdate, LayoutData, NodeCommand, NodeShapeData, NodeStyleOverride,
+            SortedNodeLayout,
+            edge_bundle_key, edge_bundles, node_pos, update_edges_groups,
         },
     },
 };
 use const_format::concatcp;
 use eframe::egui::{self, Pos2, Sense, Vec2};
-use egui::{Key, Painter, Popup, Rect, Shape, Slider, Stroke, StrokeKind};
+use egui::{Key, Painter, Popup, Rect, Slider, Stroke, StrokeKind, UiKind, epaint::ClippedShape};
 use rand::RngExt;
 
 const INITIAL_DISTANCE: f32 = 100.0;
+// size of the scene content, the layout positions are relative to its center
+const SCENE_SIZE: f32 = 1000.0;
+const SCENE_CENTER: Pos2 = Pos2::new(SCENE_SIZE / 2.0, SCENE_SIZE / 2.0);
+const BOOKMARK_KEYS: [Key; 9] = [
+    Key::Num1,
+    Key::Num2,
+    Key::Num3,
+    Key::Num4,
+    Key::Num5,
+    Key::Num6,
+    Key::Num7,
+    Key::Num8,
+    Key::Num9,
+];
 
 struct ReferencesState {
     pub count: u32,
     pub visible: u32,
 }
 
+fn menu_text(text: &str, keymap: &Keymap, action: ViewAction) -> String {
+    match keymap.binding(action) {
+        Some(binding) => format!("{} ({})", text, binding),
+        None => text.to_string(),
+    }
+}
+
 impl NodeContextAction {
-    fn show_menu(ui: &mut egui::Ui, opened_by_keyboard: bool, has_zoom: bool) -> NodeContextAction {
-        let hide_button = ui.button("Hide (H)");
+    fn show_menu(
+        ui: &mut egui::Ui,
+        opened_by_keyboard: bool,
+        has_zoom: bool,
+        keymap: &Keymap,
+        in_cluster: bool,
+        is_meta_node: bool,
+    ) -> NodeContextAction {
+        let hide_button = ui.button(menu_text("Hide", keymap, ViewAction::HideNode));
         if opened_by_keyboard {
             hide_button.request_focus();
         }
         if hide_button.clicked() {
             return NodeContextAction::Hide;
         }
-        if ui.button("Hide this type (T)").clicked() {
+        if ui.button(menu_text("Hide this type", keymap, ViewAction::HideThisType)).clicked() {
             return NodeContextAction::HideThisType;
         }
         if ui.button("Hide this type with Edge Preservation").clicked() {
             return NodeContextAction::HideThisTypePreserveEdges;
         }
-        if ui.button("Hide other (O)").clicked() {
+        if ui.button(menu_text("Hide other", keymap, ViewAction::HideOther)).clicked() {
             return NodeContextAction::HideOther;
         }
         if ui.button("Hide other types").clicked() {
@@ -81,7 +119,7 @@ impl NodeContextAction {
         if req.clicked() {
             return NodeContextAction::HideZoomInvisible;
         }
-        if ui.button("Expand (E)").clicked() {
+        if ui.button(menu_text("Expand", keymap, ViewAction::ExpandNode)).clicked() {
             return NodeContextAction::Expand(ExpandType::Both);
         }
         if ui.button("Expand Referenced").clicked() {
@@ -96,12 +134,31 @@ impl NodeContextAction {
         if ui.button("List all Instances of this type").clicked() {
             return NodeContextAction::ShowAllInstanceInTable;
         }
-        if ui.button("Lock Position").clicked() {
+        if ui
+            .button("Pin Position")
+            .on_hover_text("Layout algorithms do not move the selected nodes")
+            .clicked()
+        {
             return NodeContextAction::ChangeLockPosition(true);
         }
-        if ui.button("Unlock Position").clicked() {
+        if ui.button("Unpin Position").clicked() {
             return NodeContextAction::ChangeLockPosition(false);
         }
+        if ui.button("Style Selected Nodes...").clicked() {
+            return NodeContextAction::StyleSelected;
+        }
+        if is_meta_node {
+            if ui.button("Expand Cluster").clicked() {
+                return NodeContextAction::ExpandCluster;
+            }
+        } else if in_cluster
+            && ui
+                .button("Collapse Cluster")
+                .on_hover_text("Show all nodes of the cluster as one node")
+                .clicked()
+        {
+            return NodeContextAction::CollapseCluster;
+        }
         NodeContextAction::None
     }
 }
@@ -109,7 +166,8 @@ impl NodeContextAction {
 impl RdfGlanceApp {
     pub fn show_graph(&mut self, ui: &mut egui::Ui) -> NodeAction {
         let mut node_to_click: NodeAction = NodeAction::None;
-        if self.visible_nodes.nodes.read().unwrap().is_empty() {
+        let mut view_actions = ui.input(|i| self.config.keymap.pressed(i));
+        if self.visible_nodes.layout_data.read().unwrap().nodes.is_empty() {
             ui.heading(concatcp!(
                 "No nodes to display. Go to tables or browser and add a node to graph using button with ",
                 ICON_GRAPH
@@ -121,11 +179,11 @@ impl RdfGlanceApp {
                     let mut node_change_context = NodeChangeContext {
                         rdfwrap: &mut self.rdfwrap,
                         visible_nodes: &mut self.visible_nodes,
-                        config: &self.persistent_data.config_data,
+                        config: &self.config,
                     };
                     if rdf_data.init_visual_graph(&mut node_change_context, &self.ui_state.hidden_predicates) {
                         self.visible_nodes
-                            .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                            .start_layout(&self.config, &self.ui_state.hidden_predicates);
                     }
                 }
             }
@@ -133,120 +191,117 @@ impl RdfGlanceApp {
                 ui.horizontal(|ui| {
                     let undo_button =
                         ui.add_enabled(!self.visible_nodes.undo_stack.is_empty(), egui::Button::new(ICON_UNDO));
-                    if undo_button.on_hover_text("Undo").clicked()
-                        || ui.input(|i| i.modifiers.command && i.key_pressed(Key::Z))
-                    {
-                        self.visible_nodes
-                            .undo(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                    if undo_button.on_hover_text(self.config.keymap.hint(ViewAction::Undo)).clicked() {
+                        view_actions.push(ViewAction::Undo);
                     }
                     let redo_button =
                         ui.add_enabled(!self.visible_nodes.redo_stack.is_empty(), egui::Button::new(ICON_REDO));
-                    if redo_button.on_hover_text("Redo").clicked()
-                        || ui.input(|i| i.modifiers.command && i.key_pressed(Key::Y))
-                    {
-                        self.visible_nodes
-                            .redo(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                    if redo_button.on_hover_text(self.config.keymap.hint(ViewAction::Redo)).clicked() {
+                        view_actions.push(ViewAction::Redo);
                     }
                 });
             }
+            for action in view_actions {
+                if matches!(action, ViewAction::Undo | ViewAction::Redo) {
+                    self.execute_view_action(action);
+                }
+            }
             return NodeAction::None;
         }
         ui.horizontal(|ui| {
+            let keymap = &self.config.keymap;
             let undo_button = ui.add_enabled(!self.visible_nodes.undo_stack.is_empty(), egui::Button::new(ICON_UNDO));
-            if undo_button.on_hover_text("Undo").clicked() || ui.input(|i| i.modifiers.command && i.key_pressed(Key::Z))
-            {
-                self.visible_nodes
-                    .undo(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+            if undo_button.on_hover_text(keymap.hint(ViewAction::Undo)).clicked() {
+                view_actions.push(ViewAction::Undo);
             }
             let redo_button = ui.add_enabled(!self.visible_nodes.redo_stack.is_empty(), egui::Button::new(ICON_REDO));
-            if redo_button.on_hover_text("Redo").clicked() || ui.input(|i| i.modifiers.command && i.key_pressed(Key::Y))
-            {
-                self.visible_nodes
-                    .redo(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+            if redo_button.on_hover_text(keymap.hint(ViewAction::Redo)).clicked() {
+                view_actions.push(ViewAction::Redo);
             }
-            if ui.button(ICON_EXPAND).on_hover_text("Expand Nodes (+)").clicked()
-                || ui.input(|i| i.key_pressed(Key::Plus))
-            {
-                if let Ok(mut rdf_data) = self.rdf_data.write() {
-                    let mut node_change_context = NodeChangeContext {
-                        rdfwrap: &mut self.rdfwrap,
-                        visible_nodes: &mut self.visible_nodes,
-                        config: &self.persistent_data.config_data,
-                    };
-                    if rdf_data.expand_all(&mut node_change_context, &self.ui_state.hidden_predicates) {
-                        self.visible_nodes
-                            .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
-                    }
-                }
+            if ui.button(ICON_EXPAND).on_hover_text(keymap.hint(ViewAction::ExpandAll)).clicked() {
+                view_actions.push(ViewAction::ExpandAll);
             }
-            if ui
-                .button(ICON_UNEXPAND)
-                .on_hover_text("Unexpand Nodes, Remove Leaves (-)")
-                .clicked()
-                || ui.input(|i| i.key_pressed(Key::Minus))
-            {
-                if let Ok(mut rdf_data) = self.rdf_data.write() {
-                    let mut node_change_context = NodeChangeContext {
-                        rdfwrap: &mut self.rdfwrap,
-                        visible_nodes: &mut self.visible_nodes,
-                        config: &self.persistent_data.config_data,
-                    };
-                    if rdf_data.unexpand_all(&mut node_change_context, &self.ui_state.hidden_predicates) {
-                        self.visible_nodes
-                            .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
-                    }
-                }
+            if ui.button(ICON_UNEXPAND).on_hover_text(keymap.hint(ViewAction::UnexpandAll)).clicked() {
+                view_actions.push(ViewAction::UnexpandAll);
+            }
+            if ui.button(ICON_CENTER).on_hover_text(keymap.hint(ViewAction::CenterGraph)).clicked() {
+                view_actions.push(ViewAction::CenterGraph);
             }
+            ui.menu_button(ICON_BOOKMARK, |ui| {
+                ui.label("Ctrl+1..9 saves the view, 1..9 restores it");
+                for slot in 0..BOOKMARK_KEYS.len() {
+                    ui.horizontal(|ui| {
+                        let is_saved = self.graph_state.bookmarks[slot].is_some();
+                        ui.label(format!("{}", slot + 1));
+                        if ui.add_enabled(is_saved, egui::Button::new("Go")).clicked() {
+                            self.graph_state.restore_bookmark(slot);
+                            ui.close_kind(UiKind::Menu);
+                        }
+                        if ui.button("Save").clicked() {
+                            self.graph_state.save_bookmark(slot);
+                            ui.close_kind(UiKind::Menu);
+                        }
+                        if ui.add_enabled(is_saved, egui::Button::new("Clear")).clicked() {
+                            self.graph_state.bookmarks[slot] = None;
+                        }
+                    });
+                }
+            })
+            .response
+            .on_hover_text("View Bookmarks (Ctrl+1..9 save, 1..9 jump)");
             if ui
-                .button(ICON_CENTER)
-                .on_hover_text("Center Graph and reset zoom (Home)")
+                .selectable_label(self.graph_snapshots.is_some(), "Snapshots")
+                .on_hover_text("Save and restore named views with visible nodes, positions and hidden predicates")
                 .clicked()
-                || ui.input(|i| i.key_pressed(Key::Home))
             {
-                self.graph_state.scene_rect = Rect::ZERO;
-                self.visible_nodes.to_center();
+                self.toggle_graph_snapshots();
             }
             if self.visible_nodes.has_semantic_zoom {
                 ui.label("Semantic zoom");
                 ui.add(Slider::new(&mut self.ui_state.semantic_zoom_magnitude, 1..=10));
             }
+            if let Ok(rdf_data) = self.rdf_data.read() {
+                show_graph_filter(ui, &rdf_data.node_data.named_graphs, &mut self.ui_state.graph_filter);
+            }
             self.visible_nodes.show_handle_layout_ui(
                 ui,
-                &self.persistent_data.config_data,
+                &self.config,
                 &self.ui_state.hidden_predicates,
             );
             ui.label("nodes force");
             let response = ui.add(Slider::new(
-                &mut self.persistent_data.config_data.m_repulsion_constant,
+                &mut self.config.m_repulsion_constant,
                 0.1..=8.0,
             ));
             if response.changed() {
+                self.store_layout_forces();
                 if let Some(layout_handle) = &self.visible_nodes.layout_handle {
                     let _ = layout_handle
                         .update_sender
                         .send(LayoutConfUpdate::UpdateRepulsionConstant(
-                            self.persistent_data.config_data.m_repulsion_constant,
+                            self.config.m_repulsion_constant,
                         ));
                 } else {
                     self.visible_nodes
-                        .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                        .start_layout(&self.config, &self.ui_state.hidden_predicates);
                 }
             }
             ui.label("edges force");
             let response = ui.add(Slider::new(
-                &mut self.persistent_data.config_data.m_attraction_factor,
+                &mut self.config.m_attraction_factor,
                 0.02..=3.0,
             ));
             if response.changed() {
+                self.store_layout_forces();
                 if let Some(layout_handle) = &self.visible_nodes.layout_handle {
                     let _ = layout_handle
                         .update_sender
                         .send(LayoutConfUpdate::UpdateAttractionFactor(
-                            self.persistent_data.config_data.m_attraction_factor,
+                            self.config.m_attraction_factor,
                         ));
                 } else {
                     self.visible_nodes
-                        .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                        .start_layout(&self.config, &self.ui_state.hidden_predicates);
                 }
             }
             if ui
@@ -256,6 +311,22 @@ impl RdfGlanceApp {
             {
                 self.ui_state.show_labels = !self.ui_state.show_labels;
             }
+            if (self.ui_state.full_rendering || !self.ui_state.render_simplification.is_full())
+                && ui
+                    .selectable_label(!self.ui_state.full_rendering, "Fast")
+                    .on_hover_text("Simplified drawing for large graphs, can be configured in settings")
+                    .clicked()
+            {
+                self.ui_state.full_rendering = !self.ui_state.full_rendering;
+            }
+            if ui
+                .selectable_label(self.visible_nodes.flatten_blank_nodes, "Inline Blank Nodes")
+                .on_hover_text("Show blank nodes and rdf lists inside of their named node (details panel and tooltip) instead of as own nodes")
+                .clicked()
+            {
+                self.persistent_data.config_data.flatten_blank_nodes = !self.visible_nodes.flatten_blank_nodes;
+                self.update_config();
+            }
             if ui
                 .selectable_label(self.ui_state.show_num_hidden_refs, ICON_NUMBER)
                 .on_hover_text("Show Number of unexpanded references")
@@ -270,11 +341,69 @@ impl RdfGlanceApp {
             {
                 self.ui_state.fade_unselected = !self.ui_state.fade_unselected;
             }
+            if ui
+                .selectable_label(self.ui_state.bundle_edges, "Bundle")
+                .on_hover_text("Draw many parallel edges as one edge with count, the limit can be configured in settings")
+                .clicked()
+            {
+                self.ui_state.bundle_edges = !self.ui_state.bundle_edges;
+                self.ui_state.edge_bundle = None;
+            }
+            if ui
+                .selectable_label(self.ui_state.show_minimap, "Minimap")
+                .on_hover_text("Show overview of all nodes, click or drag in it to move the view")
+                .clicked()
+            {
+                self.ui_state.show_minimap = !self.ui_state.show_minimap;
+            }
+            if ui
+                .selectable_label(self.ui_state.show_legend, "Legend")
+                .on_hover_text("Show the node types and relations of the graph, they can be hidden from the legend")
+                .clicked()
+            {
+                self.ui_state.show_legend = !self.ui_state.show_legend;
+            }
+            if ui
+                .selectable_label(self.degree_of_interest.is_some(), "Focus")
+                .on_hover_text("Show only the nodes near the selected node and the most connected nodes, follows the selection")
+                .clicked()
+            {
+                self.set_degree_of_interest(self.degree_of_interest.is_none());
+            }
+            if let Some(degree_of_interest) = &mut self.degree_of_interest {
+                let hops_changed = ui
+                    .add(egui::DragValue::new(&mut degree_of_interest.hops).range(1..=6).suffix(" hops"))
+                    .changed();
+                let landmarks_changed = ui
+                    .add(egui::DragValue::new(&mut degree_of_interest.landmarks).range(0..=50).suffix(" landmarks"))
+                    .on_hover_text("Number of the most connected nodes that stay visible")
+                    .changed();
+                if hops_changed || landmarks_changed {
+                    degree_of_interest.focus = None;
+                }
+            }
+            if !self.ui_state.read_only {
+                if ui
+                    .selectable_label(self.ui_state.authoring_mode, "Author")
+                    .on_hover_text("Create new nodes, drag from one node to another to create a reference")
+                    .clicked()
+                {
+                    self.ui_state.authoring_mode = !self.ui_state.authoring_mode;
+                    self.ui_state.link_drag = None;
+                }
+                if self.ui_state.authoring_mode && ui.button("New Node").clicked() {
+                    self.ui_state.new_node = Some(NewNode {
+                        pos: self.graph_state.scene_rect.center() - SCENE_CENTER.to_vec2(),
+                        ..NewNode::default()
+                    });
+                }
+            }
             if ui
                 .button(ICON_CLEAN_ALL)
                 .on_hover_text("Remove all nodes and edges from graph")
                 .clicked()
             {
+                self.degree_of_interest = None;
                 self.visible_nodes.clean_all();
             }
             if ui.button(ICON_EXPORT).on_hover_text("Export as SVG file").clicked() {
@@ -298,9 +427,10 @@ impl RdfGlanceApp {
 - Zoom use Ctrl + mouse wheel
 - Right mouse button down and drag to translate
 - Left mouse down and move for rectangle select
-- Shift Down for multiple select
+- Shift or Ctrl Down for multiple select
 
-Expand Relations - double click on node",
+Expand Relations - double click on node
+Keyboard shortcuts can be changed in the settings",
                         );
                     });
             }
@@ -312,8 +442,19 @@ Expand Relations - double click on node",
                 {
                     self.ui_state.show_properties = !self.ui_state.show_properties;
                 }
+                if ui
+                    .selectable_label(self.ui_state.show_incoming_references, ICON_REV_LINK)
+                    .on_hover_text("Show/Hide What Links Here Panel (incoming references of hovered node)")
+                    .clicked()
+                {
+                    self.ui_state.show_incoming_references = !self.ui_state.show_incoming_references;
+                }
             });
         });
+        for action in view_actions.iter() {
+            self.execute_view_action(*action);
+        }
+        self.update_degree_of_interest();
         match self.ui_state.style_edit {
             StyleEdit::Node(type_style_edit) => {
                 self.display_node_style(ui, type_style_edit);
@@ -322,6 +463,18 @@ Expand Relations - double click on node",
                 self.display_edge_style(ui, edge_style_edit);
             }
             StyleEdit::None => {
+                if self.ui_state.show_incoming_references {
+                    egui::Panel::left("incoming_references_panel")
+                        .exact_size(300.0)
+                        .show_inside(ui, |ui| {
+                            egui::ScrollArea::both().id_salt("incoming_references_scroll").show(ui, |ui| {
+                                let incoming_action = self.display_incoming_references(ui);
+                                if !matches!(incoming_action, NodeAction::None) {
+                                    node_to_click = incoming_action;
+                                }
+                            });
+                        });
+                }
                 if self.ui_state.show_properties {
                     egui::Panel::right("right_panel")
                         .exact_size(500.0)
@@ -330,19 +483,190 @@ Expand Relations - double click on node",
                                 node_to_click = self.display_node_details(ui);
                             });
                         });
+                }
+                if self.ui_state.show_properties || self.ui_state.show_incoming_references {
                     egui::CentralPanel::default().show_inside(ui, |ui| {
-                        self.display_graph( ui, &mut node_to_click);
+                        self.display_graph( ui, &mut node_to_click, &view_actions);
                     });
                 } else {
-                    self.display_graph( ui, &mut node_to_click);
+                    self.display_graph( ui, &mut node_to_click, &view_actions);
                 }
             }
         }
+        self.show_edge_bundle(ui);
+        self.show_group_style(ui);
         node_to_click
     }
 
+    /**
+     * Central dispatcher of the view actions triggered by toolbar or keyboard.
+     * The actions that depend on the scene or selected node are handled in display_graph.
+     */
+    fn execute_view_action(&mut self, action: ViewAction) {
+        match action {
+            ViewAction::Undo => {
+                self.visible_nodes
+                    .undo(&self.config, &self.ui_state.hidden_predicates);
+            }
+            ViewAction::Redo => {
+                self.visible_nodes
+                    .redo(&self.config, &self.ui_state.hidden_predicates);
+            }
+            ViewAction::ExpandAll | ViewAction::UnexpandAll => {
+                if let Ok(mut rdf_data) = self.rdf_data.write() {
+                    let mut node_change_context = NodeChangeContext {
+                        rdfwrap: &mut self.rdfwrap,
+                        visible_nodes: &mut self.visible_nodes,
+                        config: &self.config,
+                    };
+                    let changed = if matches!(action, ViewAction::ExpandAll) {
+                        rdf_data.expand_all(&mut node_change_context, &self.ui_state.hidden_predicates)
+                    } else {
+                        rdf_data.unexpand_all(&mut node_change_context, &self.ui_state.hidden_predicates)
+                    };
+                    if changed {
+                        self.visible_nodes
+                            .start_layout(&self.config, &self.ui_state.hidden_predicates);
+                    }
+                }
+            }
+            ViewAction::CenterGraph => {
+                self.graph_state.scene_rect = Rect::ZERO;
+                self.visible_nodes.to_center();
+            }
+            _ => {}
+        }
+    }
+
+    /// Style override of the first selected node that has one
+    fn selected_style_override(&self) -> Option<NodeStyleOverride> {
+        let layout = self.visible_nodes.layout_data.read().ok()?;
+        self.ui_state.selected_nodes.iter().find_map(|node_index| {
+            let pos = layout.nodes.binary_search_by(|e| e.node_index.cmp(node_index)).ok()?;
+            layout.individual_node_styles.get(pos)?.style_override
+        })
+    }
+
+    fn set_selected_style_override(&mut self, style_override: Option<NodeStyleOverride>) {
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            let LayoutData {
+                nodes,
+                individual_node_styles,
+                ..
+            } = &mut *layout;
+            for node_index in self.ui_state.selected_nodes.iter() {
+                if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(node_index))
+                    && let Some(node_style) = individual_node_styles.get_mut(pos)
+                {
+                    node_style.style_override = style_override;
+                }
+            }
+        }
+        self.visible_nodes.update_node_shapes = true;
+    }
+
+    fn show_group_style(&mut self, ui: &mut egui::Ui) {
+        let Some(group_style) = &mut self.ui_state.group_style else {
+            return;
+        };
+        let mut close = false;
+        let mut apply: Option<Option<NodeStyleOverride>> = None;
+        let selected_count = self.ui_state.selected_nodes.len();
+        egui::Window::new("Style Selected Nodes").collapsible(false).resizable(false).show(ui.ctx(), |ui| {
+            ui.label(format!("{} selected nodes", selected_count));
+            egui::Grid::new("group_style").num_columns(2).show(ui, |ui| {
+                ui.label("Fill color:");
+                ui.color_edit_button_srgba(&mut group_style.color);
+                ui.end_row();
+                ui.label("Border color:");
+                ui.color_edit_button_srgba(&mut group_style.border_color);
+                ui.end_row();
+                ui.label("Border width:");
+                ui.add(egui::Slider::new(&mut group_style.border_width, 0.0..=10.0));
+                ui.end_row();
+                ui.label("Size factor:");
+                ui.add(egui::Slider::new(&mut group_style.size_factor, 0.2..=5.0));
+                ui.end_row();
+            });
+            ui.horizontal(|ui| {
+                if ui.add_enabled(selected_count > 0, egui::Button::new("Apply")).clicked() {
+                    apply = Some(Some(*group_style));
+                }
+                if ui
+                    .add_enabled(selected_count > 0, egui::Button::new("Reset"))
+                    .on_hover_text("Use the type style for the selected nodes again")
+                    .clicked()
+                {
+                    apply = Some(None);
+                }
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        });
+        if let Some(style_override) = apply {
+            self.set_selected_style_override(style_override);
+        }
+        if close {
+            self.ui_state.group_style = None;
+        }
+    }
+
+    fn show_edge_bundle(&mut self, ui: &mut egui::Ui) {
+        let Some(edge_bundle) = &self.ui_state.edge_bundle else {
+            return;
+        };
+        let mut close = false;
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let label_context =
+                LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
+            let node_label = |node_index: IriIndex| {
+                rdf_data
+                    .node_data
+                    .get_node_by_index(node_index)
+                    .map(|(iri, node)| {
+                        node.node_label(
+                            iri,
+                            &self.visualization_style,
+                            true,
+                            label_context.language_index,
+                            &rdf_data.node_data.indexers,
+                        )
+                        .to_string()
+                    })
+                    .unwrap_or_default()
+            };
+            egui::Window::new("Edge Bundle").collapsible(false).resizable(true).show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "{} edges between {} and {}",
+                    edge_bundle.predicates.len(),
+                    node_label(edge_bundle.from),
+                    node_label(edge_bundle.to)
+                ));
+                ui.separator();
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for (predicate, forward) in edge_bundle.predicates.iter() {
+                        let predicate_label = rdf_data.node_data.predicate_display(
+                            *predicate,
+                            &label_context,
+                            &rdf_data.node_data.indexers,
+                        );
+                        ui.label(format!("{} {}", if *forward { "->" } else { "<-" }, predicate_label.as_str()));
+                    }
+                });
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        }
+        if close {
+            self.ui_state.edge_bundle = None;
+        }
+    }
+
     pub fn display_node_details(&mut self, ui: &mut egui::Ui) -> NodeAction {
         let mut node_to_click = NodeAction::None;
+        let mut node_edit_action = NodeEditAction::None;
         if let Some(iri_index) = &self.ui_state.selected_node {
             if let Some(node_pos) = self.visible_nodes.get_pos(*iri_index) {
                 if let Ok(rdf_data) = self.rdf_data.read() {
@@ -355,7 +679,7 @@ Expand Relations - double click on node",
                             ui.strong("Types:");
                             let label_context = LabelContext::new(
                                 self.ui_state.display_language,
-                                self.persistent_data.config_data.iri_display,
+                                self.config.iri_display,
                                 &rdf_data.prefix_manager,
                             );
                             for type_index in &current_node.types {
@@ -373,11 +697,12 @@ Expand Relations - double click on node",
                             }
                         });
                         let mut check_lock: Option<bool> = None;
-                        if let Ok(node_positions) = self.visible_nodes.positions.read() {
+                        if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                            let node_positions = &layout.positions;
                             if let Some(position) = node_positions.get(node_pos) {
                                 if ui
-                                    .selectable_label(position.locked, ICON_KEY)
-                                    .on_hover_text("Lock Position for Layout")
+                                    .selectable_label(position.locked, ICON_PIN)
+                                    .on_hover_text("Pin Position for Layout")
                                     .clicked()
                                 {
                                     check_lock = Some(!position.locked);
@@ -385,12 +710,48 @@ Expand Relations - double click on node",
                             }
                         }
                         if let Some(check_lock) = check_lock {
-                            if let Ok(mut node_positions) = self.visible_nodes.positions.write() {
+                            if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                                let node_positions = &mut layout.positions;
                                 if let Some(position) = node_positions.get_mut(node_pos) {
                                     position.locked = check_lock;
                                 }
                             }
                         }
+                        if !self.ui_state.read_only
+                            && ui
+                                .selectable_label(self.ui_state.node_edit.is_some(), "✏ Edit")
+                                .on_hover_text("Edit the data properties of the node")
+                                .clicked()
+                        {
+                            self.ui_state.node_edit = match self.ui_state.node_edit {
+                                Some(_) => None,
+                                None => Some(NodeEdit::default()),
+                            };
+                        }
+                        if let Ok(mut layout) = self.visible_nodes.layout_data.write()
+                            && let Some(node_style) = layout.individual_node_styles.get_mut(node_pos)
+                        {
+                            ui.horizontal(|ui| {
+                                ui.label("Semantic zoom up to:");
+                                if ui
+                                    .add(egui::DragValue::new(&mut node_style.semantic_zoom_interval.to).range(0..=10))
+                                    .on_hover_text("Highest zoom magnitude where the node is visible (0 - always visible)")
+                                    .changed()
+                                {
+                                    node_style.semantic_zoom_interval.from = 0;
+                                    node_style.semantic_zoom_manual = true;
+                                    self.visible_nodes.has_semantic_zoom = true;
+                                }
+                                if node_style.semantic_zoom_manual
+                                    && ui
+                                        .button("Auto")
+                                        .on_hover_text("Computed semantic zoom can change the value again")
+                                        .clicked()
+                                {
+                                    node_style.semantic_zoom_manual = false;
+                                }
+                            });
+                        }
                         ui.add_space(10.0);
                         if !current_node.properties.is_empty() {
                             let available_width = (ui.available_width() - 100.0).max(400.0);
@@ -401,11 +762,13 @@ Expand Relations - double click on node",
                                 .show(ui, |ui| {
                                     let label_context = LabelContext::new(
                                         self.ui_state.display_language,
-                                        self.persistent_data.config_data.iri_display,
+                                        self.config.iri_display,
                                         &rdf_data.prefix_manager,
                                     );
-                                    for (predicate_index, prop_value) in &current_node.properties {
-                                        if self.persistent_data.config_data.suppress_other_language_data {
+                                    for (position, (predicate_index, prop_value)) in
+                                        current_node.properties.iter().enumerate()
+                                    {
+                                        if self.config.suppress_other_language_data {
                                             if let Literal::LangString(lang, _) = prop_value {
                                                 if *lang != self.ui_state.display_language {
                                                     if *lang == 0 && self.ui_state.display_language != 0 {
@@ -448,11 +811,53 @@ Expand Relations - double click on node",
                                         }
                                         lab_button_response
                                             .on_hover_text("Set this property as label for the node type");
-                                        ui.label(prop_value.as_str_ref(&rdf_data.node_data.indexers));
+                                        let value = prop_value.as_str_ref(&rdf_data.node_data.indexers);
+                                        match &mut self.ui_state.node_edit {
+                                            Some(node_edit) => edit_property_value(
+                                                ui,
+                                                node_edit,
+                                                *iri_index,
+                                                position,
+                                                value,
+                                                &mut node_edit_action,
+                                            ),
+                                            None => {
+                                                ui.label(value);
+                                            }
+                                        }
                                         ui.end_row();
                                     }
                                 });
                         }
+                        if let Some(node_edit) = &mut self.ui_state.node_edit {
+                            add_property_ui(ui, node_edit, &mut node_edit_action);
+                            change_set_ui(ui, &rdf_data.node_data.change_set, &mut node_edit_action);
+                        }
+                        if self.visible_nodes.flatten_blank_nodes {
+                            let blank_node_trees = blank_node_trees(&rdf_data.node_data, current_node);
+                            if !blank_node_trees.is_empty() {
+                                ui.add_space(10.0);
+                                ui.strong("Blank Nodes:");
+                                let label_context = LabelContext::new(
+                                    self.ui_state.display_language,
+                                    self.config.iri_display,
+                                    &rdf_data.prefix_manager,
+                                );
+                                let mut reference_action = ReferenceAction::None;
+                                show_blank_node_trees(
+                                    ui,
+                                    &blank_node_trees,
+                                    &rdf_data.node_data,
+                                    &self.visualization_style,
+                                    self.config.short_iri,
+                                    &label_context,
+                                    &mut reference_action,
+                                );
+                                if let ReferenceAction::ShowNode(node_index) = reference_action {
+                                    node_to_click = NodeAction::BrowseNode(node_index);
+                                }
+                            }
+                        }
                         if !current_node.references.is_empty() {
                             ui.add_space(10.0);
                             ui.strong("References");
@@ -479,7 +884,7 @@ Expand Relations - double click on node",
                             }
                             let label_context = LabelContext::new(
                                 self.ui_state.display_language,
-                                self.persistent_data.config_data.iri_display,
+                                self.config.iri_display,
                                 &rdf_data.prefix_manager,
                             );
                             for reference_index in references.iter() {
@@ -500,7 +905,7 @@ Expand Relations - double click on node",
                                         npos.add_many(
                                             &mut self.visible_nodes,
                                             &nodes_to_add,
-                                            &self.persistent_data.config_data,
+                                            &self.config,
                                         );
                                         if !npos.is_empty() {
                                             update_layout_edges(
@@ -511,7 +916,7 @@ Expand Relations - double click on node",
                                             );
                                             npos.position(&mut self.visible_nodes);
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
@@ -527,7 +932,8 @@ Expand Relations - double click on node",
                                     // ext_button.show_tooltip_text("Extend this relation for all visible nodes");
                                     if ext_button.clicked() {
                                         let mut nodes_to_add: Vec<(IriIndex, IriIndex)> = Vec::new();
-                                        for visible_index in self.visible_nodes.nodes.read().unwrap().iter() {
+                                        for visible_index in self.visible_nodes.layout_data.read().unwrap().nodes.iter()
+                                        {
                                             let visible_node =
                                                 rdf_data.node_data.get_node_by_index(visible_index.node_index);
                                             if let Some((_v_node_iri, visible_node)) = visible_node {
@@ -542,7 +948,7 @@ Expand Relations - double click on node",
                                         npos.add_many(
                                             &mut self.visible_nodes,
                                             &nodes_to_add,
-                                            &self.persistent_data.config_data,
+                                            &self.config,
                                         );
                                         if !npos.is_empty() {
                                             update_layout_edges(
@@ -553,7 +959,7 @@ Expand Relations - double click on node",
                                             );
                                             npos.position(&mut self.visible_nodes);
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
@@ -566,11 +972,14 @@ Expand Relations - double click on node",
                                         // show_but.show_tooltip_text("Show all relations of this type");
                                         if show_but.clicked() {
                                             self.ui_state.hidden_predicates.remove(*reference_index);
-                                            if let Ok(mut edges) = self.visible_nodes.edges.write() {
-                                                update_edges_groups(&mut edges, &self.ui_state.hidden_predicates);
+                                            if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                                                update_edges_groups(
+                                                    &mut layout.edges,
+                                                    &self.ui_state.hidden_predicates,
+                                                );
                                             }
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
@@ -579,11 +988,14 @@ Expand Relations - double click on node",
                                         // hide_but.show_tooltip_text("Hide all relations of this type");
                                         if hide_but.clicked() {
                                             self.ui_state.hidden_predicates.add(*reference_index);
-                                            if let Ok(mut edges) = self.visible_nodes.edges.write() {
-                                                update_edges_groups(&mut edges, &self.ui_state.hidden_predicates);
+                                            if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                                                update_edges_groups(
+                                                    &mut layout.edges,
+                                                    &self.ui_state.hidden_predicates,
+                                                );
                                             }
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
@@ -617,7 +1029,7 @@ Expand Relations - double click on node",
                             }
                             let label_context = LabelContext::new(
                                 self.ui_state.display_language,
-                                self.persistent_data.config_data.iri_display,
+                                self.config.iri_display,
                                 &rdf_data.prefix_manager,
                             );
                             for reference_index in references.iter() {
@@ -638,7 +1050,7 @@ Expand Relations - double click on node",
                                         npos.add_many(
                                             &mut self.visible_nodes,
                                             &nodes_to_add,
-                                            &self.persistent_data.config_data,
+                                            &self.config,
                                         );
                                         if !npos.is_empty() {
                                             update_layout_edges(
@@ -649,7 +1061,7 @@ Expand Relations - double click on node",
                                             );
                                             npos.position(&mut self.visible_nodes);
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
@@ -663,7 +1075,7 @@ Expand Relations - double click on node",
                                     }
                                     if ui.button("➕").clicked() {
                                         let mut nodes_to_add: Vec<(IriIndex, IriIndex)> = Vec::new();
-                                        for node_layout in self.visible_nodes.nodes.read().unwrap().iter() {
+                                        for node_layout in self.visible_nodes.layout_data.read().unwrap().nodes.iter() {
                                             let visible_node =
                                                 rdf_data.node_data.get_node_by_index(node_layout.node_index);
                                             if let Some((_, visible_node)) = visible_node {
@@ -678,7 +1090,7 @@ Expand Relations - double click on node",
                                         npos.add_many(
                                             &mut self.visible_nodes,
                                             &nodes_to_add,
-                                            &self.persistent_data.config_data,
+                                            &self.config,
                                         );
                                         if !npos.is_empty() {
                                             update_layout_edges(
@@ -689,7 +1101,7 @@ Expand Relations - double click on node",
                                             );
                                             npos.position(&mut self.visible_nodes);
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
@@ -700,21 +1112,24 @@ Expand Relations - double click on node",
                                     if self.ui_state.hidden_predicates.contains(*reference_index) {
                                         if ui.button("👁").clicked() {
                                             self.ui_state.hidden_predicates.remove(*reference_index);
-                                            if let Ok(mut edges) = self.visible_nodes.edges.write() {
-                                                update_edges_groups(&mut edges, &self.ui_state.hidden_predicates);
+                                            if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                                                update_edges_groups(
+                                                    &mut layout.edges,
+                                                    &self.ui_state.hidden_predicates,
+                                                );
                                             }
                                             self.visible_nodes.start_layout(
-                                                &self.persistent_data.config_data,
+                                                &self.config,
                                                 &self.ui_state.hidden_predicates,
                                             );
                                         }
                                     } else if ui.button("❌").clicked() {
                                         self.ui_state.hidden_predicates.add(*reference_index);
-                                        if let Ok(mut edges) = self.visible_nodes.edges.write() {
-                                            update_edges_groups(&mut edges, &self.ui_state.hidden_predicates);
+                                        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                                            update_edges_groups(&mut layout.edges, &self.ui_state.hidden_predicates);
                                         }
                                         self.visible_nodes.start_layout(
-                                            &self.persistent_data.config_data,
+                                            &self.config,
                                             &self.ui_state.hidden_predicates,
                                         );
                                     }
@@ -727,14 +1142,39 @@ Expand Relations - double click on node",
         } else {
             ui.label("no node selected");
         }
+        if let Some(node_index) = self.ui_state.selected_node {
+            self.apply_node_edit_action(ui.ctx(), node_index, node_edit_action);
+        }
         node_to_click
     }
 
-    pub fn display_graph(&mut self, ui: &mut egui::Ui, node_browse: &mut NodeAction) {
+    /// Minimap with the layout positions of all and of selected nodes, only if some nodes are outside of the visible part
+    fn graph_minimap(&self, graph_rect: Rect) -> Option<(Minimap, Vec<Pos2>, Vec<Pos2>)> {
+        if !self.ui_state.show_minimap {
+            return None;
+        }
+        let layout = self.visible_nodes.layout_data.read().ok()?;
+        let layout_positions: Vec<Pos2> = layout.positions.iter().map(|position| position.pos).collect();
+        let bounds = points_bounds(&layout_positions)?;
+        let view_rect = self.graph_state.scene_rect.translate(-SCENE_CENTER.to_vec2());
+        if view_rect.contains_rect(bounds) {
+            return None;
+        }
+        let selected = self
+            .ui_state
+            .selected_nodes
+            .iter()
+            .filter_map(|node_index| layout.nodes.binary_search_by(|e| e.node_index.cmp(node_index)).ok())
+            .filter_map(|pos| layout.positions.get(pos).map(|position| position.pos))
+            .collect();
+        Some((Minimap::new(graph_rect, bounds), layout_positions, selected))
+    }
+
+    pub fn display_graph(&mut self, ui: &mut egui::Ui, node_browse: &mut NodeAction, view_actions: &[ViewAction]) {
         let mut node_count = 0;
         let mut edge_count = 0;
         let mut secondary_clicked = false;
-        let mut is_shift_down = false;
+        let mut extend_selection = false;
         let mut single_clicked = false;
         let mut double_clicked = false;
         let mut primary_down = false;
@@ -742,20 +1182,44 @@ Expand Relations - double click on node",
         let mut was_context_click = false;
         let mut node_to_click: Option<IriIndex> = None;
         let mut node_to_hover: Option<IriIndex> = None;
+        // node under the mouse pointer, node_to_hover falls back to the selected node
+        let mut pointer_hovered_node: Option<IriIndex> = None;
         let mut was_action = false;
         let mut start_translate = false;
 
         let ctx = &ui.ctx().clone();
         let global_mouse_pos = ctx.pointer_hover_pos().unwrap_or(Pos2::new(0.0, 0.0));
         let global_rect = ui.min_rect();
+        let visible_node_count = self
+            .visible_nodes
+            .layout_data
+            .read()
+            .map_or(0, |layout| layout.nodes.len());
+        self.update_render_simplification(visible_node_count, ctx.input(|i| i.time));
+        self.update_size_mapping();
+        // the exported image is painted with full details
+        let png_export_request = self.png_export_request.take();
+        let saved_simplification = self.ui_state.render_simplification;
+        if png_export_request.is_some() {
+            self.ui_state.render_simplification = RenderSimplification::default();
+        }
+        let render_simplification = self.ui_state.render_simplification;
+        let mut png_shapes: Option<(Vec<ClippedShape>, Rect)> = None;
 
-        let scene = egui::Scene::new().zoom_range(0.1..=4.0);
+        let scene = egui::Scene::new().zoom_range(0.1..=4.0).max_inner_size(Vec2::splat(SCENE_SIZE));
         let popup_id = ui.make_persistent_id("node_context_menu");
         let is_context_menu_open = Popup::is_id_open(ctx, popup_id);
         let mut translation_vec: Option<Vec2> = None;
         let mut zoom: Option<f32> = None;
+        // (slot, save)
+        let mut bookmark_action: Option<(usize, bool)> = None;
         let mut put_selection_rect: Option<Rect> = None;
+        // node from which a new reference was dragged, if the mouse is released
+        let mut released_link: Option<IriIndex> = None;
         let mut scaling: f32 = 1.0;
+        let graph_rect = ui.available_rect_before_wrap();
+        let minimap = self.graph_minimap(graph_rect);
+        let over_minimap = minimap.as_ref().is_some_and(|(minimap, _, _)| minimap.rect.contains(global_mouse_pos));
 
         if let Ok(rdf_data) = self.rdf_data.read() {
             scene.show(ui, &mut self.graph_state.scene_rect, |ui| {
@@ -787,14 +1251,22 @@ Expand Relations - double click on node",
                     primary_down = input.pointer.button_pressed(egui::PointerButton::Primary)
                         && global_rect.contains(global_mouse_pos);
                     secondary_down = input.pointer.button_pressed(egui::PointerButton::Secondary);
-                    is_shift_down = input.modifiers.shift;
+                    if over_minimap {
+                        single_clicked = false;
+                        secondary_clicked = false;
+                        double_clicked = false;
+                        primary_down = false;
+                        secondary_down = false;
+                    }
+                    extend_selection = input.modifiers.shift || input.modifiers.command;
                     if input.pointer.button_released(egui::PointerButton::Primary) {
                         self.ui_state.node_to_drag = None;
+                        released_link = self.ui_state.link_drag.take();
                         if let Some(selection_start_rect) = self.ui_state.selection_start_rect.take() {
                             let sel_rect = Rect::from_two_pos(selection_start_rect, mouse_pos);
                             if sel_rect.size().min_elem() > 5.0 {
                                 put_selection_rect = Some(sel_rect);
-                                if !is_shift_down {
+                                if !extend_selection {
                                     self.ui_state.selected_node = None;
                                     self.ui_state.selected_nodes.clear();
                                 }
@@ -832,34 +1304,36 @@ Expand Relations - double click on node",
                                 translation_vec = Some(Vec2::new(-20.0, 0.0))
                             }
                         }
-                        if input.key_pressed(egui::Key::PageUp) {
+                        for (slot, key) in BOOKMARK_KEYS.iter().enumerate() {
+                            if input.key_pressed(*key) {
+                                if input.modifiers.command {
+                                    bookmark_action = Some((slot, true));
+                                } else if input.modifiers.is_none() {
+                                    bookmark_action = Some((slot, false));
+                                }
+                            }
+                        }
+                        if view_actions.contains(&ViewAction::ZoomIn) {
                             zoom = Some(0.9);
-                        } else if input.key_pressed(egui::Key::PageDown) {
+                        } else if view_actions.contains(&ViewAction::ZoomOut) {
                             zoom = Some(1.1);
                         }
-                        if input.modifiers.command && input.key_pressed(Key::A) {
+                        if view_actions.contains(&ViewAction::SelectAll) {
                             self.visible_nodes.select_all(&mut self.ui_state);
                         }
-                        // There is currently not defined key for opening context menu so use Shift + F10
-                        if input.modifiers.shift && input.key_pressed(egui::Key::F10) {
+                        if view_actions.contains(&ViewAction::ContextMenu) {
                             if let Some(selected_index) = self.ui_state.selected_node {
-                                if let Ok(pos) = self
-                                    .visible_nodes
-                                    .nodes
-                                    .read()
-                                    .unwrap()
-                                    .binary_search_by(|e| e.node_index.cmp(&selected_index))
+                                if let Ok(layout) = self.visible_nodes.layout_data.read()
+                                    && let Ok(pos) =
+                                        layout.nodes.binary_search_by(|e| e.node_index.cmp(&selected_index))
+                                    && let Some(transform) = transform
                                 {
-                                    if let Ok(positions) = self.visible_nodes.positions.read() {
-                                        if let Some(transform) = transform {
-                                            was_context_click = true;
-                                            was_action = true;
-                                            let pos = transform * (center + positions[pos].pos.to_vec2());
-                                            self.ui_state.context_menu_pos = pos;
-                                            self.ui_state.context_menu_opened_by_keyboard = true;
-                                            self.ui_state.context_menu_node = Some(selected_index);
-                                        }
-                                    }
+                                    was_context_click = true;
+                                    was_action = true;
+                                    let pos = transform * (center + layout.positions[pos].pos.to_vec2());
+                                    self.ui_state.context_menu_pos = pos;
+                                    self.ui_state.context_menu_opened_by_keyboard = true;
+                                    self.ui_state.context_menu_node = Some(selected_index);
                                 }
                             }
                         }
@@ -867,38 +1341,38 @@ Expand Relations - double click on node",
                 });
                 let label_context = LabelContext::new(
                     self.ui_state.display_language,
-                    self.persistent_data.config_data.iri_display,
+                    self.config.iri_display,
                     &rdf_data.prefix_manager,
                 );
-                edge_count += self.visible_nodes.edges.read().unwrap().len() as u32;
+                edge_count += self.visible_nodes.layout_data.read().unwrap().edges.len() as u32;
                 let mut selected_related_nodes_pos = Vec::new();
                 // draw all edges
                 // we draw the edges first so the nodes are on top of them
-                if self.visible_nodes.show_orthogonal
-                    && let Some(orth_edges) = &self.visible_nodes.orth_edges
-                {
+                let orth_edges =
+                    self.visible_nodes.orth_edges.as_ref().filter(|_| self.visible_nodes.show_orthogonal);
+                if let Some(orth_edges) = orth_edges {
                     if self.ui_state.fade_unselected {
                         if let Some(selected_node) = &self.ui_state.selected_node {
-                            if let Ok(nodes) = self.visible_nodes.nodes.read() {
-                                if let Ok(edges) = self.visible_nodes.edges.read() {
-                                    let selected_pos = nodes.binary_search_by(|e| e.node_index.cmp(selected_node));
-                                    if let Ok(selected_pos) = selected_pos {
-                                        selected_related_nodes_pos.push(selected_pos);
-                                        for edge in edges.iter() {
-                                            if edge.from == selected_pos {
-                                                selected_related_nodes_pos.push(edge.to);
-                                            } else if edge.to == selected_pos {
-                                                selected_related_nodes_pos.push(edge.from);
-                                            }
+                            if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                                let LayoutData { nodes, edges, .. } = &*layout;
+                                let selected_pos = nodes.binary_search_by(|e| e.node_index.cmp(selected_node));
+                                if let Ok(selected_pos) = selected_pos {
+                                    selected_related_nodes_pos.push(selected_pos);
+                                    for edge in edges.iter() {
+                                        if edge.from == selected_pos {
+                                            selected_related_nodes_pos.push(edge.to);
+                                        } else if edge.to == selected_pos {
+                                            selected_related_nodes_pos.push(edge.from);
                                         }
                                     }
-                                    selected_related_nodes_pos.sort_unstable();
-                                    selected_related_nodes_pos.dedup();
                                 }
+                                selected_related_nodes_pos.sort_unstable();
+                                selected_related_nodes_pos.dedup();
                             }
                         }
                     }
-                    if let Ok(individual_node_styles) = self.visible_nodes.individual_node_styles.read() {
+                    if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                        let individual_node_styles = &layout.individual_node_styles;
                         for orth_edge in orth_edges.edges.iter() {
                             if self.visible_nodes.has_semantic_zoom {
                                 if !individual_node_styles[orth_edge.from_node]
@@ -921,338 +1395,515 @@ Expand Relations - double click on node",
                             let edge_style = self
                                 .visualization_style
                                 .get_edge_syle(orth_edge.predicate, ui.visuals().dark_mode);
-                            let (arrow_pos, arrow_pre) = if orth_edge.from_node < orth_edge.to_node {
-                                let len = points.len();
-                                (points[len - 1], points[len - 2])
-                            } else {
-                                (points[0], points[1])
-                            };
-                            let arrow_unit = (arrow_pos - arrow_pre).normalized();
-                            let arrow_angle = std::f32::consts::PI / 6.0; // 30 degrees
-
-                            // Rotate vector by ±arrow_angle to get arrowhead points
-                            let cos_theta = arrow_angle.cos();
-                            let sin_theta = arrow_angle.sin();
-                            let arrow_size = edge_style.arrow_size;
-
-                            let left = arrow_pos
-                                - arrow_size
-                                    * Vec2::new(
-                                        cos_theta * arrow_unit.x - sin_theta * arrow_unit.y,
-                                        sin_theta * arrow_unit.x + cos_theta * arrow_unit.y,
-                                    );
-                            let right = arrow_pos
-                                - arrow_size
-                                    * Vec2::new(
-                                        cos_theta * arrow_unit.x + sin_theta * arrow_unit.y,
-                                        -sin_theta * arrow_unit.x + cos_theta * arrow_unit.y,
-                                    );
-
-                            let stroke = egui::Stroke::new(edge_style.width, fade_color(edge_style.color, faded));
-                            // Draw arrowhead lines
-                            match edge_style.target_style {
-                                ArrowStyle::Arrow => {
-                                    painter.line_segment([arrow_pos, left], stroke);
-                                    painter.line_segment([arrow_pos, right], stroke);
-                                }
-                                ArrowStyle::ArrorTriangle => {
-                                    painter.line_segment([arrow_pos, left], stroke);
-                                    painter.line_segment([arrow_pos, right], stroke);
-                                    painter.line_segment([left, right], stroke);
-                                }
-                                ArrowStyle::ArrorFilled => {
-                                    let shape = Shape::convex_polygon(
-                                        vec![arrow_pos, left, right],
-                                        edge_style.color,
-                                        Stroke::NONE,
-                                    );
-                                    painter.add(shape);
+                            let len = points.len();
+                            let ((target, target_pre), (source, source_pre)) =
+                                if orth_edge.from_node < orth_edge.to_node {
+                                    ((points[len - 1], points[len - 2]), (points[0], points[1]))
+                                } else {
+                                    ((points[0], points[1]), (points[len - 1], points[len - 2]))
+                                };
+                            draw_edge_line(painter, &points, edge_style, faded);
+                            // the middle of an orthogonal edge has no direction, so the arrow is drawn at the target
+                            if edge_style.arrow_location != ArrowLocation::None {
+                                draw_arrow_head(painter, target, (target - target_pre).normalized(), edge_style, faded);
+                            }
+                            if edge_style.arrow_location == ArrowLocation::Both {
+                                draw_arrow_head(painter, source, (source - source_pre).normalized(), edge_style, faded);
+                            }
+                        }
+                    }
+                }
+                if orth_edges.is_none_or(|orth_edges| orth_edges.is_partial())
+                    && let Ok(layout) = self.visible_nodes.layout_data.read()
+                {
+                    let LayoutData {
+                        nodes,
+                        edges,
+                        positions,
+                        node_shapes,
+                        individual_node_styles,
+                    } = &*layout;
+                    if self.ui_state.fade_unselected
+                        && let Some(selected_node) = &self.ui_state.selected_node
+                    {
+                        let selected_pos = nodes.binary_search_by(|e| e.node_index.cmp(selected_node));
+                        if let Ok(selected_pos) = selected_pos {
+                            selected_related_nodes_pos.push(selected_pos);
+                            for edge in edges.iter() {
+                                if edge.from == selected_pos {
+                                    selected_related_nodes_pos.push(edge.to);
+                                } else if edge.to == selected_pos {
+                                    selected_related_nodes_pos.push(edge.from);
                                 }
                             }
-                            let line = egui::Shape::line(points, stroke);
-                            painter.add(line);
                         }
+                        selected_related_nodes_pos.sort_unstable();
+                        selected_related_nodes_pos.dedup();
                     }
-                } else {
-                    if let Ok(nodes) = self.visible_nodes.nodes.read() {
-                        if let Ok(positions) = self.visible_nodes.positions.read() {
-                            if let Ok(individual_node_styles) = self.visible_nodes.individual_node_styles.read() {
-                                if let Ok(edges) = self.visible_nodes.edges.read() {
-                                    if self.ui_state.fade_unselected {
-                                        if let Some(selected_node) = &self.ui_state.selected_node {
-                                            let selected_pos =
-                                                nodes.binary_search_by(|e| e.node_index.cmp(selected_node));
-                                            if let Ok(selected_pos) = selected_pos {
-                                                selected_related_nodes_pos.push(selected_pos);
-                                                for edge in edges.iter() {
-                                                    if edge.from == selected_pos {
-                                                        selected_related_nodes_pos.push(edge.to);
-                                                    } else if edge.to == selected_pos {
-                                                        selected_related_nodes_pos.push(edge.from);
-                                                    }
-                                                }
-                                            }
-                                            selected_related_nodes_pos.sort_unstable();
-                                            selected_related_nodes_pos.dedup();
-                                        }
-                                    }
-                                    if let Ok(node_shapes) = self.visible_nodes.node_shapes.read() {
-                                        for edge in edges.iter() {
-                                            if self.ui_state.hidden_predicates.contains(edge.predicate) {
-                                                continue;
-                                            }
-                                            if self.visible_nodes.has_semantic_zoom {
-                                                if !individual_node_styles[edge.from]
-                                                    .semantic_zoom_interval
-                                                    .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                                    || !individual_node_styles[edge.to]
-                                                        .semantic_zoom_interval
-                                                        .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                                {
-                                                    continue;
-                                                }
-                                            }
-
-                                            let node_label = || {
-                                                let reference_label = rdf_data.node_data.predicate_display(
-                                                    edge.predicate,
-                                                    &label_context,
+                    // drawing many edge labels is slow and the labels are not readable anyway
+                    let show_edge_labels = scaling >= self.config.edge_label_min_zoom
+                        && !render_simplification.hide_edge_labels
+                        && (self.config.edge_label_max_edges == 0 || edges.len() <= self.config.edge_label_max_edges);
+                    let view_rect = painter.clip_rect();
+                    let bundles = if self.ui_state.bundle_edges {
+                        edge_bundles(
+                            edges,
+                            &self.ui_state.hidden_predicates,
+                            self.config.edge_bundle_threshold,
+                        )
+                    } else {
+                        HashMap::new()
+                    };
+                    for (edge_index, edge) in edges.iter().enumerate() {
+                        if self.ui_state.hidden_predicates.contains(edge.predicate) {
+                            continue;
+                        }
+                        if let Some(orth_edges) = orth_edges
+                            && orth_edges.is_routed(edge.from, edge.to)
+                        {
+                            continue;
+                        }
+                        if self.visible_nodes.has_semantic_zoom
+                            && (!individual_node_styles[edge.from]
+                                .semantic_zoom_interval
+                                .is_visible(self.ui_state.semantic_zoom_magnitude)
+                                || !individual_node_styles[edge.to]
+                                    .semantic_zoom_interval
+                                    .is_visible(self.ui_state.semantic_zoom_magnitude))
+                        {
+                            continue;
+                        }
+                        if let Some(bundle) = bundles.get(&edge_bundle_key(edge)) {
+                            // the whole bundle is drawn for its first edge
+                            if bundle[0] == edge_index {
+                                let badge_rect = draw_edge_bundle(
+                                    painter,
+                                    center + positions[edge.from].pos.to_vec2(),
+                                    node_shapes[edge.from].size,
+                                    node_shapes[edge.from].node_shape,
+                                    center + positions[edge.to].pos.to_vec2(),
+                                    node_shapes[edge.to].size,
+                                    node_shapes[edge.to].node_shape,
+                                    bundle.len(),
+                                    ui.visuals(),
+                                );
+                                if single_clicked && badge_rect.contains(mouse_pos) {
+                                    self.ui_state.edge_bundle = Some(EdgeBundle {
+                                        from: nodes[edge.from].node_index,
+                                        to: nodes[edge.to].node_index,
+                                        predicates: bundle
+                                            .iter()
+                                            .map(|index| (edges[*index].predicate, edges[*index].from == edge.from))
+                                            .collect(),
+                                    });
+                                    was_action = true;
+                                }
+                            }
+                            continue;
+                        }
+                        let edge_key = (nodes[edge.from].node_index, edge.predicate, nodes[edge.to].node_index);
+                        if let Some(wikidata_statements) = &self.wikidata_statements
+                            && wikidata_statements.truthy_only
+                            && !wikidata_statements.is_truthy(&edge_key)
+                        {
+                            continue;
+                        }
+                        if let Some(graph_filter) = &self.ui_state.graph_filter
+                            && !graph_filter.contains_reference(&rdf_data.node_data.named_graphs, &edge_key)
+                        {
+                            continue;
+                        }
+                        let show_label = show_edge_labels
+                            && (!self.config.edge_labels_selected_only
+                                || self.ui_state.selected_nodes.contains(&edge_key.0)
+                                || self.ui_state.selected_nodes.contains(&edge_key.2));
+                        // qualifiers of collapsed wikidata statements are shown as part of the label
+                        let annotation = match &self.wikidata_statements {
+                            Some(wikidata_statements) if show_label && wikidata_statements.show_qualifiers => {
+                                wikidata_statements.annotation(
+                                    &edge_key,
+                                    &rdf_data.node_data,
+                                    &label_context,
+                                    |node_index| {
+                                        rdf_data
+                                            .node_data
+                                            .get_node_by_index(node_index)
+                                            .map(|(iri, node)| {
+                                                node.node_label(
+                                                    iri,
+                                                    &self.visualization_style,
+                                                    true,
+                                                    label_context.language_index,
                                                     &rdf_data.node_data.indexers,
-                                                );
-                                                reference_label.as_str().to_owned()
-                                            };
-                                            let pos1 = center + positions[edge.from].pos.to_vec2();
-                                            if edge.from != edge.to {
-                                                let node_shape_from = &node_shapes[edge.from];
-                                                let node_shape_to = &node_shapes[edge.to];
-                                                let pos2 = center + positions[edge.to].pos.to_vec2();
-                                                let faded = !selected_related_nodes_pos.is_empty()
-                                                    && !(selected_related_nodes_pos.binary_search(&edge.from).is_ok()
-                                                        && selected_related_nodes_pos.binary_search(&edge.to).is_ok());
-                                                draw_edge(
-                                                    painter,
-                                                    pos1,
-                                                    node_shape_from.size,
-                                                    node_shape_from.node_shape,
-                                                    pos2,
-                                                    node_shape_to.size,
-                                                    node_shape_to.node_shape,
-                                                    self.visualization_style
-                                                        .get_edge_syle(edge.predicate, ui.visuals().dark_mode),
-                                                    node_label,
-                                                    faded,
-                                                    edge.bezier_distance,
-                                                    ui.visuals(),
-                                                );
-                                            } else {
-                                                let faded = !selected_related_nodes_pos.is_empty()
-                                                    && selected_related_nodes_pos.binary_search(&edge.from).is_err();
-                                                let node_shape_from = &node_shapes[edge.from];
-                                                draw_self_edge(
-                                                    painter,
-                                                    pos1,
-                                                    node_shape_from.size,
-                                                    edge.bezier_distance,
-                                                    node_shape_from.node_shape,
-                                                    self.visualization_style
-                                                        .get_edge_syle(edge.predicate, ui.visuals().dark_mode),
-                                                    faded,
-                                                    node_label,
-                                                    ui.visuals(),
-                                                );
-                                            }
-                                        }
-                                    }
+                                                )
+                                                .to_string()
+                                            })
+                                            .unwrap_or_default()
+                                    },
+                                )
+                            }
+                            _ => None,
+                        };
+                        let edge_display = match &self.edge_metadata_style {
+                            Some(edge_metadata_style) if edge_metadata_style.is_active() => edge_metadata_style
+                                .edge_display(
+                                    &edge_key,
+                                    self.visualization_style
+                                        .get_edge_syle(edge.predicate, ui.visuals().dark_mode),
+                                ),
+                            _ => EdgeDisplay::Default,
+                        };
+                        let edge_style = match &edge_display {
+                            EdgeDisplay::Hidden => continue,
+                            EdgeDisplay::Styled(edge_style) => edge_style,
+                            EdgeDisplay::Default => self
+                                .visualization_style
+                                .get_edge_syle(edge.predicate, ui.visuals().dark_mode),
+                        };
+
+                        let node_label = || {
+                            let reference_label = rdf_data.node_data.predicate_display(
+                                edge.predicate,
+                                &label_context,
+                                &rdf_data.node_data.indexers,
+                            );
+                            // members of resolved rdf lists are labeled with their position
+                            let reference_label = match rdf_data
+                                .node_data
+                                .rdf_lists
+                                .member_position(edge_key.0, edge_key.1, edge_key.2)
+                            {
+                                Some(position) => {
+                                    format!("{} [{}]", reference_label.as_str(), position + 1)
                                 }
+                                None => reference_label.as_str().to_owned(),
+                            };
+                            match &annotation {
+                                Some(annotation) => format!("{} ({})", reference_label, annotation),
+                                None => reference_label,
+                            }
+                        };
+                        let pos1 = center + positions[edge.from].pos.to_vec2();
+                        if render_simplification.lod {
+                            // no arrows, curves and self edges, only lines crossing the view
+                            let pos2 = center + positions[edge.to].pos.to_vec2();
+                            if edge.from != edge.to && view_rect.intersects(Rect::from_two_pos(pos1, pos2)) {
+                                let faded = !selected_related_nodes_pos.is_empty()
+                                    && (selected_related_nodes_pos.binary_search(&edge.from).is_err()
+                                        || selected_related_nodes_pos.binary_search(&edge.to).is_err());
+                                painter.line_segment(
+                                    [pos1, pos2],
+                                    Stroke::new(edge_style.width, fade_color(edge_style.color, faded)),
+                                );
                             }
+                        } else if edge.from != edge.to {
+                            let node_shape_from = &node_shapes[edge.from];
+                            let node_shape_to = &node_shapes[edge.to];
+                            let pos2 = center + positions[edge.to].pos.to_vec2();
+                            let faded = !(selected_related_nodes_pos.is_empty()
+                                || (selected_related_nodes_pos.binary_search(&edge.from).is_ok()
+                                    && selected_related_nodes_pos.binary_search(&edge.to).is_ok()));
+                            draw_edge(
+                                painter,
+                                pos1,
+                                node_shape_from.size,
+                                node_shape_from.node_shape,
+                                pos2,
+                                node_shape_to.size,
+                                node_shape_to.node_shape,
+                                edge_style,
+                                node_label,
+                                show_label,
+                                faded,
+                                edge.bezier_distance,
+                                ui.visuals(),
+                            );
+                        } else {
+                            let faded = !selected_related_nodes_pos.is_empty()
+                                && selected_related_nodes_pos.binary_search(&edge.from).is_err();
+                            let node_shape_from = &node_shapes[edge.from];
+                            draw_self_edge(
+                                painter,
+                                pos1,
+                                node_shape_from.size,
+                                edge.bezier_distance,
+                                node_shape_from.node_shape,
+                                edge_style,
+                                faded,
+                                node_label,
+                                show_label,
+                                ui.visuals(),
+                            );
                         }
                     }
                 }
 
+                let mut node_moved = false;
                 if let Some(node_to_drag_index) = &self.ui_state.node_to_drag {
-                    if let Some(node_pos) = self.visible_nodes.get_pos(*node_to_drag_index) {
-                        if let Ok(mut positions) = self.visible_nodes.positions.write() {
-                            positions[node_pos].pos =
-                                (mouse_pos - center - self.ui_state.drag_diff.to_vec2()).to_pos2();
-                            if self.ui_state.selected_nodes.contains(node_to_drag_index)
-                                && self.ui_state.selected_nodes.len() > 1
-                            {
-                                let drag_diff = mouse_pos - self.ui_state.drag_start;
-                                for selected_node in &self.ui_state.selected_nodes {
-                                    if selected_node != node_to_drag_index {
-                                        if let Some(selected_node_pos) = self.visible_nodes.get_pos(*selected_node) {
-                                            positions[selected_node_pos].pos += drag_diff;
-                                        }
-                                    }
+                    if let Ok(mut layout) = self.visible_nodes.layout_data.write()
+                        && let Some(drag_node_pos) = node_pos(&layout.nodes, *node_to_drag_index)
+                    {
+                        let LayoutData { nodes, positions, .. } = &mut *layout;
+                        let new_pos = (mouse_pos - center - self.ui_state.drag_diff.to_vec2()).to_pos2();
+                        node_moved = positions[drag_node_pos].pos != new_pos;
+                        positions[drag_node_pos].pos = new_pos;
+                        if self.ui_state.selected_nodes.contains(node_to_drag_index)
+                            && self.ui_state.selected_nodes.len() > 1
+                        {
+                            let drag_diff = mouse_pos - self.ui_state.drag_start;
+                            for selected_node in &self.ui_state.selected_nodes {
+                                if selected_node != node_to_drag_index
+                                    && let Some(selected_node_pos) = node_pos(nodes, *selected_node)
+                                {
+                                    positions[selected_node_pos].pos += drag_diff;
                                 }
-                                self.ui_state.drag_start = mouse_pos;
                             }
+                            self.ui_state.drag_start = mouse_pos;
                         }
                     }
                 }
+                if node_moved {
+                    self.visible_nodes.invalidate_orthogonal();
+                }
+                self.visible_nodes.update_orthogonal(
+                    &self.ui_state.hidden_predicates,
+                    ctx.input(|input| input.time),
+                    self.ui_state.node_to_drag.is_some(),
+                );
                 // draw all nodes
-                if let Ok(positions) = self.visible_nodes.positions.read() {
-                    if let Ok(nodes) = self.visible_nodes.nodes.read() {
-                        if let Ok(individual_node_style) = self.visible_nodes.individual_node_styles.read() {
-                            let mut next_node_selection = if !matches!(node_selection_move, NodeSelectionMove::None) {
-                                if let Some(selected_index) = self.ui_state.selected_node {
-                                    if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(&selected_index)) {
-                                        NextNodeSelection::new(selected_index, positions[pos].pos, node_selection_move)
-                                    } else {
-                                        // The selected node is not visible anymore just pick the first
-                                        if let Some(first_node) = nodes.first() {
-                                            NextNodeSelection::new(
-                                                first_node.node_index,
-                                                positions[0].pos,
-                                                node_selection_move,
-                                            )
-                                        } else {
-                                            NextNodeSelection::empty()
-                                        }
-                                    }
+                let mut computed_node_shapes = None;
+                if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                    let LayoutData {
+                        nodes,
+                        positions,
+                        individual_node_styles: individual_node_style,
+                        ..
+                    } = &*layout;
+                    let mut next_node_selection = if !matches!(node_selection_move, NodeSelectionMove::None) {
+                        if let Some(selected_index) = self.ui_state.selected_node {
+                            if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(&selected_index)) {
+                                NextNodeSelection::new(selected_index, positions[pos].pos, node_selection_move)
+                            } else {
+                                // The selected node is not visible anymore just pick the first
+                                if let Some(first_node) = nodes.first() {
+                                    NextNodeSelection::new(first_node.node_index, positions[0].pos, node_selection_move)
                                 } else {
-                                    if let Some(first_node) = nodes.first() {
-                                        NextNodeSelection::new(
-                                            first_node.node_index,
-                                            positions[0].pos,
-                                            node_selection_move,
-                                        )
-                                    } else {
-                                        NextNodeSelection::empty()
-                                    }
+                                    NextNodeSelection::empty()
                                 }
+                            }
+                        } else {
+                            if let Some(first_node) = nodes.first() {
+                                NextNodeSelection::new(first_node.node_index, positions[0].pos, node_selection_move)
                             } else {
                                 NextNodeSelection::empty()
-                            };
-                            let mut new_node_shapes: Option<Vec<NodeShapeData>> =
-                                if self.visible_nodes.update_node_shapes {
-                                    Some(Vec::with_capacity(nodes.len()))
-                                } else {
-                                    None
-                                };
-                            for ((node_pos, node_layout), node_position) in
-                                nodes.iter().enumerate().zip(positions.iter())
+                            }
+                        }
+                    } else {
+                        NextNodeSelection::empty()
+                    };
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Some(social_chart) = &mut self.social_chart {
+                        social_chart.receive_photos(ui.ctx());
+                    }
+                    let mut new_node_shapes: Option<Vec<NodeShapeData>> = if self.visible_nodes.update_node_shapes {
+                        Some(Vec::with_capacity(nodes.len()))
+                    } else {
+                        None
+                    };
+                    for ((node_pos, node_layout), node_position) in nodes.iter().enumerate().zip(positions.iter()) {
+                        next_node_selection.consider_node(node_layout.node_index, node_position.pos);
+                        if let Some((object_iri, object)) = rdf_data.node_data.get_node_by_index(node_layout.node_index)
+                        {
+                            if self.visible_nodes.has_semantic_zoom
+                                && !self.visible_nodes.update_node_shapes
+                                && !individual_node_style[node_pos]
+                                    .semantic_zoom_interval
+                                    .is_visible(self.ui_state.semantic_zoom_magnitude)
                             {
-                                next_node_selection.consider_node(node_layout.node_index, node_position.pos);
-                                if let Some((object_iri, object)) =
-                                    rdf_data.node_data.get_node_by_index(node_layout.node_index)
-                                {
-                                    if self.visible_nodes.has_semantic_zoom && !self.visible_nodes.update_node_shapes {
-                                        if !individual_node_style[node_pos]
-                                            .semantic_zoom_interval
-                                            .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                        {
-                                            continue;
-                                        }
-                                    }
-                                    let pos = center + node_position.pos.to_vec2();
-                                    if let Some(put_selection_rect) = put_selection_rect {
-                                        if put_selection_rect.contains(pos) {
-                                            self.ui_state.selected_nodes.insert(node_layout.node_index);
-                                        }
-                                    }
-                                    let faded = !selected_related_nodes_pos.is_empty()
-                                        && selected_related_nodes_pos.binary_search(&node_pos).is_err();
-                                    let (node_rect, node_shape) = draw_node(
-                                        &self.visualization_style,
-                                        individual_node_style.get(node_pos),
-                                        &rdf_data.node_data.indexers,
-                                        &self.ui_state,
-                                        &self.persistent_data.config_data,
-                                        painter,
-                                        object,
-                                        object_iri,
-                                        pos,
-                                        self.ui_state.selected_node == Some(node_layout.node_index)
-                                            || self.ui_state.selected_nodes.contains(&node_layout.node_index),
-                                        false,
-                                        faded,
-                                        ui.visuals(),
-                                    );
-                                    // TODO Error can not refresh nodes_shapes if semantic zoom is enabled.
-                                    // because it will produce too few of them
-                                    // quick fix always compute new shapes if semantic zoom is 0
-                                    if let Some(new_node_shapes) = &mut new_node_shapes {
-                                        new_node_shapes.push(NodeShapeData {
-                                            node_shape,
-                                            size: node_rect.size(),
-                                            ..Default::default()
-                                        });
-                                    }
-                                    if self.ui_state.context_menu_node.is_none() || was_action {
-                                        if single_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
-                                            if is_shift_down
-                                                && self.ui_state.selected_nodes.contains(&node_layout.node_index)
-                                            {
-                                                self.ui_state.selected_nodes.remove(&node_layout.node_index);
-                                                if let Some(selected_index) = self.ui_state.selected_node {
-                                                    if self.ui_state.selected_nodes.is_empty() {
-                                                        self.ui_state.selected_node = None;
-                                                    } else {
-                                                        if self.ui_state.selected_nodes.contains(&selected_index) {
-                                                            self.ui_state.selected_node =
-                                                                self.ui_state.selected_nodes.iter().next().copied();
-                                                        }
-                                                    }
-                                                }
+                                continue;
+                            }
+                            if let Some(graph_filter) = &self.ui_state.graph_filter
+                                && !self.visible_nodes.update_node_shapes
+                                && !graph_filter.contains_node(node_layout.node_index)
+                            {
+                                continue;
+                            }
+                            let pos = center + node_position.pos.to_vec2();
+                            if let Some(put_selection_rect) = put_selection_rect
+                                && put_selection_rect.contains(pos)
+                            {
+                                self.ui_state.selected_nodes.insert(node_layout.node_index);
+                            }
+                            // node shapes are needed for all nodes if they are recomputed
+                            if render_simplification.lod
+                                && new_node_shapes.is_none()
+                                && !painter.clip_rect().expand(100.0).contains(pos)
+                            {
+                                continue;
+                            }
+                            let faded = !selected_related_nodes_pos.is_empty()
+                                && selected_related_nodes_pos.binary_search(&node_pos).is_err();
+                            let (node_rect, node_shape) = draw_node(
+                                &self.visualization_style,
+                                individual_node_style.get(node_pos),
+                                &rdf_data.node_data.indexers,
+                                &self.ui_state,
+                                &self.config,
+                                painter,
+                                object,
+                                object_iri,
+                                pos,
+                                self.ui_state.selected_node == Some(node_layout.node_index)
+                                    || self.ui_state.selected_nodes.contains(&node_layout.node_index),
+                                false,
+                                faded,
+                                node_position.locked,
+                                self.dataset_diff
+                                    .as_ref()
+                                    .and_then(|dataset_diff| dataset_diff.node_status(node_layout.node_index)),
+                                ui.visuals(),
+                            );
+                            if node_shape == NodeShape::Circle
+                                && let Some(social_chart) = &mut self.social_chart
+                                && social_chart.vocabulary.is_person(object)
+                                && let Some(texture_id) = social_chart.photo(
+                                    ui.ctx(),
+                                    node_layout.node_index,
+                                    &rdf_data.node_data,
+                                    &rdf_data.prefix_manager,
+                                )
+                            {
+                                draw_circle_image(
+                                    painter,
+                                    node_rect.center(),
+                                    node_rect.width() / 2.0 - 1.0,
+                                    texture_id,
+                                    faded,
+                                );
+                            }
+                            if let Some(node_style) = individual_node_style.get(node_pos)
+                                && node_style.cluster_size > 0
+                            {
+                                draw_cluster_badge(painter, node_rect, node_style.cluster_size, ui.visuals());
+                            }
+                            if self.visible_nodes.flatten_blank_nodes
+                                && has_blank_node_tree(&rdf_data.node_data, object)
+                            {
+                                draw_composite_outline(
+                                    painter,
+                                    node_rect,
+                                    node_shape,
+                                    fade_color(ui.visuals().text_color(), faded),
+                                );
+                            }
+                            // TODO Error can not refresh nodes_shapes if semantic zoom is enabled.
+                            // because it will produce too few of them
+                            // quick fix always compute new shapes if semantic zoom is 0
+                            if let Some(new_node_shapes) = &mut new_node_shapes {
+                                new_node_shapes.push(NodeShapeData {
+                                    node_shape,
+                                    size: node_rect.size(),
+                                });
+                            }
+                            if self.ui_state.context_menu_node.is_none() || was_action {
+                                if single_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    if extend_selection
+                                        && self.ui_state.selected_nodes.contains(&node_layout.node_index)
+                                    {
+                                        self.ui_state.selected_nodes.remove(&node_layout.node_index);
+                                        if let Some(selected_index) = self.ui_state.selected_node {
+                                            if self.ui_state.selected_nodes.is_empty() {
+                                                self.ui_state.selected_node = None;
                                             } else {
-                                                self.ui_state.selected_node = Some(node_layout.node_index);
-                                                if !is_shift_down {
-                                                    self.ui_state.selected_nodes.clear();
+                                                if self.ui_state.selected_nodes.contains(&selected_index) {
+                                                    self.ui_state.selected_node =
+                                                        self.ui_state.selected_nodes.iter().next().copied();
                                                 }
-                                                self.ui_state.selected_nodes.insert(node_layout.node_index);
                                             }
-                                            was_action = true;
-                                        }
-                                        if primary_down
-                                            && !self.visible_nodes.show_orthogonal
-                                            && is_overlapping(&node_rect, mouse_pos, node_shape)
-                                        {
-                                            self.ui_state.node_to_drag = Some(node_layout.node_index);
-                                            self.ui_state.drag_diff = (mouse_pos - node_rect.center()).to_pos2();
-                                            self.ui_state.drag_start = mouse_pos;
-                                            was_action = true;
-                                        }
-                                        if double_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
-                                            node_to_click = Some(node_layout.node_index);
-                                            was_action = true;
                                         }
-                                        if secondary_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
-                                            was_context_click = true;
-                                            self.ui_state.context_menu_opened_by_keyboard = false;
-                                            self.ui_state.context_menu_pos = global_mouse_pos;
-                                            self.ui_state.context_menu_node = Some(node_layout.node_index);
-                                            was_action = true;
+                                    } else {
+                                        self.ui_state.selected_node = Some(node_layout.node_index);
+                                        if !extend_selection {
+                                            self.ui_state.selected_nodes.clear();
                                         }
+                                        self.ui_state.selected_nodes.insert(node_layout.node_index);
                                     }
-                                    if !was_action && is_overlapping(&node_rect, mouse_pos, node_shape) {
-                                        node_to_hover = Some(node_layout.node_index);
-                                        ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
-                                    } else if secondary_down && self.ui_state.translate_drag.is_none() {
-                                        start_translate = true;
+                                    was_action = true;
+                                }
+                                if primary_down && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    if self.ui_state.authoring_mode {
+                                        self.ui_state.link_drag = Some(node_layout.node_index);
+                                    } else {
+                                        self.ui_state.node_to_drag = Some(node_layout.node_index);
                                     }
-                                    node_count += 1;
+                                    self.ui_state.drag_diff = (mouse_pos - node_rect.center()).to_pos2();
+                                    self.ui_state.drag_start = mouse_pos;
+                                    was_action = true;
                                 }
-                            }
-                            if let Some(new_node_shapes) = new_node_shapes {
-                                if let Ok(mut node_shapes) = self.visible_nodes.node_shapes.write() {
-                                    *node_shapes = new_node_shapes;
-                                    self.visible_nodes.update_node_shapes = false;
+                                if double_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    node_to_click = Some(node_layout.node_index);
+                                    was_action = true;
+                                }
+                                if secondary_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    was_context_click = true;
+                                    self.ui_state.context_menu_opened_by_keyboard = false;
+                                    self.ui_state.context_menu_pos = global_mouse_pos;
+                                    self.ui_state.context_menu_node = Some(node_layout.node_index);
+                                    was_action = true;
                                 }
                             }
-                            if let Some((next_selected, _pos)) = next_node_selection.next_selected {
-                                self.ui_state.selected_node = Some(next_selected);
-                                self.ui_state.selected_nodes.clear();
-                                self.ui_state.selected_nodes.insert(next_selected);
+                            if !was_action && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                node_to_hover = Some(node_layout.node_index);
+                                ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
+                            } else if secondary_down && self.ui_state.translate_drag.is_none() {
+                                start_translate = true;
                             }
+                            node_count += 1;
                         }
                     }
+                    computed_node_shapes = new_node_shapes;
+                    if let Some((next_selected, _pos)) = next_node_selection.next_selected {
+                        self.ui_state.selected_node = Some(next_selected);
+                        self.ui_state.selected_nodes.clear();
+                        self.ui_state.selected_nodes.insert(next_selected);
+                    }
+                }
+                // node shapes are stored after the read lock of the layout is released
+                if let Some(new_node_shapes) = computed_node_shapes {
+                    if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                        layout.node_shapes = new_node_shapes;
+                        self.visible_nodes.update_node_shapes = false;
+                    }
                 }
                 /* TODO Unselect node but only in clicked in the graph area
                 if primary_down && !was_action {
                     self.ui_state.selected_node = None;
                 }
                 */
+                if let Some(hovered_node) = node_to_hover
+                    && self.ui_state.hovered_node != Some(hovered_node)
+                {
+                    self.ui_state.hovered_node = Some(hovered_node);
+                    if self.ui_state.show_incoming_references {
+                        // the panel is drawn before the graph
+                        ctx.request_repaint();
+                    }
+                }
+                pointer_hovered_node = node_to_hover;
+                if let Some(from) = released_link
+                    && let Some(to) = pointer_hovered_node
+                    && from != to
+                {
+                    self.ui_state.new_link = Some(NewLink { from, to });
+                }
+                if let Some(link_drag) = self.ui_state.link_drag
+                    && let Ok(layout) = self.visible_nodes.layout_data.read()
+                    && let Some(node_pos) = node_pos(&layout.nodes, link_drag)
+                {
+                    let from = center + layout.positions[node_pos].pos.to_vec2();
+                    painter.line_segment([from, mouse_pos], ui.visuals().selection.stroke);
+                    painter.circle_filled(mouse_pos, 4.0, ui.visuals().selection.stroke.color);
+                }
                 if node_to_hover.is_none() && self.ui_state.selected_node.is_some() {
                     node_to_hover = self.ui_state.selected_node;
                 }
@@ -1261,24 +1912,36 @@ Expand Relations - double click on node",
                     let node_layout = self.visible_nodes.get_pos(node_to_hover);
                     if let Some(node_pos) = node_layout {
                         if let Some((object_iri, object)) = rdf_data.node_data.get_node_by_index(node_to_hover) {
-                            if let Ok(individual_node_style) = self.visible_nodes.individual_node_styles.read() {
-                                if let Ok(positions) = self.visible_nodes.positions.read() {
-                                    let pos = center + positions[node_pos].pos.to_vec2();
-                                    draw_node(
-                                        &self.visualization_style,
-                                        individual_node_style.get(node_pos),
-                                        &rdf_data.node_data.indexers,
-                                        &self.ui_state,
-                                        &self.persistent_data.config_data,
-                                        painter,
-                                        object,
-                                        object_iri,
-                                        pos,
-                                        self.ui_state.selected_node == Some(node_to_hover),
-                                        true,
-                                        false,
-                                        ui.visuals(),
-                                    );
+                            if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                                let LayoutData {
+                                    positions,
+                                    individual_node_styles: individual_node_style,
+                                    ..
+                                } = &*layout;
+                                let pos = center + positions[node_pos].pos.to_vec2();
+                                let (node_rect, _node_shape) = draw_node(
+                                    &self.visualization_style,
+                                    individual_node_style.get(node_pos),
+                                    &rdf_data.node_data.indexers,
+                                    &self.ui_state,
+                                    &self.config,
+                                    painter,
+                                    object,
+                                    object_iri,
+                                    pos,
+                                    self.ui_state.selected_node == Some(node_to_hover),
+                                    true,
+                                    false,
+                                    positions[node_pos].locked,
+                                    self.dataset_diff
+                                        .as_ref()
+                                        .and_then(|dataset_diff| dataset_diff.node_status(node_to_hover)),
+                                    ui.visuals(),
+                                );
+                                if let Some(node_style) = individual_node_style.get(node_pos)
+                                    && node_style.cluster_size > 0
+                                {
+                                    draw_cluster_badge(painter, node_rect, node_style.cluster_size, ui.visuals());
                                 }
                             }
                         }
@@ -1302,8 +1965,34 @@ Expand Relations - double click on node",
                         Rect::from_min_max(Pos2::new(-5_000.0, -5_000.0), Pos2::new(10_000.0, 10_000.0));
                     let _response = ui.interact(max_rect, id, Sense::click_and_drag());
                 }
+                if png_export_request.is_some() {
+                    let shapes = ctx.graphics(|graphics| {
+                        graphics
+                            .get(ui.layer_id())
+                            .map(|paint_list| paint_list.all_entries().cloned().collect())
+                            .unwrap_or_default()
+                    });
+                    png_shapes = Some((shapes, painter.clip_rect()));
+                }
             });
         }
+        if let Some(request) = png_export_request {
+            self.ui_state.render_simplification = saved_simplification;
+            if let Some((shapes, view_rect)) = png_shapes {
+                let image = Self::png_export_image(ctx, &request, shapes, view_rect, ui.visuals().panel_fill);
+                self.write_png_export(request, image);
+            }
+        }
+        self.show_size_legend(ctx, graph_rect);
+        self.show_graph_legend(ctx, graph_rect);
+        self.show_authoring_dialogs(ctx);
+        if let Some((minimap, layout_positions, selected)) = minimap {
+            let view_rect = self.graph_state.scene_rect.translate(-SCENE_CENTER.to_vec2());
+            if let Some(new_center) = minimap.show(ctx, &layout_positions, &selected, view_rect) {
+                self.graph_state.scene_rect =
+                    Rect::from_center_size(SCENE_CENTER + new_center.to_vec2(), self.graph_state.scene_rect.size());
+            }
+        }
         if start_translate {
             self.ui_state.translate_drag = Some((global_mouse_pos, self.graph_state.scene_rect.min));
             ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Move);
@@ -1321,6 +2010,14 @@ Expand Relations - double click on node",
             let center = self.graph_state.scene_rect.center();
             self.graph_state.scene_rect = Rect::from_center_size(center, self.graph_state.scene_rect.size() * zoom);
         }
+        if let Some((slot, save)) = bookmark_action {
+            if save {
+                self.graph_state.save_bookmark(slot);
+                self.set_status_message(&format!("View saved as bookmark {}", slot + 1));
+            } else if !self.graph_state.restore_bookmark(slot) {
+                self.set_status_message(&format!("No view saved for bookmark {} (use Ctrl+{})", slot + 1, slot + 1));
+            }
+        }
         if was_context_click {
             Popup::toggle_id(ctx, popup_id);
         } else {
@@ -1336,9 +2033,23 @@ Expand Relations - double click on node",
         }
         let mut node_action: NodeContextAction = NodeContextAction::None;
         popup_at(ui, popup_id, self.ui_state.context_menu_pos, 200.0, |ui| {
-            if let Some(_node_index) = &self.ui_state.context_menu_node {
+            if let Some(node_index) = &self.ui_state.context_menu_node {
                 let has_zoom = self.visible_nodes.has_semantic_zoom && self.ui_state.semantic_zoom_magnitude > 1;
-                node_action = NodeContextAction::show_menu(ui, self.ui_state.context_menu_opened_by_keyboard, has_zoom);
+                let node_style = self.visible_nodes.layout_data.read().ok().and_then(|layout| {
+                    let pos = node_pos(&layout.nodes, *node_index)?;
+                    layout.individual_node_styles.get(pos).copied()
+                });
+                let in_cluster = self.visualization_style.use_color_overwrite
+                    && node_style.is_some_and(|node_style| node_style.color_overwrite > 0);
+                let is_meta_node = node_style.is_some_and(|node_style| node_style.cluster_size > 0);
+                node_action = NodeContextAction::show_menu(
+                    ui,
+                    self.ui_state.context_menu_opened_by_keyboard,
+                    has_zoom,
+                    &self.config.keymap,
+                    in_cluster,
+                    is_meta_node,
+                );
                 self.ui_state.context_menu_opened_by_keyboard = false;
                 if !matches!(node_action, NodeContextAction::None) {
                     Popup::close_id(ctx, popup_id);
@@ -1353,24 +2064,18 @@ Expand Relations - double click on node",
         }
         if let Some(current_index) = self.ui_state.selected_node {
             if matches!(node_action, NodeContextAction::None) {
-                ui.input(|i| {
-                    if i.key_pressed(Key::E) {
-                        node_action = NodeContextAction::Expand(ExpandType::Both);
-                        self.ui_state.context_menu_node = Some(current_index);
-                    } else if i.key_pressed(Key::H) {
-                        node_action = NodeContextAction::Hide;
-                        self.ui_state.context_menu_node = Some(current_index);
-                    } else if i.key_pressed(Key::O) {
-                        node_action = NodeContextAction::HideOther;
-                        self.ui_state.context_menu_node = Some(current_index);
-                    } else if i.key_pressed(Key::T) {
-                        node_action = NodeContextAction::HideThisType;
-                        self.ui_state.context_menu_node = Some(current_index);
-                    } else if i.key_pressed(Key::Enter) {
-                        *node_browse = NodeAction::BrowseNode(current_index);
-                        self.ui_state.context_menu_node = Some(current_index);
+                for action in view_actions {
+                    match action {
+                        ViewAction::ExpandNode => node_action = NodeContextAction::Expand(ExpandType::Both),
+                        ViewAction::HideNode => node_action = NodeContextAction::Hide,
+                        ViewAction::HideOther => node_action = NodeContextAction::HideOther,
+                        ViewAction::HideThisType => node_action = NodeContextAction::HideThisType,
+                        ViewAction::BrowseNode => *node_browse = NodeAction::BrowseNode(current_index),
+                        _ => continue,
                     }
-                });
+                    self.ui_state.context_menu_node = Some(current_index);
+                    break;
+                }
             }
         }
         if put_selection_rect.is_some() {
@@ -1394,7 +2099,7 @@ Expand Relations - double click on node",
                                         !self.ui_state.selected_nodes.contains(&node.node_index)
                                     });
                                 self.visible_nodes
-                                    .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                                    .start_layout(&self.config, &self.ui_state.hidden_predicates);
                                 check_selection = true;
                             }
                             NodeContextAction::HideThisType => {
@@ -1409,7 +2114,7 @@ Expand Relations - double click on node",
                                         }
                                     });
                                     self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                     );
                                 } else {
@@ -1422,7 +2127,7 @@ Expand Relations - double click on node",
                                         }
                                     });
                                     self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                     );
                                 }
@@ -1433,7 +2138,7 @@ Expand Relations - double click on node",
                                     x.node_index == current_index
                                 });
                                 self.visible_nodes
-                                    .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                                    .start_layout(&self.config, &self.ui_state.hidden_predicates);
                                 check_selection = true;
                             }
                             NodeContextAction::HideOtherTypes => {
@@ -1449,7 +2154,7 @@ Expand Relations - double click on node",
                                     });
                                     if was_change {
                                         self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                         );
                                     }
@@ -1473,7 +2178,7 @@ Expand Relations - double click on node",
                                     });
                                 if was_change {
                                     self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                     );
                                 }
@@ -1485,7 +2190,7 @@ Expand Relations - double click on node",
                                     .hide_unconnected(current_index, &self.ui_state.hidden_predicates)
                                 {
                                     self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                     );
                                 }
@@ -1501,27 +2206,27 @@ Expand Relations - double click on node",
                             }
                             NodeContextAction::HideZoomInvisible => {
                                 if self.visible_nodes.has_semantic_zoom && self.ui_state.semantic_zoom_magnitude > 1 {
-                                    let to_remove: Vec<usize> = if let Ok(individual_node_styles) =
-                                        self.visible_nodes.individual_node_styles.read()
-                                    {
-                                        let to_remove: Vec<usize> = individual_node_styles
-                                            .iter()
-                                            .enumerate()
-                                            .filter_map(|(index, node_style)| {
-                                                if !node_style
-                                                    .semantic_zoom_interval
-                                                    .is_visible(self.ui_state.semantic_zoom_magnitude)
-                                                {
-                                                    Some(index)
-                                                } else {
-                                                    None
-                                                }
-                                            })
-                                            .collect();
-                                        to_remove
-                                    } else {
-                                        Vec::new()
-                                    };
+                                    let to_remove: Vec<usize> =
+                                        if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                                            let to_remove: Vec<usize> = layout
+                                                .individual_node_styles
+                                                .iter()
+                                                .enumerate()
+                                                .filter_map(|(index, node_style)| {
+                                                    if !node_style
+                                                        .semantic_zoom_interval
+                                                        .is_visible(self.ui_state.semantic_zoom_magnitude)
+                                                    {
+                                                        Some(index)
+                                                    } else {
+                                                        None
+                                                    }
+                                                })
+                                                .collect();
+                                            to_remove
+                                        } else {
+                                            Vec::new()
+                                        };
                                     self.visible_nodes
                                         .remove_pos_list(&to_remove, &self.ui_state.hidden_predicates);
                                 }
@@ -1530,7 +2235,7 @@ Expand Relations - double click on node",
                                 let mut node_change_context = NodeChangeContext {
                                     rdfwrap: &mut self.rdfwrap,
                                     visible_nodes: &mut self.visible_nodes,
-                                    config: &self.persistent_data.config_data,
+                                    config: &self.config,
                                 };
                                 if rdf_data.expand_node(
                                     &self.ui_state.selected_nodes,
@@ -1539,7 +2244,7 @@ Expand Relations - double click on node",
                                     &self.ui_state.hidden_predicates,
                                 ) {
                                     self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                     );
                                 }
@@ -1549,7 +2254,7 @@ Expand Relations - double click on node",
                                 let mut node_change_context = NodeChangeContext {
                                     rdfwrap: &mut self.rdfwrap,
                                     visible_nodes: &mut self.visible_nodes,
-                                    config: &self.persistent_data.config_data,
+                                    config: &self.config,
                                 };
                                 if rdf_data.expand_all_by_types(
                                     &types,
@@ -1557,7 +2262,7 @@ Expand Relations - double click on node",
                                     &self.ui_state.hidden_predicates,
                                 ) {
                                     self.visible_nodes.start_layout(
-                                        &self.persistent_data.config_data,
+                                        &self.config,
                                         &self.ui_state.hidden_predicates,
                                     );
                                 }
@@ -1579,12 +2284,13 @@ Expand Relations - double click on node",
                                     }
                                 });
                                 self.visible_nodes
-                                    .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                                    .start_layout(&self.config, &self.ui_state.hidden_predicates);
                             }
                             NodeContextAction::ShowAllInstanceInTable => {
                                 let types = current_node.highest_priority_types(&self.visualization_style);
                                 if !types.is_empty() {
-                                    if let Ok(nodes) = self.visible_nodes.nodes.read() {
+                                    if let Ok(layout) = self.visible_nodes.layout_data.read() {
+                                        let nodes = &layout.nodes;
                                         let h_type = types[0];
                                         let mut all_instances: Vec<IriIndex> = Vec::new();
                                         for node_layout in nodes.iter() {
@@ -1599,19 +2305,48 @@ Expand Relations - double click on node",
                                     }
                                 }
                             }
+                            NodeContextAction::StyleSelected => {
+                                self.ui_state.group_style = Some(self.selected_style_override().unwrap_or_default());
+                            }
                             NodeContextAction::ChangeLockPosition(lock) => {
-                                if let Ok(nodes) = self.visible_nodes.nodes.read() {
-                                    if let Ok(mut positions) = self.visible_nodes.positions.write() {
-                                        for selected_index in self.ui_state.selected_nodes.iter() {
-                                            if let Ok(pos) =
-                                                nodes.binary_search_by(|e| e.node_index.cmp(selected_index))
-                                            {
-                                                positions[pos].locked = lock;
-                                            }
+                                if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                                    let LayoutData { nodes, positions, .. } = &mut *layout;
+                                    for selected_index in self.ui_state.selected_nodes.iter() {
+                                        if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(selected_index)) {
+                                            positions[pos].locked = lock;
                                         }
                                     }
                                 }
                             }
+                            NodeContextAction::CollapseCluster => {
+                                let cluster = self.visible_nodes.layout_data.read().ok().and_then(|layout| {
+                                    let pos = node_pos(&layout.nodes, current_index)?;
+                                    layout
+                                        .individual_node_styles
+                                        .get(pos)
+                                        .map(|node_style| node_style.color_overwrite)
+                                });
+                                if let Some(cluster) = cluster
+                                    && self
+                                        .visible_nodes
+                                        .collapse_clusters(&[cluster], &self.ui_state.hidden_predicates)
+                                        > 0
+                                {
+                                    self.visible_nodes
+                                        .start_layout(&self.config, &self.ui_state.hidden_predicates);
+                                }
+                                check_selection = true;
+                            }
+                            NodeContextAction::ExpandCluster => {
+                                if self.visible_nodes.expand_cluster(
+                                    current_index,
+                                    &rdf_data.node_data,
+                                    &self.ui_state.hidden_predicates,
+                                ) {
+                                    self.visible_nodes
+                                        .start_layout(&self.config, &self.ui_state.hidden_predicates);
+                                }
+                            }
                             NodeContextAction::None => {
                                 // do nothing
                             }
@@ -1641,33 +2376,51 @@ Expand Relations - double click on node",
 
         if let Some(node_to_click) = node_to_click {
             if let Ok(mut rdf_data) = self.rdf_data.write() {
-                let mut node_change_context = NodeChangeContext {
-                    rdfwrap: &mut self.rdfwrap,
-                    visible_nodes: &mut self.visible_nodes,
-                    config: &self.persistent_data.config_data,
-                };
-                let mut nodes: BTreeSet<IriIndex> = BTreeSet::new();
-                nodes.insert(node_to_click);
-                if rdf_data.expand_node(
-                    &nodes,
-                    ExpandType::Both,
-                    &mut node_change_context,
+                // double click on meta-node of collapsed cluster shows the cluster members again
+                if self.visible_nodes.expand_cluster(
+                    node_to_click,
+                    &rdf_data.node_data,
                     &self.ui_state.hidden_predicates,
                 ) {
                     self.visible_nodes
-                        .start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                        .start_layout(&self.config, &self.ui_state.hidden_predicates);
+                } else {
+                    let mut node_change_context = NodeChangeContext {
+                        rdfwrap: &mut self.rdfwrap,
+                        visible_nodes: &mut self.visible_nodes,
+                        config: &self.config,
+                    };
+                    let mut nodes: BTreeSet<IriIndex> = BTreeSet::new();
+                    nodes.insert(node_to_click);
+                    if rdf_data.expand_node(
+                        &nodes,
+                        ExpandType::Both,
+                        &mut node_change_context,
+                        &self.ui_state.hidden_predicates,
+                    ) {
+                        self.visible_nodes
+                            .start_layout(&self.config, &self.ui_state.hidden_predicates);
+                    }
                 }
             }
         }
 
         if let Ok(rdf_data) = self.rdf_data.read() {
+            if self.config.node_tooltips
+                && self.ui_state.node_to_drag.is_none()
+                && self.ui_state.context_menu_node.is_none()
+                && let Some(hovered_node) = pointer_hovered_node
+                && let Some(pointer_pos) = ctx.pointer_hover_pos()
+            {
+                self.show_node_tooltip(ctx, pointer_pos, hovered_node, &rdf_data);
+            }
             if let Some(node_to_hover) = node_to_hover {
                 if let Some((hover_node_iri, hover_node)) = rdf_data.node_data.get_node_by_index(node_to_hover) {
                     self.status_message.clear();
                     self.status_message.push_str(hover_node.node_label(
                         hover_node_iri,
                         &self.visualization_style,
-                        self.persistent_data.config_data.short_iri,
+                        self.config.short_iri,
                         self.ui_state.display_language,
                         &rdf_data.node_data.indexers,
                     ));
@@ -1683,7 +2436,7 @@ Expand Relations - double click on node",
                             selected_node.node_label(
                                 selected_node_iri,
                                 &self.visualization_style,
-                                self.persistent_data.config_data.short_iri,
+                                self.config.short_iri,
                                 self.ui_state.display_language,
                                 &rdf_data.node_data.indexers
                             ),
@@ -1698,6 +2451,104 @@ Expand Relations - double click on node",
                     .push_str(format!("Nodes: {}, Edges: {}", node_count, edge_count).as_str());
             }
         }
+        self.show_render_notice(ui, global_rect);
+    }
+
+    /// Tooltip near the mouse pointer with IRI, reference counts and the tooltip properties of the node types
+    fn show_node_tooltip(&self, ctx: &egui::Context, pointer_pos: Pos2, node_index: IriIndex, rdf_data: &RdfData) {
+        let Some((node_iri, node)) = rdf_data.node_data.get_node_by_index(node_index) else {
+            return;
+        };
+        let indexers = &rdf_data.node_data.indexers;
+        let label_context =
+            LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
+        let hidden_references = self
+            .visible_nodes
+            .layout_data
+            .read()
+            .ok()
+            .and_then(|layout| {
+                let pos = node_pos(&layout.nodes, node_index)?;
+                layout
+                    .individual_node_styles
+                    .get(pos)
+                    .map(|node_style| node_style.hidden_references)
+            })
+            .unwrap_or(0);
+        let tooltip_properties = self.visualization_style.get_tooltip_properties(&node.types);
+        egui::Area::new(egui::Id::new("graph_node_tooltip"))
+            .order(egui::Order::Tooltip)
+            .fixed_pos(pointer_pos + Vec2::new(16.0, 16.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.set_max_width(400.0);
+                    ui.strong(node.node_label(
+                        node_iri,
+                        &self.visualization_style,
+                        self.config.short_iri,
+                        self.ui_state.display_language,
+                        indexers,
+                    ));
+                    ui.label(node_iri);
+                    if !node.types.is_empty() {
+                        let types: Vec<String> = node
+                            .types
+                            .iter()
+                            .map(|type_index| {
+                                rdf_data
+                                    .node_data
+                                    .type_display(*type_index, &label_context, indexers)
+                                    .as_str()
+                                    .to_string()
+                            })
+                            .collect();
+                        ui.label(format!("Types: {}", types.join(", ")));
+                    }
+                    ui.label(format!(
+                        "References: {} out, {} in, {} not visible",
+                        node.references.len(),
+                        node.reverse_references.len(),
+                        hidden_references
+                    ));
+                    if self.visible_nodes.flatten_blank_nodes && has_blank_node_tree(&rdf_data.node_data, node) {
+                        ui.separator();
+                        // the tooltip is not interactive, the links are only shown
+                        let mut reference_action = ReferenceAction::None;
+                        show_blank_node_trees(
+                            ui,
+                            &blank_node_trees(&rdf_data.node_data, node),
+                            &rdf_data.node_data,
+                            &self.visualization_style,
+                            self.config.short_iri,
+                            &label_context,
+                            &mut reference_action,
+                        );
+                    }
+                    if !tooltip_properties.is_empty() {
+                        ui.separator();
+                        egui::Grid::new("graph_node_tooltip_properties").num_columns(2).show(ui, |ui| {
+                            for predicate_index in tooltip_properties.iter() {
+                                let Some(value) = node.get_property(
+                                    *predicate_index,
+                                    self.ui_state.display_language,
+                                    &indexers.language_fallback,
+                                ) else {
+                                    continue;
+                                };
+                                ui.label(
+                                    rdf_data
+                                        .node_data
+                                        .predicate_display(*predicate_index, &label_context, indexers)
+                                        .as_str(),
+                                );
+                                ui.label(value.as_str_ref(indexers));
+                                ui.end_row();
+                            }
+                        });
+                    }
+                });
+            });
     }
 
     pub fn export_edges<W: io::Write>(
@@ -1707,7 +2558,8 @@ Expand Relations - double click on node",
         label_context: &LabelContext,
     ) -> std::io::Result<()> {
         wtr.write_record(["source", "target", "predicate"])?;
-        if let Ok(edges) = self.visible_nodes.edges.read() {
+        if let Ok(layout) = self.visible_nodes.layout_data.read() {
+            let edges = &layout.edges;
             for edge in edges.iter() {
                 if !self.ui_state.hidden_predicates.contains(edge.predicate) {
                     let predicate_label =
@@ -1731,7 +2583,8 @@ Expand Relations - double click on node",
         styles: &GVisualizationStyle,
     ) -> std::io::Result<()> {
         wtr.write_record(["id", "iri", "type", "label"])?;
-        if let Ok(nodes) = self.visible_nodes.nodes.read() {
+        if let Ok(layout) = self.visible_nodes.layout_data.read() {
+            let nodes = &layout.nodes;
             for (idx, node) in nodes.iter().enumerate() {
                 if let Some((iri, nobject)) = node_data.get_node_by_index(node.node_index) {
                     wtr.write_field(idx.to_string())?;
@@ -1798,9 +2651,35 @@ pub fn draw_node(
     selected: bool,
     highlighted: bool,
     faded: bool,
+    locked: bool,
+    diff_status: Option<DiffStatus>,
     visuals: &egui::Visuals,
 ) -> (Rect, NodeShape) {
     let node_type_style = visualization_style.get_type_style(&node_object.types);
+    let mapped_style;
+    let node_type_style = if node_type_style.node_size == NodeSize::Mapped
+        && let Some(mapped_size) = individual_node_style.map(|style| style.mapped_size)
+        && !mapped_size.is_nan()
+    {
+        mapped_style = node_type_style.with_mapped_size(mapped_size);
+        &mapped_style
+    } else {
+        node_type_style
+    };
+    let rule_style;
+    let node_type_style = if let Some(style_rule) = visualization_style.style_rules.matching(node_object, indexers) {
+        rule_style = style_rule.apply(node_type_style);
+        &rule_style
+    } else {
+        node_type_style
+    };
+    let simple_style;
+    let node_type_style = if ui_state.render_simplification.simple_shapes {
+        simple_style = simple_node_style(node_type_style);
+        &simple_style
+    } else {
+        node_type_style
+    };
     let type_style = if (visualization_style.use_size_overwrite || visualization_style.use_color_overwrite)
         && individual_node_style.is_some()
     {
@@ -1845,6 +2724,20 @@ pub fn draw_node(
     } else {
         node_type_style
     };
+    let override_style;
+    let type_style = if let Some(style_override) = individual_node_style.and_then(|f| f.style_override) {
+        override_style = style_override.apply(type_style);
+        &override_style
+    } else {
+        type_style
+    };
+    let diff_style;
+    let type_style = if let Some(diff_status) = diff_status {
+        diff_style = diff_node_style(type_style, diff_status);
+        &diff_style
+    } else {
+        type_style
+    };
     let node_label = node_object.node_label(
         object_iri,
         visualization_style,
@@ -1857,7 +2750,7 @@ pub fn draw_node(
     } else {
         0
     };
-    draw_node_label(
+    let (node_rect, node_shape) = draw_node_label(
         painter,
         node_label,
         type_style,
@@ -1865,10 +2758,74 @@ pub fn draw_node(
         selected,
         highlighted,
         faded,
-        ui_state.show_labels,
+        ui_state.show_labels && !ui_state.render_simplification.hide_labels,
         display_num_hidden_refs,
         visuals,
-    )
+    );
+    if locked && !ui_state.render_simplification.simple_shapes {
+        painter.text(
+            node_rect.right_top(),
+            egui::Align2::CENTER_CENTER,
+            ICON_PIN,
+            egui::FontId::proportional(11.0),
+            visuals.text_color(),
+        );
+    }
+    (node_rect, node_shape)
+}
+
+/// Circle with the size of the type style, used for large graphs
+// Nodes of a dataset comparison are filled with the color of their status
+fn diff_node_style(type_style: &NodeStyle, diff_status: DiffStatus) -> NodeStyle {
+    NodeStyle {
+        color: diff_status.color(),
+        priority: type_style.priority,
+        label_index: type_style.label_index,
+        node_shape: type_style.node_shape,
+        node_size: type_style.node_size,
+        width: type_style.width,
+        height: type_style.height,
+        border_width: type_style.border_width,
+        border_color: type_style.border_color,
+        corner_radius: type_style.corner_radius,
+        max_lines: type_style.max_lines,
+        label_position: type_style.label_position,
+        label_max_width: type_style.label_max_width,
+        font_size: type_style.font_size,
+        label_color: type_style.label_color,
+        icon_style: None,
+        is_default: type_style.is_default,
+    }
+}
+
+fn simple_node_style(type_style: &NodeStyle) -> NodeStyle {
+    NodeStyle {
+        color: type_style.color,
+        priority: type_style.priority,
+        label_index: type_style.label_index,
+        node_shape: NodeShape::Circle,
+        node_size: NodeSize::Fixed,
+        width: if matches!(type_style.node_size, NodeSize::Fixed | NodeSize::Mapped) {
+            type_style.width.max(type_style.height)
+        } else {
+            NodeStyle::default().width
+        },
+        height: type_style.height,
+        border_width: type_style.border_width,
+        border_color: type_style.border_color,
+        corner_radius: type_style.corner_radius,
+        max_lines: type_style.max_lines,
+        label_position: if type_style.label_position == LabelPosition::Center {
+            LabelPosition::Above
+        } else {
+            type_style.label_position
+        },
+        label_max_width: type_style.label_max_width,
+        font_size: type_style.font_size,
+        label_color: type_style.label_color,
+        icon_style: None,
+        is_default: type_style.is_default,
+    }
 }
 
 pub fn update_layout_edges(
@@ -1878,75 +2835,117 @@ pub fn update_layout_edges(
     hidden_predicates: &SortedVec,
 ) {
     let mut visited_nodes: HashSet<IriIndex> = HashSet::with_capacity(new_nodes.nodes.len());
-    if let Ok(mut edges) = layout_nodes.edges.write() {
-        if let Ok(mut individal_node_styles) = layout_nodes.individual_node_styles.write() {
-            for node_index in new_nodes.iter_values() {
-                if let Some(node_pos) = layout_nodes.get_pos(*node_index) {
-                    if let Some((_str, nobject)) = node_data.get_node_by_index(*node_index) {
-                        for (pred_index, ref_iri) in nobject.references.iter() {
-                            if *ref_iri == *node_index {
-                                let edge = Edge {
-                                    from: node_pos,
-                                    to: node_pos,
-                                    predicate: *pred_index,
-                                    bezier_distance: 0.0,
-                                };
-                                edges.push(edge);
-                            } else if !visited_nodes.contains(ref_iri) {
-                                if let Some(ref_pos) = layout_nodes.get_pos(*ref_iri) {
-                                    let edge = Edge {
-                                        from: node_pos,
-                                        to: ref_pos,
-                                        predicate: *pred_index,
-                                        bezier_distance: 0.0,
-                                    };
-                                    edges.push(edge);
-                                    if let Some(other_style) = individal_node_styles.get_mut(ref_pos) {
-                                        if other_style.hidden_references > 0 {
-                                            other_style.hidden_references -= 1;
-                                        }
-                                    }
-                                } else {
-                                    if let Some(indivial_node_style) = individal_node_styles.get_mut(node_pos) {
-                                        indivial_node_style.hidden_references += 1;
-                                    }
-                                }
+    let flatten_blank_nodes = layout_nodes.flatten_blank_nodes;
+    if let Ok(mut layout) = layout_nodes.layout_data.write() {
+        let LayoutData {
+            nodes,
+            edges,
+            individual_node_styles: individal_node_styles,
+            ..
+        } = &mut *layout;
+        let collapsed_clusters = &layout_nodes.collapsed_clusters;
+        let get_pos = |value: IriIndex| node_pos(nodes, collapsed_clusters.visible_index(value));
+        for node_index in new_nodes.iter_values() {
+            if let Some(node_pos) = get_pos(*node_index)
+                && let Some((_str, nobject)) = node_data.get_node_by_index(*node_index)
+            {
+                for (pred_index, ref_iri) in nobject.references.iter() {
+                    if *ref_iri == *node_index {
+                        let edge = Edge {
+                            from: node_pos,
+                            to: node_pos,
+                            predicate: *pred_index,
+                            bezier_distance: 0.0,
+                        };
+                        edges.push(edge);
+                    } else if !visited_nodes.contains(ref_iri) {
+                        if let Some(ref_pos) = get_pos(*ref_iri) {
+                            let edge = Edge {
+                                from: node_pos,
+                                to: ref_pos,
+                                predicate: *pred_index,
+                                bezier_distance: 0.0,
+                            };
+                            edges.push(edge);
+                            if let Some(other_style) = individal_node_styles.get_mut(ref_pos)
+                                && other_style.hidden_references > 0
+                            {
+                                other_style.hidden_references -= 1;
+                            }
+                        } else if flatten_blank_nodes && is_blank_node(node_data, *ref_iri) {
+                            // the blank node is part of the composite node
+                        } else {
+                            if let Some(indivial_node_style) = individal_node_styles.get_mut(node_pos) {
+                                indivial_node_style.hidden_references += 1;
                             }
                         }
-                        for (pred_index, ref_iri) in nobject.reverse_references.iter() {
-                            if *ref_iri != *node_index && !visited_nodes.contains(ref_iri) {
-                                if let Some(ref_pos) = layout_nodes.get_pos(*ref_iri) {
-                                    let edge = Edge {
-                                        from: ref_pos,
+                    }
+                }
+                let mut composite_edges: HashSet<(usize, usize, IriIndex)> = HashSet::new();
+                if flatten_blank_nodes {
+                    for (pred_index, ref_iri) in composite_references(node_data, nobject) {
+                        if ref_iri != *node_index
+                            && !visited_nodes.contains(&ref_iri)
+                            && let Some(ref_pos) = get_pos(ref_iri)
+                            && composite_edges.insert((node_pos, ref_pos, pred_index))
+                        {
+                            edges.push(Edge {
+                                from: node_pos,
+                                to: ref_pos,
+                                predicate: pred_index,
+                                bezier_distance: 0.0,
+                            });
+                        }
+                    }
+                }
+                for (pred_index, ref_iri) in nobject.reverse_references.iter() {
+                    if *ref_iri != *node_index && !visited_nodes.contains(ref_iri) {
+                        if let Some(ref_pos) = get_pos(*ref_iri) {
+                            let edge = Edge {
+                                from: ref_pos,
+                                to: node_pos,
+                                predicate: *pred_index,
+                                bezier_distance: 0.0,
+                            };
+                            edges.push(edge);
+                            if let Some(other_style) = individal_node_styles.get_mut(ref_pos)
+                                && other_style.hidden_references > 0
+                            {
+                                other_style.hidden_references -= 1;
+                            }
+                        } else if flatten_blank_nodes && is_blank_node(node_data, *ref_iri) {
+                            // edges from the composite nodes that contain the blank node
+                            for root_index in blank_node_roots(node_data, *ref_iri) {
+                                if root_index != *node_index
+                                    && !visited_nodes.contains(&root_index)
+                                    && let Some(root_pos) = get_pos(root_index)
+                                    && composite_edges.insert((root_pos, node_pos, *pred_index))
+                                {
+                                    edges.push(Edge {
+                                        from: root_pos,
                                         to: node_pos,
                                         predicate: *pred_index,
                                         bezier_distance: 0.0,
-                                    };
-                                    edges.push(edge);
-                                    if let Some(other_style) = individal_node_styles.get_mut(ref_pos) {
-                                        if other_style.hidden_references > 0 {
-                                            other_style.hidden_references -= 1;
-                                        }
-                                    }
-                                } else {
-                                    if let Some(indivial_node_style) = individal_node_styles.get_mut(node_pos) {
-                                        indivial_node_style.hidden_references += 1;
-                                    }
+                                    });
                                 }
                             }
+                        } else {
+                            if let Some(indivial_node_style) = individal_node_styles.get_mut(node_pos) {
+                                indivial_node_style.hidden_references += 1;
+                            }
                         }
                     }
                 }
-                visited_nodes.insert(*node_index);
-            }
-            /*
-            println!("Layout edges: {}", layout_nodes.edges.len());
-            for edge in layout_nodes.edges.iter_mut() {
-                println!("Edge: {} -> {}", edge.from, edge.to);
             }
-             */
-            update_edges_groups(&mut edges, hidden_predicates);
+            visited_nodes.insert(*node_index);
         }
+        /*
+        println!("Layout edges: {}", layout_nodes.edges.len());
+        for edge in layout_nodes.edges.iter_mut() {
+            println!("Edge: {} -> {}", edge.from, edge.to);
+        }
+         */
+        update_edges_groups(edges, hidden_predicates);
     }
 }
 
@@ -1956,111 +2955,107 @@ pub fn add_preserved_edges(
     node_data: &NodeData,
     hidden_predicates: &SortedVec,
 ) {
-    if let Ok(mut edges) = layout_nodes.edges.write() {
-        if let Ok(nodes) = layout_nodes.nodes.read() {
-            let mut positions_to_preserve: Vec<usize> = Vec::new();
-            for (pos, node_layout) in nodes.iter().enumerate() {
+    if let Ok(mut layout) = layout_nodes.layout_data.write() {
+        let LayoutData { nodes, edges, .. } = &mut *layout;
+        let mut positions_to_preserve: Vec<usize> = Vec::new();
+        for (pos, node_layout) in nodes.iter().enumerate() {
+            let node = node_data.get_node_by_index(node_layout.node_index);
+            if let Some((_iri, nobject)) = node
+                && hidden_types.iter().any(|type_index| nobject.types.contains(type_index))
+            {
+                positions_to_preserve.push(pos)
+            }
+        }
+        nodes
+            .iter()
+            .enumerate()
+            .filter(|(_pos, node_layout)| {
                 let node = node_data.get_node_by_index(node_layout.node_index);
                 if let Some((_iri, nobject)) = node {
-                    if hidden_types.iter().any(|type_index| nobject.types.contains(type_index)) {
-                        positions_to_preserve.push(pos)
-                    }
+                    hidden_types.iter().any(|type_index| nobject.types.contains(type_index))
+                } else {
+                    false
                 }
-            }
-            nodes
-                .iter()
-                .enumerate()
-                .filter(|(_pos, node_layout)| {
-                    let node = node_data.get_node_by_index(node_layout.node_index);
-                    if let Some((_iri, nobject)) = node {
-                        hidden_types.iter().any(|type_index| nobject.types.contains(type_index))
-                    } else {
-                        false
-                    }
-                })
-                .map(|(pos, _node_layout)| pos)
-                .for_each(|position_to_preserve| {
-                    let mut preserved_edges: Vec<Edge> = Vec::new();
-                    let pos_edges: Vec<&Edge> = edges
-                        .iter()
-                        .filter(|edge| edge.from == position_to_preserve || edge.to == position_to_preserve)
-                        .collect();
-                    let mut has_to = false;
-                    for edge in pos_edges.iter() {
-                        // We create preserved edges only if the are 2 edges that jump over the node
-                        if edge.to == position_to_preserve {
-                            has_to = true;
-                            for edge2 in pos_edges.iter() {
-                                if edge2.from == position_to_preserve && edge2.to != edge.from {
-                                    if preserved_edges.iter().any(|e| {
-                                        (e.from == edge.from && e.to == edge2.to)
-                                            || (e.from == edge2.to && e.to == edge.from)
-                                    }) {
-                                        continue;
-                                    }
-                                    preserved_edges.push(Edge {
-                                        from: edge.from,
-                                        to: edge2.to,
-                                        predicate: edge.predicate,
-                                        bezier_distance: 0.0,
-                                    });
+            })
+            .map(|(pos, _node_layout)| pos)
+            .for_each(|position_to_preserve| {
+                let mut preserved_edges: Vec<Edge> = Vec::new();
+                let pos_edges: Vec<&Edge> = edges
+                    .iter()
+                    .filter(|edge| edge.from == position_to_preserve || edge.to == position_to_preserve)
+                    .collect();
+                let mut has_to = false;
+                for edge in pos_edges.iter() {
+                    // We create preserved edges only if the are 2 edges that jump over the node
+                    if edge.to == position_to_preserve {
+                        has_to = true;
+                        for edge2 in pos_edges.iter() {
+                            if edge2.from == position_to_preserve && edge2.to != edge.from {
+                                if preserved_edges.iter().any(|e| {
+                                    (e.from == edge.from && e.to == edge2.to)
+                                        || (e.from == edge2.to && e.to == edge.from)
+                                }) {
+                                    continue;
                                 }
+                                preserved_edges.push(Edge {
+                                    from: edge.from,
+                                    to: edge2.to,
+                                    predicate: edge.predicate,
+                                    bezier_distance: 0.0,
+                                });
                             }
                         }
                     }
-                    if preserved_edges.is_empty() {
-                        // No edges found. But create edges if to nodes point to or from the node for hide
-                        if has_to {
-                            for edge in pos_edges.iter() {
-                                if edge.to == position_to_preserve {
-                                    for edge2 in pos_edges.iter() {
-                                        if edge2.to == position_to_preserve
-                                            && edge2.from != edge.from
-                                            && edge.from < edge2.from
+                }
+                if preserved_edges.is_empty() {
+                    // No edges found. But create edges if to nodes point to or from the node for hide
+                    if has_to {
+                        for edge in pos_edges.iter() {
+                            if edge.to == position_to_preserve {
+                                for edge2 in pos_edges.iter() {
+                                    if edge2.to == position_to_preserve
+                                        && edge2.from != edge.from
+                                        && edge.from < edge2.from
+                                    {
+                                        if preserved_edges
+                                            .iter()
+                                            .any(|e| e.from == edge.from && e.to == edge2.from)
                                         {
-                                            if preserved_edges
-                                                .iter()
-                                                .any(|e| e.from == edge.from && e.to == edge2.from)
-                                            {
-                                                continue;
-                                            }
-                                            preserved_edges.push(Edge {
-                                                from: edge.from,
-                                                to: edge2.from,
-                                                predicate: edge.predicate,
-                                                bezier_distance: 0.0,
-                                            });
+                                            continue;
                                         }
+                                        preserved_edges.push(Edge {
+                                            from: edge.from,
+                                            to: edge2.from,
+                                            predicate: edge.predicate,
+                                            bezier_distance: 0.0,
+                                        });
                                     }
                                 }
                             }
-                        } else {
-                            for edge in pos_edges.iter() {
-                                if edge.from == position_to_preserve {
-                                    for edge2 in pos_edges.iter() {
-                                        if edge2.from == position_to_preserve
-                                            && edge2.to != edge.to
-                                            && edge.to < edge2.to
-                                        {
-                                            if preserved_edges.iter().any(|e| e.from == edge.to && e.to == edge2.to) {
-                                                continue;
-                                            }
-                                            preserved_edges.push(Edge {
-                                                from: edge.to,
-                                                to: edge2.to,
-                                                predicate: edge.predicate,
-                                                bezier_distance: 0.0,
-                                            });
+                        }
+                    } else {
+                        for edge in pos_edges.iter() {
+                            if edge.from == position_to_preserve {
+                                for edge2 in pos_edges.iter() {
+                                    if edge2.from == position_to_preserve && edge2.to != edge.to && edge.to < edge2.to {
+                                        if preserved_edges.iter().any(|e| e.from == edge.to && e.to == edge2.to) {
+                                            continue;
                                         }
+                                        preserved_edges.push(Edge {
+                                            from: edge.to,
+                                            to: edge2.to,
+                                            predicate: edge.predicate,
+                                            bezier_distance: 0.0,
+                                        });
                                     }
                                 }
                             }
                         }
                     }
-                    edges.extend(preserved_edges);
-                    update_edges_groups(&mut edges, hidden_predicates);
-                });
-        }
+                }
+                edges.extend(preserved_edges);
+                update_edges_groups(edges, hidden_predicates);
+            });
     }
 }
 
@@ -2109,9 +3104,10 @@ impl NeighborPos {
     }
 
     pub fn position(&self, node_layout: &mut SortedNodeLayout) {
-        if let Ok(mut positions) = node_layout.positions.write() {
+        if let Ok(mut layout) = node_layout.layout_data.write() {
+            let LayoutData { nodes, positions, .. } = &mut *layout;
             for (root_node_index, neighbours) in self.nodes.iter() {
-                let root_node = node_layout.get_pos(*root_node_index);
+                let root_node = node_pos(nodes, *root_node_index);
                 if let Some(root_pos) = root_node {
                     let mut angle: f32 = rand::rng().random_range(0.0..std::f32::consts::TAU);
                     let angle_diff = std::f32::consts::TAU / neighbours.len() as f32;
@@ -2119,7 +3115,7 @@ impl NeighborPos {
                     for node_iri in neighbours.iter() {
                         let x = root_pos.x + INITIAL_DISTANCE * angle.cos();
                         let y = root_pos.y + INITIAL_DISTANCE * angle.sin();
-                        if let Some(node_pos) = node_layout.get_pos(*node_iri) {
+                        if let Some(node_pos) = node_pos(nodes, *node_iri) {
                             positions[node_pos].pos = Pos2::new(x, y);
                         }
                         angle += angle_diff;
@@ -0,0 +1,97 @@
+use egui::{Grid, ScrollArea};
+
+use crate::RdfGlanceApp;
+
+impl RdfGlanceApp {
+    pub fn show_import_log(&mut self, ui: &mut egui::Ui) {
+        if !self.ui_state.import_log_window {
+            return;
+        }
+        let mut close = false;
+        egui::Window::new("Import Log")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(700.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    Grid::new("import_log_grid").striped(true).show(ui, |ui| {
+                        ui.strong("Source");
+                        ui.strong("Duration");
+                        ui.strong("Read");
+                        ui.strong("Merged");
+                        ui.strong("Duplicates");
+                        ui.strong("Filtered");
+                        ui.strong("Errors");
+                        ui.end_row();
+                        for report in self.import_log.reports.iter() {
+                            ui.label(&report.source);
+                            ui.label(format!("{:.3}s", report.duration.as_secs_f64()));
+                            ui.label(report.triples_read.to_string());
+                            ui.label(report.triples_merged.to_string());
+                            ui.label(report.duplicates_skipped.to_string());
+                            ui.label(report.filtered_by_language.to_string());
+                            if report.stopped {
+                                ui.label(format!("{} (stopped)", report.error_count));
+                            } else {
+                                ui.label(report.error_count.to_string());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                    for (index, report) in self.import_log.reports.iter().enumerate() {
+                        if !report.errors.is_empty() {
+                            egui::CollapsingHeader::new(format!("Errors: {}", report.source))
+                                .id_salt(("import_log_errors", index))
+                                .show(ui, |ui| {
+                                    for error in report.errors.iter() {
+                                        ui.label(error);
+                                    }
+                                });
+                        }
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Save as Text").clicked() {
+                        self.save_import_log();
+                    }
+                    if ui.button("Clear").clicked() {
+                        self.import_log.clear();
+                        close = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if close {
+            self.ui_state.import_log_window = false;
+        }
+    }
+
+    fn save_import_log(&mut self) {
+        let text = self.import_log.to_text();
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            use crate::uistate::SystemMessage;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter("Text", &["txt"])
+                .set_file_name("import_log.txt")
+                .save_file()
+                && let Err(e) = std::fs::write(&path, text)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save import log: {}", e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = web_download("import_log.txt", text.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use egui::RichText;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{LabelContext, NObject},
+    uistate::actions::NodeAction,
+};
+
+// more sources of one predicate are only counted, the panel is rebuilt every frame
+const MAX_SOURCES_PER_PREDICATE: usize = 50;
+
+impl RdfGlanceApp {
+    /// Incoming references of the node under the cursor (or the last hovered node) in the visual graph
+    pub fn display_incoming_references(&mut self, ui: &mut egui::Ui) -> NodeAction {
+        let mut node_action = NodeAction::None;
+        ui.strong("What Links Here");
+        let Some(hovered_node) = self.ui_state.hovered_node.or(self.ui_state.selected_node) else {
+            ui.label("Move the mouse over a node to see its incoming references");
+            return node_action;
+        };
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return node_action;
+        };
+        let Some((node_iri, node)) = rdf_data.node_data.get_node_by_index(hovered_node) else {
+            return node_action;
+        };
+        let label_context = LabelContext::new(
+            self.ui_state.display_language,
+            self.config.iri_display,
+            &rdf_data.prefix_manager,
+        );
+        let mut predicates: Vec<(IriIndex, Vec<IriIndex>)> = Vec::new();
+        for (predicate_index, source_index) in node.reverse_references.iter() {
+            match predicates.iter_mut().find(|(predicate, _)| predicate == predicate_index) {
+                Some((_, sources)) => sources.push(*source_index),
+                None => predicates.push((*predicate_index, vec![*source_index])),
+            }
+        }
+        predicates.sort_by_key(|(_, sources)| std::cmp::Reverse(sources.len()));
+        let dark_mode = ui.visuals().dark_mode;
+        let predicate_colors: Vec<egui::Color32> = predicates
+            .iter()
+            .map(|(predicate_index, _)| self.visualization_style.get_predicate_color(*predicate_index, dark_mode))
+            .collect();
+        let node_label = |iri: &str, node: &NObject| -> String {
+            node.node_label(
+                iri,
+                &self.visualization_style,
+                self.config.short_iri,
+                self.ui_state.display_language,
+                &rdf_data.node_data.indexers,
+            )
+            .to_string()
+        };
+        if ui.link(node_label(node_iri, node)).on_hover_text(node_iri.as_ref()).clicked() {
+            node_action = NodeAction::BrowseNode(hovered_node);
+        }
+        ui.label(format!("{} incoming references", node.reverse_references.len()));
+        ui.separator();
+        for ((predicate_index, sources), predicate_color) in predicates.iter().zip(predicate_colors) {
+            let predicate_label = rdf_data.node_data.predicate_display(
+                *predicate_index,
+                &label_context,
+                &rdf_data.node_data.indexers,
+            );
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(predicate_label.as_str()).strong().color(predicate_color));
+                ui.label(format!("({})", sources.len()));
+            });
+            ui.indent(predicate_index, |ui| {
+                for source_index in sources.iter().take(MAX_SOURCES_PER_PREDICATE) {
+                    let Some((source_iri, source_node)) = rdf_data.node_data.get_node_by_index(*source_index) else {
+                        continue;
+                    };
+                    ui.horizontal(|ui| {
+                        let is_visible = self.visible_nodes.contains(*source_index);
+                        if ui
+                            .add_enabled(!is_visible, egui::Button::new("\u{2795}").small())
+                            .on_hover_text("Add to visual graph")
+                            .clicked()
+                        {
+                            node_action = NodeAction::AddVisual(*source_index);
+                        }
+                        if ui
+                            .link(node_label(source_iri, source_node))
+                            .on_hover_text(source_iri.as_ref())
+                            .clicked()
+                        {
+                            node_action = NodeAction::BrowseNode(*source_index);
+                        }
+                    });
+                }
+                if sources.len() > MAX_SOURCES_PER_PREDICATE {
+                    ui.label(format!("... and {} more", sources.len() - MAX_SOURCES_PER_PREDICATE));
+                }
+            });
+        }
+        node_action
+    }
+}
@@ -0,0 +1,66 @@
+use egui::{Color32, Key};
+
+use crate::{
+    RdfGlanceApp,
+    domain::keymap::{KeyBinding, ViewAction},
+};
+
+impl RdfGlanceApp {
+    /// Settings section to rebind the keyboard shortcuts of the visual graph, returns true if a shortcut was changed
+    pub fn show_keymap_config(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = false;
+        ui.label("Keyboard shortcuts of visual graph (click on a shortcut and press the new key, Escape cancels):");
+        if let Some(action) = self.ui_state.keymap_recording {
+            let binding = ui.input(KeyBinding::from_input);
+            if let Some(binding) = binding {
+                if binding.key != Key::Escape {
+                    self.persistent_data.config_data.keymap.set_binding(action, Some(binding));
+                    changed = true;
+                }
+                self.ui_state.keymap_recording = None;
+                // the new key should not trigger the action in the same frame
+                ui.input_mut(|i| i.consume_key(i.modifiers, binding.key));
+            }
+        }
+        let keymap = &mut self.persistent_data.config_data.keymap;
+        egui::Grid::new("keymap").num_columns(4).striped(true).show(ui, |ui| {
+            for action in ViewAction::ALL {
+                ui.label(action.label());
+                let binding_text = if self.ui_state.keymap_recording == Some(action) {
+                    "Press key...".to_string()
+                } else {
+                    keymap.binding(action).map_or_else(|| "-".to_string(), |binding| binding.to_string())
+                };
+                if ui
+                    .selectable_label(self.ui_state.keymap_recording == Some(action), binding_text)
+                    .clicked()
+                {
+                    self.ui_state.keymap_recording = Some(action);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Clear").clicked() {
+                        keymap.set_binding(action, None);
+                        changed = true;
+                    }
+                    if ui.button("Default").clicked() {
+                        keymap.set_binding(action, action.default_binding());
+                        changed = true;
+                    }
+                });
+                let conflicts = keymap.conflicts(action);
+                if conflicts.is_empty() {
+                    ui.label("");
+                } else {
+                    let names: Vec<&str> = conflicts.iter().map(|conflict| conflict.label()).collect();
+                    ui.colored_label(Color32::RED, format!("Also used by: {}", names.join(", ")));
+                }
+                ui.end_row();
+            }
+        });
+        if ui.button("Reset All Shortcuts").clicked() {
+            keymap.bindings.clear();
+            changed = true;
+        }
+        changed
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    RdfGlanceApp,
+    domain::{LangIndex, LanguageFallback},
+    ui::style::ICON_DELETE,
+};
+
+impl RdfGlanceApp {
+    /// Resolves the language tags of the config to the language indexes of the current data
+    pub fn apply_language_fallback(&mut self) {
+        let needs_update = self.rdf_data.read().is_ok_and(|rdf_data| {
+            rdf_data.node_data.indexers.language_fallback
+                != LanguageFallback::from_tags(
+                    &self.config.language_fallback,
+                    self.config.language_fallback_any,
+                    &rdf_data.node_data.indexers.language_indexer,
+                )
+        });
+        if needs_update && let Ok(mut rdf_data) = self.rdf_data.write() {
+            rdf_data.node_data.indexers.language_fallback = LanguageFallback::from_tags(
+                &self.config.language_fallback,
+                self.config.language_fallback_any,
+                &rdf_data.node_data.indexers.language_indexer,
+            );
+        }
+    }
+
+    pub fn show_language_panel(&mut self, ui: &mut egui::Ui) {
+        if !self.show_language_panel {
+            return;
+        }
+        let mut changed = false;
+        let mut open = true;
+        let data_languages: Vec<(LangIndex, String)> = match self.rdf_data.read() {
+            Ok(rdf_data) => self
+                .ui_state
+                .language_sort
+                .iter()
+                .filter_map(|language_index| {
+                    rdf_data
+                        .node_data
+                        .get_language(*language_index)
+                        .map(|language| (*language_index, language.to_string()))
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        let config = &mut self.persistent_data.config_data;
+        egui::Window::new("Languages")
+            .open(&mut open)
+            .resizable(false)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Display language:");
+                    let selected = data_languages
+                        .iter()
+                        .find(|(language_index, _)| *language_index == self.ui_state.display_language)
+                        .map_or("", |(_, language)| language.as_str());
+                    egui::ComboBox::from_id_salt("display_language")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            for (language_index, language) in data_languages.iter() {
+                                ui.selectable_value(&mut self.ui_state.display_language, *language_index, language);
+                            }
+                        });
+                });
+                ui.separator();
+                ui.label(
+                    "If there is no label or value in the display language, the languages are tried in this order:",
+                );
+                let mut move_up: Option<usize> = None;
+                let mut remove: Option<usize> = None;
+                egui::Grid::new("language_fallback").num_columns(3).show(ui, |ui| {
+                    for (pos, tag) in config.language_fallback.iter().enumerate() {
+                        let known = data_languages.iter().any(|(_, language)| language == tag);
+                        if known {
+                            ui.label(format!("{}. {}", pos + 1, tag));
+                        } else {
+                            ui.weak(format!("{}. {}", pos + 1, tag))
+                                .on_hover_text("The language is not in the data");
+                        }
+                        if ui
+                            .add_enabled(pos > 0, egui::Button::new("Up"))
+                            .on_hover_text("Move up")
+                            .clicked()
+                        {
+                            move_up = Some(pos);
+                        }
+                        if ui.button(ICON_DELETE).on_hover_text("Remove").clicked() {
+                            remove = Some(pos);
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(pos) = move_up {
+                    config.language_fallback.swap(pos - 1, pos);
+                    changed = true;
+                }
+                if let Some(pos) = remove {
+                    config.language_fallback.remove(pos);
+                    changed = true;
+                }
+                egui::ComboBox::from_id_salt("add_fallback_language")
+                    .selected_text("Add language")
+                    .show_ui(ui, |ui| {
+                        for (_, language) in data_languages.iter() {
+                            if !config.language_fallback.contains(language)
+                                && ui.selectable_label(false, language).clicked()
+                            {
+                                config.language_fallback.push(language.clone());
+                                changed = true;
+                            }
+                        }
+                    });
+                ui.label("Then values without language are used.");
+                if ui
+                    .checkbox(&mut config.language_fallback_any, "At last use values in any language")
+                    .changed()
+                {
+                    changed = true;
+                }
+            });
+        if !open {
+            self.show_language_panel = false;
+        }
+        if changed {
+            self.update_config();
+        }
+    }
+}
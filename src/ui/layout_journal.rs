@@ -0,0 +1,126 @@
+use egui::ScrollArea;
+
+use crate::{
+    DisplayType, RdfGlanceApp,
+    integration::layout_journal::{LayoutJournal, restore_layout},
+    uistate::ImportFormat,
+};
+
+impl RdfGlanceApp {
+    /// Opens the journal and offers to restore the layout if the previous session ended without saving it
+    pub fn open_layout_journal(&mut self) {
+        let Some(path) = LayoutJournal::default_path() else {
+            return;
+        };
+        match LayoutJournal::read(&path) {
+            Ok(content) => self.journal_restore = content,
+            Err(e) => log::warn!("Can not read layout journal {}: {}", path.display(), e),
+        }
+        self.layout_journal = Some(LayoutJournal::new(path));
+    }
+
+    pub fn update_layout_journal(&mut self, ctx: &egui::Context) {
+        if self.journal_restore.is_some() {
+            return;
+        }
+        let Some(layout_journal) = &mut self.layout_journal else {
+            return;
+        };
+        if ctx.input(|i| i.viewport().close_requested()) {
+            // normal exit, the journal is only needed after a crash
+            layout_journal.reset();
+            return;
+        }
+        // positions change all the time during force layout, it is written after the layout has settled
+        if !layout_journal.is_due() || self.visible_nodes.compute_layout || self.visible_nodes.layout_handle.is_some() {
+            return;
+        }
+        // do not block the ui while data are loaded
+        if let Ok(rdf_data) = self.rdf_data.try_read()
+            && let Err(e) = layout_journal.write_changes(&self.visible_nodes, &rdf_data.node_data)
+        {
+            log::warn!("Can not write layout journal: {}", e);
+        }
+    }
+
+    pub fn show_journal_restore(&mut self, ui: &mut egui::Ui) {
+        let Some(journal_restore) = &self.journal_restore else {
+            return;
+        };
+        let mut load_source: Option<String> = None;
+        let mut restore = false;
+        let mut discard = false;
+        let is_loading = self.load_handle.is_some();
+        let has_data = self.rdf_data.try_read().is_ok_and(|rdf_data| !rdf_data.node_data.is_empty());
+        egui::Window::new("Restore Layout")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "The last session ended without saving the visual graph layout ({} nodes).",
+                    journal_restore.positions.len()
+                ));
+                if !journal_restore.sources.is_empty() {
+                    ui.label("Load the data of the session and restore the layout:");
+                    ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                        for source in journal_restore.sources.iter() {
+                            ui.horizontal(|ui| {
+                                if ui.add_enabled(!is_loading, egui::Button::new("Load")).clicked() {
+                                    load_source = Some(source.clone());
+                                }
+                                ui.label(source);
+                            });
+                        }
+                    });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(has_data && !is_loading, egui::Button::new("Restore Layout"))
+                        .on_disabled_hover_text("Load the data first")
+                        .clicked()
+                    {
+                        restore = true;
+                    }
+                    if ui.button("Discard").clicked() {
+                        discard = true;
+                    }
+                });
+            });
+        if let Some(source) = load_source {
+            if source.ends_with(".rdfglance") {
+                self.load_project(std::path::Path::new(&source), ui.visuals().dark_mode);
+            } else if source.starts_with("http://") || source.starts_with("https://") {
+                self.load_ttl_from_url(&source, ImportFormat::Auto, ui.visuals().dark_mode);
+            } else {
+                self.load_ttl(&source, ui.visuals().dark_mode);
+            }
+        }
+        if restore && let Some(journal_restore) = self.journal_restore.take() {
+            let restored = if let Ok(rdf_data) = self.rdf_data.read() {
+                restore_layout(
+                    &journal_restore,
+                    &mut self.visible_nodes,
+                    &rdf_data.node_data,
+                    &self.ui_state.hidden_predicates,
+                )
+            } else {
+                0
+            };
+            let missing = journal_restore.positions.len() - restored;
+            if missing > 0 {
+                self.set_status_message(&format!("Layout restored, {} nodes not found in data", missing));
+            } else {
+                self.set_status_message("Layout restored");
+            }
+            self.display_type = DisplayType::Graph;
+        }
+        if discard {
+            self.journal_restore = None;
+            if let Some(layout_journal) = &mut self.layout_journal {
+                layout_journal.discard();
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use egui::{Color32, RichText, ScrollArea};
+use log::Level;
+
+use crate::{
+    RdfGlanceApp,
+    domain::app_log::{clear_records, records_to_text, with_records},
+};
+
+pub struct LogConsole {
+    pub min_level: Level,
+    pub filter: String,
+}
+
+impl Default for LogConsole {
+    fn default() -> Self {
+        Self {
+            min_level: Level::Info,
+            filter: String::new(),
+        }
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn open_log_console(&mut self) {
+        if self.log_console.is_none() {
+            self.log_console = Some(LogConsole::default());
+        }
+    }
+
+    pub fn show_log_console(&mut self, ui: &mut egui::Ui) {
+        let Some(log_console) = &mut self.log_console else {
+            return;
+        };
+        let mut close = false;
+        let mut export = false;
+        egui::Window::new("Log Console")
+            .collapsible(true)
+            .resizable(true)
+            .default_width(800.0)
+            .default_height(400.0)
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Level");
+                    egui::ComboBox::from_id_salt("log_console_level")
+                        .selected_text(log_console.min_level.as_str())
+                        .show_ui(ui, |ui| {
+                            for level in [Level::Error, Level::Warn, Level::Info, Level::Debug] {
+                                ui.selectable_value(&mut log_console.min_level, level, level.as_str());
+                            }
+                        });
+                    ui.label("Filter");
+                    ui.text_edit_singleline(&mut log_console.filter);
+                    if ui.button("Copy").on_hover_text("Copy the log to clipboard").clicked() {
+                        ui.ctx().copy_text(records_to_text(log_console.min_level));
+                    }
+                    if ui.button("Save as Text").clicked() {
+                        export = true;
+                    }
+                    if ui.button("Clear").clicked() {
+                        clear_records();
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+                ui.separator();
+                let filter = log_console.filter.to_lowercase();
+                with_records(log_console.min_level, |records| {
+                    let records: Vec<_> = records
+                        .iter()
+                        .filter(|record| {
+                            filter.is_empty()
+                                || record.message.to_lowercase().contains(&filter)
+                                || record.target.contains(&filter)
+                        })
+                        .collect();
+                    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                    ScrollArea::both()
+                        .auto_shrink([false, false])
+                        .stick_to_bottom(true)
+                        .show_rows(ui, row_height, records.len(), |ui, row_range| {
+                            for record in &records[row_range] {
+                                let color = match record.level {
+                                    Level::Error => Color32::RED,
+                                    Level::Warn => Color32::ORANGE,
+                                    Level::Info => ui.visuals().text_color(),
+                                    Level::Debug | Level::Trace => ui.visuals().weak_text_color(),
+                                };
+                                ui.label(RichText::new(record.to_line()).monospace().color(color));
+                            }
+                        });
+                });
+            });
+        if export {
+            let text = records_to_text(log_console.min_level);
+            self.save_log_text(text);
+        }
+        if close {
+            self.log_console = None;
+        }
+    }
+
+    fn save_log_text(&mut self, text: String) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            use crate::uistate::SystemMessage;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter("Text", &["txt", "log"])
+                .set_file_name("rdfglance.log")
+                .save_file()
+                && let Err(e) = std::fs::write(&path, text)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save log: {}", e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = web_download("rdfglance.log", text.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use const_format::concatcp;
+use egui::{Align2, Color32, FontId, Pos2, Rect, Sense, Stroke, Vec2};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{config::IriDisplay, geo::GeoIndex},
+    ui::style::{ICON_BROWSE, ICON_CENTER, ICON_GRAPH},
+    uistate::{actions::NodeAction, layout::node_pos},
+};
+
+const POINT_RADIUS: f32 = 4.0;
+const PICK_DISTANCE: f32 = 8.0;
+const MIN_ZOOM: f32 = 1.0;
+const MAX_ZOOM: f32 = 1_000_000.0;
+// graticule steps in degrees, the smallest with at least MIN_GRID_SPACING pixels is used
+const GRID_STEPS: [f64; 14] = [
+    0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0, 15.0, 30.0, 45.0,
+];
+const MIN_GRID_SPACING: f32 = 60.0;
+
+/**
+ * State of the map display.
+ * The map has no tiles, the nodes are drawn in equirectangular projection with a graticule.
+ * The geo index is built when the map is shown first and is dropped if the data change.
+ */
+pub struct MapView {
+    pub geo_index: Option<GeoIndex>,
+    // map center as (longitude, latitude)
+    pub center: Pos2,
+    // pixels per degree
+    pub zoom: f32,
+    // zoom to all nodes on next display
+    pub fit_pending: bool,
+    // show only the nodes that are in the visual graph
+    pub only_visual_graph: bool,
+    pub context_node: Option<IriIndex>,
+}
+
+impl Default for MapView {
+    fn default() -> Self {
+        Self {
+            geo_index: None,
+            center: Pos2::ZERO,
+            zoom: 3.0,
+            fit_pending: true,
+            only_visual_graph: false,
+            context_node: None,
+        }
+    }
+}
+
+impl MapView {
+    pub fn clean(&mut self) {
+        self.geo_index = None;
+        self.fit_pending = true;
+        self.context_node = None;
+    }
+
+    fn to_screen(&self, rect: &Rect, lon: f64, lat: f64) -> Pos2 {
+        rect.center()
+            + Vec2::new(
+                (lon as f32 - self.center.x) * self.zoom,
+                (self.center.y - lat as f32) * self.zoom,
+            )
+    }
+
+    fn to_geo(&self, rect: &Rect, pos: Pos2) -> Pos2 {
+        let diff = pos - rect.center();
+        Pos2::new(self.center.x + diff.x / self.zoom, self.center.y - diff.y / self.zoom)
+    }
+
+    /// Center and zoom to show the bounds in the rect
+    fn fit(rect: &Rect, bounds: (f64, f64, f64, f64)) -> (Pos2, f32) {
+        let (min_lon, min_lat, max_lon, max_lat) = bounds;
+        let center = Pos2::new(((min_lon + max_lon) / 2.0) as f32, ((min_lat + max_lat) / 2.0) as f32);
+        let width = ((max_lon - min_lon) as f32).max(0.01);
+        let height = ((max_lat - min_lat) as f32).max(0.01);
+        let zoom = (rect.width() * 0.9 / width).min(rect.height() * 0.9 / height);
+        (center, zoom.clamp(MIN_ZOOM, MAX_ZOOM))
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn show_map_view(&mut self, ui: &mut egui::Ui) -> NodeAction {
+        let mut node_action = NodeAction::None;
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return node_action;
+        };
+        if self.map_view.geo_index.is_none() {
+            self.map_view.geo_index = Some(GeoIndex::new(&rdf_data.node_data, &rdf_data.prefix_manager));
+        }
+        let visible_nodes: Option<Vec<IriIndex>> = if self.map_view.only_visual_graph {
+            self.visible_nodes
+                .layout_data
+                .read()
+                .ok()
+                .map(|layout| layout.nodes.iter().map(|node| node.node_index).collect())
+        } else {
+            None
+        };
+        let is_shown = |node_index: IriIndex| match &visible_nodes {
+            Some(nodes) => nodes.binary_search(&node_index).is_ok(),
+            None => true,
+        };
+        // the node selected in the table is marked too
+        let table_node = self
+            .type_index
+            .selected_type
+            .and_then(|type_index| self.type_index.types.get(&type_index))
+            .and_then(|type_data| type_data.instance_view.selected_idx)
+            .map(|(node_index, _)| node_index);
+        let is_selected = |node_index: IriIndex| {
+            self.ui_state.selected_node == Some(node_index)
+                || self.ui_state.selected_nodes.contains(&node_index)
+                || table_node == Some(node_index)
+        };
+
+        let map_view = &mut self.map_view;
+        let Some(geo_index) = &map_view.geo_index else {
+            return node_action;
+        };
+        let mut fit_selection = false;
+        ui.horizontal(|ui| {
+            ui.label(format!("{} nodes with coordinates", geo_index.points.len()));
+            ui.checkbox(&mut map_view.only_visual_graph, "Only nodes of visual graph");
+            if ui
+                .button(concatcp!(ICON_CENTER, " Fit All"))
+                .on_hover_text("Zoom to all shown nodes")
+                .clicked()
+            {
+                map_view.fit_pending = true;
+            }
+            if ui.button("Zoom to Selection").clicked() {
+                fit_selection = true;
+            }
+        });
+        if geo_index.points.is_empty() {
+            ui.label("No nodes with coordinates (geo:lat/geo:long, schema:latitude/schema:longitude or WKT literals) found");
+            return node_action;
+        }
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        if map_view.fit_pending || fit_selection {
+            let bounds = if fit_selection {
+                geo_index.bounds(|point| is_shown(point.node) && is_selected(point.node))
+            } else {
+                geo_index.bounds(|point| is_shown(point.node))
+            };
+            if let Some(bounds) = bounds {
+                (map_view.center, map_view.zoom) = MapView::fit(&rect, bounds);
+            }
+            map_view.fit_pending = false;
+        }
+        if response.dragged() {
+            let delta = response.drag_delta();
+            map_view.center -= Vec2::new(delta.x / map_view.zoom, -delta.y / map_view.zoom);
+        }
+        if let Some(hover_pos) = response.hover_pos() {
+            let (scroll, zoom_delta) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor = zoom_delta * (scroll / 200.0).exp();
+            if factor != 1.0 {
+                // keep the position under the cursor
+                let geo_pos = map_view.to_geo(&rect, hover_pos);
+                map_view.zoom = (map_view.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+                let moved = map_view.to_geo(&rect, hover_pos);
+                map_view.center += geo_pos - moved;
+            }
+        }
+
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+        let grid_color = visuals.weak_text_color().gamma_multiply(0.4);
+        let text_color = visuals.weak_text_color();
+        let top_left = map_view.to_geo(&rect, rect.left_top());
+        let bottom_right = map_view.to_geo(&rect, rect.right_bottom());
+        let step = GRID_STEPS
+            .iter()
+            .copied()
+            .find(|step| *step as f32 * map_view.zoom >= MIN_GRID_SPACING)
+            .unwrap_or(90.0);
+        let mut lon = (top_left.x as f64 / step).floor() * step;
+        while lon <= bottom_right.x as f64 {
+            if (-180.0..=180.0).contains(&lon) {
+                let x = map_view.to_screen(&rect, lon, 0.0).x;
+                let stroke_color = if lon == 0.0 { text_color } else { grid_color };
+                painter.vline(x, rect.y_range(), Stroke::new(1.0, stroke_color));
+                painter.text(
+                    Pos2::new(x + 2.0, rect.bottom() - 2.0),
+                    Align2::LEFT_BOTTOM,
+                    format!("{}°", lon),
+                    FontId::proportional(10.0),
+                    text_color,
+                );
+            }
+            lon += step;
+        }
+        let mut lat = (bottom_right.y as f64 / step).floor() * step;
+        while lat <= top_left.y as f64 {
+            if (-90.0..=90.0).contains(&lat) {
+                let y = map_view.to_screen(&rect, 0.0, lat).y;
+                let stroke_color = if lat == 0.0 { text_color } else { grid_color };
+                painter.hline(rect.x_range(), y, Stroke::new(1.0, stroke_color));
+                painter.text(
+                    Pos2::new(rect.left() + 2.0, y - 2.0),
+                    Align2::LEFT_BOTTOM,
+                    format!("{}°", lat),
+                    FontId::proportional(10.0),
+                    text_color,
+                );
+            }
+            lat += step;
+        }
+
+        let pointer_pos = response.hover_pos();
+        let mut nearest: Option<(IriIndex, f32)> = None;
+        let mut selected_points = Vec::new();
+        for point in geo_index.points.iter().filter(|point| is_shown(point.node)) {
+            let pos = map_view.to_screen(&rect, point.lon, point.lat);
+            if !rect.expand(PICK_DISTANCE).contains(pos) {
+                continue;
+            }
+            if is_selected(point.node) {
+                selected_points.push((point.node, pos));
+                continue;
+            }
+            let color = match rdf_data.node_data.get_node_by_index(point.node) {
+                Some((_, node)) => self.visualization_style.get_type_style(&node.types).color,
+                None => Color32::GRAY,
+            };
+            painter.circle(pos, POINT_RADIUS, color, Stroke::new(1.0, visuals.window_stroke.color));
+            if let Some(pointer_pos) = pointer_pos {
+                let distance = pointer_pos.distance(pos);
+                if distance <= PICK_DISTANCE && nearest.is_none_or(|(_, nearest)| distance < nearest) {
+                    nearest = Some((point.node, distance));
+                }
+            }
+        }
+        // selected nodes are drawn on top
+        for (node_index, pos) in selected_points.iter() {
+            painter.circle(
+                *pos,
+                POINT_RADIUS + 2.0,
+                visuals.selection.bg_fill,
+                Stroke::new(2.0, visuals.selection.stroke.color),
+            );
+            if let Some(pointer_pos) = pointer_pos {
+                let distance = pointer_pos.distance(*pos);
+                if distance <= PICK_DISTANCE && nearest.is_none_or(|(_, nearest)| distance < nearest) {
+                    nearest = Some((*node_index, distance));
+                }
+            }
+        }
+
+        let should_short_iri = matches!(self.config.iri_display, IriDisplay::Shorten);
+        let node_label = |node_index: IriIndex| -> String {
+            match rdf_data.node_data.get_node_by_index(node_index) {
+                Some((iri, node)) => node
+                    .node_label(
+                        iri,
+                        &self.visualization_style,
+                        should_short_iri,
+                        self.ui_state.display_language,
+                        &rdf_data.node_data.indexers,
+                    )
+                    .to_string(),
+                None => String::new(),
+            }
+        };
+        if let Some((node_index, _)) = nearest {
+            let pos = pointer_pos.unwrap_or_default();
+            let galley = painter.layout_no_wrap(node_label(node_index), FontId::proportional(14.0), visuals.text_color());
+            let label_rect = Rect::from_min_size(pos + Vec2::new(12.0, -galley.size().y - 4.0), galley.size()).expand(3.0);
+            painter.rect_filled(label_rect, 3.0, visuals.window_fill);
+            painter.galley(label_rect.min + Vec2::splat(3.0), galley, visuals.text_color());
+        }
+
+        let mut clicked_node = None;
+        if response.double_clicked() {
+            if let Some((node_index, _)) = nearest {
+                node_action = NodeAction::BrowseNode(node_index);
+            }
+        } else if response.clicked()
+            && let Some((node_index, _)) = nearest
+        {
+            clicked_node = Some((node_index, ui.input(|i| i.modifiers.command || i.modifiers.shift)));
+        }
+        if response.secondary_clicked() {
+            map_view.context_node = nearest.map(|(node_index, _)| node_index);
+        }
+        if let Some(context_node) = map_view.context_node {
+            response.context_menu(|ui| {
+                ui.label(node_label(context_node));
+                ui.separator();
+                if ui.button(concatcp!(ICON_BROWSE, " Browse")).clicked() {
+                    node_action = NodeAction::BrowseNode(context_node);
+                    ui.close();
+                }
+                if ui.button(concatcp!(ICON_GRAPH, " Show in Visual Graph")).clicked() {
+                    node_action = NodeAction::ShowVisual(context_node);
+                    ui.close();
+                }
+                if ui.button(concatcp!(ICON_GRAPH, " Add to Visual Graph")).clicked() {
+                    node_action = NodeAction::AddVisual(context_node);
+                    ui.close();
+                }
+            });
+        }
+        drop(rdf_data);
+        if let Some((node_index, add_to_selection)) = clicked_node {
+            self.select_map_node(node_index, add_to_selection);
+        }
+        node_action
+    }
+
+    /// Selects the node in the visual graph and in the instance table of its type
+    fn select_map_node(&mut self, node_index: IriIndex, add_to_selection: bool) {
+        if add_to_selection {
+            if !self.ui_state.selected_nodes.remove(&node_index) {
+                self.ui_state.selected_nodes.insert(node_index);
+            }
+        } else {
+            self.ui_state.selected_nodes.clear();
+            self.ui_state.selected_nodes.insert(node_index);
+        }
+        self.ui_state.selected_node = Some(node_index);
+        let node_types = match self.rdf_data.read() {
+            Ok(rdf_data) => match rdf_data.node_data.get_node_by_index(node_index) {
+                Some((_, node)) => node.types.clone(),
+                None => return,
+            },
+            Err(_) => return,
+        };
+        // prefer the type shown in the table
+        let table_type = self
+            .type_index
+            .selected_type
+            .filter(|selected_type| node_types.contains(selected_type))
+            .or_else(|| node_types.first().copied());
+        if let Some(table_type) = table_type
+            && let Some(type_data) = self.type_index.types.get_mut(&table_type)
+            && let Some(pos) = type_data.filtered_instances.iter().position(|instance| *instance == node_index)
+        {
+            type_data.instance_view.selected_idx = Some((node_index, pos));
+            self.type_index.selected_type = Some(table_type);
+        }
+        // only nodes of the visual graph are kept in its selection
+        if let Ok(layout) = self.visible_nodes.layout_data.read()
+            && node_pos(&layout.nodes, node_index).is_none()
+        {
+            self.ui_state.selected_nodes.remove(&node_index);
+        }
+    }
+}
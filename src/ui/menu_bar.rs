@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{collections::BTreeSet, path::Path};
 
 #[cfg(target_arch = "wasm32")]
 use crate::uistate::File;
@@ -11,12 +11,13 @@ use rfd::FileDialog;
 use strum::IntoEnumIterator;
 
 use crate::{
-    RdfGlanceApp, SystemMessage,
-    domain::statistics::StatisticsData,
+    IriIndex, RdfGlanceApp, SystemMessage,
+    domain::{LabelContext, recent_items::RecentKind, statistics::StatisticsData},
     graph_algorithms::GraphAlgorithm,
+    integration::{persistency::read_project_thumbnail, stress_data::StressDataConfig, turtle::RdfExportFormat},
     layoutalg::{LayoutAlgorithm, run_layout_algorithm},
     ui::style::ICON_LANG,
-    uistate::{ImportFormat, ImportFromUrlData, actions::NodeContextAction},
+    uistate::{ExportScope, ImportFormat, ImportFromUrlData, actions::NodeContextAction, style_theme::BUNDLED_THEMES},
 };
 
 enum MenuAction {
@@ -47,24 +48,21 @@ impl RdfGlanceApp {
                         menu_action = MenuAction::LoadProject;
                         ui.close_kind(UiKind::Menu);
                     }
-                    if !self.is_empty() && ui.button("Save Project\tCtrl-S").clicked() {
+                    if !self.is_empty() && !self.ui_state.read_only && ui.button("Save Project\tCtrl-S").clicked() {
                         menu_action = MenuAction::SaveProject;
                         ui.close_kind(UiKind::Menu);
                     }
-                    if !self.persistent_data.last_projects.is_empty() {
-                        let mut last_project_clicked: Option<Box<str>> = None;
-                        ui.menu_button("Last Visited Projects:", |ui| {
-                            for last_file in &self.persistent_data.last_projects {
-                                if ui.button(last_file).clicked() {
-                                    last_project_clicked = Some(last_file.clone());
-                                }
-                            }
-                            if let Some(last_project_clicked) = last_project_clicked {
-                                ui.close_kind(UiKind::Menu);
-                                let last_project_path = Path::new(&*last_project_clicked);
-                                self.load_project(last_project_path, ui.visuals().dark_mode);
-                            }
-                        });
+                    if ui.button("Import Analysis Bundle...").clicked() {
+                        self.import_bundle_dialog(ui.visuals().dark_mode);
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if !self.is_empty() && ui.button("Export Analysis Bundle...").clicked() {
+                        self.open_bundle_export();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui.button("Analysis Notes").clicked() {
+                        self.show_analysis_notes = true;
+                        ui.close_kind(UiKind::Menu);
                     }
                     ui.separator();
                 }
@@ -72,11 +70,16 @@ impl RdfGlanceApp {
                     menu_action = MenuAction::ImportRDF;
                     ui.close_kind(UiKind::Menu);
                 }
-                if ui.button("Import RDF File from URL").clicked() {
+                if ui.button("Load from URL...").clicked() {
                     self.import_file_from_url_dialog(ui);
                     ui.close_kind(UiKind::Menu);
                 }
                 #[cfg(not(target_arch = "wasm32"))]
+                if !self.ui_state.read_only && ui.button("Import CSV with Mapping...").clicked() {
+                    self.open_csv_import_dialog();
+                    ui.close_kind(UiKind::Menu);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
                 if ui.button("Import all from dir").clicked() {
                     if let Some(path) = FileDialog::new().pick_folder() {
                         let selected_dir = Some(path.display().to_string());
@@ -86,8 +89,71 @@ impl RdfGlanceApp {
                     }
                     ui.close_kind(UiKind::Menu);
                 }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button("SPARQL Query").clicked() {
+                    self.open_sparql_query();
+                    ui.close_kind(UiKind::Menu);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui
+                    .button("Browse SPARQL Endpoint")
+                    .on_hover_text("Nodes are loaded from the endpoint when browsed or expanded")
+                    .clicked()
+                {
+                    let last_endpoints: Vec<String> = self
+                        .persistent_data
+                        .recent_items
+                        .locations(RecentKind::Endpoint)
+                        .iter()
+                        .map(|endpoint| endpoint.to_string())
+                        .collect();
+                    self.sparql_dialog = Some(crate::ui::sparql_dialog::SparqlDialog::new(&last_endpoints));
+                    ui.close_kind(UiKind::Menu);
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    if ui.button("Apply Project Template").clicked() {
+                        self.apply_project_template_dialog();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if !self.is_empty() && ui.button("Save as Project Template").clicked() {
+                        self.save_project_template_dialog();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if !self.is_empty() && ui.button("Import Style Theme").clicked() {
+                        self.import_style_theme_dialog();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if !self.is_empty() && ui.button("Export Style Theme").clicked() {
+                        self.export_style_theme_dialog();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                }
+                if !self.is_empty() {
+                    ui.menu_button("Bundled Style Themes", |ui| {
+                        for (name, json) in BUNDLED_THEMES.iter() {
+                            if ui.button(*name).clicked() {
+                                self.apply_bundled_theme(json);
+                                ui.close_kind(UiKind::Menu);
+                            }
+                        }
+                    });
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if !self.is_empty() && !self.ui_state.read_only && ui.button("Apply Patch File").clicked() {
+                    self.open_patch_file_dialog();
+                    ui.close_kind(UiKind::Menu);
+                }
+                if !self.is_empty() && ui.button("Visualize IRI List").clicked() {
+                    self.open_node_list();
+                    ui.close_kind(UiKind::Menu);
+                }
+                if !self.is_empty() && ui.button("DCAT Catalog").on_hover_text("Datasets and distributions of data catalogs").clicked() {
+                    self.open_dcat_browser();
+                    ui.close_kind(UiKind::Menu);
+                }
                 if !self.is_empty() {
-                    let has_visual_graph_nodes = !self.visible_nodes.nodes.read().unwrap().is_empty();
+                    let has_visual_graph_nodes = !self.visible_nodes.layout_data.read().unwrap().nodes.is_empty();
                     ui.add_enabled_ui(has_visual_graph_nodes, |ui| {
                         if ui.button("Export Edges").clicked() {
                             #[cfg(not(target_arch = "wasm32"))]
@@ -100,7 +166,7 @@ impl RdfGlanceApp {
                                     use crate::domain::LabelContext;
                                     let label_context = LabelContext::new(
                                         self.ui_state.display_language,
-                                        self.persistent_data.config_data.iri_display,
+                                        self.config.iri_display,
                                         &rdf_data.prefix_manager,
                                     );
                                     let mut wtr = csv::Writer::from_path(path).unwrap();
@@ -119,7 +185,7 @@ impl RdfGlanceApp {
                                 use crate::domain::graph_model::LabelContext;
                                 let label_context = LabelContext::new(
                                     self.ui_state.display_language,
-                                    self.persistent_data.config_data.iri_display,
+                                    self.config.iri_display,
                                     &rdf_data.prefix_manager,
                                 );
                                 let buf = Vec::new();
@@ -151,7 +217,7 @@ impl RdfGlanceApp {
                                     use crate::domain::LabelContext;
                                     let label_context = LabelContext::new(
                                         self.ui_state.display_language,
-                                        self.persistent_data.config_data.iri_display,
+                                        self.config.iri_display,
                                         &rdf_data.prefix_manager,
                                     );
                                     let mut wtr = csv::Writer::from_path(path).unwrap();
@@ -175,7 +241,7 @@ impl RdfGlanceApp {
                                 use crate::domain::graph_model::LabelContext;
                                 let label_context = LabelContext::new(
                                     self.ui_state.display_language,
-                                    self.persistent_data.config_data.iri_display,
+                                    self.config.iri_display,
                                     &rdf_data.prefix_manager,
                                 );
                                 let buf = Vec::new();
@@ -203,45 +269,102 @@ impl RdfGlanceApp {
                         }
                     });
                 }
-                if ui.button("Export SVG").clicked() {
-                    self.export_svg_dialog();
-                    ui.close_kind(UiKind::Menu);
-                }
-                /*
-                if ui.button("Sparql Endpoint").clicked() {
-                    self.sparql_dialog =
-                        Some(SparqlDialog::new(&self.persistent_data.last_endpoints));
-                    ui.close_menu();
-                }
-                 */
-                if !self.persistent_data.last_files.is_empty() {
-                    let mut last_file_clicked: Option<Box<str>> = None;
-                    ui.menu_button("Last Imported Files:", |ui| {
-                        for last_file in &self.persistent_data.last_files {
-                            if ui.button(last_file).clicked() {
-                                last_file_clicked = Some(last_file.clone());
-                            }
+                if !self.is_empty() {
+                    ui.menu_button("Export RDF", |ui| {
+                        let has_visual_graph_nodes = !self.visible_nodes.layout_data.read().unwrap().nodes.is_empty();
+                        let mut scopes: Vec<(ExportScope, &str, bool)> = vec![(ExportScope::AllData, "All Data", true)];
+                        if let Some(type_index) = self.type_index.selected_type {
+                            scopes.push((ExportScope::TypeInstances(type_index), "Filtered Table Instances", true));
                         }
-                        if let Some(last_file_clicked) = last_file_clicked {
-                            ui.close_kind(UiKind::Menu);
-                            let path = Path::new(last_file_clicked.as_ref());
-                            if path.exists() {
-                                if path.is_dir() {
-                                    self.load_ttl_dir(&last_file_clicked);
-                                } else {
-                                    self.load_ttl(&last_file_clicked, ui.visuals().dark_mode);
+                        scopes.push((ExportScope::VisualGraph, "Visual Graph", has_visual_graph_nodes));
+                        for (scope, scope_label, enabled) in scopes {
+                            for format in RdfExportFormat::ALL {
+                                if ui
+                                    .add_enabled(enabled, egui::Button::new(format!("{} as {}", scope_label, format.label())))
+                                    .clicked()
+                                {
+                                    self.export_rdf_dialog(scope, format);
+                                    ui.close_kind(UiKind::Menu);
                                 }
                             }
-                            ui.ctx().request_repaint();
                         }
                     });
                 }
-                if !self.is_empty() {
-                    ui.separator();
-                    if ui.button("Clean Data").clicked() {
-                        self.clean_data();
-                        ui.close_kind(UiKind::Menu);
-                    }
+                #[cfg(not(target_arch = "wasm32"))]
+                if !self.is_empty()
+                    && ui
+                        .button("Compare with File...")
+                        .on_hover_text("Differences between the loaded data and another file, e.g. the previous release")
+                        .clicked()
+                {
+                    ui.close_kind(UiKind::Menu);
+                    self.compare_with_file_dialog();
+                }
+                if !self.is_empty()
+                    && ui
+                        .button("Export SHACL Shapes")
+                        .on_hover_text("Node shapes for all types derived from the instance data")
+                        .clicked()
+                {
+                    self.export_shacl_dialog();
+                    ui.close_kind(UiKind::Menu);
+                }
+                if ui.button("Export SVG").clicked() {
+                    self.export_svg_dialog();
+                    ui.close_kind(UiKind::Menu);
+                }
+                if ui.button("Export PNG").clicked() {
+                    self.open_png_export();
+                    ui.close_kind(UiKind::Menu);
+                }
+                if ui.button("Export GraphML").on_hover_text("Visible graph for yEd or Gephi").clicked() {
+                    self.export_graphml_dialog();
+                    ui.close_kind(UiKind::Menu);
+                }
+                if !self.import_log.is_empty() && ui.button("Import Log").clicked() {
+                    self.ui_state.import_log_window = true;
+                    ui.close_kind(UiKind::Menu);
+                }
+                if ui.button("Log Console").clicked() {
+                    self.open_log_console();
+                    ui.close_kind(UiKind::Menu);
+                }
+                self.recent_items_menu(ui);
+                ui.separator();
+                // data changes can not be undone while loading, the loader writes into the data
+                let can_change_data = self.load_handle.is_none();
+                let undo_text = match self.data_history.undo_description() {
+                    Some(description) => format!("Undo {}", description),
+                    None => "Undo Data Change".to_string(),
+                };
+                if ui
+                    .add_enabled(
+                        can_change_data && self.data_history.undo_description().is_some(),
+                        egui::Button::new(undo_text),
+                    )
+                    .clicked()
+                {
+                    self.undo_data_change(ui.visuals().dark_mode);
+                    ui.close_kind(UiKind::Menu);
+                }
+                let redo_text = match self.data_history.redo_description() {
+                    Some(description) => format!("Redo {}", description),
+                    None => "Redo Data Change".to_string(),
+                };
+                if ui
+                    .add_enabled(
+                        can_change_data && self.data_history.redo_description().is_some(),
+                        egui::Button::new(redo_text),
+                    )
+                    .clicked()
+                {
+                    self.redo_data_change(ui.visuals().dark_mode);
+                    ui.close_kind(UiKind::Menu);
+                }
+                if !self.is_empty() && ui.button("Clean Data").clicked() {
+                    self.record_data_change("Clean Data");
+                    self.clean_data();
+                    ui.close_kind(UiKind::Menu);
                 }
                 consume_keys = true;
             });
@@ -284,23 +407,37 @@ impl RdfGlanceApp {
                             ui.close_kind(UiKind::Menu);
                         }
                     });
+                    ui.add_enabled_ui(self.can_compare_selected_nodes(), |ui| {
+                        if ui.button("Compare Nodes").on_hover_text("Compare 2 to 5 selected nodes").clicked() {
+                            self.compare_selected_nodes();
+                            ui.close_kind(UiKind::Menu);
+                        }
+                    });
                     consume_keys = true;
                 });
                 ui.menu_button("Layout", |ui| {
                     for entry in LayoutAlgorithm::iter() {
                         let label = entry.to_string();
                         if ui.button(label).clicked() {
-                            run_layout_algorithm(
+                            if let Some(warning) = run_layout_algorithm(
                                 entry,
                                 &mut self.visible_nodes,
+                                self.ui_state.selected_node,
                                 &self.ui_state.selected_nodes,
                                 &self.ui_state.hidden_predicates,
                                 &self.visualization_style,
                                 self.rdf_data.clone(),
-                            );
+                                self.ui_state.hierarchy_predicate,
+                                &self.config,
+                            ) {
+                                self.system_message = SystemMessage::Info(warning);
+                            }
                             ui.close_kind(UiKind::Menu);
                         }
                     }
+                    ui.menu_button("Hierarchy Predicate", |ui| {
+                        self.hierarchy_predicate_menu(ui);
+                    });
                     ui.separator();
                     if ui
                         .checkbox(
@@ -312,6 +449,46 @@ impl RdfGlanceApp {
                         self.visualization_style.change_default_styles();
                         self.visible_nodes.update_node_shapes = true;
                     }
+                    let mut color_by_namespace = self.config.color_by_namespace;
+                    if ui
+                        .checkbox(&mut color_by_namespace, "Color types by namespace")
+                        .on_hover_text("Types of the same namespace (prefix) get shades of the same color")
+                        .changed()
+                    {
+                        // the value is changed on the level it comes from (project or global)
+                        match &mut self.project_config.color_by_namespace {
+                            Some(value) => *value = color_by_namespace,
+                            None => self.persistent_data.config_data.color_by_namespace = color_by_namespace,
+                        }
+                        self.update_config();
+                        self.update_type_colors(ui.visuals().dark_mode);
+                    }
+                    ui.separator();
+                    if ui.button("Semantic Zoom...").clicked() {
+                        self.open_semantic_zoom();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui
+                        .button("Social / Org Chart")
+                        .on_hover_text("Styles and layout for FOAF and ORG data: people with photos, organizations, reporting lines")
+                        .clicked()
+                    {
+                        self.apply_social_chart(ui.visuals().dark_mode);
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if let Some(social_chart) = &mut self.social_chart {
+                        ui.checkbox(&mut social_chart.show_photos, "Show photos");
+                        let has_pins = !social_chart.pinned_nodes.is_empty();
+                        if ui
+                            .add_enabled(has_pins, egui::Button::new("Release Org Chart Pins"))
+                            .on_hover_text("Unpin the nodes placed by the org chart, nodes pinned by you stay pinned")
+                            .clicked()
+                        {
+                            self.release_social_chart_pins();
+                            self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+                            ui.close_kind(UiKind::Menu);
+                        }
+                    }
                 });
             }
             if !self.is_empty() {
@@ -319,22 +496,32 @@ impl RdfGlanceApp {
                     for entry in GraphAlgorithm::iter() {
                         let label = entry.to_string();
                         if ui.button(label).clicked() {
-                            if self.visible_nodes.nodes.read().unwrap().is_empty() {
+                            if self.visible_nodes.layout_data.read().unwrap().nodes.is_empty() {
                                 self.system_message = SystemMessage::Info(
                                     "No data to compute statistics. Add nodes to visual graph".to_string(),
                                 );
                                 ui.close_kind(UiKind::Menu);
                                 return;
                             }
+                            if entry == GraphAlgorithm::BetweennessCentrality {
+                                // can take long for big graphs, computed in background with progress
+                                self.start_betweenness_job();
+                                ui.close_kind(UiKind::Menu);
+                                return;
+                            }
                             if self.statistics_data.is_none() {
                                 self.statistics_data = Some(StatisticsData::default());
                             }
+                            let weighted_edges =
+                                if entry.is_weighted() { self.statistics_weighted_edges() } else { Vec::new() };
                             self.visible_nodes.run_algorithm(
                                 entry,
                                 &self.visualization_style,
                                 self.statistics_data.as_mut().unwrap(),
-                                &self.persistent_data.config_data,
+                                &self.config,
                                 &self.ui_state.hidden_predicates,
+                                None,
+                                &weighted_edges,
                             );
                             // TODO ask for confirmation
                             self.visualization_style.use_size_overwrite = true;
@@ -364,6 +551,60 @@ impl RdfGlanceApp {
                             {
                                 self.visible_nodes.update_node_shapes = true;
                             }
+                            for cluster_result in self.cluster_results() {
+                                if ui
+                                    .button(format!("Apply {} to Graph", cluster_result))
+                                    .on_hover_text("Color the nodes by cluster")
+                                    .clicked()
+                                {
+                                    self.apply_clusters(cluster_result);
+                                    ui.close_kind(UiKind::Menu);
+                                }
+                            }
+                            if ui
+                                .checkbox(&mut self.visible_nodes.group_by_cluster, "Group layout by cluster")
+                                .on_hover_text("Force layout pulls the nodes of the same cluster together")
+                                .changed()
+                            {
+                                self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+                            }
+                            if ui
+                                .add_enabled(
+                                    self.visualization_style.use_color_overwrite,
+                                    egui::Button::new("Collapse All Clusters"),
+                                )
+                                .on_hover_text("Show every cluster as one node, double click expands it")
+                                .clicked()
+                            {
+                                self.collapse_all_clusters();
+                                ui.close_kind(UiKind::Menu);
+                            }
+                            if ui
+                                .add_enabled(
+                                    !self.visible_nodes.collapsed_clusters.is_empty(),
+                                    egui::Button::new("Expand All Clusters"),
+                                )
+                                .clicked()
+                            {
+                                self.expand_all_clusters();
+                                ui.close_kind(UiKind::Menu);
+                            }
+                            if ui
+                                .button("Metric Correlation Plot")
+                                .on_hover_text("Scatter plot of two statistics or a statistic and a numeric property")
+                                .clicked()
+                            {
+                                self.open_metric_correlation();
+                                ui.close_kind(UiKind::Menu);
+                            }
+                            if ui
+                                .button("Metric Summary by Type")
+                                .on_hover_text("Mean, median and max of the statistics for each type")
+                                .clicked()
+                            {
+                                self.open_type_metric_summary();
+                                ui.close_kind(UiKind::Menu);
+                            }
                         },
                     );
                     ui.separator();
@@ -373,9 +614,35 @@ impl RdfGlanceApp {
                         }
                         self.visualization_style.use_size_overwrite = false;
                         self.visualization_style.use_color_overwrite = false;
+                        self.visible_nodes.group_by_cluster = false;
                         self.visible_nodes.update_node_shapes = true;
                         ui.close_kind(UiKind::Menu);
                     }
+                    ui.separator();
+                    if ui.button("Validate SKOS").on_hover_text("Check mappings and hierarchy of all SKOS concepts").clicked() {
+                        self.validate_skos();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui.button("Find Cycles").on_hover_text("Find reference cycles in all data").clicked() {
+                        self.open_cycle_search();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui.button("Dependency Order").on_hover_text("Order nodes topologically by one predicate").clicked() {
+                        self.open_dependency_order();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui.button("Transitive Closure").on_hover_text("All nodes reachable over one predicate, optionally as virtual edges").clicked() {
+                        self.open_transitive_closure();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui.button("Edge Metadata").on_hover_text("Edge width, color and date filter from reified statements").clicked() {
+                        self.open_edge_metadata();
+                        ui.close_kind(UiKind::Menu);
+                    }
+                    if ui.button("Wikidata Statements").on_hover_text("Collapse Wikidata statement nodes to edges with qualifiers").clicked() {
+                        self.open_wikidata_statements(ui.visuals().dark_mode);
+                        ui.close_kind(UiKind::Menu);
+                    }
                     consume_keys = true;
                 });
             }
@@ -384,6 +651,24 @@ impl RdfGlanceApp {
                     self.ui_state.about_window = true;
                     ui.close_kind(UiKind::Menu);
                 }
+                if ui.button("Session Statistics").clicked() {
+                    self.ui_state.session_statistics_window = true;
+                    ui.close_kind(UiKind::Menu);
+                }
+                if ui.button("Reset Settings...").clicked() {
+                    self.open_reset_settings();
+                    ui.close_kind(UiKind::Menu);
+                }
+                ui.menu_button("Developer", |ui| {
+                    if ui
+                        .button("Generate Stress Test Data")
+                        .on_hover_text("Synthetic data to evaluate performance settings")
+                        .clicked()
+                    {
+                        self.stress_data_config = Some(StressDataConfig::default());
+                        ui.close_kind(UiKind::Menu);
+                    }
+                });
                 ui.hyperlink_to(
                     "Manual/Documentation",
                     "https://github.com/xdobry/rdfglance/blob/main/documentation/manual.md",
@@ -414,6 +699,13 @@ impl RdfGlanceApp {
                                     }
                                 }
                             });
+                        if ui
+                            .button(ICON_LANG)
+                            .on_hover_text("Display language and fallback languages")
+                            .clicked()
+                        {
+                            self.show_language_panel = !self.show_language_panel;
+                        }
                         ui.label(format!("{:.4}", self.ui_state.cpu_usage));
                     });
                 }
@@ -432,14 +724,82 @@ impl RdfGlanceApp {
         match menu_action {
             MenuAction::ImportRDF => self.import_file_dialog(ui),
             MenuAction::LoadProject => self.load_project_dialog(ui.visuals().dark_mode),
-            MenuAction::SaveProject => self.save_project_dialog(),
+            MenuAction::SaveProject => {
+                if !self.ui_state.read_only {
+                    self.save_project_dialog()
+                }
+            }
             MenuAction::None => {}
         }
     }
+    /// Predicate that defines the layers of the hierarchical layout, chosen from the predicates of the visible edges
+    fn hierarchy_predicate_menu(&mut self, ui: &mut egui::Ui) {
+        let mut predicates: Vec<IriIndex> = self
+            .visible_nodes
+            .layout_data
+            .read()
+            .map(|layout| {
+                layout
+                    .edges
+                    .iter()
+                    .filter(|edge| edge.from != edge.to && !self.ui_state.hidden_predicates.contains(edge.predicate))
+                    .map(|edge| edge.predicate)
+                    .collect::<BTreeSet<IriIndex>>()
+                    .into_iter()
+                    .collect()
+            })
+            .unwrap_or_default();
+        if let Some((predicate, _)) = self.ui_state.hierarchy_predicate
+            && !predicates.contains(&predicate)
+        {
+            predicates.push(predicate);
+        }
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let label_context =
+            LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
+        let mut labeled: Vec<(IriIndex, String)> = predicates
+            .into_iter()
+            .map(|predicate| {
+                let label = rdf_data
+                    .node_data
+                    .predicate_display(predicate, &label_context, &rdf_data.node_data.indexers);
+                (predicate, label.as_str().to_string())
+            })
+            .collect();
+        labeled.sort_by(|a, b| a.1.cmp(&b.1));
+        let mut upward = self.ui_state.hierarchy_predicate.is_none_or(|(_, upward)| upward);
+        if ui
+            .radio(self.ui_state.hierarchy_predicate.is_none(), "All visible edges")
+            .on_hover_text("The layers are inferred from all visible edges")
+            .clicked()
+        {
+            self.ui_state.hierarchy_predicate = None;
+        }
+        egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+            for (predicate, label) in labeled.iter() {
+                let selected = self.ui_state.hierarchy_predicate.is_some_and(|(selected, _)| selected == *predicate);
+                if ui.radio(selected, label).clicked() {
+                    self.ui_state.hierarchy_predicate = Some((*predicate, upward));
+                }
+            }
+        });
+        ui.separator();
+        if ui
+            .checkbox(&mut upward, "Object is superior")
+            .on_hover_text("As for rdfs:subClassOf, skos:broader or org:reportsTo, otherwise the subject is the superior")
+            .changed()
+            && let Some((predicate, _)) = self.ui_state.hierarchy_predicate
+        {
+            self.ui_state.hierarchy_predicate = Some((predicate, upward));
+        }
+    }
+
     pub fn import_file_from_url_dialog(&mut self, _ui: &mut egui::Ui) {
         self.import_from_url = Some(ImportFromUrlData {
             url: String::new(),
-            format: ImportFormat::Turtle,
+            format: ImportFormat::Auto,
             focus_requested: false,
         });
     }
@@ -447,7 +807,7 @@ impl RdfGlanceApp {
     pub fn import_file_dialog(&mut self, ui: &mut egui::Ui) {
         #[cfg(not(target_arch = "wasm32"))]
         if let Some(path) = FileDialog::new()
-            .add_filter("RDF Files", &["ttl", "rdf", "xml", "nt", "trig", "nq", "jsonld","csv","json","ndjson"])
+            .add_filter("RDF Files", &["ttl", "rdf", "xml", "nt", "trig", "nq", "jsonld","csv","json","ndjson","hdt"])
             .pick_file()
         {
             let selected_file = Some(path.display().to_string());
@@ -460,7 +820,7 @@ impl RdfGlanceApp {
             use poll_promise::Promise;
             self.file_upload = Some(Promise::spawn_local(async {
                 let file_selected = rfd::AsyncFileDialog::new()
-                    .add_filter("rdf", &["ttl", "rdf", "xml", "nt", "trig", "nq", "jsonld","csv","json","ndjson"])
+                    .add_filter("rdf", &["ttl", "rdf", "xml", "nt", "trig", "nq", "jsonld","csv","json","ndjson","hdt"])
                     .pick_file()
                     .await;
                 if let Some(curr_file) = file_selected {
@@ -481,13 +841,17 @@ impl RdfGlanceApp {
         if let Some(result) = &self.file_upload {
             match &result.ready() {
                 Some(Ok(File { path, data })) => {
-                    let language_filter = self.persistent_data.config_data.language_filter();
+                    let language_filter = self.config.language_filter();
+                    let data_loading = crate::uistate::DataLoading::new();
                     let rdfttl = if let Ok(mut rdf_data) = self.rdf_data.write() {
-                        let rdfttl = RDFWrap::load_file_data(path, data, &mut rdf_data, &language_filter);
+                        let description = if rdf_data.node_data.is_empty() { "Load" } else { "Merge" };
+                        self.data_history.record_snapshot(&format!("{} {}", description, path), &rdf_data, &self.config);
+                        let rdfttl = RDFWrap::load_file_data(path, data, &mut *rdf_data, &language_filter, Some(&data_loading));
                         Some(rdfttl)
                     } else {
                         None
                     };
+                    self.collect_import_reports(&data_loading);
                     if let Some(rdfttl) = rdfttl {
                         match rdfttl {
                             Err(err) => {
@@ -497,6 +861,7 @@ impl RdfGlanceApp {
                                 let load_message = format!("Loaded: {} triples: {}", path, triples_count);
                                 self.set_status_message(&load_message);
                                 self.update_data_indexes(visuals.dark_mode);
+                                self.apply_pending_template();
                             }
                         }
                     }
@@ -529,14 +894,29 @@ impl RdfGlanceApp {
                 self.clean_data();
                 self.rdf_data = app_data.rdf_data;
                 self.ui_state = app_data.ui_state;
+                self.project_config = app_data.project_config;
                 self.visible_nodes = app_data.visible_nodes;
+                self.graph_state = app_data.graph_state;
+                self.update_config();
                 self.update_data_indexes(is_dark_mode);
+                if let Some(table_layout) = app_data.pending_table_layout {
+                    self.type_index.apply_table_layout(table_layout);
+                }
                 if !app_data.visualization_style.node_styles.is_empty() {
                     self.visualization_style = app_data.visualization_style;
                 }
-                let file_name: Box<str> = Box::from(path.display().to_string());
-                if !self.persistent_data.last_projects.iter().any(|f| *f == file_name) {
-                    self.persistent_data.last_projects.push(file_name);
+                self.visible_nodes.edge_forces = self.visualization_style.edge_layout_forces();
+                #[cfg(not(target_arch = "wasm32"))]
+                if let Some(layout_journal) = &mut self.layout_journal {
+                    layout_journal.saved(&path.display().to_string(), &self.visible_nodes);
+                }
+                let recent_item = self
+                    .persistent_data
+                    .recent_items
+                    .touch(RecentKind::Project, &path.display().to_string());
+                // the project could be saved on other machine so take its own thumbnail
+                if let Ok(Some(thumbnail)) = read_project_thumbnail(path) {
+                    recent_item.thumbnail = Some(thumbnail);
                 }
             }
         }
@@ -554,10 +934,14 @@ impl RdfGlanceApp {
                     self.system_message = SystemMessage::Error(format!("Can not save project: {}", e));
                 }
                 Ok(_) => {
-                    let file_name: Box<str> = Box::from(path.display().to_string());
-                    if !self.persistent_data.last_projects.iter().any(|f| *f == file_name) {
-                        self.persistent_data.last_projects.push(file_name);
+                    if let Some(layout_journal) = &mut self.layout_journal {
+                        layout_journal.saved(&path.display().to_string(), &self.visible_nodes);
                     }
+                    let thumbnail = self.graph_thumbnail();
+                    self.persistent_data
+                        .recent_items
+                        .touch(RecentKind::Project, &path.display().to_string())
+                        .thumbnail = Some(thumbnail);
                     self.set_status_message("Project saved");
                 }
             }
@@ -1,61 +1,73 @@
-use std::sync::{Arc, RwLock};
-
-use egui::{Color32, Key, Popup, Pos2, Rect, Sense, Slider, Vec2};
+use egui::{Color32, Popup, Pos2, Rect, Sense, Slider, Vec2};
 
 use crate::{
-    IriIndex, RdfGlanceApp, domain::{LabelContext, graph_styles::{EdgeFont, EdgeStyle, NodeStyle}, type_index::TypeInstanceIndex}, support::{SortedVec, uitools::popup_at}, ui::{draw_edge, draw_node_label, draw_self_edge, graph_view::is_overlapping, style::{ICON_REDO, ICON_UNDO}}, uistate::{actions::NodeAction, layout::{Edge, LayoutConfUpdate, NodeLayout, NodeShapeData, SortedNodeLayout, update_edges_groups}}
+    IriIndex, RdfGlanceApp, domain::{LabelContext, NodeData, config::IriDisplay, graph_styles::{ArrowStyle, EdgeFont, EdgeStyle, LabelPosition, LineStyle, NodeShape, NodeSize, NodeStyle}, keymap::ViewAction, type_index::TypeInstanceIndex, type_samples::{INSTANCE_OF_EDGE, TypeSamples, sample_instance, sample_node_index}}, support::{SortedVec, uitools::popup_at}, ui::{draw_edge, draw_node_label, draw_self_edge, graph_view::is_overlapping, style::{ICON_REDO, ICON_UNDO}}, uistate::{actions::NodeAction, layout::{Edge, LayoutConfUpdate, LayoutData, NodeLayout, NodeShapeData, SortedNodeLayout, update_edges_groups}}
 };
 
 const NODE_RMIN: f32 = 4.0;
 const NODE_RMAX: f32 = 80.0;
+const MAX_SAMPLES: usize = 10;
 
 impl RdfGlanceApp {
     pub fn show_meta_graph(&mut self, ui: &mut egui::Ui) -> NodeAction {
         let mut node_action = NodeAction::None;
+        let view_actions = ui.input(|i| self.config.keymap.pressed(i));
 
         ui.horizontal(|ui| {
             if ui.button("Rebuild Meta Graph").clicked() {
                 self.build_meta_graph();
             }
             let undo_button = ui.add_enabled(!self.meta_nodes.undo_stack.is_empty(), egui::Button::new(ICON_UNDO));
-            if undo_button.on_hover_text("Undo").clicked() 
-                || ui.input(|i| i.modifiers.command && i.key_pressed(Key::Z)) {
-                self.meta_nodes.undo(&self.persistent_data.config_data, &self.ui_state.hidden_predicates );
+            if undo_button.on_hover_text(self.config.keymap.hint(ViewAction::Undo)).clicked() 
+                || view_actions.contains(&ViewAction::Undo) {
+                self.meta_nodes.undo(&self.config, &self.ui_state.hidden_predicates );
             }
             let redo_button = ui.add_enabled(!self.meta_nodes.redo_stack.is_empty(), egui::Button::new(ICON_REDO));
-            if redo_button.on_hover_text("Redo").clicked() 
-                || ui.input(|i| i.modifiers.command && i.key_pressed(Key::Y)) {
-                self.meta_nodes.redo(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+            if redo_button.on_hover_text(self.config.keymap.hint(ViewAction::Redo)).clicked() 
+                || view_actions.contains(&ViewAction::Redo) {
+                self.meta_nodes.redo(&self.config, &self.ui_state.hidden_predicates);
             }
             if ui.checkbox(&mut self.ui_state.meta_count_to_size, "Instance Count as Size").clicked() {
                 self.meta_nodes.update_node_shapes = true;
             }
+            ui.checkbox(&mut self.ui_state.meta_count_to_width, "Reference Count as Width");
+            ui.checkbox(&mut self.ui_state.meta_cardinalities, "Cardinalities");
+            ui.add(Slider::new(&mut self.ui_state.meta_sample_count, 1..=MAX_SAMPLES).text("samples"))
+                .on_hover_text("Number of example instances shown by Show Samples");
+            if ui.add_enabled(!self.meta_samples.is_empty(), egui::Button::new("Hide Samples")).clicked() {
+                self.hide_all_samples();
+            }
+            if ui.button("Export SVG").on_hover_text("Meta graph with a legend of type colors").clicked() {
+                self.export_meta_svg_dialog();
+            }
             self.meta_nodes
-                .show_handle_layout_ui(ui, &self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                .show_handle_layout_ui(ui, &self.config, &self.ui_state.hidden_predicates);
             ui.label("nodes force");
             let response = ui.add(Slider::new(
-                &mut self.persistent_data.config_data.m_repulsion_constant,
+                &mut self.config.m_repulsion_constant,
                 0.1..=8.0,
             ));
             if response.changed() {
+                self.store_layout_forces();
                 if let Some(layout_handle) = &self.meta_nodes.layout_handle {
                     let _ = layout_handle.update_sender.send(LayoutConfUpdate::UpdateRepulsionConstant(
-                        self.persistent_data.config_data.m_repulsion_constant));
+                        self.config.m_repulsion_constant));
                 } else {
-                    self.meta_nodes.start_layout(&self.persistent_data.config_data,&self.ui_state.hidden_predicates);
+                    self.meta_nodes.start_layout(&self.config,&self.ui_state.hidden_predicates);
                 }
             }
             ui.label("edges force");
             let response = ui.add(Slider::new(
-                &mut self.persistent_data.config_data.m_attraction_factor,
+                &mut self.config.m_attraction_factor,
                 0.02..=3.0,
             ));
             if response.changed() {
+                self.store_layout_forces();
                 if let Some(layout_handle) = &self.meta_nodes.layout_handle {
                     let _ = layout_handle.update_sender.send(LayoutConfUpdate::UpdateAttractionFactor(
-                        self.persistent_data.config_data.m_attraction_factor));
+                        self.config.m_attraction_factor));
                 } else {
-                    self.meta_nodes.start_layout(&self.persistent_data.config_data,&self.ui_state.hidden_predicates);
+                    self.meta_nodes.start_layout(&self.config,&self.ui_state.hidden_predicates);
                 }
             }
         });
@@ -78,8 +90,12 @@ impl RdfGlanceApp {
             let mut single_clicked = false;
             let mut double_clicked = false;
             let mut primary_down = false;
+            let mut command_down = false;
             // let mut was_context_click = false;
             let mut node_to_click: Option<IriIndex> = None;
+            // samples are changed after the drawing that holds the data lock
+            let mut sample_type: Option<IriIndex> = None;
+            let mut samples_to_hide: Option<IriIndex> = None;
             let mut node_to_hover: Option<IriIndex> = None;
             let mut was_action = false;
 
@@ -112,6 +128,7 @@ impl RdfGlanceApp {
                     single_clicked = input.pointer.button_clicked(egui::PointerButton::Primary);
                     secondary_clicked = input.pointer.button_clicked(egui::PointerButton::Secondary);
                     double_clicked = input.pointer.button_double_clicked(egui::PointerButton::Primary);
+                    command_down = input.modifiers.command;
                     if input.pointer.button_pressed(egui::PointerButton::Primary) {
                         primary_down = true;
                     }
@@ -122,160 +139,317 @@ impl RdfGlanceApp {
                 if let Ok(rdf_data) = self.rdf_data.read() {
                     let label_context = LabelContext::new(
                         self.ui_state.display_language,
-                        self.persistent_data.config_data.iri_display,
+                        self.config.iri_display,
                         &rdf_data.prefix_manager,
                     );
                     let mut edge_style: EdgeStyle = EdgeStyle {
                         edge_font: Some(EdgeFont {
                             font_size: 14.0,
                             font_color: Color32::BLACK,
+                            ..EdgeFont::default()
                         }),
                         ..EdgeStyle::default()
                     };
-                    edge_count += self.meta_nodes.edges.read().unwrap().len();
+                    edge_count += self.meta_nodes.layout_data.read().unwrap().edges.len();
                     if let Some(node_to_drag_index) = &self.ui_state.node_to_drag {
                         if let Some(node_pos) = self.meta_nodes.get_pos(*node_to_drag_index) {
-                            if let Ok(mut positions) = self.meta_nodes.positions.write() {
+                            if let Ok(mut layout) = self.meta_nodes.layout_data.write() {
+                                let positions = &mut layout.positions;
                                 positions[node_pos].pos =
                                     (mouse_pos - center - self.ui_state.drag_diff.to_vec2()).to_pos2();
                             }
                         }
                     }
-                    if let Ok(positions) = self.meta_nodes.positions.read() {
-                        if let Ok(edges) = self.meta_nodes.edges.read() {
-                            if let Ok(node_shapes) = self.meta_nodes.node_shapes.read() {
-                                for edge in edges.iter() {
-                                    let node_label = || {
-                                        let reference_label = rdf_data.node_data.predicate_display(
-                                            edge.predicate,
-                                            &label_context,
-                                            &rdf_data.node_data.indexers,
-                                        );
-                                        reference_label.as_str().to_owned()
-                                    };
-                                    let pos1 = center + positions[edge.from].pos.to_vec2();
-                                    let p_edge_style = self.visualization_style.get_edge_syle(edge.predicate, ui.visuals().dark_mode);
-                                    edge_style.color = p_edge_style.color;
-                                    if edge.from != edge.to {
-                                        let node_shape_from = &node_shapes[edge.from];
-                                        let node_shape_to = &node_shapes[edge.to];
-                                        let pos2 = center + positions[edge.to].pos.to_vec2();
-                                        draw_edge(
-                                            painter,
-                                            pos1,
-                                            node_shape_from.size,
-                                            node_shape_from.node_shape,
-                                            pos2,
-                                            node_shape_to.size,
-                                            node_shape_to.node_shape,
-                                            &edge_style,
-                                            node_label,
-                                            false,
-                                            edge.bezier_distance,
-                                            ui.visuals()
-                                        );
-                                    } else {
-                                        let node_shape_from = &node_shapes[edge.from];
-                                        draw_self_edge(
-                                            painter,
-                                            pos1,
-                                            node_shape_from.size,
-                                            edge.bezier_distance,
-                                            node_shape_from.node_shape,
-                                            &edge_style,
-                                            false,
-                                            node_label,
-                                            ui.visuals(),
+                    let mut computed_node_shapes = None;
+                    if let Ok(layout) = self.meta_nodes.layout_data.read() {
+                        let LayoutData { nodes, edges, positions, node_shapes, .. } = &*layout;
+                        let edge_references: Vec<MetaEdgeReferences> = edges
+                            .iter()
+                            .map(|edge| {
+                                meta_edge_references(
+                                    &self.type_index,
+                                    nodes[edge.from].node_index,
+                                    nodes[edge.to].node_index,
+                                    edge.predicate,
+                                )
+                            })
+                            .collect();
+                        let max_count = edge_references
+                            .iter()
+                            .map(|references| references.count)
+                            .max()
+                            .unwrap_or(0);
+                        let cardinality_font = egui::FontId::proportional(11.0);
+                        let cardinality_color = ui.visuals().text_color();
+                        let sub_class_of = self.type_index.type_hierarchy.as_ref().map(|h| h.sub_class_of);
+                        for (edge, references) in edges.iter().zip(edge_references.iter()) {
+                            if edge.predicate == INSTANCE_OF_EDGE {
+                                let node_shape_from = &node_shapes[edge.from];
+                                let node_shape_to = &node_shapes[edge.to];
+                                let instance_of_style = EdgeStyle {
+                                    color: ui.visuals().weak_text_color(),
+                                    line_style: LineStyle::Dotted,
+                                    ..EdgeStyle::default()
+                                };
+                                draw_edge(
+                                    painter,
+                                    center + positions[edge.from].pos.to_vec2(),
+                                    node_shape_from.size,
+                                    node_shape_from.node_shape,
+                                    center + positions[edge.to].pos.to_vec2(),
+                                    node_shape_to.size,
+                                    node_shape_to.node_shape,
+                                    &instance_of_style,
+                                    || String::from("a"),
+                                    false,
+                                    false,
+                                    edge.bezier_distance,
+                                    ui.visuals(),
+                                );
+                                continue;
+                            }
+                            let is_sample_edge = sample_instance(nodes[edge.from].node_index).is_some();
+                            let node_label = || {
+                                let reference_label = rdf_data.node_data.predicate_display(
+                                    edge.predicate,
+                                    &label_context,
+                                    &rdf_data.node_data.indexers,
+                                );
+                                reference_label.as_str().to_owned()
+                            };
+                            let pos1 = center + positions[edge.from].pos.to_vec2();
+                            let p_edge_style = self.visualization_style.get_edge_syle(edge.predicate, ui.visuals().dark_mode);
+                            edge_style.color = p_edge_style.color;
+                            edge_style.width = if self.ui_state.meta_count_to_width && !is_sample_edge {
+                                reference_width(references.count, max_count)
+                            } else {
+                                EdgeStyle::default().width
+                            };
+                            // inheritance is drawn like UML generalization
+                            let is_inheritance = edge.from != edge.to && sub_class_of == Some(edge.predicate);
+                            if is_inheritance {
+                                edge_style.width = EdgeStyle::default().width;
+                                edge_style.line_style = LineStyle::Dashed;
+                                edge_style.target_style = ArrowStyle::ArrorTriangle;
+                            } else {
+                                edge_style.line_style = LineStyle::Solid;
+                                edge_style.target_style = ArrowStyle::Arrow;
+                            }
+                            if edge.from != edge.to {
+                                let node_shape_from = &node_shapes[edge.from];
+                                let node_shape_to = &node_shapes[edge.to];
+                                let pos2 = center + positions[edge.to].pos.to_vec2();
+                                draw_edge(
+                                    painter,
+                                    pos1,
+                                    node_shape_from.size,
+                                    node_shape_from.node_shape,
+                                    pos2,
+                                    node_shape_to.size,
+                                    node_shape_to.node_shape,
+                                    &edge_style,
+                                    node_label,
+                                    true,
+                                    false,
+                                    edge.bezier_distance,
+                                    ui.visuals()
+                                );
+                                if self.ui_state.meta_cardinalities && !is_inheritance && !is_sample_edge {
+                                    let dir = (pos2 - pos1).normalized();
+                                    // bezier edges of parallel edges are bend to the side
+                                    let side = dir.rot90() * (edge.bezier_distance * 0.5 + 10.0);
+                                    let from_distance = node_shape_from.size.max_elem() * 0.5 + 14.0;
+                                    let to_distance = node_shape_to.size.max_elem() * 0.5 + 14.0;
+                                    painter.text(
+                                        pos1 + dir * from_distance + side,
+                                        egui::Align2::CENTER_CENTER,
+                                        references.out_cardinality.as_str(),
+                                        cardinality_font.clone(),
+                                        cardinality_color,
+                                    );
+                                    if let Some(in_cardinality) = &references.in_cardinality {
+                                        painter.text(
+                                            pos2 - dir * to_distance + side,
+                                            egui::Align2::CENTER_CENTER,
+                                            in_cardinality.as_str(),
+                                            cardinality_font.clone(),
+                                            cardinality_color,
                                         );
                                     }
                                 }
+                            } else {
+                                let node_shape_from = &node_shapes[edge.from];
+                                draw_self_edge(
+                                    painter,
+                                    pos1,
+                                    node_shape_from.size,
+                                    edge.bezier_distance,
+                                    node_shape_from.node_shape,
+                                    &edge_style,
+                                    false,
+                                    node_label,
+                                    true,
+                                    ui.visuals(),
+                                );
                             }
                         }
                         let mut node_style: NodeStyle = NodeStyle::default();
-                        if let Ok(nodes) = self.meta_nodes.nodes.read() {
-                            let mut new_node_shapes: Option<Vec<NodeShapeData>> = if self.meta_nodes.update_node_shapes {
-                                Some(Vec::with_capacity(nodes.len()))
-                            } else {
-                                None
-                            };
-                            for (node_layout, node_position) in nodes.iter().zip(positions.iter()) {
-                                let pos = center + node_position.pos.to_vec2();
-                                let type_style = self.visualization_style.get_type_style_one(node_layout.node_index);
-                                node_style.color = type_style.color;
-                                if self.ui_state.meta_count_to_size
-                                    && self.type_index.min_instance_type_count < self.type_index.max_instance_type_count
-                                {
-                                    let type_data = self.type_index.types.get(&node_layout.node_index);
-                                    if let Some(type_data) = type_data {
-                                        node_style.width = value_to_radius(
-                                            type_data.instances.len(),
-                                            self.type_index.min_instance_type_count,
-                                            self.type_index.max_instance_type_count,
-                                            NODE_RMIN,
-                                            NODE_RMAX,
-                                        );
+                        let mut sample_style = NodeStyle {
+                            node_shape: NodeShape::Rect,
+                            node_size: NodeSize::Label,
+                            label_position: LabelPosition::Center,
+                            font_size: 12.0,
+                            ..NodeStyle::default()
+                        };
+                        let should_short_iri = matches!(self.config.iri_display, IriDisplay::Shorten);
+                        let mut new_node_shapes: Option<Vec<NodeShapeData>> = if self.meta_nodes.update_node_shapes {
+                            Some(Vec::with_capacity(nodes.len()))
+                        } else {
+                            None
+                        };
+                        for (node_layout, node_position) in nodes.iter().zip(positions.iter()) {
+                            let pos = center + node_position.pos.to_vec2();
+                            let sample = sample_instance(node_layout.node_index);
+                            if let Some(instance) = sample {
+                                let Some((iri, node)) = rdf_data.node_data.get_node_by_index(instance) else {
+                                    if let Some(new_node_shapes) = &mut new_node_shapes {
+                                        new_node_shapes.push(NodeShapeData::default());
                                     }
-                                } else {
-                                    node_style.width = 15.0;
-                                }
-                                let type_display = rdf_data.node_data.type_display(
-                                    node_layout.node_index,
-                                    &label_context,
+                                    continue;
+                                };
+                                let sample_type = self.meta_samples.sample_types(instance).next().unwrap_or_default();
+                                sample_style.color = self.visualization_style.get_type_style_one(sample_type).color;
+                                let instance_label = node.node_label(
+                                    iri,
+                                    &self.visualization_style,
+                                    should_short_iri,
+                                    self.ui_state.display_language,
                                     &rdf_data.node_data.indexers,
                                 );
                                 let (node_rect, node_shape) = draw_node_label(
                                     painter,
-                                    type_display.as_str(),
-                                    &node_style,
+                                    instance_label,
+                                    &sample_style,
                                     pos,
                                     self.ui_state.selected_node == Some(node_layout.node_index),
                                     false,
                                     false,
                                     true,
                                     0,
-                                    ui.visuals()
+                                    ui.visuals(),
                                 );
                                 if let Some(new_node_shapes) = &mut new_node_shapes {
                                     new_node_shapes.push(NodeShapeData {
                                         node_shape,
                                         size: node_rect.size(),
-                                        ..Default::default()
                                     });
-                                }   
-                                if self.ui_state.context_menu_node.is_none() || was_action {
-                                    if single_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                }
+                                if (self.ui_state.context_menu_node.is_none() || was_action)
+                                    && is_overlapping(&node_rect, mouse_pos, node_shape)
+                                {
+                                    // samples have no context menu, ctrl-click and double click open the instance
+                                    if single_clicked {
                                         self.ui_state.selected_node = Some(node_layout.node_index);
+                                        if command_down {
+                                            node_action = NodeAction::BrowseNode(instance);
+                                        }
                                         was_action = true;
                                     }
-                                    if primary_down && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    if primary_down {
                                         self.ui_state.node_to_drag = Some(node_layout.node_index);
                                         self.ui_state.drag_diff = (mouse_pos - node_rect.center()).to_pos2();
                                         was_action = true;
                                     }
-                                    if double_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
-                                        node_to_click = Some(node_layout.node_index);
-                                        was_action = true;
-                                    }
-                                    if secondary_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
-                                        was_context_click = true;
-                                        self.ui_state.context_menu_pos = global_mouse_pos;
-                                        self.ui_state.context_menu_node = Some(node_layout.node_index);
+                                    if double_clicked {
+                                        node_action = NodeAction::BrowseNode(instance);
                                         was_action = true;
                                     }
-                                    if !was_action && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    if !was_action {
                                         node_to_hover = Some(node_layout.node_index);
                                         ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
                                     }
                                 }
                                 node_count += 1;
+                                continue;
                             }
-                            if let Some(new_node_shapes) = new_node_shapes {
-                                if let Ok(mut node_shapes) = self.meta_nodes.node_shapes.write() {
-                                    *node_shapes = new_node_shapes;
-                                    self.meta_nodes.update_node_shapes = false;
+                            let type_style = self.visualization_style.get_type_style_one(node_layout.node_index);
+                            node_style.color = type_style.color;
+                            if self.ui_state.meta_count_to_size
+                                && self.type_index.min_instance_type_count < self.type_index.max_instance_type_count
+                            {
+                                let type_data = self.type_index.types.get(&node_layout.node_index);
+                                if let Some(type_data) = type_data {
+                                    node_style.width = value_to_radius(
+                                        type_data.instances.len(),
+                                        self.type_index.min_instance_type_count,
+                                        self.type_index.max_instance_type_count,
+                                        NODE_RMIN,
+                                        NODE_RMAX,
+                                    );
                                 }
+                            } else {
+                                node_style.width = 15.0;
                             }
+                            let type_display = rdf_data.node_data.type_display(
+                                node_layout.node_index,
+                                &label_context,
+                                &rdf_data.node_data.indexers,
+                            );
+                            let (node_rect, node_shape) = draw_node_label(
+                                painter,
+                                type_display.as_str(),
+                                &node_style,
+                                pos,
+                                self.ui_state.selected_node == Some(node_layout.node_index),
+                                false,
+                                false,
+                                true,
+                                0,
+                                ui.visuals()
+                            );
+                            if let Some(new_node_shapes) = &mut new_node_shapes {
+                                new_node_shapes.push(NodeShapeData {
+                                    node_shape,
+                                    size: node_rect.size(),
+                                });
+                            }   
+                            if self.ui_state.context_menu_node.is_none() || was_action {
+                                if single_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    self.ui_state.selected_node = Some(node_layout.node_index);
+                                    if command_down {
+                                        node_action = NodeAction::ShowType(node_layout.node_index);
+                                    }
+                                    was_action = true;
+                                }
+                                if primary_down && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    self.ui_state.node_to_drag = Some(node_layout.node_index);
+                                    self.ui_state.drag_diff = (mouse_pos - node_rect.center()).to_pos2();
+                                    was_action = true;
+                                }
+                                if double_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    node_to_click = Some(node_layout.node_index);
+                                    was_action = true;
+                                }
+                                if secondary_clicked && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    was_context_click = true;
+                                    self.ui_state.context_menu_pos = global_mouse_pos;
+                                    self.ui_state.context_menu_node = Some(node_layout.node_index);
+                                    was_action = true;
+                                }
+                                if !was_action && is_overlapping(&node_rect, mouse_pos, node_shape) {
+                                    node_to_hover = Some(node_layout.node_index);
+                                    ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Grabbing);
+                                }
+                            }
+                            node_count += 1;
                         }
+                        computed_node_shapes = new_node_shapes;
+                    }
+                    if let Some(new_node_shapes) = computed_node_shapes
+                        && let Ok(mut layout) = self.meta_nodes.layout_data.write()
+                    {
+                        layout.node_shapes = new_node_shapes;
+                        self.meta_nodes.update_node_shapes = false;
                     }
     
                     let consume_events = was_action || self.ui_state.node_to_drag.is_some() || node_to_hover.is_some();
@@ -296,12 +470,14 @@ impl RdfGlanceApp {
                         if let Some(node_index) = &self.ui_state.context_menu_node {
                             let mut close_menu = false;
                             let current_index = *node_index;
-                            let node_action = TypeNodeContextAction::show_menu(ui);
-                            match node_action {
+                            let context_action =
+                                TypeNodeContextAction::show_menu(ui, self.meta_samples.is_expanded(current_index));
+                            match context_action {
                                 TypeNodeContextAction::Hide => {
                                     let hidden_predicates = SortedVec::new();
+                                    samples_to_hide = Some(current_index);
                                     self.meta_nodes.remove(current_index, &hidden_predicates);
-                                    self.meta_nodes.start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                                    self.meta_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
                                     close_menu = true;
                                 }
                                 TypeNodeContextAction::HideSameInstCount => {
@@ -323,10 +499,23 @@ impl RdfGlanceApp {
                                     }
                                     close_menu = true;
                                 },
+                                TypeNodeContextAction::ToggleSamples => {
+                                    if self.meta_samples.is_expanded(current_index) {
+                                        samples_to_hide = Some(current_index);
+                                    } else {
+                                        sample_type = Some(current_index);
+                                    }
+                                    close_menu = true;
+                                }
                                 TypeNodeContextAction::HideOthers => {
+                                    self.meta_samples.clear();
                                     self.meta_nodes.clear();
                                     self.meta_nodes.add_by_index(current_index);
-                                    self.meta_nodes.start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                                    self.meta_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+                                    close_menu = true;
+                                }
+                                TypeNodeContextAction::ShowInTable => {
+                                    node_action = NodeAction::ShowType(current_index);
                                     close_menu = true;
                                 }
                                 TypeNodeContextAction::None => {
@@ -366,11 +555,16 @@ impl RdfGlanceApp {
                                 }
                             }
                             if was_add {
-                                self.meta_nodes.edges = Arc::new(RwLock::new(create_types_layout_edges(
+                                let edges = create_types_layout_edges(
                                     &self.meta_nodes,
                                     &self.type_index,
-                                )));                           
-                                self.meta_nodes.start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+                                    &self.meta_samples,
+                                    &rdf_data.node_data,
+                                );
+                                if let Ok(mut layout) = self.meta_nodes.layout_data.write() {
+                                    layout.edges = edges;
+                                }
+                                self.meta_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
                             }
                         }
                     }
@@ -381,31 +575,167 @@ impl RdfGlanceApp {
                     }
                 }
             });
+            if let Some(sample_type) = sample_type {
+                self.show_samples(sample_type);
+            }
+            if let Some(samples_to_hide) = samples_to_hide {
+                self.hide_samples(samples_to_hide);
+            }
         });
         node_action
     }
 
     pub fn build_meta_graph(&mut self) {
         self.meta_nodes.clear();
+        self.meta_samples.clear();
         for (type_index, _type_node) in self.type_index.types.iter() {
             self.meta_nodes.add(NodeLayout::new(*type_index));
         }
-        self.meta_nodes.edges = Arc::new(RwLock::new(create_types_layout_edges(
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let edges = create_types_layout_edges(
             &self.meta_nodes,
             &self.type_index,
-        )));
-        self.meta_nodes.start_layout(&self.persistent_data.config_data, &self.ui_state.hidden_predicates);
+            &self.meta_samples,
+            &rdf_data.node_data,
+        );
+        if let Ok(mut layout) = self.meta_nodes.layout_data.write() {
+            layout.edges = edges;
+        }
+        self.meta_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+    }
+
+    /// Adds sample instances of the type to the meta graph, they are placed around the type node
+    fn show_samples(&mut self, type_index: IriIndex) {
+        let Some(type_data) = self.type_index.types.get(&type_index) else {
+            return;
+        };
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let node_data = &rdf_data.node_data;
+        let instances = self
+            .meta_samples
+            .expand(type_index, type_data, node_data, self.ui_state.meta_sample_count);
+        if instances.is_empty() {
+            return;
+        }
+        for instance in instances.iter() {
+            self.meta_nodes.add(NodeLayout::new(sample_node_index(*instance)));
+        }
+        if let Some(type_pos) = self.meta_nodes.get_pos(type_index) {
+            let sample_positions: Vec<Option<usize>> = instances
+                .iter()
+                .map(|instance| self.meta_nodes.get_pos(sample_node_index(*instance)))
+                .collect();
+            if let Ok(mut layout) = self.meta_nodes.layout_data.write() {
+                let positions = &mut layout.positions;
+                let type_pos = positions[type_pos].pos;
+                let count = sample_positions.len() as f32;
+                for (i, sample_pos) in sample_positions.into_iter().enumerate() {
+                    if let Some(sample_pos) = sample_pos {
+                        let angle = std::f32::consts::TAU * i as f32 / count;
+                        positions[sample_pos].pos = type_pos + Vec2::angled(angle) * 80.0;
+                    }
+                }
+            }
+        }
+        let edges = create_types_layout_edges(&self.meta_nodes, &self.type_index, &self.meta_samples, node_data);
+        if let Ok(mut layout) = self.meta_nodes.layout_data.write() {
+            layout.edges = edges;
+        }
+        self.meta_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+    }
+
+    fn hide_samples(&mut self, type_index: IriIndex) {
+        let removed: Vec<IriIndex> = self
+            .meta_samples
+            .collapse(type_index)
+            .into_iter()
+            .map(sample_node_index)
+            .collect();
+        if !removed.is_empty() {
+            let hidden_predicates = SortedVec::new();
+            self.meta_nodes
+                .retain(&hidden_predicates, false, |node| !removed.contains(&node.node_index));
+        }
+    }
+
+    fn hide_all_samples(&mut self) {
+        self.meta_samples.clear();
+        let hidden_predicates = SortedVec::new();
+        self.meta_nodes
+            .retain(&hidden_predicates, false, |node| sample_instance(node.node_index).is_none());
+    }
+
+    /// Adds the new types and recomputes the edges, the positions of the known types are kept
+    pub fn refresh_meta_graph(&mut self) {
+        if self.meta_nodes.layout_data.read().map_or(true, |layout| layout.nodes.is_empty()) {
+            return;
+        }
+        for type_index in self.type_index.types.keys() {
+            if !self.meta_nodes.contains(*type_index) {
+                self.meta_nodes.add(NodeLayout::new(*type_index));
+            }
+        }
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let edges = create_types_layout_edges(&self.meta_nodes, &self.type_index, &self.meta_samples, &rdf_data.node_data);
+        if let Ok(mut layout) = self.meta_nodes.layout_data.write() {
+            layout.edges = edges;
+        }
+        self.meta_nodes.update_node_shapes = true;
     }
 
     pub fn display_type_node_details(&mut self, ui: &mut egui::Ui) -> NodeAction {
         let mut node_to_click = NodeAction::None;
+        if let Some(iri_index) = &self.ui_state.selected_node
+            && let Some(instance) = sample_instance(*iri_index)
+        {
+            if self.meta_nodes.contains(*iri_index)
+                && let Ok(rdf_data) = self.rdf_data.read()
+                && let Some((iri, node)) = rdf_data.node_data.get_node_by_index(instance)
+            {
+                let label_context = LabelContext::new(
+                    self.ui_state.display_language,
+                    self.config.iri_display,
+                    &rdf_data.prefix_manager,
+                );
+                let instance_label = node.node_label(
+                    iri,
+                    &self.visualization_style,
+                    matches!(self.config.iri_display, IriDisplay::Shorten),
+                    self.ui_state.display_language,
+                    &rdf_data.node_data.indexers,
+                );
+                if ui.button(instance_label).on_hover_text("Browse the instance").clicked() {
+                    node_to_click = NodeAction::BrowseNode(instance);
+                }
+                ui.label(iri.as_ref());
+                ui.label("Sample of:");
+                for type_index in self.meta_samples.sample_types(instance) {
+                    let type_display = rdf_data
+                        .node_data
+                        .type_display(type_index, &label_context, &rdf_data.node_data.indexers);
+                    if ui.button(type_display.as_str()).clicked() {
+                        node_to_click = NodeAction::ShowType(type_index);
+                    }
+                }
+                if ui.button("Show in Visual Graph").clicked() {
+                    node_to_click = NodeAction::ShowVisual(instance);
+                }
+            }
+            return node_to_click;
+        }
         if let Some(iri_index) = &self.ui_state.selected_node {
             if self.meta_nodes.contains(*iri_index) {
                 if let Some(type_data) = self.type_index.types.get(iri_index) {
                     if let Ok(rdf_data) = self.rdf_data.read() {
                         let label_context = LabelContext::new(
                             self.ui_state.display_language,
-                            self.persistent_data.config_data.iri_display,
+                            self.config.iri_display,
                             &rdf_data.prefix_manager,
                         );
                         let type_display =
@@ -415,6 +745,11 @@ impl RdfGlanceApp {
                             node_to_click = NodeAction::ShowType(*iri_index);
                         }
                         ui.label(format!("Instance count: {}", type_data.instances.len()));
+                        if let Some(type_hierarchy) = &self.type_index.type_hierarchy
+                            && let Some(count) = type_hierarchy.transitive_instance_count(*iri_index)
+                        {
+                            ui.label(format!("Including subclasses: {}", count));
+                        }
                         ui.add_space(5.0);
                         type_data.display_data_props(ui, &label_context, &rdf_data.node_data);
                         ui.add_space(5.0);
@@ -432,13 +767,15 @@ impl RdfGlanceApp {
 enum TypeNodeContextAction {
     None,
     Hide,
+    ToggleSamples,
     HideSameInstCount,
     HideOthers,
     Expand,
+    ShowInTable,
 }
 
 impl TypeNodeContextAction {
-    fn show_menu(ui: &mut egui::Ui) -> TypeNodeContextAction {
+    fn show_menu(ui: &mut egui::Ui, samples_shown: bool) -> TypeNodeContextAction {
         if ui.button("Hide").clicked() {
             return TypeNodeContextAction::Hide;
         }
@@ -451,6 +788,13 @@ impl TypeNodeContextAction {
         if ui.button("Expand").clicked() {
             return TypeNodeContextAction::Expand;
         }
+        if ui.button("Show Instances in Table").clicked() {
+            return TypeNodeContextAction::ShowInTable;
+        }
+        let samples_label = if samples_shown { "Hide Samples" } else { "Show Samples" };
+        if ui.button(samples_label).clicked() {
+            return TypeNodeContextAction::ToggleSamples;
+        }
         TypeNodeContextAction::None
     }
 }
@@ -476,9 +820,65 @@ fn value_to_radius(value: usize, min: usize, max: usize, rmin: f32, rmax: f32) -
     (area / std::f32::consts::PI).sqrt()
 }
 
-fn create_types_layout_edges(layout_nodes: &SortedNodeLayout, type_index: &TypeInstanceIndex) -> Vec<Edge> {
+struct MetaEdgeReferences {
+    count: u32,
+    // number of references of one source instance
+    out_cardinality: String,
+    // number of references to one target instance, the target type could miss the reverse references
+    in_cardinality: Option<String>,
+}
+
+fn meta_edge_references(
+    type_index: &TypeInstanceIndex,
+    from_type: IriIndex,
+    to_type: IriIndex,
+    predicate: IriIndex,
+) -> MetaEdgeReferences {
+    let out_references = type_index
+        .types
+        .get(&from_type)
+        .and_then(|type_data| type_data.references.get(&predicate));
+    let in_references = type_index
+        .types
+        .get(&to_type)
+        .and_then(|type_data| type_data.rev_references.get(&predicate));
+    MetaEdgeReferences {
+        count: out_references.map(|references| references.count).unwrap_or(0),
+        out_cardinality: out_references
+            .map(|references| cardinality_label(references.min_cardinality, references.max_cardinality))
+            .unwrap_or_default(),
+        in_cardinality: in_references
+            .map(|references| cardinality_label(references.min_cardinality, references.max_cardinality)),
+    }
+}
+
+fn cardinality_label(min: u32, max: u32) -> String {
+    if min == max {
+        min.to_string()
+    } else {
+        format!("{}..{}", min, max)
+    }
+}
+
+/// Edge width 1..8 proportional to the square root of the reference count, so small counts stay visible
+fn reference_width(count: u32, max_count: u32) -> f32 {
+    if max_count == 0 {
+        return 1.0;
+    }
+    1.0 + 7.0 * (count as f32 / max_count as f32).sqrt()
+}
+
+fn create_types_layout_edges(
+    layout_nodes: &SortedNodeLayout,
+    type_index: &TypeInstanceIndex,
+    samples: &TypeSamples,
+    node_data: &NodeData,
+) -> Vec<Edge> {
     let mut edges = Vec::new();
-    for (node_pos, node_layout) in layout_nodes.nodes.read().unwrap().iter().enumerate() {
+    let Ok(layout) = layout_nodes.layout_data.read() else {
+        return edges;
+    };
+    for (node_pos, node_layout) in layout.nodes.iter().enumerate() {
         if let Some(type_data) = type_index.types.get(&node_layout.node_index) {
             for (pred_index, ref_characteristics) in &type_data.references {
                 for ref_type_iri in &ref_characteristics.types {
@@ -490,7 +890,7 @@ fn create_types_layout_edges(layout_nodes: &SortedNodeLayout, type_index: &TypeI
                             bezier_distance: 0.0,
                         };
                         edges.push(edge);
-                    } else if let Some(ref_pos) = layout_nodes.get_pos(*ref_type_iri) {
+                    } else if let Some(ref_pos) = crate::uistate::layout::node_pos(&layout.nodes, *ref_type_iri) {
                         let edge = Edge {
                             from: node_pos,
                             to: ref_pos,
@@ -502,6 +902,49 @@ fn create_types_layout_edges(layout_nodes: &SortedNodeLayout, type_index: &TypeI
                 }
             }
         }
+        // inheritance edges go from the sub type to the super type
+        if let Some(type_hierarchy) = &type_index.type_hierarchy
+            && let Some(super_types) = type_hierarchy.super_types.get(&node_layout.node_index)
+        {
+            for super_type in super_types {
+                if let Some(super_pos) = layout_nodes.get_pos(*super_type) {
+                    edges.push(Edge {
+                        from: node_pos,
+                        to: super_pos,
+                        predicate: type_hierarchy.sub_class_of,
+                        bezier_distance: 0.0,
+                    });
+                }
+            }
+        }
+    }
+    // samples are linked to their types and by the references between them
+    for (sample_type, instances) in samples.samples.iter() {
+        let Some(type_pos) = layout_nodes.get_pos(*sample_type) else {
+            continue;
+        };
+        for instance in instances {
+            if let Some(sample_pos) = layout_nodes.get_pos(sample_node_index(*instance)) {
+                edges.push(Edge {
+                    from: sample_pos,
+                    to: type_pos,
+                    predicate: INSTANCE_OF_EDGE,
+                    bezier_distance: 0.0,
+                });
+            }
+        }
+    }
+    for (from, predicate, to) in samples.sample_references(node_data) {
+        if let Some(from_pos) = layout_nodes.get_pos(sample_node_index(from))
+            && let Some(to_pos) = layout_nodes.get_pos(sample_node_index(to))
+        {
+            edges.push(Edge {
+                from: from_pos,
+                to: to_pos,
+                predicate,
+                bezier_distance: 0.0,
+            });
+        }
     }
     let hidden_predicates = SortedVec::new();
     update_edges_groups(&mut edges, &hidden_predicates);
@@ -510,7 +953,7 @@ fn create_types_layout_edges(layout_nodes: &SortedNodeLayout, type_index: &TypeI
 
 #[cfg(test)]
 mod tests {
-    use std::sync::RwLock;
+    use std::collections::HashMap;
 
     use crate::{layoutalg::force::layout_graph_nodes, uistate::layout::{LayoutConfig, NodePosition}};
     use super::*;
@@ -523,38 +966,107 @@ mod tests {
         for (type_index, _type_node) in vs.type_index.types.iter() {
             vs.meta_nodes.add_by_index(*type_index);
         }
-        assert!(vs.meta_nodes.nodes.read().unwrap().len() > 0);
+        assert!(!vs.meta_nodes.layout_data.read().unwrap().nodes.is_empty());
         for (type_index, _type_node) in vs.type_index.types.iter() {
             vs.meta_nodes.add_by_index(*type_index);
         }
 
-        let edges = create_types_layout_edges(&vs.meta_nodes, &vs.type_index);
+        let edges = {
+            let rdf_data = vs.rdf_data.read().unwrap();
+            create_types_layout_edges(&vs.meta_nodes, &vs.type_index, &vs.meta_samples, &rdf_data.node_data)
+        };
         assert!(edges.len() > 0);
-        vs.meta_nodes.edges = Arc::new(RwLock::new(edges));
-
-        let mut positions: Vec<NodePosition> = Vec::with_capacity(vs.meta_nodes.nodes.read().unwrap().len());
-        for _ in 0..vs.meta_nodes.nodes.read().unwrap().len() {
-            positions.push(NodePosition::default());
-        }
-        vs.meta_nodes.positions = Arc::new(RwLock::new(positions));
+        let mut layout = vs.meta_nodes.layout_data.write().unwrap();
+        layout.edges = edges;
+        layout.positions = (0..layout.nodes.len()).map(|_| NodePosition::default()).collect();
         let layout_config = LayoutConfig {
-            repulsion_constant: vs.persistent_data.config_data.m_repulsion_constant,
-            attraction_factor: vs.persistent_data.config_data.m_attraction_factor,
-            gravity_effect_radius: vs.persistent_data.config_data.gravity_effect_radius,
+            repulsion_constant: vs.config.m_repulsion_constant,
+            attraction_factor: vs.config.m_attraction_factor,
+            gravity_effect_radius: vs.config.gravity_effect_radius,
+            cluster_attraction: 0.0,
+            edge_forces: HashMap::new(),
         };
         let hidden_predicates = SortedVec::new();
         let (max_move, positions) = layout_graph_nodes(
-            &vs.meta_nodes.nodes.read().unwrap(),
-            &vs.meta_nodes.node_shapes.read().unwrap(),
-            &vs.meta_nodes.positions.read().unwrap(),
-            &vs.meta_nodes.edges.read().unwrap(),
+            &layout.nodes,
+            &layout.node_shapes,
+            &layout.positions,
+            &layout.edges,
+            &[],
             &layout_config,
             &hidden_predicates,
             100.0,
         );
         assert!(max_move > 0.0);
-        assert!(positions.len() == vs.meta_nodes.nodes.read().unwrap().len());
+        assert!(positions.len() == layout.nodes.len());
 
         Ok(())
     }
+
+    #[test]
+    fn test_meta_samples() {
+        let mut vs = RdfGlanceApp::new(None, vec![]);
+        vs.load_ttl("sample-rdf-data/programming_languages.ttl", true);
+        vs.join_load(true);
+        vs.build_meta_graph();
+        let type_count = vs.meta_nodes.layout_data.read().unwrap().nodes.len();
+        let (type_index, _) = vs
+            .type_index
+            .types
+            .iter()
+            .find(|(_, type_data)| type_data.instances.len() > 3)
+            .unwrap();
+        let type_index = *type_index;
+        vs.show_samples(type_index);
+        assert_eq!(type_count + 3, vs.meta_nodes.layout_data.read().unwrap().nodes.len());
+        let type_pos = vs.meta_nodes.get_pos(type_index).unwrap();
+        let instance_of_edges = vs
+            .meta_nodes
+            .layout_data
+            .read()
+            .unwrap()
+            .edges
+            .iter()
+            .filter(|edge| edge.predicate == INSTANCE_OF_EDGE && edge.to == type_pos)
+            .count();
+        assert_eq!(3, instance_of_edges);
+        // the sample nodes are not mixed with the types
+        assert!(
+            vs.meta_nodes.layout_data.read().unwrap().nodes[type_count..]
+                .iter()
+                .all(|node| sample_instance(node.node_index).is_some())
+        );
+
+        vs.hide_samples(type_index);
+        assert_eq!(type_count, vs.meta_nodes.layout_data.read().unwrap().nodes.len());
+        assert!(vs.meta_samples.is_empty());
+    }
+
+    #[test]
+    fn test_meta_edge_references() {
+        assert_eq!("1", cardinality_label(1, 1));
+        assert_eq!("0..5", cardinality_label(0, 5));
+        assert_eq!(1.0, reference_width(0, 0));
+        assert_eq!(8.0, reference_width(100, 100));
+        assert!(reference_width(10, 100) < reference_width(50, 100));
+
+        let mut vs = RdfGlanceApp::new(None, vec![]);
+        vs.load_ttl("sample-rdf-data/programming_languages.ttl", true);
+        vs.join_load(true);
+        let (type_index, predicate, references) = vs
+            .type_index
+            .types
+            .iter()
+            .find_map(|(type_index, type_data)| {
+                type_data
+                    .references
+                    .iter()
+                    .find(|(_, references)| !references.types.is_empty())
+                    .map(|(predicate, references)| (*type_index, *predicate, references))
+            })
+            .unwrap();
+        let edge_references = meta_edge_references(&vs.type_index, type_index, references.types[0], predicate);
+        assert_eq!(references.count, edge_references.count);
+        assert!(!edge_references.out_cardinality.is_empty());
+    }
 }
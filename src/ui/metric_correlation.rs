@@ -0,0 +1,204 @@
+use egui::{Color32, PointerButton, Stroke};
+use egui_plot::{Plot, Points, Polygon};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext, NodeData,
+        metric_correlation::{CorrelationPoints, MetricSource, numeric_properties},
+        statistics::StatisticsData,
+    },
+    uistate::SystemMessage,
+};
+
+pub struct MetricCorrelation {
+    pub x: MetricSource,
+    pub y: MetricSource,
+    pub numeric_properties: Vec<IriIndex>,
+    // corners of the brushing rectangle in plot coordinates
+    pub brush_start: Option<[f64; 2]>,
+    pub brush_end: Option<[f64; 2]>,
+}
+
+impl RdfGlanceApp {
+    pub fn open_metric_correlation(&mut self) {
+        let Some(statistics_data) = &self.statistics_data else {
+            return;
+        };
+        if statistics_data.results.is_empty() {
+            self.system_message = SystemMessage::Info("Run some statistics algorithms first".to_string());
+            return;
+        }
+        let numeric_properties = if let Ok(rdf_data) = self.rdf_data.read() {
+            numeric_properties(statistics_data, &rdf_data.node_data)
+        } else {
+            Vec::new()
+        };
+        let y = if statistics_data.results.len() > 1 {
+            MetricSource::Statistic(1)
+        } else if let Some(predicate_index) = numeric_properties.first() {
+            MetricSource::Property(*predicate_index)
+        } else {
+            MetricSource::Statistic(0)
+        };
+        self.metric_correlation = Some(MetricCorrelation {
+            x: MetricSource::Statistic(0),
+            y,
+            numeric_properties,
+            brush_start: None,
+            brush_end: None,
+        });
+    }
+
+    pub fn show_metric_correlation(&mut self, ui: &mut egui::Ui) {
+        let (Some(metric_correlation), Some(statistics_data)) = (&mut self.metric_correlation, &self.statistics_data)
+        else {
+            return;
+        };
+        let mut close = false;
+        let mut brushed: Option<Vec<IriIndex>> = None;
+        let extend_selection = ui.input(|i| i.modifiers.shift);
+        egui::Window::new("Metric Correlation")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let node_data = &rdf_data.node_data;
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    let sources: Vec<(MetricSource, String)> = (0..statistics_data.results.len())
+                        .map(MetricSource::Statistic)
+                        .chain(
+                            metric_correlation
+                                .numeric_properties
+                                .iter()
+                                .map(|predicate_index| MetricSource::Property(*predicate_index)),
+                        )
+                        .map(|source| {
+                            let label = source_label(source, statistics_data, node_data, &label_context);
+                            (source, label)
+                        })
+                        .collect();
+                    ui.horizontal(|ui| {
+                        source_combo(ui, "X", &mut metric_correlation.x, &sources);
+                        source_combo(ui, "Y", &mut metric_correlation.y, &sources);
+                    });
+                    let points =
+                        CorrelationPoints::new(statistics_data, node_data, metric_correlation.x, metric_correlation.y);
+                    match points.pearson() {
+                        Some(r) => ui.label(format!("Points: {}, Pearson r = {:.3}", points.points.len(), r)),
+                        None => ui.label(format!("Points: {}", points.points.len())),
+                    };
+                    ui.label("Drag a rectangle to select the nodes in the graph (Shift to extend the selection)");
+                    let (selected, unselected): (Vec<[f64; 2]>, Vec<[f64; 2]>) = {
+                        let mut selected = Vec::new();
+                        let mut unselected = Vec::new();
+                        for (point, node_index) in points.points.iter().zip(points.nodes.iter()) {
+                            if self.ui_state.selected_nodes.contains(node_index) {
+                                selected.push(*point);
+                            } else {
+                                unselected.push(*point);
+                            }
+                        }
+                        (selected, unselected)
+                    };
+                    let selection_color = ui.visuals().selection.bg_fill;
+                    let brush = metric_correlation.brush_start.zip(metric_correlation.brush_end);
+                    let plot_response = Plot::new("metric_correlation")
+                        .height(400.0)
+                        .allow_drag(false)
+                        .x_axis_label(source_label(metric_correlation.x, statistics_data, node_data, &label_context))
+                        .y_axis_label(source_label(metric_correlation.y, statistics_data, node_data, &label_context))
+                        .show(ui, |plot_ui| {
+                            plot_ui.points(Points::new("nodes", unselected).radius(3.0).color(Color32::GRAY));
+                            plot_ui.points(Points::new("selected", selected).radius(4.0).color(selection_color));
+                            if let Some((start, end)) = brush {
+                                plot_ui.polygon(
+                                    Polygon::new(
+                                        "brush",
+                                        vec![start, [end[0], start[1]], end, [start[0], end[1]]],
+                                    )
+                                    .stroke(Stroke::new(1.0, selection_color))
+                                    .fill_color(selection_color.gamma_multiply(0.2)),
+                                );
+                            }
+                        });
+                    let response = &plot_response.response;
+                    let pointer_coordinate = response.interact_pointer_pos().map(|pos| {
+                        let point = plot_response.transform.value_from_position(pos);
+                        [point.x, point.y]
+                    });
+                    if response.drag_started_by(PointerButton::Primary) {
+                        metric_correlation.brush_start = pointer_coordinate;
+                        metric_correlation.brush_end = pointer_coordinate;
+                    } else if response.dragged_by(PointerButton::Primary) {
+                        if pointer_coordinate.is_some() {
+                            metric_correlation.brush_end = pointer_coordinate;
+                        }
+                    } else if response.drag_stopped_by(PointerButton::Primary) {
+                        if let Some((start, end)) =
+                            metric_correlation.brush_start.zip(metric_correlation.brush_end)
+                        {
+                            brushed = Some(points.nodes_in_rect(start, end));
+                        }
+                        metric_correlation.brush_start = None;
+                        metric_correlation.brush_end = None;
+                    }
+                }
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        if let Some(brushed) = brushed {
+            if !extend_selection {
+                self.ui_state.selected_nodes.clear();
+            }
+            self.ui_state.selected_node = brushed.first().cloned();
+            self.ui_state.selected_nodes.extend(brushed);
+        }
+        if close {
+            self.metric_correlation = None;
+        }
+    }
+}
+
+fn source_label(
+    source: MetricSource,
+    statistics_data: &StatisticsData,
+    node_data: &NodeData,
+    label_context: &LabelContext,
+) -> String {
+    match source {
+        MetricSource::Statistic(result_index) => statistics_data
+            .results
+            .get(result_index)
+            .map(|result| result.statistics_value().to_string())
+            .unwrap_or_default(),
+        MetricSource::Property(predicate_index) => node_data
+            .predicate_display(predicate_index, label_context, &node_data.indexers)
+            .as_str()
+            .to_string(),
+    }
+}
+
+fn source_combo(ui: &mut egui::Ui, label: &str, source: &mut MetricSource, sources: &[(MetricSource, String)]) {
+    let selected_text = sources
+        .iter()
+        .find(|(candidate, _)| candidate == source)
+        .map(|(_, label)| label.as_str())
+        .unwrap_or_default();
+    egui::ComboBox::from_label(label)
+        .width(200.0)
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            for (candidate, candidate_label) in sources {
+                ui.selectable_value(source, *candidate, candidate_label);
+            }
+        });
+}
@@ -0,0 +1,96 @@
+use egui::{Id, Order, Pos2, Rect, Sense, Stroke, StrokeKind, Vec2};
+
+const MINIMAP_MAX_SIZE: Vec2 = Vec2::new(200.0, 140.0);
+const MINIMAP_MARGIN: f32 = 8.0;
+// bigger graphs are drawn with every n-th node only
+const MAX_MINIMAP_NODES: usize = 20_000;
+
+/**
+ * Overview of the whole graph in the corner of the graph view.
+ * Maps the layout positions of the nodes (scene coordinates relative to the scene center) to the small rectangle.
+ */
+pub struct Minimap {
+    pub rect: Rect,
+    bounds: Rect,
+    scale: f32,
+}
+
+impl Minimap {
+    /// The minimap keeps the aspect ratio of the bounds and is placed in the bottom right corner of the graph
+    pub fn new(graph_rect: Rect, bounds: Rect) -> Self {
+        let bounds = bounds.expand(bounds.size().max_elem().max(1.0) * 0.05);
+        let scale = (MINIMAP_MAX_SIZE.x / bounds.width()).min(MINIMAP_MAX_SIZE.y / bounds.height());
+        let size = bounds.size() * scale;
+        let rect = Rect::from_min_size(graph_rect.right_bottom() - size - Vec2::splat(MINIMAP_MARGIN), size);
+        Self { rect, bounds, scale }
+    }
+
+    pub fn to_minimap(&self, layout_pos: Pos2) -> Pos2 {
+        self.rect.min + (layout_pos - self.bounds.min) * self.scale
+    }
+
+    pub fn to_scene(&self, minimap_pos: Pos2) -> Pos2 {
+        self.bounds.min + (minimap_pos - self.rect.min) / self.scale
+    }
+
+    /**
+     * Draws the nodes and the visible part of the scene (view_rect in layout positions).
+     * Returns the new center of the visible part if the minimap was clicked or dragged.
+     */
+    pub fn show(&self, ctx: &egui::Context, nodes: &[Pos2], selected: &[Pos2], view_rect: Rect) -> Option<Pos2> {
+        let mut new_center = None;
+        egui::Area::new(Id::new("graph_minimap"))
+            .order(Order::Foreground)
+            .fixed_pos(self.rect.min)
+            .show(ctx, |ui| {
+                let (rect, response) = ui.allocate_exact_size(self.rect.size(), Sense::click_and_drag());
+                let painter = ui.painter_at(rect);
+                let visuals = ui.visuals();
+                painter.rect(
+                    rect,
+                    2.0,
+                    visuals.extreme_bg_color.gamma_multiply(0.85),
+                    visuals.window_stroke,
+                    StrokeKind::Inside,
+                );
+                let node_color = visuals.text_color().gamma_multiply(0.6);
+                let step = nodes.len().div_ceil(MAX_MINIMAP_NODES).max(1);
+                for pos in nodes.iter().step_by(step) {
+                    painter.rect_filled(
+                        Rect::from_center_size(self.to_minimap(*pos), Vec2::splat(2.0)),
+                        0.0,
+                        node_color,
+                    );
+                }
+                for pos in selected {
+                    painter.circle_filled(self.to_minimap(*pos), 2.5, visuals.selection.stroke.color);
+                }
+                let view_rect = Rect::from_min_max(self.to_minimap(view_rect.min), self.to_minimap(view_rect.max));
+                painter.rect(
+                    view_rect,
+                    0.0,
+                    visuals.selection.bg_fill.gamma_multiply(0.2),
+                    Stroke::new(1.5, visuals.selection.stroke.color),
+                    StrokeKind::Middle,
+                );
+                if (response.dragged() || response.clicked())
+                    && let Some(pointer_pos) = response.interact_pointer_pos()
+                {
+                    new_center = Some(self.to_scene(pointer_pos.clamp(rect.min, rect.max)));
+                }
+                if response.hovered() {
+                    ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::Crosshair);
+                }
+                response.on_hover_text("Click or drag to move the view");
+            });
+        new_center
+    }
+}
+
+/// Rectangle containing all points, None for no points
+pub fn points_bounds(points: &[Pos2]) -> Option<Rect> {
+    let first = *points.first()?;
+    Some(points.iter().fold(Rect::from_min_max(first, first), |bounds, pos| {
+        bounds.union(Rect::from_min_max(*pos, *pos))
+    }))
+}
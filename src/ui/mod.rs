@@ -2,16 +2,60 @@ pub mod drawing;
 pub mod style;
 pub mod browse_view;
 pub mod config;
+pub mod cycles;
+pub mod data_quality;
 pub mod graph_styles;
 pub mod graph_view;
+pub mod png_export;
+pub mod import_log;
 pub mod menu_bar;
 pub mod meta_graph;
+pub mod metric_correlation;
+pub mod node_comparison;
+pub mod patch_dialog;
 pub mod prefix_manager;
+pub mod recent_items;
+pub mod session_statistics;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod sparql_dialog;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bundle_dialog;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod drop_dialog;
 pub mod statistics;
+pub mod stress_data;
 pub mod table_view;
+pub mod topological_sort;
+pub mod transitive_closure;
+pub mod type_metric_summary;
+pub mod edge_metadata;
+pub mod wikidata;
+pub mod dcat;
+pub mod social_chart;
+pub mod semantic_zoom;
+pub mod render_mode;
+pub mod node_list;
+pub mod reset_settings;
+pub mod log_console;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod layout_journal;
+pub mod incoming_references;
 pub mod visual_query;
 pub mod reference_resolver;
+pub mod named_graphs;
+pub mod column_chart;
+pub mod dataset_diff;
+pub mod map_view;
+pub mod timeline_view;
+pub mod property_distribution;
+pub mod csv_import;
+pub mod minimap;
+pub mod graph_snapshots;
+pub mod language_panel;
+pub mod keymap;
+pub mod size_mapping;
+pub mod authoring;
+pub mod graph_legend;
+pub mod node_edit;
 
 pub use self::drawing::*;
\ No newline at end of file
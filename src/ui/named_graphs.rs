@@ -0,0 +1,33 @@
+use crate::domain::named_graphs::{GraphFilter, NamedGraphs};
+
+/**
+ * Combo box to select the named graph shown in table and graph views.
+ * Nothing is shown if the data have no named graphs.
+ * Returns true if the selection was changed.
+ */
+pub fn show_graph_filter(ui: &mut egui::Ui, named_graphs: &NamedGraphs, graph_filter: &mut Option<GraphFilter>) -> bool {
+    if named_graphs.is_empty() {
+        return false;
+    }
+    let selected_graph = graph_filter.as_ref().map(|graph_filter| graph_filter.graph_index);
+    let mut new_graph = selected_graph;
+    egui::ComboBox::from_id_salt("named_graph")
+        .selected_text(
+            selected_graph
+                .and_then(|graph_index| named_graphs.graph_name(graph_index))
+                .unwrap_or("All graphs"),
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut new_graph, None, "All graphs");
+            for (graph_index, graph_name) in named_graphs.graphs() {
+                ui.selectable_value(&mut new_graph, Some(graph_index), graph_name);
+            }
+        })
+        .response
+        .on_hover_text("Show only nodes and edges of the named graph");
+    if new_graph != selected_graph {
+        *graph_filter = new_graph.map(|graph_index| GraphFilter::new(named_graphs, graph_index));
+        return true;
+    }
+    false
+}
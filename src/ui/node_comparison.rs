@@ -0,0 +1,113 @@
+use egui::{Grid, RichText, ScrollArea};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext,
+        node_comparison::{MAX_COMPARED_NODES, MIN_COMPARED_NODES, NodeComparison},
+    },
+    uistate::DisplayType,
+};
+
+impl RdfGlanceApp {
+    pub fn can_compare_selected_nodes(&self) -> bool {
+        (MIN_COMPARED_NODES..=MAX_COMPARED_NODES).contains(&self.ui_state.selected_nodes.len())
+    }
+
+    pub fn compare_selected_nodes(&mut self) {
+        let nodes: Vec<IriIndex> = self.ui_state.selected_nodes.iter().cloned().collect();
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            self.node_comparison = Some(NodeComparison::new(&rdf_data.node_data, &nodes));
+        }
+    }
+
+    pub fn show_node_comparison(&mut self, ui: &mut egui::Ui) {
+        let Some(node_comparison) = &mut self.node_comparison else {
+            return;
+        };
+        let mut close = false;
+        let mut node_to_browse: Option<IriIndex> = None;
+        egui::Window::new("Compare Nodes")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(800.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "Predicates: {}, differing: {}",
+                        node_comparison.rows.len() - 1,
+                        node_comparison.differing_rows()
+                    ));
+                    ui.checkbox(&mut node_comparison.only_differences, "Only differences");
+                });
+                ui.separator();
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    let diff_color = ui.visuals().warn_fg_color;
+                    ScrollArea::both().max_height(500.0).show(ui, |ui| {
+                        Grid::new("node_comparison_grid").striped(true).show(ui, |ui| {
+                            ui.label("");
+                            for node_index in node_comparison.nodes.iter() {
+                                if let Some((node_iri, node)) = rdf_data.node_data.get_node_by_index(*node_index) {
+                                    let label = node.node_label(
+                                        node_iri,
+                                        &self.visualization_style,
+                                        self.config.short_iri,
+                                        self.ui_state.display_language,
+                                        &rdf_data.node_data.indexers,
+                                    );
+                                    if ui.button(RichText::new(label).strong()).on_hover_text(&**node_iri).clicked() {
+                                        node_to_browse = Some(*node_index);
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                            for row in node_comparison.rows.iter() {
+                                if node_comparison.only_differences && !row.differs {
+                                    continue;
+                                }
+                                let predicate_label = match row.predicate {
+                                    Some(predicate) => rdf_data
+                                        .node_data
+                                        .predicate_display(predicate, &label_context, &rdf_data.node_data.indexers)
+                                        .as_str()
+                                        .to_string(),
+                                    None => "Types".to_string(),
+                                };
+                                if row.differs {
+                                    ui.label(RichText::new(predicate_label).color(diff_color));
+                                } else {
+                                    ui.label(predicate_label);
+                                }
+                                for values in row.values.iter() {
+                                    let text = values.join("\n");
+                                    if row.differs {
+                                        ui.label(RichText::new(text).color(diff_color));
+                                    } else {
+                                        ui.label(text);
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                }
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        if let Some(node_to_browse) = node_to_browse {
+            self.show_object_by_index(node_to_browse, true);
+            self.display_type = DisplayType::Browse;
+        }
+        if close {
+            self.node_comparison = None;
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    IriIndex, RdfGlanceApp, SystemMessage,
+    domain::{
+        change_set::{ChangeSet, iri_node, literal_triple, property_triple},
+        data_change::DataChange,
+    },
+    integration::rdf_patch::PatchOperation,
+    uistate::NodeEdit,
+};
+
+pub enum NodeEditAction {
+    None,
+    // position of the data property and the new value
+    Change(usize, String),
+    Delete(usize),
+    // predicate and value of the new data property
+    Add(String, String),
+    ExportSparqlUpdate,
+    ExportRdfPatch,
+    CopySparqlUpdate,
+    PushSparqlUpdate,
+    ClearChanges,
+}
+
+/// Value and edit buttons of a data property in the node details, two grid cells
+pub fn edit_property_value(
+    ui: &mut egui::Ui,
+    node_edit: &mut NodeEdit,
+    node_index: IriIndex,
+    position: usize,
+    value: &str,
+    action: &mut NodeEditAction,
+) {
+    let mut close = false;
+    match &mut node_edit.edited_property {
+        Some((edited_node, edited_position, new_value)) if *edited_node == node_index && *edited_position == position => {
+            let text_edit = if value.contains('\n') {
+                egui::TextEdit::multiline(new_value)
+            } else {
+                egui::TextEdit::singleline(new_value)
+            };
+            let response = ui.add(text_edit.desired_width(ui.available_width()));
+            let enter_pressed = response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter));
+            ui.horizontal(|ui| {
+                if ui.small_button("✔").on_hover_text("Save the value").clicked() || enter_pressed {
+                    if new_value != value {
+                        *action = NodeEditAction::Change(position, new_value.clone());
+                    }
+                    close = true;
+                }
+                if ui.small_button("✖").on_hover_text("Cancel").clicked() {
+                    close = true;
+                }
+            });
+        }
+        _ => {
+            ui.label(value);
+            ui.horizontal(|ui| {
+                if ui.small_button("✏").on_hover_text("Edit the value").clicked() {
+                    node_edit.edited_property = Some((node_index, position, value.to_string()));
+                }
+                if ui.small_button("🗑").on_hover_text("Delete the property").clicked() {
+                    *action = NodeEditAction::Delete(position);
+                }
+            });
+        }
+    }
+    if close {
+        node_edit.edited_property = None;
+    }
+}
+
+pub fn add_property_ui(ui: &mut egui::Ui, node_edit: &mut NodeEdit, action: &mut NodeEditAction) {
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut node_edit.new_predicate)
+                .hint_text("predicate (prefix:name or iri)")
+                .desired_width(200.0),
+        );
+        ui.add(
+            egui::TextEdit::singleline(&mut node_edit.new_value)
+                .hint_text("value")
+                .desired_width(200.0),
+        );
+        if ui
+            .add_enabled(!node_edit.new_predicate.trim().is_empty(), egui::Button::new("Add Property"))
+            .clicked()
+        {
+            *action = NodeEditAction::Add(node_edit.new_predicate.clone(), node_edit.new_value.clone());
+            node_edit.new_value.clear();
+        }
+    });
+}
+
+pub fn change_set_ui(ui: &mut egui::Ui, change_set: &ChangeSet, action: &mut NodeEditAction) {
+    ui.horizontal(|ui| {
+        ui.label(format!("Changed triples: {}", change_set.len()));
+        ui.add_enabled_ui(!change_set.is_empty(), |ui| {
+            if ui.button("Export SPARQL Update").clicked() {
+                *action = NodeEditAction::ExportSparqlUpdate;
+            }
+            if ui.button("Export RDF Patch").clicked() {
+                *action = NodeEditAction::ExportRdfPatch;
+            }
+            if ui.button("Copy SPARQL Update").clicked() {
+                *action = NodeEditAction::CopySparqlUpdate;
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            if ui
+                .button("Push to Endpoint")
+                .on_hover_text("Review and send the changes as SPARQL Update to an endpoint")
+                .clicked()
+            {
+                *action = NodeEditAction::PushSparqlUpdate;
+            }
+            if ui
+                .button("Clear")
+                .on_hover_text("Forget the recorded changes, the data stay changed")
+                .clicked()
+            {
+                *action = NodeEditAction::ClearChanges;
+            }
+        });
+    });
+}
+
+impl RdfGlanceApp {
+    pub fn apply_node_edit_action(&mut self, ctx: &egui::Context, node_index: IriIndex, action: NodeEditAction) {
+        match action {
+            NodeEditAction::None => {}
+            NodeEditAction::Change(position, value) => {
+                let operations = self
+                    .read_rdf_data(|rdf_data| {
+                        let (_, node) = rdf_data.node_data.get_node_by_index(node_index)?;
+                        let (predicate_index, literal) = node.properties.get(position)?;
+                        let old_triple = literal_triple(
+                            &rdf_data.node_data,
+                            &rdf_data.prefix_manager,
+                            node_index,
+                            *predicate_index,
+                            literal,
+                        )?;
+                        let new_triple = property_triple(
+                            &rdf_data.node_data,
+                            &rdf_data.prefix_manager,
+                            node_index,
+                            old_triple.predicate.clone(),
+                            &value,
+                            Some(literal),
+                        )?;
+                        Some(vec![PatchOperation::Delete(old_triple), PatchOperation::Add(new_triple)])
+                    })
+                    .flatten();
+                self.edit_node_triples("Edit Property", operations);
+            }
+            NodeEditAction::Delete(position) => {
+                let operations = self
+                    .read_rdf_data(|rdf_data| {
+                        let (_, node) = rdf_data.node_data.get_node_by_index(node_index)?;
+                        let (predicate_index, literal) = node.properties.get(position)?;
+                        let triple = literal_triple(
+                            &rdf_data.node_data,
+                            &rdf_data.prefix_manager,
+                            node_index,
+                            *predicate_index,
+                            literal,
+                        )?;
+                        Some(vec![PatchOperation::Delete(triple)])
+                    })
+                    .flatten();
+                self.edit_node_triples("Delete Property", operations);
+            }
+            NodeEditAction::Add(predicate, value) => {
+                let operations = self
+                    .read_rdf_data(|rdf_data| {
+                        let predicate = iri_node(&predicate, &rdf_data.prefix_manager)?;
+                        let triple = property_triple(
+                            &rdf_data.node_data,
+                            &rdf_data.prefix_manager,
+                            node_index,
+                            predicate,
+                            &value,
+                            None,
+                        )?;
+                        Some(vec![PatchOperation::Add(triple)])
+                    })
+                    .flatten();
+                if operations.is_none() {
+                    self.system_message = SystemMessage::Error(format!(
+                        "Predicate {} is not a valid iri or uses an unknown prefix",
+                        predicate
+                    ));
+                }
+                self.edit_node_triples("Add Property", operations);
+            }
+            NodeEditAction::ExportSparqlUpdate => {
+                let style = self.config.turtle_style.clone();
+                if let Some(text) = self.read_rdf_data(|rdf_data| {
+                    rdf_data
+                        .node_data
+                        .change_set
+                        .to_sparql_update_pretty(&rdf_data.node_data, &rdf_data.prefix_manager, &style)
+                }) {
+                    self.save_change_set(text, "SPARQL Update", "ru");
+                }
+            }
+            NodeEditAction::ExportRdfPatch => {
+                if let Some(text) = self.read_rdf_data(|rdf_data| rdf_data.node_data.change_set.to_rdf_patch()) {
+                    self.save_change_set(text, "RDF Patch", "rdfp");
+                }
+            }
+            NodeEditAction::CopySparqlUpdate => {
+                if let Some(text) = self.read_rdf_data(|rdf_data| rdf_data.node_data.change_set.to_sparql_update()) {
+                    ctx.copy_text(text);
+                    self.set_status_message("SPARQL Update copied to clipboard");
+                }
+            }
+            NodeEditAction::PushSparqlUpdate => {
+                #[cfg(not(target_arch = "wasm32"))]
+                self.open_sparql_update_push();
+            }
+            NodeEditAction::ClearChanges => {
+                self.mut_rdf_data(|rdf_data| rdf_data.node_data.change_set.clear());
+            }
+        }
+    }
+
+    fn edit_node_triples(&mut self, description: &str, operations: Option<Vec<PatchOperation>>) {
+        let Some(operations) = operations else {
+            return;
+        };
+        if let Some(result) = self.edit_data_triples(description, operations) {
+            if result.duplicates > 0 {
+                self.set_status_message("The property value exists already");
+            }
+            self.notify_data_change(DataChange::TriplesMerged);
+        }
+    }
+
+    fn save_change_set(&mut self, text: String, format_name: &str, extension: &str) {
+        let file_name = format!("changes.{}", extension);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter(format_name, &[extension])
+                .set_file_name(&file_name)
+                .save_file()
+                && let Err(e) = std::fs::write(&path, text)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save {}: {}", format_name, e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = format_name;
+            let _ = web_download(&file_name, text.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use egui::ScrollArea;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::node_list::resolve_node_list,
+    uistate::{DisplayType, SystemMessage},
+};
+
+#[derive(Default)]
+pub struct NodeList {
+    pub text: String,
+    // keep the nodes already shown in the visual graph
+    pub add_to_graph: bool,
+    // IRIs of the last visualization that could not be found in the data
+    pub unknown: Vec<String>,
+}
+
+impl RdfGlanceApp {
+    pub fn open_node_list(&mut self) {
+        if self.node_list.is_none() {
+            self.node_list = Some(NodeList::default());
+        }
+    }
+
+    /// Replaces the visual graph by the nodes and the edges between them
+    pub fn visualize_nodes(&mut self, nodes: &[IriIndex]) {
+        if nodes.is_empty() {
+            return;
+        }
+        self.visible_nodes.clean_all();
+        self.show_and_select_nodes(nodes);
+        self.display_type = DisplayType::Graph;
+    }
+
+    pub fn show_node_list(&mut self, ui: &mut egui::Ui) {
+        let Some(node_list) = &mut self.node_list else {
+            return;
+        };
+        let mut close = false;
+        let mut visualize = false;
+        egui::Window::new("Visualize IRI List")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label("Full or prefixed IRIs separated by new lines, spaces or commas");
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut node_list.text)
+                            .desired_rows(10)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.button("Load from File").clicked()
+                        && let Some(path) = rfd::FileDialog::new()
+                            .add_filter("IRI List", &["txt", "csv", "tsv"])
+                            .pick_file()
+                    {
+                        match std::fs::read_to_string(&path) {
+                            Ok(text) => node_list.text = text,
+                            Err(e) => {
+                                self.system_message =
+                                    SystemMessage::Error(format!("Can not read file {}: {}", path.display(), e));
+                            }
+                        }
+                    }
+                    ui.checkbox(&mut node_list.add_to_graph, "Add to current graph");
+                });
+                if !node_list.unknown.is_empty() {
+                    ui.separator();
+                    ui.label(format!("{} IRIs not found in data", node_list.unknown.len()));
+                    ScrollArea::vertical()
+                        .id_salt("unknown_iris")
+                        .max_height(100.0)
+                        .show(ui, |ui| {
+                            for iri in node_list.unknown.iter() {
+                                ui.label(iri);
+                            }
+                        });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Visualize").clicked() {
+                        visualize = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if visualize {
+            let resolved = if let Ok(rdf_data) = self.rdf_data.read() {
+                resolve_node_list(&node_list.text, &rdf_data.node_data, &rdf_data.prefix_manager)
+            } else {
+                (Vec::new(), Vec::new())
+            };
+            let (nodes, unknown) = resolved;
+            node_list.unknown = unknown;
+            let add_to_graph = node_list.add_to_graph;
+            self.set_status_message(&format!("Visualize {} nodes", nodes.len()));
+            if add_to_graph {
+                self.show_and_select_nodes(&nodes);
+                self.display_type = DisplayType::Graph;
+            } else {
+                self.visualize_nodes(&nodes);
+            }
+        }
+        if close {
+            self.node_list = None;
+        }
+    }
+}
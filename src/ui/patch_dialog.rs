@@ -0,0 +1,122 @@
+use egui::{Color32, ScrollArea};
+
+use crate::{
+    RdfGlanceApp,
+    domain::data_change::DataChange,
+    integration::rdf_patch::{PatchFormat, PatchOperation, RdfPatch},
+    uistate::{PatchPreview, SystemMessage},
+};
+
+impl RdfGlanceApp {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn open_patch_file_dialog(&mut self) {
+        use rfd::FileDialog;
+
+        if let Some(path) = FileDialog::new()
+            .add_filter("RDF Patch", &["rdfp", "rdfpatch", "patch"])
+            .add_filter("LD Patch", &["ldpatch", "ldp"])
+            .pick_file()
+        {
+            let file_name = path.display().to_string();
+            match std::fs::read_to_string(&path) {
+                Ok(text) => {
+                    let patch = RdfPatch::parse(&file_name, &text);
+                    self.patch_preview = Some(PatchPreview { file_name, patch });
+                }
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not read patch file {}: {}", file_name, e));
+                }
+            }
+        }
+    }
+
+    pub fn show_patch_preview(&mut self, ui: &mut egui::Ui) {
+        let Some(patch_preview) = &self.patch_preview else {
+            return;
+        };
+        let mut apply_clicked = false;
+        let mut cancel_clicked = false;
+        egui::Window::new("Apply Patch")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(800.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                let patch = &patch_preview.patch;
+                let format = match patch.format {
+                    PatchFormat::RdfPatch => "RDF Patch",
+                    PatchFormat::LdPatch => "LD Patch",
+                };
+                ui.label(format!("{} ({})", patch_preview.file_name, format));
+                ui.label(format!(
+                    "Operations: {} add, {} delete",
+                    patch.add_count(),
+                    patch.delete_count()
+                ));
+                ui.separator();
+                let row_height = ui.text_style_height(&egui::TextStyle::Body);
+                ScrollArea::both()
+                    .id_salt("patch_operations")
+                    .max_height(300.0)
+                    .show_rows(ui, row_height, patch.operations.len(), |ui, row_range| {
+                        for operation in &patch.operations[row_range] {
+                            match operation {
+                                PatchOperation::Add(triple) => {
+                                    ui.colored_label(Color32::DARK_GREEN, format!("+ {}", triple));
+                                }
+                                PatchOperation::Delete(triple) => {
+                                    ui.colored_label(Color32::DARK_RED, format!("- {}", triple));
+                                }
+                            }
+                        }
+                    });
+                if !patch.errors.is_empty() {
+                    ui.separator();
+                    ui.label(format!("Errors: {}", patch.errors.len()));
+                    ScrollArea::vertical()
+                        .id_salt("patch_errors")
+                        .max_height(100.0)
+                        .show(ui, |ui| {
+                            for error in patch.errors.iter() {
+                                ui.label(error);
+                            }
+                        });
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!patch.operations.is_empty(), egui::Button::new("Apply"))
+                        .clicked()
+                    {
+                        apply_clicked = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel_clicked = true;
+                    }
+                });
+            });
+        ui.disable();
+        if cancel_clicked {
+            self.patch_preview = None;
+        }
+        if apply_clicked
+            && let Some(patch_preview) = self.patch_preview.take()
+        {
+            let language_filter = self.config.language_filter();
+            let result = if let Ok(mut rdf_data) = self.rdf_data.write() {
+                let node_count = rdf_data.node_data.len();
+                Some((node_count, patch_preview.patch.apply(&mut rdf_data, &language_filter)))
+            } else {
+                None
+            };
+            if let Some((node_count, mut result)) = result {
+                self.record_triple_change("Apply Patch", node_count, &mut result, false);
+                self.set_status_message(&format!(
+                    "Patch applied: {} added, {} deleted, {} duplicates, {} not found",
+                    result.added, result.deleted, result.duplicates, result.not_found
+                ));
+                self.notify_data_change(DataChange::TriplesMerged);
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::PathBuf;
+
+use egui::{Color32, ColorImage, Rect, epaint::ClippedShape};
+
+use crate::{
+    RdfGlanceApp,
+    integration::png::{PngExport, encode_png, png_scale, render_png, shapes_extent},
+    uistate::SystemMessage,
+};
+
+/// Export waiting for the next frame of the graph view, which paints the exported shapes
+pub struct PngExportRequest {
+    pub settings: PngExport,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub path: PathBuf,
+}
+
+impl RdfGlanceApp {
+    pub fn open_png_export(&mut self) {
+        if self.png_export.is_none() {
+            self.png_export = Some(PngExport::default());
+        }
+    }
+
+    pub fn show_png_export(&mut self, ui: &mut egui::Ui) {
+        let Some(png_export) = &mut self.png_export else {
+            return;
+        };
+        let mut close = false;
+        let mut export = false;
+        egui::Window::new("Export PNG")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut png_export.full_extent, true, "Whole graph");
+                    ui.radio_value(&mut png_export.full_extent, false, "Visible area");
+                });
+                ui.add(egui::Slider::new(&mut png_export.scale, 0.5..=4.0).step_by(0.5).text("Scale"))
+                    .on_hover_text("Image pixels per point of the graph view");
+                ui.checkbox(&mut png_export.transparent, "Transparent background");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Export...").clicked() {
+                        export = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if export {
+            let settings = *png_export;
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(path) = rfd::FileDialog::new()
+                .add_filter("PNG", &["png"])
+                .set_file_name("graph.png")
+                .save_file()
+            {
+                self.png_export_request = Some(PngExportRequest { settings, path });
+                close = true;
+            }
+            #[cfg(target_arch = "wasm32")]
+            {
+                self.png_export_request = Some(PngExportRequest { settings });
+                close = true;
+            }
+            ui.ctx().request_repaint();
+        }
+        if close {
+            self.png_export = None;
+        }
+    }
+
+    /// Renders the shapes painted by the graph view in this frame, view_rect is the visible area
+    pub fn png_export_image(
+        ctx: &egui::Context,
+        request: &PngExportRequest,
+        shapes: Vec<ClippedShape>,
+        view_rect: Rect,
+        background: Color32,
+    ) -> ColorImage {
+        let region = if request.settings.full_extent {
+            shapes_extent(&shapes)
+        } else {
+            view_rect
+        };
+        let background = if request.settings.transparent {
+            Color32::TRANSPARENT
+        } else {
+            background
+        };
+        render_png(ctx, shapes, region, png_scale(region, request.settings.scale), background)
+    }
+
+    pub fn write_png_export(&mut self, request: PngExportRequest, image: ColorImage) {
+        let png = match encode_png(&image) {
+            Ok(png) => png,
+            Err(e) => {
+                self.system_message = SystemMessage::Error(format!("Can not export png: {}", e));
+                return;
+            }
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        match std::fs::write(&request.path, png) {
+            Err(e) => {
+                self.system_message = SystemMessage::Error(format!("Can not export png: {}", e));
+            }
+            Ok(_) => {
+                self.set_status_message(&format!(
+                    "Graph exported as {}x{} image",
+                    image.size[0], image.size[1]
+                ));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+            let _ = request;
+            let _ = web_download("graph.png", &png);
+        }
+    }
+}
@@ -1,10 +1,40 @@
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread::{self, JoinHandle};
 
+use crate::{
+    RdfGlanceApp,
+    domain::{
+        data_change::DataChange,
+        prefix_manager::{PrefixSuggestion, catalog_prefix},
+    },
+    uistate::{SystemMessage, actions::NodeAction},
+};
 
-use crate::{uistate::actions::NodeAction, RdfGlanceApp};
+// only the most used namespaces are looked up online
+const MAX_LOOKUP_NAMESPACES: usize = 20;
 
+/// Prefix suggestions for the namespaces of the loaded data that have no prefix
+#[derive(Default)]
+pub struct PrefixSuggestions {
+    pub suggestions: Vec<PrefixSuggestion>,
+    // namespaces without suggestion with number of their iris, most used first
+    pub unmatched: Vec<(String, usize)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    lookup: Option<JoinHandle<Vec<PrefixSuggestion>>>,
+}
 
 impl RdfGlanceApp {
     pub fn show_prefixes(&mut self, ui: &mut egui::Ui) -> NodeAction {
+        ui.horizontal(|ui| {
+            if ui
+                .button("Suggest Prefixes")
+                .on_hover_text("Prefixes of well-known vocabularies for the namespaces of the data without prefix")
+                .clicked()
+            {
+                self.suggest_prefixes();
+            }
+        });
+        self.show_prefix_suggestions(ui);
         egui::ScrollArea::vertical().show(ui, |ui| {
             egui::Grid::new("prefixes").striped(true).show(ui, |ui| {
                 ui.heading("Prefix");
@@ -21,5 +51,169 @@ impl RdfGlanceApp {
         });
         NodeAction::None
     }
+
+    fn suggest_prefixes(&mut self) {
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let node_data = &rdf_data.node_data;
+        let iris = node_data
+            .node_cache
+            .iter()
+            .map(|(iri, _)| iri.as_ref())
+            .chain(node_data.indexers.type_indexer.map.iter().map(|(_, iri)| iri))
+            .chain(node_data.indexers.predicate_indexer.map.iter().map(|(_, iri)| iri));
+        let namespaces = rdf_data.prefix_manager.unknown_namespaces(iris);
+        let suggestions = rdf_data.prefix_manager.suggest_prefixes(&namespaces);
+        let unmatched: Vec<(String, usize)> = namespaces
+            .into_iter()
+            .filter(|(namespace, _)| catalog_prefix(namespace).is_none())
+            .collect();
+        drop(rdf_data);
+        if suggestions.is_empty() && unmatched.is_empty() {
+            self.prefix_suggestions = None;
+            self.system_message = SystemMessage::Info("All namespaces of the data have a prefix".to_string());
+            return;
+        }
+        self.prefix_suggestions = Some(PrefixSuggestions {
+            suggestions,
+            unmatched,
+            ..Default::default()
+        });
+    }
+
+    fn show_prefix_suggestions(&mut self, ui: &mut egui::Ui) {
+        let Some(prefix_suggestions) = &mut self.prefix_suggestions else {
+            return;
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        if prefix_suggestions.lookup.as_ref().is_some_and(|lookup| lookup.is_finished())
+            && let Some(lookup) = prefix_suggestions.lookup.take()
+        {
+            let mut found = lookup.join().unwrap_or_default();
+            if let Ok(rdf_data) = self.rdf_data.read() {
+                found.retain(|suggestion| rdf_data.prefix_manager.is_free_prefix(&suggestion.prefix, &[]));
+            }
+            prefix_suggestions
+                .unmatched
+                .retain(|(namespace, _)| !found.iter().any(|suggestion| suggestion.namespace == *namespace));
+            prefix_suggestions.suggestions.extend(found);
+        }
+        let mut to_apply: Vec<PrefixSuggestion> = Vec::new();
+        let mut close = false;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong(format!("{} suggested prefixes", prefix_suggestions.suggestions.len()));
+                if !prefix_suggestions.suggestions.is_empty() && ui.button("Apply All Suggestions").clicked() {
+                    to_apply = prefix_suggestions.suggestions.clone();
+                }
+                #[cfg(not(target_arch = "wasm32"))]
+                if prefix_suggestions.lookup.is_some() {
+                    ui.spinner();
+                    ui.label("Looking up prefix.cc");
+                } else if !prefix_suggestions.unmatched.is_empty()
+                    && ui
+                        .button("Look up on prefix.cc")
+                        .on_hover_text(format!(
+                            "Asks prefix.cc for the {} most used namespaces without suggestion",
+                            prefix_suggestions.unmatched.len().min(MAX_LOOKUP_NAMESPACES)
+                        ))
+                        .clicked()
+                {
+                    prefix_suggestions.lookup = Some(start_prefix_cc_lookup(
+                        &prefix_suggestions.unmatched,
+                        &prefix_suggestions.suggestions,
+                        ui.ctx(),
+                    ));
+                }
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+            egui::ScrollArea::vertical()
+                .id_salt("prefix_suggestions")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("prefix_suggestions_grid").striped(true).show(ui, |ui| {
+                        ui.label("Prefix");
+                        ui.label("Namespace");
+                        ui.label("Iris");
+                        ui.end_row();
+                        for suggestion in prefix_suggestions.suggestions.iter() {
+                            ui.label(&suggestion.prefix);
+                            ui.label(&suggestion.namespace);
+                            ui.label(suggestion.count.to_string());
+                            if ui.button("Apply").clicked() {
+                                to_apply.push(suggestion.clone());
+                            }
+                            ui.end_row();
+                        }
+                        for (namespace, count) in prefix_suggestions.unmatched.iter() {
+                            ui.weak("?");
+                            ui.label(namespace);
+                            ui.label(count.to_string());
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+        if !to_apply.is_empty() {
+            prefix_suggestions
+                .suggestions
+                .retain(|suggestion| !to_apply.iter().any(|applied| applied.namespace == suggestion.namespace));
+            if prefix_suggestions.suggestions.is_empty() && prefix_suggestions.unmatched.is_empty() {
+                close = true;
+            }
+            self.apply_prefix_suggestions(&to_apply);
+        }
+        if close {
+            self.prefix_suggestions = None;
+        }
+    }
+
+    /// Adds the prefixes and shows the iris of the loaded data with them
+    pub fn apply_prefix_suggestions(&mut self, suggestions: &[PrefixSuggestion]) {
+        self.record_data_change("Apply prefixes");
+        if let Ok(mut rdf_data) = self.rdf_data.write() {
+            for suggestion in suggestions {
+                rdf_data.prefix_manager.add_prefix(&suggestion.prefix, &suggestion.namespace);
+            }
+            let rdf_data = &mut *rdf_data;
+            rdf_data.node_data.apply_prefixes(&rdf_data.prefix_manager);
+        }
+        self.notify_data_change(DataChange::StyleChanged);
+    }
 }
 
+/// Looks up the namespaces on prefix.cc in a background thread, prefixes already used are skipped
+#[cfg(not(target_arch = "wasm32"))]
+fn start_prefix_cc_lookup(
+    unmatched: &[(String, usize)],
+    suggestions: &[PrefixSuggestion],
+    ctx: &egui::Context,
+) -> JoinHandle<Vec<PrefixSuggestion>> {
+    use crate::domain::prefix_manager::lookup_prefix_cc;
+
+    let namespaces: Vec<(String, usize)> = unmatched.iter().take(MAX_LOOKUP_NAMESPACES).cloned().collect();
+    let mut used_prefixes: Vec<String> = suggestions.iter().map(|suggestion| suggestion.prefix.clone()).collect();
+    let ctx = ctx.clone();
+    thread::spawn(move || {
+        let mut found: Vec<PrefixSuggestion> = Vec::new();
+        for (namespace, count) in namespaces {
+            match lookup_prefix_cc(&namespace) {
+                Ok(Some(prefix)) if !used_prefixes.contains(&prefix) => {
+                    used_prefixes.push(prefix.clone());
+                    found.push(PrefixSuggestion {
+                        prefix,
+                        namespace,
+                        count,
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("prefix.cc lookup of {} failed: {}", namespace, e),
+            }
+        }
+        ctx.request_repaint();
+        found
+    })
+}
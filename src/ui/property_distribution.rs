@@ -0,0 +1,210 @@
+use egui::Slider;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext,
+        column_chart::{ColumnChart, MAX_CATEGORIES},
+        type_index::ValueTypes,
+    },
+};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum StatisticsPanel {
+    GraphStatistics,
+    PropertyDistribution,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DistributionKind {
+    // histogram of numbers or top-k values
+    Values,
+    Languages,
+}
+
+// what the chart was computed for, the instances are compared by count and checksum
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct ChartKey {
+    type_index: IriIndex,
+    predicate: IriIndex,
+    kind: DistributionKind,
+    top_k: usize,
+    instance_count: usize,
+    instance_checksum: u64,
+}
+
+/**
+ * Value distribution of a property of the selected type.
+ * The chart is computed from the instances that pass the current table filter
+ * and recomputed when the filter changes.
+ */
+pub struct PropertyDistribution {
+    pub type_index: Option<IriIndex>,
+    pub predicate: Option<IriIndex>,
+    pub kind: DistributionKind,
+    pub top_k: usize,
+    pub chart: Option<ColumnChart>,
+    chart_key: Option<ChartKey>,
+}
+
+impl Default for PropertyDistribution {
+    fn default() -> Self {
+        Self {
+            type_index: None,
+            predicate: None,
+            kind: DistributionKind::Values,
+            top_k: 10,
+            chart: None,
+            chart_key: None,
+        }
+    }
+}
+
+impl PropertyDistribution {
+    pub fn clean(&mut self) {
+        self.chart = None;
+        self.chart_key = None;
+    }
+}
+
+// order independent, the table could be sorted without changing the filter
+fn instance_checksum(instances: &[IriIndex]) -> u64 {
+    instances
+        .iter()
+        .fold(0u64, |sum, instance| sum.wrapping_add((*instance as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+}
+
+impl RdfGlanceApp {
+    pub fn show_property_distribution(&mut self, ui: &mut egui::Ui) {
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let label_context = LabelContext::new(
+            self.ui_state.display_language,
+            self.config.iri_display,
+            &rdf_data.prefix_manager,
+        );
+        let distribution = &mut self.property_distribution;
+        let types: Vec<IriIndex> = self
+            .type_index
+            .types_order
+            .iter()
+            .filter(|type_index| {
+                self.type_index
+                    .types
+                    .get(type_index)
+                    .is_some_and(|type_data| !type_data.properties.is_empty())
+            })
+            .copied()
+            .collect();
+        if types.is_empty() {
+            ui.label("No type with data properties found");
+            return;
+        }
+        if distribution.type_index.is_none_or(|type_index| !types.contains(&type_index)) {
+            let table_type = self.type_index.selected_type.filter(|selected_type| types.contains(selected_type));
+            distribution.type_index = Some(table_type.unwrap_or(types[0]));
+            distribution.predicate = None;
+        }
+        let current_type = distribution.type_index.unwrap_or(types[0]);
+        let Some(type_data) = self.type_index.types.get(&current_type) else {
+            return;
+        };
+        let type_label = |type_index: IriIndex| {
+            rdf_data
+                .node_data
+                .type_display(type_index, &label_context, &rdf_data.node_data.indexers)
+                .as_str()
+                .to_owned()
+        };
+        let predicate_label = |predicate: IriIndex| {
+            rdf_data
+                .node_data
+                .predicate_display(predicate, &label_context, &rdf_data.node_data.indexers)
+                .as_str()
+                .to_owned()
+        };
+        let mut predicates: Vec<(IriIndex, String)> = type_data
+            .properties
+            .keys()
+            .map(|predicate| (*predicate, predicate_label(*predicate)))
+            .collect();
+        predicates.sort_by(|a, b| a.1.cmp(&b.1));
+        if distribution
+            .predicate
+            .is_none_or(|predicate| !type_data.properties.contains_key(&predicate))
+        {
+            distribution.predicate = predicates.first().map(|(predicate, _)| *predicate);
+        }
+        let has_languages = distribution
+            .predicate
+            .and_then(|predicate| type_data.properties.get(&predicate))
+            .is_some_and(|characteristics| characteristics.value_types.contains(ValueTypes::LANG_STRING));
+        if !has_languages {
+            distribution.kind = DistributionKind::Values;
+        }
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Type")
+                .selected_text(type_label(current_type))
+                .show_ui(ui, |ui| {
+                    for type_index in types.iter() {
+                        ui.selectable_value(&mut distribution.type_index, Some(*type_index), type_label(*type_index));
+                    }
+                });
+            egui::ComboBox::from_label("Property")
+                .selected_text(distribution.predicate.map(predicate_label).unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    for (predicate, label) in predicates.iter() {
+                        ui.selectable_value(&mut distribution.predicate, Some(*predicate), label);
+                    }
+                });
+            ui.selectable_value(&mut distribution.kind, DistributionKind::Values, "Values")
+                .on_hover_text("Histogram of numeric values or the most frequent values");
+            ui.add_enabled_ui(has_languages, |ui| {
+                ui.selectable_value(&mut distribution.kind, DistributionKind::Languages, "Languages")
+                    .on_hover_text("Language tags of the values");
+            });
+            if distribution.kind == DistributionKind::Values {
+                ui.add(Slider::new(&mut distribution.top_k, 1..=MAX_CATEGORIES).text("top values"));
+            }
+        });
+        let instances = &type_data.filtered_instances;
+        if instances.len() == type_data.instances.len() {
+            ui.label(format!("Instances: {}", instances.len()));
+        } else {
+            ui.label(format!(
+                "Instances: {} of {} (table filter)",
+                instances.len(),
+                type_data.instances.len()
+            ));
+        }
+        let Some(predicate) = distribution.predicate else {
+            return;
+        };
+        let chart_key = ChartKey {
+            type_index: current_type,
+            predicate,
+            kind: distribution.kind,
+            top_k: distribution.top_k,
+            instance_count: instances.len(),
+            instance_checksum: instance_checksum(instances),
+        };
+        if distribution.chart_key != Some(chart_key) {
+            let title = predicate_label(predicate);
+            distribution.chart = Some(match distribution.kind {
+                DistributionKind::Values => {
+                    ColumnChart::from_instances(title, predicate, &rdf_data.node_data, instances, distribution.top_k)
+                }
+                DistributionKind::Languages => {
+                    ColumnChart::language_distribution(title, predicate, &rdf_data.node_data, instances)
+                }
+            });
+            distribution.chart_key = Some(chart_key);
+        }
+        if let Some(chart) = &distribution.chart {
+            ui.horizontal(|ui| chart.export_button(ui));
+            let height = (ui.available_height() - 30.0).max(150.0);
+            chart.show_plot(ui, "property_distribution", height);
+        }
+    }
+}
@@ -0,0 +1,278 @@
+use std::path::Path;
+
+use egui::{Color32, Pos2, Rect, Sense, Stroke, UiKind, Vec2};
+
+use crate::{
+    RdfGlanceApp,
+    domain::recent_items::{RecentKind, Thumbnail},
+    ui::style::{ICON_DELETE, ICON_PIN},
+    uistate::{ImportFormat, LastVisitedSelection},
+};
+
+const THUMBNAIL_SIZE: f32 = 48.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RecentAction {
+    // Replace current data
+    Open,
+    // Replace current data and disable data changing actions
+    OpenReadOnly,
+    // Add data to the current data
+    Merge,
+    // Open the endpoint dialog to change the connection before connecting
+    ConnectWithOptions,
+    TogglePin,
+    Forget,
+}
+
+impl RdfGlanceApp {
+    pub fn recent_item_action(&mut self, kind: RecentKind, location: &str, action: RecentAction, is_dark_mode: bool) {
+        match action {
+            RecentAction::TogglePin => {
+                self.persistent_data.recent_items.toggle_pin(kind, location);
+                return;
+            }
+            RecentAction::Forget => {
+                self.persistent_data.recent_items.remove(kind, location);
+                return;
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            RecentAction::ConnectWithOptions => {
+                let node_query = self
+                    .persistent_data
+                    .recent_items
+                    .get(kind, location)
+                    .map(|item| item.node_query)
+                    .unwrap_or_default();
+                self.sparql_dialog = Some(crate::ui::sparql_dialog::SparqlDialog::with_node_query(
+                    &[location.to_string()],
+                    node_query,
+                ));
+                return;
+            }
+            #[cfg(target_arch = "wasm32")]
+            RecentAction::ConnectWithOptions => return,
+            RecentAction::Open | RecentAction::OpenReadOnly => {
+                if kind != RecentKind::Project && !self.is_empty() {
+                    self.clean_data();
+                }
+            }
+            RecentAction::Merge => {}
+        }
+        match kind {
+            RecentKind::Project => {
+                self.load_project(Path::new(location), is_dark_mode);
+            }
+            RecentKind::File => {
+                let path = Path::new(location);
+                if !path.exists() {
+                    self.system_message = crate::SystemMessage::Error(format!("File not found: {}", location));
+                    return;
+                }
+                if path.is_dir() {
+                    self.load_ttl_dir(location);
+                } else {
+                    self.load_ttl(location, is_dark_mode);
+                }
+            }
+            RecentKind::Url => {
+                self.persistent_data.recent_items.touch(RecentKind::Url, location);
+                self.load_ttl_from_url(location, ImportFormat::Auto, is_dark_mode);
+            }
+            #[cfg(not(target_arch = "wasm32"))]
+            RecentKind::Endpoint => {
+                let node_query = self
+                    .persistent_data
+                    .recent_items
+                    .get(kind, location)
+                    .map(|item| item.node_query)
+                    .unwrap_or_default();
+                self.connect_sparql_endpoint(location, node_query);
+            }
+            #[cfg(target_arch = "wasm32")]
+            RecentKind::Endpoint => {}
+        }
+        self.ui_state.read_only = action == RecentAction::OpenReadOnly;
+    }
+
+    pub fn graph_thumbnail(&self) -> Thumbnail {
+        let Some((rdf_data, layout)) = self.visible_nodes.read_with_data(&self.rdf_data) else {
+            return Thumbnail::default();
+        };
+        let colors: Vec<[u8; 3]> = layout
+            .nodes
+            .iter()
+            .map(|node_layout| {
+                let color = match rdf_data.node_data.get_node_by_index(node_layout.node_index) {
+                    Some((_, node)) => self.visualization_style.get_type_style(&node.types).color,
+                    None => Color32::GRAY,
+                };
+                [color.r(), color.g(), color.b()]
+            })
+            .collect();
+        let positions: Vec<(f32, f32)> = layout
+            .positions
+            .iter()
+            .map(|position| (position.pos.x, position.pos.y))
+            .collect();
+        let edges: Vec<(usize, usize)> = layout.edges.iter().map(|edge| (edge.from, edge.to)).collect();
+        Thumbnail::new(&positions, &edges).with_colors(colors.into_iter())
+    }
+
+    /// List of recent items shown if no data are loaded
+    pub fn show_recent_items(&mut self, ui: &mut egui::Ui) {
+        let items: Vec<(RecentKind, Box<str>, bool, Option<Thumbnail>)> = self
+            .persistent_data
+            .recent_items
+            .sorted()
+            .into_iter()
+            .map(|item| (item.kind, item.location.clone(), item.pinned, item.thumbnail.clone()))
+            .collect();
+        if items.is_empty() {
+            return;
+        }
+        let mut enter_pressed = false;
+        let mut delete_pressed = false;
+        let mut selected = match self.ui_state.last_visited_selection {
+            LastVisitedSelection::Item(index) => index.min(items.len() - 1),
+            LastVisitedSelection::None => 0,
+        };
+        ui.input(|i| {
+            if i.key_pressed(egui::Key::Enter) {
+                enter_pressed = true;
+            } else if i.key_pressed(egui::Key::Delete) {
+                delete_pressed = true;
+            } else if i.key_pressed(egui::Key::ArrowUp) {
+                selected = selected.saturating_sub(1);
+            } else if i.key_pressed(egui::Key::ArrowDown) && selected + 1 < items.len() {
+                selected += 1;
+            }
+        });
+        self.ui_state.last_visited_selection = LastVisitedSelection::Item(selected);
+
+        let mut item_action: Option<(RecentKind, Box<str>, RecentAction)> = None;
+        ui.spacing();
+        ui.heading("Recently used:");
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("recent_items").striped(true).show(ui, |ui| {
+                for (index, (kind, location, pinned, thumbnail)) in items.iter().enumerate() {
+                    if index == selected {
+                        let painter = ui.painter();
+                        painter.rect_filled(ui.available_rect_before_wrap(), 0.0, ui.visuals().selection.bg_fill);
+                        if enter_pressed {
+                            item_action = Some((*kind, location.clone(), RecentAction::Open));
+                        } else if delete_pressed {
+                            item_action = Some((*kind, location.clone(), RecentAction::Forget));
+                        }
+                    }
+                    match thumbnail {
+                        Some(thumbnail) if !thumbnail.is_empty() => paint_thumbnail(ui, thumbnail),
+                        _ => {
+                            ui.label("");
+                        }
+                    }
+                    ui.label(kind.label());
+                    if ui.button(&**location).clicked() {
+                        item_action = Some((*kind, location.clone(), RecentAction::Open));
+                    }
+                    if matches!(kind, RecentKind::File | RecentKind::Project) {
+                        if ui.button("Read-only").on_hover_text("Open without data changing actions").clicked() {
+                            item_action = Some((*kind, location.clone(), RecentAction::OpenReadOnly));
+                        }
+                    } else if *kind == RecentKind::Endpoint && cfg!(not(target_arch = "wasm32")) {
+                        if ui.button("Options").on_hover_text("Change the connection before connecting").clicked() {
+                            item_action = Some((*kind, location.clone(), RecentAction::ConnectWithOptions));
+                        }
+                    } else {
+                        ui.label("");
+                    }
+                    if ui.selectable_label(*pinned, ICON_PIN).on_hover_text("Pin").clicked() {
+                        item_action = Some((*kind, location.clone(), RecentAction::TogglePin));
+                    }
+                    if ui.button(ICON_DELETE).on_hover_text("Forget").clicked() {
+                        item_action = Some((*kind, location.clone(), RecentAction::Forget));
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+        if let Some((kind, location, action)) = item_action {
+            self.recent_item_action(kind, &location, action, ui.visuals().dark_mode);
+        }
+    }
+
+    /// Submenu of file menu with quick actions for recent items
+    pub fn recent_items_menu(&mut self, ui: &mut egui::Ui) {
+        if self.persistent_data.recent_items.is_empty() {
+            return;
+        }
+        let mut item_action: Option<(RecentKind, Box<str>, RecentAction)> = None;
+        ui.menu_button("Recently Used", |ui| {
+            let has_data = !self.is_empty();
+            for item in self.persistent_data.recent_items.sorted() {
+                let text = if item.pinned {
+                    format!("{} {}: {}", ICON_PIN, item.kind.label(), item.location)
+                } else {
+                    format!("{}: {}", item.kind.label(), item.location)
+                };
+                ui.menu_button(text, |ui| {
+                    let open_text = if item.kind == RecentKind::Endpoint { "Connect" } else { "Open" };
+                    if ui.button(open_text).clicked() {
+                        item_action = Some((item.kind, item.location.clone(), RecentAction::Open));
+                    }
+                    if item.kind == RecentKind::Endpoint
+                        && cfg!(not(target_arch = "wasm32"))
+                        && ui.button("Connect with Options...").clicked()
+                    {
+                        item_action = Some((item.kind, item.location.clone(), RecentAction::ConnectWithOptions));
+                    }
+                    if matches!(item.kind, RecentKind::File | RecentKind::Project) && ui.button("Open Read-only").clicked() {
+                        item_action = Some((item.kind, item.location.clone(), RecentAction::OpenReadOnly));
+                    }
+                    if has_data
+                        && matches!(item.kind, RecentKind::File | RecentKind::Url)
+                        && ui.button("Merge into Current Data").clicked()
+                    {
+                        item_action = Some((item.kind, item.location.clone(), RecentAction::Merge));
+                    }
+                    if ui.button(if item.pinned { "Unpin" } else { "Pin" }).clicked() {
+                        item_action = Some((item.kind, item.location.clone(), RecentAction::TogglePin));
+                    }
+                    if ui.button("Forget").clicked() {
+                        item_action = Some((item.kind, item.location.clone(), RecentAction::Forget));
+                    }
+                });
+            }
+        });
+        if let Some((kind, location, action)) = item_action {
+            ui.close_kind(UiKind::Menu);
+            self.recent_item_action(kind, &location, action, ui.visuals().dark_mode);
+            ui.ctx().request_repaint();
+        }
+    }
+}
+
+fn paint_thumbnail(ui: &mut egui::Ui, thumbnail: &Thumbnail) {
+    let (response, painter) = ui.allocate_painter(Vec2::splat(THUMBNAIL_SIZE), Sense::hover());
+    let rect: Rect = response.rect.shrink(2.0);
+    let scale = rect.width() / 255.0;
+    let to_pos = |node: &[u8; 2]| Pos2::new(rect.min.x + node[0] as f32 * scale, rect.min.y + node[1] as f32 * scale);
+    let stroke = Stroke::new(0.5, ui.visuals().weak_text_color());
+    for edge in thumbnail.edges.iter() {
+        if let (Some(from), Some(to)) = (thumbnail.nodes.get(edge[0] as usize), thumbnail.nodes.get(edge[1] as usize)) {
+            painter.line_segment([to_pos(from), to_pos(to)], stroke);
+        }
+    }
+    let node_color = if ui.visuals().dark_mode {
+        Color32::LIGHT_BLUE
+    } else {
+        Color32::DARK_BLUE
+    };
+    for (node_pos, node) in thumbnail.nodes.iter().enumerate() {
+        let color = match thumbnail.colors.get(node_pos) {
+            Some([r, g, b]) => Color32::from_rgb(*r, *g, *b),
+            None => node_color,
+        };
+        painter.circle_filled(to_pos(node), 1.5, color);
+    }
+}
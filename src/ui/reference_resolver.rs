@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use rayon::prelude::*;
 
 use crate::{
-    IriIndex, RdfGlanceApp, domain::{LabelContext, LabelDisplayValue, Literal, RdfData, reference_resolver::resolve_references, type_index::{TypeInstanceIndex, ValueTypes}}, ui::style::ICON_DELETE, uistate::actions::NodeAction
+    IriIndex, RdfGlanceApp, domain::{LabelContext, LabelDisplayValue, Literal, RdfData, data_change::DataChange, reference_resolver::resolve_references, type_index::{TypeInstanceIndex, ValueTypes}}, ui::style::ICON_DELETE, uistate::actions::NodeAction
 };
 
 pub struct ReferenceResolver {
@@ -169,7 +169,7 @@ impl RdfGlanceApp {
             if let Ok(rdf_data) = self.rdf_data.read() {           
                 let label_context = LabelContext::new(
                     self.ui_state.display_language,
-                    self.persistent_data.config_data.iri_display,
+                    self.config.iri_display,
                     &rdf_data.prefix_manager,
                 );
                 if ui.button("Compute possible references from data").clicked() {
@@ -192,6 +192,7 @@ impl RdfGlanceApp {
             let has_defined = self.reference_resolver.has_defined();
             ui.add_enabled_ui(has_defined, |ui| {
                 if ui.button("Resolve References").clicked() {
+                    self.record_data_change("Resolve References");
                     if let Ok(mut rdf_data) = self.rdf_data.write() { 
                         for resolver_item in self.reference_resolver.items.iter() {
                             if let Some(from_type) = resolver_item.from_type && let Some(from_predicate) = resolver_item.from_predicate 
@@ -202,7 +203,7 @@ impl RdfGlanceApp {
                             }
                         }
                         self.reference_resolver.clean();
-                        self.type_index.update(&rdf_data.node_data);
+                        self.data_changes.notify(DataChange::TriplesMerged);
                     }
                 }            
             });
@@ -0,0 +1,71 @@
+use egui::{Align2, Rect, Vec2};
+
+use crate::{RdfGlanceApp, domain::render_mode::RenderSimplification};
+
+// seconds the notice about simplified drawing stays visible
+const RENDER_NOTICE_DURATION: f64 = 10.0;
+
+impl RdfGlanceApp {
+    /// Switches the drawing mode if the number of visible nodes crosses the configured thresholds
+    pub fn update_render_simplification(&mut self, node_count: usize, now: f64) {
+        let simplification = if self.ui_state.full_rendering {
+            RenderSimplification::default()
+        } else {
+            RenderSimplification::for_node_count(node_count, &self.config)
+        };
+        let added = simplification.added_to(&self.ui_state.render_simplification);
+        if !added.is_full() {
+            log::info!("Simplified drawing for {} nodes: {}", node_count, added.describe());
+            self.ui_state.render_notice = Some((
+                format!(
+                    "{} visible nodes, switched to faster drawing: {}",
+                    node_count,
+                    added.describe()
+                ),
+                now,
+            ));
+        }
+        self.ui_state.render_simplification = simplification;
+    }
+
+    pub fn show_render_notice(&mut self, ui: &egui::Ui, graph_rect: Rect) {
+        let Some((notice, shown)) = &self.ui_state.render_notice else {
+            return;
+        };
+        let remaining = RENDER_NOTICE_DURATION - (ui.input(|i| i.time) - shown);
+        if remaining <= 0.0 {
+            self.ui_state.render_notice = None;
+            return;
+        }
+        let mut full_rendering = false;
+        let mut close = false;
+        egui::Area::new(egui::Id::new("render_notice"))
+            .pivot(Align2::CENTER_BOTTOM)
+            .fixed_pos(graph_rect.center_bottom() - Vec2::new(0.0, 10.0))
+            .show(ui.ctx(), |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(notice);
+                        if ui
+                            .button("Full Drawing")
+                            .on_hover_text("Draw all details regardless of the number of nodes")
+                            .clicked()
+                        {
+                            full_rendering = true;
+                        }
+                        if ui.button("OK").clicked() {
+                            close = true;
+                        }
+                    });
+                });
+            });
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+        if full_rendering {
+            self.ui_state.full_rendering = true;
+            self.ui_state.render_simplification = RenderSimplification::default();
+        }
+        if full_rendering || close {
+            self.ui_state.render_notice = None;
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{
+    RdfGlanceApp,
+    domain::{
+        config::{Config, ConfigOverrides},
+        recent_items::RecentItems,
+    },
+    uistate::GraphState,
+};
+
+#[derive(Clone, Copy)]
+pub struct ResetSettings {
+    // egui memory (window positions, panel sizes, scroll positions) and graph views
+    pub view_state: bool,
+    // global settings and the settings overwritten by the project
+    pub settings: bool,
+    pub recent_items: bool,
+}
+
+impl Default for ResetSettings {
+    fn default() -> Self {
+        Self {
+            view_state: true,
+            settings: false,
+            recent_items: false,
+        }
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn open_reset_settings(&mut self) {
+        if self.reset_settings.is_none() {
+            self.reset_settings = Some(ResetSettings::default());
+        }
+    }
+
+    pub fn show_reset_settings(&mut self, ui: &mut egui::Ui) {
+        let Some(reset_settings) = &mut self.reset_settings else {
+            return;
+        };
+        let mut close = false;
+        let mut reset = false;
+        egui::Window::new("Reset Settings")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.checkbox(&mut reset_settings.view_state, "View state")
+                    .on_hover_text("Window positions, panel sizes, graph view positions and bookmarks");
+                ui.checkbox(&mut reset_settings.settings, "All settings")
+                    .on_hover_text("Global settings and the settings overwritten by the project");
+                ui.checkbox(&mut reset_settings.recent_items, "Recent files, projects and endpoints");
+                if self.safe_mode {
+                    ui.label("The application runs in safe mode, the reset state will be saved on exit");
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let any_selected =
+                        reset_settings.view_state || reset_settings.settings || reset_settings.recent_items;
+                    if ui.add_enabled(any_selected, egui::Button::new("Reset")).clicked() {
+                        reset = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if reset {
+            let ResetSettings {
+                view_state,
+                settings,
+                recent_items,
+            } = *reset_settings;
+            if view_state {
+                ui.ctx().memory_mut(|memory| *memory = egui::Memory::default());
+                self.graph_state = GraphState::default();
+                self.meta_graph_state = GraphState::default();
+                self.ui_state.show_properties = true;
+                self.ui_state.show_incoming_references = false;
+            }
+            if settings {
+                self.persistent_data.config_data = Config::default();
+                self.project_config = ConfigOverrides::default();
+                self.update_config();
+                self.visible_nodes.update_node_shapes = true;
+            }
+            if recent_items {
+                self.persistent_data.recent_items = RecentItems::default();
+            }
+            // the reset state should replace the persisted (maybe corrupted) one
+            self.safe_mode = false;
+            self.set_status_message("Settings reset");
+            close = true;
+        }
+        if close {
+            self.reset_settings = None;
+        }
+    }
+}
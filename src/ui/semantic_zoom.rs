@@ -0,0 +1,151 @@
+use crate::{
+    RdfGlanceApp,
+    graph_algorithms::StatisticValue,
+    domain::semantic_zoom::{SemanticZoomSettings, ZoomMapping, ZoomSource, zoom_layers},
+};
+
+impl RdfGlanceApp {
+    pub fn open_semantic_zoom(&mut self) {
+        if self.semantic_zoom.is_none() {
+            self.semantic_zoom = Some(SemanticZoomSettings::default());
+        }
+    }
+
+    pub fn show_semantic_zoom(&mut self, ui: &mut egui::Ui) {
+        let Some(settings) = &mut self.semantic_zoom else {
+            return;
+        };
+        let mut close = false;
+        let mut apply = false;
+        let mut clear = false;
+        let mut sources = vec![ZoomSource::Degree, ZoomSource::TypePriority];
+        if let Some(statistics_data) = &self.statistics_data {
+            sources.extend(
+                statistics_data
+                    .results
+                    .iter()
+                    .map(|result| result.statistics_value())
+                    .filter(|statistic_value| {
+                        !matches!(
+                            statistic_value,
+                            StatisticValue::ClusteringLouvain | StatisticValue::ClusteringSpectral
+                        )
+                    })
+                    .map(ZoomSource::Statistic),
+            );
+        }
+        egui::Window::new("Semantic Zoom")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label("Nodes with high values stay visible if the semantic zoom slider is moved up");
+                egui::Grid::new("semantic_zoom_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Metric");
+                    egui::ComboBox::from_id_salt("semantic_zoom_source")
+                        .selected_text(settings.source.label())
+                        .show_ui(ui, |ui| {
+                            for source in sources.iter() {
+                                ui.selectable_value(&mut settings.source, *source, source.label());
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Mapping");
+                    ui.horizontal(|ui| {
+                        ui.radio_value(&mut settings.mapping, ZoomMapping::Rank, "Rank")
+                            .on_hover_text("Few top nodes in the highest layer, layer size grows geometrically");
+                        ui.radio_value(&mut settings.mapping, ZoomMapping::Linear, "Linear")
+                            .on_hover_text("Value range is divided into equal layers");
+                    });
+                    ui.end_row();
+                    ui.label("");
+                    ui.checkbox(&mut settings.invert, "Low values stay visible");
+                    ui.end_row();
+                });
+                ui.label("Nodes with manually set zoom in the node details are not changed");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Apply").clicked() {
+                        apply = true;
+                    }
+                    if ui.button("Clear Semantic Zoom").clicked() {
+                        clear = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if apply {
+            let source = settings.source;
+            let mapping = settings.mapping;
+            let invert = settings.invert;
+            self.apply_semantic_zoom(source, mapping, invert);
+        }
+        if clear {
+            if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+                let individual_node_styles = &mut layout.individual_node_styles;
+                for node_style in individual_node_styles.iter_mut() {
+                    node_style.semantic_zoom_interval = Default::default();
+                    node_style.semantic_zoom_manual = false;
+                }
+            }
+            self.visible_nodes.has_semantic_zoom = false;
+            self.ui_state.semantic_zoom_magnitude = 1;
+        }
+        if close {
+            self.semantic_zoom = None;
+        }
+    }
+
+    fn semantic_zoom_values(&mut self, source: ZoomSource) -> Option<Vec<f32>> {
+        let (rdf_data, layout) = self.visible_nodes.read_with_data(&self.rdf_data)?;
+        let mut values = vec![0.0; layout.nodes.len()];
+        match source {
+            ZoomSource::Statistic(statistic_value) => {
+                let statistics_data = self.statistics_data.as_ref()?;
+                let result = statistics_data
+                    .results
+                    .iter()
+                    .find(|result| result.statistics_value() == statistic_value)?;
+                for ((_, node_pos), value) in statistics_data.nodes.iter().zip(result.get_data_vec()) {
+                    if let Some(node_value) = values.get_mut(*node_pos as usize) {
+                        *node_value = *value;
+                    }
+                }
+            }
+            ZoomSource::Degree => {
+                for edge in layout.edges.iter() {
+                    if self.ui_state.hidden_predicates.contains(edge.predicate) {
+                        continue;
+                    }
+                    values[edge.from] += 1.0;
+                    values[edge.to] += 1.0;
+                }
+            }
+            ZoomSource::TypePriority => {
+                for (node_pos, node_layout) in layout.nodes.iter().enumerate() {
+                    if let Some((_, node)) = rdf_data.node_data.get_node_by_index(node_layout.node_index) {
+                        values[node_pos] = self.visualization_style.get_type_style(&node.types).priority as f32;
+                    }
+                }
+            }
+        }
+        Some(values)
+    }
+
+    pub fn apply_semantic_zoom(&mut self, source: ZoomSource, mapping: ZoomMapping, invert: bool) {
+        let Some(values) = self.semantic_zoom_values(source) else {
+            self.set_status_message(&format!("No values for {}", source.label()));
+            return;
+        };
+        let layers = zoom_layers(&values, mapping, invert);
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            for (node_style, layer) in layout.individual_node_styles.iter_mut().zip(layers) {
+                node_style.set_zoom_layer(layer);
+            }
+        }
+        self.visible_nodes.has_semantic_zoom = true;
+        self.visible_nodes.update_node_shapes = true;
+    }
+}
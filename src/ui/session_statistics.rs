@@ -0,0 +1,129 @@
+use egui::{Grid, ScrollArea};
+
+use crate::{
+    RdfGlanceApp,
+    domain::{LabelContext, session_statistics::format_duration},
+};
+
+impl RdfGlanceApp {
+    pub fn show_session_statistics(&mut self, ui: &mut egui::Ui) {
+        if !self.ui_state.session_statistics_window {
+            return;
+        }
+        let mut close = false;
+        egui::Window::new("Session Statistics")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(500.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label("The statistics are collected only locally and never leave the application.");
+                ui.separator();
+                let statistics = &self.session_statistics;
+                Grid::new("session_statistics_grid").show(ui, |ui| {
+                    ui.label("Nodes explored:");
+                    ui.label(statistics.explored_nodes.len().to_string());
+                    ui.end_row();
+                    ui.label("Node visits:");
+                    ui.label(statistics.node_visits.to_string());
+                    ui.end_row();
+                    ui.label("Queries run:");
+                    ui.label(statistics.queries_run.to_string());
+                    ui.end_row();
+                });
+                ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                    ui.strong("Busiest types");
+                    if let Ok(rdf_data) = self.rdf_data.read() {
+                        let label_context = LabelContext::new(
+                            self.ui_state.display_language,
+                            self.config.iri_display,
+                            &rdf_data.prefix_manager,
+                        );
+                        Grid::new("session_statistics_types").striped(true).show(ui, |ui| {
+                            for (type_index, count) in statistics.busiest_types(20) {
+                                let type_label = rdf_data.node_data.type_display(
+                                    type_index,
+                                    &label_context,
+                                    &rdf_data.node_data.indexers,
+                                );
+                                ui.label(type_label.as_str());
+                                ui.label(count.to_string());
+                                ui.end_row();
+                            }
+                        });
+                    }
+                    ui.strong("Time per view");
+                    Grid::new("session_statistics_views").striped(true).show(ui, |ui| {
+                        for (view, seconds) in statistics.views_by_time() {
+                            ui.label(view);
+                            ui.label(format_duration(seconds));
+                            ui.end_row();
+                        }
+                    });
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Save Report").clicked() {
+                        self.save_session_statistics();
+                    }
+                    if ui.button("Reset").clicked() {
+                        self.session_statistics.clear();
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if close {
+            self.ui_state.session_statistics_window = false;
+        }
+    }
+
+    pub fn record_explored_node(&mut self, node_index: crate::IriIndex) {
+        if let Ok(rdf_data) = self.rdf_data.read()
+            && let Some((_, node)) = rdf_data.node_data.get_node_by_index(node_index)
+        {
+            self.session_statistics.record_node(node_index, &node.types);
+        }
+    }
+
+    fn save_session_statistics(&mut self) {
+        let text = if let Ok(rdf_data) = self.rdf_data.read() {
+            let label_context = LabelContext::new(
+                self.ui_state.display_language,
+                self.config.iri_display,
+                &rdf_data.prefix_manager,
+            );
+            self.session_statistics.to_text(|type_index| {
+                rdf_data
+                    .node_data
+                    .type_display(type_index, &label_context, &rdf_data.node_data.indexers)
+                    .as_str()
+                    .to_string()
+            })
+        } else {
+            return;
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            use crate::uistate::SystemMessage;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter("Text", &["txt"])
+                .set_file_name("session_statistics.txt")
+                .save_file()
+                && let Err(e) = std::fs::write(&path, text)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save session statistics: {}", e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = web_download("session_statistics.txt", text.as_bytes());
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use egui::{Align2, Id, Order, Pos2, Rect, Stroke, Vec2};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext,
+        graph_styles::NodeSize,
+        size_mapping::{SizeMapping, SizeSource},
+    },
+    graph_algorithms::StatisticValue,
+};
+
+const LEGEND_MARGIN: f32 = 8.0;
+
+impl RdfGlanceApp {
+    fn uses_size_mapping(&self) -> bool {
+        self.visualization_style.size_mapping.source.is_some()
+            && self
+                .visualization_style
+                .node_styles
+                .values()
+                .any(|node_style| node_style.node_size == NodeSize::Mapped)
+    }
+
+    /**
+     * Computes the mapped sizes of the visible nodes if the nodes, the statistics or the mapping have changed.
+     * The sizes are stored in the individual node styles, so the drawing does not need to look up the values.
+     */
+    pub fn update_size_mapping(&mut self) {
+        if !self.uses_size_mapping() {
+            return;
+        }
+        let (statistics_epoch, statistics_results) = self
+            .statistics_data
+            .as_ref()
+            .map_or((0, 0), |statistics_data| (statistics_data.data_epoch, statistics_data.results.len()));
+        let size_mapping = &mut self.visualization_style.size_mapping;
+        let key = (
+            self.visible_nodes.data_epoch,
+            statistics_epoch,
+            statistics_results,
+            size_mapping.version,
+        );
+        if size_mapping.applied_key == Some(key) {
+            return;
+        }
+        size_mapping.applied_key = Some(key);
+        let Some((rdf_data, layout)) = self.visible_nodes.read_with_data(&self.rdf_data) else {
+            return;
+        };
+        let mut values: Vec<f32> = vec![f32::NAN; layout.nodes.len()];
+        match size_mapping.source {
+            Some(SizeSource::Property(predicate)) => {
+                let indexers = &rdf_data.node_data.indexers;
+                for (value, node_layout) in values.iter_mut().zip(layout.nodes.iter()) {
+                    if let Some((_, node)) = rdf_data.node_data.get_node_by_index(node_layout.node_index) {
+                        *value = node
+                            .properties
+                            .iter()
+                            .filter(|(property, _)| *property == predicate)
+                            .find_map(|(_, literal)| literal.as_str_ref(indexers).trim().parse::<f32>().ok())
+                            .unwrap_or(f32::NAN);
+                    }
+                }
+            }
+            Some(SizeSource::Statistic(statistic_value)) => {
+                if let Some(statistics_data) = &self.statistics_data
+                    && statistics_data.data_epoch == self.visible_nodes.data_epoch
+                    && let Some(result) = statistics_data
+                        .results
+                        .iter()
+                        .find(|result| result.statistics_value() == statistic_value)
+                {
+                    for (value, (_, node_pos)) in result.get_data_vec().iter().zip(statistics_data.nodes.iter()) {
+                        if let Some(node_value) = values.get_mut(*node_pos as usize) {
+                            *node_value = *value;
+                        }
+                    }
+                }
+            }
+            None => {}
+        }
+        drop(layout);
+        drop(rdf_data);
+        let (sizes, range) = size_mapping.map_values(&values);
+        size_mapping.applied_range = range;
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            for (individual_node_style, size) in layout.individual_node_styles.iter_mut().zip(sizes) {
+                individual_node_style.mapped_size = size;
+            }
+        }
+        self.visible_nodes.update_node_shapes = true;
+    }
+
+    /// Legend of the mapped node sizes in the bottom left corner of the graph view
+    pub fn show_size_legend(&self, ctx: &egui::Context, graph_rect: Rect) {
+        if !self.uses_size_mapping() {
+            return;
+        }
+        let size_mapping = &self.visualization_style.size_mapping;
+        let legend = size_mapping.legend();
+        if legend.is_empty() {
+            return;
+        }
+        let source_label = match size_mapping.source {
+            Some(SizeSource::Statistic(statistic_value)) => statistic_value.to_string(),
+            Some(SizeSource::Property(predicate)) => self.rdf_data.read().map_or(String::new(), |rdf_data| {
+                let label_context =
+                    LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
+                rdf_data
+                    .node_data
+                    .predicate_display(predicate, &label_context, &rdf_data.node_data.indexers)
+                    .as_str()
+                    .to_owned()
+            }),
+            None => String::new(),
+        };
+        let max_size = legend.iter().map(|(_, size)| *size).fold(0.0, f32::max);
+        egui::Area::new(Id::new("size_legend"))
+            .order(Order::Foreground)
+            .pivot(Align2::LEFT_BOTTOM)
+            .fixed_pos(graph_rect.left_bottom() + Vec2::new(LEGEND_MARGIN, -LEGEND_MARGIN))
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(format!("Node size: {}", source_label));
+                    ui.horizontal(|ui| {
+                        for (value, size) in legend.iter() {
+                            ui.vertical_centered(|ui| {
+                                let (rect, _) = ui.allocate_exact_size(Vec2::splat(max_size), egui::Sense::hover());
+                                let center = Pos2::new(rect.center().x, rect.max.y - size / 2.0);
+                                ui.painter().circle_stroke(center, size / 2.0, Stroke::new(1.0, ui.visuals().text_color()));
+                                ui.label(format!("{:.3}", value).trim_end_matches('0').trim_end_matches('.'));
+                            });
+                        }
+                    });
+                });
+            });
+    }
+}
+
+/// Settings of the size mapping shared by all types with mapped size
+pub fn display_size_mapping(
+    ui: &mut egui::Ui,
+    size_mapping: &mut SizeMapping,
+    properties: &[(IriIndex, String)],
+    statistic_values: &[StatisticValue],
+) {
+    ui.group(|ui| {
+        ui.label("Size Mapping (shared by all types with mapped size)");
+        let mut changed = false;
+        let selected_text = match size_mapping.source {
+            Some(SizeSource::Property(predicate)) => properties
+                .iter()
+                .find(|(predicate_index, _)| *predicate_index == predicate)
+                .map_or_else(|| "Property of other type".to_string(), |(_, label)| label.clone()),
+            Some(SizeSource::Statistic(statistic_value)) => statistic_value.to_string(),
+            None => "None".to_string(),
+        };
+        ui.horizontal(|ui| {
+            ui.label("Value:");
+            egui::ComboBox::from_id_salt("size_mapping_source")
+                .selected_text(selected_text)
+                .show_ui(ui, |ui| {
+                    changed |= ui.selectable_value(&mut size_mapping.source, None, "None").changed();
+                    for (predicate_index, label) in properties.iter() {
+                        changed |= ui
+                            .selectable_value(&mut size_mapping.source, Some(SizeSource::Property(*predicate_index)), label)
+                            .changed();
+                    }
+                    for statistic_value in statistic_values.iter() {
+                        changed |= ui
+                            .selectable_value(
+                                &mut size_mapping.source,
+                                Some(SizeSource::Statistic(*statistic_value)),
+                                statistic_value.to_string(),
+                            )
+                            .changed();
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            changed |= ui
+                .checkbox(&mut size_mapping.auto_range, "Value range from data")
+                .on_hover_text("Otherwise the values are clamped to the range")
+                .changed();
+            ui.add_enabled_ui(!size_mapping.auto_range, |ui| {
+                changed |= ui.add(egui::DragValue::new(&mut size_mapping.clamp_min).prefix("min ")).changed();
+                changed |= ui.add(egui::DragValue::new(&mut size_mapping.clamp_max).prefix("max ")).changed();
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Node Size:");
+            changed |= ui
+                .add(egui::Slider::new(&mut size_mapping.min_size, 3.0..=150.0).text("min"))
+                .changed();
+            changed |= ui
+                .add(egui::Slider::new(&mut size_mapping.max_size, 3.0..=150.0).text("max"))
+                .changed();
+        });
+        if changed {
+            size_mapping.version += 1;
+        }
+    });
+}
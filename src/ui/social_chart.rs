@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc;
+
+use egui::TextureId;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{NodeData, prefix_manager::PrefixManager, social_chart::SocialVocabulary},
+    layoutalg::hierarchical::org_chart_layout,
+    uistate::{SystemMessage, layout::LayoutData},
+};
+
+// photos are downloaded in background threads, only some at the same time
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_PHOTO_REQUESTS: usize = 4;
+#[cfg(not(target_arch = "wasm32"))]
+const PHOTO_SIZE: u32 = 96;
+
+enum NodePhoto {
+    Loading,
+    Loaded(egui::TextureHandle),
+    Missing,
+}
+
+/// Social/org chart preset that is active for the current data, it keeps the loaded photos of the people
+pub struct SocialChart {
+    pub vocabulary: SocialVocabulary,
+    pub show_photos: bool,
+    // nodes pinned by the org chart layout, nodes pinned by the user are not included
+    pub pinned_nodes: Vec<IriIndex>,
+    photos: HashMap<IriIndex, NodePhoto>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_photos: usize,
+    #[cfg(not(target_arch = "wasm32"))]
+    photo_sender: mpsc::Sender<(IriIndex, Option<egui::ColorImage>)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    photo_receiver: mpsc::Receiver<(IriIndex, Option<egui::ColorImage>)>,
+}
+
+impl SocialChart {
+    pub fn new(vocabulary: SocialVocabulary) -> Self {
+        #[cfg(not(target_arch = "wasm32"))]
+        let (photo_sender, photo_receiver) = mpsc::channel();
+        Self {
+            vocabulary,
+            show_photos: true,
+            pinned_nodes: Vec::new(),
+            photos: HashMap::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_photos: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            photo_sender,
+            #[cfg(not(target_arch = "wasm32"))]
+            photo_receiver,
+        }
+    }
+
+    /// Creates the textures of the photos downloaded since the last frame
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn receive_photos(&mut self, ctx: &egui::Context) {
+        while let Ok((node_index, image)) = self.photo_receiver.try_recv() {
+            self.pending_photos = self.pending_photos.saturating_sub(1);
+            let photo = match image {
+                Some(image) => NodePhoto::Loaded(ctx.load_texture(
+                    format!("photo_{}", node_index),
+                    image,
+                    egui::TextureOptions::LINEAR,
+                )),
+                None => NodePhoto::Missing,
+            };
+            self.photos.insert(node_index, photo);
+        }
+    }
+
+    /// Texture of the photo of the node, the download is started on the first request
+    pub fn photo(
+        &mut self,
+        ctx: &egui::Context,
+        node_index: IriIndex,
+        node_data: &NodeData,
+        prefix_manager: &PrefixManager,
+    ) -> Option<TextureId> {
+        if !self.show_photos {
+            return None;
+        }
+        match self.photos.get(&node_index) {
+            Some(NodePhoto::Loaded(texture)) => return Some(texture.id()),
+            Some(NodePhoto::Loading) | Some(NodePhoto::Missing) => return None,
+            None => {}
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if self.pending_photos >= MAX_PHOTO_REQUESTS {
+                return None;
+            }
+            let url = node_data
+                .get_node_by_index(node_index)
+                .and_then(|(_, node)| self.vocabulary.photo_url(node_data, prefix_manager, node));
+            let Some(url) = url else {
+                self.photos.insert(node_index, NodePhoto::Missing);
+                return None;
+            };
+            self.photos.insert(node_index, NodePhoto::Loading);
+            self.pending_photos += 1;
+            let photo_sender = self.photo_sender.clone();
+            let ctx = ctx.clone();
+            std::thread::spawn(move || {
+                let image = match fetch_photo(&url) {
+                    Ok(image) => Some(image),
+                    Err(err) => {
+                        log::warn!("Can not load photo {}: {}", url, err);
+                        None
+                    }
+                };
+                // the receiver is dropped if the chart was closed meanwhile
+                let _ = photo_sender.send((node_index, image));
+                ctx.request_repaint();
+            });
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            // photos from other hosts are mostly blocked by CORS in the browser
+            let _ = (ctx, node_data, prefix_manager);
+            self.photos.insert(node_index, NodePhoto::Missing);
+        }
+        None
+    }
+}
+
+/// Downloads the photo and crops it to a square thumbnail
+#[cfg(not(target_arch = "wasm32"))]
+fn fetch_photo(url: &str) -> anyhow::Result<egui::ColorImage> {
+    let client = reqwest::blocking::Client::new();
+    let response = client
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "RDFGlance")
+        .send()?
+        .error_for_status()?;
+    let bytes = response.bytes()?;
+    let image = image::load_from_memory(&bytes)?;
+    let side = image.width().min(image.height());
+    let image = image
+        .crop_imm((image.width() - side) / 2, (image.height() - side) / 2, side, side)
+        .thumbnail(PHOTO_SIZE, PHOTO_SIZE)
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(size, image.as_flat_samples().as_slice()))
+}
+
+impl RdfGlanceApp {
+    pub fn apply_social_chart(&mut self, is_dark_mode: bool) {
+        let vocabulary = if let Ok(rdf_data) = self.rdf_data.read() {
+            SocialVocabulary::new(&rdf_data.node_data, &rdf_data.prefix_manager)
+        } else {
+            return;
+        };
+        if vocabulary.is_empty() {
+            self.system_message = SystemMessage::Info(
+                "No social or organizational data found. The chart uses FOAF (foaf:Person, foaf:knows) and ORG (org:Organization, org:reportsTo, org:memberOf)".to_string(),
+            );
+            return;
+        }
+        vocabulary.apply_styles(&mut self.visualization_style, is_dark_mode);
+        self.visible_nodes.update_node_shapes = true;
+        self.release_social_chart_pins();
+        let pinned_nodes = org_chart_layout(&mut self.visible_nodes, &vocabulary.hierarchy);
+        self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+        self.set_status_message(&format!(
+            "Social chart: {} nodes in reporting lines are pinned, other nodes use force layout",
+            pinned_nodes.len()
+        ));
+        let mut social_chart = SocialChart::new(vocabulary);
+        social_chart.pinned_nodes = pinned_nodes;
+        self.social_chart = Some(social_chart);
+    }
+
+    /// Unpins the nodes pinned by the org chart layout, the pins of the user stay
+    pub fn release_social_chart_pins(&mut self) {
+        let Some(social_chart) = &mut self.social_chart else {
+            return;
+        };
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            let LayoutData { nodes, positions, .. } = &mut *layout;
+            for node_index in social_chart.pinned_nodes.drain(..) {
+                if let Ok(pos) = nodes.binary_search_by(|node| node.node_index.cmp(&node_index)) {
+                    positions[pos].locked = false;
+                }
+            }
+        }
+    }
+}
@@ -1,12 +1,28 @@
+use std::thread::JoinHandle;
+
+use egui_extras::{Column, TableBuilder};
+
+use crate::{
+    DisplayType, IriIndex, RdfGlanceApp,
+    domain::{RdfData, data_change::DataChange, recent_items::RecentKind},
+    integration::sparql::{NodeQuery, SparqlAdapter, SparqlResult, SparqlTerm, merge_triples, query_form, run_query, run_update},
+};
+
 pub struct SparqlDialog {
     endpoint: String,
     current_combo: usize,
+    pub node_query: NodeQuery,
 }
 
 impl SparqlDialog {
     pub fn new(last_endpoints: &[String]) -> Self {
+        Self::with_node_query(last_endpoints, NodeQuery::default())
+    }
+
+    pub fn with_node_query(last_endpoints: &[String], node_query: NodeQuery) -> Self {
         Self {
             current_combo: 0,
+            node_query,
             endpoint: if !last_endpoints.is_empty() {
                 last_endpoints[0].clone()
             } else {
@@ -41,6 +57,10 @@ impl SparqlDialog {
                             }
                         });
                 }
+                ui.label("Resolve nodes with:");
+                for node_query in [NodeQuery::Construct, NodeQuery::Describe] {
+                    ui.radio_value(&mut self.node_query, node_query, node_query.label());
+                }
                 ui.horizontal(|ui| {
                     ui.add_enabled_ui(!self.endpoint.is_empty(), |ui| {
                         if ui.button("Connect").clicked() {
@@ -64,4 +84,443 @@ impl SparqlDialog {
             (close_dialog, None)
         }
     }
-}
\ No newline at end of file
+}
+const DEFAULT_QUERY: &str = "CONSTRUCT {
+  ?s ?p ?o
+}
+WHERE {
+  ?s ?p ?o
+}
+LIMIT 100";
+
+/**
+ * Editor for arbitrary SPARQL queries against an endpoint.
+ * SELECT results are shown as table, CONSTRUCT results can be merged into the loaded data.
+ */
+pub struct SparqlQueryEditor {
+    endpoint: String,
+    query: String,
+    result: Option<SparqlResult>,
+    error: Option<String>,
+    handle: Option<JoinHandle<anyhow::Result<SparqlResult>>>,
+}
+
+impl SparqlQueryEditor {
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+
+    pub fn set_query(&mut self, query: &str) {
+        self.query = query.to_string();
+        self.result = None;
+        self.error = None;
+    }
+}
+
+/// Review of the SPARQL update with the local changes before it is sent to the endpoint
+pub struct SparqlUpdatePush {
+    endpoint: String,
+    statements: Vec<String>,
+    // deletes of blank nodes are sent as DELETE WHERE patterns
+    blank_node_deletes: Vec<String>,
+    error: Option<String>,
+    handle: Option<JoinHandle<anyhow::Result<()>>>,
+}
+
+impl RdfGlanceApp {
+    /// Nodes are loaded from the endpoint when browsed or expanded
+    pub fn connect_sparql_endpoint(&mut self, endpoint: &str, node_query: NodeQuery) {
+        self.rdfwrap = Box::new(SparqlAdapter::new(endpoint, node_query));
+        if !endpoint.is_empty() {
+            self.persistent_data.recent_items.touch(RecentKind::Endpoint, endpoint).node_query = node_query;
+            self.set_status_message(&format!("Connected to {}, nodes are loaded when browsed", endpoint));
+        }
+    }
+
+    pub fn open_sparql_query(&mut self) {
+        if self.sparql_query.is_none() {
+            let endpoints = self.persistent_data.recent_items.locations(RecentKind::Endpoint);
+            self.sparql_query = Some(SparqlQueryEditor {
+                endpoint: endpoints.first().map(|endpoint| endpoint.to_string()).unwrap_or_default(),
+                query: DEFAULT_QUERY.to_string(),
+                result: None,
+                error: None,
+                handle: None,
+            });
+        }
+    }
+
+    pub fn show_sparql_query(&mut self, ui: &mut egui::Ui) {
+        let Some(editor) = &mut self.sparql_query else {
+            return;
+        };
+        if editor.handle.as_ref().is_some_and(|handle| handle.is_finished())
+            && let Some(handle) = editor.handle.take()
+        {
+            match handle.join() {
+                Ok(Ok(result)) => editor.result = Some(result),
+                Ok(Err(e)) => editor.error = Some(e.to_string()),
+                Err(_) => editor.error = Some("Query thread panicked".to_string()),
+            }
+        }
+        let endpoints = self.persistent_data.recent_items.locations(RecentKind::Endpoint);
+        let is_running = editor.handle.is_some();
+        let mut run = false;
+        let mut merge = false;
+        let mut visualize = false;
+        let mut close = false;
+        let mut browse_iri: Option<String> = None;
+        egui::Window::new("SPARQL Query")
+            .collapsible(false)
+            .resizable(true)
+            .default_size([700.0, 500.0])
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Endpoint:");
+                    ui.add(egui::TextEdit::singleline(&mut editor.endpoint).desired_width(450.0));
+                    if !endpoints.is_empty() {
+                        ui.menu_button("Recent", |ui| {
+                            for endpoint in endpoints.iter() {
+                                if ui.button(endpoint.as_ref()).clicked() {
+                                    editor.endpoint = endpoint.to_string();
+                                    ui.close_kind(egui::UiKind::Menu);
+                                }
+                            }
+                        });
+                    }
+                });
+                egui::ScrollArea::vertical().id_salt("sparql_query_text").max_height(200.0).show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut editor.query)
+                            .code_editor()
+                            .desired_rows(8)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !is_running && !editor.endpoint.is_empty() && query_form(&editor.query).is_some(),
+                            egui::Button::new("Run"),
+                        )
+                        .on_disabled_hover_text("Endpoint and SELECT, ASK, CONSTRUCT or DESCRIBE query needed")
+                        .clicked()
+                        || (!is_running
+                            && !editor.endpoint.is_empty()
+                            && ui.input(|i| i.modifiers.command && i.key_pressed(egui::Key::Enter)))
+                    {
+                        run = true;
+                    }
+                    if is_running {
+                        ui.spinner();
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+                if let Some(error) = &editor.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                ui.separator();
+                match &editor.result {
+                    Some(SparqlResult::Table { variables, rows }) => {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{} rows", rows.len()));
+                            if ui
+                                .button("Show IRIs in Graph")
+                                .on_hover_text("Visual graph with all result IRIs known in the loaded data")
+                                .clicked()
+                            {
+                                visualize = true;
+                            }
+                        });
+                        show_result_table(ui, variables, rows, &mut browse_iri);
+                    }
+                    Some(SparqlResult::Boolean(value)) => {
+                        ui.label(format!("Result: {}", value));
+                    }
+                    Some(SparqlResult::Graph { triples, .. }) => {
+                        ui.horizontal(|ui| {
+                            ui.label(format!("{} triples", triples.len()));
+                            if ui
+                                .add_enabled(
+                                    !triples.is_empty() && !self.ui_state.read_only,
+                                    egui::Button::new("Merge into Data"),
+                                )
+                                .clicked()
+                            {
+                                merge = true;
+                            }
+                        });
+                        let rows: Vec<Vec<Option<SparqlTerm>>> = triples
+                            .iter()
+                            .map(|triple| {
+                                vec![
+                                    Some(SparqlTerm::Literal(triple.subject.to_string())),
+                                    Some(SparqlTerm::Literal(triple.predicate.to_string())),
+                                    Some(SparqlTerm::Literal(triple.object.to_string())),
+                                ]
+                            })
+                            .collect();
+                        let variables = ["subject", "predicate", "object"].map(str::to_string);
+                        show_result_table(ui, &variables, &rows, &mut None);
+                    }
+                    None => {}
+                }
+            });
+        if run {
+            editor.result = None;
+            editor.error = None;
+            let endpoint = editor.endpoint.trim().to_string();
+            let query = editor.query.clone();
+            self.persistent_data.recent_items.touch(RecentKind::Endpoint, &endpoint);
+            self.session_statistics.record_query();
+            editor.handle = Some(std::thread::spawn(move || run_query(&endpoint, &query)));
+        }
+        let merge_report = if merge
+            && let Some(SparqlResult::Graph { triples, prefixes }) = &editor.result
+            && let Ok(mut rdf_data) = self.rdf_data.write()
+        {
+            let language_filter = self.config.language_filter();
+            let source = format!("SPARQL {}", editor.endpoint);
+            let node_count = rdf_data.node_data.len();
+            let mut applied = Vec::new();
+            let report = merge_triples(&source, triples, prefixes, &mut rdf_data, &language_filter, &mut applied);
+            self.data_history
+                .record_triples(&format!("Merge {}", source), node_count, applied, Vec::new(), false, &self.config);
+            Some(report)
+        } else {
+            None
+        };
+        let visualize_nodes = if visualize && let Some(SparqlResult::Table { rows, .. }) = &editor.result {
+            if let Ok(rdf_data) = self.rdf_data.read() {
+                let mut nodes: Vec<IriIndex> = rows
+                    .iter()
+                    .flatten()
+                    .filter_map(|term| match term {
+                        Some(SparqlTerm::Iri(iri)) => find_node(iri, &rdf_data),
+                        _ => None,
+                    })
+                    .collect();
+                nodes.sort_unstable();
+                nodes.dedup();
+                Some(nodes)
+            } else {
+                None
+            }
+        } else {
+            None
+        };
+        if let Some(report) = merge_report {
+            self.set_status_message(&format!(
+                "Merged {} triples, {} duplicates",
+                report.triples_merged, report.duplicates_skipped
+            ));
+            self.import_log.add(report);
+            self.notify_data_change(DataChange::TriplesMerged);
+        }
+        if let Some(nodes) = visualize_nodes {
+            if nodes.is_empty() {
+                self.set_status_message("No result IRI found in the loaded data");
+            } else {
+                self.set_status_message(&format!("Visualize {} nodes", nodes.len()));
+                self.visualize_nodes(&nodes);
+            }
+        }
+        if let Some(iri) = browse_iri {
+            let node_index = if let Ok(rdf_data) = self.rdf_data.read() {
+                find_node(&iri, &rdf_data)
+            } else {
+                None
+            };
+            match node_index {
+                Some(node_index) => {
+                    self.display_type = DisplayType::Browse;
+                    self.show_object_by_index(node_index, true);
+                }
+                None => self.set_status_message(&format!("{} is not in the loaded data", iri)),
+            }
+        }
+        if close {
+            self.sparql_query = None;
+        }
+    }
+
+    /// Opens the review of the SPARQL update with the edited triples of the change set
+    pub fn open_sparql_update_push(&mut self) {
+        let (statements, blank_node_deletes) = self
+            .read_rdf_data(|rdf_data| {
+                let change_set = &rdf_data.node_data.change_set;
+                let blank_node_deletes =
+                    change_set.blank_node_deletes().iter().map(|triple| triple.to_string()).collect();
+                (change_set.sparql_update_statements(), blank_node_deletes)
+            })
+            .unwrap_or_default();
+        if statements.is_empty() {
+            self.set_status_message("No changes to push");
+            return;
+        }
+        let endpoints = self.persistent_data.recent_items.locations(RecentKind::Endpoint);
+        self.sparql_update_push = Some(SparqlUpdatePush {
+            endpoint: endpoints.first().map(|endpoint| endpoint.to_string()).unwrap_or_default(),
+            statements,
+            blank_node_deletes,
+            error: None,
+            handle: None,
+        });
+    }
+
+    pub fn show_sparql_update_push(&mut self, ui: &mut egui::Ui) {
+        let Some(push) = &mut self.sparql_update_push else {
+            return;
+        };
+        let mut pushed = false;
+        if push.handle.as_ref().is_some_and(|handle| handle.is_finished())
+            && let Some(handle) = push.handle.take()
+        {
+            match handle.join() {
+                Ok(Ok(())) => pushed = true,
+                Ok(Err(e)) => push.error = Some(e.to_string()),
+                Err(_) => push.error = Some("Update thread panicked".to_string()),
+            }
+        }
+        let endpoints = self.persistent_data.recent_items.locations(RecentKind::Endpoint);
+        let is_running = push.handle.is_some();
+        let mut run = false;
+        let mut close = false;
+        egui::Window::new("Push Changes to SPARQL Endpoint")
+            .collapsible(false)
+            .resizable(true)
+            .default_size([700.0, 400.0])
+            .show(ui.ctx(), |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Update endpoint:");
+                    ui.add(egui::TextEdit::singleline(&mut push.endpoint).desired_width(450.0));
+                    if !endpoints.is_empty() {
+                        ui.menu_button("Recent", |ui| {
+                            for endpoint in endpoints.iter() {
+                                if ui.button(endpoint.as_ref()).clicked() {
+                                    push.endpoint = endpoint.to_string();
+                                    ui.close_kind(egui::UiKind::Menu);
+                                }
+                            }
+                        });
+                    }
+                });
+                if !push.blank_node_deletes.is_empty() {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        "Blank nodes can not be deleted by DELETE DATA, these triples are deleted by DELETE WHERE \
+                         and match all nodes of the endpoint with the same triples:",
+                    );
+                    egui::ScrollArea::vertical().id_salt("blank_node_deletes").max_height(100.0).show(ui, |ui| {
+                        for triple in push.blank_node_deletes.iter() {
+                            ui.add(egui::Label::new(egui::RichText::new(triple).monospace()).selectable(true));
+                        }
+                    });
+                    ui.separator();
+                }
+                ui.label("The statements are sent as one update request:");
+                egui::ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for statement in push.statements.iter() {
+                        ui.separator();
+                        ui.add(egui::Label::new(egui::RichText::new(statement.trim_end()).monospace()).selectable(true));
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!is_running && !push.endpoint.trim().is_empty(), egui::Button::new("Push"))
+                        .clicked()
+                    {
+                        run = true;
+                    }
+                    if is_running {
+                        ui.spinner();
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+                if let Some(error) = &push.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+        if run {
+            push.error = None;
+            let endpoint = push.endpoint.trim().to_string();
+            let update = push.statements.join(";\n");
+            self.persistent_data.recent_items.touch(RecentKind::Endpoint, &endpoint);
+            push.handle = Some(std::thread::spawn(move || run_update(&endpoint, &update)));
+        }
+        if pushed {
+            let update = push.statements.join(";\n");
+            let endpoint = push.endpoint.trim().to_string();
+            // changes made while the update was running stay in the change set
+            self.mut_rdf_data(|rdf_data| {
+                if rdf_data.node_data.change_set.to_sparql_update() == update {
+                    rdf_data.node_data.change_set.clear();
+                }
+            });
+            self.set_status_message(&format!("Changes pushed to {}", endpoint));
+            close = true;
+        }
+        if close {
+            self.sparql_update_push = None;
+        }
+    }
+}
+
+// nodes are stored with prefixed IRIs if the prefix is known
+fn find_node(iri: &str, rdf_data: &RdfData) -> Option<IriIndex> {
+    rdf_data
+        .node_data
+        .get_node_index(iri)
+        .or_else(|| rdf_data.node_data.get_node_index(&rdf_data.prefix_manager.get_prefixed(iri)))
+}
+
+fn show_result_table(
+    ui: &mut egui::Ui,
+    variables: &[String],
+    rows: &[Vec<Option<SparqlTerm>>],
+    browse_iri: &mut Option<String>,
+) {
+    let text_height = egui::TextStyle::Body
+        .resolve(ui.style())
+        .size
+        .max(ui.spacing().interact_size.y);
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        .columns(Column::initial(200.0).at_least(40.0).clip(true), variables.len())
+        .max_scroll_height(f32::INFINITY)
+        .header(20.0, |mut header| {
+            for variable in variables {
+                header.col(|ui| {
+                    ui.strong(variable);
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(text_height, rows.len(), |mut row| {
+                for term in rows[row.index()].iter() {
+                    row.col(|ui| match term {
+                        Some(SparqlTerm::Iri(iri)) => {
+                            let response = ui.link(iri).on_hover_text("Browse node");
+                            if response.clicked() {
+                                *browse_iri = Some(iri.clone());
+                            }
+                        }
+                        Some(SparqlTerm::Literal(value)) => {
+                            ui.label(value);
+                        }
+                        Some(SparqlTerm::BlankNode(id)) => {
+                            ui.label(format!("_:{}", id));
+                        }
+                        None => {}
+                    });
+                }
+            });
+        });
+}
@@ -1,15 +1,17 @@
-use std::{borrow::Cow, cmp::min, io};
+use std::{borrow::Cow, cmp::min, collections::BTreeSet, io, sync::atomic::Ordering, time::Duration};
 
 use const_format::concatcp;
 use egui::{Color32, CursorIcon, Key, Pos2, Rect, Sense, Stroke, Vec2};
 use egui_extras::StripBuilder;
+use strum::IntoEnumIterator;
 
 use crate::{
-    RdfGlanceApp, domain::{LabelContext, LangIndex, RdfData, config::{Config, IriDisplay}, graph_styles::GVisualizationStyle, statistics::StatisticsData, type_index::ValueStatistics
+    RdfGlanceApp, domain::{LabelContext, LangIndex, RdfData, config::{Config, IriDisplay}, graph_styles::GVisualizationStyle, metric_correlation::{numeric_properties_of, property_number}, statistics::StatisticsData, type_index::ValueStatistics
     }, support::uitools::{ScrollBar, primary_color}, ui::{
+        property_distribution::StatisticsPanel,
         style::{ICON_CLOSE, ICON_EXPORT}, 
         table_view::{text_wrapped, text_wrapped_link}
-    }, uistate::{UIState, actions::NodeAction}
+    }, graph_algorithms::{GraphAlgorithm, StatisticValue, weights::{EdgeWeight, WeightedEdge, weighted_edges}}, uistate::{SystemMessage, UIState, actions::NodeAction, layout::{IndividualNodeStyleData, LayoutData, node_pos}, statistics_job::BetweennessJob}
 };
 
 const ROW_HIGHT: f32 = 17.0;
@@ -25,6 +27,98 @@ enum StatisticsTableAction {
 
 impl RdfGlanceApp {
     pub fn show_statistics(&mut self, ui: &mut egui::Ui) -> NodeAction {
+        ui.horizontal(|ui| {
+            ui.selectable_value(&mut self.statistics_panel, StatisticsPanel::GraphStatistics, "Graph Statistics");
+            ui.selectable_value(
+                &mut self.statistics_panel,
+                StatisticsPanel::PropertyDistribution,
+                "Property Distribution",
+            )
+            .on_hover_text("Value distribution of a property for the instances of the table filter");
+        });
+        if self.statistics_panel == StatisticsPanel::PropertyDistribution {
+            self.show_property_distribution(ui);
+            return NodeAction::None;
+        }
+        ui.horizontal(|ui| {
+            let config_data = &mut self.persistent_data.config_data;
+            ui.label("Betweenness Centrality:");
+            let mut changed = ui
+                .checkbox(&mut config_data.betweenness_approximate, "Approximate")
+                .on_hover_text("Use only random source nodes, the exact computation is slow for big graphs")
+                .changed();
+            ui.add_enabled_ui(config_data.betweenness_approximate, |ui| {
+                ui.label("Sample size:");
+                changed |= ui
+                    .add(egui::DragValue::new(&mut config_data.betweenness_samples).range(10..=100_000))
+                    .changed();
+            });
+            if changed {
+                self.update_config();
+            }
+            let has_nodes = self.visible_nodes.layout_data.read().is_ok_and(|layout| !layout.nodes.is_empty());
+            if ui
+                .add_enabled(has_nodes && self.betweenness_job.is_none(), egui::Button::new("Compute"))
+                .on_hover_text("Compute for the nodes of visual graph")
+                .clicked()
+            {
+                self.start_betweenness_job();
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Edge weight for PageRank, Closeness and Louvain:");
+            let edge_weight = self.ui_state.statistics_edge_weight;
+            let mut new_edge_weight = edge_weight;
+            if let Ok(rdf_data) = self.rdf_data.read() {
+                let label_context = LabelContext::new(
+                    self.ui_state.display_language,
+                    self.config.iri_display,
+                    &rdf_data.prefix_manager,
+                );
+                let weight_label = |edge_weight: EdgeWeight| match edge_weight {
+                    EdgeWeight::Unweighted => "Unweighted".to_string(),
+                    EdgeWeight::Multiplicity => "Number of parallel edges".to_string(),
+                    EdgeWeight::DataProperty(predicate_index) => format!(
+                        "Target node {}",
+                        rdf_data
+                            .node_data
+                            .predicate_display(predicate_index, &label_context, &rdf_data.node_data.indexers)
+                            .as_str()
+                    ),
+                };
+                egui::ComboBox::from_id_salt("statistics_edge_weight")
+                    .selected_text(weight_label(edge_weight))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut new_edge_weight, EdgeWeight::Unweighted, weight_label(EdgeWeight::Unweighted));
+                        ui.selectable_value(
+                            &mut new_edge_weight,
+                            EdgeWeight::Multiplicity,
+                            weight_label(EdgeWeight::Multiplicity),
+                        );
+                        // only computed if the combo is open
+                        let numeric_properties = match self.visible_nodes.layout_data.read() {
+                            Ok(layout) => numeric_properties_of(layout.nodes.iter().map(|node| node.node_index), &rdf_data.node_data),
+                            Err(_) => Vec::new(),
+                        };
+                        for predicate_index in numeric_properties {
+                            let property_weight = EdgeWeight::DataProperty(predicate_index);
+                            ui.selectable_value(&mut new_edge_weight, property_weight, weight_label(property_weight));
+                        }
+                    })
+                    .response
+                    .on_hover_text("A numeric data property of the edge target node is used as weight (1 if not set)");
+            }
+            if new_edge_weight != edge_weight {
+                self.ui_state.statistics_edge_weight = new_edge_weight;
+                // the results of weighted algorithms are computed again
+                if let Some(statistics_data) = &mut self.statistics_data {
+                    statistics_data.results.retain(|result| {
+                        !GraphAlgorithm::iter()
+                            .any(|algorithm| algorithm.is_weighted() && algorithm.get_statistics_values().contains(&result.statistics_value()))
+                    });
+                }
+            }
+        });
         if self.statistics_data.is_some() {
             ui.horizontal(|ui| {
                 ui.label("Statistics Data Available");
@@ -44,7 +138,7 @@ impl RdfGlanceApp {
                             let store_res = self.statistics_data.as_ref().unwrap().export_csv_writer(
                                 &rdf_data,
                                 &mut wtr,
-                                self.persistent_data.config_data.iri_display,
+                                self.config.iri_display,
                                 &self.visualization_style,
                                 self.ui_state.display_language,
                             );
@@ -62,7 +156,7 @@ impl RdfGlanceApp {
                             let mut wtr = csv::Writer::from_writer(buf);
                             let _ = self.statistics_data.as_ref().unwrap().export_csv_writer(&rdf_data,
                                 &mut wtr,
-                                self.persistent_data.config_data.iri_display,
+                                self.config.iri_display,
                                 &self.visualization_style,
                                 self.ui_state.display_language);
                             let buf = wtr.into_inner().unwrap();
@@ -78,6 +172,192 @@ impl RdfGlanceApp {
         }
     }
 
+    /// Edges of visual graph with the weights chosen in the statistics panel
+    pub fn statistics_weighted_edges(&self) -> Vec<WeightedEdge> {
+        let edge_weight = self.ui_state.statistics_edge_weight;
+        let node_values: Vec<Option<f32>> = match edge_weight {
+            EdgeWeight::DataProperty(predicate_index) => {
+                match self.visible_nodes.read_with_data(&self.rdf_data) {
+                    Some((rdf_data, layout)) => layout
+                        .nodes
+                        .iter()
+                        .map(|node| {
+                            property_number(&rdf_data.node_data, node.node_index, predicate_index)
+                                .map(|value| value as f32)
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                }
+            }
+            _ => Vec::new(),
+        };
+        match self.visible_nodes.layout_data.read() {
+            Ok(layout) => weighted_edges(&layout.edges, &self.ui_state.hidden_predicates, edge_weight, |pos| {
+                node_values.get(pos).copied().flatten()
+            }),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Clustering results that can be applied to the visual graph
+    pub fn cluster_results(&self) -> Vec<StatisticValue> {
+        self.statistics_data.as_ref().map_or(Vec::new(), |statistics_data| {
+            statistics_data
+                .results
+                .iter()
+                .map(|result| result.statistics_value())
+                .filter(|value| value.is_clustering())
+                .collect()
+        })
+    }
+
+    /**
+     * Colors the nodes of visual graph by the clusters of the result.
+     * If layout is grouped by cluster the layout is restarted, so the clusters are moved together.
+     */
+    pub fn apply_clusters(&mut self, statistic_value: StatisticValue) {
+        let Some(statistics_data) = &self.statistics_data else {
+            return;
+        };
+        let Some(result) = statistics_data
+            .results
+            .iter()
+            .find(|result| result.statistics_value() == statistic_value)
+        else {
+            return;
+        };
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            let LayoutData {
+                nodes,
+                individual_node_styles,
+                ..
+            } = &mut *layout;
+            if individual_node_styles.len() != nodes.len() {
+                individual_node_styles.resize(nodes.len(), IndividualNodeStyleData::default());
+            }
+            for node_style in individual_node_styles.iter_mut() {
+                node_style.color_overwrite = 0;
+            }
+            // the visual graph could be changed since the computation, so the nodes are found by iri
+            for ((iri_index, _), value) in statistics_data.nodes.iter().zip(result.get_data_vec()) {
+                if let Some(pos) = node_pos(nodes, *iri_index) {
+                    individual_node_styles[pos].set_cluster(*value as u32);
+                }
+            }
+        }
+        self.visualization_style.use_color_overwrite = true;
+        self.visible_nodes.update_node_shapes = true;
+        if self.visible_nodes.group_by_cluster {
+            self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+        }
+    }
+
+    /// Collapses every cluster of the visual graph into a meta-node
+    pub fn collapse_all_clusters(&mut self) {
+        let clusters: Vec<u16> = match self.visible_nodes.layout_data.read() {
+            Ok(layout) => layout
+                .individual_node_styles
+                .iter()
+                .map(|node_style| node_style.color_overwrite)
+                .filter(|cluster| *cluster > 0)
+                .collect::<BTreeSet<u16>>()
+                .into_iter()
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        if self.visible_nodes.collapse_clusters(&clusters, &self.ui_state.hidden_predicates) > 0 {
+            self.ui_state
+                .selected_nodes
+                .retain(|node_index| !self.visible_nodes.collapsed_clusters.is_collapsed(*node_index));
+            if self.ui_state.selected_node.is_some_and(|selected| !self.ui_state.selected_nodes.contains(&selected)) {
+                self.ui_state.selected_node = None;
+            }
+            self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+        }
+    }
+
+    pub fn expand_all_clusters(&mut self) {
+        if let Ok(rdf_data) = self.rdf_data.read()
+            && self
+                .visible_nodes
+                .expand_all_clusters(&rdf_data.node_data, &self.ui_state.hidden_predicates)
+        {
+            self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+        }
+    }
+
+    /// Starts betweenness centrality for visual graph in background, approximated if configured
+    pub fn start_betweenness_job(&mut self) {
+        if self.betweenness_job.is_some() {
+            return;
+        }
+        let sample_size = self
+            .config
+            .betweenness_approximate
+            .then_some(self.config.betweenness_samples);
+        self.betweenness_job = Some(BetweennessJob::start(
+            &self.visible_nodes,
+            &self.ui_state.hidden_predicates,
+            sample_size,
+        ));
+    }
+
+    /// Progress window of the betweenness computation, applies the result when finished
+    pub fn show_betweenness_job(&mut self, ui: &mut egui::Ui) {
+        let Some(job) = &self.betweenness_job else {
+            return;
+        };
+        if job.is_finished() {
+            let job = self.betweenness_job.take().unwrap();
+            let data_epoch = job.data_epoch;
+            match job.join() {
+                Some(values) if data_epoch == self.visible_nodes.data_epoch => {
+                    if self.statistics_data.is_none() {
+                        self.statistics_data = Some(StatisticsData::default());
+                    }
+                    self.visible_nodes.run_algorithm(
+                        GraphAlgorithm::BetweennessCentrality,
+                        &self.visualization_style,
+                        self.statistics_data.as_mut().unwrap(),
+                        &self.config,
+                        &self.ui_state.hidden_predicates,
+                        Some(values),
+                        &[],
+                    );
+                    self.visualization_style.use_size_overwrite = true;
+                    self.visualization_style.use_color_overwrite = true;
+                }
+                Some(_) => {
+                    self.system_message = SystemMessage::Info(
+                        "Visual graph has changed during computation, betweenness centrality is not applied".to_string(),
+                    );
+                }
+                // cancelled
+                None => {}
+            }
+            return;
+        }
+        let mut cancel = false;
+        egui::Window::new("Betweenness Centrality")
+            .collapsible(false)
+            .resizable(false)
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "Processed {} of {} source nodes",
+                    job.progress.load(Ordering::Relaxed),
+                    job.sources
+                ));
+                ui.add(egui::ProgressBar::new(job.progress()).desired_width(300.0).show_percentage());
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+        if cancel {
+            job.cancel.store(true, Ordering::Relaxed);
+        }
+        ui.ctx().request_repaint_after(Duration::from_millis(100));
+    }
+
     pub fn show_statistics_data(&mut self, ui: &mut egui::Ui) -> NodeAction {
         let mut instance_action = NodeAction::None;
         if let Some(statistics_data) = self.statistics_data.as_mut() {
@@ -94,9 +374,9 @@ impl RdfGlanceApp {
                                 &mut rdf_data,
                                 &mut instance_action,
                                 &self.ui_state,
-                                self.persistent_data.config_data.iri_display,
+                                self.config.iri_display,
                                 &self.visualization_style,
-                                &self.persistent_data.config_data,
+                                &self.config,
                             );
                         }
                     });
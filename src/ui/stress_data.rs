@@ -0,0 +1,149 @@
+use egui::{DragValue, Grid};
+
+use crate::{
+    RdfGlanceApp,
+    integration::stress_data::{DegreeDistribution, StressDataConfig},
+    uistate::SystemMessage,
+};
+
+impl RdfGlanceApp {
+    pub fn show_stress_data_dialog(&mut self, ui: &mut egui::Ui) {
+        let Some(stress_config) = &mut self.stress_data_config else {
+            return;
+        };
+        let mut close = false;
+        let mut generate = false;
+        let mut save = false;
+        egui::Window::new("Generate Stress Test Data")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                Grid::new("stress_data_grid").num_columns(2).show(ui, |ui| {
+                    ui.label("Nodes");
+                    ui.add(DragValue::new(&mut stress_config.node_count).range(1..=10_000_000).speed(100));
+                    ui.end_row();
+                    ui.label("Types");
+                    ui.add(DragValue::new(&mut stress_config.type_count).range(1..=1000));
+                    ui.end_row();
+                    ui.label("Reference predicates");
+                    ui.add(DragValue::new(&mut stress_config.predicate_count).range(1..=1000));
+                    ui.end_row();
+                    ui.label("Average degree");
+                    ui.add(DragValue::new(&mut stress_config.average_degree).range(0..=100));
+                    ui.end_row();
+                    ui.label("Degree distribution");
+                    egui::ComboBox::from_id_salt("stress_data_distribution")
+                        .selected_text(format!("{:?}", stress_config.distribution))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut stress_config.distribution, DegreeDistribution::PowerLaw, "PowerLaw");
+                            ui.selectable_value(&mut stress_config.distribution, DegreeDistribution::Uniform, "Uniform");
+                        });
+                    ui.end_row();
+                    ui.label("Literals per node");
+                    ui.add(DragValue::new(&mut stress_config.literal_count).range(0..=100));
+                    ui.end_row();
+                    ui.label("Literal size");
+                    ui.add(DragValue::new(&mut stress_config.literal_size).range(0..=100_000));
+                    ui.end_row();
+                    ui.label("Random seed");
+                    ui.add(DragValue::new(&mut stress_config.seed));
+                    ui.end_row();
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Generate and Load").clicked() {
+                        generate = true;
+                    }
+                    if ui.button("Save as File").clicked() {
+                        save = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        let stress_config = stress_config.clone();
+        if generate {
+            self.load_stress_data(&stress_config, ui.visuals().dark_mode);
+            close = true;
+        }
+        if save {
+            self.save_stress_data(&stress_config);
+        }
+        if close {
+            self.stress_data_config = None;
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_stress_data(&mut self, stress_config: &StressDataConfig, is_dark_mode: bool) {
+        // written to a temporary file so the usual background loading is used
+        let path = std::env::temp_dir().join("rdfglance_stress_data.ttl");
+        let result = std::fs::File::create(&path).map_err(anyhow::Error::from).and_then(|file| {
+            let mut writer = std::io::BufWriter::new(file);
+            stress_config.write_turtle(&mut writer)
+        });
+        match result {
+            Ok(_) => self.load_ttl(&path.to_string_lossy(), is_dark_mode),
+            Err(e) => {
+                self.system_message = SystemMessage::Error(format!("Can not generate stress data: {}", e));
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_stress_data(&mut self, stress_config: &StressDataConfig, is_dark_mode: bool) {
+        use crate::integration::rdfwrap::RDFWrap;
+
+        let mut data: Vec<u8> = Vec::new();
+        if let Err(e) = stress_config.write_turtle(&mut data) {
+            self.system_message = SystemMessage::Error(format!("Can not generate stress data: {}", e));
+            return;
+        }
+        self.record_data_change("Load stress data");
+        let language_filter = self.config.language_filter();
+        let result = if let Ok(mut rdf_data) = self.rdf_data.write() {
+            Some(RDFWrap::load_file_data("stress_data.ttl", &data, &mut *rdf_data, &language_filter, None))
+        } else {
+            None
+        };
+        match result {
+            Some(Ok(triples_count)) => {
+                self.set_status_message(&format!("Generated stress data: {} triples", triples_count));
+                self.update_data_indexes(is_dark_mode);
+            }
+            Some(Err(e)) => {
+                self.system_message = SystemMessage::Error(format!("Can not load stress data: {}", e));
+            }
+            None => {}
+        }
+    }
+
+    fn save_stress_data(&mut self, stress_config: &StressDataConfig) {
+        let mut data: Vec<u8> = Vec::new();
+        if let Err(e) = stress_config.write_turtle(&mut data) {
+            self.system_message = SystemMessage::Error(format!("Can not generate stress data: {}", e));
+            return;
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter("Turtle", &["ttl"])
+                .set_file_name("stress_data.ttl")
+                .save_file()
+                && let Err(e) = std::fs::write(&path, data)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save stress data: {}", e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = web_download("stress_data.ttl", &data);
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub const ICON_CONFIG: &str = "\u{26ed}";
 pub const ICON_OPEN_FOLDER: &str = "\u{1f5c1}";
 pub const ICON_LANG: &str = "\u{fe4e8}";
 pub const ICON_DELETE: &str = "\u{1f5d1}";
+pub const ICON_PIN: &str = "\u{1f4cc}";
 pub const ICON_CLOSE: &str = "\u{1f5d9}";
 pub const ICON_FILTER: &str = "\u{1f50d}";
 pub const ICON_WRENCH: &str = "\u{1f527}";
@@ -33,3 +34,6 @@ pub const ICON_QUERY_BUILDER: &str = "\u{1f453}";
 pub const ICON_RUN: &str = "\u{1f3c3}";
 pub const ICON_LINK: &str = "\u{1f517}";
 pub const ICON_REV_LINK: &str = "\u{2baa}\u{1f517}";
+pub const ICON_BOOKMARK: &str = "\u{1f516}";
+pub const ICON_MAP: &str = "\u{1f30d}";
+pub const ICON_TIMELINE: &str = "\u{1f4c5}";
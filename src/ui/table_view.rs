@@ -1,15 +1,16 @@
+use std::borrow::Cow;
 use std::io;
 use std::cmp::min;
 
 use const_format::concatcp;
-use egui::{Align, Align2, Color32, CursorIcon, Key, Layout, Popup, Pos2, Rect, Sense, Slider, Stroke, Vec2};
+use egui::{Align, Align2, Color32, CursorIcon, Key, Layout, Popup, Pos2, Rect, Sense, Slider, Stroke, UiKind, Vec2};
 use egui_extras::{Column, StripBuilder, TableBuilder};
 use rayon::prelude::*;
 
 const IMMADIATE_FILTER_COUNT: usize = 20000;
 
 use super::style::ICON_EXPORT;
-use crate::domain::type_index::{ColumnDesc, InstanceColumnResize, TableContextMenu, TypeCellAction, TypeData, TypeInstanceIndex};
+use crate::domain::type_index::{ColumnAbbreviation, ColumnDesc, DEFAULT_TRUNCATE_CHARS, InstanceColumnResize, TableContextMenu, TypeCellAction, TypeData, TypeInstanceIndex};
 use crate::{
     uistate::actions::ReferenceAction,
     uistate::ref_selection::RefSelection,
@@ -18,9 +19,12 @@ use crate::{
     domain::RdfData, 
     uistate::UIState,
     ui::browse_view::{show_references},
+    ui::named_graphs::show_graph_filter,
     domain::config::IriDisplay,
     IriIndex,
-    domain::{LabelContext, LangIndex, NodeData},
+    domain::{LabelContext, LangIndex, NObject, NodeData},
+    domain::named_graphs::GraphFilter,
+    domain::column_chart::{ColumnChart, MAX_CATEGORIES},
     domain::prefix_manager::PrefixManager,
     ui::style::{ICON_CLOSE, ICON_FILTER, ICON_GRAPH},
     support::uitools::{ScrollBar, popup_at, primary_color, strong_unselectable},
@@ -459,9 +463,33 @@ impl TypeData {
                     .filter(|p| p.visible)
                     .skip(self.instance_view.column_pos as usize)
                 {
-                    let property = node.get_property_count(column_desc.predicate_index, layout_data.display_language);
-                    if let Some((property, count)) = property {
-                        let value = property.as_str_ref(&node_data.indexers);
+                    let cell_value = if column_desc.all_languages {
+                        all_languages_value(node, column_desc, node_data)
+                    } else if let Some(table_language) = layout_data.table_language {
+                        node.get_property_language(
+                            column_desc.predicate_index,
+                            table_language,
+                            &node_data.indexers.language_fallback,
+                        )
+                        .map(|(property, count, fallback)| {
+                            let value = column_desc.abbreviation.apply(property.as_str_ref(&node_data.indexers));
+                            // value in another language is marked with its language tag
+                            match fallback.and_then(|fallback| node_data.get_language(fallback)) {
+                                Some(fallback) => (Cow::Owned(format!("[{}] {}", fallback, value)), count),
+                                None => (value, count),
+                            }
+                        })
+                    } else {
+                        node.get_property_count(
+                            column_desc.predicate_index,
+                            layout_data.display_language,
+                            &node_data.indexers.language_fallback,
+                        )
+                        .map(|(property, count)| {
+                            (column_desc.abbreviation.apply(property.as_str_ref(&node_data.indexers)), count)
+                        })
+                    };
+                    if let Some((value, count)) = cell_value {
                         let cell_rect = egui::Rect::from_min_size(
                             available_rect.left_top() + Vec2::new(xpos, ypos),
                             Vec2::new(column_desc.width, ROW_HIGHT),
@@ -474,7 +502,7 @@ impl TypeData {
                             painter.rect_filled(cell_rect, 0.0, ui.visuals().code_bg_color);
                         }
                         text_wrapped(
-                            value,
+                            &value,
                             column_desc.width,
                             painter,
                             cell_rect.left_top(),
@@ -652,6 +680,36 @@ impl TypeData {
                         *table_action = TableAction::ValueStatistics(column_predicate, self.instance_view.context_menu.pos());
                         close_menu = true;
                     }
+                    if ui.button("Chart this column").clicked() {
+                        *table_action = TableAction::ChartColumn(column_predicate);
+                        close_menu = true;
+                    }
+                    if let Some(column_desc) = self
+                        .instance_view
+                        .display_properties
+                        .iter_mut()
+                        .find(|column_desc| column_desc.predicate_index == column_predicate)
+                    {
+                        ui.separator();
+                        ui.label("Long values");
+                        let abbreviation = &mut column_desc.abbreviation;
+                        ui.radio_value(abbreviation, ColumnAbbreviation::None, "Full value");
+                        let truncate_chars = match abbreviation {
+                            ColumnAbbreviation::Truncate(max_chars) => *max_chars,
+                            _ => DEFAULT_TRUNCATE_CHARS,
+                        };
+                        ui.horizontal(|ui| {
+                            ui.radio_value(abbreviation, ColumnAbbreviation::Truncate(truncate_chars), "Truncate at");
+                            if let ColumnAbbreviation::Truncate(max_chars) = abbreviation {
+                                ui.add(egui::DragValue::new(max_chars).range(1..=10000).suffix(" chars"));
+                            }
+                        });
+                        ui.radio_value(abbreviation, ColumnAbbreviation::FirstLine, "First line only");
+                        ui.radio_value(abbreviation, ColumnAbbreviation::Length, "Value length");
+                        ui.separator();
+                        ui.checkbox(&mut column_desc.all_languages, "All languages")
+                            .on_hover_text("Show the values of all languages side by side");
+                    }
                     let hidden_columns: Vec<&ColumnDesc> = self
                         .instance_view
                         .display_properties
@@ -736,6 +794,7 @@ impl TypeData {
                             "ref",
                             &label_context,
                             self.instance_view.ref_selection.ref_index(false),
+                            None,
                         );
                         if ref_result != ReferenceAction::None {
                             node_to_click = ref_result;
@@ -753,6 +812,7 @@ impl TypeData {
                                 "ref_by",
                                 &label_context,
                                 self.instance_view.ref_selection.ref_index(true),
+                                None,
                             );
                             if ref_result != ReferenceAction::None {
                                 node_to_click = ref_result;
@@ -863,6 +923,31 @@ impl TypeData {
             }
         });
     }
+    /// Filters the instances by the filter text and by the named graph selected in the ui state
+    pub fn apply_instance_filter(&mut self, node_data: &NodeData, graph_filter: Option<&GraphFilter>) {
+        let instance_filter = &self.instance_view.instance_filter;
+        self.filtered_instances = self
+            .instances
+            .iter()
+            .cloned()
+            .filter(|&instance_index| {
+                if graph_filter.is_some_and(|graph_filter| !graph_filter.contains_node(instance_index)) {
+                    return false;
+                }
+                let node = node_data.get_node_by_index(instance_index);
+                if let Some((node_iri, node)) = node {
+                    if node.apply_filter(instance_filter, node_iri, &node_data.indexers) {
+                        return true;
+                    }
+                }
+                false
+            })
+            .collect();
+        if (self.instance_view.pos / ROW_HIGHT) as usize >= self.filtered_instances.len() {
+            self.instance_view.pos = 0.0;
+        }
+        self.update_selected_index();
+    }
     pub fn update_selected_index(&mut self) {
         if let Some((iri, idx)) = self.instance_view.selected_idx {
             if idx == 0 {
@@ -918,7 +1003,11 @@ impl TypeData {
                 wtr.write_field(iri_ref)?;
                 for column_desc in self.instance_view.display_properties.iter() {
                     if column_desc.visible {
-                        let property = node.get_property_count(column_desc.predicate_index, lang_index);
+                        let property = node.get_property_count(
+                            column_desc.predicate_index,
+                            lang_index,
+                            &rdf_data.node_data.indexers.language_fallback,
+                        );
                         if let Some((property, _count)) = property {
                             let value = property.as_str_ref(&rdf_data.node_data.indexers);
                             wtr.write_field(value)?;
@@ -935,6 +1024,50 @@ impl TypeData {
     }
 }
 
+// Values of all languages side by side, each prefixed with its language tag
+fn all_languages_value<'a>(
+    node: &NObject,
+    column_desc: &ColumnDesc,
+    node_data: &'a NodeData,
+) -> Option<(Cow<'a, str>, u32)> {
+    let values = node.get_property_languages(column_desc.predicate_index);
+    if values.is_empty() {
+        return None;
+    }
+    let text = values
+        .iter()
+        .map(|(language_index, value)| {
+            let value = column_desc.abbreviation.apply(value.as_str_ref(&node_data.indexers));
+            match language_index.and_then(|language_index| node_data.get_language(language_index)) {
+                Some(language) => format!("{}: {}", language, value),
+                None => value.into_owned(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" | ");
+    Some((Cow::Owned(text), values.len() as u32))
+}
+
+fn show_table_language(ui: &mut egui::Ui, node_data: &NodeData, layout_data: &mut UIState) {
+    const DATA_LANGUAGE: &str = "Data language";
+    let selected_text = layout_data
+        .table_language
+        .and_then(|language_index| node_data.get_language(language_index))
+        .unwrap_or(DATA_LANGUAGE);
+    egui::ComboBox::from_id_salt("table_language")
+        .selected_text(selected_text)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut layout_data.table_language, None, DATA_LANGUAGE);
+            for language_index in layout_data.language_sort.iter() {
+                if let Some(language) = node_data.get_language(*language_index) {
+                    ui.selectable_value(&mut layout_data.table_language, Some(*language_index), language);
+                }
+            }
+        })
+        .response
+        .on_hover_text("Language of the literal columns. Values shown in another language are marked with its tag");
+}
+
 pub fn text_wrapped(
     text: &str,
     width: f32,
@@ -1052,6 +1185,12 @@ impl TypeInstanceIndex {
                         if type_filter_response.changed() {
                             self.apply_filter(&mut rdf_data.node_data, &label_context);
                         }
+                        if self.type_hierarchy.is_some() {
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut self.group_by_hierarchy, "Group by superclass");
+                                ui.checkbox(&mut self.count_transitive, "Count subclass instances");
+                            });
+                        }
                         let (selected_type, type_table_action) = self.show_types(
                             ui,
                             &mut rdf_data.node_data,
@@ -1216,15 +1355,54 @@ impl TypeInstanceIndex {
                     }
                     if ui.button(ICON_CLOSE).clicked() {
                         type_data.instance_view.instance_filter.clear();
-                        type_data.filtered_instances = type_data.instances.clone();
-                        type_data.update_selected_index();
-                        type_data.instance_view.instance_filter.clear();
+                        if layout_data.graph_filter.is_some() {
+                            table_action = TableAction::Filter;
+                        } else {
+                            type_data.filtered_instances = type_data.instances.clone();
+                            type_data.update_selected_index();
+                        }
+                    }
+                    show_graph_filter(ui, &rdf_data.node_data.named_graphs, &mut layout_data.graph_filter);
+                    if rdf_data.node_data.unique_languages() > 1 {
+                        show_table_language(ui, &rdf_data.node_data, layout_data);
                     }
                     ui.label(format!(
                         "{}/{}",
                         type_data.filtered_instances.len(),
                         type_data.instances.len()
                     ));
+                    if ui
+                        .add_enabled(!type_data.filtered_instances.is_empty(), egui::Button::new(ICON_GRAPH))
+                        .on_hover_text("Show filtered instances and the edges between them in visual graph")
+                        .clicked()
+                    {
+                        instance_action = NodeAction::VisualizeNodes(type_data.filtered_instances.clone());
+                    }
+                    ui.add_enabled_ui(!type_data.filtered_instances.is_empty(), |ui| {
+                        ui.menu_button(concatcp!("+", ICON_GRAPH), |ui| {
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::DragValue::new(&mut type_data.instance_view.sample_size)
+                                        .range(1..=type_data.filtered_instances.len().max(1)),
+                                );
+                                if ui.button("Add Random Instances").clicked() {
+                                    instance_action = NodeAction::AddVisualNodes(
+                                        type_data.sample_instances(type_data.instance_view.sample_size, &mut rand::rng()),
+                                    );
+                                    ui.close_kind(UiKind::Menu);
+                                }
+                            });
+                            if ui
+                                .button(format!("Add All {} Filtered Instances", type_data.filtered_instances.len()))
+                                .clicked()
+                            {
+                                instance_action = NodeAction::AddVisualNodes(type_data.filtered_instances.clone());
+                                ui.close_kind(UiKind::Menu);
+                            }
+                        })
+                        .response
+                        .on_hover_text("Add instances to the current visual graph");
+                    });
                     let visible_columns = type_data.instance_view.visible_columns();
                     if visible_columns > 1 {
                         if type_data.instance_view.column_pos > visible_columns - 1 {
@@ -1399,28 +1577,7 @@ impl TypeInstanceIndex {
                         }
                     }
                     TableAction::Filter => {
-                        type_data.filtered_instances = type_data
-                            .instances
-                            .iter()
-                            .cloned()
-                            .filter(|&instance_index| {
-                                let node = rdf_data.node_data.get_node_by_index(instance_index);
-                                if let Some((node_iri, node)) = node {
-                                    if node.apply_filter(
-                                        &type_data.instance_view.instance_filter,
-                                        node_iri,
-                                        &rdf_data.node_data.indexers,
-                                    ) {
-                                        return true;
-                                    }
-                                }
-                                false
-                            })
-                            .collect();
-                        if (type_data.instance_view.pos / ROW_HIGHT) as usize >= type_data.filtered_instances.len() {
-                            type_data.instance_view.pos = 0.0;
-                        }
-                        type_data.update_selected_index();
+                        type_data.apply_instance_filter(&rdf_data.node_data, layout_data.graph_filter.as_ref());
                     }
                     TableAction::HidePropExists(predicate_to_hide) => {
                         type_data.filtered_instances.retain(|&instance_index| {
@@ -1474,12 +1631,41 @@ impl TypeInstanceIndex {
                        self.type_cell_action = TypeCellAction::ShowValueStatistics(mouse_pos);
                        Popup::open_id(ui.ctx(), popup_id);
                     }
+                    TableAction::ChartColumn(predicate) => {
+                        let label_context =
+                            LabelContext::new(layout_data.display_language, iri_display, &rdf_data.prefix_manager);
+                        let title = rdf_data
+                            .node_data
+                            .predicate_display(predicate, &label_context, &rdf_data.node_data.indexers)
+                            .as_str()
+                            .to_string();
+                        self.column_chart = Some(ColumnChart::from_instances(
+                            title,
+                            predicate,
+                            &rdf_data.node_data,
+                            &type_data.filtered_instances,
+                            MAX_CATEGORIES,
+                        ));
+                    }
                     TableAction::None => {}
                 }
             }
         } else {
             ui.label("Select a type to display its instances");
         }
+        if let Some(column_chart) = &self.column_chart
+            && column_chart.show_window(ui.ctx())
+        {
+            self.column_chart = None;
+        }
+        // the named graph can be also changed in the graph view
+        let graph_index = layout_data.graph_filter.as_ref().map(|graph_filter| graph_filter.graph_index);
+        if graph_index != self.filtered_graph {
+            self.filtered_graph = graph_index;
+            for type_data in self.types.values_mut() {
+                type_data.apply_instance_filter(&rdf_data.node_data, layout_data.graph_filter.as_ref());
+            }
+        }
         instance_action
     }
 
@@ -1513,6 +1699,11 @@ impl TypeInstanceIndex {
             .sense(Sense::click());
 
         let mut selected_type_index = 0;
+        // (type, depth in the class hierarchy)
+        let rows: Vec<(IriIndex, usize)> = match &self.type_hierarchy {
+            Some(type_hierarchy) if self.group_by_hierarchy => type_hierarchy.ordered(&self.types_filtered),
+            _ => self.types_filtered.iter().map(|type_index| (*type_index, 0)).collect(),
+        };
 
         table
             .header(20.0, |mut header| {
@@ -1564,8 +1755,8 @@ impl TypeInstanceIndex {
             })
             .body(|body| {
                 let label_context = LabelContext::new(layout_data.display_language, iri_display, prefix_manager);
-                body.rows(text_height, self.types_filtered.len(), |mut row| {
-                    let type_index = self.types_filtered.get(row.index()).unwrap();
+                body.rows(text_height, rows.len(), |mut row| {
+                    let (type_index, depth) = &rows[row.index()];
                     let is_selected = self.selected_type == Some(*type_index);
                     row.set_selected(is_selected);
                     if is_selected {
@@ -1574,10 +1765,24 @@ impl TypeInstanceIndex {
                     let type_data = self.types.get(type_index).unwrap();
                     let type_label = node_data.type_display(*type_index, &label_context, &node_data.indexers);
                     row.col(|ui| {
+                        ui.add_space(*depth as f32 * 12.0);
                         ui.add(egui::Label::new(type_label.as_str()).selectable(false));
                     });
                     row.col(|ui| {
-                        ui.label(type_data.instances.len().to_string());
+                        let transitive_count = self
+                            .type_hierarchy
+                            .as_ref()
+                            .filter(|_| self.count_transitive)
+                            .and_then(|type_hierarchy| type_hierarchy.transitive_instance_count(*type_index));
+                        match transitive_count {
+                            Some(count) => {
+                                ui.label(count.to_string())
+                                    .on_hover_text(format!("{} direct instances", type_data.instances.len()));
+                            }
+                            None => {
+                                ui.label(type_data.instances.len().to_string());
+                            }
+                        }
                     });
                     row.col(|ui| {
                         ui.label(type_data.properties.len().to_string());
@@ -1595,14 +1800,14 @@ impl TypeInstanceIndex {
             });
         ui.input(|i| {
             if i.modifiers.ctrl && i.key_pressed(egui::Key::ArrowDown) {
-                let new_selected_type = self.types_filtered.get(selected_type_index + 1);
-                if let Some(new_selected_type) = new_selected_type {
+                let new_selected_type = rows.get(selected_type_index + 1);
+                if let Some((new_selected_type, _depth)) = new_selected_type {
                     selected_type = Some(*new_selected_type);
                 }
             }
             if selected_type_index > 0 && i.modifiers.ctrl && i.key_pressed(egui::Key::ArrowUp) {
-                let new_selected_type = self.types_filtered.get(selected_type_index - 1);
-                if let Some(new_selected_type) = new_selected_type {
+                let new_selected_type = rows.get(selected_type_index - 1);
+                if let Some((new_selected_type, _depth)) = new_selected_type {
                     selected_type = Some(*new_selected_type);
                 }
             }
@@ -1627,6 +1832,7 @@ pub enum TableAction {
     HidePropExists(IriIndex),
     HidePropNonMulti(IriIndex),
     ValueStatistics(IriIndex, Pos2),
+    ChartColumn(IriIndex),
     Filter,
 }
 
@@ -0,0 +1,312 @@
+use const_format::concatcp;
+use egui::{Align2, FontId, Pos2, Rect, Sense, Stroke, Vec2};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext,
+        config::IriDisplay,
+        edge_metadata::days_to_date,
+        timeline::{TimelineItem, date_predicates, time_ticks, timeline_items},
+    },
+    ui::style::ICON_CENTER,
+    uistate::actions::NodeAction,
+};
+
+const AXIS_HEIGHT: f32 = 30.0;
+const LANE_HEIGHT: f32 = 20.0;
+const LABEL_GAP: f32 = 6.0;
+const MIN_TICK_SPACING: f32 = 110.0;
+// more visible items are drawn only as marks on the axis
+const MAX_LABELED_ITEMS: usize = 2000;
+
+/**
+ * State of the timeline display.
+ * The instances of the chosen type are placed on the time axis by the value of a date property.
+ */
+pub struct TimelineView {
+    pub type_index: Option<IriIndex>,
+    pub predicate: Option<IriIndex>,
+    pub items: Vec<TimelineItem>,
+    // items must be computed again (selection or data changed)
+    pub items_dirty: bool,
+    // time (days) at the left border
+    pub start: f64,
+    pub days_per_pixel: f64,
+    pub fit_pending: bool,
+}
+
+impl Default for TimelineView {
+    fn default() -> Self {
+        Self {
+            type_index: None,
+            predicate: None,
+            items: Vec::new(),
+            items_dirty: true,
+            start: 0.0,
+            days_per_pixel: 1.0,
+            fit_pending: true,
+        }
+    }
+}
+
+impl TimelineView {
+    pub fn clean(&mut self) {
+        self.items.clear();
+        self.items_dirty = true;
+        self.fit_pending = true;
+    }
+
+    fn fit(&mut self, width: f32) {
+        if let (Some(first), Some(last)) = (self.items.first(), self.items.last()) {
+            let span = (last.time - first.time).max(1.0);
+            self.days_per_pixel = span / (width as f64 * 0.9);
+            self.start = first.time - span * 0.05;
+        }
+    }
+}
+
+fn format_time(time: f64) -> String {
+    let day = time.floor();
+    let minutes = ((time - day) * 24.0 * 60.0).round() as u32;
+    if minutes == 0 {
+        days_to_date(day as i64)
+    } else {
+        format!("{} {:02}:{:02}", days_to_date(day as i64), minutes / 60, minutes % 60)
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn show_timeline(&mut self, ui: &mut egui::Ui) -> NodeAction {
+        let mut node_action = NodeAction::None;
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return node_action;
+        };
+        let label_context = LabelContext::new(
+            self.ui_state.display_language,
+            self.config.iri_display,
+            &rdf_data.prefix_manager,
+        );
+        let date_types: Vec<(IriIndex, Vec<IriIndex>)> = self
+            .type_index
+            .types_order
+            .iter()
+            .filter_map(|type_index| {
+                let predicates = date_predicates(self.type_index.types.get(type_index)?);
+                (!predicates.is_empty()).then_some((*type_index, predicates))
+            })
+            .collect();
+        if date_types.is_empty() {
+            ui.label("No type with xsd:date or xsd:dateTime properties found");
+            return node_action;
+        }
+        let timeline = &mut self.timeline_view;
+        // start with the type shown in the table
+        if timeline.type_index.is_none_or(|type_index| !date_types.iter().any(|(t, _)| *t == type_index)) {
+            let table_type = self
+                .type_index
+                .selected_type
+                .filter(|selected_type| date_types.iter().any(|(t, _)| t == selected_type));
+            timeline.type_index = Some(table_type.unwrap_or(date_types[0].0));
+            timeline.predicate = None;
+            timeline.items_dirty = true;
+        }
+        let current_type = timeline.type_index.unwrap_or(date_types[0].0);
+        let predicates = date_types
+            .iter()
+            .find(|(type_index, _)| *type_index == current_type)
+            .map(|(_, predicates)| predicates.as_slice())
+            .unwrap_or_default();
+        if timeline.predicate.is_none_or(|predicate| !predicates.contains(&predicate)) {
+            timeline.predicate = predicates.first().copied();
+            timeline.items_dirty = true;
+        }
+        let type_label = |type_index: IriIndex| {
+            rdf_data
+                .node_data
+                .type_display(type_index, &label_context, &rdf_data.node_data.indexers)
+                .as_str()
+                .to_owned()
+        };
+        let predicate_label = |predicate: IriIndex| {
+            rdf_data
+                .node_data
+                .predicate_display(predicate, &label_context, &rdf_data.node_data.indexers)
+                .as_str()
+                .to_owned()
+        };
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_label("Type")
+                .selected_text(type_label(current_type))
+                .show_ui(ui, |ui| {
+                    for (type_index, _) in date_types.iter() {
+                        if ui
+                            .selectable_value(&mut timeline.type_index, Some(*type_index), type_label(*type_index))
+                            .clicked()
+                        {
+                            timeline.predicate = None;
+                            timeline.items_dirty = true;
+                        }
+                    }
+                });
+            egui::ComboBox::from_label("Date property")
+                .selected_text(timeline.predicate.map(predicate_label).unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    for predicate in predicates {
+                        if ui
+                            .selectable_value(&mut timeline.predicate, Some(*predicate), predicate_label(*predicate))
+                            .clicked()
+                        {
+                            timeline.items_dirty = true;
+                        }
+                    }
+                });
+            if ui
+                .button(concatcp!(ICON_CENTER, " Fit All"))
+                .on_hover_text("Zoom to all instances")
+                .clicked()
+            {
+                timeline.fit_pending = true;
+            }
+            ui.label(format!("{} instances with date", timeline.items.len()));
+        });
+        if timeline.items_dirty {
+            timeline.items = match (timeline.type_index, timeline.predicate) {
+                (Some(type_index), Some(predicate)) => match self.type_index.types.get(&type_index) {
+                    Some(type_data) => timeline_items(&type_data.instances, predicate, &rdf_data.node_data),
+                    None => Vec::new(),
+                },
+                _ => Vec::new(),
+            };
+            timeline.items_dirty = false;
+            timeline.fit_pending = true;
+        }
+        if timeline.items.is_empty() {
+            ui.label("No parsable date values");
+            return node_action;
+        }
+
+        let (rect, response) = ui.allocate_exact_size(ui.available_size(), Sense::click_and_drag());
+        if timeline.fit_pending {
+            timeline.fit(rect.width());
+            timeline.fit_pending = false;
+        }
+        if response.dragged() {
+            timeline.start -= response.drag_delta().x as f64 * timeline.days_per_pixel;
+        }
+        if let Some(hover_pos) = response.hover_pos() {
+            let (scroll, zoom_delta) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor = zoom_delta * (scroll / 200.0).exp();
+            if factor != 1.0 {
+                // keep the time under the cursor
+                let cursor_time = timeline.start + (hover_pos.x - rect.left()) as f64 * timeline.days_per_pixel;
+                timeline.days_per_pixel = (timeline.days_per_pixel / factor as f64).clamp(1.0 / 24.0 / 60.0, 1_000_000.0);
+                timeline.start = cursor_time - (hover_pos.x - rect.left()) as f64 * timeline.days_per_pixel;
+            }
+        }
+
+        let painter = ui.painter_at(rect);
+        let visuals = ui.visuals();
+        painter.rect_filled(rect, 0.0, visuals.extreme_bg_color);
+        let axis_y = rect.bottom() - AXIS_HEIGHT;
+        let end = timeline.start + rect.width() as f64 * timeline.days_per_pixel;
+        let to_x = |time: f64| rect.left() + ((time - timeline.start) / timeline.days_per_pixel) as f32;
+        let grid_color = visuals.weak_text_color().gamma_multiply(0.4);
+        painter.hline(rect.x_range(), axis_y, Stroke::new(1.0, visuals.text_color()));
+        let max_ticks = (rect.width() / MIN_TICK_SPACING).max(1.0) as usize;
+        for (time, label) in time_ticks(timeline.start, end, max_ticks) {
+            let x = to_x(time);
+            painter.vline(x, rect.top()..=axis_y, Stroke::new(1.0, grid_color));
+            painter.text(
+                Pos2::new(x, axis_y + 4.0),
+                Align2::CENTER_TOP,
+                label,
+                FontId::proportional(11.0),
+                visuals.weak_text_color(),
+            );
+        }
+
+        let first_visible = timeline.items.partition_point(|item| item.time < timeline.start);
+        let last_visible = timeline.items.partition_point(|item| item.time <= end);
+        let visible_items = &timeline.items[first_visible..last_visible];
+        let show_labels = visible_items.len() <= MAX_LABELED_ITEMS;
+        let max_lanes = ((axis_y - rect.top()) / LANE_HEIGHT).max(1.0) as usize;
+        let should_short_iri = matches!(self.config.iri_display, IriDisplay::Shorten);
+        let font = FontId::proportional(12.0);
+        let pointer_pos = response.hover_pos();
+        // right end of the last label in each lane
+        let mut lane_ends: Vec<f32> = Vec::new();
+        let mut hovered: Option<&TimelineItem> = None;
+        for item in visible_items {
+            let x = to_x(item.time);
+            let Some((iri, node)) = rdf_data.node_data.get_node_by_index(item.node) else {
+                continue;
+            };
+            let color = self.visualization_style.get_type_style(&node.types).color;
+            let selected = self.ui_state.selected_node == Some(item.node);
+            let mark_stroke = if selected {
+                Stroke::new(2.0, visuals.selection.stroke.color)
+            } else {
+                Stroke::new(1.0, visuals.window_stroke.color)
+            };
+            painter.circle(Pos2::new(x, axis_y), 4.0, color, mark_stroke);
+            let mut item_rect = Rect::from_center_size(Pos2::new(x, axis_y), Vec2::splat(10.0));
+            if show_labels {
+                let label = node.node_label(
+                    iri,
+                    &self.visualization_style,
+                    should_short_iri,
+                    self.ui_state.display_language,
+                    &rdf_data.node_data.indexers,
+                );
+                let galley = painter.layout_no_wrap(label.to_owned(), font.clone(), visuals.strong_text_color());
+                let lane = lane_ends.iter().position(|lane_end| *lane_end < x);
+                let lane = match lane {
+                    Some(lane) => Some(lane),
+                    None if lane_ends.len() < max_lanes => {
+                        lane_ends.push(f32::MIN);
+                        Some(lane_ends.len() - 1)
+                    }
+                    None => None,
+                };
+                if let Some(lane) = lane {
+                    let lane_y = axis_y - (lane + 1) as f32 * LANE_HEIGHT;
+                    let label_rect =
+                        Rect::from_min_size(Pos2::new(x, lane_y), galley.size() + Vec2::new(8.0, 4.0));
+                    lane_ends[lane] = label_rect.right() + LABEL_GAP;
+                    painter.vline(x, label_rect.bottom()..=axis_y, Stroke::new(1.0, color));
+                    let fill = if selected { visuals.selection.bg_fill } else { visuals.window_fill };
+                    painter.rect(
+                        label_rect,
+                        3.0,
+                        fill,
+                        Stroke::new(1.0, color),
+                        egui::StrokeKind::Inside,
+                    );
+                    painter.galley(label_rect.min + Vec2::new(4.0, 2.0), galley, visuals.strong_text_color());
+                    item_rect = item_rect.union(label_rect);
+                }
+            }
+            if pointer_pos.is_some_and(|pointer_pos| item_rect.contains(pointer_pos)) {
+                hovered = Some(item);
+            }
+        }
+        if let Some(item) = hovered {
+            ui.output_mut(|o| o.cursor_icon = egui::CursorIcon::PointingHand);
+            response.clone().on_hover_text(format_time(item.time));
+            if response.clicked() {
+                node_action = NodeAction::BrowseNode(item.node);
+            }
+        }
+        if !show_labels {
+            painter.text(
+                rect.left_top() + Vec2::new(4.0, 4.0),
+                Align2::LEFT_TOP,
+                "Too many instances for labels, zoom in",
+                FontId::proportional(12.0),
+                visuals.weak_text_color(),
+            );
+        }
+        node_action
+    }
+}
@@ -0,0 +1,164 @@
+use egui::{RichText, ScrollArea};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::LabelContext,
+    graph_algorithms::topological_sort::DependencyOrder,
+    ui::cycles::predicate_combo,
+    uistate::{DisplayType, SystemMessage},
+};
+
+impl RdfGlanceApp {
+    pub fn open_dependency_order(&mut self) {
+        if self.dependency_order.is_none() {
+            self.dependency_order = Some(DependencyOrder::default());
+        }
+    }
+
+    pub fn show_dependency_order(&mut self, ui: &mut egui::Ui) {
+        let Some(dependency_order) = &mut self.dependency_order else {
+            return;
+        };
+        let mut close = false;
+        let mut compute = false;
+        let mut export = false;
+        let mut node_to_browse: Option<IriIndex> = None;
+        egui::Window::new("Dependency Order")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    predicate_combo(
+                        ui,
+                        "Predicate",
+                        &mut dependency_order.predicate,
+                        "<Select predicate>",
+                        &label_context,
+                        &rdf_data,
+                        &self.type_index,
+                    );
+                    ui.checkbox(&mut dependency_order.dependencies_first, "Dependencies first")
+                        .on_hover_text("For \"a requires b\" b is placed before a");
+                    if ui
+                        .add_enabled(dependency_order.predicate.is_some(), egui::Button::new("Compute Order"))
+                        .clicked()
+                    {
+                        compute = true;
+                    }
+                    if let Some(result) = &dependency_order.result {
+                        ui.separator();
+                        if result.is_complete() {
+                            ui.label(format!("Ordered nodes: {}", result.order.len()));
+                        } else {
+                            ui.label(
+                                RichText::new(format!(
+                                    "Ordered nodes: {}, in cycle: {}, depending on cycle: {}",
+                                    result.order.len(),
+                                    result.cyclic.len(),
+                                    result.blocked.len()
+                                ))
+                                .color(ui.visuals().warn_fg_color),
+                            );
+                        }
+                        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                            let mut node_row = |ui: &mut egui::Ui, position: String, node_index: IriIndex, warn: bool| {
+                                if let Some((iri, _)) = rdf_data.node_data.get_node_by_index(node_index) {
+                                    ui.horizontal(|ui| {
+                                        let text = RichText::new(format!("{} {}", position, iri));
+                                        let text = if warn { text.color(ui.visuals().warn_fg_color) } else { text };
+                                        ui.label(text);
+                                        if ui.small_button("Browse").clicked() {
+                                            node_to_browse = Some(node_index);
+                                        }
+                                    });
+                                }
+                            };
+                            for (position, node_index) in result.order.iter().enumerate() {
+                                node_row(ui, format!("{}.", position + 1), *node_index, false);
+                            }
+                            for node_index in result.cyclic.iter() {
+                                node_row(ui, "cycle:".to_string(), *node_index, true);
+                            }
+                            for node_index in result.blocked.iter() {
+                                node_row(ui, "blocked:".to_string(), *node_index, true);
+                            }
+                        });
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(dependency_order.result.is_some(), egui::Button::new("Export as Text"))
+                        .clicked()
+                    {
+                        export = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if compute {
+            self.compute_dependency_order();
+        }
+        if export {
+            self.export_dependency_order();
+        }
+        if let Some(node_to_browse) = node_to_browse {
+            self.show_object_by_index(node_to_browse, true);
+            self.display_type = DisplayType::Browse;
+        }
+        if close {
+            self.dependency_order = None;
+        }
+    }
+
+    fn export_dependency_order(&mut self) {
+        let Some(result) = self.dependency_order.as_ref().and_then(|order| order.result.as_ref()) else {
+            return;
+        };
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let text = result.to_text(|node_index| {
+            rdf_data
+                .node_data
+                .get_node_by_index(node_index)
+                .map(|(iri, _)| {
+                    rdf_data
+                        .prefix_manager
+                        .get_full_opt(iri)
+                        .map(|full_iri| full_iri.to_string())
+                        .unwrap_or_else(|| iri.to_string())
+                })
+                .unwrap_or_default()
+        });
+        drop(rdf_data);
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            use rfd::FileDialog;
+
+            if let Some(path) = FileDialog::new()
+                .add_filter("Text", &["txt"])
+                .set_file_name("dependency_order.txt")
+                .save_file()
+                && let Err(e) = std::fs::write(&path, text)
+            {
+                self.system_message = SystemMessage::Error(format!("Can not save dependency order: {}", e));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            use crate::support::uitools::web_download;
+
+            let _ = web_download("dependency_order.txt", text.as_bytes());
+        }
+    }
+}
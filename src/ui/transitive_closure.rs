@@ -0,0 +1,214 @@
+use egui::ScrollArea;
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext,
+        graph_styles::{EdgeStyle, LineStyle},
+        transitive_closure::{find_closure_predicate, materialize_closure, reachable_nodes, remove_references},
+    },
+    ui::cycles::predicate_combo,
+    uistate::DisplayType,
+};
+
+#[derive(Default)]
+pub struct TransitiveClosure {
+    pub predicate: Option<IriIndex>,
+    // start node of the last query and the nodes reachable from it
+    pub start: Option<IriIndex>,
+    pub reachable: Vec<IriIndex>,
+}
+
+impl RdfGlanceApp {
+    pub fn open_transitive_closure(&mut self) {
+        if self.transitive_closure.is_none() {
+            self.transitive_closure = Some(TransitiveClosure::default());
+        }
+    }
+
+    // Current node in the browser or the selected node in the graph
+    fn closure_start_node(&self) -> Option<IriIndex> {
+        match self.display_type {
+            DisplayType::Graph => self.ui_state.selected_node.or(self.current_iri),
+            _ => self.current_iri.or(self.ui_state.selected_node),
+        }
+    }
+
+    pub fn show_transitive_closure(&mut self, ui: &mut egui::Ui) {
+        let start_node = self.closure_start_node();
+        let Some(transitive_closure) = &mut self.transitive_closure else {
+            return;
+        };
+        let mut close = false;
+        let mut materialize = false;
+        let mut remove = false;
+        let mut show_in_graph = false;
+        let mut node_to_browse: Option<IriIndex> = None;
+        egui::Window::new("Transitive Closure")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(600.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    predicate_combo(
+                        ui,
+                        "Predicate",
+                        &mut transitive_closure.predicate,
+                        "<Select predicate>",
+                        &label_context,
+                        &rdf_data,
+                        &self.type_index,
+                    );
+                    let predicate = transitive_closure.predicate;
+                    ui.horizontal(|ui| {
+                        let start_iri = start_node
+                            .and_then(|node_index| rdf_data.node_data.get_node_by_index(node_index))
+                            .map(|(iri, _)| iri.to_string());
+                        let query_button = ui
+                            .add_enabled(
+                                predicate.is_some() && start_node.is_some(),
+                                egui::Button::new("Reachable from Current Node"),
+                            )
+                            .on_hover_text(start_iri.unwrap_or_else(|| "No current node".to_string()));
+                        if query_button.clicked()
+                            && let (Some(predicate), Some(start_node)) = (predicate, start_node)
+                        {
+                            transitive_closure.start = Some(start_node);
+                            transitive_closure.reachable = reachable_nodes(&rdf_data.node_data, predicate, start_node);
+                        }
+                        if ui
+                            .add_enabled(predicate.is_some(), egui::Button::new("Materialize as Edges"))
+                            .on_hover_text("Add virtual edges (predicate+) for all indirect connections")
+                            .clicked()
+                        {
+                            materialize = true;
+                        }
+                        let has_closure = predicate
+                            .and_then(|predicate| find_closure_predicate(&rdf_data.node_data, predicate))
+                            .is_some();
+                        if ui
+                            .add_enabled(has_closure, egui::Button::new("Remove Closure Edges"))
+                            .clicked()
+                        {
+                            remove = true;
+                        }
+                    });
+                    if let Some(start) = transitive_closure.start
+                        && let Some((start_iri, _)) = rdf_data.node_data.get_node_by_index(start)
+                    {
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            ui.label(format!(
+                                "Reachable from {}: {}",
+                                start_iri,
+                                transitive_closure.reachable.len()
+                            ));
+                            if ui
+                                .add_enabled(!transitive_closure.reachable.is_empty(), egui::Button::new("Show in Graph"))
+                                .clicked()
+                            {
+                                show_in_graph = true;
+                            }
+                        });
+                        ScrollArea::vertical().max_height(400.0).show(ui, |ui| {
+                            for node_index in transitive_closure.reachable.iter() {
+                                if let Some((iri, _)) = rdf_data.node_data.get_node_by_index(*node_index) {
+                                    ui.horizontal(|ui| {
+                                        ui.label(iri.as_ref());
+                                        if ui.small_button("Browse").clicked() {
+                                            node_to_browse = Some(*node_index);
+                                        }
+                                    });
+                                }
+                            }
+                        });
+                    }
+                }
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        let predicate = transitive_closure.predicate;
+        if show_in_graph && let Some(start) = transitive_closure.start {
+            let mut nodes = vec![start];
+            nodes.extend(transitive_closure.reachable.iter().cloned());
+            self.show_and_select_nodes(&nodes);
+            self.display_type = DisplayType::Graph;
+        }
+        if let Some(predicate) = predicate {
+            if materialize {
+                self.materialize_closure(predicate, ui.visuals().dark_mode);
+            } else if remove {
+                self.remove_closure(predicate, ui.visuals().dark_mode);
+            }
+        }
+        if let Some(node_to_browse) = node_to_browse {
+            self.show_object_by_index(node_to_browse, true);
+            self.display_type = DisplayType::Browse;
+        }
+        if close {
+            self.transitive_closure = None;
+        }
+    }
+
+    fn materialize_closure(&mut self, predicate: IriIndex, is_dark_mode: bool) {
+        self.record_data_change("Materialize Transitive Closure");
+        let result = if let Ok(mut rdf_data) = self.rdf_data.write() {
+            let added = materialize_closure(&mut rdf_data.node_data, predicate);
+            find_closure_predicate(&rdf_data.node_data, predicate).map(|closure_predicate| (closure_predicate, added))
+        } else {
+            None
+        };
+        let Some((closure_predicate, added)) = result else {
+            return;
+        };
+        let color = self.visualization_style.get_predicate_color(predicate, is_dark_mode);
+        self.visualization_style.edge_styles.insert(
+            closure_predicate,
+            EdgeStyle {
+                color,
+                width: 1.0,
+                line_style: LineStyle::Dashed,
+                ..EdgeStyle::default()
+            },
+        );
+        self.update_data_indexes(is_dark_mode);
+        self.refresh_closure_edges(closure_predicate);
+        self.set_status_message(&format!("Transitive closure: {} edges added", added));
+    }
+
+    fn remove_closure(&mut self, predicate: IriIndex, is_dark_mode: bool) {
+        self.record_data_change("Remove Transitive Closure");
+        let result = if let Ok(mut rdf_data) = self.rdf_data.write() {
+            find_closure_predicate(&rdf_data.node_data, predicate).map(|closure_predicate| {
+                let removed = remove_references(&mut rdf_data.node_data, closure_predicate);
+                (closure_predicate, removed)
+            })
+        } else {
+            None
+        };
+        let Some((closure_predicate, removed)) = result else {
+            return;
+        };
+        self.update_data_indexes(is_dark_mode);
+        self.refresh_closure_edges(closure_predicate);
+        self.set_status_message(&format!("Transitive closure: {} edges removed", removed));
+    }
+
+    fn refresh_closure_edges(&mut self, closure_predicate: IriIndex) {
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            self.visible_nodes.refresh_predicate_edges(
+                closure_predicate,
+                &rdf_data.node_data,
+                &self.ui_state.hidden_predicates,
+            );
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use egui::{Grid, RichText, ScrollArea};
+
+use crate::{
+    IriIndex, RdfGlanceApp,
+    domain::{
+        LabelContext, NodeData,
+        type_metric_summary::{Aggregate, SummarySortKey, TypeMetricSummary},
+    },
+    uistate::{DisplayType, SystemMessage},
+};
+
+impl RdfGlanceApp {
+    pub fn open_type_metric_summary(&mut self) {
+        let Some(statistics_data) = &self.statistics_data else {
+            return;
+        };
+        if statistics_data.results.is_empty() {
+            self.system_message = SystemMessage::Info("Run some statistics algorithms first".to_string());
+            return;
+        }
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            self.type_metric_summary = Some(TypeMetricSummary::new(statistics_data, &rdf_data.node_data));
+        }
+    }
+
+    pub fn show_type_metric_summary(&mut self, ui: &mut egui::Ui) {
+        let (Some(summary), Some(statistics_data)) = (&mut self.type_metric_summary, &self.statistics_data) else {
+            return;
+        };
+        let mut close = false;
+        let mut type_to_show: Option<IriIndex> = None;
+        egui::Window::new("Metric Summary by Type")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(800.0)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                if let Ok(rdf_data) = self.rdf_data.read() {
+                    let label_context = LabelContext::new(
+                        self.ui_state.display_language,
+                        self.config.iri_display,
+                        &rdf_data.prefix_manager,
+                    );
+                    let type_label = |type_index: Option<IriIndex>| -> String {
+                        type_display(type_index, &rdf_data.node_data, &label_context)
+                    };
+                    let mut sort_key: Option<SummarySortKey> = None;
+                    ui.label("Click a column header to sort");
+                    ui.separator();
+                    ScrollArea::both().max_height(500.0).show(ui, |ui| {
+                        Grid::new("type_metric_summary_grid").striped(true).show(ui, |ui| {
+                            let mut header = |ui: &mut egui::Ui, label: String, key: SummarySortKey| {
+                                let label = if summary.sort_key == key {
+                                    format!("{} {}", label, if summary.ascending { "\u{25B2}" } else { "\u{25BC}" })
+                                } else {
+                                    label
+                                };
+                                if ui.button(RichText::new(label).strong()).clicked() {
+                                    sort_key = Some(key);
+                                }
+                            };
+                            header(ui, "Type".to_string(), SummarySortKey::Type);
+                            header(ui, "Count".to_string(), SummarySortKey::Count);
+                            for (result_index, result) in statistics_data.results.iter().enumerate() {
+                                for aggregate in Aggregate::ALL {
+                                    header(
+                                        ui,
+                                        format!("{} {}", result.statistics_value(), aggregate.label()),
+                                        SummarySortKey::Metric(result_index, aggregate),
+                                    );
+                                }
+                            }
+                            ui.end_row();
+                            for row in summary.rows.iter() {
+                                match row.type_index {
+                                    Some(type_index) => {
+                                        if ui
+                                            .link(type_label(row.type_index))
+                                            .on_hover_text("Show instances in table")
+                                            .clicked()
+                                        {
+                                            type_to_show = Some(type_index);
+                                        }
+                                    }
+                                    None => {
+                                        ui.label(type_label(None));
+                                    }
+                                }
+                                ui.label(row.count.to_string());
+                                for metric in row.metrics.iter() {
+                                    for aggregate in Aggregate::ALL {
+                                        ui.label(format!("{:.4}", metric.get(aggregate)));
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    });
+                    if let Some(sort_key) = sort_key {
+                        summary.set_sort_key(sort_key, type_label);
+                    }
+                }
+                ui.separator();
+                if ui.button("Close").clicked() {
+                    close = true;
+                }
+            });
+        if let Some(type_index) = type_to_show {
+            self.type_index.selected_type = Some(type_index);
+            self.display_type = DisplayType::Table;
+        }
+        if close {
+            self.type_metric_summary = None;
+        }
+    }
+}
+
+fn type_display(type_index: Option<IriIndex>, node_data: &NodeData, label_context: &LabelContext) -> String {
+    match type_index {
+        Some(type_index) => node_data
+            .type_display(type_index, label_context, &node_data.indexers)
+            .as_str()
+            .to_string(),
+        None => "<untyped>".to_string(),
+    }
+}
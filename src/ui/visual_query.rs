@@ -55,6 +55,7 @@ impl RdfGlanceApp {
                                 }
                                 self.visual_query.instance_view.pos = 0.0;
                                 self.visual_query.instances = table_query.compute_instances(&rdf_data);
+                                self.session_statistics.record_query();
                             }
                             if !self.visual_query.instances.is_empty() {
                                 ui.label((self.visual_query.instances.len() / self.visual_query.tables_pro_row).to_string());
@@ -64,7 +65,7 @@ impl RdfGlanceApp {
                             }
                         });
                     } else {
-                        let label_context = LabelContext::new(self.ui_state.display_language, self.persistent_data.config_data.iri_display, &rdf_data.prefix_manager);
+                        let label_context = LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
                         let type_str = rdf_data.node_data.type_display(selected_type, &label_context, &rdf_data.node_data.indexers);
                         egui::ComboBox::from_id_salt("selected_type")
                             .selected_text(type_str.as_str())
@@ -102,7 +103,7 @@ impl RdfGlanceApp {
             egui::ScrollArea::vertical().show(ui, |ui| {
                 ui.set_height(PANEL_H);
                 if let Ok(rdf_data) = self.rdf_data.read() {
-                    let label_context = LabelContext::new(self.ui_state.display_language, self.persistent_data.config_data.iri_display, &rdf_data.prefix_manager);
+                    let label_context = LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
                     egui::Panel::right("details_panel")
                         .exact_size(500.0)
                         .show_inside(ui, |ui| {
@@ -171,7 +172,7 @@ impl RdfGlanceApp {
             return NodeAction::None;
         }
         if let Ok(rdf_data) = self.rdf_data.read() {
-            let label_context = LabelContext::new(self.ui_state.display_language, self.persistent_data.config_data.iri_display, &rdf_data.prefix_manager);
+            let label_context = LabelContext::new(self.ui_state.display_language, self.config.iri_display, &rdf_data.prefix_manager);
             // For simplicity, we will just show the number of instances for now
             let a_height = ui.available_height();
             let available_width = ui.available_width();
@@ -298,7 +299,7 @@ impl RdfGlanceApp {
                         for column_desc in table_query.visible_predicates
                             .iter()
                             .filter(|p| p.visible) {
-                                let property = node.get_property_count(column_desc.predicate_index, self.ui_state.display_language);
+                                let property = node.get_property_count(column_desc.predicate_index, self.ui_state.display_language, &rdf_data.node_data.indexers.language_fallback);
                                 if let Some((property, count)) = property {
                                     let value = property.as_str_ref(&rdf_data.node_data.indexers);
                                     let cell_rect = egui::Rect::from_min_size(
@@ -490,7 +491,7 @@ impl RdfGlanceApp {
             if let Ok(rdf_data) = self.rdf_data.read() {
                 let label_context = LabelContext::new(
                     self.ui_state.display_language,
-                    self.persistent_data.config_data.iri_display,
+                    self.config.iri_display,
                     &rdf_data.prefix_manager,
                 );
                 let mut wtr = csv::Writer::from_path(path).unwrap();
@@ -507,7 +508,7 @@ impl RdfGlanceApp {
         if let Ok(rdf_data) = self.rdf_data.read() {
             let label_context = LabelContext::new(
                 self.ui_state.display_language,
-                self.persistent_data.config_data.iri_display,
+                self.config.iri_display,
                 &rdf_data.prefix_manager,
             );
             let mut buf = Vec::new();
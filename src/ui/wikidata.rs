@@ -0,0 +1,124 @@
+use crate::{
+    RdfGlanceApp,
+    domain::wikidata::WikidataStatements,
+    uistate::{SystemMessage, layout::update_edges_groups},
+};
+
+impl RdfGlanceApp {
+    pub fn open_wikidata_statements(&mut self, is_dark_mode: bool) {
+        if self.wikidata_statements.is_none() {
+            let result = if let Ok(mut rdf_data) = self.rdf_data.write() {
+                let rdf_data = &mut *rdf_data;
+                let mut wikidata_statements = WikidataStatements::new(&mut rdf_data.node_data, &rdf_data.prefix_manager);
+                let added = wikidata_statements.materialize(&mut rdf_data.node_data);
+                Some((wikidata_statements, added))
+            } else {
+                None
+            };
+            let Some((mut wikidata_statements, added)) = result else {
+                return;
+            };
+            if wikidata_statements.is_empty() {
+                self.system_message = SystemMessage::Info(
+                    "No Wikidata statements found. Statements are nodes referenced by p: properties with ps: values"
+                        .to_string(),
+                );
+                return;
+            }
+            // statement nodes are not expanded and not shown any more
+            for predicate in wikidata_statements.statement_predicates.iter() {
+                if !self.ui_state.hidden_predicates.contains(*predicate) {
+                    self.ui_state.hidden_predicates.add(*predicate);
+                    wikidata_statements.hidden_predicates.push(*predicate);
+                }
+            }
+            self.visible_nodes.retain(&self.ui_state.hidden_predicates, false, |node_layout| {
+                !wikidata_statements.statement_nodes.contains(&node_layout.node_index)
+            });
+            self.set_status_message(&format!(
+                "Wikidata: {} statements collapsed, {} edges added",
+                wikidata_statements.len(),
+                added
+            ));
+            self.wikidata_statements = Some(wikidata_statements);
+            self.refresh_wikidata_edges(is_dark_mode);
+        }
+        self.ui_state.wikidata_window = true;
+    }
+
+    pub fn show_wikidata_statements(&mut self, ui: &mut egui::Ui) {
+        if !self.ui_state.wikidata_window {
+            return;
+        }
+        let Some(wikidata_statements) = &mut self.wikidata_statements else {
+            return;
+        };
+        let mut close = false;
+        let mut reset = false;
+        egui::Window::new("Wikidata Statements")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(format!(
+                    "{} statements are shown as direct edges",
+                    wikidata_statements.len()
+                ));
+                ui.checkbox(&mut wikidata_statements.truthy_only, "Only truthy statements")
+                    .on_hover_text("Hide edges of deprecated statements and of statements without the best rank");
+                ui.checkbox(&mut wikidata_statements.show_qualifiers, "Qualifiers in edge labels")
+                    .on_hover_text("Show qualifiers (e.g. start time, end time) and rank as part of the edge label");
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Reset").on_hover_text("Show the statement nodes again").clicked() {
+                        reset = true;
+                    }
+                    if ui.button("Close").clicked() {
+                        close = true;
+                    }
+                });
+            });
+        if reset {
+            self.reset_wikidata_statements(ui.visuals().dark_mode);
+            close = true;
+        }
+        if close {
+            self.ui_state.wikidata_window = false;
+        }
+    }
+
+    fn reset_wikidata_statements(&mut self, is_dark_mode: bool) {
+        let Some(mut wikidata_statements) = self.wikidata_statements.take() else {
+            return;
+        };
+        if let Ok(mut rdf_data) = self.rdf_data.write() {
+            wikidata_statements.remove_added(&mut rdf_data.node_data);
+        }
+        for predicate in wikidata_statements.hidden_predicates.iter() {
+            self.ui_state.hidden_predicates.remove(*predicate);
+        }
+        self.wikidata_statements = Some(wikidata_statements);
+        self.refresh_wikidata_edges(is_dark_mode);
+        self.wikidata_statements = None;
+    }
+
+    fn refresh_wikidata_edges(&mut self, is_dark_mode: bool) {
+        self.update_data_indexes(is_dark_mode);
+        let Some(wikidata_statements) = &self.wikidata_statements else {
+            return;
+        };
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            for predicate in wikidata_statements.direct_predicates.iter() {
+                self.visible_nodes.refresh_predicate_edges(
+                    *predicate,
+                    &rdf_data.node_data,
+                    &self.ui_state.hidden_predicates,
+                );
+            }
+        }
+        if let Ok(mut layout) = self.visible_nodes.layout_data.write() {
+            update_edges_groups(&mut layout.edges, &self.ui_state.hidden_predicates);
+        }
+        self.visible_nodes.start_layout(&self.config, &self.ui_state.hidden_predicates);
+    }
+}
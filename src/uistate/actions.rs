@@ -24,6 +24,9 @@ pub enum NodeContextAction {
     HideThisTypePreserveEdges,
     ShowAllInstanceInTable,
     ChangeLockPosition(bool),
+    StyleSelected,
+    CollapseCluster,
+    ExpandCluster,
 }
 
 pub enum NodeAction {
@@ -32,7 +35,11 @@ pub enum NodeAction {
     ShowType(IriIndex),
     ShowTypeInstances(IriIndex, Vec<IriIndex>),
     ShowVisual(IriIndex),
-    AddVisual(IriIndex)
+    AddVisual(IriIndex),
+    // visual graph with exactly these nodes and the edges between them
+    VisualizeNodes(Vec<IriIndex>),
+    // nodes added to the current visual graph around the first one
+    AddVisualNodes(Vec<IriIndex>),
 }
 
 
@@ -2,20 +2,19 @@ use const_format::concatcp;
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 use std::{
-    collections::HashMap,
-    path::Path,
+    collections::{HashMap, VecDeque},
     sync::{
         Arc, RwLock,
-        atomic::{AtomicBool, AtomicUsize, Ordering},
+        atomic::Ordering,
     },
     thread::{self, JoinHandle},
     time::Duration,
 };
 
-use crate::{domain::{type_index::TypeInstanceIndex, visual_query::VisualQuery}, ui::{reference_resolver::ReferenceResolver, style::*}};
+use crate::{graph_algorithms::{cycles::CycleSearch, degree_of_interest::DegreeOfInterest, topological_sort::DependencyOrder}, integration::{graphml::write_graphml, rdf_patch::{PatchOperation, PatchResult}, stress_data::StressDataConfig, turtle::RdfExportFormat}, domain::{data_quality::DataQualityReport, edge_metadata::EdgeMetadataStyle, wikidata::WikidataStatements, commands::DataHistory, data_change::{DataChange, DataChanges}, type_samples::TypeSamples, dataset_diff::DatasetDiff, semantic_zoom::SemanticZoomSettings, size_mapping::SizeMapping, style_rules::StyleRules, app_log::init_logger, import_log::ImportLog, graph_model::LanguageFallback, node_comparison::NodeComparison, session_statistics::SessionStatistics, type_index::{IndexedTypes, TableLayout, TypeInstanceIndex}, type_metric_summary::TypeMetricSummary, visual_query::VisualQuery}, ui::{dcat::DcatBrowser, prefix_manager::PrefixSuggestions, social_chart::SocialChart, map_view::MapView, timeline_view::TimelineView, property_distribution::{PropertyDistribution, StatisticsPanel}, csv_import::CsvImportWizard, graph_snapshots::SnapshotPanel, reference_resolver::ReferenceResolver, node_list::NodeList, reset_settings::ResetSettings, log_console::LogConsole, metric_correlation::MetricCorrelation, style::*, transitive_closure::TransitiveClosure}, uistate::{graph_legend::GraphLegend, statistics_job::BetweennessJob}};
 use anyhow::Error;
 use eframe::{Frame, Storage};
-use egui::{Key, Rangef, Rect, Ui};
+use egui::{Key, Ui};
 use egui_extras::StripBuilder;
 use string_interner::Symbol;
 
@@ -26,13 +25,23 @@ use crate::uistate::File;
 const SAMPLE_DATA: &[u8] = include_bytes!("../../sample-rdf-data/programming_languages.ttl");
 
 #[cfg(not(target_arch = "wasm32"))]
-use crate::ui::sparql_dialog::SparqlDialog;
+use crate::ui::sparql_dialog::{SparqlDialog, SparqlQueryEditor, SparqlUpdatePush};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::ui::bundle_dialog::BundleExport;
+use crate::ui::png_export::PngExportRequest;
+use crate::integration::png::PngExport;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::integration::layout_journal::{JournalContent, LayoutJournal};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::integration::deep_link::{DeepLink, native::DeepLinkListener};
 use crate::{
     DisplayType, IriIndex, SystemMessage,
     domain::{
         LangIndex, NodeChangeContext, NodeData, RdfData,
         app_persistence::AppPersistentData,
-        config::Config,
+        blank_node_tree::is_blank_node,
+        recent_items::RecentKind,
+        config::{Config, ConfigOverrides},
         graph_styles::{GVisualizationStyle, NodeStyle},
         prefix_manager::PrefixManager,
         statistics::StatisticsData,
@@ -41,11 +50,11 @@ use crate::{
     support::uitools::primary_color,
     ui::{
         graph_view::{NeighborPos, update_layout_edges},
-        style::{ICON_DELETE, ICON_OPEN_FOLDER},
+        style::ICON_OPEN_FOLDER,
     },
     uistate::{
-        DataLoading, GraphState, ImportFormat, ImportFromUrlData, LastVisitedSelection, LoadResult, UIState,
-        actions::NodeAction, layout::SortedNodeLayout, ref_selection::RefSelection,
+        DataLoading, ExportScope, GraphState, ImportFormat, ImportFromUrlData, LoadResult, PatchPreview, UIState,
+        actions::NodeAction, layout::SortedNodeLayout, project_template::ProjectTemplate, ref_selection::RefSelection,
     },
 };
 
@@ -60,6 +69,8 @@ pub struct RdfGlanceApp {
     pub ui_state: UIState,
     pub visible_nodes: SortedNodeLayout,
     pub meta_nodes: SortedNodeLayout,
+    // example instances shown in the meta graph
+    pub meta_samples: TypeSamples,
     pub graph_state: GraphState,
     pub meta_graph_state: GraphState,
     pub visualization_style: GVisualizationStyle,
@@ -68,36 +79,119 @@ pub struct RdfGlanceApp {
     pub reference_resolver: ReferenceResolver,
     #[cfg(not(target_arch = "wasm32"))]
     pub sparql_dialog: Option<SparqlDialog>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub sparql_query: Option<SparqlQueryEditor>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub sparql_update_push: Option<SparqlUpdatePush>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub bundle_export: Option<BundleExport>,
+    // files dropped while data are loaded, waiting for replace or merge
+    #[cfg(not(target_arch = "wasm32"))]
+    pub dropped_files: Option<Vec<String>>,
+    pub png_export: Option<PngExport>,
+    pub png_export_request: Option<PngExportRequest>,
+    // counts of the legend, recomputed if the visible nodes change
+    pub graph_legend: GraphLegend,
+    pub graph_snapshots: Option<SnapshotPanel>,
+    pub show_language_panel: bool,
+    // running betweenness centrality computation
+    pub betweenness_job: Option<BetweennessJob>,
+    // free text notes of the analysis, stored in the analysis bundle
+    pub analysis_notes: String,
+    pub show_analysis_notes: bool,
+    // None in safe mode or if there is no storage directory
+    #[cfg(not(target_arch = "wasm32"))]
+    pub layout_journal: Option<LayoutJournal>,
+    // journal of the previous session offered for restore, the journal is not written until restored or discarded
+    #[cfg(not(target_arch = "wasm32"))]
+    pub journal_restore: Option<JournalContent>,
     pub status_message: String,
     pub system_message: SystemMessage,
     pub rdf_data: Arc<RwLock<RdfData>>,
     pub type_index: TypeInstanceIndex,
     pub persistent_data: AppPersistentData,
+    // Effective config: global config from persistent data with project overrides
+    pub config: Config,
+    pub project_config: ConfigOverrides,
     pub help_open: bool,
     pub load_handle: Option<JoinHandle<Option<Result<LoadResult, Error>>>>,
     #[cfg(target_arch = "wasm32")]
     pub file_upload: Option<poll_promise::Promise<Result<File, anyhow::Error>>>,
     pub data_loading: Option<Arc<DataLoading>>,
+    pub import_log: ImportLog,
+    pub session_statistics: SessionStatistics,
     pub import_from_url: Option<ImportFromUrlData>,
+    pub patch_preview: Option<PatchPreview>,
+    pub pending_template: Option<ProjectTemplate>,
+    // table layout read from a project file, applied after the type index is rebuilt
+    pub pending_table_layout: Option<TableLayout>,
+    pub node_comparison: Option<NodeComparison>,
+    pub dataset_diff: Option<DatasetDiff>,
+    pub data_quality: Option<DataQualityReport>,
+    pub cycle_search: Option<CycleSearch>,
+    // semantic fisheye filter of the visual graph (active if set)
+    pub degree_of_interest: Option<DegreeOfInterest>,
+    pub dependency_order: Option<DependencyOrder>,
+    pub transitive_closure: Option<TransitiveClosure>,
+    pub metric_correlation: Option<MetricCorrelation>,
+    pub type_metric_summary: Option<TypeMetricSummary>,
+    pub stress_data_config: Option<StressDataConfig>,
+    pub edge_metadata_style: Option<EdgeMetadataStyle>,
+    pub wikidata_statements: Option<WikidataStatements>,
+    pub dcat_browser: Option<DcatBrowser>,
+    pub prefix_suggestions: Option<PrefixSuggestions>,
+    pub social_chart: Option<SocialChart>,
+    pub map_view: MapView,
+    pub timeline_view: TimelineView,
+    pub statistics_panel: StatisticsPanel,
+    pub property_distribution: PropertyDistribution,
+    pub csv_import: Option<CsvImportWizard>,
+    pub data_history: DataHistory,
+    pub data_changes: DataChanges,
+    // actions injected by scripts and tests, one is applied per frame like an action of the views
+    pub injected_node_actions: VecDeque<NodeAction>,
+    pub semantic_zoom: Option<SemanticZoomSettings>,
+    pub node_list: Option<NodeList>,
+    pub log_console: Option<LogConsole>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub deep_link_listener: Option<DeepLinkListener>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub pending_deep_link: Option<DeepLink>,
+    // started with --safe-mode, the persisted settings are not loaded and not saved
+    pub safe_mode: bool,
+    pub reset_settings: Option<ResetSettings>,
 }
 
+pub const SAFE_MODE_ARG: &str = "--safe-mode";
+
 // Implement default values for MyApp
 impl RdfGlanceApp {
     pub fn new(storage: Option<&dyn Storage>, args: Vec<String>) -> Self {
+        init_logger();
+        // in safe mode the persisted settings are ignored, they could be corrupted
+        let safe_mode = args.iter().any(|arg| arg == SAFE_MODE_ARG);
+        let args: Vec<String> = args.into_iter().filter(|arg| arg != SAFE_MODE_ARG).collect();
         let persistent_data: Option<AppPersistentData> = match storage {
-            Some(storage) => {
+            Some(storage) if !safe_mode => {
                 let persistent_data_string = storage.get_string("persistent_data");
                 if let Some(persistent_data_string) = persistent_data_string {
-                    let mut persistent_data: AppPersistentData =
-                        serde_json::from_str(&persistent_data_string).expect("Failed to parse persistent data");
-                    persistent_data.last_endpoints.retain(|endpoint| !endpoint.is_empty());
-                    Some(persistent_data)
+                    match serde_json::from_str::<AppPersistentData>(&persistent_data_string) {
+                        Ok(mut persistent_data) => {
+                            persistent_data.migrate_recent_items();
+                            Some(persistent_data)
+                        }
+                        Err(e) => {
+                            log::error!("Failed to parse persistent data, using default settings: {}", e);
+                            None
+                        }
+                    }
                 } else {
                     None
                 }
             }
-            None => None,
+            _ => None,
         };
+        let persistent_data = persistent_data.unwrap_or_default();
         let mut app = Self {
             object_iri: String::new(),
             current_iri: None,
@@ -108,17 +202,35 @@ impl RdfGlanceApp {
             display_type: DisplayType::Table,
             #[cfg(not(target_arch = "wasm32"))]
             sparql_dialog: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            sparql_query: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            sparql_update_push: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            bundle_export: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            dropped_files: None,
+            png_export: None,
+            png_export_request: None,
+            graph_legend: GraphLegend::default(),
+            graph_snapshots: None,
+            show_language_panel: false,
+            betweenness_job: None,
+            analysis_notes: String::new(),
+            show_analysis_notes: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            layout_journal: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            journal_restore: None,
             status_message: String::new(),
             type_index: TypeInstanceIndex::new(),
             system_message: SystemMessage::None,
             visible_nodes: SortedNodeLayout::new(),
             meta_nodes: SortedNodeLayout::new(),
-            persistent_data: persistent_data.unwrap_or(AppPersistentData {
-                last_files: vec![],
-                last_endpoints: vec![],
-                last_projects: vec![],
-                config_data: Config::default(),
-            }),
+            meta_samples: TypeSamples::default(),
+            config: persistent_data.config_data.clone(),
+            project_config: ConfigOverrides::default(),
+            persistent_data,
             rdf_data: Arc::new(RwLock::new(RdfData {
                 node_data: NodeData::new(),
                 prefix_manager: PrefixManager::new(),
@@ -126,6 +238,9 @@ impl RdfGlanceApp {
             visualization_style: GVisualizationStyle {
                 node_styles: HashMap::new(),
                 edge_styles: HashMap::new(),
+                tooltip_properties: HashMap::new(),
+                style_rules: StyleRules::default(),
+                size_mapping: SizeMapping::default(),
                 default_node_style: NodeStyle::default(),
                 use_size_overwrite: false,
                 use_color_overwrite: false,
@@ -133,29 +248,84 @@ impl RdfGlanceApp {
                 min_size: 5.0,
                 max_size: 50.0,
             },
-            graph_state: GraphState { scene_rect: Rect::ZERO },
-            meta_graph_state: GraphState { scene_rect: Rect::ZERO },
+            graph_state: GraphState::default(),
+            meta_graph_state: GraphState::default(),
             statistics_data: None,
             visual_query: VisualQuery::default(),
             ui_state: UIState::default(),
             help_open: false,
             load_handle: None,
             data_loading: None,
+            import_log: ImportLog::default(),
+            session_statistics: SessionStatistics::default(),
+            node_comparison: None,
+            dataset_diff: None,
+            data_quality: None,
+            cycle_search: None,
+            degree_of_interest: None,
+            dependency_order: None,
+            transitive_closure: None,
+            metric_correlation: None,
+            type_metric_summary: None,
+            stress_data_config: None,
+            edge_metadata_style: None,
+            wikidata_statements: None,
+            dcat_browser: None,
+            prefix_suggestions: None,
+            social_chart: None,
+            map_view: MapView::default(),
+            timeline_view: TimelineView::default(),
+            statistics_panel: StatisticsPanel::GraphStatistics,
+            property_distribution: PropertyDistribution::default(),
+            csv_import: None,
+            data_history: DataHistory::default(),
+            data_changes: DataChanges::default(),
+            injected_node_actions: VecDeque::new(),
+            semantic_zoom: None,
+            node_list: None,
+            log_console: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            deep_link_listener: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_deep_link: None,
             #[cfg(target_arch = "wasm32")]
             file_upload: None,
             import_from_url: None,
+            patch_preview: None,
+            pending_template: None,
+            pending_table_layout: None,
             reference_resolver: ReferenceResolver::default(),
+            safe_mode,
+            reset_settings: None,
         };
+        app.visible_nodes.flatten_blank_nodes = app.config.flatten_blank_nodes;
+        if safe_mode {
+            app.set_status_message("Safe mode: settings are not loaded and not saved");
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if app.config.deep_links {
+            app.start_deep_link_listener();
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if !safe_mode {
+            app.open_layout_journal();
+        }
         #[cfg(not(target_arch = "wasm32"))]
         if !args.is_empty() {
             let first_arg = args[0].as_str();
-            // TODO does not know the dark mode yet.
-            app.load_ttl(first_arg, false);
+            if DeepLink::is_deep_link(first_arg) {
+                app.pending_deep_link = DeepLink::parse(first_arg);
+            } else if first_arg.starts_with("http://") || first_arg.starts_with("https://") {
+                app.load_ttl_from_url(first_arg, ImportFormat::Auto, false);
+            } else {
+                // TODO does not know the dark mode yet.
+                app.load_ttl(first_arg, false);
+            }
         }
         #[cfg(target_arch = "wasm32")]
         if args.len() > 0 {
             let first_arg = args[0].as_str();
-            app.load_ttl_from_url(first_arg, ImportFormat::Turtle, true);
+            app.load_ttl_from_url(first_arg, ImportFormat::Auto, true);
         }
         app
     }
@@ -195,6 +365,9 @@ impl RdfGlanceApp {
                 return;
             }
         }
+        if let Ok(mut rdf_data) = self.rdf_data.write() {
+            rdf_data.resolve_node(index, &mut self.rdfwrap);
+        }
         if let Ok(rdf_data) = self.rdf_data.read() {
             let node = rdf_data.node_data.get_node_by_index(index);
             if let Some((node_iri, current_node)) = node {
@@ -206,6 +379,7 @@ impl RdfGlanceApp {
                     self.nav_pos = self.nav_history.len() - 1;
                 }
                 self.ref_selection.init_from_node(current_node);
+                self.session_statistics.record_node(index, &current_node.types);
             }
         }
     }
@@ -238,9 +412,10 @@ impl RdfGlanceApp {
     #[cfg(target_arch = "wasm32")]
     pub fn load_ttl(&mut self, file_name: &str, is_dark_mode: bool) {
         use crate::integration::rdfwrap::RDFWrap;
-        let language_filter = self.persistent_data.config_data.language_filter();
+        self.record_data_change(&self.load_description(file_name));
+        let language_filter = self.config.language_filter();
         let rdfttl = if let Ok(mut rdf_data) = self.rdf_data.write() {
-            Some(RDFWrap::load_file(file_name, &mut rdf_data, &language_filter, None))
+            Some(RDFWrap::load_file(file_name, &mut *rdf_data, &language_filter, None))
         } else {
             None
         };
@@ -253,10 +428,9 @@ impl RdfGlanceApp {
                 Ok(triples_count) => {
                     let load_message = format!("Loaded: {} triples: {}", file_name, triples_count);
                     self.set_status_message(&load_message);
-                    if !self.persistent_data.last_files.iter().any(|f| *f == file_name.into()) {
-                        self.persistent_data.last_files.push(file_name.into());
-                    }
+                    self.persistent_data.recent_items.touch(RecentKind::File, file_name);
                     self.update_data_indexes(is_dark_mode);
+                    self.apply_pending_template();
                 }
             }
         }
@@ -264,7 +438,7 @@ impl RdfGlanceApp {
 
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_ttl(&mut self, file_name: &str, _is_dark_mode: bool) {
-        use std::{sync::atomic::AtomicUsize, thread};
+        use std::thread;
 
         use crate::uistate::DataLoading;
 
@@ -272,37 +446,30 @@ impl RdfGlanceApp {
             self.system_message = SystemMessage::Info("Loading in progress".to_string());
             return;
         }
+        self.record_data_change(&self.load_description(file_name));
         let rdf_data_clone = Arc::clone(&self.rdf_data);
-        let language_filter = self.persistent_data.config_data.language_filter();
+        let language_filter = self.config.language_filter();
         let file_name_cpy = file_name.to_string();
-        let data_loading = Arc::new(DataLoading {
-            stop_loading: Arc::new(AtomicBool::new(false)),
-            progress: Arc::new(AtomicUsize::new(0)),
-            total_triples: Arc::new(AtomicUsize::new(0)),
-            read_pos: Arc::new(AtomicUsize::new(0)),
-            total_size: Arc::new(AtomicUsize::new(0)),
-            finished: Arc::new(AtomicBool::new(false)),
-        });
+        let data_loading = Arc::new(DataLoading::new());
         let data_loading_clone = Arc::clone(&data_loading);
         self.data_loading = Some(data_loading);
+        let resolve_rdf_lists = self.config.resolve_rdf_lists;
         let handle = thread::spawn(move || {
             let my_data_loading = data_loading_clone.as_ref();
-            let erg = if let Ok(mut rdf_data) = rdf_data_clone.write() {
-                Some(
-                    RDFWrap::load_file(
-                        file_name_cpy.as_str(),
-                        &mut rdf_data,
-                        &language_filter,
-                        Some(my_data_loading),
-                    )
-                    .map(|triples_count| LoadResult {
-                        triples_count,
-                        file_name: Some(file_name_cpy),
-                    }),
+            // the data are locked by the loader for each chunk of triples
+            let erg = Some(
+                RDFWrap::load_file(
+                    file_name_cpy.as_str(),
+                    rdf_data_clone.as_ref(),
+                    &language_filter,
+                    Some(my_data_loading),
                 )
-            } else {
-                None
-            };
+                .map(|triples_count| LoadResult {
+                    triples_count,
+                    file_names: vec![file_name_cpy],
+                    indexed_types: index_loaded_data(&rdf_data_clone, resolve_rdf_lists, my_data_loading),
+                }),
+            );
             my_data_loading.finished.store(true, Ordering::Relaxed);
             erg
         });
@@ -311,46 +478,38 @@ impl RdfGlanceApp {
 
     #[cfg(not(target_arch = "wasm32"))]
     pub fn load_ttl_from_url(&mut self, url: &str, format: ImportFormat, _is_dark_mode: bool) {
-        use std::{sync::atomic::AtomicUsize, thread};
+        use std::thread;
 
-        use crate::uistate::DataLoading;
+        use crate::{integration::remote::load_from_url, uistate::DataLoading};
 
         if self.load_handle.is_some() {
             self.system_message = SystemMessage::Info("Loading in progress".to_string());
             return;
         }
+        self.record_data_change(&self.load_description(url));
         let rdf_data_clone = Arc::clone(&self.rdf_data);
-        let language_filter = self.persistent_data.config_data.language_filter();
+        let language_filter = self.config.language_filter();
         let url_cpy = url.to_string();
-        let data_loading = Arc::new(DataLoading {
-            stop_loading: Arc::new(AtomicBool::new(false)),
-            progress: Arc::new(AtomicUsize::new(0)),
-            total_triples: Arc::new(AtomicUsize::new(0)),
-            read_pos: Arc::new(AtomicUsize::new(0)),
-            total_size: Arc::new(AtomicUsize::new(0)),
-            finished: Arc::new(AtomicBool::new(false)),
-        });
+        let data_loading = Arc::new(DataLoading::new());
         let data_loading_clone = Arc::clone(&data_loading);
         self.data_loading = Some(data_loading);
+        let resolve_rdf_lists = self.config.resolve_rdf_lists;
         let handle = thread::spawn(move || {
             let my_data_loading = data_loading_clone.as_ref();
-            let erg = if let Ok(mut rdf_data) = rdf_data_clone.write() {
-                Some(
-                    RDFWrap::load_from_url(
-                        url_cpy.as_ref(),
-                        &mut rdf_data,
-                        &language_filter,
-                        format,
-                        Some(my_data_loading),
-                    )
-                    .map(|triples_count| LoadResult {
-                        triples_count,
-                        file_name: None,
-                    }),
+            let erg = Some(
+                load_from_url(
+                    url_cpy.as_ref(),
+                    rdf_data_clone.as_ref(),
+                    &language_filter,
+                    format,
+                    Some(my_data_loading),
                 )
-            } else {
-                None
-            };
+                .map(|triples_count| LoadResult {
+                    triples_count,
+                    file_names: Vec::new(),
+                    indexed_types: index_loaded_data(&rdf_data_clone, resolve_rdf_lists, my_data_loading),
+                }),
+            );
             my_data_loading.finished.store(true, Ordering::Relaxed);
             erg
         });
@@ -358,48 +517,40 @@ impl RdfGlanceApp {
     }
 
     #[cfg(target_arch = "wasm32")]
-    pub fn load_ttl_from_url(&mut self, url: &str, _format: ImportFormat, _is_dark_mode: bool) {
-        let url_cpy = url.to_string();
+    pub fn load_ttl_from_url(&mut self, url: &str, format: ImportFormat, _is_dark_mode: bool) {
+        use crate::integration::remote::fetch_rdf;
         use poll_promise::Promise;
-        self.file_upload = Some(Promise::spawn_local(async move {
-            let client = reqwest::Client::new();
-            let request = client.get(url_cpy.as_str()).header("Accept", "text/turtle");
-            match request.send().await {
-                Ok(resp) => {
-                    if let Ok(bytes) = resp.bytes().await {
-                        return Ok(crate::uistate::File {
-                            path: "url.ttl".to_string(),
-                            data: bytes.to_vec(),
-                        });
-                    }
-                }
-                Err(err) => {
-                    return Err(anyhow::anyhow!("Error downloading from URL {}", err));
-                }
-            }
-            Err(anyhow::anyhow!("Upload: no file Selected"))
-        }));
+
+        let url_cpy = url.to_string();
+        self.file_upload = Some(Promise::spawn_local(async move { fetch_rdf(&url_cpy, format).await }));
     }
 
     pub fn join_load(&mut self, is_dark_mode: bool) {
         if let Some(handle) = self.load_handle.take() {
+            let stopped = self
+                .data_loading
+                .as_ref()
+                .is_some_and(|data_loading| data_loading.stop_loading.load(Ordering::Relaxed));
             match handle.join() {
                 Ok(Some(Ok(load_result))) => {
-                    self.set_status_message(&format!("Loaded {} triples", load_result.triples_count));
-                    self.update_data_indexes(is_dark_mode);
-                    if let Some(file_name) = load_result.file_name {
-                        let file_name_cpy = file_name.into_boxed_str();
-                        if let Some(position) = self.persistent_data.last_files.iter().position(|f| *f == file_name_cpy)
-                        {
-                            self.persistent_data.last_files.remove(position);
-                            self.persistent_data.last_files.insert(0, file_name_cpy);
-                        } else {
-                            self.persistent_data.last_files.insert(0, file_name_cpy);
-                        }
+                    if stopped {
+                        self.system_message = SystemMessage::Info(format!(
+                            "Loading stopped after {} triples. The data loaded so far are kept.",
+                            load_result.triples_count
+                        ));
+                    } else {
+                        self.set_status_message(&format!("Loaded {} triples", load_result.triples_count));
+                    }
+                    self.update_data_indexes_with(is_dark_mode, load_result.indexed_types);
+                    self.apply_pending_template();
+                    for file_name in load_result.file_names.iter() {
+                        self.persistent_data.recent_items.touch(RecentKind::File, file_name);
                     }
                 }
                 Ok(Some(Err(err))) => {
                     self.system_message = SystemMessage::Error(format!("Error loading data: {}", err));
+                    // the chunks merged before the error stay in the data
+                    self.update_data_indexes(is_dark_mode);
                 }
                 Ok(None) => {
                     self.system_message = SystemMessage::Error("Error loading data".to_string());
@@ -408,24 +559,42 @@ impl RdfGlanceApp {
                     self.system_message = SystemMessage::Error("Thread panicked".to_string());
                 }
             }
-            self.data_loading = None;
+            if let Some(data_loading) = self.data_loading.take() {
+                self.collect_import_reports(&data_loading);
+            }
+        }
+    }
+
+    pub fn collect_import_reports(&mut self, data_loading: &DataLoading) {
+        for report in data_loading.take_reports() {
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(layout_journal) = &mut self.layout_journal
+                && report.triples_read > 0
+            {
+                layout_journal.add_source(&report.source);
+            }
+            self.import_log.add(report);
         }
     }
 
     #[cfg(target_arch = "wasm32")]
     pub fn load_ttl_data(&mut self, file_name: &str, data: &Vec<u8>, is_dark_mode: bool) {
         use crate::integration::rdfwrap::RDFWrap;
-        let language_filter = self.persistent_data.config_data.language_filter();
+        self.record_data_change(&self.load_description(file_name));
+        let language_filter = self.config.language_filter();
+        let data_loading = DataLoading::new();
         let rdfttl = if let Ok(mut rdf_data) = self.rdf_data.write() {
             Some(RDFWrap::load_file_data(
                 file_name,
                 data,
-                &mut rdf_data,
+                &mut *rdf_data,
                 &language_filter,
+                Some(&data_loading),
             ))
         } else {
             None
         };
+        self.collect_import_reports(&data_loading);
         if let Some(rdfttl) = rdfttl {
             match rdfttl {
                 Err(err) => {
@@ -435,47 +604,44 @@ impl RdfGlanceApp {
                     let load_message = format!("Loaded: {} triples: {}", file_name, triples_count);
                     self.set_status_message(&load_message);
                     self.update_data_indexes(is_dark_mode);
+                    self.apply_pending_template();
                 }
             }
         }
     }
 
     pub fn load_ttl_dir(&mut self, dir_name: &str) {
+        self.load_ttl_paths(vec![dir_name.to_string()]);
+    }
+
+    /// Loads files and directories one after another into the current data
+    pub fn load_ttl_paths(&mut self, paths: Vec<String>) {
         if self.load_handle.is_some() {
             self.system_message = SystemMessage::Info("Loading in progress".to_string());
             return;
         }
+        let description = match paths.as_slice() {
+            [path] => self.load_description(path),
+            _ if self.is_empty() => format!("Load {} files", paths.len()),
+            _ => format!("Merge {} files", paths.len()),
+        };
+        self.record_data_change(&description);
         let rdf_data_clone = Arc::clone(&self.rdf_data);
-        let language_filter = self.persistent_data.config_data.language_filter();
-        let dir_name_cpy = dir_name.to_string();
-        let data_loading = Arc::new(DataLoading {
-            stop_loading: Arc::new(AtomicBool::new(false)),
-            progress: Arc::new(AtomicUsize::new(0)),
-            total_triples: Arc::new(AtomicUsize::new(0)),
-            read_pos: Arc::new(AtomicUsize::new(0)),
-            total_size: Arc::new(AtomicUsize::new(0)),
-            finished: Arc::new(AtomicBool::new(false)),
-        });
+        let language_filter = self.config.language_filter();
+        let data_loading = Arc::new(DataLoading::new());
         let data_loading_clone = Arc::clone(&data_loading);
         self.data_loading = Some(data_loading);
+        let resolve_rdf_lists = self.config.resolve_rdf_lists;
         let handle = thread::spawn(move || {
             let my_data_loading = data_loading_clone.as_ref();
-            let erg = if let Ok(mut rdf_data) = rdf_data_clone.write() {
-                Some(
-                    RDFWrap::load_from_dir(
-                        dir_name_cpy.as_str(),
-                        &mut rdf_data,
-                        &language_filter,
-                        Some(my_data_loading),
-                    )
-                    .map(|triples_count| LoadResult {
-                        triples_count,
-                        file_name: Some(dir_name_cpy),
-                    }),
-                )
-            } else {
-                None
-            };
+            let erg = Some(
+                RDFWrap::load_paths(&paths, rdf_data_clone.as_ref(), &language_filter, Some(my_data_loading))
+                .map(|triples_count| LoadResult {
+                    triples_count,
+                    file_names: paths,
+                    indexed_types: index_loaded_data(&rdf_data_clone, resolve_rdf_lists, my_data_loading),
+                }),
+            );
             my_data_loading.finished.store(true, Ordering::Relaxed);
             erg
         });
@@ -487,6 +653,11 @@ impl RdfGlanceApp {
         self.status_message.push_str(message);
     }
     pub fn update_data_indexes(&mut self, is_dark_mode: bool) {
+        self.update_data_indexes_with(is_dark_mode, None);
+    }
+
+    /// The type statistics could be already computed (and the data prepared) in the loading thread
+    pub fn update_data_indexes_with(&mut self, is_dark_mode: bool, indexed_types: Option<IndexedTypes>) {
         if let Ok(mut rdf_data) = self.rdf_data.write() {
             self.ui_state.language_sort.clear();
             for (index, _lang) in rdf_data.node_data.indexers.language_indexer.map.iter() {
@@ -498,20 +669,34 @@ impl RdfGlanceApp {
                     .get_language(*a)
                     .cmp(&rdf_data.node_data.get_language(*b))
             });
-            if self.persistent_data.config_data.resolve_rdf_lists {
-                rdf_data.resolve_rdf_lists();
-            }
-            for (_iri, node) in rdf_data.node_data.iter_mut() {
-                node.references.sort_by(|a, b| a.0.cmp(&b.0));
-                node.reverse_references.sort_by(|a, b| a.0.cmp(&b.0));
+            rdf_data.node_data.indexers.language_fallback = LanguageFallback::from_tags(
+                &self.config.language_fallback,
+                self.config.language_fallback_any,
+                &rdf_data.node_data.indexers.language_indexer,
+            );
+            match indexed_types {
+                Some(indexed_types) => self.type_index.apply_indexed_types(indexed_types, &rdf_data.node_data),
+                None => {
+                    rdf_data.prepare_for_index(self.config.resolve_rdf_lists);
+                    self.type_index.update(&rdf_data.node_data);
+                }
             }
-            self.type_index.update(&rdf_data.node_data);
+            self.map_view.clean();
+            self.timeline_view.clean();
+            self.property_distribution.clean();
 
             self.visualization_style.preset_styles(
                 &self.type_index,
                 &rdf_data.node_data.indexers.predicate_indexer,
                 is_dark_mode,
             );
+            if self.config.color_by_namespace {
+                self.visualization_style.assign_namespace_colors(
+                    &rdf_data.node_data.indexers.type_indexer,
+                    &rdf_data.prefix_manager,
+                    is_dark_mode,
+                );
+            }
             rdf_data.node_data.indexers.predicate_indexer.map.shrink_to_fit();
             rdf_data.node_data.indexers.type_indexer.map.shrink_to_fit();
             rdf_data.node_data.indexers.language_indexer.map.shrink_to_fit();
@@ -542,125 +727,8 @@ impl RdfGlanceApp {
                 );
             }
         }
-        let mut enter_pressed = false;
-        let mut delete_pressed = false;
-        if let LastVisitedSelection::File(i) = self.ui_state.last_visited_selection {
-            if i >= self.persistent_data.last_files.len() {
-                self.ui_state.last_visited_selection =
-                    LastVisitedSelection::File(self.persistent_data.last_files.len() - 1);
-            }
-        }
-        if matches!(self.ui_state.last_visited_selection, LastVisitedSelection::None)
-            && self.persistent_data.last_files.len() > 0
-        {
-            self.ui_state.last_visited_selection = LastVisitedSelection::File(0);
-        }
-
-        ui.input(|i| {
-            if i.key_pressed(egui::Key::Enter) {
-                enter_pressed = true;
-            } else if i.key_pressed(egui::Key::Delete) {
-                delete_pressed = true;
-            } else if i.key_pressed(egui::Key::ArrowUp) {
-                if let LastVisitedSelection::File(i) = self.ui_state.last_visited_selection {
-                    if i > 0 {
-                        self.ui_state.last_visited_selection = LastVisitedSelection::File(i - 1);
-                    }
-                }
-            } else if i.key_pressed(egui::Key::ArrowDown) {
-                if let LastVisitedSelection::File(i) = self.ui_state.last_visited_selection {
-                    if i + 1 < self.persistent_data.last_files.len() {
-                        self.ui_state.last_visited_selection = LastVisitedSelection::File(i + 1);
-                    }
-                }
-            }
-        });
-        StripBuilder::new(ui)
-            .size(egui_extras::Size::Relative {
-                fraction: 0.5,
-                range: Rangef::EVERYTHING,
-            })
-            .size(egui_extras::Size::Relative {
-                fraction: 0.5,
-                range: Rangef::EVERYTHING,
-            })
-            .horizontal(|mut strip| {
-                strip.cell(|ui| {
-                    if !self.persistent_data.last_files.is_empty() {
-                        let mut last_file_clicked: Option<String> = None;
-                        let mut last_file_forget: Option<String> = None;
-                        ui.spacing();
-                        ui.heading("Last imported files:");
-                        egui::ScrollArea::vertical().show(ui, |ui| {
-                            egui::Grid::new("lfiles").striped(true).show(ui, |ui| {
-                                for (index,last_file) in self.persistent_data.last_files.iter().enumerate() {
-                                    if matches!(self.ui_state.last_visited_selection, LastVisitedSelection::File(i) if i == index) {
-                                        let painter = ui.painter();
-                                        painter.rect_filled(ui.available_rect_before_wrap(), 0.0, ui.visuals().selection.bg_fill);
-                                        if enter_pressed {
-                                            last_file_clicked = Some(last_file.to_string());
-                                        } else if delete_pressed {
-                                            last_file_forget = Some(last_file.to_string());
-                                        }
-                                    }
-                                    if ui.button(last_file).clicked() {
-                                        last_file_clicked = Some(last_file.to_string());
-                                    }
-                                    if ui.button(ICON_DELETE).clicked() {
-                                        last_file_forget = Some(last_file.to_string());
-                                    }
-                                    ui.end_row();
-                                }
-                            });
-                        });
-                        if let Some(last_file_clicked) = last_file_clicked {
-                            let path = Path::new(last_file_clicked.as_str());
-                            if path.exists() {
-                                if path.is_dir() {
-                                    self.load_ttl_dir(&last_file_clicked);
-                                } else {
-                                    self.load_ttl(&last_file_clicked, ui.visuals().dark_mode);
-                                }
-                            }
-                        }
-                        if let Some(last_file_forget) = last_file_forget {
-                            self.persistent_data
-                                .last_files
-                                .retain(|file| *file != last_file_forget.as_str().into());
-                        }
-                    }
-                });
-                strip.cell(|ui| {
-                    if !self.persistent_data.last_files.is_empty() {
-                        let mut last_file_clicked: Option<String> = None;
-                        let mut last_file_forget: Option<String> = None;
-                        ui.spacing();
-                        ui.heading("Last visited projects:");
-                        egui::ScrollArea::vertical().show(ui, |ui| {
-                            egui::Grid::new("lprojects").striped(true).show(ui, |ui| {
-                                for last_file in &self.persistent_data.last_projects {
-                                    if ui.button(last_file).clicked() {
-                                        last_file_clicked = Some(last_file.to_string());
-                                    }
-                                    if ui.button(ICON_DELETE).clicked() {
-                                        last_file_forget = Some(last_file.to_string());
-                                    }
-                                    ui.end_row();
-                                }
-                            });
-                        });
-                        if let Some(last_file_clicked) = last_file_clicked {
-                            let last_project_path = Path::new(&*last_file_clicked);
-                            self.load_project(last_project_path, ui.visuals().dark_mode);
-                        }
-                        if let Some(last_file_forget) = last_file_forget {
-                            self.persistent_data
-                                .last_projects
-                                .retain(|file| *file != last_file_forget.as_str().into());
-                        }
-                    }
-                });
-            });
+        ui.add_space(20.0);
+        self.show_recent_items(ui);
     }
     pub fn is_empty(&self) -> bool {
         self.rdf_data.read().unwrap().node_data.len() == 0
@@ -681,7 +749,205 @@ impl RdfGlanceApp {
         });
         self.visible_nodes.clear();
         self.meta_nodes.clear();
+        self.meta_samples.clear();
         self.visual_query.clean();
+        self.close_data_views();
+        self.semantic_zoom = None;
+        self.node_list = None;
+        self.project_config = ConfigOverrides::default();
+        self.update_config();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(layout_journal) = &mut self.layout_journal {
+            layout_journal.reset();
+        }
+    }
+
+    /// Closes the analysis windows that refer to nodes of the data
+    fn close_data_views(&mut self) {
+        self.node_comparison = None;
+        self.dataset_diff = None;
+        self.data_quality = None;
+        self.cycle_search = None;
+        self.degree_of_interest = None;
+        self.dependency_order = None;
+        self.transitive_closure = None;
+        self.metric_correlation = None;
+        self.type_metric_summary = None;
+        self.edge_metadata_style = None;
+        self.wikidata_statements = None;
+        self.dcat_browser = None;
+        self.social_chart = None;
+    }
+
+    pub fn notify_data_change(&self, change: DataChange) {
+        self.data_changes.notify(change);
+    }
+
+    /**
+     * Updates the structures that depend on the data for all changes notified since the last frame.
+     * Any data change rebuilds the whole type index and refreshes the meta graph (see DataChange::changes_data),
+     * more changes in one frame rebuild it only once.
+     */
+    pub fn process_data_changes(&mut self, is_dark_mode: bool) {
+        let changes = self.data_changes.take();
+        if changes.is_empty() {
+            return;
+        }
+        if changes.iter().any(|change| change.changes_data()) {
+            self.update_data_indexes(is_dark_mode);
+            self.refresh_meta_graph();
+        }
+        if changes.contains(&DataChange::StyleChanged) {
+            self.visible_nodes.update_node_shapes = true;
+            self.meta_nodes.update_node_shapes = true;
+            self.visible_nodes
+                .update_edge_forces(self.visualization_style.edge_layout_forces());
+        }
+    }
+
+    /// Keeps a copy of the data before a data changing operation for undo (if enabled)
+    pub fn record_data_change(&mut self, description: &str) {
+        if let Ok(rdf_data) = self.rdf_data.read()
+            && !self.data_history.record_snapshot(description, &rdf_data, &self.config)
+        {
+            log::info!("No undo for {}, data copies are disabled or the data are too big", description);
+        }
+    }
+
+    /// Takes the triple operations that changed the data for undo, node_count is the number of nodes before
+    pub fn record_triple_change(
+        &mut self,
+        description: &str,
+        node_count: usize,
+        result: &mut PatchResult,
+        is_edit: bool,
+    ) {
+        let operations = std::mem::take(&mut result.applied);
+        let deleted_graphs = std::mem::take(&mut result.deleted_graphs);
+        if !self
+            .data_history
+            .record_triples(description, node_count, operations, deleted_graphs, is_edit, &self.config)
+        {
+            log::info!("No undo for {}, undo is disabled", description);
+        }
+    }
+
+    /// Applies the edit operations, they are recorded in the change set and for undo
+    pub fn edit_data_triples(&mut self, description: &str, operations: Vec<PatchOperation>) -> Option<PatchResult> {
+        let mut operations = operations;
+        let (node_count, mut result) = self.mut_rdf_data(|rdf_data| {
+            let node_count = rdf_data.node_data.len();
+            (node_count, rdf_data.edit_triples(std::mem::take(&mut operations)))
+        })?;
+        self.record_triple_change(description, node_count, &mut result, true);
+        Some(result)
+    }
+
+    fn load_description(&self, source: &str) -> String {
+        let name = source
+            .rsplit(['/', '\\'])
+            .find(|part| !part.is_empty())
+            .unwrap_or(source);
+        if self.is_empty() {
+            format!("Load {}", name)
+        } else {
+            format!("Merge {}", name)
+        }
+    }
+
+    pub fn undo_data_change(&mut self, is_dark_mode: bool) {
+        let description = match self.rdf_data.write() {
+            Ok(mut rdf_data) => self.data_history.undo(&mut rdf_data),
+            Err(_) => None,
+        };
+        if let Some(description) = description {
+            self.data_restored(is_dark_mode);
+            self.set_status_message(&format!("Undo: {}", description));
+        }
+    }
+
+    pub fn redo_data_change(&mut self, is_dark_mode: bool) {
+        let description = match self.rdf_data.write() {
+            Ok(mut rdf_data) => self.data_history.redo(&mut rdf_data),
+            Err(_) => None,
+        };
+        if let Some(description) = description {
+            self.data_restored(is_dark_mode);
+            self.set_status_message(&format!("Redo: {}", description));
+        }
+    }
+
+    /// Removes the nodes that do not exist in the restored data from the views and rebuilds the indexes
+    fn data_restored(&mut self, is_dark_mode: bool) {
+        let node_count = self.rdf_data.read().map(|rdf_data| rdf_data.node_data.len()).unwrap_or(0);
+        let exists = |node_index: &IriIndex| (*node_index as usize) < node_count;
+        self.close_data_views();
+        self.statistics_data = None;
+        self.meta_nodes.clear();
+        self.meta_samples.clear();
+        self.visible_nodes.retain(&self.ui_state.hidden_predicates, false, |node_layout| {
+            exists(&node_layout.node_index)
+        });
+        self.ui_state.selected_nodes.retain(exists);
+        if self.ui_state.selected_node.is_some_and(|node_index| !exists(&node_index)) {
+            self.ui_state.selected_node = None;
+        }
+        self.nav_history.retain(exists);
+        self.nav_pos = self.nav_pos.min(self.nav_history.len().saturating_sub(1));
+        if self.current_iri.is_some_and(|node_index| !exists(&node_index)) {
+            self.current_iri = None;
+            self.object_iri.clear();
+        }
+        self.update_data_indexes(is_dark_mode);
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            self.visible_nodes.refresh_edges(&rdf_data.node_data, &self.ui_state.hidden_predicates);
+        }
+        if node_count == 0 {
+            self.display_type = DisplayType::Table;
+        }
+    }
+
+    pub fn update_config(&mut self) {
+        self.config = self.persistent_data.config_data.with_overrides(&self.project_config);
+        self.apply_language_fallback();
+        if self.visible_nodes.flatten_blank_nodes != self.config.flatten_blank_nodes {
+            self.visible_nodes.flatten_blank_nodes = self.config.flatten_blank_nodes;
+            self.update_blank_node_flattening();
+        }
+    }
+
+    /// Generated type colors are assigned again after the color by namespace setting was changed
+    pub fn update_type_colors(&mut self, is_dark_mode: bool) {
+        if self.config.color_by_namespace {
+            if let Ok(rdf_data) = self.rdf_data.read() {
+                self.visualization_style.assign_namespace_colors(
+                    &rdf_data.node_data.indexers.type_indexer,
+                    &rdf_data.prefix_manager,
+                    is_dark_mode,
+                );
+            }
+        } else {
+            self.visualization_style.assign_type_colors(is_dark_mode);
+        }
+        self.data_changes.notify(DataChange::StyleChanged);
+    }
+
+    /// Removes the blank nodes from the visual graph if they are flattened and rebuilds the edges
+    fn update_blank_node_flattening(&mut self) {
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            if self.visible_nodes.flatten_blank_nodes {
+                self.visible_nodes.retain(&self.ui_state.hidden_predicates, false, |node_layout| {
+                    !is_blank_node(&rdf_data.node_data, node_layout.node_index)
+                });
+            }
+            self.visible_nodes
+                .refresh_edges(&rdf_data.node_data, &self.ui_state.hidden_predicates);
+        }
+    }
+
+    pub fn store_layout_forces(&mut self) {
+        self.project_config
+            .store_layout_forces(&self.config, &mut self.persistent_data.config_data);
     }
 
     pub fn mut_rdf_data<R>(&mut self, mut mutator: impl FnMut(&mut RdfData) -> R) -> Option<R> {
@@ -702,7 +968,132 @@ impl RdfGlanceApp {
         NodeChangeContext {
             rdfwrap: &mut self.rdfwrap,
             visible_nodes: &mut self.visible_nodes,
-            config: &self.persistent_data.config_data,
+            config: &self.config,
+        }
+    }
+
+    /// Nodes of the export scope, all nodes of the data, the filtered table instances or the visible graph
+    fn export_nodes_of_scope(&self, scope: ExportScope) -> Vec<IriIndex> {
+        match scope {
+            ExportScope::AllData => {
+                let nodes_len = self.rdf_data.read().map_or(0, |rdf_data| rdf_data.node_data.len());
+                (0..nodes_len as IriIndex).collect()
+            }
+            ExportScope::TypeInstances(type_index) => self
+                .type_index
+                .types
+                .get(&type_index)
+                .map(|type_data| type_data.filtered_instances.clone())
+                .unwrap_or_default(),
+            ExportScope::VisualGraph => self
+                .visible_nodes
+                .layout_data
+                .read()
+                .map(|layout| layout.nodes.iter().map(|node| node.node_index).collect())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn export_rdf_dialog(&mut self, scope: ExportScope, format: RdfExportFormat) {
+        use crate::integration::turtle::write_rdf;
+
+        let nodes = self.export_nodes_of_scope(scope);
+        let file_name = format!("{}.{}", scope.file_name(), format.file_extension());
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = FileDialog::new()
+            .add_filter(format.label(), &[format.file_extension()])
+            .set_file_name(&file_name)
+            .save_file()
+            && let Ok(rdf_data) = self.rdf_data.read()
+        {
+            use std::{fs::File, io::BufWriter};
+            let file = File::create(path);
+            if let Ok(file) = file {
+                let mut writer = BufWriter::new(file);
+                let store_res = write_rdf(
+                    &mut writer,
+                    &rdf_data.node_data,
+                    &rdf_data.prefix_manager,
+                    &nodes,
+                    format,
+                    &self.config.turtle_style,
+                );
+                if let Err(e) = store_res {
+                    self.system_message = SystemMessage::Error(format!("Can not export {}: {}", format.label(), e));
+                }
+            } else {
+                self.system_message = SystemMessage::Error(format!("Can not save {}", format.label()));
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            let mut buf = Vec::new();
+            let store_res = write_rdf(
+                &mut buf,
+                &rdf_data.node_data,
+                &rdf_data.prefix_manager,
+                &nodes,
+                format,
+                &self.config.turtle_style,
+            );
+            match store_res {
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not export {}: {}", format.label(), e));
+                }
+                Ok(_) => {
+                    use crate::support::uitools::web_download;
+                    let _ = web_download(&file_name, &buf);
+                }
+            }
+        }
+    }
+
+    pub fn export_shacl_dialog(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = FileDialog::new()
+            .add_filter("Turtle", &["ttl"])
+            .set_file_name("shapes.ttl")
+            .save_file()
+            && let Ok(rdf_data) = self.rdf_data.read()
+        {
+            use crate::integration::shacl::write_shacl;
+            use std::fs::File;
+            let file = File::create(path);
+            if let Ok(mut file) = file {
+                let store_res = write_shacl(
+                    &mut file,
+                    &self.type_index,
+                    &rdf_data.node_data,
+                    &rdf_data.prefix_manager,
+                    &self.config.turtle_style,
+                );
+                if let Err(e) = store_res {
+                    self.system_message = SystemMessage::Error(format!("Can not export SHACL shapes: {}", e));
+                }
+            } else {
+                self.system_message = SystemMessage::Error("Can not save SHACL shapes".to_string());
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            use crate::integration::shacl::write_shacl;
+            let mut buf = Vec::new();
+            let store_res = write_shacl(
+                &mut buf,
+                &self.type_index,
+                &rdf_data.node_data,
+                &rdf_data.prefix_manager,
+                &self.config.turtle_style,
+            );
+            match store_res {
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not export SHACL shapes: {}", e));
+                }
+                Ok(_) => {
+                    use crate::support::uitools::web_download;
+                    let _ = web_download("shapes.ttl", &buf);
+                }
+            }
         }
     }
 
@@ -718,7 +1109,7 @@ impl RdfGlanceApp {
                 use std::fs::File;
                 let label_context = LabelContext::new(
                     self.ui_state.display_language,
-                    self.persistent_data.config_data.iri_display,
+                    self.config.iri_display,
                     &rdf_data.prefix_manager,
                 );
                 let file = File::create(path);
@@ -740,7 +1131,7 @@ impl RdfGlanceApp {
             use crate::domain::graph_model::LabelContext;
             let label_context = LabelContext::new(
                 self.ui_state.display_language,
-                self.persistent_data.config_data.iri_display,
+                self.config.iri_display,
                 &rdf_data.prefix_manager,
             );
             let mut buf = Vec::new();
@@ -756,6 +1147,123 @@ impl RdfGlanceApp {
             }
         }
     }
+
+    pub fn export_meta_svg_dialog(&mut self) {
+        use crate::domain::LabelContext;
+        let rdf_data = self.rdf_data.clone();
+        let Ok(rdf_data) = rdf_data.read() else {
+            return;
+        };
+        let label_context = LabelContext::new(
+            self.ui_state.display_language,
+            self.config.iri_display,
+            &rdf_data.prefix_manager,
+        );
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = FileDialog::new()
+            .add_filter("SVG", &["svg"])
+            .set_file_name("meta_graph.svg")
+            .save_file()
+        {
+            match std::fs::File::create(path) {
+                Ok(mut file) => {
+                    if let Err(e) = self.export_meta_svg(&mut file, &rdf_data.node_data, &label_context) {
+                        self.system_message = SystemMessage::Error(format!("Can not export svg: {}", e));
+                    }
+                }
+                Err(_) => {
+                    self.system_message = SystemMessage::Error("Can not save svg".to_string());
+                }
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            let mut buf = Vec::new();
+            match self.export_meta_svg(&mut buf, &rdf_data.node_data, &label_context) {
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not export svg: {}", e));
+                }
+                Ok(_) => {
+                    use crate::support::uitools::web_download;
+                    let _ = web_download("meta_graph.svg", &buf);
+                }
+            }
+        }
+    }
+
+    pub fn export_graphml_dialog(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = FileDialog::new()
+            .add_filter("GraphML", &["graphml"])
+            .set_file_name("graph.graphml")
+            .save_file()
+            && let Ok(rdf_data) = self.rdf_data.read()
+        {
+            use crate::domain::LabelContext;
+            use std::fs::File;
+            let label_context = LabelContext::new(
+                self.ui_state.display_language,
+                self.config.iri_display,
+                &rdf_data.prefix_manager,
+            );
+            let file = File::create(path);
+            if let Ok(mut file) = file {
+                if let Err(e) = self.export_graphml(&mut file, &rdf_data.node_data, &label_context) {
+                    self.system_message = SystemMessage::Error(format!("Can not export GraphML: {}", e));
+                }
+            } else {
+                self.system_message = SystemMessage::Error("Can not save GraphML".to_string());
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        if let Ok(rdf_data) = self.rdf_data.read() {
+            use crate::domain::graph_model::LabelContext;
+            let label_context = LabelContext::new(
+                self.ui_state.display_language,
+                self.config.iri_display,
+                &rdf_data.prefix_manager,
+            );
+            let mut buf = Vec::new();
+            let store_res = self.export_graphml(&mut buf, &rdf_data.node_data, &label_context);
+            match store_res {
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not export GraphML: {}", e));
+                }
+                Ok(_) => {
+                    use crate::support::uitools::web_download;
+                    let _ = web_download("graph.graphml", &buf);
+                }
+            }
+        }
+    }
+
+    pub fn export_graphml<W: std::io::Write>(
+        &self,
+        wtr: &mut W,
+        node_data: &NodeData,
+        label_context: &crate::domain::LabelContext,
+    ) -> std::io::Result<()> {
+        write_graphml(
+            wtr,
+            &self.visible_nodes,
+            node_data,
+            label_context,
+            &self.visualization_style,
+            &self.ui_state.hidden_predicates,
+            self.config.short_iri,
+        )
+    }
+}
+
+/// Prepares the loaded data and computes the type statistics, so the ui thread does not freeze on large data
+fn index_loaded_data(rdf_data: &RwLock<RdfData>, resolve_rdf_lists: bool, data_loading: &DataLoading) -> Option<IndexedTypes> {
+    let mut rdf_data = rdf_data.write().ok()?;
+    data_loading.indexing.store(true, Ordering::Relaxed);
+    data_loading.total_nodes.store(rdf_data.node_data.len(), Ordering::Relaxed);
+    rdf_data.prepare_for_index(resolve_rdf_lists);
+    Some(IndexedTypes::new(&rdf_data.node_data, &|indexed, _| {
+        data_loading.indexed_nodes.fetch_max(indexed, Ordering::Relaxed);
+    }))
 }
 
 impl eframe::App for RdfGlanceApp {
@@ -778,7 +1286,19 @@ impl eframe::App for RdfGlanceApp {
                         "Read triples: {}",
                         data_loading.total_triples.load(Ordering::Relaxed)
                     ));
-                    if !data_loading.stop_loading.load(Ordering::Relaxed) && ui.button("Stop Loading").clicked() {
+                    ui.label(format!("Nodes: {}", data_loading.total_nodes.load(Ordering::Relaxed)));
+                    if data_loading.indexing.load(Ordering::Relaxed) {
+                        let total_nodes = data_loading.total_nodes.load(Ordering::Relaxed).max(1);
+                        let progress = data_loading.indexed_nodes.load(Ordering::Relaxed) as f32 / total_nodes as f32;
+                        ui.label("Indexing types");
+                        ui.add(egui::ProgressBar::new(progress).desired_width(300.0).show_percentage());
+                    }
+                    if !data_loading.stop_loading.load(Ordering::Relaxed)
+                        && ui
+                            .button("Stop Loading")
+                            .on_hover_text("Stop reading, the triples loaded so far are kept")
+                            .clicked()
+                    {
                         data_loading.stop_loading.store(true, Ordering::Relaxed);
                     }
                     ui.ctx().request_repaint_after(Duration::from_millis(100));
@@ -811,7 +1331,7 @@ impl eframe::App for RdfGlanceApp {
             let mut cancel_clicked = false;
             let mut ok_clicked = false;
             if let Some(import_from_url_data) = &mut self.import_from_url {
-                egui::Window::new("Import from URL")
+                egui::Window::new("Load from URL")
                     .collapsible(false)
                     .resizable(true)
                     .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0]) // Center the modal
@@ -828,6 +1348,16 @@ impl eframe::App for RdfGlanceApp {
                                 ok_clicked = true;
                             }
                         });
+                        ui.horizontal(|ui| {
+                            ui.label("Format:");
+                            egui::ComboBox::from_id_salt("import_url_format")
+                                .selected_text(import_from_url_data.format.label())
+                                .show_ui(ui, |ui| {
+                                    for format in ImportFormat::ALL {
+                                        ui.selectable_value(&mut import_from_url_data.format, format, format.label());
+                                    }
+                                });
+                        });
                         ui.horizontal(|ui| {
                             let import_but =
                                 ui.add_enabled(!import_from_url_data.url.is_empty(), egui::Button::new("Import"));
@@ -850,12 +1380,53 @@ impl eframe::App for RdfGlanceApp {
                         self.system_message = SystemMessage::Error("URL cannot be empty".to_string());
                     } else {
                         let url = import_from_url_data.url.clone();
+                        self.persistent_data.recent_items.touch(RecentKind::Url, &url);
                         self.load_ttl_from_url(&url, import_from_url_data.format, ui.visuals().dark_mode);
                     }
                 }
                 self.import_from_url = None;
             }
             self.show_about(ui);
+            self.show_import_log(ui);
+            self.show_patch_preview(ui);
+            self.show_session_statistics(ui);
+            self.show_node_comparison(ui);
+            self.show_dataset_diff(ui);
+            self.show_data_quality(ui);
+            self.show_cycle_search(ui);
+            self.show_dependency_order(ui);
+            self.show_transitive_closure(ui);
+            self.show_metric_correlation(ui);
+            self.show_type_metric_summary(ui);
+            self.show_stress_data_dialog(ui);
+            self.show_edge_metadata(ui);
+            self.show_wikidata_statements(ui);
+            self.show_dcat_browser(ui);
+            self.show_semantic_zoom(ui);
+            self.show_node_list(ui);
+            self.show_reset_settings(ui);
+            self.show_log_console(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.show_journal_restore(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.show_sparql_query(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.show_sparql_update_push(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.show_bundle_export(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.show_dropped_files_dialog(ui);
+            self.show_analysis_notes(ui);
+            self.show_csv_import(ui);
+            self.show_graph_snapshots(ui);
+            self.show_png_export(ui);
+            self.show_language_panel(ui);
+            self.show_betweenness_job(ui);
+            #[cfg(not(target_arch = "wasm32"))]
+            self.load_icon_images(ui.ctx());
+            let frame_time = ui.input(|i| i.time);
+            self.session_statistics
+                .record_view_time(&format!("{:?}", self.display_type), frame_time);
 
             self.menu_bar(ui);
             // The menu bar action could start loading data, so we check if data is being loaded
@@ -896,6 +1467,16 @@ impl eframe::App for RdfGlanceApp {
                         DisplayType::Statistics,
                         concatcp!(ICON_STATISTICS, " Statistics"),
                     );
+                    ui.selectable_value(
+                        &mut self.display_type,
+                        DisplayType::Map,
+                        concatcp!(ICON_MAP, " Map"),
+                    );
+                    ui.selectable_value(
+                        &mut self.display_type,
+                        DisplayType::Timeline,
+                        concatcp!(ICON_TIMELINE, " Timeline"),
+                    );
                 });
                 ui.selectable_value(
                     &mut self.display_type,
@@ -941,6 +1522,8 @@ impl eframe::App for RdfGlanceApp {
                             self.display_type = DisplayType::Statistics;
                         } else if is_mod && i.key_pressed(Key::Num5) {
                             self.display_type = DisplayType::VisualQuery;
+                        } else if is_mod && i.key_pressed(Key::Num9) {
+                            self.display_type = DisplayType::Map;
                         }
                     }
                 })
@@ -965,7 +1548,7 @@ impl eframe::App for RdfGlanceApp {
                                         &mut rdf_data,
                                         &mut self.ui_state,
                                         &self.visualization_style,
-                                        self.persistent_data.config_data.iri_display,
+                                        self.config.iri_display,
                                     )
                                 } else {
                                     NodeAction::None
@@ -974,7 +1557,7 @@ impl eframe::App for RdfGlanceApp {
                             DisplayType::Configuration => self.show_config(ui),
                             DisplayType::Prefixes => self.show_prefixes(ui),
                             DisplayType::MetaGraph => {
-                                let is_empty = self.meta_nodes.nodes.read().unwrap().is_empty();
+                                let is_empty = self.meta_nodes.layout_data.read().unwrap().nodes.is_empty();
                                 if is_empty {
                                     self.build_meta_graph();
                                 }
@@ -983,12 +1566,23 @@ impl eframe::App for RdfGlanceApp {
                             DisplayType::Statistics => self.show_statistics(ui),
                             DisplayType::VisualQuery => self.show_visual_query(ui),
                             DisplayType::ReferenceResolver => self.show_reference_resolver(ui),
+                            DisplayType::Map => self.show_map_view(ui),
+                            DisplayType::Timeline => self.show_timeline(ui),
                         };
                     });
                     strip.cell(|ui| {
                         ui.label(&self.status_message);
                     });
                 });
+            #[cfg(not(target_arch = "wasm32"))]
+            if let Some(deep_link_action) = self.deep_link_action(ui.ctx()) {
+                node_action = deep_link_action;
+            }
+            if matches!(node_action, NodeAction::None)
+                && let Some(injected_action) = self.injected_node_actions.pop_front()
+            {
+                node_action = injected_action;
+            }
 
             match node_action {
                 NodeAction::ShowType(type_index) => {
@@ -1014,6 +1608,7 @@ impl eframe::App for RdfGlanceApp {
                 }
                 NodeAction::ShowVisual(node_index) => {
                     self.display_type = DisplayType::Graph;
+                    self.record_explored_node(node_index);
                     if self.visible_nodes.add_by_index(node_index) {
                         if let Ok(rdf_data) = self.rdf_data.read() {
                             let npos = NeighborPos::one(node_index);
@@ -1031,6 +1626,7 @@ impl eframe::App for RdfGlanceApp {
                     self.ui_state.selection_start_rect = None;
                 }
                 NodeAction::AddVisual(node_index) => {
+                    self.record_explored_node(node_index);
                     if self.visible_nodes.add_by_index(node_index) {
                         if let Ok(rdf_data) = self.rdf_data.read() {
                             let npos = NeighborPos::one(node_index);
@@ -1047,25 +1643,26 @@ impl eframe::App for RdfGlanceApp {
                     self.ui_state.selected_node = Some(node_index);
                     self.ui_state.selected_nodes.insert(node_index);
                 }
+                NodeAction::VisualizeNodes(nodes) => {
+                    self.visualize_nodes(&nodes);
+                }
+                NodeAction::AddVisualNodes(nodes) => {
+                    if !nodes.is_empty() {
+                        self.show_and_select_nodes(&nodes);
+                        self.set_status_message(&format!("Added {} nodes to the visual graph", nodes.len()));
+                        self.display_type = DisplayType::Graph;
+                    }
+                }
                 NodeAction::None => {}
             }
             #[cfg(not(target_arch = "wasm32"))]
             if let Some(dialog) = &mut self.sparql_dialog {
-                let (close_dialog, result) = dialog.show(ui.ctx(), &self.persistent_data.last_endpoints);
+                let last_endpoints = self.persistent_data.recent_items.locations(RecentKind::Endpoint);
+                let (close_dialog, result) = dialog.show(ui.ctx(), &last_endpoints);
                 if close_dialog {
                     if let Some(endpoint) = result {
-                        use crate::integration::sparql::SparqlAdapter;
-
-                        self.rdfwrap = Box::new(SparqlAdapter::new(&endpoint));
-                        if !endpoint.is_empty()
-                            && !self
-                                .persistent_data
-                                .last_endpoints
-                                .iter()
-                                .any(|e| *e == endpoint.as_str().into())
-                        {
-                            self.persistent_data.last_endpoints.push(endpoint.into());
-                        }
+                        let node_query = dialog.node_query;
+                        self.connect_sparql_endpoint(&endpoint, node_query);
                     }
                     self.sparql_dialog = None;
                 }
@@ -1078,32 +1675,23 @@ impl eframe::App for RdfGlanceApp {
             }
              */
         });
+        self.process_data_changes(ui.visuals().dark_mode);
 
-        ui.ctx().input(|i| {
-            for file in &i.raw.dropped_files {
-                if let Some(file_path) = &file.path {
-                    #[cfg(not(target_arch = "wasm32"))]
-                    {
-                        let path = Path::new(file_path);
-                        if path.exists() {
-                            if path.is_dir() {
-                                self.load_ttl_dir(file_path.to_str().unwrap());
-                            } else {
-                                let file_path = path.to_str();
-                                if let Some(file_path) = file_path {
-                                    self.load_ttl(file_path, false);
-                                } else {
-                                    println!("File dropped path is not valid UTF-8: {:?}", path);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        });
+        #[cfg(not(target_arch = "wasm32"))]
+        self.update_layout_journal(ui.ctx());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_dropped_files(ui.ctx());
+    }
+
+    fn persist_egui_memory(&self) -> bool {
+        !self.safe_mode
     }
 
     fn save(&mut self, _storage: &mut dyn Storage) {
+        if self.safe_mode {
+            return;
+        }
         if let Ok(persistent_data_string) = serde_json::to_string(&self.persistent_data) {
             _storage.set_string("persistent_data", persistent_data_string);
             // println!("save called");
@@ -0,0 +1,309 @@
+use std::collections::HashMap;
+
+use eframe::egui::Vec2;
+
+use crate::{
+    IriIndex,
+    domain::NodeData,
+    support::SortedVec,
+    uistate::layout::{
+        Edge, IndividualNodeStyleData, NodeLayout, NodePosition, NodeShapeData, SortedNodeLayout, update_edges_groups,
+    },
+};
+
+/// Node hidden in the meta-node of a collapsed cluster
+#[derive(Clone, Copy)]
+pub struct CollapsedMember {
+    pub index: IriIndex,
+    // position relative to the meta-node, so the members follow the meta-node if it was moved
+    pub offset: Vec2,
+    pub locked: bool,
+    pub style: IndividualNodeStyleData,
+}
+
+/**
+ * Clusters collapsed into one meta-node.
+ * The meta-node is the cluster member with the most edges, the other members are removed
+ * from the layout and their edges to nodes outside of the cluster are redirected to the meta-node.
+ */
+#[derive(Default)]
+pub struct CollapsedClusters {
+    pub members: HashMap<IriIndex, Vec<CollapsedMember>>,
+    pub meta_node_of: HashMap<IriIndex, IriIndex>,
+}
+
+impl CollapsedClusters {
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.members.clear();
+        self.meta_node_of.clear();
+    }
+
+    /// The node that represents the node in the layout, the meta-node for collapsed nodes
+    pub fn visible_index(&self, node_index: IriIndex) -> IriIndex {
+        self.meta_node_of.get(&node_index).copied().unwrap_or(node_index)
+    }
+
+    pub fn is_collapsed(&self, node_index: IriIndex) -> bool {
+        self.meta_node_of.contains_key(&node_index)
+    }
+}
+
+impl SortedNodeLayout {
+    /**
+     * Collapses every given cluster (color_overwrite of the node style) into one meta-node.
+     * Clusters that already have a meta-node are skipped.
+     *
+     * Returns the number of collapsed clusters.
+     */
+    pub fn collapse_clusters(&mut self, clusters: &[u16], hidden_predicates: &SortedVec) -> usize {
+        let collapsed = self
+            .mut_nodes(|nodes, positions, edges, node_shapes, individual_node_styles| {
+                let collapsed =
+                    collapse_nodes(nodes, positions, edges, node_shapes, individual_node_styles, clusters);
+                update_edges_groups(edges, hidden_predicates);
+                collapsed
+            })
+            .unwrap_or_default();
+        if collapsed.is_empty() {
+            return 0;
+        }
+        for (meta_node, members) in collapsed.iter() {
+            for member in members.iter() {
+                self.collapsed_clusters.meta_node_of.insert(member.index, *meta_node);
+            }
+        }
+        let count = collapsed.len();
+        self.collapsed_clusters.members.extend(collapsed);
+        self.data_epoch += 1;
+        self.show_orthogonal = false;
+        self.orth_edges = None;
+        self.update_node_shapes = true;
+        count
+    }
+
+    /**
+     * Shows the members of the meta-node again around it.
+     *
+     * Returns false if the node is not a meta-node.
+     */
+    pub fn expand_cluster(&mut self, meta_node: IriIndex, node_data: &NodeData, hidden_predicates: &SortedVec) -> bool {
+        let Some(members) = self.collapsed_clusters.members.remove(&meta_node) else {
+            return false;
+        };
+        for member in members.iter() {
+            self.collapsed_clusters.meta_node_of.remove(&member.index);
+        }
+        self.mut_nodes(|nodes, positions, edges, node_shapes, individual_node_styles| {
+            expand_nodes(nodes, positions, node_shapes, individual_node_styles, meta_node, &members);
+            // edges are rebuilt from the data below
+            edges.clear();
+        });
+        self.refresh_edges(node_data, hidden_predicates);
+        self.data_epoch += 1;
+        self.show_orthogonal = false;
+        self.orth_edges = None;
+        self.update_node_shapes = true;
+        true
+    }
+
+    pub fn expand_all_clusters(&mut self, node_data: &NodeData, hidden_predicates: &SortedVec) -> bool {
+        let meta_nodes: Vec<IriIndex> = self.collapsed_clusters.members.keys().copied().collect();
+        let mut expanded = false;
+        for meta_node in meta_nodes {
+            expanded |= self.expand_cluster(meta_node, node_data, hidden_predicates);
+        }
+        expanded
+    }
+}
+
+fn collapse_nodes(
+    nodes: &mut Vec<NodeLayout>,
+    positions: &mut Vec<NodePosition>,
+    edges: &mut Vec<Edge>,
+    node_shapes: &mut Vec<NodeShapeData>,
+    individual_node_styles: &mut Vec<IndividualNodeStyleData>,
+    clusters: &[u16],
+) -> Vec<(IriIndex, Vec<CollapsedMember>)> {
+    node_shapes.resize(nodes.len(), NodeShapeData::default());
+    individual_node_styles.resize(nodes.len(), IndividualNodeStyleData::default());
+    let mut cluster_members: HashMap<u16, Vec<usize>> = HashMap::new();
+    for (pos, node_style) in individual_node_styles.iter().enumerate() {
+        if clusters.contains(&node_style.color_overwrite) {
+            cluster_members.entry(node_style.color_overwrite).or_default().push(pos);
+        }
+    }
+    cluster_members.retain(|_cluster, members| {
+        members.len() > 1 && members.iter().all(|pos| individual_node_styles[*pos].cluster_size == 0)
+    });
+    if cluster_members.is_empty() {
+        return Vec::new();
+    }
+    let mut degree: Vec<u32> = vec![0; nodes.len()];
+    for edge in edges.iter() {
+        degree[edge.from] += 1;
+        degree[edge.to] += 1;
+    }
+    // the visible node (position) for every node after collapse
+    let mut mapped: Vec<usize> = (0..nodes.len()).collect();
+    let mut collapsed: Vec<(IriIndex, Vec<CollapsedMember>)> = Vec::with_capacity(cluster_members.len());
+    for members in cluster_members.values() {
+        let meta_pos = *members.iter().max_by_key(|pos| (degree[**pos], usize::MAX - **pos)).unwrap();
+        let meta_position = positions[meta_pos].pos;
+        let mut collapsed_members: Vec<CollapsedMember> = Vec::with_capacity(members.len() - 1);
+        for pos in members.iter().filter(|pos| **pos != meta_pos) {
+            mapped[*pos] = meta_pos;
+            collapsed_members.push(CollapsedMember {
+                index: nodes[*pos].node_index,
+                offset: positions[*pos].pos - meta_position,
+                locked: positions[*pos].locked,
+                style: individual_node_styles[*pos],
+            });
+        }
+        individual_node_styles[meta_pos].cluster_size = members.len() as u32;
+        collapsed.push((nodes[meta_pos].node_index, collapsed_members));
+    }
+    // edges inside of the cluster are dropped, the other are redirected to the meta-node
+    edges.retain_mut(|edge| {
+        let from = mapped[edge.from];
+        let to = mapped[edge.to];
+        if from == to && edge.from != edge.to {
+            return false;
+        }
+        edge.from = from;
+        edge.to = to;
+        true
+    });
+    let mut new_positions: Vec<usize> = vec![0; nodes.len()];
+    let mut write = 0;
+    for read in 0..nodes.len() {
+        if mapped[read] != read {
+            continue;
+        }
+        nodes[write] = nodes[read];
+        positions[write] = positions[read];
+        node_shapes[write] = node_shapes[read];
+        individual_node_styles[write] = individual_node_styles[read];
+        new_positions[read] = write;
+        write += 1;
+    }
+    nodes.truncate(write);
+    positions.truncate(write);
+    node_shapes.truncate(write);
+    individual_node_styles.truncate(write);
+    edges.iter_mut().for_each(|edge| {
+        edge.from = new_positions[edge.from];
+        edge.to = new_positions[edge.to];
+    });
+    collapsed
+}
+
+/// Adds the members and keeps the vectors sorted by node index, the edges must be rebuilt after it
+fn expand_nodes(
+    nodes: &mut Vec<NodeLayout>,
+    positions: &mut Vec<NodePosition>,
+    node_shapes: &mut Vec<NodeShapeData>,
+    individual_node_styles: &mut Vec<IndividualNodeStyleData>,
+    meta_node: IriIndex,
+    members: &[CollapsedMember],
+) {
+    node_shapes.resize(nodes.len(), NodeShapeData::default());
+    individual_node_styles.resize(nodes.len(), IndividualNodeStyleData::default());
+    let Ok(meta_pos) = nodes.binary_search_by(|node| node.node_index.cmp(&meta_node)) else {
+        return;
+    };
+    individual_node_styles[meta_pos].cluster_size = 0;
+    let meta_position = positions[meta_pos].pos;
+    for member in members.iter() {
+        if nodes.binary_search_by(|node| node.node_index.cmp(&member.index)).is_ok() {
+            continue;
+        }
+        nodes.push(NodeLayout::new(member.index));
+        positions.push(NodePosition {
+            pos: meta_position + member.offset,
+            vel: Vec2::ZERO,
+            locked: member.locked,
+        });
+        node_shapes.push(NodeShapeData::default());
+        individual_node_styles.push(member.style);
+    }
+    let mut order: Vec<usize> = (0..nodes.len()).collect();
+    order.sort_unstable_by_key(|pos| nodes[*pos].node_index);
+    *nodes = order.iter().map(|pos| nodes[*pos]).collect();
+    *positions = order.iter().map(|pos| positions[*pos]).collect();
+    *node_shapes = order.iter().map(|pos| node_shapes[*pos]).collect();
+    *individual_node_styles = order.iter().map(|pos| individual_node_styles[*pos]).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::Pos2;
+
+    fn edge(from: usize, to: usize) -> Edge {
+        Edge {
+            from,
+            to,
+            predicate: 0,
+            bezier_distance: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_collapse_and_expand_nodes() {
+        let mut nodes: Vec<NodeLayout> = (10..15).map(NodeLayout::new).collect();
+        let mut positions: Vec<NodePosition> = (0..5)
+            .map(|i| NodePosition {
+                pos: Pos2::new(i as f32 * 10.0, 0.0),
+                vel: Vec2::ZERO,
+                locked: false,
+            })
+            .collect();
+        let mut node_shapes = vec![NodeShapeData::default(); 5];
+        let mut individual_node_styles = vec![IndividualNodeStyleData::default(); 5];
+        // cluster 0 (color 1): nodes 0, 1, 2 with 1 as hub, nodes 3 and 4 are outside
+        for pos in 0..3 {
+            individual_node_styles[pos].set_cluster(0);
+        }
+        let mut edges = vec![edge(1, 0), edge(1, 2), edge(0, 3), edge(4, 2), edge(3, 4), edge(1, 3)];
+        let collapsed = collapse_nodes(
+            &mut nodes,
+            &mut positions,
+            &mut edges,
+            &mut node_shapes,
+            &mut individual_node_styles,
+            &[1],
+        );
+        assert_eq!(1, collapsed.len());
+        assert_eq!(11, collapsed[0].0);
+        assert_eq!(
+            vec![11, 13, 14],
+            nodes.iter().map(|node| node.node_index).collect::<Vec<IriIndex>>()
+        );
+        assert_eq!(3, individual_node_styles[0].cluster_size);
+        // internal edges are dropped, the edges of the members go to the meta-node
+        let edge_pairs: Vec<(usize, usize)> = edges.iter().map(|edge| (edge.from, edge.to)).collect();
+        assert_eq!(vec![(0, 1), (2, 0), (1, 2), (0, 1)], edge_pairs);
+        assert_eq!(Vec2::new(-10.0, 0.0), collapsed[0].1[0].offset);
+
+        positions[0].pos = Pos2::new(100.0, 100.0);
+        expand_nodes(
+            &mut nodes,
+            &mut positions,
+            &mut node_shapes,
+            &mut individual_node_styles,
+            11,
+            &collapsed[0].1,
+        );
+        assert_eq!(
+            vec![10, 11, 12, 13, 14],
+            nodes.iter().map(|node| node.node_index).collect::<Vec<IriIndex>>()
+        );
+        assert_eq!(Pos2::new(90.0, 100.0), positions[0].pos);
+        assert_eq!(0, individual_node_styles[1].cluster_size);
+        assert_eq!(1, individual_node_styles[2].color_overwrite);
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+
+use crate::{
+    IriIndex,
+    domain::{NObject, NodeData, graph_styles::GVisualizationStyle},
+    uistate::layout::{Edge, NodeLayout},
+};
+
+/// Node types and predicates of the visual graph with their counts, shown in the legend of the graph view
+#[derive(Default)]
+pub struct GraphLegend {
+    // (type, number of nodes) ordered by the number of nodes
+    pub types: Vec<(IriIndex, usize)>,
+    // nodes without a type with style
+    pub untyped: usize,
+    // (predicate, number of edges) ordered by the number of edges
+    pub predicates: Vec<(IriIndex, usize)>,
+    // data epoch of the visible nodes the legend was computed for, 0 if not computed
+    pub data_epoch: u32,
+}
+
+/// The type that gives the node its style, the legend groups the nodes by it
+pub fn legend_type(node: &NObject, styles: &GVisualizationStyle) -> Option<IriIndex> {
+    node.highest_priority_types(styles).first().copied()
+}
+
+impl GraphLegend {
+    pub fn new(
+        nodes: &[NodeLayout],
+        edges: &[Edge],
+        node_data: &NodeData,
+        styles: &GVisualizationStyle,
+        data_epoch: u32,
+    ) -> Self {
+        let mut type_counts: HashMap<IriIndex, usize> = HashMap::new();
+        let mut untyped = 0;
+        for node_layout in nodes.iter() {
+            match node_data
+                .get_node_by_index(node_layout.node_index)
+                .and_then(|(_, node)| legend_type(node, styles))
+            {
+                Some(type_index) => *type_counts.entry(type_index).or_default() += 1,
+                None => untyped += 1,
+            }
+        }
+        let mut predicate_counts: HashMap<IriIndex, usize> = HashMap::new();
+        for edge in edges.iter() {
+            *predicate_counts.entry(edge.predicate).or_default() += 1;
+        }
+        Self {
+            types: sorted_counts(type_counts),
+            untyped,
+            predicates: sorted_counts(predicate_counts),
+            data_epoch,
+        }
+    }
+}
+
+fn sorted_counts(counts: HashMap<IriIndex, usize>) -> Vec<(IriIndex, usize)> {
+    let mut counts: Vec<(IriIndex, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        RdfData, graph_styles::NodeStyle, size_mapping::SizeMapping,
+        style_rules::StyleRules,
+    };
+
+    #[test]
+    fn test_graph_legend() {
+        let mut rdf_data = RdfData::from_patch(
+            "A <http://example.org/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .\n\
+             A <http://example.org/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Agent> .\n\
+             A <http://example.org/b> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .\n\
+             A <http://example.org/c> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Agent> .\n\
+             A <http://example.org/a> <http://example.org/knows> <http://example.org/b> .\n\
+             A <http://example.org/a> <http://example.org/knows> <http://example.org/c> .\n\
+             A <http://example.org/b> <http://example.org/likes> <http://example.org/d> .\n",
+        );
+        let indexers = &mut rdf_data.node_data.indexers;
+        let person = indexers.type_indexer.get_index("http://example.org/Person");
+        let agent = indexers.type_indexer.get_index("http://example.org/Agent");
+        let knows = indexers.predicate_indexer.get_index("http://example.org/knows");
+        let likes = indexers.predicate_indexer.get_index("http://example.org/likes");
+        let node_data = &rdf_data.node_data;
+        let mut styles = GVisualizationStyle {
+            node_styles: HashMap::new(),
+            edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
+            default_node_style: NodeStyle::default(),
+            use_size_overwrite: false,
+            use_color_overwrite: false,
+            default_label_in_node: false,
+            min_size: 5.0,
+            max_size: 50.0,
+        };
+        styles.node_styles.insert(
+            person,
+            NodeStyle {
+                priority: 10,
+                ..NodeStyle::default()
+            },
+        );
+        styles.node_styles.insert(agent, NodeStyle::default());
+        let nodes: Vec<NodeLayout> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| NodeLayout::new(node_data.get_node_index(&format!("http://example.org/{}", name)).unwrap()))
+            .collect();
+        let edges = vec![
+            Edge {
+                from: 0,
+                to: 1,
+                predicate: knows,
+                bezier_distance: 0.0,
+            },
+            Edge {
+                from: 0,
+                to: 2,
+                predicate: knows,
+                bezier_distance: 0.0,
+            },
+            Edge {
+                from: 1,
+                to: 3,
+                predicate: likes,
+                bezier_distance: 0.0,
+            },
+        ];
+        let legend = GraphLegend::new(&nodes, &edges, node_data, &styles, 3);
+        // a is counted as person, its type with the higher priority
+        assert_eq!(vec![(person, 2), (agent, 1)], legend.types);
+        assert_eq!(1, legend.untyped);
+        assert_eq!(vec![(knows, 2), (likes, 1)], legend.predicates);
+        assert_eq!(3, legend.data_epoch);
+    }
+}
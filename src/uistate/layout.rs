@@ -1,9 +1,9 @@
 use crate::{
     IriIndex, domain::{
-        config::Config, 
-        graph_styles::{GVisualizationStyle, NodeShape}, 
+        NodeData, RdfData, blank_node_tree::composite_references, config::Config, 
+        graph_styles::{EdgeLayoutForce, GVisualizationStyle, NodeShape, NodeStyle}, 
         statistics::{StatisticsData, StatisticsResult, distribute_to_zoom_layers}
-    }, graph_algorithms::{GraphAlgorithm, run_algorithm, run_clustering_algorithm}, layoutalg::force::layout_graph_nodes, support::SortedVec, ui::style::{ICON_KEEP_TEMPERATURE, ICON_KEY, ICON_REFRESH, ICON_STOP}, uistate::UIState
+    }, graph_algorithms::{GraphAlgorithm, run_algorithm, run_clustering_algorithm, weights::WeightedEdge}, layoutalg::{force::layout_graph_nodes, ortho::reroute_orthogonal_edges}, support::SortedVec, ui::style::{ICON_KEEP_TEMPERATURE, ICON_KEY, ICON_REFRESH, ICON_STOP}, uistate::{UIState, cluster_collapse::CollapsedClusters}
 };
 
 use eframe::egui::Vec2;
@@ -14,7 +14,7 @@ use rayon::prelude::*;
 use std::{
     collections::{BTreeSet, HashMap, VecDeque},
     sync::{
-        Arc, RwLock,
+        Arc, RwLock, RwLockReadGuard,
         atomic::{AtomicBool, Ordering},
         mpsc,
     },
@@ -101,7 +101,59 @@ pub struct IndividualNodeStyleData {
     // 0 means no overwrite
     pub color_overwrite: u16,
     pub semantic_zoom_interval: LayerInterval,
+    // set manually in node details, computed semantic zoom does not change it
+    pub semantic_zoom_manual: bool,
     pub hidden_references: u32,
+    // set for selected nodes from the context menu, has precedence over the type style
+    pub style_override: Option<NodeStyleOverride>,
+    // number of nodes of the collapsed cluster if the node is a meta-node, 0 otherwise
+    pub cluster_size: u32,
+    // size from the size mapping of the style, NaN if the node has no value
+    pub mapped_size: f32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct NodeStyleOverride {
+    pub color: egui::Color32,
+    pub border_color: egui::Color32,
+    pub border_width: f32,
+    // multiplies the size of the type style
+    pub size_factor: f32,
+}
+
+impl Default for NodeStyleOverride {
+    fn default() -> Self {
+        Self {
+            color: egui::Color32::from_rgb(255, 200, 80),
+            border_color: egui::Color32::BLACK,
+            border_width: 2.0,
+            size_factor: 1.0,
+        }
+    }
+}
+
+impl NodeStyleOverride {
+    pub fn apply(&self, type_style: &NodeStyle) -> NodeStyle {
+        NodeStyle {
+            color: self.color,
+            priority: type_style.priority,
+            label_index: type_style.label_index,
+            node_shape: type_style.node_shape,
+            node_size: type_style.node_size,
+            width: type_style.width * self.size_factor,
+            height: type_style.height * self.size_factor,
+            border_width: self.border_width,
+            border_color: self.border_color,
+            corner_radius: type_style.corner_radius,
+            max_lines: type_style.max_lines,
+            label_position: type_style.label_position,
+            label_max_width: type_style.label_max_width,
+            font_size: type_style.font_size,
+            label_color: type_style.label_color,
+            icon_style: type_style.icon_style.clone(),
+            is_default: false,
+        }
+    }
 }
 
 impl Default for IndividualNodeStyleData {
@@ -110,7 +162,11 @@ impl Default for IndividualNodeStyleData {
             size_overwrite: f32::NAN,
             color_overwrite: 0,
             semantic_zoom_interval: LayerInterval::default(),
+            semantic_zoom_manual: false,
             hidden_references: 0,
+            style_override: None,
+            cluster_size: 0,
+            mapped_size: f32::NAN,
         }
     }
 }
@@ -120,13 +176,21 @@ impl IndividualNodeStyleData {
         let mapped_size: f32 =
             visualization_style.min_size + value * (visualization_style.max_size - visualization_style.min_size);
         self.size_overwrite = mapped_size;
-        self.semantic_zoom_interval.set_from_normalized(value);
+        if !self.semantic_zoom_manual {
+            self.semantic_zoom_interval.set_from_normalized(value);
+        }
     }
     pub fn set_cluster(&mut self, cluster: u32) {
         self.color_overwrite = (cluster + 1) as u16;
     }
+    pub fn set_zoom_layer(&mut self, layer: u8) {
+        if !self.semantic_zoom_manual {
+            self.semantic_zoom_interval.set_from_layout(layer);
+        }
+    }
 }
 
+#[derive(Clone)]
 pub struct Edge {
     pub from: usize,
     pub to: usize,
@@ -154,6 +218,15 @@ impl Default for NodePosition {
     }
 }
 
+impl NodePosition {
+    /// Used by layout algorithms, locked (pinned) nodes keep their position
+    pub fn move_to(&mut self, pos: Pos2) {
+        if !self.locked {
+            self.pos = pos;
+        }
+    }
+}
+
 // Used to store efficiently all information needed to layout the graph
 // nodes, edges, positions and node shapes
 // It uses mostly indexes of nodes and provide methods to add and remove nodes
@@ -161,12 +234,13 @@ impl Default for NodePosition {
 // Edges stores indexes of nodes in nodes vector (not the primary node index)
 // This enable fast access needed to layout the graph but make
 // it hard to manipulate the structure for add and remove operation
+// The vectors are shared with the background layout thread in LayoutData behind one lock,
+// so a view never holds more than one layout lock and can not deadlock with the layout thread.
+// The lock must not be taken again while it is held (also not by methods like get_pos).
+// The rdf data lock of the app is always taken before the layout lock, the layout thread does not use it.
+// Views that need both locks take them with read_with_data.
 pub struct SortedNodeLayout {
-    pub nodes: Arc<RwLock<Vec<NodeLayout>>>,
-    pub edges: Arc<RwLock<Vec<Edge>>>,
-    pub positions: Arc<RwLock<Vec<NodePosition>>>,
-    pub node_shapes: Arc<RwLock<Vec<NodeShapeData>>>,
-    pub individual_node_styles: Arc<RwLock<Vec<IndividualNodeStyleData>>>,
+    pub layout_data: Arc<RwLock<LayoutData>>,
     pub orth_edges: Option<OrthEdges>,
     pub layout_temperature: f32,
     pub keep_temperature: Arc<AtomicBool>,
@@ -178,15 +252,42 @@ pub struct SortedNodeLayout {
     pub compute_layout: bool,
     pub lock_layout: bool,
     pub show_orthogonal: bool,
+    // the orthogonal edges do not match the moved nodes and must be routed again
+    pub orth_outdated: bool,
+    pub orth_routed_time: f64,
+    // blank node trees are part of the referencing node, edges go to the named nodes in the trees
+    pub flatten_blank_nodes: bool,
+    // force layout groups the nodes by the cluster applied from clustering statistics
+    pub group_by_cluster: bool,
+    // preferred edge length and stiffness of predicates set in the edge styles
+    pub edge_forces: HashMap<IriIndex, EdgeLayoutForce>,
+    pub collapsed_clusters: CollapsedClusters,
     pub data_epoch: u32,
     pub undo_stack: Vec<NodeCommand>,
     pub redo_stack: Vec<NodeCommand>,
 }
 
+/// Position of the node in already locked sorted nodes.
+/// Use it instead of SortedNodeLayout::get_pos if the layout lock is held.
+pub fn node_pos(nodes: &[NodeLayout], value: IriIndex) -> Option<usize> {
+    nodes.binary_search_by(|e| e.node_index.cmp(&value)).ok()
+}
+
+/// The vectors of the visual graph, all of them have the same length except edges
+#[derive(Default)]
+pub struct LayoutData {
+    pub nodes: Vec<NodeLayout>,
+    pub edges: Vec<Edge>,
+    pub positions: Vec<NodePosition>,
+    pub node_shapes: Vec<NodeShapeData>,
+    pub individual_node_styles: Vec<IndividualNodeStyleData>,
+}
+
 #[derive(Debug)]
 pub enum LayoutConfUpdate {
     UpdateRepulsionConstant(f32),
     UpdateAttractionFactor(f32),
+    UpdateEdgeForces(HashMap<IriIndex, EdgeLayoutForce>),
 }
 
 pub struct LayoutHandle {
@@ -215,8 +316,61 @@ pub struct EdgeMemo {
     pub predicate: IriIndex,
 }
 
+// minimal time in seconds between the routings of orthogonal edges while a node is dragged
+const ORTH_REROUTE_INTERVAL: f64 = 0.2;
+
+impl SortedNodeLayout {
+    /// Only the selected subgraph is routed orthogonal, the other edges are bezier curves
+    pub fn is_partial_orthogonal(&self) -> bool {
+        self.show_orthogonal && self.orth_edges.as_ref().is_some_and(|orth_edges| orth_edges.is_partial())
+    }
+
+    /// Marks the orthogonal edges as outdated after the nodes were moved
+    pub fn invalidate_orthogonal(&mut self) {
+        if self.show_orthogonal {
+            self.orth_outdated = true;
+        }
+    }
+
+    /// Routes the outdated orthogonal edges again. While dragging the routing is throttled and does not
+    /// move the other nodes to widen the channels, this is done once the drag ends.
+    pub fn update_orthogonal(&mut self, hidden_predicates: &SortedVec, time: f64, dragging: bool) {
+        if !self.orth_outdated || !self.show_orthogonal {
+            return;
+        }
+        if dragging {
+            if time - self.orth_routed_time < ORTH_REROUTE_INTERVAL {
+                return;
+            }
+        } else {
+            self.orth_outdated = false;
+        }
+        self.orth_routed_time = time;
+        reroute_orthogonal_edges(self, hidden_predicates, !dragging);
+    }
+}
+
 pub struct OrthEdges {
     pub edges: Vec<OrthEdge>,    
+    // sorted positions of the routed nodes if only the selected subgraph is routed
+    pub routed_nodes: Option<Vec<usize>>,
+}
+
+impl OrthEdges {
+    pub fn is_partial(&self) -> bool {
+        self.routed_nodes.is_some()
+    }
+
+    pub fn is_routed_node(&self, node_pos: usize) -> bool {
+        self.routed_nodes
+            .as_ref()
+            .is_none_or(|routed_nodes| routed_nodes.binary_search(&node_pos).is_ok())
+    }
+
+    /// The edge is drawn orthogonal, other edges of partial routing are drawn as bezier curves
+    pub fn is_routed(&self, from: usize, to: usize) -> bool {
+        self.is_routed_node(from) && self.is_routed_node(to)
+    }
 }
 
 pub struct OrthEdge {
@@ -229,11 +383,7 @@ pub struct OrthEdge {
 impl Default for SortedNodeLayout {
     fn default() -> Self {
         Self {
-            nodes: Arc::new(RwLock::new(Vec::new())),
-            positions: Arc::new(RwLock::new(Vec::new())),
-            edges: Arc::new(RwLock::new(Vec::new())),
-            node_shapes: Arc::new(RwLock::new(Vec::new())),
-            individual_node_styles: Arc::new(RwLock::new(Vec::new())),
+            layout_data: Arc::new(RwLock::new(LayoutData::default())),
             orth_edges: None,
             compute_layout: true,
             keep_temperature: Arc::new(AtomicBool::new(false)),
@@ -244,6 +394,12 @@ impl Default for SortedNodeLayout {
             update_node_shapes: true,
             has_semantic_zoom: false,
             show_orthogonal: false,
+            orth_outdated: false,
+            orth_routed_time: 0.0,
+            flatten_blank_nodes: false,
+            group_by_cluster: false,
+            edge_forces: HashMap::new(),
+            collapsed_clusters: CollapsedClusters::default(),
             data_epoch: 1,
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
@@ -263,37 +419,40 @@ impl SortedNodeLayout {
         self.data_epoch += 1;
         self.show_orthogonal = false;
         self.orth_edges = None;
-        if let Ok(mut nodes) = self.nodes.write() {
-            if let Ok(mut node_shapes) = self.node_shapes.write() {
-                if let Ok(mut positions) = self.positions.write() {
-                    if let Ok(mut individual_node_styles) = self.individual_node_styles.write() {
-                        if let Ok(mut edges) = self.edges.write() {
-                            return match nodes.binary_search_by(|e| e.node_index.cmp(&value.node_index)) {
-                                Ok(_) => false, // Value already exists, do nothing
-                                Err(pos) => {
-                                    // Insert at correct position
-                                    nodes.insert(pos, value);
-                                    positions.insert(pos, NodePosition::default());
-                                    node_shapes.insert(pos, NodeShapeData::default());
-                                    individual_node_styles.insert(pos, IndividualNodeStyleData::default());
-                                    self.update_node_shapes = true;
-                                    for i in 0..edges.len() {
-                                        if edges[i].from >= pos {
-                                            edges[i].from += 1;
-                                        }
-                                        if edges[i].to >= pos {
-                                            edges[i].to += 1;
-                                        }
-                                    }
-                                    true
-                                }
-                            };
+        let inserted = if let Ok(mut layout) = self.layout_data.write() {
+            let LayoutData {
+                nodes,
+                edges,
+                positions,
+                node_shapes,
+                individual_node_styles,
+            } = &mut *layout;
+            match nodes.binary_search_by(|e| e.node_index.cmp(&value.node_index)) {
+                Ok(_) => false, // Value already exists, do nothing
+                Err(pos) => {
+                    // Insert at correct position
+                    nodes.insert(pos, value);
+                    positions.insert(pos, NodePosition::default());
+                    node_shapes.insert(pos, NodeShapeData::default());
+                    individual_node_styles.insert(pos, IndividualNodeStyleData::default());
+                    for edge in edges.iter_mut() {
+                        if edge.from >= pos {
+                            edge.from += 1;
+                        }
+                        if edge.to >= pos {
+                            edge.to += 1;
                         }
                     }
+                    true
                 }
             }
+        } else {
+            false
+        };
+        if inserted {
+            self.update_node_shapes = true;
         }
-        false
+        inserted
     }
 
     // use add_many operation if possible. Do not call it in the loop
@@ -313,7 +472,8 @@ impl SortedNodeLayout {
         inserted_callback: impl FnMut(&(IriIndex, IriIndex)),
     ) -> bool {
         self.stop_layout();
-        let index_to_add = if let Ok(nodes) = self.nodes.read() {
+        let index_to_add = if let Ok(layout) = self.layout_data.read() {
+            let nodes = &layout.nodes;
             if nodes.len() >= config.max_visible_nodes {
                 return false;
             }
@@ -323,13 +483,14 @@ impl SortedNodeLayout {
                 .iter()
                 .filter(|(_parent_index, node_index)| {
                     nodes.binary_search_by(|node| node.node_index.cmp(node_index)).is_err()
+                        && !self.collapsed_clusters.is_collapsed(*node_index)
                 })
                 .map(|p| (p.0, p.1))
                 .collect();
             index_to_add.sort_unstable_by(|a, b| a.1.cmp(&b.1));
             index_to_add.dedup_by(|a, b| a.1 == b.1);
             if index_to_add.len() + nodes.len() > config.max_visible_nodes {
-                println!("Truncating nodes to add to visual graph for reaching the configured display limit");
+                log::warn!("Truncating nodes to add to visual graph for reaching the configured display limit");
                 index_to_add.truncate(config.max_visible_nodes - nodes.len());
             }
             index_to_add.iter().for_each(inserted_callback);
@@ -339,59 +500,58 @@ impl SortedNodeLayout {
         };
         if !index_to_add.is_empty() {
             self.update_node_shapes = true;
-            if let Ok(mut nodes) = self.nodes.write() {
-                if let Ok(mut node_shapes) = self.node_shapes.write() {
-                    if let Ok(mut positions) = self.positions.write() {
-                        if let Ok(mut individual_node_styles) = self.individual_node_styles.write() {
-                            if let Ok(mut edges) = self.edges.write() {
-                                // stores the new indexes for old nodes indexes, needed to update edges from and to fields
-                                let mut new_positions: Vec<usize> = Vec::with_capacity(nodes.len());
-                                for i in 0..nodes.len() {
-                                    new_positions.push(i);
-                                }
-                                // we use in place merge on target vector. The vector is resized and the new elements
-                                // are inserted from the end by iterating the new nodes and old nodes from the end.
-                                let orig_len = nodes.len();
-                                let b_len = index_to_add.len();
-
-                                nodes.resize(orig_len + b_len, NodeLayout { node_index: 0 });
-                                node_shapes.resize(orig_len + b_len, NodeShapeData::default());
-                                positions.resize(orig_len + b_len, NodePosition::default());
-                                individual_node_styles.resize(orig_len + b_len, IndividualNodeStyleData::default());
-
-                                let mut i = orig_len as isize - 1;
-                                let mut j = b_len as isize - 1;
-                                let mut k = (orig_len + b_len) as isize - 1;
-
-                                while j >= 0 {
-                                    if i >= 0 && nodes[i as usize].node_index > index_to_add[j as usize].1 {
-                                        nodes[k as usize] = nodes[i as usize];
-                                        node_shapes[k as usize] = node_shapes[i as usize];
-                                        positions[k as usize] = positions[i as usize];
-                                        new_positions[i as usize] = k as usize;
-                                        individual_node_styles[k as usize] = individual_node_styles[i as usize];
-                                        i -= 1;
-                                    } else {
-                                        nodes[k as usize] = NodeLayout {
-                                            node_index: index_to_add[j as usize].1,
-                                        };
-                                        node_shapes[k as usize] = NodeShapeData::default();
-                                        positions[k as usize] = NodePosition::default();
-                                        individual_node_styles[k as usize] = IndividualNodeStyleData::default();
-                                        j -= 1;
-                                    }
-                                    k -= 1;
-                                }
-
-                                // now need to set new edge indexes to new ones
-                                edges.par_iter_mut().for_each(|edge| {
-                                    edge.from = new_positions[edge.from];
-                                    edge.to = new_positions[edge.to];
-                                });
-                            }
-                        }
+            if let Ok(mut layout) = self.layout_data.write() {
+                let LayoutData {
+                    nodes,
+                    edges,
+                    positions,
+                    node_shapes,
+                    individual_node_styles,
+                } = &mut *layout;
+                // stores the new indexes for old nodes indexes, needed to update edges from and to fields
+                let mut new_positions: Vec<usize> = Vec::with_capacity(nodes.len());
+                for i in 0..nodes.len() {
+                    new_positions.push(i);
+                }
+                // we use in place merge on target vector. The vector is resized and the new elements
+                // are inserted from the end by iterating the new nodes and old nodes from the end.
+                let orig_len = nodes.len();
+                let b_len = index_to_add.len();
+
+                nodes.resize(orig_len + b_len, NodeLayout { node_index: 0 });
+                node_shapes.resize(orig_len + b_len, NodeShapeData::default());
+                positions.resize(orig_len + b_len, NodePosition::default());
+                individual_node_styles.resize(orig_len + b_len, IndividualNodeStyleData::default());
+
+                let mut i = orig_len as isize - 1;
+                let mut j = b_len as isize - 1;
+                let mut k = (orig_len + b_len) as isize - 1;
+
+                while j >= 0 {
+                    if i >= 0 && nodes[i as usize].node_index > index_to_add[j as usize].1 {
+                        nodes[k as usize] = nodes[i as usize];
+                        node_shapes[k as usize] = node_shapes[i as usize];
+                        positions[k as usize] = positions[i as usize];
+                        new_positions[i as usize] = k as usize;
+                        individual_node_styles[k as usize] = individual_node_styles[i as usize];
+                        i -= 1;
+                    } else {
+                        nodes[k as usize] = NodeLayout {
+                            node_index: index_to_add[j as usize].1,
+                        };
+                        node_shapes[k as usize] = NodeShapeData::default();
+                        positions[k as usize] = NodePosition::default();
+                        individual_node_styles[k as usize] = IndividualNodeStyleData::default();
+                        j -= 1;
                     }
+                    k -= 1;
                 }
+
+                // now need to set new edge indexes to new ones
+                edges.par_iter_mut().for_each(|edge| {
+                    edge.from = new_positions[edge.from];
+                    edge.to = new_positions[edge.to];
+                });
             }
             self.data_epoch += 1;
             self.show_orthogonal = false;
@@ -417,24 +577,15 @@ impl SortedNodeLayout {
         ) -> R,
     ) -> Option<R> {
         self.stop_layout();
-        if let Ok(mut nodes) = self.nodes.write() {
-            if let Ok(mut positions) = self.positions.write() {
-                if let Ok(mut edges) = self.edges.write() {
-                    if let Ok(mut node_shapes) = self.node_shapes.write() {
-                        if let Ok(mut individual_node_styles) = self.individual_node_styles.write() {
-                            return Some(mutator(
-                                &mut nodes,
-                                &mut positions,
-                                &mut edges,
-                                &mut node_shapes,
-                                &mut individual_node_styles,
-                            ));
-                        }
-                    }
-                }
-            }
-        }
-        None
+        let mut layout = self.layout_data.write().ok()?;
+        let LayoutData {
+            nodes,
+            edges,
+            positions,
+            node_shapes,
+            individual_node_styles,
+        } = &mut *layout;
+        Some(mutator(nodes, positions, edges, node_shapes, individual_node_styles))
     }
 
     pub fn remove(&mut self, value: IriIndex, hidden_predicates: &SortedVec) {
@@ -451,6 +602,7 @@ impl SortedNodeLayout {
             node_shapes.clear();
             individual_node_styles.clear();
         });
+        self.collapsed_clusters.clear();
         self.show_orthogonal = false;
         self.orth_edges = None;
         self.redo_stack.clear();
@@ -463,8 +615,9 @@ impl SortedNodeLayout {
      * Returns true if any node was removed.
      */
     pub fn retain(&mut self, hidden_predicates: &SortedVec, is_undo: bool, f: impl Fn(&NodeLayout) -> bool) -> bool {
-        let pos_to_remove = if let Ok(nodes) = self.nodes.read() {
-            let pos_to_remove: Vec<usize> = nodes
+        let pos_to_remove = if let Ok(layout) = self.layout_data.read() {
+            let pos_to_remove: Vec<usize> = layout
+                .nodes
                 .iter()
                 .enumerate()
                 .filter(|(_node_pos, node)| !f(node))
@@ -636,20 +789,28 @@ impl SortedNodeLayout {
     }
 
     pub fn get_pos(&self, value: IriIndex) -> Option<usize> {
-        if let Ok(nodes) = self.nodes.read() {
-            if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(&value)) {
-                return Some(pos);
-            }
+        if let Ok(layout) = self.layout_data.read() {
+            return node_pos(&layout.nodes, value);
         }
         None
     }
 
+    /// Locks the rdf data and then the layout, the only lock order used by the views
+    pub fn read_with_data<'a>(
+        &'a self,
+        rdf_data: &'a RwLock<RdfData>,
+    ) -> Option<(RwLockReadGuard<'a, RdfData>, RwLockReadGuard<'a, LayoutData>)> {
+        let rdf_data = rdf_data.read().ok()?;
+        let layout = self.layout_data.read().ok()?;
+        Some((rdf_data, layout))
+    }
+
     pub fn to_center(&mut self) {
         let mut x = 0.0;
         let mut y = 0.0;
         let mut count = 0;
-        if let Ok(positions) = self.positions.read() {
-            for node_pos in positions.iter() {
+        if let Ok(layout) = self.layout_data.read() {
+            for node_pos in layout.positions.iter() {
                 x += node_pos.pos.x;
                 y += node_pos.pos.y;
                 count += 1;
@@ -657,8 +818,8 @@ impl SortedNodeLayout {
         }
         x /= count as f32;
         y /= count as f32;
-        if let Ok(mut positions) = self.positions.write() {
-            for node_pos in positions.iter_mut() {
+        if let Ok(mut layout) = self.layout_data.write() {
+            for node_pos in layout.positions.iter_mut() {
                 node_pos.pos.x -= x;
                 node_pos.pos.y -= y;
             }
@@ -673,6 +834,7 @@ impl SortedNodeLayout {
             node_shapes.clear();
             individual_node_styles.clear();
         });
+        self.collapsed_clusters.clear();
         self.undo_stack.clear();
         self.redo_stack.clear();
     }
@@ -696,22 +858,22 @@ impl SortedNodeLayout {
         let mut keep_temperature = self.keep_temperature.load(Ordering::Relaxed);
         #[cfg(target_arch = "wasm32")]
         if self.compute_layout {
-            let config = LayoutConfig {
-                repulsion_constant: config.m_repulsion_constant,
-                attraction_factor: config.m_attraction_factor,
-                gravity_effect_radius: config.gravity_effect_radius,
+            let config = self.layout_config(config);
+            let (max_move, new_positions) = {
+                let layout = self.layout_data.read().unwrap();
+                layout_graph_nodes(
+                    &layout.nodes,
+                    &layout.node_shapes,
+                    &layout.positions,
+                    &layout.edges,
+                    &layout.individual_node_styles,
+                    &config,
+                    hidden_predicates,
+                    self.layout_temperature,
+                )
             };
-            let (max_move, new_positions) = layout_graph_nodes(
-                &self.nodes.read().unwrap(),
-                &self.node_shapes.read().unwrap(),
-                &self.positions.read().unwrap(),
-                &self.edges.read().unwrap(),
-                &config,
-                hidden_predicates,
-                self.layout_temperature,
-            );
-            if let Ok(mut positions) = self.positions.write() {
-                *positions = new_positions;
+            if let Ok(mut layout) = self.layout_data.write() {
+                layout.positions = new_positions;
             }
             if !keep_temperature {
                 self.layout_temperature *= 0.98;
@@ -792,6 +954,35 @@ impl SortedNodeLayout {
         self.start_background_layout(config, hidden_predicates, 100.0);
     }
 
+    /// Force that groups the nodes of a cluster in the layout
+    fn cluster_attraction(&self) -> f32 {
+        if self.group_by_cluster { 0.5 } else { 0.0 }
+    }
+
+    /// Forces of the force layout for the visual graph
+    pub fn layout_config(&self, config: &Config) -> LayoutConfig {
+        LayoutConfig {
+            repulsion_constant: config.m_repulsion_constant,
+            attraction_factor: config.m_attraction_factor,
+            gravity_effect_radius: config.gravity_effect_radius,
+            cluster_attraction: self.cluster_attraction(),
+            edge_forces: self.edge_forces.clone(),
+        }
+    }
+
+    /// Takes the edge forces of the styles, a running layout uses them at once
+    pub fn update_edge_forces(&mut self, edge_forces: HashMap<IriIndex, EdgeLayoutForce>) {
+        if self.edge_forces == edge_forces {
+            return;
+        }
+        if let Some(layout_handle) = &self.layout_handle {
+            let _ = layout_handle
+                .update_sender
+                .send(LayoutConfUpdate::UpdateEdgeForces(edge_forces.clone()));
+        }
+        self.edge_forces = edge_forces;
+    }
+
     pub fn stop_layout(&mut self) {
         self.stop_background_layout.store(true, Ordering::Relaxed);
     }
@@ -803,16 +994,9 @@ impl SortedNodeLayout {
         self.show_orthogonal = false;
         self.orth_edges = None;
         // println!("Starting background layout thread");
-        let nodes_clone = Arc::clone(&self.nodes);
-        let edges_clone = Arc::clone(&self.edges);
-        let positions_clone = Arc::clone(&self.positions);
-        let node_shapes_clone = Arc::clone(&self.node_shapes);
+        let layout_data = Arc::clone(&self.layout_data);
         let keep_temperature = Arc::clone(&self.keep_temperature);
-        let mut layout_config = LayoutConfig {
-            repulsion_constant: config.m_repulsion_constant,
-            attraction_factor: config.m_attraction_factor,
-            gravity_effect_radius: config.gravity_effect_radius,
-        };
+        let mut layout_config = self.layout_config(config);
         self.background_layout_finished.store(false, Ordering::Relaxed);
         self.stop_background_layout.store(false, Ordering::Relaxed);
         let is_done = Arc::clone(&self.background_layout_finished);
@@ -842,18 +1026,19 @@ impl SortedNodeLayout {
                         LayoutConfUpdate::UpdateAttractionFactor(value) => {
                             layout_config.attraction_factor = value;
                         }
+                        LayoutConfUpdate::UpdateEdgeForces(edge_forces) => {
+                            layout_config.edge_forces = edge_forces;
+                        }
                     }
                 }
                 let (max_move, new_positions) = {
-                    let positions = positions_clone.read().unwrap();
-                    let nodes = nodes_clone.read().unwrap();
-                    let node_shapes = node_shapes_clone.read().unwrap();
-                    let edges = edges_clone.read().unwrap();
+                    let layout = layout_data.read().unwrap();
                     layout_graph_nodes(
-                        &nodes,
-                        &node_shapes,
-                        &positions,
-                        &edges,
+                        &layout.nodes,
+                        &layout.node_shapes,
+                        &layout.positions,
+                        &layout.edges,
+                        &layout.individual_node_styles,
                         &layout_config,
                         &hidden_predicates,
                         temperature,
@@ -868,8 +1053,8 @@ impl SortedNodeLayout {
                     // better could be to use something like double buffering and store it in update are and the ui thread
                     // copy the positions in changed in its own structure
                     // the decision is who should wait ui or layout thread, now the layout thread waits for ui
-                    if let Ok(mut positions) = positions_clone.write() {
-                        *positions = new_positions;
+                    if let Ok(mut layout) = layout_data.write() {
+                        layout.positions = new_positions;
                     }
                 }
                 let keep_temperature = keep_temperature.load(Ordering::Relaxed);
@@ -895,19 +1080,21 @@ impl SortedNodeLayout {
     }
 
     pub fn hide_orphans(&mut self, hidden_predicates: &SortedVec) {
-        let mut used_positions: Vec<usize> = self
-            .edges
-            .read()
-            .unwrap()
-            .iter()
-            .filter(|edge| !hidden_predicates.contains(edge.predicate))
-            .flat_map(|edge| vec![edge.from, edge.to])
-            .collect();
+        let (mut used_positions, nodes_len) = {
+            let layout = self.layout_data.read().unwrap();
+            let used_positions: Vec<usize> = layout
+                .edges
+                .iter()
+                .filter(|edge| !hidden_predicates.contains(edge.predicate))
+                .flat_map(|edge| vec![edge.from, edge.to])
+                .collect();
+            (used_positions, layout.nodes.len())
+        };
         used_positions.sort_unstable();
         used_positions.dedup();
         let mut used_positions_cursor = 0;
         let mut pos_to_remove: Vec<usize> = Vec::new();
-        for pos in 0..self.nodes.read().unwrap().len() {
+        for pos in 0..nodes_len {
             if used_positions_cursor >= used_positions.len() {
                 pos_to_remove.push(pos);
             } else if pos == used_positions[used_positions_cursor] {
@@ -924,14 +1111,16 @@ impl SortedNodeLayout {
             Some(pos) => pos,
             None => return false,
         };
-        let len = self.nodes.read().unwrap().len();
+        let layout = self.layout_data.read().unwrap();
+        let len = layout.nodes.len();
         let mut adj = vec![Vec::new(); len];
-        for edge in self.edges.read().unwrap().iter() {
+        for edge in layout.edges.iter() {
             if !hidden_predicates.contains(edge.predicate) {
                 adj[edge.from].push(edge.to);
                 adj[edge.to].push(edge.from);
             }
         }
+        drop(layout);
 
         // BFS traversal
         let mut visited = FixedBitSet::with_capacity(len);
@@ -959,7 +1148,8 @@ impl SortedNodeLayout {
     }
 
     pub fn remove_redundant_edges(&mut self, hidden_predicates: &SortedVec) {
-        if let Ok(mut edges) = self.edges.write() {
+        if let Ok(mut layout) = self.layout_data.write() {
+            let edges = &mut layout.edges;
             let mut groups: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
             for (edge_index, edge) in edges.iter().enumerate() {
                 groups
@@ -987,10 +1177,63 @@ impl SortedNodeLayout {
                 edges.remove(*pos);
             }
             // println!("Removed {} redundant edges", edges.len());
-            update_edges_groups(&mut edges, hidden_predicates);
+            update_edges_groups(edges, hidden_predicates);
         }
     }
 
+    /// Rebuilds the edges of one predicate between visible nodes, after references of the predicate were changed in the data
+    pub fn refresh_predicate_edges(&mut self, predicate: IriIndex, node_data: &NodeData, hidden_predicates: &SortedVec) {
+        self.rebuild_edges(Some(predicate), node_data, hidden_predicates);
+    }
+
+    /// Rebuilds all edges between the visible nodes, e.g. after the data were replaced by undo
+    pub fn refresh_edges(&mut self, node_data: &NodeData, hidden_predicates: &SortedVec) {
+        self.rebuild_edges(None, node_data, hidden_predicates);
+    }
+
+    fn rebuild_edges(&mut self, predicate: Option<IriIndex>, node_data: &NodeData, hidden_predicates: &SortedVec) {
+        if let Ok(mut layout) = self.layout_data.write() {
+            let LayoutData { nodes, edges, .. } = &mut *layout;
+            edges.retain(|edge| predicate.is_some_and(|predicate| edge.predicate != predicate));
+            // members of collapsed clusters are sources and targets of the edges of their meta-node
+            let sources = nodes.iter().enumerate().map(|(node_pos, node_layout)| (node_pos, node_layout.node_index));
+            let member_sources = self.collapsed_clusters.members.iter().flat_map(|(meta_node, members)| {
+                let meta_pos = node_pos(nodes, *meta_node);
+                members.iter().filter_map(move |member| meta_pos.map(|meta_pos| (meta_pos, member.index)))
+            });
+            for (node_pos, node_index) in sources.chain(member_sources) {
+                let Some((_, node)) = node_data.get_node_by_index(node_index) else {
+                    continue;
+                };
+                let composite_references: Vec<_> = if self.flatten_blank_nodes {
+                    composite_references(node_data, node)
+                        .into_iter()
+                        .filter(|(_, target_index)| *target_index != node_index)
+                        .collect()
+                } else {
+                    Vec::new()
+                };
+                for (reference_predicate, target_index) in node.references.iter().chain(composite_references.iter()) {
+                    let visible_index = self.collapsed_clusters.visible_index(*target_index);
+                    if predicate.is_none_or(|predicate| *reference_predicate == predicate)
+                        && let Ok(target_pos) = nodes.binary_search_by(|node| node.node_index.cmp(&visible_index))
+                        // edges inside of collapsed cluster
+                        && (target_pos != node_pos || *target_index == node_index)
+                    {
+                        edges.push(Edge {
+                            from: node_pos,
+                            to: target_pos,
+                            predicate: *reference_predicate,
+                            bezier_distance: 0.0,
+                        });
+                    }
+                }
+            }
+            update_edges_groups(edges, hidden_predicates);
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub fn run_algorithm(
         &mut self,
         graph_algorithm: GraphAlgorithm,
@@ -998,149 +1241,149 @@ impl SortedNodeLayout {
         statistics_data: &mut StatisticsData,
         config: &Config,
         hidden_predicates: &SortedVec,
+        mut computed_values: Option<Vec<f32>>,
+        weighted_edges: &[WeightedEdge],
     ) {
-        if let Ok(nodes) = self.nodes.read() {
+        if let Ok(mut layout) = self.layout_data.write() {
+            let LayoutData {
+                nodes,
+                edges,
+                individual_node_styles: individual_node_style,
+                ..
+            } = &mut *layout;
             if !nodes.is_empty() {
-                if let Ok(edges) = self.edges.read() {
-                    // println!("run algorithm: {:?}", graph_algorithm);
-                    let nodes_len = nodes.len();
-                    if self.data_epoch != statistics_data.data_epoch {
-                        if let Ok(mut individual_node_style) = self.individual_node_styles.write() {
-                            if individual_node_style.len() != nodes.len() {
-                                individual_node_style.resize(nodes.len(), IndividualNodeStyleData::default());
-                            }
-                            statistics_data.nodes.resize(nodes_len, (0, 0));
-                            for (index, node) in nodes.iter().enumerate() {
-                                statistics_data.nodes[index] = (node.node_index, index as u32);
-                            }
-                            if statistics_data.selected_idx.is_none() && !statistics_data.nodes.is_empty() {
-                                statistics_data.selected_idx = Some((statistics_data.nodes[0].0, 0));
-                            }
-                            statistics_data.results.clear();
-                            if graph_algorithm.is_clustering() {
-                                let cluster = run_clustering_algorithm(
-                                    graph_algorithm,
-                                    nodes_len,
-                                    &edges,
-                                    config,
-                                    hidden_predicates,
-                                );
-                                let values = cluster.node_cluster.iter().map(|e| *e as f32).collect::<Vec<f32>>();
-                                for (index, value) in cluster.node_cluster.iter().enumerate() {
-                                    individual_node_style[index].set_cluster(*value);
-                                }
-                                statistics_data
-                                    .results
-                                    .push(StatisticsResult::new_for_alg(values, graph_algorithm));
-                                if let Some(parameters) = cluster.parameters {
-                                    statistics_data.results.push(StatisticsResult::new_for_values(
-                                        parameters,
-                                        graph_algorithm.get_statistics_values()[1],
-                                    ));
-                                }
-                            } else {
-                                let values: Vec<f32> =
-                                    run_algorithm(graph_algorithm, nodes_len, &edges, hidden_predicates);
-                                let values_layers: Vec<u8> = distribute_to_zoom_layers(&values);
-                                for (index, (layer, value)) in values_layers.iter().zip(&values).enumerate() {
-                                    individual_node_style[index].set_size_value(*value, visualization_style);
-                                    individual_node_style[index]
-                                        .semantic_zoom_interval
-                                        .set_from_layout(*layer);
-                                }
-                                statistics_data
-                                    .results
-                                    .push(StatisticsResult::new_for_alg(values, graph_algorithm));
-                            }
-                            self.update_node_shapes = true;
-                            self.has_semantic_zoom = true;
-                            statistics_data.data_epoch = self.data_epoch;
+                log::info!("Run algorithm {:?} on {} nodes", graph_algorithm, nodes.len());
+                let nodes_len = nodes.len();
+                if self.data_epoch != statistics_data.data_epoch {
+                    if individual_node_style.len() != nodes.len() {
+                        individual_node_style.resize(nodes.len(), IndividualNodeStyleData::default());
+                    }
+                    statistics_data.nodes.resize(nodes_len, (0, 0));
+                    for (index, node) in nodes.iter().enumerate() {
+                        statistics_data.nodes[index] = (node.node_index, index as u32);
+                    }
+                    if statistics_data.selected_idx.is_none() && !statistics_data.nodes.is_empty() {
+                        statistics_data.selected_idx = Some((statistics_data.nodes[0].0, 0));
+                    }
+                    statistics_data.results.clear();
+                    if graph_algorithm.is_clustering() {
+                        let cluster = run_clustering_algorithm(
+                            graph_algorithm,
+                            nodes_len,
+                            edges,
+                            config,
+                            hidden_predicates,
+                            weighted_edges,
+                        );
+                        let values = cluster.node_cluster.iter().map(|e| *e as f32).collect::<Vec<f32>>();
+                        for (index, value) in cluster.node_cluster.iter().enumerate() {
+                            individual_node_style[index].set_cluster(*value);
+                        }
+                        statistics_data
+                            .results
+                            .push(StatisticsResult::new_for_alg(values, graph_algorithm));
+                        if let Some(parameters) = cluster.parameters {
+                            statistics_data.results.push(StatisticsResult::new_for_values(
+                                parameters,
+                                graph_algorithm.get_statistics_values()[1],
+                            ));
                         }
                     } else {
-                        let statistic_value = graph_algorithm.get_statistics_values()[0];
-                        let result = statistics_data
+                        let values: Vec<f32> = computed_values.take().unwrap_or_else(|| {
+                            run_algorithm(graph_algorithm, nodes_len, edges, hidden_predicates, weighted_edges)
+                        });
+                        let values_layers: Vec<u8> = distribute_to_zoom_layers(&values);
+                        for (index, (layer, value)) in values_layers.iter().zip(&values).enumerate() {
+                            individual_node_style[index].set_size_value(*value, visualization_style);
+                            individual_node_style[index].set_zoom_layer(*layer);
+                        }
+                        statistics_data
                             .results
-                            .iter()
-                            .find(|res| res.statistics_value() == statistic_value);
-                        if let Some(result) = result {
-                            // no action needed the data is already in result but we need to set the individual node styles
-                            if let Ok(mut individual_node_style) = self.individual_node_styles.write() {
-                                if graph_algorithm.is_clustering() {
-                                    for (index, value) in result.get_data_vec().iter().enumerate() {
-                                        let node_index = statistics_data.nodes[index].1 as usize;
-                                        individual_node_style[node_index].set_cluster(*value as u32);
-                                    }
-                                } else {
-                                    let values_layers: Vec<u8> = distribute_to_zoom_layers(result.get_data_vec());
-                                    for (index, (value, layer)) in
-                                        result.get_data_vec().iter().zip(&values_layers).enumerate()
-                                    {
-                                        let node_index = statistics_data.nodes[index].1 as usize;
-                                        individual_node_style[node_index].set_size_value(*value, visualization_style);
-                                        individual_node_style[index]
-                                            .semantic_zoom_interval
-                                            .set_from_layout(*layer);
-                                    }
-                                }
+                            .push(StatisticsResult::new_for_alg(values, graph_algorithm));
+                    }
+                    self.update_node_shapes = true;
+                    self.has_semantic_zoom = true;
+                    statistics_data.data_epoch = self.data_epoch;
+                } else {
+                    let statistic_value = graph_algorithm.get_statistics_values()[0];
+                    if computed_values.is_some() {
+                        // new computed values replace the old result
+                        statistics_data
+                            .results
+                            .retain(|res| res.statistics_value() != statistic_value);
+                    }
+                    let result = statistics_data
+                        .results
+                        .iter()
+                        .find(|res| res.statistics_value() == statistic_value);
+                    if let Some(result) = result {
+                        // no action needed the data is already in result but we need to set the individual node styles
+                        if graph_algorithm.is_clustering() {
+                            for (index, value) in result.get_data_vec().iter().enumerate() {
+                                let node_index = statistics_data.nodes[index].1 as usize;
+                                individual_node_style[node_index].set_cluster(*value as u32);
                             }
                         } else {
-                            if graph_algorithm.is_clustering() {
-                                let cluster = run_clustering_algorithm(
-                                    graph_algorithm,
-                                    nodes_len,
-                                    &edges,
-                                    config,
-                                    hidden_predicates,
-                                );
-                                let values = statistics_data
-                                    .nodes
-                                    .iter()
-                                    .map(|(_iri, pos)| cluster.node_cluster[*pos as usize] as f32)
-                                    .collect::<Vec<f32>>();
-                                if let Ok(mut individual_node_style) = self.individual_node_styles.write() {
-                                    for (index, value) in cluster.node_cluster.iter().enumerate() {
-                                        individual_node_style[index].set_cluster(*value);
-                                    }
-                                }
-                                statistics_data
-                                    .results
-                                    .push(StatisticsResult::new_for_alg(values, graph_algorithm));
-                                if let Some(parameters) = cluster.parameters {
-                                    let values = statistics_data
-                                        .nodes
-                                        .iter()
-                                        .map(|(_iri, pos)| parameters[*pos as usize])
-                                        .collect::<Vec<f32>>();
-                                    statistics_data.results.push(StatisticsResult::new_for_values(
-                                        values,
-                                        graph_algorithm.get_statistics_values()[1],
-                                    ));
-                                }
-                            } else {
-                                let values = run_algorithm(graph_algorithm, nodes_len, &edges, hidden_predicates);
-                                // the values could be already resorted so use position index to get them in right order
-                                let sorted_values = statistics_data
-                                    .nodes
-                                    .iter()
-                                    .map(|(_iri, pos)| values[*pos as usize])
-                                    .collect::<Vec<f32>>();
-                                let values_layers: Vec<u8> = distribute_to_zoom_layers(&values);
-                                if let Ok(mut individual_node_style) = self.individual_node_styles.write() {
-                                    for (index, (value, layer)) in values.iter().zip(&values_layers).enumerate() {
-                                        individual_node_style[index].set_size_value(*value, visualization_style);
-                                        individual_node_style[index]
-                                            .semantic_zoom_interval
-                                            .set_from_layout(*layer);
-                                    }
-                                }
-                                statistics_data
-                                    .results
-                                    .push(StatisticsResult::new_for_alg(sorted_values, graph_algorithm));
+                            let values_layers: Vec<u8> = distribute_to_zoom_layers(result.get_data_vec());
+                            for (index, (value, layer)) in result.get_data_vec().iter().zip(&values_layers).enumerate()
+                            {
+                                let node_index = statistics_data.nodes[index].1 as usize;
+                                individual_node_style[node_index].set_size_value(*value, visualization_style);
+                                individual_node_style[index].set_zoom_layer(*layer);
                             }
                         }
-                        self.update_node_shapes = true;
-                        self.has_semantic_zoom = true;
+                    } else if graph_algorithm.is_clustering() {
+                        let cluster = run_clustering_algorithm(
+                            graph_algorithm,
+                            nodes_len,
+                            edges,
+                            config,
+                            hidden_predicates,
+                            weighted_edges,
+                        );
+                        let values = statistics_data
+                            .nodes
+                            .iter()
+                            .map(|(_iri, pos)| cluster.node_cluster[*pos as usize] as f32)
+                            .collect::<Vec<f32>>();
+                        for (index, value) in cluster.node_cluster.iter().enumerate() {
+                            individual_node_style[index].set_cluster(*value);
+                        }
+                        statistics_data
+                            .results
+                            .push(StatisticsResult::new_for_alg(values, graph_algorithm));
+                        if let Some(parameters) = cluster.parameters {
+                            let values = statistics_data
+                                .nodes
+                                .iter()
+                                .map(|(_iri, pos)| parameters[*pos as usize])
+                                .collect::<Vec<f32>>();
+                            statistics_data.results.push(StatisticsResult::new_for_values(
+                                values,
+                                graph_algorithm.get_statistics_values()[1],
+                            ));
+                        }
+                    } else {
+                        let values = computed_values.take().unwrap_or_else(|| {
+                            run_algorithm(graph_algorithm, nodes_len, edges, hidden_predicates, weighted_edges)
+                        });
+                        // the values could be already resorted so use position index to get them in right order
+                        let sorted_values = statistics_data
+                            .nodes
+                            .iter()
+                            .map(|(_iri, pos)| values[*pos as usize])
+                            .collect::<Vec<f32>>();
+                        let values_layers: Vec<u8> = distribute_to_zoom_layers(&values);
+                        for (index, (value, layer)) in values.iter().zip(&values_layers).enumerate() {
+                            individual_node_style[index].set_size_value(*value, visualization_style);
+                            individual_node_style[index].set_zoom_layer(*layer);
+                        }
+                        statistics_data
+                            .results
+                            .push(StatisticsResult::new_for_alg(sorted_values, graph_algorithm));
                     }
+                    self.update_node_shapes = true;
+                    self.has_semantic_zoom = true;
                 }
             }
         }
@@ -1152,7 +1395,7 @@ impl SortedNodeLayout {
             self.orth_edges = None;
             command.undo(self, &hidden_predicates, config, true);
         } else {
-            println!("Nothing to undo");
+            log::debug!("Nothing to undo");
         }
     }
 
@@ -1165,12 +1408,12 @@ impl SortedNodeLayout {
     }
 
     pub fn select_all(&self, ui_state: &mut UIState) {
-        if let Ok(nodes) = self.nodes.read() {
-            for node in nodes.iter() {
+        if let Ok(layout) = self.layout_data.read() {
+            for node in layout.nodes.iter() {
                 ui_state.selected_nodes.insert(node.node_index);
             }
-            if ui_state.selected_node.is_none() && !nodes.is_empty() {
-                ui_state.selected_node = Some(nodes[0].node_index);
+            if ui_state.selected_node.is_none() && !layout.nodes.is_empty() {
+                ui_state.selected_node = Some(layout.nodes[0].node_index);
             }
         }
     }
@@ -1181,9 +1424,9 @@ impl SortedNodeLayout {
     }
 
     pub fn invert_selection(&self, ui_state: &mut UIState) {
-        if let Ok(nodes) = self.nodes.read() {
+        if let Ok(layout) = self.layout_data.read() {
             let mut new_selection: BTreeSet<IriIndex> = BTreeSet::new();
-            for node in nodes.iter() {
+            for node in layout.nodes.iter() {
                 if !ui_state.selected_nodes.contains(&node.node_index) {
                     new_selection.insert(node.node_index);
                 }
@@ -1200,61 +1443,60 @@ impl SortedNodeLayout {
     }
 
     pub fn expand_selection(&self, ui_state: &mut UIState) {
-        if let Ok(edges) = self.edges.read() {
-            if let Ok(nodes) = self.nodes.read() {
-                let mut new_selected: Vec<IriIndex> = Vec::new();
-                for selected_node in ui_state.selected_nodes.iter() {
-                    if let Some(pos) = nodes.binary_search_by(|e| e.node_index.cmp(&selected_node)).ok() {
-                        for edge in edges.iter() {
-                            if edge.from == pos {
-                                let node_index = nodes[edge.to].node_index;
-                                new_selected.push(node_index);
-                            } else if edge.to == pos {
-                                let node_index = nodes[edge.from].node_index;
-                                new_selected.push(node_index);
-                            }
+        if let Ok(layout) = self.layout_data.read() {
+            let LayoutData { nodes, edges, .. } = &*layout;
+            let mut new_selected: Vec<IriIndex> = Vec::new();
+            for selected_node in ui_state.selected_nodes.iter() {
+                if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(selected_node)) {
+                    for edge in edges.iter() {
+                        if edge.from == pos {
+                            let node_index = nodes[edge.to].node_index;
+                            new_selected.push(node_index);
+                        } else if edge.to == pos {
+                            let node_index = nodes[edge.from].node_index;
+                            new_selected.push(node_index);
                         }
                     }
                 }
-                for node in new_selected {
-                    ui_state.selected_nodes.insert(node);
-                }
+            }
+            for node in new_selected {
+                ui_state.selected_nodes.insert(node);
             }
         }
     }
 
     pub fn shirk_selection(&self, ui_state: &mut UIState) {
-        if let Ok(edges) = self.edges.read() {
-            if let Ok(nodes) = self.nodes.read() {
-                let mut to_remove: Vec<IriIndex> = Vec::new();
-                for selected_node in ui_state.selected_nodes.iter() {
-                    if let Some(pos) = nodes.binary_search_by(|e| e.node_index.cmp(&selected_node)).ok() {
-                        let mut connected_num = 0;
-                        for edge in edges.iter() {
-                            if edge.from != edge.to
-                                && (edge.from == pos && ui_state.selected_nodes.contains(&nodes[edge.to].node_index))
-                                || (edge.to == pos && ui_state.selected_nodes.contains(&nodes[edge.from].node_index))
-                            {
-                                connected_num += 1;
-                                if connected_num > 1 {
-                                    break;
-                                }
+        if let Ok(layout) = self.layout_data.read() {
+            let LayoutData { nodes, edges, .. } = &*layout;
+            let mut to_remove: Vec<IriIndex> = Vec::new();
+            for selected_node in ui_state.selected_nodes.iter() {
+                if let Ok(pos) = nodes.binary_search_by(|e| e.node_index.cmp(selected_node)) {
+                    let mut connected_num = 0;
+                    for edge in edges.iter() {
+                        if edge.from != edge.to
+                            && (edge.from == pos && ui_state.selected_nodes.contains(&nodes[edge.to].node_index))
+                            || (edge.to == pos && ui_state.selected_nodes.contains(&nodes[edge.from].node_index))
+                        {
+                            connected_num += 1;
+                            if connected_num > 1 {
+                                break;
                             }
                         }
-                        if connected_num <= 1 {
-                            to_remove.push(*selected_node);
-                        }
                     }
-                }
-                for node in to_remove {
-                    ui_state.selected_nodes.remove(&node);
-                }
-                if let Some(selected_index) = ui_state.selected_node {
-                    if !ui_state.selected_nodes.contains(&selected_index) && !ui_state.selected_nodes.is_empty() {
-                        ui_state.selected_node = Some(*ui_state.selected_nodes.iter().next().unwrap());
+                    if connected_num <= 1 {
+                        to_remove.push(*selected_node);
                     }
                 }
             }
+            for node in to_remove {
+                ui_state.selected_nodes.remove(&node);
+            }
+            if let Some(selected_index) = ui_state.selected_node
+                && !ui_state.selected_nodes.contains(&selected_index)
+                && !ui_state.selected_nodes.is_empty()
+            {
+                ui_state.selected_node = Some(*ui_state.selected_nodes.iter().next().unwrap());
+            }
         }
     }
 }
@@ -1306,10 +1548,35 @@ pub fn update_edges_groups(edges: &mut [Edge], hidden_predicates: &SortedVec) {
 
 
 
+/// Groups of more than threshold edges that connect the same two nodes (despite the direction).
+/// Self edges are not bundled. The key is edge_bundle_key of the edges.
+pub fn edge_bundles(
+    edges: &[Edge],
+    hidden_predicates: &SortedVec,
+    threshold: usize,
+) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut bundles: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (edge_index, edge) in edges.iter().enumerate() {
+        if edge.from != edge.to && !hidden_predicates.contains(edge.predicate) {
+            bundles.entry(edge_bundle_key(edge)).or_default().push(edge_index);
+        }
+    }
+    bundles.retain(|_key, bundle| bundle.len() > threshold);
+    bundles
+}
+
+pub fn edge_bundle_key(edge: &Edge) -> (usize, usize) {
+    (edge.from.min(edge.to), edge.from.max(edge.to))
+}
+
 pub struct LayoutConfig {
     pub repulsion_constant: f32,
     pub attraction_factor: f32,
     pub gravity_effect_radius: f32,
+    // pull of the nodes to the center of their cluster, 0 disables grouping by cluster
+    pub cluster_attraction: f32,
+    // predicates with own edge length and stiffness, the other edges use attraction_factor
+    pub edge_forces: HashMap<IriIndex, EdgeLayoutForce>,
 }
 
 
@@ -1483,4 +1750,46 @@ mod tests {
         assert!(sorted_nodes.contains(0));
         assert!(sorted_nodes.contains(10));
     }
+
+    #[test]
+    fn test_edge_bundles() {
+        let edge = |from: usize, to: usize, predicate: IriIndex| Edge {
+            from,
+            to,
+            predicate,
+            bezier_distance: 0.0,
+        };
+        let edges = vec![edge(0, 1, 1), edge(1, 0, 2), edge(0, 1, 3), edge(1, 2, 1), edge(2, 2, 1), edge(2, 2, 2)];
+        let mut hidden_predicates = SortedVec::new();
+        let bundles = edge_bundles(&edges, &hidden_predicates, 2);
+        assert_eq!(1, bundles.len());
+        assert_eq!(Some(&vec![0, 1, 2]), bundles.get(&edge_bundle_key(&edges[1])));
+        hidden_predicates.add(3);
+        assert!(edge_bundles(&edges, &hidden_predicates, 2).is_empty());
+        assert_eq!(2, edge_bundles(&edges, &hidden_predicates, 0).len());
+    }
+
+    #[test]
+    fn test_shared_layout_data() {
+        let mut sorted_nodes = super::SortedNodeLayout::default();
+        for node_index in 0..10 {
+            sorted_nodes.add(super::NodeLayout::new(node_index));
+        }
+        let shared = Arc::clone(&sorted_nodes.layout_data);
+        sorted_nodes.layout_data.write().unwrap().positions[0].pos.x = 0.0;
+        // the layout thread and the ui share one lock for all vectors
+        let writer = std::thread::spawn(move || {
+            for _ in 0..1000 {
+                let mut layout = shared.write().unwrap();
+                layout.positions[0].pos.x += 1.0;
+            }
+        });
+        for _ in 0..1000 {
+            let layout = sorted_nodes.layout_data.read().unwrap();
+            assert_eq!(layout.nodes.len(), layout.positions.len());
+            assert_eq!(Some(3), node_pos(&layout.nodes, 3));
+        }
+        writer.join().unwrap();
+        assert_eq!(1000.0, sorted_nodes.layout_data.read().unwrap().positions[0].pos.x);
+    }
 }
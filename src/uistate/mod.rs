@@ -4,5 +4,10 @@ pub mod uistate;
 pub mod ref_selection;
 pub mod app;
 pub mod visual_query;
+pub mod project_template;
+pub mod statistics_job;
+pub mod cluster_collapse;
+pub mod graph_legend;
+pub mod style_theme;
 
 pub use self::uistate::*;
\ No newline at end of file
@@ -0,0 +1,278 @@
+use std::{collections::HashMap, path::Path};
+
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::FileDialog;
+use string_interner::Symbol;
+
+use crate::{
+    IriIndex, RdfGlanceApp, SystemMessage,
+    domain::{
+        StringIndexer,
+        graph_styles::{EdgeStyle, NodeStyle},
+        prefix_manager::PrefixManager,
+    },
+    support::SortedVec,
+    ui::graph_view::{NeighborPos, update_layout_edges},
+};
+
+/**
+ * Project template contains only the data independent parts of a project (styles, prefixes, hidden predicates)
+ * and the seeds for the initial graph and the starting SPARQL query. It is applied to new data of the same domain.
+ * All indexes refer to the type and predicate indexers of the template and must be mapped by iri.
+ */
+pub struct ProjectTemplate {
+    pub prefix_manager: PrefixManager,
+    pub predicate_indexer: StringIndexer,
+    pub type_indexer: StringIndexer,
+    pub node_styles: HashMap<IriIndex, NodeStyle>,
+    pub edge_styles: HashMap<IriIndex, EdgeStyle>,
+    pub hidden_predicates: SortedVec,
+    pub type_seeds: Vec<TypeSeed>,
+    // Iris of nodes that should always be in the initial graph
+    pub seed_nodes: Vec<Box<str>>,
+    // Query of the SPARQL editor, it is opened again when the template is applied
+    pub starting_query: Option<String>,
+}
+
+// Number of instances of the type that are put to the initial graph
+pub struct TypeSeed {
+    pub type_index: IriIndex,
+    pub count: u32,
+}
+
+impl Default for ProjectTemplate {
+    fn default() -> Self {
+        Self {
+            prefix_manager: PrefixManager::new(),
+            predicate_indexer: StringIndexer::new(),
+            type_indexer: StringIndexer::new(),
+            node_styles: HashMap::new(),
+            edge_styles: HashMap::new(),
+            hidden_predicates: SortedVec::new(),
+            type_seeds: Vec::new(),
+            seed_nodes: Vec::new(),
+            starting_query: None,
+        }
+    }
+}
+
+impl ProjectTemplate {
+    // Converts the iri prefixed by template prefixes to iri prefixed by data prefixes
+    fn data_iri(&self, iri: &str, prefix_manager: &PrefixManager) -> String {
+        match self.prefix_manager.get_full_opt(iri) {
+            Some(full_iri) => prefix_manager.get_prefixed(&full_iri),
+            None => prefix_manager.get_prefixed(iri),
+        }
+    }
+
+    // Maps template indexes to the indexes of the data, unknown iris are not mapped
+    fn index_map(
+        &self,
+        template_indexer: &StringIndexer,
+        data_indexer: &StringIndexer,
+        prefix_manager: &PrefixManager,
+    ) -> HashMap<IriIndex, IriIndex> {
+        let mut index_map = HashMap::new();
+        for (symbol, iri) in template_indexer.map.iter() {
+            if let Some(data_symbol) = data_indexer.map.get(self.data_iri(iri, prefix_manager)) {
+                index_map.insert(symbol.to_usize() as IriIndex, data_symbol.to_usize() as IriIndex);
+            }
+        }
+        index_map
+    }
+}
+
+impl RdfGlanceApp {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save_project_template_dialog(&mut self) {
+        if let Some(path) = FileDialog::new()
+            .add_filter("RDF Glance project template", &["rdfgtemplate"])
+            .set_file_name("project.rdfgtemplate")
+            .save_file()
+        {
+            match self.store_template(path.as_path()) {
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not save project template: {}", e));
+                }
+                Ok(_) => {
+                    self.set_status_message("Project template saved");
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn apply_project_template_dialog(&mut self) {
+        if let Some(path) = FileDialog::new()
+            .add_filter("RDF Glance project template", &["rdfgtemplate"])
+            .pick_file()
+        {
+            self.load_project_template(path.as_path());
+        }
+    }
+
+    pub fn load_project_template(&mut self, path: &Path) {
+        match ProjectTemplate::restore(path) {
+            Err(e) => {
+                self.system_message = SystemMessage::Error(format!("Can not load project template: {}", e));
+            }
+            Ok(template) => {
+                if self.is_empty() {
+                    // The iris are prefixed during loading, so the prefixes must be known before the data are loaded
+                    self.mut_rdf_data(|rdf_data| {
+                        for (iri, prefix) in template.prefix_manager.prefixes.iter() {
+                            if rdf_data.prefix_manager.prefixes.get_by_right(prefix).is_none() {
+                                rdf_data.prefix_manager.add_prefix(prefix, iri);
+                            }
+                        }
+                    });
+                    self.pending_template = Some(template);
+                    self.set_status_message("Project template will be applied to loaded data");
+                } else {
+                    self.apply_project_template(template);
+                    self.set_status_message("Project template applied");
+                }
+            }
+        }
+    }
+
+    pub fn apply_pending_template(&mut self) {
+        if let Some(template) = self.pending_template.take() {
+            self.apply_project_template(template);
+        }
+    }
+
+    pub fn apply_project_template(&mut self, mut template: ProjectTemplate) {
+        let Ok(rdf_data) = self.rdf_data.read() else {
+            return;
+        };
+        let type_map = template.index_map(
+            &template.type_indexer,
+            &rdf_data.node_data.indexers.type_indexer,
+            &rdf_data.prefix_manager,
+        );
+        let predicate_map = template.index_map(
+            &template.predicate_indexer,
+            &rdf_data.node_data.indexers.predicate_indexer,
+            &rdf_data.prefix_manager,
+        );
+        for (type_index, mut node_style) in template.node_styles.drain() {
+            if let Some(data_type_index) = type_map.get(&type_index) {
+                // the label predicate may be missing in the data, then the label of the type default style is kept
+                node_style.label_index = match predicate_map.get(&node_style.label_index) {
+                    Some(label_index) => *label_index,
+                    None => self.visualization_style.get_type_style_one(*data_type_index).label_index,
+                };
+                self.visualization_style.node_styles.insert(*data_type_index, node_style);
+            }
+        }
+        for (predicate_index, edge_style) in template.edge_styles.drain() {
+            if let Some(data_predicate_index) = predicate_map.get(&predicate_index) {
+                self.visualization_style.edge_styles.insert(*data_predicate_index, edge_style);
+            }
+        }
+        for predicate_index in template.hidden_predicates.data.iter() {
+            if let Some(data_predicate_index) = predicate_map.get(predicate_index) {
+                self.ui_state.hidden_predicates.add(*data_predicate_index);
+            }
+        }
+
+        let mut seeds: Vec<(IriIndex, IriIndex)> = Vec::new();
+        for type_seed in template.type_seeds.iter() {
+            if let Some(data_type_index) = type_map.get(&type_seed.type_index)
+                && let Some(type_data) = self.type_index.types.get(data_type_index)
+            {
+                for node_index in type_data.instances.iter().take(type_seed.count as usize) {
+                    seeds.push((*node_index, *node_index));
+                }
+            }
+        }
+        for iri in template.seed_nodes.iter() {
+            let data_iri = template.data_iri(iri, &rdf_data.prefix_manager);
+            if let Some(node_index) = rdf_data.node_data.get_node_index(&data_iri) {
+                seeds.push((node_index, node_index));
+            }
+        }
+        if !seeds.is_empty() {
+            let mut npos = NeighborPos::new();
+            if npos.add_many(&mut self.visible_nodes, &seeds, &self.config) {
+                update_layout_edges(
+                    &npos,
+                    &mut self.visible_nodes,
+                    &rdf_data.node_data,
+                    &self.ui_state.hidden_predicates,
+                );
+                npos.create_undo(&mut self.visible_nodes);
+                self.visible_nodes
+                    .start_layout(&self.config, &self.ui_state.hidden_predicates);
+            }
+        }
+        drop(rdf_data);
+        if let Some(query) = template.starting_query.take() {
+            self.open_sparql_query();
+            if let Some(sparql_query) = &mut self.sparql_query {
+                sparql_query.set_query(&query);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use egui::Color32;
+
+    use crate::RdfGlanceApp;
+
+    use super::*;
+
+    #[test]
+    fn test_template_round_trip() {
+        let mut dir = PathBuf::from("target/test-files");
+        fs::create_dir_all(&dir).expect("Failed to create test directory");
+        dir.push("template.rdfgtemplate");
+
+        let mut app = RdfGlanceApp::new(None, vec![]);
+        app.load_ttl("sample-rdf-data/programming_languages.ttl", true);
+        app.join_load(true);
+        let (type_index, node_index) = app
+            .read_rdf_data(|rdf_data| {
+                let node_index = rdf_data
+                    .node_data
+                    .get_node_index("dbr:Rust_(programming_language)")
+                    .unwrap();
+                let (_, node) = rdf_data.node_data.get_node_by_index(node_index).unwrap();
+                (node.types[0], node_index)
+            })
+            .unwrap();
+        app.visualization_style.node_styles.get_mut(&type_index).unwrap().color = Color32::RED;
+        app.visible_nodes.add_by_index(node_index);
+        app.ui_state.selected_nodes.insert(node_index);
+        app.open_sparql_query();
+        app.sparql_query.as_mut().unwrap().set_query("SELECT ?s WHERE { ?s a ?type }");
+        app.store_template(&dir).unwrap();
+        assert!(RdfGlanceApp::restore(&dir).is_err());
+
+        let mut other = RdfGlanceApp::new(None, vec![]);
+        other.load_ttl("sample-rdf-data/programming_languages.ttl", true);
+        other.join_load(true);
+        other.load_project_template(&dir);
+        assert!(other.pending_template.is_none());
+        let other_type_index = other
+            .read_rdf_data(|rdf_data| {
+                rdf_data.node_data.indexers.type_indexer.map.get(
+                    app.rdf_data.read().unwrap().node_data.indexers.type_indexer.index_to_str(type_index).unwrap(),
+                )
+            })
+            .unwrap()
+            .unwrap()
+            .to_usize() as IriIndex;
+        assert_eq!(Color32::RED, other.visualization_style.node_styles.get(&other_type_index).unwrap().color);
+        assert!(!other.visible_nodes.layout_data.read().unwrap().nodes.is_empty());
+        assert_eq!(
+            Some("SELECT ?s WHERE { ?s a ?type }"),
+            other.sparql_query.as_ref().map(|sparql_query| sparql_query.query())
+        );
+    }
+}
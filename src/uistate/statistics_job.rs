@@ -0,0 +1,91 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread::{self, JoinHandle};
+
+use crate::{
+    graph_algorithms::{betweenness_centrality::compute_betweenness_centrality_sampled, utils::normalize},
+    support::SortedVec,
+    uistate::layout::SortedNodeLayout,
+};
+
+/**
+ * Betweenness centrality computed in a background thread (on wasm directly).
+ * The ui shows the progress (processed source nodes) and can cancel the computation.
+ */
+pub struct BetweennessJob {
+    pub progress: Arc<AtomicUsize>,
+    pub cancel: Arc<AtomicBool>,
+    // number of source nodes to process
+    pub sources: usize,
+    // the result is dropped if the visual graph has changed meanwhile
+    pub data_epoch: u32,
+    #[cfg(not(target_arch = "wasm32"))]
+    join_handle: JoinHandle<Option<Vec<f32>>>,
+    #[cfg(target_arch = "wasm32")]
+    result: Option<Vec<f32>>,
+}
+
+impl BetweennessJob {
+    /// sample_size None or not smaller than number of nodes computes the exact values
+    pub fn start(visible_nodes: &SortedNodeLayout, hidden_predicates: &SortedVec, sample_size: Option<usize>) -> Self {
+        let (nodes_len, edges) = visible_nodes
+            .layout_data
+            .read()
+            .map(|layout| (layout.nodes.len(), layout.edges.clone()))
+            .unwrap_or_default();
+        let sample_size = sample_size.unwrap_or(nodes_len).min(nodes_len);
+        let progress = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let hidden_predicates = hidden_predicates.clone();
+        let compute = {
+            let progress = Arc::clone(&progress);
+            let cancel = Arc::clone(&cancel);
+            move || {
+                compute_betweenness_centrality_sampled(
+                    nodes_len,
+                    &edges,
+                    &hidden_predicates,
+                    sample_size,
+                    &progress,
+                    &cancel,
+                )
+                .map(|results| normalize(results.into_iter().map(|result| result.node_betweenness).collect()))
+            }
+        };
+        Self {
+            progress,
+            cancel,
+            sources: sample_size,
+            data_epoch: visible_nodes.data_epoch,
+            #[cfg(not(target_arch = "wasm32"))]
+            join_handle: thread::spawn(compute),
+            #[cfg(target_arch = "wasm32")]
+            result: compute(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.join_handle.is_finished();
+        #[cfg(target_arch = "wasm32")]
+        return true;
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.sources == 0 {
+            return 1.0;
+        }
+        self.progress.load(Ordering::Relaxed) as f32 / self.sources as f32
+    }
+
+    /// Normalized values in order of visual graph nodes, None if cancelled
+    pub fn join(self) -> Option<Vec<f32>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return self.join_handle.join().ok().flatten();
+        #[cfg(target_arch = "wasm32")]
+        return self.result;
+    }
+}
@@ -0,0 +1,387 @@
+use std::collections::BTreeMap;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::Path;
+
+use egui::Color32;
+#[cfg(not(target_arch = "wasm32"))]
+use rfd::FileDialog;
+use serde::{Deserialize, Serialize};
+use string_interner::Symbol;
+
+use crate::{
+    IriIndex, RdfGlanceApp, SystemMessage,
+    domain::{
+        Indexers, StringIndexer,
+        data_change::DataChange,
+        graph_styles::{EdgeStyle, GVisualizationStyle, NodeStyle},
+        prefix_manager::PrefixManager,
+        style_rules::{RuleOperator, StyleRule},
+    },
+};
+
+// (name, json) of the themes shipped with the application
+pub const BUNDLED_THEMES: [(&str, &str); 3] = [
+    ("FOAF", include_str!("../../assets/themes/foaf.json")),
+    ("Schema.org", include_str!("../../assets/themes/schema_org.json")),
+    ("SKOS and OWL", include_str!("../../assets/themes/skos_owl.json")),
+];
+
+/**
+ * Visualization styles stored as json so they can be shared between projects and users.
+ * In contrast to the project styles all types and predicates are referenced by full iris,
+ * so the theme can be applied to any data that use the same vocabulary.
+ */
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct StyleTheme {
+    pub name: String,
+    // type iri -> style
+    pub node_styles: BTreeMap<String, ThemeNodeStyle>,
+    // predicate iri -> style
+    pub edge_styles: BTreeMap<String, EdgeStyle>,
+    // type iri -> predicate iris
+    pub tooltip_properties: BTreeMap<String, Vec<String>>,
+    pub style_rules: Vec<ThemeStyleRule>,
+    pub default_label_in_node: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ThemeNodeStyle {
+    // predicate iri of the label, the label index of the style is bound to the data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label_property: Option<String>,
+    #[serde(flatten)]
+    pub style: NodeStyle,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ThemeStyleRule {
+    pub type_iri: String,
+    pub predicate_iri: String,
+    pub operator: RuleOperator,
+    pub value: String,
+    pub color: Option<Color32>,
+    pub size_factor: f32,
+}
+
+fn full_iri(indexer: &StringIndexer, index: IriIndex, prefix_manager: &PrefixManager) -> Option<String> {
+    let iri = indexer.index_to_str(index)?;
+    Some(prefix_manager.get_full_opt(iri).map_or_else(|| iri.to_string(), |full_iri| full_iri.into()))
+}
+
+// Theme iris are full iris, but prefixed iris with the prefixes of the data are accepted too
+fn data_index(indexer: &StringIndexer, iri: &str, prefix_manager: &PrefixManager) -> Option<IriIndex> {
+    indexer
+        .map
+        .get(prefix_manager.get_prefixed(iri))
+        .or_else(|| indexer.map.get(iri))
+        .map(|symbol| symbol.to_usize() as IriIndex)
+}
+
+impl StyleTheme {
+    pub fn from_style(
+        name: &str,
+        visualization_style: &GVisualizationStyle,
+        indexers: &Indexers,
+        prefix_manager: &PrefixManager,
+    ) -> Self {
+        let type_iri = |index: IriIndex| full_iri(&indexers.type_indexer, index, prefix_manager);
+        let predicate_iri = |index: IriIndex| full_iri(&indexers.predicate_indexer, index, prefix_manager);
+        let mut theme = StyleTheme {
+            name: name.to_string(),
+            default_label_in_node: visualization_style.default_label_in_node,
+            ..StyleTheme::default()
+        };
+        for (type_index, node_style) in visualization_style.node_styles.iter() {
+            if let Some(iri) = type_iri(*type_index) {
+                let label_property = if node_style.label_index == 0 {
+                    None
+                } else {
+                    predicate_iri(node_style.label_index)
+                };
+                theme.node_styles.insert(
+                    iri,
+                    ThemeNodeStyle {
+                        label_property,
+                        style: node_style.clone(),
+                    },
+                );
+            }
+        }
+        for (predicate_index, edge_style) in visualization_style.edge_styles.iter() {
+            if let Some(iri) = predicate_iri(*predicate_index) {
+                theme.edge_styles.insert(iri, edge_style.clone());
+            }
+        }
+        for (type_index, properties) in visualization_style.tooltip_properties.iter() {
+            if let Some(iri) = type_iri(*type_index) {
+                theme
+                    .tooltip_properties
+                    .insert(iri, properties.iter().filter_map(|property| predicate_iri(*property)).collect());
+            }
+        }
+        for rule in visualization_style.style_rules.rules.iter() {
+            if let Some(type_iri) = type_iri(rule.type_index)
+                && let Some(predicate_iri) = predicate_iri(rule.predicate)
+            {
+                theme.style_rules.push(ThemeStyleRule {
+                    type_iri,
+                    predicate_iri,
+                    operator: rule.operator,
+                    value: rule.value.clone(),
+                    color: rule.color,
+                    size_factor: rule.size_factor,
+                });
+            }
+        }
+        theme
+    }
+
+    /// Applies the styles of the types and predicates known in the data, returns the number of applied styles
+    pub fn apply(
+        &self,
+        visualization_style: &mut GVisualizationStyle,
+        indexers: &Indexers,
+        prefix_manager: &PrefixManager,
+    ) -> usize {
+        let type_index = |iri: &str| data_index(&indexers.type_indexer, iri, prefix_manager);
+        let predicate_index = |iri: &str| data_index(&indexers.predicate_indexer, iri, prefix_manager);
+        let mut applied = 0;
+        for (iri, theme_style) in self.node_styles.iter() {
+            if let Some(type_index) = type_index(iri) {
+                let mut node_style = theme_style.style.clone();
+                node_style.label_index = theme_style
+                    .label_property
+                    .as_deref()
+                    .and_then(predicate_index)
+                    .unwrap_or(0);
+                node_style.is_default = false;
+                visualization_style.node_styles.insert(type_index, node_style);
+                applied += 1;
+            }
+        }
+        for (iri, edge_style) in self.edge_styles.iter() {
+            if let Some(predicate_index) = predicate_index(iri) {
+                visualization_style.edge_styles.insert(predicate_index, edge_style.clone());
+                applied += 1;
+            }
+        }
+        for (iri, properties) in self.tooltip_properties.iter() {
+            if let Some(type_index) = type_index(iri) {
+                visualization_style.tooltip_properties.insert(
+                    type_index,
+                    properties.iter().filter_map(|property| predicate_index(property)).collect(),
+                );
+            }
+        }
+        let rules: Vec<StyleRule> = self
+            .style_rules
+            .iter()
+            .filter_map(|rule| {
+                Some(StyleRule {
+                    operator: rule.operator,
+                    value: rule.value.clone(),
+                    color: rule.color,
+                    size_factor: rule.size_factor,
+                    ..StyleRule::new(type_index(&rule.type_iri)?, predicate_index(&rule.predicate_iri)?)
+                })
+            })
+            .collect();
+        if !rules.is_empty() {
+            visualization_style.style_rules.rules = rules;
+            visualization_style.style_rules.update_index();
+        }
+        visualization_style.default_label_in_node = self.default_label_in_node;
+        applied
+    }
+
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl RdfGlanceApp {
+    pub fn apply_style_theme(&mut self, theme: &StyleTheme) {
+        let applied = if let Ok(rdf_data) = self.rdf_data.read() {
+            theme.apply(
+                &mut self.visualization_style,
+                &rdf_data.node_data.indexers,
+                &rdf_data.prefix_manager,
+            )
+        } else {
+            0
+        };
+        self.data_changes.notify(DataChange::StyleChanged);
+        self.set_status_message(&format!("Style theme \"{}\" applied to {} types and predicates", theme.name, applied));
+    }
+
+    pub fn apply_bundled_theme(&mut self, json: &str) {
+        match StyleTheme::from_json(json) {
+            Ok(theme) => self.apply_style_theme(&theme),
+            Err(e) => {
+                self.system_message = SystemMessage::Error(format!("Can not read style theme: {}", e));
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn export_style_theme_dialog(&mut self) {
+        if let Some(path) = FileDialog::new()
+            .add_filter("Style theme", &["json"])
+            .set_file_name("style-theme.json")
+            .save_file()
+        {
+            let name = path
+                .file_stem()
+                .map_or_else(|| "Style theme".to_string(), |stem| stem.to_string_lossy().to_string());
+            let json = match self.rdf_data.read() {
+                Ok(rdf_data) => StyleTheme::from_style(
+                    &name,
+                    &self.visualization_style,
+                    &rdf_data.node_data.indexers,
+                    &rdf_data.prefix_manager,
+                )
+                .to_json(),
+                Err(_) => return,
+            };
+            match json.map_err(anyhow::Error::from).and_then(|json| Ok(std::fs::write(&path, json)?)) {
+                Ok(_) => self.set_status_message("Style theme exported"),
+                Err(e) => {
+                    self.system_message = SystemMessage::Error(format!("Can not export style theme: {}", e));
+                }
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn import_style_theme_dialog(&mut self) {
+        if let Some(path) = FileDialog::new().add_filter("Style theme", &["json"]).pick_file() {
+            self.load_style_theme(path.as_path());
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_style_theme(&mut self, path: &Path) {
+        match std::fs::read_to_string(path)
+            .map_err(anyhow::Error::from)
+            .and_then(|json| Ok(StyleTheme::from_json(&json)?))
+        {
+            Ok(theme) => self.apply_style_theme(&theme),
+            Err(e) => {
+                self.system_message = SystemMessage::Error(format!("Can not import style theme: {}", e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        RdfData,
+        graph_styles::{ArrowStyle, NodeShape},
+        size_mapping::SizeMapping,
+        style_rules::StyleRules,
+    };
+    use std::collections::HashMap;
+
+    fn empty_style() -> GVisualizationStyle {
+        GVisualizationStyle {
+            node_styles: HashMap::new(),
+            edge_styles: HashMap::new(),
+            tooltip_properties: HashMap::new(),
+            style_rules: StyleRules::default(),
+            size_mapping: SizeMapping::default(),
+            default_node_style: NodeStyle::default(),
+            use_size_overwrite: false,
+            use_color_overwrite: false,
+            default_label_in_node: false,
+            min_size: 5.0,
+            max_size: 50.0,
+        }
+    }
+
+    fn rdf_data() -> RdfData {
+        RdfData::from_patch(
+            "A <http://example.org/a> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://xmlns.com/foaf/0.1/Person> .\n\
+             A <http://example.org/a> <http://xmlns.com/foaf/0.1/name> \"Alice\" .\n\
+             A <http://example.org/a> <http://xmlns.com/foaf/0.1/knows> <http://example.org/b> .\n",
+        )
+    }
+
+    #[test]
+    fn test_theme_round_trip() {
+        let mut rdf_data = rdf_data();
+        let indexers = &mut rdf_data.node_data.indexers;
+        let person = indexers.type_indexer.get_index("foaf:Person");
+        let name = indexers.predicate_indexer.get_index("foaf:name");
+        let knows = indexers.predicate_indexer.get_index("foaf:knows");
+        let mut style = empty_style();
+        style.node_styles.insert(
+            person,
+            NodeStyle {
+                color: Color32::RED,
+                node_shape: NodeShape::Rect,
+                label_index: name,
+                ..NodeStyle::default()
+            },
+        );
+        style.edge_styles.insert(
+            knows,
+            EdgeStyle {
+                target_style: ArrowStyle::Diamond,
+                ..EdgeStyle::default()
+            },
+        );
+        style.tooltip_properties.insert(person, vec![name]);
+        style.style_rules.rules.push(StyleRule::new(person, name));
+        style.default_label_in_node = true;
+
+        let theme = StyleTheme::from_style("test", &style, &rdf_data.node_data.indexers, &rdf_data.prefix_manager);
+        let json = theme.to_json().unwrap();
+        assert!(json.contains("http://xmlns.com/foaf/0.1/Person"));
+        assert!(json.contains("\"label_property\": \"http://xmlns.com/foaf/0.1/name\""));
+
+        let theme = StyleTheme::from_json(&json).unwrap();
+        let mut other = empty_style();
+        assert_eq!(2, theme.apply(&mut other, &rdf_data.node_data.indexers, &rdf_data.prefix_manager));
+        let node_style = other.node_styles.get(&person).unwrap();
+        assert_eq!(Color32::RED, node_style.color);
+        assert_eq!(NodeShape::Rect, node_style.node_shape);
+        assert_eq!(name, node_style.label_index);
+        assert!(!node_style.is_default);
+        assert!(other.edge_styles.get(&knows).unwrap().target_style == ArrowStyle::Diamond);
+        assert_eq!(Some(&vec![name]), other.tooltip_properties.get(&person));
+        assert_eq!(style.style_rules.rules, other.style_rules.rules);
+        assert!(other.default_label_in_node);
+    }
+
+    #[test]
+    fn test_bundled_themes() {
+        let rdf_data = rdf_data();
+        for (name, json) in BUNDLED_THEMES.iter() {
+            let theme = StyleTheme::from_json(json).unwrap_or_else(|e| panic!("theme {} can not be read: {}", name, e));
+            assert!(!theme.node_styles.is_empty());
+        }
+        let foaf = StyleTheme::from_json(BUNDLED_THEMES[0].1).unwrap();
+        let mut style = empty_style();
+        assert!(foaf.apply(&mut style, &rdf_data.node_data.indexers, &rdf_data.prefix_manager) >= 2);
+        let person = data_index(
+            &rdf_data.node_data.indexers.type_indexer,
+            "http://xmlns.com/foaf/0.1/Person",
+            &rdf_data.prefix_manager,
+        )
+        .unwrap();
+        let name = data_index(
+            &rdf_data.node_data.indexers.predicate_indexer,
+            "http://xmlns.com/foaf/0.1/name",
+            &rdf_data.prefix_manager,
+        )
+        .unwrap();
+        assert_eq!(name, style.node_styles.get(&person).unwrap().label_index);
+    }
+}
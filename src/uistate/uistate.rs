@@ -1,15 +1,18 @@
 use std::{
     collections::BTreeSet, 
-    sync::{Arc, atomic::{AtomicBool, AtomicUsize}}
+    sync::{Arc, Mutex, atomic::{AtomicBool, AtomicUsize}}
 };
 
 use egui::{Pos2, Rect};
 
 use crate::{
     IriIndex, 
-    domain::LangIndex, 
+    domain::{LangIndex, import_log::ImportReport, keymap::ViewAction, named_graphs::GraphFilter, render_mode::RenderSimplification, type_index::IndexedTypes}, 
+    graph_algorithms::weights::EdgeWeight,
+    integration::{rdf_patch::RdfPatch, remote::ACCEPT_RDF},
     support::SortedVec, 
     uistate::actions::NodeContextAction,
+    uistate::layout::NodeStyleOverride,
     uistate::visual_query::VisualQueryUIState,
 };
 
@@ -31,19 +34,69 @@ pub struct UIState {
     // 1 - magnitude see most nodes, 0 - should be not used, meaning all nodes (also the possible cluster nodes)
     pub semantic_zoom_magnitude: u8,
     pub meta_count_to_size: bool,
+    pub meta_count_to_width: bool,
+    pub meta_cardinalities: bool,
+    // number of example instances shown for a type in the meta graph
+    pub meta_sample_count: usize,
     pub display_language: LangIndex,
     pub language_sort: Vec<LangIndex>,
     pub show_properties: bool,
+    pub show_incoming_references: bool,
+    // last node under the cursor in the visual graph
+    pub hovered_node: Option<IriIndex>,
     pub show_labels: bool,
     pub fade_unselected: bool,
+    // parallel edges are drawn as one bundle
+    pub bundle_edges: bool,
+    // overview of all nodes in the corner of the graph view
+    pub show_minimap: bool,
+    // node types and predicates of the visual graph in the corner of the graph view
+    pub show_legend: bool,
+    // edited style for the selected nodes (window is open if set)
+    pub group_style: Option<NodeStyleOverride>,
+    // edges of the clicked bundle
+    pub edge_bundle: Option<EdgeBundle>,
+    // data properties of the selected node are editable in the node details
+    pub node_edit: Option<NodeEdit>,
+    // new nodes and references are created in the graph view, dragging a node draws a reference
+    pub authoring_mode: bool,
+    // node from which a new reference is dragged
+    pub link_drag: Option<IriIndex>,
+    // dialogs for the new node and the new reference (open if set)
+    pub new_node: Option<NewNode>,
+    pub new_link: Option<NewLink>,
+    // predicate of the new references (full or prefixed iri), kept for the next reference
+    pub link_predicate: String,
+    // weights of the edges for the weighted graph algorithms
+    pub statistics_edge_weight: EdgeWeight,
+    // action in the keyboard settings that waits for the new key
+    pub keymap_recording: Option<ViewAction>,
     pub show_num_hidden_refs: bool,
     pub style_edit: StyleEdit,
     pub icon_name_filter: String,
     pub cpu_usage: f32,
     pub about_window: bool,
+    pub import_log_window: bool,
+    pub session_statistics_window: bool,
+    pub edge_metadata_window: bool,
+    pub wikidata_window: bool,
     pub last_visited_selection: LastVisitedSelection,
+    // Data changing actions (patches, saving project) are disabled
+    pub read_only: bool,
     pub menu_action: Option<NodeContextAction>,
     pub visual_query: VisualQueryUIState,
+    // simplifications active for the current number of visible nodes
+    pub render_simplification: RenderSimplification,
+    // user has chosen full drawing despite of the node count
+    pub full_rendering: bool,
+    // message about the automatic switch and time (egui input time) it was shown
+    pub render_notice: Option<(String, f64)>,
+    // only nodes and edges of the named graph are shown in table and graph views
+    pub graph_filter: Option<GraphFilter>,
+    // language of the literal columns in the instance table, None uses the data language
+    pub table_language: Option<LangIndex>,
+    // predicate that defines the layers of the hierarchical layout, true if the object is the superior
+    pub hierarchy_predicate: Option<(IriIndex, bool)>,
 }
 
 impl Default for UIState {
@@ -59,22 +112,51 @@ impl Default for UIState {
             display_language: 0,
             language_sort: Vec::new(),
             show_properties: true,
+            show_incoming_references: false,
+            hovered_node: None,
             show_labels: true,
             style_edit: StyleEdit::None,
             drag_diff: Pos2::ZERO,
             drag_start: Pos2::ZERO,
             icon_name_filter: String::new(),
             fade_unselected: false,
+            bundle_edges: false,
+            show_minimap: true,
+            show_legend: false,
+            group_style: None,
+            edge_bundle: None,
+            node_edit: None,
+            authoring_mode: false,
+            link_drag: None,
+            new_node: None,
+            new_link: None,
+            link_predicate: String::new(),
+            keymap_recording: None,
+            statistics_edge_weight: EdgeWeight::Unweighted,
             meta_count_to_size: true,
+            meta_count_to_width: true,
+            meta_cardinalities: true,
+            meta_sample_count: 3,
             cpu_usage: 0.0,
             semantic_zoom_magnitude: 1,
             about_window: false,
+            import_log_window: false,
+            session_statistics_window: false,
+            edge_metadata_window: false,
+            wikidata_window: false,
             show_num_hidden_refs: true,
             last_visited_selection: LastVisitedSelection::None,
+            read_only: false,
             menu_action: None,
             selection_start_rect: None,
             translate_drag: None,
             visual_query: VisualQueryUIState::default(),
+            render_simplification: RenderSimplification::default(),
+            full_rendering: false,
+            render_notice: None,
+            graph_filter: None,
+            table_language: None,
+            hierarchy_predicate: None,
         }
     }
 }
@@ -83,22 +165,97 @@ impl UIState {
     pub fn clean(&mut self) {
         self.selected_node = None;
         self.context_menu_node = None;
+        self.hovered_node = None;
         self.node_to_drag = None;
         self.hidden_predicates.data.clear();
         self.visual_query.clean();
+        self.read_only = false;
+        self.full_rendering = false;
+        self.render_notice = None;
+        self.graph_filter = None;
+        self.table_language = None;
+        self.edge_bundle = None;
+        self.node_edit = None;
+        self.authoring_mode = false;
+        self.link_drag = None;
+        self.new_node = None;
+        self.new_link = None;
     }
 }
 
+pub struct EdgeBundle {
+    pub from: IriIndex,
+    pub to: IriIndex,
+    // predicate and true if the edge goes in the direction from -> to
+    pub predicates: Vec<(IriIndex, bool)>,
+}
+
+#[derive(Default)]
+pub struct NodeEdit {
+    // (node, position of the edited data property, new value)
+    pub edited_property: Option<(IriIndex, usize, String)>,
+    // predicate (full or prefixed iri) and value of a new data property
+    pub new_predicate: String,
+    pub new_value: String,
+}
+
+#[derive(Default)]
+pub struct NewNode {
+    // type of the new node (full or prefixed iri), may be empty
+    pub type_iri: String,
+    // iri of the new node, minted from the namespace and the type if empty
+    pub iri: String,
+    pub label: String,
+    // layout position of the new node (center of the view)
+    pub pos: Pos2,
+}
+
+pub struct NewLink {
+    pub from: IriIndex,
+    pub to: IriIndex,
+}
+
 pub enum LastVisitedSelection {
     None,
-    File(usize),
-    Project(usize),
+    // Index in sorted recent items
+    Item(usize),
 }
 
 
 
 pub struct GraphState {
     pub scene_rect: Rect,
+    // camera (position and zoom) saved for keys 1-9
+    pub bookmarks: [Option<Rect>; 9],
+}
+
+impl Default for GraphState {
+    fn default() -> Self {
+        Self {
+            scene_rect: Rect::ZERO,
+            bookmarks: [None; 9],
+        }
+    }
+}
+
+impl GraphState {
+
+    pub fn save_bookmark(&mut self, slot: usize) {
+        if let Some(bookmark) = self.bookmarks.get_mut(slot) {
+            *bookmark = Some(self.scene_rect);
+        }
+    }
+
+    /// Returns false if nothing is saved for the slot
+    pub fn restore_bookmark(&mut self, slot: usize) -> bool {
+        match self.bookmarks.get(slot) {
+            Some(Some(bookmark)) => {
+                self.scene_rect = *bookmark;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 
@@ -119,6 +276,8 @@ pub enum DisplayType {
     Statistics,
     VisualQuery,
     ReferenceResolver,
+    Map,
+    Timeline,
 }
 
 // Define the application structure
@@ -130,9 +289,58 @@ pub struct DataLoading {
     pub stop_loading: Arc<AtomicBool>,
     pub progress: Arc<AtomicUsize>,
     pub total_triples: Arc<AtomicUsize>,
+    // number of nodes in the data after the last merged chunk
+    pub total_nodes: Arc<AtomicUsize>,
     pub read_pos: Arc<AtomicUsize>,
     pub total_size: Arc<AtomicUsize>,
     pub finished: Arc<AtomicBool>,
+    // set when the loading is finished and the types are indexed
+    pub indexing: Arc<AtomicBool>,
+    pub indexed_nodes: Arc<AtomicUsize>,
+    // Filled by the loader for each processed file, taken by the ui thread after join
+    pub import_reports: Mutex<Vec<ImportReport>>,
+}
+
+impl Default for DataLoading {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DataLoading {
+    pub fn new() -> Self {
+        Self {
+            stop_loading: Arc::new(AtomicBool::new(false)),
+            progress: Arc::new(AtomicUsize::new(0)),
+            total_triples: Arc::new(AtomicUsize::new(0)),
+            total_nodes: Arc::new(AtomicUsize::new(0)),
+            read_pos: Arc::new(AtomicUsize::new(0)),
+            total_size: Arc::new(AtomicUsize::new(0)),
+            finished: Arc::new(AtomicBool::new(false)),
+            indexing: Arc::new(AtomicBool::new(false)),
+            indexed_nodes: Arc::new(AtomicUsize::new(0)),
+            import_reports: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn add_report(&self, report: ImportReport) {
+        if let Ok(mut import_reports) = self.import_reports.lock() {
+            import_reports.push(report);
+        }
+    }
+
+    pub fn take_reports(&self) -> Vec<ImportReport> {
+        if let Ok(mut import_reports) = self.import_reports.lock() {
+            std::mem::take(&mut *import_reports)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+pub struct PatchPreview {
+    pub file_name: String,
+    pub patch: RdfPatch,
 }
 
 pub struct ImportFromUrlData {
@@ -143,32 +351,80 @@ pub struct ImportFromUrlData {
 
 #[derive(PartialEq, Copy, Clone, Debug)]
 pub enum ImportFormat {
+    // format is negotiated with the server
+    Auto,
     Turtle,
     RdfXml,
     NTriples,
+    JsonLd,
 }
 
 impl ImportFormat {
+    pub const ALL: [ImportFormat; 5] = [
+        ImportFormat::Auto,
+        ImportFormat::Turtle,
+        ImportFormat::RdfXml,
+        ImportFormat::NTriples,
+        ImportFormat::JsonLd,
+    ];
+
     pub fn mime_type(&self) -> &str {
         match self {
+            ImportFormat::Auto => ACCEPT_RDF,
             ImportFormat::Turtle => "text/turtle",
             ImportFormat::RdfXml => "application/rdf+xml",
             ImportFormat::NTriples => "application/n-triples",
+            ImportFormat::JsonLd => "application/ld+json",
         }
     }
 
-    pub fn file_extension(&self) -> &str {
+    // None if the format is taken from the response
+    pub fn file_extension(&self) -> Option<&'static str> {
+        match self {
+            ImportFormat::Auto => None,
+            ImportFormat::Turtle => Some("ttl"),
+            ImportFormat::RdfXml => Some("rdf"),
+            ImportFormat::NTriples => Some("nt"),
+            ImportFormat::JsonLd => Some("jsonld"),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            ImportFormat::Auto => "Auto (content negotiation)",
+            ImportFormat::Turtle => "Turtle",
+            ImportFormat::RdfXml => "RDF/XML",
+            ImportFormat::NTriples => "N-Triples",
+            ImportFormat::JsonLd => "JSON-LD",
+        }
+    }
+}
+
+/// Nodes written by the RDF export
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum ExportScope {
+    AllData,
+    // filtered instances of the type in the tables tab
+    TypeInstances(IriIndex),
+    VisualGraph,
+}
+
+impl ExportScope {
+    pub fn file_name(&self) -> &'static str {
         match self {
-            ImportFormat::Turtle => "ttl",
-            ImportFormat::RdfXml => "rdf",
-            ImportFormat::NTriples => "nt",
+            ExportScope::AllData => "data",
+            ExportScope::TypeInstances(_) => "instances",
+            ExportScope::VisualGraph => "graph",
         }
     }
 }
 
 pub struct LoadResult {
     pub triples_count: u32,
-    pub file_name: Option<String>,
+    // files and directories added to the recent items
+    pub file_names: Vec<String>,
+    // type statistics computed in the loading thread
+    pub indexed_types: Option<IndexedTypes>,
 }
 
 
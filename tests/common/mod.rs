@@ -0,0 +1,77 @@
+use std::{sync::atomic::Ordering, time::Duration};
+
+use eframe::App;
+use egui::{Pos2, RawInput, Rect, Vec2};
+use rdf_glance::{
+    RdfGlanceApp,
+    uistate::{actions::NodeAction, actions::NodeContextAction},
+};
+
+/**
+ * Runs the app without window.
+ * Each step renders one frame, so the flows go through the same code as in the real app.
+ * Actions are injected like actions of the views.
+ */
+pub struct Harness {
+    pub app: RdfGlanceApp,
+    ctx: egui::Context,
+    frame: eframe::Frame,
+    time: f64,
+}
+
+impl Harness {
+    pub fn new() -> Self {
+        Self {
+            app: RdfGlanceApp::new(None, vec![]),
+            ctx: egui::Context::default(),
+            frame: eframe::Frame::_new_kittest(),
+            time: 0.0,
+        }
+    }
+
+    pub fn load(&mut self, file_name: &str) {
+        self.app.load_ttl(file_name, false);
+        self.app.join_load(false);
+        self.step();
+    }
+
+    pub fn step(&mut self) {
+        self.time += 1.0 / 60.0;
+        let raw_input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(1280.0, 800.0))),
+            time: Some(self.time),
+            ..Default::default()
+        };
+        let app = &mut self.app;
+        let frame = &mut self.frame;
+        let _ = self.ctx.run_ui(raw_input, |ui| {
+            app.ui(ui, frame);
+        });
+    }
+
+    pub fn node_action(&mut self, node_action: NodeAction) {
+        self.app.injected_node_actions.push_back(node_action);
+        self.step();
+    }
+
+    /// Action of the node context menu of the visual graph for the selected node
+    pub fn context_action(&mut self, context_action: NodeContextAction) {
+        self.app.ui_state.menu_action = Some(context_action);
+        self.step();
+    }
+
+    /// Renders frames until the background layout is finished, false on timeout
+    pub fn wait_for_layout(&mut self, max_frames: usize) -> bool {
+        for _ in 0..max_frames {
+            self.step();
+            if self.app.visible_nodes.layout_handle.is_none()
+                || self.app.visible_nodes.background_layout_finished.load(Ordering::Acquire)
+            {
+                self.step();
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        false
+    }
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use common::Harness;
+use rdf_glance::{
+    domain::{LabelContext, rdf_data::ExpandType},
+    ui::property_distribution::StatisticsPanel,
+    uistate::{
+        DisplayType,
+        actions::{NodeAction, NodeContextAction},
+    },
+};
+
+#[test]
+fn test_load_expand_layout_export() {
+    let mut harness = Harness::new();
+    harness.load("sample-rdf-data/programming_languages.ttl");
+    assert!(!harness.app.is_empty());
+    assert!(!harness.app.type_index.types.is_empty());
+
+    let node_index = harness.app.rdf_data.read().unwrap().node_data.get_node_index("dbr:Java_(programming_language)");
+    let node_index = node_index.expect("sample node");
+    harness.node_action(NodeAction::ShowVisual(node_index));
+    assert_eq!(DisplayType::Graph, harness.app.display_type);
+    assert_eq!(1, harness.app.visible_nodes.layout_data.read().unwrap().nodes.len());
+
+    harness.context_action(NodeContextAction::Expand(ExpandType::Both));
+    let visible_nodes = harness.app.visible_nodes.layout_data.read().unwrap().nodes.len();
+    assert!(visible_nodes > 1);
+    assert!(!harness.app.visible_nodes.layout_data.read().unwrap().edges.is_empty());
+
+    assert!(harness.wait_for_layout(1000), "layout not finished");
+    let positions = harness.app.visible_nodes.layout_data.read().unwrap().positions.clone();
+    assert_eq!(visible_nodes, positions.len());
+    assert!(positions.iter().all(|position| position.pos.x.is_finite() && position.pos.y.is_finite()));
+
+    let mut svg: Vec<u8> = Vec::new();
+    {
+        let rdf_data = harness.app.rdf_data.read().unwrap();
+        let label_context =
+            LabelContext::new(harness.app.ui_state.display_language, harness.app.config.iri_display, &rdf_data.prefix_manager);
+        harness.app.export_svg(&mut svg, &rdf_data.node_data, &label_context).unwrap();
+    }
+    let svg = String::from_utf8(svg).unwrap();
+    assert!(svg.contains("<svg"));
+}
+
+#[test]
+fn test_table_and_browse_actions() {
+    let mut harness = Harness::new();
+    harness.load("sample-rdf-data/programming_languages.ttl");
+    let type_index = harness.app.type_index.types_order[0];
+    harness.node_action(NodeAction::ShowType(type_index));
+    assert_eq!(DisplayType::Table, harness.app.display_type);
+    assert_eq!(Some(type_index), harness.app.type_index.selected_type);
+
+    let instance = harness.app.type_index.types.get(&type_index).unwrap().instances[0];
+    harness.node_action(NodeAction::BrowseNode(instance));
+    assert_eq!(DisplayType::Browse, harness.app.display_type);
+    // a few more frames of all views must not panic
+    for display_type in [DisplayType::MetaGraph, DisplayType::Statistics, DisplayType::Map, DisplayType::Timeline, DisplayType::Table] {
+        harness.app.display_type = display_type;
+        harness.step();
+    }
+    assert!(!harness.app.meta_nodes.layout_data.read().unwrap().nodes.is_empty());
+    // the sample data have release dates
+    assert!(!harness.app.timeline_view.items.is_empty());
+
+    harness.app.display_type = DisplayType::Statistics;
+    harness.app.statistics_panel = StatisticsPanel::PropertyDistribution;
+    harness.step();
+    assert!(harness.app.property_distribution.chart.is_some());
+}